@@ -0,0 +1,197 @@
+/*
+ *  Copyright 2021 QuantumBadger
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Measures the time taken to draw 50,000 copies of a small image using
+//! `Graphics2D::draw_image_instances()`, compared to drawing each copy with
+//! its own call to `Graphics2D::draw_image_rotated_tinted()`.
+//!
+//! A headless OpenGL context is used. On OpenGL 3.3 and later,
+//! `draw_image_instances()` uses instanced rendering, so only the position,
+//! rotation, and tint of each copy are uploaded. Each call to
+//! `draw_image_rotated_tinted()` generates the vertices of its copy on the
+//! CPU, which is also how `draw_image_instances()` works on older versions.
+//! For comparison, the software renderer is also measured, where vertices are
+//! always generated on the CPU.
+//!
+//! Run using `cargo bench --bench image_instances`.
+
+#![deny(warnings)]
+
+use std::convert::TryInto;
+use std::ffi::CString;
+use std::time::{Duration, Instant};
+
+use glutin::config::ConfigTemplateBuilder;
+use glutin::context::{
+    ContextApi,
+    ContextAttributesBuilder,
+    NotCurrentGlContext,
+    Version
+};
+use glutin::display::{GetGlDisplay, GlDisplay};
+use glutin::surface::{PbufferSurface, SurfaceAttributesBuilder};
+use glutin_winit::DisplayBuilder;
+use speedy2d::color::Color;
+use speedy2d::dimen::{UVec2, Vec2};
+use speedy2d::image::{ImageDataType, ImageHandle, ImageInstance, ImageSmoothingMode};
+use speedy2d::shape::URect;
+use speedy2d::{GLRenderer, Graphics2D};
+use winit::event_loop::EventLoop;
+
+const WIDTH: u32 = 1024;
+const HEIGHT: u32 = 768;
+const INSTANCE_COUNT: usize = 50_000;
+const WARM_UP_FRAMES: u32 = 3;
+const MEASURED_FRAMES: u32 = 20;
+
+fn mean_frame_time<F>(renderer: &mut GLRenderer, mut draw: F) -> Duration
+where
+    F: FnMut(&mut Graphics2D)
+{
+    let mut frame = || {
+        renderer.draw_frame(|graphics| {
+            graphics.clear_screen(Color::BLACK);
+            draw(graphics);
+        })
+    };
+
+    for _ in 0..WARM_UP_FRAMES {
+        frame();
+    }
+
+    let start = Instant::now();
+
+    for _ in 0..MEASURED_FRAMES {
+        frame();
+    }
+
+    // Reading a pixel waits for the GPU to finish drawing
+    renderer.draw_frame(|graphics| {
+        graphics.capture_region(
+            ImageDataType::RGBA,
+            URect::new(UVec2::ZERO, UVec2::new(1, 1))
+        )
+    });
+
+    start.elapsed() / MEASURED_FRAMES
+}
+
+fn create_image(renderer: &mut GLRenderer) -> ImageHandle
+{
+    renderer
+        .create_image_from_raw_pixels(
+            ImageDataType::RGBA,
+            ImageSmoothingMode::NearestNeighbor,
+            UVec2::new(4, 4),
+            &[255; 4 * 4 * 4]
+        )
+        .unwrap()
+}
+
+fn run<F: FnOnce(&mut GLRenderer)>(event_loop: &EventLoop<()>, action: F)
+{
+    let (_window, config) = DisplayBuilder::new()
+        .build(event_loop, ConfigTemplateBuilder::new(), |mut configs| {
+            configs.next().unwrap()
+        })
+        .unwrap();
+
+    let gl_display = config.display();
+
+    let context_attr = ContextAttributesBuilder::new()
+        .with_context_api(ContextApi::OpenGl(Some(Version::new(2, 0))))
+        .build(None);
+
+    let context = unsafe { gl_display.create_context(&config, &context_attr) }.unwrap();
+
+    let surface = unsafe {
+        gl_display.create_pbuffer_surface(
+            &config,
+            &SurfaceAttributesBuilder::<PbufferSurface>::new()
+                .build(WIDTH.try_into().unwrap(), HEIGHT.try_into().unwrap())
+        )
+    }
+    .unwrap();
+
+    let _context = context.make_current(&surface).unwrap();
+
+    let mut renderer = unsafe {
+        GLRenderer::new_for_gl_context((WIDTH, HEIGHT), |name| {
+            gl_display.get_proc_address(CString::new(name).unwrap().as_c_str())
+                as *const _
+        })
+        .unwrap()
+    };
+
+    action(&mut renderer)
+}
+
+fn measure(name: &str, renderer: &mut GLRenderer)
+{
+    let image = create_image(renderer);
+
+    // The center, rotation, and tint of each copy
+    let sprites: Vec<(Vec2, f32, Color)> = (0..INSTANCE_COUNT)
+        .map(|i| {
+            let x = (i % 250) as f32 * 4.0;
+            let y = (i / 250) as f32 * 3.5;
+
+            (
+                Vec2::new(x + 10.0, y + 10.0),
+                i as f32 * 0.01,
+                Color::from_rgba(1.0, 0.5, (i % 7) as f32 / 6.0, 0.8)
+            )
+        })
+        .collect();
+
+    let instances: Vec<ImageInstance> = sprites
+        .iter()
+        .map(|(center, rotation, tint)| {
+            ImageInstance::new(*center)
+                .with_rotation(*rotation)
+                .with_tint(*tint)
+        })
+        .collect();
+
+    let batched = mean_frame_time(renderer, |graphics| {
+        graphics.draw_image_instances(&image, &instances);
+    });
+
+    let individual = mean_frame_time(renderer, |graphics| {
+        for (center, rotation, tint) in &sprites {
+            graphics.draw_image_rotated_tinted(*center, *tint, &image, *rotation, 1.0);
+        }
+    });
+
+    println!(
+        "{}, {} instances, mean time per frame:",
+        name, INSTANCE_COUNT
+    );
+    println!("  draw_image_instances():      {:?}", batched);
+    println!("  draw_image_rotated_tinted(): {:?}", individual);
+}
+
+fn main()
+{
+    let event_loop = EventLoop::new().unwrap();
+
+    run(&event_loop, |renderer| measure("OpenGL", renderer));
+
+    measure(
+        "Software renderer",
+        &mut GLRenderer::new_software((WIDTH, HEIGHT)).unwrap()
+    );
+}
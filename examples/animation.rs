@@ -53,7 +53,7 @@ impl WindowHandler for MyWindowHandler
         let position =
             center + Vec2::new(elapsed_secs.cos() * offset, elapsed_secs.sin() * offset);
 
-        graphics.draw_circle(position, 75.0, Color::from_rgb(0.8, 0.9, 1.0));
+        graphics.draw_circle((position, 75.0), Color::from_rgb(0.8, 0.9, 1.0));
 
         // Request that we draw another frame once this one has finished
         helper.request_redraw();
@@ -45,7 +45,7 @@ impl WindowHandler for MyWindowHandler
     {
         graphics.clear_screen(Color::WHITE);
 
-        graphics.draw_circle((150.0, 120.0), 75.0, Color::from_rgb(0.8, 0.9, 1.0));
+        graphics.draw_circle(((150.0, 120.0), 75.0), Color::from_rgb(0.8, 0.9, 1.0));
 
         graphics.draw_text((290.0, 90.0), Color::BLACK, &self.text);
 
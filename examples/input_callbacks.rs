@@ -84,7 +84,7 @@ impl WindowHandler for MyWindowHandler
         };
 
         // Draw a circle at the mouse pointer location
-        graphics.draw_circle(self.mouse_pos, 20.0, color);
+        graphics.draw_circle((self.mouse_pos, 20.0), color);
     }
 
     fn on_mouse_move(&mut self, helper: &mut WindowHelper, position: Vec2)
@@ -100,6 +100,15 @@ impl WindowHandler for MyWindowHandler
         helper.request_redraw();
     }
 
+    fn on_mouse_motion_relative(&mut self, _helper: &mut WindowHelper, delta: Vec2)
+    {
+        log::info!(
+            "Got on_mouse_motion_relative callback: ({:.1}, {:.1})",
+            delta.x,
+            delta.y
+        );
+    }
+
     fn on_mouse_button_down(&mut self, helper: &mut WindowHelper, button: MouseButton)
     {
         log::info!("Got on_mouse_button_down callback: {:?}", button);
@@ -71,10 +71,10 @@ fn main()
             target.set_control_flow(ControlFlow::Poll);
 
             match event {
-                Event::WindowEvent { event, .. } => match event {
-                    WindowEvent::CloseRequested => target.exit(),
-                    _ => {}
-                },
+                Event::WindowEvent {
+                    event: WindowEvent::CloseRequested,
+                    ..
+                } => target.exit(),
 
                 Event::AboutToWait => {
                     renderer.draw_frame(|graphics| {
@@ -91,7 +91,7 @@ fn main()
 fn render_frame(graphics: &mut Graphics2D, text: &FormattedTextBlock)
 {
     graphics.clear_screen(Color::WHITE);
-    graphics.draw_circle((150.0, 120.0), 75.0, Color::from_rgb(0.8, 0.9, 1.0));
+    graphics.draw_circle(((150.0, 120.0), 75.0), Color::from_rgb(0.8, 0.9, 1.0));
     graphics.draw_text((290.0, 90.0), Color::BLACK, text);
 }
 
@@ -91,7 +91,7 @@ impl WindowHandler for MyWindowHandler
         };
 
         // Draw a circle at the mouse pointer location
-        graphics.draw_circle(self.offset, 20.0, color);
+        graphics.draw_circle((self.offset, 20.0), color);
 
         graphics.draw_text((20.0, 20.0), Color::BLACK, &self.text);
     }
@@ -105,7 +105,7 @@ impl WindowHandler for MyWindowHandler
         );
 
         if self.grabbed {
-            self.offset = self.offset + position;
+            self.offset += position;
             self.offset.x = self.offset.x.rem_euclid(self.window_size.x as f32);
             self.offset.y = self.offset.y.rem_euclid(self.window_size.y as f32);
         } else {
@@ -0,0 +1,117 @@
+/*
+ *  Copyright 2021 QuantumBadger
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+#![deny(warnings)]
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use speedy2d::color::Color;
+use speedy2d::dimen::{UVec2, Vec2};
+use speedy2d::window::{
+    MouseButton,
+    WindowCreationOptions,
+    WindowHandler,
+    WindowHelper,
+    WindowPosition,
+    WindowSize,
+    WindowStartupInfo
+};
+use speedy2d::{Graphics2D, Window};
+
+const PALETTE: [Color; 4] = [Color::RED, Color::GREEN, Color::BLUE, Color::BLACK];
+
+fn main()
+{
+    simple_logger::SimpleLogger::new().init().unwrap();
+
+    let window = Window::new_centered("Speedy2D: Canvas", (640, 480)).unwrap();
+
+    window.run_loop(CanvasWindowHandler {
+        selected_color: Rc::new(Cell::new(PALETTE[0]))
+    })
+}
+
+struct CanvasWindowHandler
+{
+    selected_color: Rc<Cell<Color>>
+}
+
+impl WindowHandler for CanvasWindowHandler
+{
+    fn on_start(&mut self, helper: &mut WindowHelper, _info: WindowStartupInfo)
+    {
+        // The palette window is opened once this callback returns, and has its
+        // own handler. Closing it leaves the canvas window open.
+        helper.create_window(
+            "Speedy2D: Palette",
+            WindowCreationOptions::new_windowed(
+                WindowSize::PhysicalPixels(UVec2::new(100, 400)),
+                Some(WindowPosition::PrimaryMonitorPixelsFromTopLeft(
+                    (50, 50).into()
+                ))
+            )
+            .with_resizable(false),
+            PaletteWindowHandler {
+                selected_color: self.selected_color.clone(),
+                mouse_position: Vec2::ZERO
+            }
+        );
+    }
+
+    fn on_draw(&mut self, helper: &mut WindowHelper, graphics: &mut Graphics2D)
+    {
+        graphics.clear_screen(Color::WHITE);
+        graphics.draw_circle(((320.0, 240.0), 150.0), self.selected_color.get());
+
+        // The palette may change the color at any time
+        helper.request_redraw();
+    }
+}
+
+struct PaletteWindowHandler
+{
+    selected_color: Rc<Cell<Color>>,
+    mouse_position: Vec2
+}
+
+impl WindowHandler for PaletteWindowHandler
+{
+    fn on_draw(&mut self, _helper: &mut WindowHelper, graphics: &mut Graphics2D)
+    {
+        graphics.clear_screen(Color::LIGHT_GRAY);
+
+        for (index, color) in PALETTE.iter().enumerate() {
+            graphics.draw_circle(((50.0, 50.0 + index as f32 * 100.0), 40.0), *color);
+        }
+    }
+
+    fn on_mouse_move(&mut self, _helper: &mut WindowHelper, position: Vec2)
+    {
+        self.mouse_position = position;
+    }
+
+    fn on_mouse_button_down(&mut self, _helper: &mut WindowHelper, button: MouseButton)
+    {
+        if button == MouseButton::Left {
+            let index = (self.mouse_position.y / 100.0) as usize;
+
+            if let Some(color) = PALETTE.get(index) {
+                self.selected_color.set(*color);
+            }
+        }
+    }
+}
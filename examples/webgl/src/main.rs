@@ -148,8 +148,7 @@ impl WindowHandler<UserEvent> for MyHandler
             .into_f32();
 
         graphics.draw_circle(
-            position * self.scale,
-            75.0 * self.scale,
+            (position * self.scale, 75.0 * self.scale),
             Color::from_rgb(0.6, 0.8, 1.0)
         );
 
@@ -14,9 +14,13 @@
  *  limitations under the License.
  */
 
+use crate::dimen::Vec2;
+use crate::error::{BacktraceError, ErrorMessage};
+
 /// A struct representing a color with red, green, blue, and alpha components.
 /// Each component is stored as a float.
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color
 {
     r: f32,
@@ -149,6 +153,106 @@ impl Color
         Color::from_int_rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8)
     }
 
+    /// Parses a color from a hexadecimal string, as commonly used in CSS and
+    /// design tools. The leading `#` is optional, and the following formats
+    /// are supported:
+    ///
+    /// * `#RGB`, for example `#F51`, equivalent to `#FF5511`
+    /// * `#RRGGBB`, for example `#FF5511`
+    /// * `#RRGGBBAA`, for example `#FF5511AA`
+    ///
+    /// If no alpha component is specified, the color will be fully opaque.
+    ///
+    /// An error is returned if the string has an unsupported length, or
+    /// contains characters other than hexadecimal digits.
+    pub fn from_hex_str(hex: &str) -> Result<Self, BacktraceError<ErrorMessage>>
+    {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+
+        if let Some(invalid) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+            return Err(ErrorMessage::msg(format!(
+                "Invalid character '{}' in hex color string '{}'",
+                invalid, hex
+            )));
+        }
+
+        let value = u32::from_str_radix(digits, 16).unwrap_or(0);
+
+        match digits.len() {
+            3 => {
+                let expand = |nibble: u32| ((nibble & 0xF) * 0x11) as u8;
+                Ok(Color::from_int_rgb(
+                    expand(value >> 8),
+                    expand(value >> 4),
+                    expand(value)
+                ))
+            }
+            6 => Ok(Color::from_hex_rgb(value)),
+            8 => Ok(Color::from_int_rgba(
+                (value >> 24) as u8,
+                (value >> 16) as u8,
+                (value >> 8) as u8,
+                value as u8
+            )),
+            length => Err(ErrorMessage::msg(format!(
+                "Invalid hex color string '{}': expected 3, 6, or 8 digits, found {}",
+                hex, length
+            )))
+        }
+    }
+
+    /// Parses a color in any of the following CSS formats. Names and function
+    /// names are case-insensitive, and surrounding whitespace is ignored.
+    ///
+    /// * Hexadecimal colors: `#RGB`, `#RGBA`, `#RRGGBB`, or `#RRGGBBAA`
+    /// * The `rgb()` and `rgba()` functions, for example `rgb(255, 136, 0)`,
+    ///   `rgba(255, 136, 0, 0.5)`, or `rgb(100% 50% 0% / 50%)`
+    /// * The `hsl()` and `hsla()` functions, for example `hsl(30, 100%, 50%)`
+    ///   or `hsla(30deg 100% 50% / 0.5)`
+    /// * Named colors, for example `rebeccapurple`, and `transparent`
+    ///
+    /// ```rust
+    /// # use speedy2d::color::Color;
+    /// let orange = Color::from_css_str("#ff8800").unwrap();
+    /// assert_eq!(Color::from_int_rgb(255, 136, 0), orange);
+    ///
+    /// let red = Color::from_css_str("rgb(255, 0, 0)").unwrap();
+    /// assert_eq!(Color::RED, red);
+    ///
+    /// let purple = Color::from_css_str("rebeccapurple").unwrap();
+    /// assert_eq!(Color::from_hex_rgb(0x663399), purple);
+    ///
+    /// assert!(Color::from_css_str("not a color").is_err());
+    /// ```
+    pub fn from_css_str(css: &str) -> Result<Self, BacktraceError<ErrorMessage>>
+    {
+        let lowercase = css.trim().to_ascii_lowercase();
+
+        if let Some(digits) = lowercase.strip_prefix('#') {
+            if digits.len() == 4 && digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                let expanded: String = digits.chars().flat_map(|c| [c, c]).collect();
+                return Color::from_hex_str(&expanded);
+            }
+
+            return Color::from_hex_str(digits);
+        }
+
+        let parsed = match lowercase.strip_suffix(')') {
+            Some(function) => function
+                .split_once('(')
+                .and_then(|(name, arguments)| parse_css_function(name.trim(), arguments)),
+
+            None if lowercase == "transparent" => Some(Color::TRANSPARENT),
+
+            None => CSS_NAMED_COLORS
+                .binary_search_by(|(name, _)| name.cmp(&lowercase.as_str()))
+                .ok()
+                .map(|index| Color::from_hex_rgb(CSS_NAMED_COLORS[index].1))
+        };
+
+        parsed.ok_or_else(|| ErrorMessage::msg(format!("Invalid CSS color '{}'", css)))
+    }
+
     /// Creates a shade of gray from the specified float value, between `0.0`
     /// and `1.0`. All three RGB components will be set to this value.
     #[inline]
@@ -157,6 +261,101 @@ impl Color
         Self::from_rgb(brightness, brightness, brightness)
     }
 
+    /// Creates a color from the specified hue, saturation, value, and alpha
+    /// components.
+    ///
+    /// The hue is specified in degrees, and values outside the range `0.0` to
+    /// `360.0` wrap around. The other components should be in the range `0.0`
+    /// to `1.0`.
+    ///
+    /// ```rust
+    /// # use speedy2d::color::Color;
+    /// assert_eq!(Color::RED, Color::from_hsv(0.0, 1.0, 1.0, 1.0));
+    /// assert_eq!(Color::BLUE, Color::from_hsv(240.0, 1.0, 1.0, 1.0));
+    /// ```
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32, alpha: f32) -> Self
+    {
+        let chroma = value * saturation;
+        let (r, g, b) = hue_to_rgb(hue, chroma);
+        let offset = value - chroma;
+
+        Color::from_rgba(r + offset, g + offset, b + offset, alpha)
+    }
+
+    /// Creates a color from the specified hue, saturation, lightness, and alpha
+    /// components.
+    ///
+    /// The hue is specified in degrees, and values outside the range `0.0` to
+    /// `360.0` wrap around. The other components should be in the range `0.0`
+    /// to `1.0`.
+    ///
+    /// ```rust
+    /// # use speedy2d::color::Color;
+    /// assert_eq!(Color::RED, Color::from_hsl(0.0, 1.0, 0.5, 1.0));
+    /// assert_eq!(Color::WHITE, Color::from_hsl(120.0, 1.0, 1.0, 1.0));
+    /// ```
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32, alpha: f32) -> Self
+    {
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let (r, g, b) = hue_to_rgb(hue, chroma);
+        let offset = lightness - chroma / 2.0;
+
+        Color::from_rgba(r + offset, g + offset, b + offset, alpha)
+    }
+
+    /// Returns the hue, saturation, and value of this color, in that order.
+    ///
+    /// The hue is in degrees, in the range `0.0` to `360.0`, and is zero for
+    /// shades of gray. The saturation and value are in the range `0.0` to
+    /// `1.0`. The alpha component can be obtained using [Color::a].
+    pub fn to_hsv(&self) -> (f32, f32, f32)
+    {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let chroma = max - min;
+
+        let saturation = if max == 0.0 { 0.0 } else { chroma / max };
+
+        (self.hue(max, chroma), saturation, max)
+    }
+
+    /// Returns the hue, saturation, and lightness of this color, in that
+    /// order.
+    ///
+    /// The hue is in degrees, in the range `0.0` to `360.0`, and is zero for
+    /// shades of gray. The saturation and lightness are in the range `0.0` to
+    /// `1.0`. The alpha component can be obtained using [Color::a].
+    pub fn to_hsl(&self) -> (f32, f32, f32)
+    {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let chroma = max - min;
+        let lightness = (max + min) / 2.0;
+
+        let saturation = if lightness == 0.0 || lightness == 1.0 {
+            0.0
+        } else {
+            chroma / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+
+        (self.hue(max, chroma), saturation, lightness)
+    }
+
+    fn hue(&self, max: f32, chroma: f32) -> f32
+    {
+        let sector = if chroma == 0.0 {
+            0.0
+        } else if max == self.r {
+            ((self.g - self.b) / chroma).rem_euclid(6.0)
+        } else if max == self.g {
+            (self.b - self.r) / chroma + 2.0
+        } else {
+            (self.r - self.g) / chroma + 4.0
+        };
+
+        sector * 60.0
+    }
+
     /// Returns the red component of the color, as a value in the range `0.0` to
     /// `1.0`.
     #[inline]
@@ -190,6 +389,43 @@ impl Color
         self.a
     }
 
+    /// Linearly interpolates between two colors, where a `t` of `0.0` returns
+    /// `a` and a `t` of `1.0` returns `b`. Values of `t` outside this range are
+    /// clamped.
+    ///
+    /// The red, green, and blue components are weighted by alpha during the
+    /// interpolation, so fading a color to [Color::TRANSPARENT] only changes
+    /// its opacity, and doesn't darken it.
+    ///
+    /// ```rust
+    /// # use speedy2d::color::Color;
+    /// assert_eq!(
+    ///     Color::from_rgba(1.0, 0.0, 0.0, 0.5),
+    ///     Color::lerp(Color::RED, Color::TRANSPARENT, 0.5)
+    /// );
+    /// ```
+    #[must_use]
+    pub fn lerp(a: Color, b: Color, t: f32) -> Color
+    {
+        let t = t.clamp(0.0, 1.0);
+        let alpha = a.a + (b.a - a.a) * t;
+
+        if alpha <= 0.0 {
+            return Color::from_rgba(
+                a.r + (b.r - a.r) * t,
+                a.g + (b.g - a.g) * t,
+                a.b + (b.b - a.b) * t,
+                0.0
+            );
+        }
+
+        let mix = |a_component: f32, b_component: f32| {
+            (a_component * a.a * (1.0 - t) + b_component * b.a * t) / alpha
+        };
+
+        Color::from_rgba(mix(a.r, b.r), mix(a.g, b.g), mix(a.b, b.b), alpha)
+    }
+
     /// Returns the brightness of the color as perceived by a human, as a value
     /// in the range `0.0` to `1.0`.
     ///
@@ -209,6 +445,725 @@ impl Color
     }
 }
 
+/// The named colors defined by CSS, sorted by name, with their RGB values.
+const CSS_NAMED_COLORS: [(&str, u32); 148] = [
+    ("aliceblue", 0xF0F8FF),
+    ("antiquewhite", 0xFAEBD7),
+    ("aqua", 0x00FFFF),
+    ("aquamarine", 0x7FFFD4),
+    ("azure", 0xF0FFFF),
+    ("beige", 0xF5F5DC),
+    ("bisque", 0xFFE4C4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xFFEBCD),
+    ("blue", 0x0000FF),
+    ("blueviolet", 0x8A2BE2),
+    ("brown", 0xA52A2A),
+    ("burlywood", 0xDEB887),
+    ("cadetblue", 0x5F9EA0),
+    ("chartreuse", 0x7FFF00),
+    ("chocolate", 0xD2691E),
+    ("coral", 0xFF7F50),
+    ("cornflowerblue", 0x6495ED),
+    ("cornsilk", 0xFFF8DC),
+    ("crimson", 0xDC143C),
+    ("cyan", 0x00FFFF),
+    ("darkblue", 0x00008B),
+    ("darkcyan", 0x008B8B),
+    ("darkgoldenrod", 0xB8860B),
+    ("darkgray", 0xA9A9A9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xA9A9A9),
+    ("darkkhaki", 0xBDB76B),
+    ("darkmagenta", 0x8B008B),
+    ("darkolivegreen", 0x556B2F),
+    ("darkorange", 0xFF8C00),
+    ("darkorchid", 0x9932CC),
+    ("darkred", 0x8B0000),
+    ("darksalmon", 0xE9967A),
+    ("darkseagreen", 0x8FBC8F),
+    ("darkslateblue", 0x483D8B),
+    ("darkslategray", 0x2F4F4F),
+    ("darkslategrey", 0x2F4F4F),
+    ("darkturquoise", 0x00CED1),
+    ("darkviolet", 0x9400D3),
+    ("deeppink", 0xFF1493),
+    ("deepskyblue", 0x00BFFF),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1E90FF),
+    ("firebrick", 0xB22222),
+    ("floralwhite", 0xFFFAF0),
+    ("forestgreen", 0x228B22),
+    ("fuchsia", 0xFF00FF),
+    ("gainsboro", 0xDCDCDC),
+    ("ghostwhite", 0xF8F8FF),
+    ("gold", 0xFFD700),
+    ("goldenrod", 0xDAA520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xADFF2F),
+    ("grey", 0x808080),
+    ("honeydew", 0xF0FFF0),
+    ("hotpink", 0xFF69B4),
+    ("indianred", 0xCD5C5C),
+    ("indigo", 0x4B0082),
+    ("ivory", 0xFFFFF0),
+    ("khaki", 0xF0E68C),
+    ("lavender", 0xE6E6FA),
+    ("lavenderblush", 0xFFF0F5),
+    ("lawngreen", 0x7CFC00),
+    ("lemonchiffon", 0xFFFACD),
+    ("lightblue", 0xADD8E6),
+    ("lightcoral", 0xF08080),
+    ("lightcyan", 0xE0FFFF),
+    ("lightgoldenrodyellow", 0xFAFAD2),
+    ("lightgray", 0xD3D3D3),
+    ("lightgreen", 0x90EE90),
+    ("lightgrey", 0xD3D3D3),
+    ("lightpink", 0xFFB6C1),
+    ("lightsalmon", 0xFFA07A),
+    ("lightseagreen", 0x20B2AA),
+    ("lightskyblue", 0x87CEFA),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xB0C4DE),
+    ("lightyellow", 0xFFFFE0),
+    ("lime", 0x00FF00),
+    ("limegreen", 0x32CD32),
+    ("linen", 0xFAF0E6),
+    ("magenta", 0xFF00FF),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66CDAA),
+    ("mediumblue", 0x0000CD),
+    ("mediumorchid", 0xBA55D3),
+    ("mediumpurple", 0x9370DB),
+    ("mediumseagreen", 0x3CB371),
+    ("mediumslateblue", 0x7B68EE),
+    ("mediumspringgreen", 0x00FA9A),
+    ("mediumturquoise", 0x48D1CC),
+    ("mediumvioletred", 0xC71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xF5FFFA),
+    ("mistyrose", 0xFFE4E1),
+    ("moccasin", 0xFFE4B5),
+    ("navajowhite", 0xFFDEAD),
+    ("navy", 0x000080),
+    ("oldlace", 0xFDF5E6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6B8E23),
+    ("orange", 0xFFA500),
+    ("orangered", 0xFF4500),
+    ("orchid", 0xDA70D6),
+    ("palegoldenrod", 0xEEE8AA),
+    ("palegreen", 0x98FB98),
+    ("paleturquoise", 0xAFEEEE),
+    ("palevioletred", 0xDB7093),
+    ("papayawhip", 0xFFEFD5),
+    ("peachpuff", 0xFFDAB9),
+    ("peru", 0xCD853F),
+    ("pink", 0xFFC0CB),
+    ("plum", 0xDDA0DD),
+    ("powderblue", 0xB0E0E6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xFF0000),
+    ("rosybrown", 0xBC8F8F),
+    ("royalblue", 0x4169E1),
+    ("saddlebrown", 0x8B4513),
+    ("salmon", 0xFA8072),
+    ("sandybrown", 0xF4A460),
+    ("seagreen", 0x2E8B57),
+    ("seashell", 0xFFF5EE),
+    ("sienna", 0xA0522D),
+    ("silver", 0xC0C0C0),
+    ("skyblue", 0x87CEEB),
+    ("slateblue", 0x6A5ACD),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xFFFAFA),
+    ("springgreen", 0x00FF7F),
+    ("steelblue", 0x4682B4),
+    ("tan", 0xD2B48C),
+    ("teal", 0x008080),
+    ("thistle", 0xD8BFD8),
+    ("tomato", 0xFF6347),
+    ("turquoise", 0x40E0D0),
+    ("violet", 0xEE82EE),
+    ("wheat", 0xF5DEB3),
+    ("white", 0xFFFFFF),
+    ("whitesmoke", 0xF5F5F5),
+    ("yellow", 0xFFFF00),
+    ("yellowgreen", 0x9ACD32)
+];
+
+/// Parses a single CSS color component, which is either a number in the range
+/// `0.0` to `number_scale`, or a percentage.
+fn parse_css_component(component: &str, number_scale: f32) -> Option<f32>
+{
+    let value = match component.strip_suffix('%') {
+        Some(percentage) => percentage.parse::<f32>().ok()? / 100.0,
+        None => component.parse::<f32>().ok()? / number_scale
+    };
+
+    if value.is_finite() {
+        Some(value.clamp(0.0, 1.0))
+    } else {
+        None
+    }
+}
+
+/// Parses the arguments of a CSS `rgb()`, `rgba()`, `hsl()`, or `hsla()`
+/// function.
+fn parse_css_function(function: &str, arguments: &str) -> Option<Color>
+{
+    let arguments: Vec<&str> = arguments
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|argument| !argument.is_empty())
+        .collect();
+
+    let alpha = match arguments.len() {
+        3 => 1.0,
+        4 => parse_css_component(arguments[3], 1.0)?,
+        _ => return None
+    };
+
+    match function {
+        "rgb" | "rgba" => Some(Color::from_rgba(
+            parse_css_component(arguments[0], 255.0)?,
+            parse_css_component(arguments[1], 255.0)?,
+            parse_css_component(arguments[2], 255.0)?,
+            alpha
+        )),
+
+        "hsl" | "hsla" => {
+            let hue = arguments[0].strip_suffix("deg").unwrap_or(arguments[0]);
+            let hue = hue.parse::<f32>().ok().filter(|hue| hue.is_finite())?;
+
+            // Percent signs are optional for the saturation and lightness
+            let percentage = |argument: &str| {
+                parse_css_component(argument.strip_suffix('%').unwrap_or(argument), 100.0)
+            };
+
+            Some(Color::from_hsl(
+                hue,
+                percentage(arguments[1])?,
+                percentage(arguments[2])?,
+                alpha
+            ))
+        }
+
+        _ => None
+    }
+}
+
+/// Returns the red, green, and blue components of a fully saturated color
+/// with the specified hue (in degrees) and chroma, before adding the offset
+/// which determines its brightness.
+fn hue_to_rgb(hue: f32, chroma: f32) -> (f32, f32, f32)
+{
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+
+    match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum GradientKind
+{
+    Linear
+    {
+        start: Vec2, end: Vec2
+    },
+    Radial
+    {
+        center: Vec2, radius: f32
+    }
+}
+
+/// The maximum difference between the true gradient offset and the linearly
+/// interpolated offset, when subdividing triangles for non-linear gradients.
+const GRADIENT_OFFSET_TOLERANCE: f32 = 0.002;
+
+/// The maximum number of times a triangle will be subdivided when drawing a
+/// non-linear gradient.
+const GRADIENT_MAX_SUBDIVISION_DEPTH: u32 = 7;
+
+/// The number of pieces a gradient band is split into when drawing, if the
+/// opacity changes between its stops, so that the colors interpolated between
+/// vertices stay close to [Color::lerp].
+const GRADIENT_ALPHA_BAND_PIECES: u32 = 8;
+
+/// A smooth transition between two or more colors, which may be used to fill
+/// shapes such as rectangles and polygons.
+///
+/// Each color is specified as a "stop" at an offset along the gradient, where
+/// `0.0` is the start of the gradient and `1.0` is the end. Points before the
+/// first stop or after the last stop are filled with the color of the nearest
+/// stop. Colors between two stops are interpolated using [Color::lerp], so
+/// fading to a stop with an alpha of `0.0` only changes the opacity.
+///
+/// The positions used to define a gradient are in the same coordinate space
+/// as the shape being filled.
+///
+/// ```rust
+/// # use speedy2d::color::{Color, Gradient};
+/// let gradient = Gradient::linear((0.0, 0.0), (100.0, 0.0))
+///     .with_stop(0.0, Color::RED)
+///     .with_stop(0.5, Color::YELLOW)
+///     .with_stop(1.0, Color::GREEN);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient
+{
+    kind: GradientKind,
+    stops: Vec<(f32, Color)>
+}
+
+impl Gradient
+{
+    /// Creates a linear gradient, which changes color along the straight line
+    /// from `start` (offset `0.0`) to `end` (offset `1.0`). The color is
+    /// constant along lines perpendicular to this axis.
+    #[inline]
+    #[must_use]
+    pub fn linear<S: Into<Vec2>, E: Into<Vec2>>(start: S, end: E) -> Self
+    {
+        Gradient {
+            kind: GradientKind::Linear {
+                start: start.into(),
+                end: end.into()
+            },
+            stops: Vec::new()
+        }
+    }
+
+    /// Creates a radial gradient, which changes color along any straight line
+    /// from `center` (offset `0.0`) to the edge of a circle with the specified
+    /// `radius` (offset `1.0`). The color is constant around circles with the
+    /// same center.
+    #[inline]
+    #[must_use]
+    pub fn radial<C: Into<Vec2>>(center: C, radius: f32) -> Self
+    {
+        Gradient {
+            kind: GradientKind::Radial {
+                center: center.into(),
+                radius
+            },
+            stops: Vec::new()
+        }
+    }
+
+    /// Adds a color stop at the specified offset. Stops may be added in any
+    /// order. If two stops are added at the same offset, the color changes
+    /// abruptly at that point.
+    #[must_use]
+    pub fn with_stop(mut self, offset: f32, color: Color) -> Self
+    {
+        insert_stop(&mut self.stops, offset, color);
+        self
+    }
+
+    /// Returns the color of the gradient at the specified point.
+    #[must_use]
+    pub fn color_at<V: Into<Vec2>>(&self, point: V) -> Color
+    {
+        let offset = self.offset_at(point.into());
+
+        self.bands()
+            .find(|band| offset <= band.end_offset)
+            .map(|band| band.color_at(offset))
+            .unwrap_or(Color::TRANSPARENT)
+    }
+
+    fn offset_at(&self, point: Vec2) -> f32
+    {
+        match &self.kind {
+            GradientKind::Linear { start, end } => {
+                let axis = *end - *start;
+                let length_squared = axis.magnitude_squared();
+
+                if length_squared == 0.0 {
+                    return 0.0;
+                }
+
+                let relative = point - *start;
+                (relative.x * axis.x + relative.y * axis.y) / length_squared
+            }
+
+            GradientKind::Radial { center, radius } => {
+                if *radius <= 0.0 {
+                    return 0.0;
+                }
+
+                (point - *center).magnitude() / *radius
+            }
+        }
+    }
+
+    /// Returns the regions between each pair of stops, in which the color
+    /// changes linearly, plus the regions of constant color before the first
+    /// stop and after the last stop.
+    fn bands(&self) -> impl Iterator<Item = GradientBand> + '_
+    {
+        // The first and last stops take their colors from the bands next to
+        // them, in case they're fully transparent
+        let first = self.stops.first().map(|first| {
+            GradientBand::between(*first, *self.stops.get(1).unwrap_or(first))
+        });
+
+        let last = self.stops.last().map(|last| {
+            let previous = self
+                .stops
+                .len()
+                .checked_sub(2)
+                .map(|index| self.stops[index]);
+            GradientBand::between(previous.unwrap_or(*last), *last)
+        });
+
+        let before = first.map(|band| GradientBand {
+            start_offset: f32::NEG_INFINITY,
+            end_offset: band.start_offset,
+            start_color: band.start_color,
+            end_color: band.start_color
+        });
+
+        let between = self
+            .stops
+            .windows(2)
+            .filter(|pair| pair[0].0 < pair[1].0)
+            .map(|pair| GradientBand::between(pair[0], pair[1]));
+
+        let after = last.map(|band| GradientBand {
+            start_offset: band.end_offset,
+            end_offset: f32::INFINITY,
+            start_color: band.end_color,
+            end_color: band.end_color
+        });
+
+        before.into_iter().chain(between).chain(after)
+    }
+
+    /// Splits the specified triangle into smaller triangles, such that the
+    /// gradient can be represented by interpolating between the colors at
+    /// each vertex. This is exact for linear gradients, and a close
+    /// approximation for radial gradients.
+    pub(crate) fn split_triangle(
+        &self,
+        triangle: &[Vec2; 3],
+        output: &mut impl FnMut([Vec2; 3], [Color; 3])
+    )
+    {
+        match self.kind {
+            GradientKind::Linear { .. } => self.split_linear_triangle(triangle, output),
+            GradientKind::Radial { .. } => {
+                self.subdivide_triangle(triangle, GRADIENT_MAX_SUBDIVISION_DEPTH, output)
+            }
+        }
+    }
+
+    /// Subdivides the triangle until the gradient offset changes almost
+    /// linearly across each piece, which may then be treated as linear.
+    fn subdivide_triangle(
+        &self,
+        triangle: &[Vec2; 3],
+        remaining_depth: u32,
+        output: &mut impl FnMut([Vec2; 3], [Color; 3])
+    )
+    {
+        let [a, b, c] = *triangle;
+        let midpoints = [(a + b) / 2.0, (b + c) / 2.0, (c + a) / 2.0];
+        let centroid = (a + b + c) / 3.0;
+
+        let offsets = triangle.map(|vertex| self.offset_at(vertex));
+
+        let is_linear = remaining_depth == 0
+            || (midpoints.iter().zip([(0, 1), (1, 2), (2, 0)].iter()).all(
+                |(midpoint, (i, j))| {
+                    (self.offset_at(*midpoint) - (offsets[*i] + offsets[*j]) / 2.0).abs()
+                        <= GRADIENT_OFFSET_TOLERANCE
+                }
+            ) && (self.offset_at(centroid) - offsets.iter().sum::<f32>() / 3.0)
+                .abs()
+                <= GRADIENT_OFFSET_TOLERANCE);
+
+        if is_linear {
+            self.split_linear_triangle(triangle, output);
+            return;
+        }
+
+        let [ab, bc, ca] = midpoints;
+
+        for piece in [[a, ab, ca], [ab, b, bc], [ca, bc, c], [ab, bc, ca]].iter() {
+            self.subdivide_triangle(piece, remaining_depth - 1, output);
+        }
+    }
+
+    /// Splits the triangle along the boundaries between each color stop,
+    /// assuming that the gradient offset changes linearly across the
+    /// triangle.
+    fn split_linear_triangle(
+        &self,
+        triangle: &[Vec2; 3],
+        output: &mut impl FnMut([Vec2; 3], [Color; 3])
+    )
+    {
+        let offsets = triangle.map(|vertex| self.offset_at(vertex));
+
+        let min_offset = offsets.iter().copied().fold(f32::INFINITY, f32::min);
+        let max_offset = offsets.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+
+        for band in self.bands() {
+            for (start_offset, end_offset) in band.pieces() {
+                if end_offset < min_offset || start_offset > max_offset {
+                    continue;
+                }
+
+                let mut polygon: Vec<(Vec2, f32)> = triangle
+                    .iter()
+                    .copied()
+                    .zip(offsets.iter().copied())
+                    .collect();
+
+                if start_offset > min_offset {
+                    polygon = clip_polygon(&polygon, |offset| offset - start_offset);
+                }
+
+                if end_offset < max_offset {
+                    polygon = clip_polygon(&polygon, |offset| end_offset - offset);
+                }
+
+                for i in 1..polygon.len().saturating_sub(1) {
+                    let vertices = [polygon[0], polygon[i], polygon[i + 1]];
+
+                    output(
+                        vertices.map(|(position, _)| position),
+                        vertices.map(|(_, offset)| band.color_at(offset))
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Inserts a color stop after any existing stops with an offset less than or
+/// equal to `offset`, keeping the stops sorted.
+fn insert_stop(stops: &mut Vec<(f32, Color)>, offset: f32, color: Color)
+{
+    let index = stops
+        .iter()
+        .position(|(existing_offset, _)| *existing_offset > offset)
+        .unwrap_or(stops.len());
+
+    stops.insert(index, (offset, color));
+}
+
+/// A sequence of colors at increasing offsets, which can be sampled to obtain
+/// the interpolated color at any offset. This is useful for heatmaps and other
+/// visualizations which map a value to a color.
+///
+/// Offsets before the first stop or after the last stop take the color of the
+/// nearest stop. Colors between two stops are interpolated using
+/// [Color::lerp].
+///
+/// ```rust
+/// # use speedy2d::color::{Color, ColorRamp};
+/// let heatmap = ColorRamp::new()
+///     .with_stop(0.0, Color::BLUE)
+///     .with_stop(0.5, Color::YELLOW)
+///     .with_stop(1.0, Color::RED);
+///
+/// assert_eq!(Color::YELLOW, heatmap.sample(0.5));
+/// assert_eq!(Color::RED, heatmap.sample(2.0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ColorRamp
+{
+    stops: Vec<(f32, Color)>
+}
+
+impl ColorRamp
+{
+    /// Creates a new color ramp with no stops. Add stops using
+    /// [ColorRamp::with_stop].
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self
+    {
+        ColorRamp { stops: Vec::new() }
+    }
+
+    /// Creates a new color ramp from the specified colors, spaced evenly
+    /// between the offsets `0.0` and `1.0`.
+    #[must_use]
+    pub fn from_colors(colors: &[Color]) -> Self
+    {
+        let spacing = 1.0 / (colors.len() as f32 - 1.0).max(1.0);
+
+        ColorRamp {
+            stops: colors
+                .iter()
+                .enumerate()
+                .map(|(index, color)| (index as f32 * spacing, *color))
+                .collect()
+        }
+    }
+
+    /// Adds a color stop at the specified offset. Stops may be added in any
+    /// order. If two stops are added at the same offset, the color changes
+    /// abruptly at that point.
+    #[must_use]
+    pub fn with_stop(mut self, offset: f32, color: Color) -> Self
+    {
+        insert_stop(&mut self.stops, offset, color);
+        self
+    }
+
+    /// Returns the color at the specified offset. If the ramp has no stops,
+    /// [Color::TRANSPARENT] is returned.
+    #[must_use]
+    pub fn sample(&self, offset: f32) -> Color
+    {
+        let next = self
+            .stops
+            .iter()
+            .position(|(stop_offset, _)| *stop_offset > offset);
+
+        match next {
+            None => self
+                .stops
+                .last()
+                .map(|(_, color)| *color)
+                .unwrap_or(Color::TRANSPARENT),
+
+            Some(0) => self.stops[0].1,
+
+            Some(index) => {
+                let (start_offset, start_color) = self.stops[index - 1];
+                let (end_offset, end_color) = self.stops[index];
+
+                Color::lerp(
+                    start_color,
+                    end_color,
+                    (offset - start_offset) / (end_offset - start_offset)
+                )
+            }
+        }
+    }
+}
+
+struct GradientBand
+{
+    start_offset: f32,
+    end_offset: f32,
+    start_color: Color,
+    end_color: Color
+}
+
+impl GradientBand
+{
+    /// Creates the band between two stops. A fully transparent stop takes
+    /// the red, green, and blue components of the other stop, so that its
+    /// hidden color doesn't show up when colors are interpolated between
+    /// vertices.
+    fn between(start: (f32, Color), end: (f32, Color)) -> Self
+    {
+        let without_alpha = |color: Color, other: Color| {
+            if color.a <= 0.0 {
+                Color::from_rgba(other.r, other.g, other.b, 0.0)
+            } else {
+                color
+            }
+        };
+
+        GradientBand {
+            start_offset: start.0,
+            end_offset: end.0,
+            start_color: without_alpha(start.1, end.1),
+            end_color: without_alpha(end.1, start.1)
+        }
+    }
+
+    fn color_at(&self, offset: f32) -> Color
+    {
+        let length = self.end_offset - self.start_offset;
+
+        if !length.is_finite() || length <= 0.0 {
+            return self.start_color;
+        }
+
+        let t = (offset - self.start_offset) / length;
+
+        Color::lerp(self.start_color, self.end_color, t)
+    }
+
+    /// Returns the ranges of offsets to draw separately. The GPU interpolates
+    /// between vertex colors without weighting them by alpha, which only
+    /// matches [Color::lerp] if the opacity is constant, or if one of the
+    /// stops is fully transparent. Otherwise, the band is split into pieces.
+    fn pieces(&self) -> impl Iterator<Item = (f32, f32)> + '_
+    {
+        let (start, end) = (&self.start_color, &self.end_color);
+
+        let count = if start.a > 0.0 && end.a > 0.0 && start.a != end.a {
+            GRADIENT_ALPHA_BAND_PIECES
+        } else {
+            1
+        };
+
+        let offset = move |index: u32| match index {
+            0 => self.start_offset,
+            index if index == count => self.end_offset,
+            index => {
+                self.start_offset
+                    + (self.end_offset - self.start_offset) * index as f32 / count as f32
+            }
+        };
+
+        (0..count).map(move |index| (offset(index), offset(index + 1)))
+    }
+}
+
+/// Clips a convex polygon, keeping only the region where `distance` is
+/// non-negative. Each vertex is stored alongside its gradient offset, and
+/// `distance` must be a linear function of that offset.
+fn clip_polygon(
+    polygon: &[(Vec2, f32)],
+    distance: impl Fn(f32) -> f32
+) -> Vec<(Vec2, f32)>
+{
+    let mut result = Vec::with_capacity(polygon.len() + 1);
+
+    for (i, current) in polygon.iter().enumerate() {
+        let next = &polygon[(i + 1) % polygon.len()];
+
+        let current_distance = distance(current.1);
+        let next_distance = distance(next.1);
+
+        if current_distance >= 0.0 {
+            result.push(*current);
+        }
+
+        if (current_distance >= 0.0) != (next_distance >= 0.0) {
+            let t = current_distance / (current_distance - next_distance);
+            result.push((
+                current.0 + (next.0 - current.0) * t,
+                current.1 + (next.1 - current.1) * t
+            ));
+        }
+    }
+
+    result
+}
+
 #[cfg(test)]
 mod tests
 {
@@ -231,4 +1186,384 @@ mod tests
             Color::from_int_rgba(0xFF, 0x55, 0x11, 0xAA)
         );
     }
+
+    #[test]
+    fn test_linear_gradient()
+    {
+        let gradient = Gradient::linear((0.0, 0.0), (100.0, 0.0))
+            .with_stop(1.0, Color::BLUE)
+            .with_stop(0.0, Color::RED)
+            .with_stop(0.5, Color::GREEN)
+            .with_stop(0.5, Color::WHITE);
+
+        assert_eq!(Color::RED, gradient.color_at((-50.0, 0.0)));
+        assert_eq!(Color::RED, gradient.color_at((0.0, 30.0)));
+        assert_eq!(
+            Color::from_rgb(0.5, 0.5, 0.0),
+            gradient.color_at((25.0, -10.0))
+        );
+        assert_eq!(
+            Color::from_rgb(0.5, 0.5, 1.0),
+            gradient.color_at((75.0, 0.0))
+        );
+        assert_eq!(Color::BLUE, gradient.color_at((150.0, 0.0)));
+
+        // Splitting a triangle must cover exactly the same area
+        let triangle = [
+            Vec2::new(-20.0, 0.0),
+            Vec2::new(120.0, 10.0),
+            Vec2::new(30.0, 60.0)
+        ];
+
+        let area = |[a, b, c]: [Vec2; 3]| {
+            ((b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)).abs() / 2.0
+        };
+
+        let mut split_area = 0.0;
+        let mut split_count = 0;
+
+        gradient.split_triangle(&triangle, &mut |vertices, colors| {
+            split_area += area(vertices);
+            split_count += 1;
+
+            for (vertex, color) in vertices.iter().zip(colors.iter()) {
+                let expected = gradient.color_at(*vertex);
+                assert!(
+                    (expected.r() - color.r()).abs() < 0.001
+                        || (vertex.x - 50.0).abs() < 0.01
+                );
+            }
+        });
+
+        assert!((area(triangle) - split_area).abs() < 0.01);
+        assert!(split_count >= 4);
+    }
+
+    #[test]
+    fn test_gradient_transparent_stop()
+    {
+        let gradient = Gradient::linear((0.0, 0.0), (100.0, 0.0))
+            .with_stop(0.0, Color::RED)
+            .with_stop(1.0, Color::TRANSPARENT);
+
+        // Fading to transparent doesn't darken the color
+        assert_eq!(
+            Color::from_rgba(1.0, 0.0, 0.0, 0.5),
+            gradient.color_at((50.0, 0.0))
+        );
+        assert_eq!(
+            Color::from_rgba(1.0, 0.0, 0.0, 0.0),
+            gradient.color_at((100.0, 0.0))
+        );
+
+        // The vertex colors are interpolated without weighting by alpha, so
+        // the transparent vertices must not be black
+        gradient.split_triangle(
+            &[
+                Vec2::new(0.0, 0.0),
+                Vec2::new(100.0, 0.0),
+                Vec2::new(0.0, 100.0)
+            ],
+            &mut |_, colors| {
+                for color in colors.iter() {
+                    assert_eq!((1.0, 0.0, 0.0), (color.r(), color.g(), color.b()));
+                }
+            }
+        );
+
+        // Gradients and color ramps interpolate in the same way
+        let translucent_blue = Color::from_rgba(0.0, 0.0, 1.0, 0.2);
+
+        let gradient = Gradient::linear((0.0, 0.0), (100.0, 0.0))
+            .with_stop(0.0, Color::RED)
+            .with_stop(1.0, translucent_blue);
+
+        let ramp = ColorRamp::new()
+            .with_stop(0.0, Color::RED)
+            .with_stop(1.0, translucent_blue);
+
+        for x in [0.0, 25.0, 50.0, 75.0, 100.0].iter() {
+            let expected = ramp.sample(x / 100.0);
+            let actual = gradient.color_at((*x, 0.0));
+
+            for (a, b) in [
+                (expected.r(), actual.r()),
+                (expected.g(), actual.g()),
+                (expected.b(), actual.b()),
+                (expected.a(), actual.a())
+            ]
+            .iter()
+            {
+                assert!((a - b).abs() < 0.0001, "{:?} != {:?}", expected, actual);
+            }
+        }
+
+        let mut triangle_count = 0;
+
+        gradient.split_triangle(
+            &[
+                Vec2::new(0.0, 0.0),
+                Vec2::new(100.0, 0.0),
+                Vec2::new(0.0, 100.0)
+            ],
+            &mut |vertices, colors| {
+                triangle_count += 1;
+
+                let centroid = (vertices[0] + vertices[1] + vertices[2]) / 3.0;
+                let interpolated = (colors[0].r() + colors[1].r() + colors[2].r()) / 3.0;
+
+                assert!((gradient.color_at(centroid).r() - interpolated).abs() < 0.05);
+            }
+        );
+
+        assert!(triangle_count >= GRADIENT_ALPHA_BAND_PIECES);
+    }
+
+    #[test]
+    fn test_radial_gradient()
+    {
+        let gradient = Gradient::radial((100.0, 100.0), 50.0)
+            .with_stop(0.0, Color::WHITE)
+            .with_stop(1.0, Color::BLACK);
+
+        assert_eq!(Color::WHITE, gradient.color_at((100.0, 100.0)));
+        assert_eq!(Color::from_gray(0.5), gradient.color_at((100.0, 125.0)));
+        assert_eq!(Color::from_gray(0.5), gradient.color_at((75.0, 100.0)));
+        assert_eq!(Color::BLACK, gradient.color_at((0.0, 0.0)));
+
+        let mut triangle_count = 0;
+
+        gradient.split_triangle(
+            &[
+                Vec2::new(0.0, 0.0),
+                Vec2::new(200.0, 0.0),
+                Vec2::new(100.0, 200.0)
+            ],
+            &mut |vertices, colors| {
+                triangle_count += 1;
+
+                let centroid = (vertices[0] + vertices[1] + vertices[2]) / 3.0;
+                let interpolated = (colors[0].r() + colors[1].r() + colors[2].r()) / 3.0;
+
+                assert!((gradient.color_at(centroid).r() - interpolated).abs() < 0.05);
+            }
+        );
+
+        assert!(triangle_count > 16);
+    }
+
+    #[test]
+    fn test_from_hex_str()
+    {
+        let expected = Color::from_int_rgb(0xFF, 0x55, 0x11);
+
+        assert_eq!(expected, Color::from_hex_str("#FF5511").unwrap());
+        assert_eq!(expected, Color::from_hex_str("ff5511").unwrap());
+        assert_eq!(expected, Color::from_hex_str("#F51").unwrap());
+        assert_eq!(expected, Color::from_hex_str("f51").unwrap());
+
+        assert_eq!(
+            Color::from_int_rgba(0xFF, 0x55, 0x11, 0xAA),
+            Color::from_hex_str("#FF5511AA").unwrap()
+        );
+        assert_eq!(
+            Color::from_int_rgba(0xFF, 0x55, 0x11, 0x00),
+            Color::from_hex_str("FF551100").unwrap()
+        );
+
+        for invalid in &[
+            "", "#", "#FF55", "#FF5511A", "#GG5511", "+FF5511", "#FF 511"
+        ] {
+            assert!(Color::from_hex_str(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    fn assert_components_eq(expected: (f32, f32, f32), actual: (f32, f32, f32))
+    {
+        let (e, a) = (expected, actual);
+        assert!(
+            (e.0 - a.0).abs() < 0.01
+                && (e.1 - a.1).abs() < 0.001
+                && (e.2 - a.2).abs() < 0.001,
+            "expected {:?}, actual {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_hsv()
+    {
+        assert_eq!(Color::RED, Color::from_hsv(0.0, 1.0, 1.0, 1.0));
+        assert_eq!(Color::YELLOW, Color::from_hsv(60.0, 1.0, 1.0, 1.0));
+        assert_eq!(Color::GREEN, Color::from_hsv(120.0, 1.0, 1.0, 1.0));
+        assert_eq!(Color::MAGENTA, Color::from_hsv(300.0, 1.0, 1.0, 1.0));
+        assert_eq!(Color::RED, Color::from_hsv(360.0, 1.0, 1.0, 1.0));
+        assert_eq!(Color::CYAN, Color::from_hsv(-180.0, 1.0, 1.0, 1.0));
+        assert_eq!(Color::GRAY, Color::from_hsv(123.0, 0.0, 0.5, 1.0));
+        assert_eq!(0.25, Color::from_hsv(0.0, 1.0, 1.0, 0.25).a());
+
+        assert_components_eq((0.0, 0.0, 0.5), Color::GRAY.to_hsv());
+        assert_components_eq((240.0, 1.0, 1.0), Color::BLUE.to_hsv());
+
+        let color = Color::from_int_rgb(0xFF, 0x55, 0x11);
+        let (h, s, v) = color.to_hsv();
+        assert_components_eq((17.14, 0.933, 1.0), (h, s, v));
+
+        let round_trip = Color::from_hsv(h, s, v, 1.0);
+        assert!((round_trip.r() - color.r()).abs() < 0.001);
+        assert!((round_trip.g() - color.g()).abs() < 0.001);
+        assert!((round_trip.b() - color.b()).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_hsl()
+    {
+        assert_eq!(Color::RED, Color::from_hsl(0.0, 1.0, 0.5, 1.0));
+        assert_eq!(Color::BLUE, Color::from_hsl(240.0, 1.0, 0.5, 1.0));
+        assert_eq!(Color::WHITE, Color::from_hsl(60.0, 1.0, 1.0, 1.0));
+        assert_eq!(Color::BLACK, Color::from_hsl(60.0, 1.0, 0.0, 1.0));
+        assert_eq!(Color::GRAY, Color::from_hsl(123.0, 0.0, 0.5, 1.0));
+
+        assert_components_eq((0.0, 0.0, 1.0), Color::WHITE.to_hsl());
+        assert_components_eq((120.0, 1.0, 0.5), Color::GREEN.to_hsl());
+
+        let color = Color::from_int_rgb(0x33, 0x66, 0x99);
+        let (h, s, l) = color.to_hsl();
+        assert_components_eq((210.0, 0.5, 0.4), (h, s, l));
+
+        let round_trip = Color::from_hsl(h, s, l, 1.0);
+        assert!((round_trip.r() - color.r()).abs() < 0.001);
+        assert!((round_trip.g() - color.g()).abs() < 0.001);
+        assert!((round_trip.b() - color.b()).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_from_css_str()
+    {
+        let orange = Color::from_int_rgb(0xFF, 0x88, 0x00);
+
+        for css in &[
+            "#ff8800",
+            "#F80",
+            " #ff8800 ",
+            "#ff8800ff",
+            "rgb(255, 136, 0)",
+            "RGB(255,136,0)",
+            "rgba(255, 136, 0, 1)",
+            "rgb(255 136 0)",
+            "rgb(255 136 0 / 100%)"
+        ] {
+            assert_eq!(orange, Color::from_css_str(css).unwrap(), "{}", css);
+        }
+
+        assert_eq!(
+            Color::from_int_rgba(0xFF, 0x88, 0x00, 0xAA),
+            Color::from_css_str("#f80a").unwrap()
+        );
+        assert_eq!(
+            Color::from_rgba(1.0, 0.5, 0.0, 0.5),
+            Color::from_css_str("rgba(100%, 50%, 0%, 0.5)").unwrap()
+        );
+        assert_eq!(
+            Color::from_rgba(1.0, 0.0, 0.0, 0.25),
+            Color::from_css_str("hsla(360deg 100% 50% / 25%)").unwrap()
+        );
+        assert_eq!(
+            Color::BLUE,
+            Color::from_css_str("hsl(240, 100%, 50%)").unwrap()
+        );
+
+        assert_eq!(
+            Color::TRANSPARENT,
+            Color::from_css_str("Transparent").unwrap()
+        );
+        assert_eq!(
+            Color::from_hex_rgb(0x663399),
+            Color::from_css_str("RebeccaPurple").unwrap()
+        );
+        assert_eq!(
+            Color::from_hex_rgb(0xF0F8FF),
+            Color::from_css_str("aliceblue").unwrap()
+        );
+        assert_eq!(
+            Color::from_hex_rgb(0x9ACD32),
+            Color::from_css_str("yellowgreen").unwrap()
+        );
+
+        for invalid in &[
+            "",
+            "#",
+            "#ff888",
+            "notacolor",
+            "rgb(255, 136)",
+            "rgb(255, 136, 0, 1, 1)",
+            "rgb(a, b, c)",
+            "rgb(255, 136, 0",
+            "cmyk(0, 0, 0, 0)",
+            "hsl(nan, 100%, 50%)"
+        ] {
+            assert!(Color::from_css_str(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_lerp()
+    {
+        assert_eq!(Color::RED, Color::lerp(Color::RED, Color::BLUE, 0.0));
+        assert_eq!(Color::BLUE, Color::lerp(Color::RED, Color::BLUE, 1.0));
+        assert_eq!(Color::BLUE, Color::lerp(Color::RED, Color::BLUE, 3.0));
+        assert_eq!(
+            Color::from_rgb(0.5, 0.0, 0.5),
+            Color::lerp(Color::RED, Color::BLUE, 0.5)
+        );
+
+        // Transparent colors don't contribute to the result
+        assert_eq!(
+            Color::from_rgba(0.0, 1.0, 0.0, 0.25),
+            Color::lerp(Color::TRANSPARENT, Color::GREEN, 0.25)
+        );
+        assert_eq!(
+            Color::TRANSPARENT,
+            Color::lerp(Color::TRANSPARENT, Color::TRANSPARENT, 0.5)
+        );
+    }
+
+    #[test]
+    fn test_color_ramp()
+    {
+        assert_eq!(Color::TRANSPARENT, ColorRamp::new().sample(0.5));
+
+        let ramp = ColorRamp::new()
+            .with_stop(1.0, Color::BLUE)
+            .with_stop(0.0, Color::RED)
+            .with_stop(0.5, Color::GREEN)
+            .with_stop(0.5, Color::WHITE);
+
+        assert_eq!(Color::RED, ramp.sample(-1.0));
+        assert_eq!(Color::RED, ramp.sample(0.0));
+        assert_eq!(Color::from_rgb(0.5, 0.5, 0.0), ramp.sample(0.25));
+        assert_eq!(Color::WHITE, ramp.sample(0.5));
+        assert_eq!(Color::from_rgb(0.5, 0.5, 1.0), ramp.sample(0.75));
+        assert_eq!(Color::BLUE, ramp.sample(1.0));
+        assert_eq!(Color::BLUE, ramp.sample(2.0));
+
+        let evenly_spaced =
+            ColorRamp::from_colors(&[Color::RED, Color::GREEN, Color::BLUE]);
+        assert_eq!(Color::GREEN, evenly_spaced.sample(0.5));
+        assert_eq!(Color::BLUE, evenly_spaced.sample(1.0));
+        assert_eq!(
+            Color::RED,
+            ColorRamp::from_colors(&[Color::RED]).sample(0.7)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip()
+    {
+        let color = Color::from_rgba(0.1, 0.25, 0.5, 0.75);
+        let json = serde_json::to_string(&color).unwrap();
+        assert_eq!(color, serde_json::from_str::<Color>(&json).unwrap());
+    }
 }
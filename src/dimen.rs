@@ -34,6 +34,7 @@ pub type UVec2 = Vector2<u32>;
 /// position.
 #[repr(C)]
 #[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vector2<T>
 {
     /// The horizontal component of the vector.
@@ -196,7 +197,6 @@ where
     T: Copy
 {
     #[inline]
-    #[must_use]
     fn from(value: (T, T)) -> Self
     {
         Vector2::new(value.0, value.1)
@@ -208,7 +208,6 @@ where
     T: Copy
 {
     #[inline]
-    #[must_use]
     fn from(value: &(T, T)) -> Self
     {
         Vector2::new(value.0, value.1)
@@ -220,7 +219,6 @@ where
     T: Copy
 {
     #[inline]
-    #[must_use]
     fn from(value: &Self) -> Self
     {
         *value
@@ -232,7 +230,6 @@ where
     T: Copy
 {
     #[inline]
-    #[must_use]
     fn from(value: &mut Self) -> Self
     {
         *value
@@ -244,7 +241,6 @@ impl<T: Copy + std::ops::Add<Output = T>, R: Into<Self>> std::ops::Add<R> for Ve
     type Output = Vector2<T>;
 
     #[inline]
-    #[must_use]
     fn add(self, rhs: R) -> Self::Output
     {
         let rhs = rhs.into();
@@ -258,7 +254,6 @@ impl<T: Copy + std::ops::Add<Output = T>, R: Into<Vector2<T>>> std::ops::Add<R>
     type Output = Vector2<T>;
 
     #[inline]
-    #[must_use]
     fn add(self, rhs: R) -> Self::Output
     {
         let rhs = rhs.into();
@@ -271,7 +266,6 @@ impl<T: Copy + std::ops::Sub<Output = T>, R: Into<Self>> std::ops::Sub<R> for Ve
     type Output = Vector2<T>;
 
     #[inline]
-    #[must_use]
     fn sub(self, rhs: R) -> Self::Output
     {
         let rhs = rhs.into();
@@ -285,7 +279,6 @@ impl<T: Copy + std::ops::Sub<Output = T>, R: Into<Vector2<T>>> std::ops::Sub<R>
     type Output = Vector2<T>;
 
     #[inline]
-    #[must_use]
     fn sub(self, rhs: R) -> Self::Output
     {
         let rhs = rhs.into();
@@ -386,7 +379,6 @@ impl<T: Copy + std::ops::Mul<Output = T>> std::ops::Mul<T> for &Vector2<T>
     type Output = Vector2<T>;
 
     #[inline]
-    #[must_use]
     fn mul(self, rhs: T) -> Self::Output
     {
         Vector2::new(self.x * rhs, self.y * rhs)
@@ -398,7 +390,6 @@ impl<T: Copy + std::ops::Mul<Output = T>> std::ops::Mul<T> for Vector2<T>
     type Output = Vector2<T>;
 
     #[inline]
-    #[must_use]
     fn mul(self, rhs: T) -> Self::Output
     {
         Vector2::new(self.x * rhs, self.y * rhs)
@@ -410,7 +401,6 @@ impl<T: Copy + std::ops::Div<Output = T>> std::ops::Div<T> for &Vector2<T>
     type Output = Vector2<T>;
 
     #[inline]
-    #[must_use]
     fn div(self, rhs: T) -> Self::Output
     {
         Vector2::new(self.x / rhs, self.y / rhs)
@@ -422,7 +412,6 @@ impl<T: Copy + std::ops::Div<Output = T>> std::ops::Div<T> for Vector2<T>
     type Output = Vector2<T>;
 
     #[inline]
-    #[must_use]
     fn div(self, rhs: T) -> Self::Output
     {
         Vector2::new(self.x / rhs, self.y / rhs)
@@ -440,13 +429,178 @@ impl<T: RoundFloat> RoundFloat for Vector2<T>
 impl<T> From<Point<T>> for Vector2<T>
 {
     #[inline]
-    #[must_use]
     fn from(point: Point<T>) -> Self
     {
         Vector2::new(point.x, point.y)
     }
 }
 
+/// A 2D affine transformation, which may be composed of any combination of
+/// translation, rotation, scaling, and skew.
+///
+/// A point `(x, y)` is transformed as follows:
+///
+/// ```text
+/// x' = a * x + c * y + tx
+/// y' = b * x + d * y + ty
+/// ```
+///
+/// Transformations can be combined by multiplying them together. The
+/// transformation `first.then(&second)` (equivalent to `second * first`)
+/// applies `first`, followed by `second`.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Matrix2D
+{
+    a: f32,
+    b: f32,
+    c: f32,
+    d: f32,
+    tx: f32,
+    ty: f32
+}
+
+impl Matrix2D
+{
+    /// The identity transformation, which leaves every point unchanged.
+    pub const IDENTITY: Matrix2D = Matrix2D::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+
+    /// Instantiates a new transformation from the specified coefficients. See
+    /// the documentation for [Matrix2D] for the meaning of each coefficient.
+    #[inline]
+    #[must_use]
+    pub const fn new(a: f32, b: f32, c: f32, d: f32, tx: f32, ty: f32) -> Self
+    {
+        Matrix2D { a, b, c, d, tx, ty }
+    }
+
+    /// A transformation which moves each point by the specified offset.
+    #[inline]
+    #[must_use]
+    pub fn translate<V: Into<Vec2>>(offset: V) -> Self
+    {
+        let offset = offset.into();
+        Matrix2D::new(1.0, 0.0, 0.0, 1.0, offset.x, offset.y)
+    }
+
+    /// A transformation which scales each point relative to the origin, by
+    /// the specified horizontal and vertical factors.
+    #[inline]
+    #[must_use]
+    pub fn scale<V: Into<Vec2>>(factor: V) -> Self
+    {
+        let factor = factor.into();
+        Matrix2D::new(factor.x, 0.0, 0.0, factor.y, 0.0, 0.0)
+    }
+
+    /// A transformation which rotates each point clockwise around the origin
+    /// by the specified angle.
+    #[inline]
+    #[must_use]
+    pub fn rotate(radians: f32) -> Self
+    {
+        let (sin, cos) = radians.sin_cos();
+        Matrix2D::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+
+    /// Returns a transformation which applies `self`, followed by `next`.
+    #[inline]
+    #[must_use]
+    pub fn then(&self, next: &Matrix2D) -> Self
+    {
+        Matrix2D::new(
+            next.a * self.a + next.c * self.b,
+            next.b * self.a + next.d * self.b,
+            next.a * self.c + next.c * self.d,
+            next.b * self.c + next.d * self.d,
+            next.a * self.tx + next.c * self.ty + next.tx,
+            next.b * self.tx + next.d * self.ty + next.ty
+        )
+    }
+
+    /// Returns the inverse of this transformation, or `None` if the
+    /// transformation cannot be inverted (for example, if it scales by zero).
+    #[must_use]
+    pub fn inverse(&self) -> Option<Self>
+    {
+        let determinant = self.a * self.d - self.b * self.c;
+
+        if determinant == 0.0 || !determinant.is_finite() {
+            return None;
+        }
+
+        let a = self.d / determinant;
+        let b = -self.b / determinant;
+        let c = -self.c / determinant;
+        let d = self.a / determinant;
+
+        Some(Matrix2D::new(
+            a,
+            b,
+            c,
+            d,
+            -(a * self.tx + c * self.ty),
+            -(b * self.tx + d * self.ty)
+        ))
+    }
+
+    /// Returns true if this is the identity transformation.
+    #[inline]
+    #[must_use]
+    pub fn is_identity(&self) -> bool
+    {
+        *self == Matrix2D::IDENTITY
+    }
+
+    /// Applies this transformation to the specified point.
+    #[inline]
+    #[must_use]
+    pub fn transform_point<V: Into<Vec2>>(&self, point: V) -> Vec2
+    {
+        let point = point.into();
+
+        Vec2::new(
+            self.a * point.x + self.c * point.y + self.tx,
+            self.b * point.x + self.d * point.y + self.ty
+        )
+    }
+
+    /// Applies this transformation to the specified vector, ignoring any
+    /// translation. This is useful for transforming sizes and directions.
+    #[inline]
+    #[must_use]
+    pub fn transform_vector<V: Into<Vec2>>(&self, vector: V) -> Vec2
+    {
+        let vector = vector.into();
+
+        Vec2::new(
+            self.a * vector.x + self.c * vector.y,
+            self.b * vector.x + self.d * vector.y
+        )
+    }
+}
+
+impl Default for Matrix2D
+{
+    #[inline]
+    fn default() -> Self
+    {
+        Matrix2D::IDENTITY
+    }
+}
+
+impl std::ops::Mul for Matrix2D
+{
+    type Output = Matrix2D;
+
+    /// Combines two transformations. The result applies `rhs`, followed by
+    /// `self`.
+    #[inline]
+    fn mul(self, rhs: Matrix2D) -> Self::Output
+    {
+        rhs.then(&self)
+    }
+}
+
 #[cfg(test)]
 mod test
 {
@@ -471,6 +625,7 @@ mod test
     }
 
     #[test]
+    #[allow(clippy::op_ref)]
     fn test_arithmetic_ref()
     {
         assert_eq!(
@@ -505,6 +660,7 @@ mod test
     }
 
     #[test]
+    #[allow(clippy::op_ref)]
     fn test_arithmetic_tuples()
     {
         assert_eq!(Vector2::new(15, 20), Vector2::new(10, 4) + (5, 16));
@@ -578,6 +734,68 @@ mod test
         assert_eq!(left, Vector2::new(20, 30));
     }
 
+    fn assert_vec_near(expected: Vec2, actual: Vec2)
+    {
+        assert!(
+            (expected - actual).magnitude() < 0.0001,
+            "Expected {:?}, got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_matrix_transform()
+    {
+        let point = Vec2::new(10.0, 20.0);
+
+        assert_eq!(point, Matrix2D::IDENTITY.transform_point(point));
+
+        assert_eq!(
+            Vec2::new(15.0, 17.0),
+            Matrix2D::translate((5.0, -3.0)).transform_point(point)
+        );
+
+        assert_eq!(
+            Vec2::new(20.0, 60.0),
+            Matrix2D::scale((2.0, 3.0)).transform_point(point)
+        );
+
+        assert_vec_near(
+            Vec2::new(-20.0, 10.0),
+            Matrix2D::rotate(std::f32::consts::FRAC_PI_2).transform_point(point)
+        );
+
+        let combined = Matrix2D::scale((2.0, 2.0)).then(&Matrix2D::translate((1.0, 1.0)));
+
+        assert_eq!(Vec2::new(21.0, 41.0), combined.transform_point(point));
+        assert_eq!(
+            combined,
+            Matrix2D::translate((1.0, 1.0)) * Matrix2D::scale((2.0, 2.0))
+        );
+        assert_eq!(Vec2::new(20.0, 40.0), combined.transform_vector(point));
+    }
+
+    #[test]
+    fn test_matrix_inverse()
+    {
+        let point = Vec2::new(10.0, 20.0);
+
+        let transform = Matrix2D::rotate(0.7)
+            .then(&Matrix2D::scale((2.0, 0.5)))
+            .then(&Matrix2D::translate((30.0, -4.0)));
+
+        let inverse = transform.inverse().unwrap();
+
+        assert_vec_near(
+            point,
+            inverse.transform_point(transform.transform_point(point))
+        );
+        assert!(transform.then(&inverse).inverse().is_some());
+
+        assert_eq!(None, Matrix2D::scale((0.0, 1.0)).inverse());
+    }
+
     #[test]
     fn test_div_assign()
     {
@@ -19,7 +19,7 @@ use std::convert::TryInto;
 use std::fmt::{Debug, Formatter};
 use std::hash::{Hash, Hasher};
 use std::iter::Peekable;
-use std::ops::Deref;
+use std::ops::{Deref, Range};
 use std::slice::Iter;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
@@ -27,11 +27,13 @@ use std::vec::IntoIter;
 
 use rusttype::Scale;
 use smallvec::{smallvec, SmallVec};
+use unicode_normalization::char::{canonical_combining_class, compose};
 use unicode_normalization::UnicodeNormalization;
 
+use crate::color::Color;
 use crate::dimen::{Vec2, Vector2};
 use crate::error::{BacktraceError, ErrorMessage};
-use crate::shape::{Rect, Rectangle};
+use crate::shape::{Path, Rect, Rectangle};
 
 static FONT_ID_GENERATOR: AtomicUsize = AtomicUsize::new(10000);
 
@@ -55,7 +57,10 @@ type FormattedTextLineVec = SmallVec<[FormattedTextLine; 1]>;
 pub struct Codepoint
 {
     user_index: UserGlyphIndex,
-    codepoint: char
+    codepoint: char,
+
+    /// The location of the `LayoutStyle` used for this codepoint.
+    style: usize
 }
 
 impl Codepoint
@@ -74,7 +79,8 @@ impl Codepoint
     {
         Codepoint {
             user_index,
-            codepoint
+            codepoint,
+            style: 0
         }
     }
 
@@ -88,13 +94,79 @@ impl Codepoint
 
         codepoints
     }
+
+    /// Normalizes the string. The `user_index` of each codepoint is its
+    /// location in the normalized string, starting from `first_user_index`,
+    /// and the byte offset in `text` of the character it was formed from is
+    /// appended to `byte_offsets`.
+    fn from_str_with_byte_offsets(
+        text: &str,
+        first_user_index: UserGlyphIndex,
+        byte_offsets: &mut Vec<usize>
+    ) -> Vec<Self>
+    {
+        let mut normalized = Vec::with_capacity(text.len());
+
+        // Each segment starts at a character which can't be combined with
+        // the one before it, so the segments are normalized independently
+        let mut segment_start = 0;
+        let mut previous = None;
+
+        for (offset, codepoint) in text.char_indices() {
+            let starts_segment = canonical_combining_class(codepoint) == 0
+                && previous
+                    .and_then(|previous| compose(previous, codepoint))
+                    .is_none();
+
+            if starts_segment && offset > 0 {
+                Codepoint::push_normalized_segment(
+                    &mut normalized,
+                    &text[segment_start..offset],
+                    segment_start
+                );
+                segment_start = offset;
+            }
+
+            previous = Some(codepoint);
+        }
+
+        if segment_start < text.len() {
+            Codepoint::push_normalized_segment(
+                &mut normalized,
+                &text[segment_start..],
+                segment_start
+            );
+        }
+
+        normalized
+            .into_iter()
+            .zip(first_user_index..)
+            .map(|((offset, codepoint), user_index)| {
+                byte_offsets.push(offset);
+                Codepoint::new(user_index, codepoint)
+            })
+            .collect()
+    }
+
+    fn push_normalized_segment(
+        output: &mut Vec<(usize, char)>,
+        segment: &str,
+        offset: usize
+    )
+    {
+        output.extend(segment.nfc().map(|codepoint| (offset, codepoint)));
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Hash)]
 struct RenderableWord
 {
     codepoints: Vec<Codepoint>,
-    is_whitespace: bool
+    is_whitespace: bool,
+
+    /// The `user_index` of the codepoint after this word in the input, or the
+    /// end of the input.
+    end_user_index: UserGlyphIndex
 }
 
 impl RenderableWord
@@ -105,7 +177,8 @@ impl RenderableWord
 
         RenderableWord {
             codepoints: self.codepoints,
-            is_whitespace: self.is_whitespace
+            is_whitespace: self.is_whitespace,
+            end_user_index: self.end_user_index
         }
     }
 }
@@ -114,12 +187,15 @@ impl RenderableWord
 enum Word
 {
     Renderable(RenderableWord),
-    Newline
+
+    /// A line break, with the `user_index` of the newline codepoint.
+    Newline(UserGlyphIndex)
 }
 
 impl Word
 {
-    fn split_words(codepoints: &[Codepoint]) -> Vec<Word>
+    fn split_words(codepoints: &[Codepoint], end_user_index: UserGlyphIndex)
+        -> Vec<Word>
     {
         let mut reader = codepoints.iter().peekable();
 
@@ -131,12 +207,16 @@ impl Word
                     // Do nothing here, just ignore it
                 }
 
-                '\n' => result.push(Word::Newline),
+                '\n' => result.push(Word::Newline(first_token.user_index)),
 
                 ' ' | '\t' => {
                     result.push(Word::Renderable(RenderableWord {
                         codepoints: vec![first_token.clone()],
-                        is_whitespace: true
+                        is_whitespace: true,
+                        end_user_index: reader
+                            .peek()
+                            .map(|next| next.user_index)
+                            .unwrap_or(end_user_index)
                     }));
                 }
 
@@ -157,7 +237,11 @@ impl Word
 
                     result.push(Word::Renderable(RenderableWord {
                         codepoints: word_codepoints,
-                        is_whitespace: false
+                        is_whitespace: false,
+                        end_user_index: reader
+                            .peek()
+                            .map(|next| next.user_index)
+                            .unwrap_or(end_user_index)
                     }));
                 }
             }
@@ -212,6 +296,20 @@ impl WordsIterator
         None
     }
 
+    /// The `user_index` of the first codepoint in the next word, if any.
+    #[inline]
+    #[must_use]
+    fn peek_user_index(&mut self) -> Option<UserGlyphIndex>
+    {
+        match self.peek()? {
+            Word::Renderable(word) => word
+                .codepoints
+                .first()
+                .map(|codepoint| codepoint.user_index),
+            Word::Newline(user_index) => Some(*user_index)
+        }
+    }
+
     #[inline]
     fn next(&mut self) -> Option<Word>
     {
@@ -241,7 +339,8 @@ struct LineLayoutMetrics
     min_descent: f32,
     max_line_gap: f32,
     last_glyph_id: Option<rusttype::GlyphId>,
-    last_font_id: Option<FontId>
+    last_font_id: Option<FontId>,
+    last_scale: Option<Scale>
 }
 
 impl LineLayoutMetrics
@@ -254,7 +353,8 @@ impl LineLayoutMetrics
             min_descent: 0.0,
             max_line_gap: 0.0,
             last_glyph_id: None,
-            last_font_id: None
+            last_font_id: None,
+            last_scale: None
         }
     }
 
@@ -269,20 +369,28 @@ impl LineLayoutMetrics
         &mut self,
         glyph: &rusttype::ScaledGlyph,
         font_id: FontId,
+        shaping: Option<&ShapedPosition>,
         scale: &Scale,
         options: &TextOptions
     ) -> f32
     {
+        let same_style =
+            self.last_font_id == Some(font_id) && self.last_scale == Some(*scale);
+
         if let Some(last_glyph_id) = self.last_glyph_id {
-            if self.last_font_id == Some(font_id) {
+            // Shaped glyphs already include any kerning in their advance
+            if same_style && shaping.is_none() {
                 self.x_pos +=
                     glyph.font().pair_kerning(*scale, last_glyph_id, glyph.id());
             }
 
-            self.x_pos += options.tracking;
+            // Tracking is added between characters, not within them
+            if !matches!(shaping, Some(shaping) if shaping.joins_previous) {
+                self.x_pos += options.tracking;
+            }
         }
 
-        if self.last_font_id != Some(font_id) {
+        if !same_style {
             let v_metrics = glyph.font().v_metrics(*scale);
 
             self.max_ascent = crate::numeric::max(self.max_ascent, v_metrics.ascent);
@@ -291,18 +399,263 @@ impl LineLayoutMetrics
                 crate::numeric::max(self.max_line_gap, v_metrics.line_gap);
         }
 
-        let advance_width = glyph.h_metrics().advance_width;
+        let advance_width = match shaping {
+            None => glyph.h_metrics().advance_width,
+            Some(shaping) => shaping.advance
+        };
 
         let glyph_x_pos_start = self.x_pos;
         self.x_pos += advance_width;
 
         self.last_font_id = Some(font_id);
+        self.last_scale = Some(*scale);
         self.last_glyph_id = Some(glyph.id());
 
         glyph_x_pos_start
     }
 }
 
+/// The font, size, and color used to lay out a codepoint. Each codepoint
+/// refers to one of these using its `style` field.
+struct LayoutStyle<'a, T: TextLayout + ?Sized>
+{
+    layout_helper: &'a T,
+    scale: Scale,
+    color: Option<Color>,
+    background_color: Option<Color>
+}
+
+impl<'a, T: TextLayout + ?Sized> LayoutStyle<'a, T>
+{
+    fn new(layout_helper: &'a T, scale: f32) -> Self
+    {
+        LayoutStyle {
+            layout_helper,
+            scale: Scale::uniform(scale),
+            color: None,
+            background_color: None
+        }
+    }
+}
+
+/// The position of a glyph as calculated by the text shaper, in pixels.
+struct ShapedPosition
+{
+    advance: f32,
+    offset: Vec2,
+
+    /// Whether the glyph is part of the same character as the previous glyph,
+    /// for example a combining mark.
+    joins_previous: bool
+}
+
+/// A glyph within a word, which has not yet been positioned on a line.
+struct WordGlyph
+{
+    glyph: FontGlyph,
+    user_index: UserGlyphIndex,
+    style: usize,
+
+    /// The location in the word of the first codepoint which this glyph
+    /// represents.
+    codepoint_location: usize,
+
+    shaping: Option<ShapedPosition>
+}
+
+impl WordGlyph
+{
+    fn unshaped(
+        glyph: FontGlyph,
+        codepoint: &Codepoint,
+        codepoint_location: usize
+    ) -> Self
+    {
+        WordGlyph {
+            glyph,
+            user_index: codepoint.user_index,
+            style: codepoint.style,
+            codepoint_location,
+            shaping: None
+        }
+    }
+}
+
+/// Looks up the glyph for the codepoint, or a replacement glyph if it's not
+/// found.
+fn lookup_glyph_or_replacement<T: TextLayout + ?Sized>(
+    layout_helper: &T,
+    codepoint: char
+) -> Option<FontGlyph>
+{
+    layout_helper
+        .lookup_glyph_for_codepoint(codepoint)
+        .or_else(|| layout_helper.lookup_glyph_for_codepoint('□'))
+        .or_else(|| layout_helper.lookup_glyph_for_codepoint('?'))
+}
+
+/// Returns the glyphs used to render the word, in the order they should be
+/// drawn from left to right.
+#[cfg(not(feature = "text-shaping"))]
+fn word_glyphs<T: TextLayout + ?Sized>(
+    styles: &[LayoutStyle<T>],
+    word: &RenderableWord
+) -> Vec<WordGlyph>
+{
+    word.codepoints
+        .iter()
+        .enumerate()
+        .filter_map(|(location, codepoint)| {
+            lookup_glyph_or_replacement(
+                styles[codepoint.style].layout_helper,
+                codepoint.codepoint
+            )
+            .map(|glyph| WordGlyph::unshaped(glyph, codepoint, location))
+        })
+        .collect()
+}
+
+/// Returns the glyphs used to render the word, in the order they should be
+/// drawn from left to right.
+///
+/// Consecutive codepoints found in the same font, and with the same style, are
+/// shaped together, so that ligatures, contextual forms, and mark positioning
+/// are applied.
+#[cfg(feature = "text-shaping")]
+fn word_glyphs<T: TextLayout + ?Sized>(
+    styles: &[LayoutStyle<T>],
+    word: &RenderableWord
+) -> Vec<WordGlyph>
+{
+    let codepoints = &word.codepoints;
+    let mut result = Vec::with_capacity(codepoints.len());
+    let mut run_start = 0;
+
+    while run_start < codepoints.len() {
+        let style_location = codepoints[run_start].style;
+        let style = &styles[style_location];
+
+        let font = match style
+            .layout_helper
+            .lookup_glyph_for_codepoint(codepoints[run_start].codepoint)
+        {
+            Some(glyph) => glyph.font,
+            None => {
+                // Missing glyphs are replaced without shaping
+                let codepoint = &codepoints[run_start];
+                if let Some(glyph) =
+                    lookup_glyph_or_replacement(style.layout_helper, codepoint.codepoint)
+                {
+                    result.push(WordGlyph::unshaped(glyph, codepoint, run_start));
+                }
+                run_start += 1;
+                continue;
+            }
+        };
+
+        let mut run_end = run_start + 1;
+
+        while run_end < codepoints.len()
+            && codepoints[run_end].style == style_location
+            && matches!(
+                style
+                    .layout_helper
+                    .lookup_glyph_for_codepoint(codepoints[run_end].codepoint),
+                Some(glyph) if glyph.font == font
+            )
+        {
+            run_end += 1;
+        }
+
+        shape_run(
+            &font,
+            codepoints,
+            run_start..run_end,
+            &style.scale,
+            &mut result
+        );
+
+        run_start = run_end;
+    }
+
+    result
+}
+
+/// Shapes the specified range of codepoints, which must all be present in the
+/// font, and appends the resulting glyphs to `output`.
+#[cfg(feature = "text-shaping")]
+fn shape_run(
+    font: &Font,
+    codepoints: &[Codepoint],
+    range: Range<usize>,
+    scale: &Scale,
+    output: &mut Vec<WordGlyph>
+)
+{
+    let face = match rustybuzz::Face::from_face(font_face(font.font()).clone()) {
+        Some(face) => face,
+        None => {
+            for location in range {
+                let codepoint = &codepoints[location];
+                if let Some(glyph) = font.lookup_glyph_for_codepoint(codepoint.codepoint)
+                {
+                    output.push(WordGlyph::unshaped(glyph, codepoint, location));
+                }
+            }
+            return;
+        }
+    };
+
+    // The shaper identifies each character by its byte offset in the string
+    let mut text = String::with_capacity(range.len());
+    let mut byte_offsets = Vec::with_capacity(range.len());
+
+    for codepoint in &codepoints[range.clone()] {
+        byte_offsets.push(text.len());
+        text.push(codepoint.codepoint);
+    }
+
+    let mut buffer = rustybuzz::UnicodeBuffer::new();
+    buffer.push_str(&text);
+    buffer.guess_segment_properties();
+
+    let shaped = rustybuzz::shape(&face, &[], buffer);
+    let pixels_per_unit = font.font().scale_for_pixel_height(scale.y);
+
+    let mut previous_cluster = None;
+
+    for (info, position) in shaped
+        .glyph_infos()
+        .iter()
+        .zip(shaped.glyph_positions().iter())
+    {
+        let location = range.start
+            + byte_offsets
+                .binary_search(&(info.cluster as usize))
+                .unwrap_or_else(|index| index.saturating_sub(1));
+
+        output.push(WordGlyph {
+            glyph: FontGlyph {
+                glyph: font.font().glyph(rusttype::GlyphId(info.glyph_id as u16)),
+                font: font.clone()
+            },
+            user_index: codepoints[location].user_index,
+            style: codepoints[location].style,
+            codepoint_location: location,
+            shaping: Some(ShapedPosition {
+                advance: position.x_advance as f32 * pixels_per_unit,
+                offset: Vec2::new(
+                    position.x_offset as f32 * pixels_per_unit,
+                    -position.y_offset as f32 * pixels_per_unit
+                ),
+                joins_previous: previous_cluster == Some(info.cluster)
+            })
+        });
+
+        previous_cluster = Some(info.cluster);
+    }
+}
+
 enum WordLayoutResult
 {
     Success(LineLayoutMetrics),
@@ -331,17 +684,33 @@ impl WordLayoutResult
     }
 }
 
-#[allow(clippy::too_many_arguments)]
+/// Returns the `user_index` of the first codepoint after the cluster starting
+/// at `location`.
+fn cluster_end_user_index(
+    word: &RenderableWord,
+    cluster_locations: &[usize],
+    location: usize
+) -> UserGlyphIndex
+{
+    let next_cluster = match cluster_locations.binary_search(&location) {
+        Ok(index) => index + 1,
+        Err(index) => index
+    };
+
+    cluster_locations
+        .get(next_cluster)
+        .map(|next_location| word.codepoints[*next_location].user_index)
+        .unwrap_or(word.end_user_index)
+}
+
 fn try_layout_word_internal<T: TextLayout + ?Sized>(
-    layout_helper: &T,
+    styles: &[LayoutStyle<T>],
     word: RenderableWord,
     remaining_words: &mut WordsIterator,
-    scale: &Scale,
     options: &TextOptions,
-    pos_y_baseline: f32,
     first_word_on_line: bool,
     previous_metrics: &LineLayoutMetrics,
-    output: &mut FormattedGlyphVec
+    output: Option<&mut FormattedGlyphVec>
 ) -> WordLayoutResult
 {
     let mut new_word_metrics = previous_metrics.clone();
@@ -349,71 +718,102 @@ fn try_layout_word_internal<T: TextLayout + ?Sized>(
 
     let mut glyphs = FormattedGlyphVec::new();
 
-    for (
-        i,
-        Codepoint {
-            user_index,
-            codepoint: c
-        }
-    ) in word.codepoints.iter().enumerate()
-    {
+    let word_glyphs = word_glyphs(styles, &word);
+
+    // The locations at which each cluster of glyphs starts. These are only
+    // needed to position the glyphs, and not when measuring the text.
+    let mut cluster_locations: Vec<usize> = Vec::new();
+
+    if output.is_some() {
+        cluster_locations
+            .extend(word_glyphs.iter().map(|glyph| glyph.codepoint_location));
+        cluster_locations.sort_unstable();
+        cluster_locations.dedup();
+    }
+
+    for (i, word_glyph) in word_glyphs.iter().enumerate() {
         // We can't modify the actual values until we're sure we can render this glyph
         let mut new_glyph_metrics = new_word_metrics.clone();
 
-        let glyph = match layout_helper.lookup_glyph_for_codepoint(*c) {
-            None => {
-                match layout_helper
-                    .lookup_glyph_for_codepoint('□')
-                    .or_else(|| layout_helper.lookup_glyph_for_codepoint('?'))
-                {
-                    None => continue,
-                    Some(glyph) => glyph
-                }
-            }
-            Some(glyph) => glyph
-        };
-
-        let scaled_glyph = glyph.glyph.scaled(*scale);
+        let style = &styles[word_glyph.style];
+        let font_id = word_glyph.glyph.font.id();
+        let scaled_glyph = word_glyph.glyph.glyph.clone().scaled(style.scale);
+        let shaping = word_glyph.shaping.as_ref();
 
         let glyph_x_pos_start = new_glyph_metrics.update_and_get_render_pos_x(
             &scaled_glyph,
-            glyph.font.id(),
-            scale,
+            font_id,
+            shaping,
+            &style.scale,
             options
         );
 
-        let formatted_glyph = FormattedGlyph {
-            user_index: *user_index,
-            glyph: scaled_glyph.positioned(rusttype::point(glyph_x_pos_start, 0.0)),
-            font_id: glyph.font.id()
-        };
+        let offset = shaping.map(|shaping| shaping.offset).unwrap_or(Vec2::ZERO);
+
+        // When only measuring the text, there's no need to position the glyph
+        let formatted_glyph = output.as_ref().map(|_| FormattedGlyph {
+            user_index: word_glyph.user_index,
+            user_index_end: cluster_end_user_index(
+                &word,
+                &cluster_locations,
+                word_glyph.codepoint_location
+            ),
+            glyph: scaled_glyph
+                .positioned(rusttype::point(glyph_x_pos_start + offset.x, offset.y)),
+            font_id,
+            color: style.color,
+            background_color: style.background_color,
+            // Set by FormattedTextBlock::set_byte_ranges(), if the text was
+            // laid out from a string
+            byte_range: None
+        });
 
         if let Some(pos_x_max) = pos_x_max {
-            if new_glyph_metrics.x_pos > pos_x_max {
-                return if first_word_on_line {
-                    if i == 0 {
-                        // First glyph in word, we should render it even though it goes
-                        // over the boundary
-                        glyphs.push(formatted_glyph);
+            let can_split_word = match options.word_wrap_style {
+                WordWrapStyle::WordBoundary => false,
+                WordWrapStyle::Anywhere => first_word_on_line || i > 0,
+                WordWrapStyle::WordWithCharacterFallback => first_word_on_line
+            };
+
+            // A word which can't be split is allowed to overflow the line,
+            // if it's the first word on the line
+            let must_overflow = first_word_on_line && !can_split_word;
+
+            if new_glyph_metrics.x_pos > pos_x_max && !must_overflow {
+                return if can_split_word {
+                    // The first glyph in the word is rendered even though it
+                    // goes over the boundary
+                    let split_index = if i == 0 {
+                        glyphs.extend(formatted_glyph);
                         new_word_metrics = new_glyph_metrics;
-
-                        // If there are more codepoints, we need to split the word
-                        if word.codepoints.len() > 1 {
-                            remaining_words.add_pending(Word::Renderable(
-                                word.starting_from_codepoint_location(i + 1)
-                            ));
-                        }
+                        1
                     } else {
+                        i
+                    };
+
+                    // If there are more codepoints, we need to split the word
+                    let rendered_location = word_glyphs[..split_index]
+                        .iter()
+                        .map(|glyph| glyph.codepoint_location)
+                        .max()
+                        .unwrap_or(0);
+
+                    let split_location = word_glyphs[split_index..]
+                        .iter()
+                        .map(|glyph| glyph.codepoint_location)
+                        .filter(|location| *location > rendered_location)
+                        .min();
+
+                    if let Some(split_location) = split_location {
                         remaining_words.add_pending(Word::Renderable(
-                            word.starting_from_codepoint_location(i)
+                            word.starting_from_codepoint_location(split_location)
                         ));
                     }
 
-                    glyphs.iter_mut().for_each(|glyph| {
-                        glyph.reposition_y(pos_y_baseline + new_word_metrics.max_ascent);
-                    });
+                    if let Some(output) = output {
+                        output.append(&mut glyphs);
+                    }
 
-                    output.append(&mut glyphs);
                     WordLayoutResult::PartialWord(new_word_metrics)
                 } else {
                     remaining_words.add_pending(Word::Renderable(word));
@@ -422,30 +822,108 @@ fn try_layout_word_internal<T: TextLayout + ?Sized>(
             }
         }
 
-        glyphs.push(formatted_glyph);
+        glyphs.extend(formatted_glyph);
         new_word_metrics = new_glyph_metrics;
     }
 
-    glyphs.iter_mut().for_each(|glyph| {
-        glyph.reposition_y(pos_y_baseline + new_word_metrics.max_ascent);
-    });
-
-    output.append(&mut glyphs);
+    if let Some(output) = output {
+        output.append(&mut glyphs);
+    }
 
     WordLayoutResult::Success(new_word_metrics)
 }
 
+/// Removes glyphs from the end of the line so that it fits within
+/// `max_width` after the ellipsis is appended, and then appends the ellipsis.
+/// Returns the new width of the line.
+///
+/// The glyphs are positioned relative to the baseline, and the ellipsis is
+/// laid out using the first style. If no glyphs need to be removed, the
+/// ellipsis takes `next_user_index`.
+fn truncate_line_internal<T: TextLayout + ?Sized>(
+    styles: &[LayoutStyle<T>],
+    glyphs: &mut FormattedGlyphVec,
+    max_width: f32,
+    ellipsis: &str,
+    next_user_index: Option<UserGlyphIndex>
+) -> f32
+{
+    let ellipsis_codepoints: Vec<char> = ellipsis.nfc().collect();
+
+    let mut ellipsis_line = layout_line_internal(
+        styles,
+        &mut WordsIterator::from(Word::split_words(
+            &Codepoint::from_unindexed_codepoints(&ellipsis_codepoints),
+            0
+        )),
+        &TextOptions::new().with_trim_each_line(false),
+        0.0,
+        true,
+        false,
+        false
+    );
+
+    let max_glyph_end = max_width - ellipsis_line.width;
+
+    let kept_glyphs = glyphs
+        .iter()
+        .take_while(|glyph| glyph.position_x() + glyph.advance_width() <= max_glyph_end)
+        .count();
+
+    // The ellipsis takes the user index of the first glyph it replaces
+    let user_index = glyphs
+        .get(kept_glyphs)
+        .map(|glyph| glyph.user_index)
+        .or(next_user_index);
+
+    // The ellipsis represents all of the glyphs it replaces
+    let user_index_end = glyphs[kept_glyphs..]
+        .iter()
+        .map(|glyph| glyph.user_index_end)
+        .max();
+
+    glyphs.truncate(kept_glyphs);
+
+    let ellipsis_start = glyphs
+        .iter()
+        .map(|glyph| glyph.position_x() + glyph.advance_width())
+        .fold(0.0, crate::numeric::max);
+
+    for glyph in Arc::make_mut(&mut ellipsis_line.glyphs).iter_mut() {
+        glyph.add_offset_x(ellipsis_start);
+        glyph.add_offset_y(-ellipsis_line.ascent);
+
+        if let Some(user_index) = user_index {
+            glyph.user_index = user_index;
+            glyph.user_index_end = user_index_end.unwrap_or(user_index);
+        }
+
+        glyphs.push(glyph.clone());
+    }
+
+    ellipsis_start + ellipsis_line.width
+}
+
 fn layout_line_internal<T: TextLayout + ?Sized>(
-    layout_helper: &T,
+    styles: &[LayoutStyle<T>],
     words: &mut WordsIterator,
-    scale: &Scale,
     options: &TextOptions,
-    pos_y_baseline: f32
+    pos_y_baseline: f32,
+    layout_glyphs: bool,
+    last_line: bool,
+    first_line_of_paragraph: bool
 ) -> FormattedTextLine
 {
     let mut line_metrics = LineLayoutMetrics::new();
     let mut glyphs = SmallVec::new();
 
+    if first_line_of_paragraph {
+        line_metrics.x_pos = options.first_line_indent;
+    }
+
+    // The glyph positions are needed to decide where to truncate the line
+    let layout_glyphs = layout_glyphs || options.truncation.is_some();
+
     let mut first_word_on_line = true;
 
     if options.trim_each_line {
@@ -459,17 +937,29 @@ fn layout_line_internal<T: TextLayout + ?Sized>(
         }
     }
 
-    while let Some(Word::Renderable(word)) = words.next() {
+    let mut newline_user_index = None;
+
+    while let Some(word) = words.next() {
+        let word = match word {
+            Word::Renderable(word) => word,
+            Word::Newline(user_index) => {
+                newline_user_index = Some(user_index);
+                break;
+            }
+        };
+
         let result = try_layout_word_internal(
-            layout_helper,
+            styles,
             word,
             words,
-            scale,
             options,
-            pos_y_baseline,
             first_word_on_line,
             &line_metrics,
-            &mut glyphs
+            if layout_glyphs {
+                Some(&mut glyphs)
+            } else {
+                None
+            }
         );
 
         if let Some(metrics) = result.get_metrics() {
@@ -483,14 +973,53 @@ fn layout_line_internal<T: TextLayout + ?Sized>(
         first_word_on_line = false;
     }
 
-    if glyphs.is_empty() {
-        let empty_metrics = layout_helper.empty_line_vertical_metrics(scale.y);
+    if line_metrics.last_glyph_id.is_none() {
+        // Empty lines aren't indented
+        line_metrics.x_pos = 0.0;
+
+        let empty_metrics = styles[0]
+            .layout_helper
+            .empty_line_vertical_metrics(styles[0].scale.y);
         line_metrics.max_ascent = empty_metrics.ascent;
         line_metrics.min_descent = empty_metrics.descent;
         line_metrics.max_line_gap = empty_metrics.line_gap;
     }
 
-    if let Some(max_width) = options.wrap_words_after_width {
+    // Text remains which didn't fit within the line limit
+    let lines_exhausted = last_line && words.has_next();
+
+    let mut truncated = lines_exhausted;
+
+    if let Some(truncation) = &options.truncation {
+        if line_metrics.x_pos > truncation.width || lines_exhausted {
+            let next_user_index = match words.peek() {
+                Some(Word::Renderable(word)) => word
+                    .codepoints
+                    .first()
+                    .map(|codepoint| codepoint.user_index),
+                _ => None
+            };
+
+            line_metrics.x_pos = truncate_line_internal(
+                styles,
+                &mut glyphs,
+                truncation.width,
+                &truncation.ellipsis,
+                next_user_index
+            );
+
+            truncated = true;
+        }
+    }
+
+    let mut start_x = 0.0;
+
+    if let Some(max_width) = options.wrap_words_after_width.or_else(|| {
+        options
+            .truncation
+            .as_ref()
+            .map(|truncation| truncation.width)
+    }) {
         let offset_x = match options.alignment {
             TextAlignment::Left => None,
             TextAlignment::Center => Some((max_width - line_metrics.x_pos) / 2.0),
@@ -501,6 +1030,17 @@ fn layout_line_internal<T: TextLayout + ?Sized>(
             for glyph in glyphs.iter_mut() {
                 glyph.add_offset_x(offset_x);
             }
+
+            start_x = offset_x;
+        }
+    }
+
+    // The ascent of the line is only known once every glyph has been added
+    for glyph in glyphs.iter_mut() {
+        glyph.add_offset_y(pos_y_baseline + line_metrics.max_ascent);
+
+        if options.pixel_snapping {
+            glyph.round_position();
         }
     }
 
@@ -511,45 +1051,91 @@ fn layout_line_internal<T: TextLayout + ?Sized>(
         height: line_metrics.height(),
         ascent: line_metrics.max_ascent,
         descent: line_metrics.min_descent,
-        line_gap: line_metrics.max_line_gap
+        line_gap: line_metrics.max_line_gap,
+        truncated,
+        start_x,
+        newline_user_index,
+        // Set by layout_multiple_lines_internal(), once the next line starts
+        user_index_range: 0..0
     }
 }
 
+/// Lays out the codepoints into lines. If `layout_glyphs` is false, only the
+/// size of the block is calculated, and the returned block contains no lines.
 fn layout_multiple_lines_internal<T: TextLayout + ?Sized>(
-    layout_helper: &T,
+    styles: &[LayoutStyle<T>],
     codepoints: &[Codepoint],
-    scale: f32,
-    options: TextOptions
+    end_user_index: UserGlyphIndex,
+    options: &TextOptions,
+    layout_glyphs: bool
 ) -> FormattedTextBlock
 {
-    let scale = Scale::uniform(scale);
-
-    let mut iterator = WordsIterator::from(Word::split_words(codepoints));
+    let mut iterator = WordsIterator::from(Word::split_words(codepoints, end_user_index));
 
     let mut pos_y = 0.0;
     let mut lines = SmallVec::new();
+    let mut line_count = 0;
 
     let mut width = 0.0;
+    let mut truncated = false;
+    let mut first_line_of_paragraph = true;
 
     while iterator.has_next() {
-        let line =
-            layout_line_internal(layout_helper, &mut iterator, &scale, &options, pos_y);
+        if options.max_lines == Some(0) {
+            truncated = true;
+            break;
+        }
+
+        let last_line = options
+            .max_lines
+            .map(|max_lines| line_count + 1 >= max_lines)
+            .unwrap_or(false);
+
+        let start_user_index = iterator.peek_user_index().unwrap_or(end_user_index);
+
+        let mut line = layout_line_internal(
+            styles,
+            &mut iterator,
+            options,
+            pos_y,
+            layout_glyphs,
+            last_line,
+            first_line_of_paragraph
+        );
+
+        line.user_index_range =
+            start_user_index..iterator.peek_user_index().unwrap_or(end_user_index);
 
         pos_y += line.height * options.line_spacing_multiplier;
 
-        if iterator.has_next() {
+        first_line_of_paragraph = line.newline_user_index.is_some();
+
+        if iterator.has_next() && !last_line {
             pos_y += line.line_gap * options.line_spacing_multiplier;
+
+            if first_line_of_paragraph {
+                pos_y += options.paragraph_spacing;
+            }
         }
 
         width = crate::numeric::max(width, line.width);
+        truncated |= line.truncated;
+        line_count += 1;
+
+        if layout_glyphs {
+            lines.push(line);
+        }
 
-        lines.push(line);
+        if last_line {
+            break;
+        }
     }
 
     FormattedTextBlock {
         lines: Arc::new(lines),
         width,
-        height: pos_y
+        height: pos_y,
+        truncated
     }
 }
 
@@ -574,8 +1160,86 @@ impl LineVerticalMetrics
     }
 }
 
+/// The vertical metrics of a font at a particular size, as encoded in the font
+/// tables. These do not depend on which characters are being rendered.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontMetrics
+{
+    ascent: f32,
+    descent: f32,
+    line_gap: f32,
+    cap_height: f32,
+    x_height: f32
+}
+
+impl FontMetrics
+{
+    /// The ascent of the font in pixels. This is the distance from the
+    /// baseline to the top of the tallest glyphs.
+    #[inline]
+    #[must_use]
+    pub fn ascent(&self) -> f32
+    {
+        self.ascent
+    }
+
+    /// The descent of the font in pixels. This is the furthest distance of
+    /// each glyph below the text baseline.
+    ///
+    /// This is negative: a value of `-10.0` means the font can descend `10`
+    /// pixels below the baseline.
+    #[inline]
+    #[must_use]
+    pub fn descent(&self) -> f32
+    {
+        self.descent
+    }
+
+    /// The recommended gap to put between each line of text, as encoded by the
+    /// font authors.
+    #[inline]
+    #[must_use]
+    pub fn line_gap(&self) -> f32
+    {
+        self.line_gap
+    }
+
+    /// The height of capital letters above the baseline, in pixels. This is
+    /// useful for visually centering a line of text, for example in a button.
+    #[inline]
+    #[must_use]
+    pub fn cap_height(&self) -> f32
+    {
+        self.cap_height
+    }
+
+    /// The height of lowercase letters (such as `x`) above the baseline, in
+    /// pixels.
+    #[inline]
+    #[must_use]
+    pub fn x_height(&self) -> f32
+    {
+        self.x_height
+    }
+
+    /// The distance in pixels between the baselines of two consecutive lines
+    /// of text. This is equal to `ascent() - descent() + line_gap()`.
+    #[inline]
+    #[must_use]
+    pub fn line_height(&self) -> f32
+    {
+        self.ascent - self.descent + self.line_gap
+    }
+}
+
 /// Objects implementing this trait are able to lay out text, ready for
 /// rendering.
+///
+/// If the `text-shaping` feature is enabled, each word is shaped before it's
+/// laid out, so that ligatures, contextual forms (such as Arabic joining),
+/// and mark positioning are applied. Lines are always laid out from left to
+/// right: the characters within a right-to-left word are displayed in the
+/// correct order, but the order of the words themselves is not changed.
 pub trait TextLayout
 {
     /// Returns the glyph corresponding to the provided codepoint. If the glyph
@@ -590,6 +1254,9 @@ pub trait TextLayout
     /// over the `user_index` field, consider using
     /// either `layout_text_line_from_codepoints()` or
     /// `layout_text_line_from_unindexed_codepoints()`.
+    ///
+    /// The range of bytes in `text` which each glyph represents is available
+    /// using [FormattedGlyph::byte_range].
     #[inline]
     #[must_use]
     fn layout_text(
@@ -599,8 +1266,13 @@ pub trait TextLayout
         options: TextOptions
     ) -> FormattedTextBlock
     {
-        let codepoints: Vec<char> = text.nfc().collect();
-        self.layout_text_from_unindexed_codepoints(codepoints.as_slice(), scale, options)
+        let mut byte_offsets = Vec::with_capacity(text.len());
+        let codepoints =
+            Codepoint::from_str_with_byte_offsets(text, 0, &mut byte_offsets);
+
+        let mut block = self.layout_text_from_codepoints(&codepoints, scale, options);
+        block.set_byte_ranges(&byte_offsets, text.len());
+        block
     }
 
     /// Lays out a block of text with the specified scale and options. The
@@ -638,7 +1310,44 @@ pub trait TextLayout
         options: TextOptions
     ) -> FormattedTextBlock
     {
-        layout_multiple_lines_internal(self, codepoints, scale, options)
+        let end_user_index = codepoints
+            .last()
+            .map(|codepoint| codepoint.user_index + 1)
+            .unwrap_or(0);
+
+        layout_multiple_lines_internal(
+            &[LayoutStyle::new(self, scale)],
+            codepoints,
+            end_user_index,
+            &options,
+            true
+        )
+    }
+
+    /// Calculates the size of the block of text which would be produced by
+    /// `layout_text()`, with the same scale and options, without laying out
+    /// each glyph.
+    ///
+    /// This is cheaper than calling `layout_text()` and discarding the result,
+    /// and is useful when the dimensions of the text are needed (for example,
+    /// when reflowing a user interface) but the text won't be drawn.
+    #[must_use]
+    fn measure_text(&self, text: &str, scale: f32, options: &TextOptions) -> Vec2
+    {
+        // The user indices aren't needed, as the glyphs won't be positioned
+        let codepoints: Vec<Codepoint> = text
+            .nfc()
+            .map(|codepoint| Codepoint::new(0, codepoint))
+            .collect();
+
+        layout_multiple_lines_internal(
+            &[LayoutStyle::new(self, scale)],
+            &codepoints,
+            0,
+            options,
+            false
+        )
+        .size()
     }
 
     /// The default metrics of a line which contains no characters.
@@ -646,6 +1355,19 @@ pub trait TextLayout
     fn empty_line_vertical_metrics(&self, scale: f32) -> LineVerticalMetrics;
 }
 
+/// Returns the parsed font tables of the specified font.
+pub(crate) fn font_face<'a>(
+    font: &'a rusttype::Font<'_>
+) -> &'a owned_ttf_parser::Face<'a>
+{
+    use owned_ttf_parser::AsFaceRef;
+
+    match font {
+        rusttype::Font::Ref(face) => face,
+        rusttype::Font::Owned(face) => face.as_face_ref()
+    }
+}
+
 /// A struct representing a font.
 #[derive(Clone)]
 pub struct Font
@@ -660,15 +1382,248 @@ impl Font
     ///
     /// The font may be in TrueType or OpenType format. Support for OpenType
     /// fonts may be limited.
+    ///
+    /// If the `image-loading` feature is enabled, color bitmap glyphs (such as
+    /// emoji in fonts using the `sbix` or `CBDT` tables) are drawn in their
+    /// own colors, rather than in the color passed to
+    /// [crate::Graphics2D::draw_text]. Color glyphs using the `COLR` or `SVG`
+    /// tables are not supported.
     pub fn new(bytes: &[u8]) -> Result<Font, BacktraceError<ErrorMessage>>
     {
         let font = rusttype::Font::try_from_vec(bytes.to_vec())
             .ok_or_else(|| ErrorMessage::msg("Failed to load font"))?;
 
-        Ok(Font {
-            id: FONT_ID_GENERATOR.fetch_add(1, Ordering::SeqCst),
-            font: Arc::new(font)
-        })
+        Ok(Font::from_rusttype(font))
+    }
+
+    /// Constructs a new instance of a variable font from the specified bytes,
+    /// with each variation axis set to the specified value. Axes are
+    /// identified by their four-character tag, for example `"wght"` (weight)
+    /// or `"wdth"` (width), and any axes which aren't specified keep their
+    /// default value.
+    ///
+    /// Values outside the range supported by the font are clamped. An error
+    /// is returned if the font doesn't have one of the specified axes.
+    ///
+    /// ```rust,no_run
+    /// use speedy2d::font::Font;
+    ///
+    /// let bytes = std::fs::read("MyVariableFont.ttf").unwrap();
+    /// let font = Font::new_with_variations(&bytes, &[("wght", 650.0), ("wdth", 90.0)])
+    ///     .unwrap();
+    /// ```
+    pub fn new_with_variations(
+        bytes: &[u8],
+        variations: &[(&str, f32)]
+    ) -> Result<Font, BacktraceError<ErrorMessage>>
+    {
+        use owned_ttf_parser::FaceMut;
+
+        let mut face = owned_ttf_parser::OwnedFace::from_vec(bytes.to_vec(), 0)
+            .map_err(|err| ErrorMessage::msg_with_cause("Failed to load font", err))?;
+
+        for (axis, value) in variations {
+            let tag: [u8; 4] = axis.as_bytes().try_into().map_err(|_| {
+                ErrorMessage::msg(format!("Invalid variation axis tag: {}", axis))
+            })?;
+
+            face.set_variation(owned_ttf_parser::Tag::from_bytes(&tag), *value)
+                .ok_or_else(|| {
+                    ErrorMessage::msg(format!("Font has no variation axis: {}", axis))
+                })?;
+        }
+
+        Ok(Font::from_rusttype(rusttype::Font::Owned(Arc::new(face))))
+    }
+
+    /// Loads a font which is installed on the system, using its family name
+    /// (for example `"Segoe UI"` or `"DejaVu Sans"`). If the family doesn't
+    /// have a face with exactly the specified weight and style, the closest
+    /// match is used. This requires the `system-fonts` feature.
+    ///
+    /// The installed fonts are scanned each time this is called, which may
+    /// take some time, so it's best to load each font once and then re-use
+    /// it.
+    ///
+    /// ```rust,no_run
+    /// use speedy2d::font::{Font, FontStyle, FontWeight};
+    ///
+    /// let font = Font::new_from_system("Segoe UI", FontWeight::BOLD, FontStyle::Normal)
+    ///     .unwrap();
+    /// ```
+    #[cfg(any(
+        doc,
+        doctest,
+        all(feature = "system-fonts", not(target_arch = "wasm32"))
+    ))]
+    pub fn new_from_system(
+        family: &str,
+        weight: FontWeight,
+        style: FontStyle
+    ) -> Result<Font, BacktraceError<ErrorMessage>>
+    {
+        let mut database = fontdb::Database::new();
+        database.load_system_fonts();
+
+        let query = fontdb::Query {
+            families: &[fontdb::Family::Name(family)],
+            weight: fontdb::Weight(weight.value()),
+            stretch: fontdb::Stretch::Normal,
+            style: match style {
+                FontStyle::Normal => fontdb::Style::Normal,
+                FontStyle::Italic => fontdb::Style::Italic,
+                FontStyle::Oblique => fontdb::Style::Oblique
+            }
+        };
+
+        let id = database.query(&query).ok_or_else(|| {
+            ErrorMessage::msg(format!("System font family not found: {}", family))
+        })?;
+
+        let font = database
+            .with_face_data(id, |data, index| {
+                rusttype::Font::try_from_vec_and_index(data.to_vec(), index)
+            })
+            .flatten()
+            .ok_or_else(|| ErrorMessage::msg("Failed to load font"))?;
+
+        Ok(Font::from_rusttype(font))
+    }
+
+    fn from_rusttype(font: rusttype::Font<'static>) -> Self
+    {
+        Font {
+            id: FONT_ID_GENERATOR.fetch_add(1, Ordering::SeqCst),
+            font: Arc::new(font)
+        }
+    }
+
+    /// Returns the vertical metrics of this font at the specified scale (in
+    /// pixels). This allows the height of a line of text to be determined
+    /// without laying out any text.
+    #[must_use]
+    pub fn metrics(&self, scale: f32) -> FontMetrics
+    {
+        let metrics = self.font.v_metrics(Scale::uniform(scale));
+        let face = font_face(&self.font);
+        let units_to_pixels = self.font.scale_for_pixel_height(scale);
+
+        // Older fonts may not specify these heights, in which case they're
+        // measured from the glyphs themselves
+        let cap_height = match face.capital_height() {
+            Some(height) => f32::from(height) * units_to_pixels,
+            None => self.glyph_height('H', scale)
+        };
+
+        let x_height = match face.x_height() {
+            Some(height) => f32::from(height) * units_to_pixels,
+            None => self.glyph_height('x', scale)
+        };
+
+        FontMetrics {
+            ascent: metrics.ascent,
+            descent: metrics.descent,
+            line_gap: metrics.line_gap,
+            cap_height,
+            x_height
+        }
+    }
+
+    /// Returns true if this font contains a glyph for the specified
+    /// codepoint. If it doesn't, the codepoint is drawn using the font's
+    /// replacement glyph, unless a [FontFamily] is used to fall back to
+    /// another font.
+    #[must_use]
+    pub fn has_glyph(&self, codepoint: char) -> bool
+    {
+        self.font.glyph(codepoint).id().0 != 0
+    }
+
+    /// Returns the horizontal advance (in pixels) of the glyph for the
+    /// specified codepoint at the specified scale. This does not take into
+    /// account kerning or tracking.
+    ///
+    /// If the font doesn't contain the codepoint, the advance of the font's
+    /// fallback glyph is returned.
+    #[must_use]
+    pub fn advance_width(&self, codepoint: char, scale: f32) -> f32
+    {
+        self.font
+            .glyph(codepoint)
+            .scaled(Scale::uniform(scale))
+            .h_metrics()
+            .advance_width
+    }
+
+    /// Returns the outline of the glyph for the specified codepoint at the
+    /// specified scale (in pixels), as a path which can be filled, stroked,
+    /// or otherwise manipulated as vector geometry.
+    ///
+    /// The path is relative to the glyph's origin on the baseline, with `y`
+    /// increasing downwards, so it can be drawn at the same position as the
+    /// corresponding [FormattedGlyph]. Curves are approximated by straight
+    /// line segments.
+    ///
+    /// Returns `None` if the font doesn't contain the codepoint, or if the
+    /// glyph has no outline (for example, a space).
+    #[must_use]
+    pub fn glyph_outline(&self, codepoint: char, scale: f32) -> Option<Path>
+    {
+        let glyph = self.font.glyph(codepoint);
+
+        if glyph.id().0 == 0 {
+            return None;
+        }
+
+        Self::build_glyph_outline(glyph, scale)
+    }
+
+    /// Returns the outline of the glyph with the specified identifier at the
+    /// specified scale (in pixels). This is useful when text has been shaped,
+    /// in which case glyphs may not correspond to a single codepoint. The
+    /// identifier of a laid out glyph can be obtained using
+    /// [FormattedGlyph::glyph_id].
+    ///
+    /// See [Font::glyph_outline] for more details. Returns `None` if the
+    /// identifier isn't valid for this font, or if the glyph has no outline.
+    #[must_use]
+    pub fn glyph_outline_by_id(&self, glyph_id: u16, scale: f32) -> Option<Path>
+    {
+        if usize::from(glyph_id) >= self.font.glyph_count() {
+            return None;
+        }
+
+        Self::build_glyph_outline(self.font.glyph(rusttype::GlyphId(glyph_id)), scale)
+    }
+
+    fn build_glyph_outline(glyph: rusttype::Glyph<'static>, scale: f32) -> Option<Path>
+    {
+        let mut builder = PathOutlineBuilder { path: Path::new() };
+
+        if glyph
+            .scaled(Scale::uniform(scale))
+            .build_outline(&mut builder)
+        {
+            Some(builder.path)
+        } else {
+            None
+        }
+    }
+
+    /// The height of the specified glyph above the baseline, or zero if the
+    /// font doesn't contain it.
+    fn glyph_height(&self, codepoint: char, scale: f32) -> f32
+    {
+        if !self.has_glyph(codepoint) {
+            return 0.0;
+        }
+
+        self.font
+            .glyph(codepoint)
+            .scaled(Scale::uniform(scale))
+            .exact_bounding_box()
+            .map(|bounds| -bounds.min.y)
+            .unwrap_or(0.0)
     }
 
     #[inline]
@@ -684,6 +1639,39 @@ impl Font
     }
 }
 
+struct PathOutlineBuilder
+{
+    path: Path
+}
+
+impl rusttype::OutlineBuilder for PathOutlineBuilder
+{
+    fn move_to(&mut self, x: f32, y: f32)
+    {
+        self.path = std::mem::take(&mut self.path).move_to((x, y));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32)
+    {
+        self.path = std::mem::take(&mut self.path).line_to((x, y));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32)
+    {
+        self.path = std::mem::take(&mut self.path).quad_to((x1, y1), (x, y));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32)
+    {
+        self.path = std::mem::take(&mut self.path).cubic_to((x1, y1), (x2, y2), (x, y));
+    }
+
+    fn close(&mut self)
+    {
+        self.path = std::mem::take(&mut self.path).close();
+    }
+}
+
 impl TextLayout for FontFamily
 {
     fn lookup_glyph_for_codepoint(&self, codepoint: char) -> Option<FontGlyph>
@@ -772,6 +1760,71 @@ impl Debug for Font
     }
 }
 
+/// The weight of a font, from `1` (the thinnest) to `1000` (the thickest). The
+/// usual weight of body text is [FontWeight::NORMAL].
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct FontWeight(u16);
+
+impl FontWeight
+{
+    /// Thin weight (100).
+    pub const THIN: FontWeight = FontWeight(100);
+    /// Extra light weight (200).
+    pub const EXTRA_LIGHT: FontWeight = FontWeight(200);
+    /// Light weight (300).
+    pub const LIGHT: FontWeight = FontWeight(300);
+    /// Normal weight (400).
+    pub const NORMAL: FontWeight = FontWeight(400);
+    /// Medium weight (500).
+    pub const MEDIUM: FontWeight = FontWeight(500);
+    /// Semi-bold weight (600).
+    pub const SEMI_BOLD: FontWeight = FontWeight(600);
+    /// Bold weight (700).
+    pub const BOLD: FontWeight = FontWeight(700);
+    /// Extra bold weight (800).
+    pub const EXTRA_BOLD: FontWeight = FontWeight(800);
+    /// Black weight (900).
+    pub const BLACK: FontWeight = FontWeight(900);
+
+    /// Creates a font weight with the specified value, which is clamped to the
+    /// range `1` to `1000`.
+    #[inline]
+    #[must_use]
+    pub fn new(value: u16) -> Self
+    {
+        FontWeight(value.clamp(1, 1000))
+    }
+
+    /// Returns the numeric value of this weight.
+    #[inline]
+    #[must_use]
+    pub fn value(&self) -> u16
+    {
+        self.0
+    }
+}
+
+impl Default for FontWeight
+{
+    fn default() -> Self
+    {
+        FontWeight::NORMAL
+    }
+}
+
+/// Whether a font is upright, or slanted.
+#[derive(Debug, Default, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum FontStyle
+{
+    /// An upright face.
+    #[default]
+    Normal,
+    /// A slanted face, designed with a cursive style.
+    Italic,
+    /// A slanted version of the upright face.
+    Oblique
+}
+
 /// A collection of fonts, in decreasing order of priority. When laying out
 /// text, if a codepoint cannot be found in the first font in the list, the
 /// subsequent fonts will also be searched.
@@ -807,14 +1860,44 @@ pub enum TextAlignment
     Right
 }
 
+/// The places at which a line of text may be broken when it's wrapped. This
+/// can be set using `TextOptions::with_word_wrap_style`.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub enum WordWrapStyle
+{
+    /// Only break lines between words. A word which is too long to fit on a
+    /// line by itself overflows the wrap width.
+    WordBoundary,
+    /// Break lines at the last character which fits, even within a word.
+    Anywhere,
+    /// Break lines between words where possible. A word which is too long to
+    /// fit on a line by itself (such as a URL or a long hash) is broken at
+    /// the last character which fits.
+    WordWithCharacterFallback
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct TextTruncation
+{
+    width: f32,
+    ellipsis: String
+}
+
 /// A series of options for specifying how text should be laid out.
+#[derive(Debug, Clone, PartialEq)]
 pub struct TextOptions
 {
     tracking: f32,
     wrap_words_after_width: Option<f32>,
+    word_wrap_style: WordWrapStyle,
+    truncation: Option<TextTruncation>,
+    max_lines: Option<usize>,
     alignment: TextAlignment,
     line_spacing_multiplier: f32,
-    trim_each_line: bool
+    trim_each_line: bool,
+    pixel_snapping: bool,
+    first_line_indent: f32,
+    paragraph_spacing: f32
 }
 
 impl TextOptions
@@ -827,9 +1910,15 @@ impl TextOptions
         TextOptions {
             tracking: 0.0,
             wrap_words_after_width: None,
+            word_wrap_style: WordWrapStyle::WordWithCharacterFallback,
+            truncation: None,
+            max_lines: None,
             alignment: TextAlignment::Left,
             line_spacing_multiplier: 1.0,
-            trim_each_line: true
+            trim_each_line: true,
+            pixel_snapping: false,
+            first_line_indent: 0.0,
+            paragraph_spacing: 0.0
         }
     }
 
@@ -864,6 +1953,54 @@ impl TextOptions
         self
     }
 
+    /// Sets where lines may be broken when wrapping is enabled using
+    /// [TextOptions::with_wrap_to_width].
+    ///
+    /// The default is [WordWrapStyle::WordWithCharacterFallback].
+    #[inline]
+    #[must_use]
+    pub fn with_word_wrap_style(mut self, word_wrap_style: WordWrapStyle) -> Self
+    {
+        self.word_wrap_style = word_wrap_style;
+        self
+    }
+
+    /// Limits the width of each line of text to the specified pixel value,
+    /// cutting lines short and appending `ellipsis` (for example `"…"`) if
+    /// they exceed that limit.
+    ///
+    /// If wrapping is also enabled using [TextOptions::with_wrap_to_width],
+    /// lines are wrapped rather than cut short, and the ellipsis is appended
+    /// to the last line when text is left out by [TextOptions::with_max_lines].
+    ///
+    /// The default is to not truncate text.
+    #[inline]
+    #[must_use]
+    pub fn with_truncate_to_width(mut self, width_px: f32, ellipsis: &str) -> Self
+    {
+        self.truncation = Some(TextTruncation {
+            width: width_px,
+            ellipsis: ellipsis.to_string()
+        });
+        self
+    }
+
+    /// Stops laying out text after the specified number of lines. Any
+    /// remaining text is left out, and [FormattedTextBlock::is_truncated]
+    /// will return `true`.
+    ///
+    /// If [TextOptions::with_truncate_to_width] is also set, the ellipsis is
+    /// appended to the last line when text is left out.
+    ///
+    /// The default is to not limit the number of lines.
+    #[inline]
+    #[must_use]
+    pub fn with_max_lines(mut self, max_lines: usize) -> Self
+    {
+        self.max_lines = Some(max_lines);
+        self
+    }
+
     /// Sets the amount of space between each line of text. The gap between the
     /// baseline of each line of text is multiplied by this value.
     ///
@@ -876,6 +2013,33 @@ impl TextOptions
         self
     }
 
+    /// Sets the amount of space (in pixels) to insert at the start of the
+    /// first line of each paragraph. A new paragraph starts after each `\n`
+    /// character in the text.
+    ///
+    /// The default is `0.0`.
+    #[inline]
+    #[must_use]
+    pub fn with_first_line_indent(mut self, first_line_indent_px: f32) -> Self
+    {
+        self.first_line_indent = first_line_indent_px;
+        self
+    }
+
+    /// Sets the amount of extra space (in pixels) to insert between each
+    /// paragraph. A new paragraph starts after each `\n` character in the
+    /// text. Unlike [TextOptions::with_line_spacing_multiplier], this doesn't
+    /// affect the space between lines which have been wrapped.
+    ///
+    /// The default is `0.0`.
+    #[inline]
+    #[must_use]
+    pub fn with_paragraph_spacing(mut self, paragraph_spacing_px: f32) -> Self
+    {
+        self.paragraph_spacing = paragraph_spacing_px;
+        self
+    }
+
     /// True if whitespace should be trimmed at the beginning of each line,
     /// false to preserve whitespace.
     ///
@@ -887,6 +2051,21 @@ impl TextOptions
         self.trim_each_line = trim_each_line;
         self
     }
+
+    /// True if the position of each glyph should be rounded to a whole number
+    /// of pixels. This makes small text look sharper, at the cost of less
+    /// even spacing between characters. For the glyphs to line up with the
+    /// pixel grid, the text must also be drawn at a whole number position.
+    ///
+    /// The default is `false`, which places each glyph with subpixel
+    /// precision.
+    #[inline]
+    #[must_use]
+    pub fn with_pixel_snapping(mut self, pixel_snapping: bool) -> Self
+    {
+        self.pixel_snapping = pixel_snapping;
+        self
+    }
 }
 
 impl Default for TextOptions
@@ -897,119 +2076,586 @@ impl Default for TextOptions
     }
 }
 
-/// Represents a glyph which has been laid out as part of a line of text.
-#[derive(Clone)]
-pub struct FormattedGlyph
+/// A shadow which is drawn behind text, using
+/// [crate::Graphics2D::draw_text_with_shadow].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextShadow
 {
-    glyph: rusttype::PositionedGlyph<'static>,
-    font_id: FontId,
-    user_index: UserGlyphIndex
+    offset: Vec2,
+    blur_radius: f32,
+    color: Color
 }
 
-impl FormattedGlyph
+impl TextShadow
 {
+    /// Instantiates a new shadow, which is drawn `offset` pixels away from
+    /// the text, in the specified color.
+    ///
+    /// The shadow is blurred by `blur_radius` pixels, so that it fades out
+    /// smoothly. A radius of `0.0` gives the shadow a sharp edge.
     #[inline]
     #[must_use]
-    pub(crate) fn glyph(&self) -> &rusttype::PositionedGlyph<'static>
+    pub fn new<V: Into<Vec2>>(offset: V, blur_radius: f32, color: Color) -> Self
     {
-        &self.glyph
+        TextShadow {
+            offset: offset.into(),
+            blur_radius,
+            color
+        }
     }
 
-    /// The identifier of the font which was used to render this glyph.
+    /// The offset of the shadow from the text, in pixels.
     #[inline]
     #[must_use]
-    pub fn font_id(&self) -> FontId
+    pub fn offset(&self) -> Vec2
     {
-        self.font_id
+        self.offset
     }
 
-    /// The `user_index` of the corresponding `Codepoint`. This allows you to
-    /// identify which input `Codepoint` corresponds to the output
-    /// `FormattedGlyph`.
+    /// The radius of the blur applied to the shadow, in pixels.
     #[inline]
     #[must_use]
-    pub fn user_index(&self) -> UserGlyphIndex
+    pub fn blur_radius(&self) -> f32
     {
-        self.user_index
+        self.blur_radius
     }
 
-    /// The `x` coordinate of this glyph, relative to the start of the line
+    /// The color of the shadow.
     #[inline]
     #[must_use]
-    pub fn position_x(&self) -> f32
+    pub fn color(&self) -> Color
     {
-        self.glyph.position().x
+        self.color
     }
+}
 
-    /// The character's advance width. In the absence of any kerning
-    /// information, this would represent the horizontal distance between
-    /// the position of this character, and the position of the next
-    /// character.
-    #[inline]
+/// A run of text within a [RichText], which is laid out with a single font,
+/// size, and color.
+#[derive(Clone)]
+pub struct TextSpan
+{
+    text: String,
+    font: Arc<dyn TextLayout + Send + Sync>,
+    scale: f32,
+    color: Option<Color>,
+    background_color: Option<Color>
+}
+
+impl TextSpan
+{
+    /// Instantiates a new span containing the specified text, which will be
+    /// laid out using `font` (for example a [Font] or [FontFamily]) at the
+    /// specified scale.
     #[must_use]
-    pub fn advance_width(&self) -> f32
+    pub fn new<T>(text: &str, font: &T, scale: f32) -> Self
+    where
+        T: TextLayout + Clone + Send + Sync + 'static
     {
-        self.glyph.unpositioned().h_metrics().advance_width
+        TextSpan {
+            text: text.to_string(),
+            font: Arc::new(font.clone()),
+            scale,
+            color: None,
+            background_color: None
+        }
     }
 
-    /// The bounding box of this glyph in pixels. This encloses the
-    /// total renderable area of the glyph.
+    /// Sets the color of this span. This overrides the color passed to
+    /// [crate::Graphics2D::draw_text].
     ///
-    /// Some glyphs, such as a space, might not render anything at all -- in
-    /// this case, this function will return `None`.
+    /// The default is to use the color passed to
+    /// [crate::Graphics2D::draw_text].
     #[inline]
     #[must_use]
-    pub fn pixel_bounding_box(&self) -> Option<Rect>
-    {
-        self.glyph.pixel_bounding_box().map(|r| {
-            Rect::from_tuples(
-                (r.min.x as f32, r.min.y as f32),
-                (r.max.x as f32, r.max.y as f32)
-            )
-        })
-    }
-
-    #[inline]
-    fn reposition_y(&mut self, y_pos: f32)
+    pub fn with_color(mut self, color: Color) -> Self
     {
-        let existing_pos = self.glyph.position();
-        self.glyph
-            .set_position(rusttype::point(existing_pos.x, y_pos));
+        self.color = Some(color);
+        self
     }
 
+    /// Sets a color to fill the area behind this span, like a highlighter.
+    /// The background spans the full height of each line containing the
+    /// span, and is drawn behind all the text in the block.
+    ///
+    /// The default is to not draw a background.
     #[inline]
-    fn add_offset_x(&mut self, offset_x: f32)
+    #[must_use]
+    pub fn with_background_color(mut self, background_color: Color) -> Self
     {
-        let existing_pos = self.glyph.position();
-        self.glyph
-            .set_position(rusttype::point(existing_pos.x + offset_x, existing_pos.y));
+        self.background_color = Some(background_color);
+        self
     }
 }
 
-/// Represents a block of text which has been laid out.
-#[derive(Clone)]
-pub struct FormattedTextBlock
+/// A block of text made up of several [TextSpan] objects, each of which may
+/// have a different font, size, and color.
+///
+/// The spans are laid out together as a single block, so lines may be wrapped
+/// in the middle of a span, and a word may contain several spans.
+#[derive(Clone, Default)]
+pub struct RichText
 {
-    lines: Arc<FormattedTextLineVec>,
-    width: f32,
-    height: f32
+    spans: Vec<TextSpan>
 }
 
-impl FormattedTextBlock
+impl RichText
 {
-    /// Iterate over the lines of text in this block.
+    /// Instantiates a new `RichText` containing no spans.
     #[inline]
-    pub fn iter_lines(&self) -> Iter<'_, FormattedTextLine>
+    #[must_use]
+    pub fn new() -> Self
     {
-        self.lines.iter()
+        RichText { spans: Vec::new() }
     }
 
-    /// The width (in pixels) of this text block.
+    /// Appends the specified span to the end of the text.
     #[inline]
     #[must_use]
-    pub fn width(&self) -> f32
+    pub fn with_span(mut self, span: TextSpan) -> Self
     {
-        self.width
+        self.spans.push(span);
+        self
+    }
+
+    /// Lays out the text with the specified options. The result may be passed
+    /// to `Graphics2D::draw_text`.
+    ///
+    /// As with [TextLayout::layout_text], the text is normalized, and the
+    /// `user_index` of each `FormattedGlyph` is undefined.
+    /// [FormattedGlyph::byte_range] returns the range of bytes which each
+    /// glyph represents, in the text of all the spans joined together. Empty
+    /// lines, and any ellipsis added using
+    /// [TextOptions::with_truncate_to_width], use the style of the first
+    /// span.
+    #[must_use]
+    pub fn layout(&self, options: TextOptions) -> FormattedTextBlock
+    {
+        self.layout_internal(&options, true)
+    }
+
+    /// Calculates the size of the block of text which would be produced by
+    /// `layout()`, with the same options, without laying out each glyph.
+    #[must_use]
+    pub fn measure(&self, options: &TextOptions) -> Vec2
+    {
+        self.layout_internal(options, false).size()
+    }
+
+    fn layout_internal(
+        &self,
+        options: &TextOptions,
+        layout_glyphs: bool
+    ) -> FormattedTextBlock
+    {
+        let mut styles = Vec::with_capacity(self.spans.len());
+        let mut codepoints = Vec::new();
+        let mut byte_offsets = Vec::new();
+        let mut offset = 0;
+
+        for span in &self.spans {
+            let style = styles.len();
+
+            styles.push(LayoutStyle {
+                layout_helper: &*span.font,
+                scale: Scale::uniform(span.scale),
+                color: span.color,
+                background_color: span.background_color
+            });
+
+            let first_byte_offset = byte_offsets.len();
+
+            codepoints.extend(
+                Codepoint::from_str_with_byte_offsets(
+                    &span.text,
+                    codepoints.len().try_into().unwrap(),
+                    &mut byte_offsets
+                )
+                .into_iter()
+                .map(|codepoint| Codepoint { style, ..codepoint })
+            );
+
+            for byte_offset in &mut byte_offsets[first_byte_offset..] {
+                *byte_offset += offset;
+            }
+
+            offset += span.text.len();
+        }
+
+        if styles.is_empty() {
+            return FormattedTextBlock {
+                lines: Arc::new(SmallVec::new()),
+                width: 0.0,
+                height: 0.0,
+                truncated: false
+            };
+        }
+
+        let mut block = layout_multiple_lines_internal(
+            &styles,
+            &codepoints,
+            codepoints.len().try_into().unwrap(),
+            options,
+            layout_glyphs
+        );
+
+        block.set_byte_ranges(&byte_offsets, offset);
+        block
+    }
+}
+
+/// Represents a glyph which has been laid out as part of a line of text.
+#[derive(Clone)]
+pub struct FormattedGlyph
+{
+    glyph: rusttype::PositionedGlyph<'static>,
+    font_id: FontId,
+    user_index: UserGlyphIndex,
+    user_index_end: UserGlyphIndex,
+    color: Option<Color>,
+    background_color: Option<Color>,
+    byte_range: Option<Range<usize>>
+}
+
+impl FormattedGlyph
+{
+    #[inline]
+    #[must_use]
+    pub(crate) fn glyph(&self) -> &rusttype::PositionedGlyph<'static>
+    {
+        &self.glyph
+    }
+
+    /// The identifier of the font which was used to render this glyph.
+    #[inline]
+    #[must_use]
+    pub fn font_id(&self) -> FontId
+    {
+        self.font_id
+    }
+
+    /// The identifier of this glyph within its font. The outline of the glyph
+    /// can be obtained by passing this to [Font::glyph_outline_by_id].
+    #[inline]
+    #[must_use]
+    pub fn glyph_id(&self) -> u16
+    {
+        self.glyph.id().0
+    }
+
+    /// The color of this glyph, if it was set using [TextSpan::with_color].
+    /// Otherwise, the glyph is drawn in the color passed to
+    /// [crate::Graphics2D::draw_text].
+    #[inline]
+    #[must_use]
+    pub fn color(&self) -> Option<Color>
+    {
+        self.color
+    }
+
+    /// The background color of this glyph, if it was set using
+    /// [TextSpan::with_background_color].
+    #[inline]
+    #[must_use]
+    pub fn background_color(&self) -> Option<Color>
+    {
+        self.background_color
+    }
+
+    /// The `user_index` of the corresponding `Codepoint`. This allows you to
+    /// identify which input `Codepoint` corresponds to the output
+    /// `FormattedGlyph`.
+    #[inline]
+    #[must_use]
+    pub fn user_index(&self) -> UserGlyphIndex
+    {
+        self.user_index
+    }
+
+    /// The range of `user_index` values represented by this glyph. This
+    /// starts at the glyph's `user_index`, and ends at the `user_index` of
+    /// the next input `Codepoint` which isn't part of the same glyph (or one
+    /// past the last `user_index`, at the end of the input).
+    ///
+    /// More than one codepoint may be represented by a single glyph, for
+    /// example when a ligature is formed.
+    #[inline]
+    #[must_use]
+    pub fn user_index_range(&self) -> Range<UserGlyphIndex>
+    {
+        self.user_index..self.user_index_end
+    }
+
+    /// The range of bytes in the source string which this glyph represents,
+    /// if the text was laid out from a string using
+    /// [TextLayout::layout_text] or [RichText::layout]. Returns `None` if the
+    /// text was laid out from codepoints.
+    ///
+    /// An ellipsis added using [TextOptions::with_truncate_to_width]
+    /// represents all of the text it replaces.
+    #[inline]
+    #[must_use]
+    pub fn byte_range(&self) -> Option<Range<usize>>
+    {
+        self.byte_range.clone()
+    }
+
+    /// The `x` coordinate of this glyph, relative to the start of the line
+    #[inline]
+    #[must_use]
+    pub fn position_x(&self) -> f32
+    {
+        self.glyph.position().x
+    }
+
+    /// The position of this glyph's origin on the baseline, relative to the
+    /// top left of the text block.
+    #[inline]
+    #[must_use]
+    pub fn position(&self) -> Vec2
+    {
+        let position = self.glyph.position();
+        Vec2::new(position.x, position.y)
+    }
+
+    /// The character's advance width. In the absence of any kerning
+    /// information, this would represent the horizontal distance between
+    /// the position of this character, and the position of the next
+    /// character.
+    #[inline]
+    #[must_use]
+    pub fn advance_width(&self) -> f32
+    {
+        self.glyph.unpositioned().h_metrics().advance_width
+    }
+
+    /// The bounding box of this glyph in pixels. This encloses the
+    /// total renderable area of the glyph.
+    ///
+    /// Some glyphs, such as a space, might not render anything at all -- in
+    /// this case, this function will return `None`.
+    #[inline]
+    #[must_use]
+    pub fn pixel_bounding_box(&self) -> Option<Rect>
+    {
+        self.glyph.pixel_bounding_box().map(|r| {
+            Rect::from_tuples(
+                (r.min.x as f32, r.min.y as f32),
+                (r.max.x as f32, r.max.y as f32)
+            )
+        })
+    }
+
+    #[inline]
+    fn add_offset_y(&mut self, offset_y: f32)
+    {
+        let existing_pos = self.glyph.position();
+        self.glyph
+            .set_position(rusttype::point(existing_pos.x, existing_pos.y + offset_y));
+    }
+
+    #[inline]
+    fn add_offset_x(&mut self, offset_x: f32)
+    {
+        let existing_pos = self.glyph.position();
+        self.glyph
+            .set_position(rusttype::point(existing_pos.x + offset_x, existing_pos.y));
+    }
+
+    #[inline]
+    fn round_position(&mut self)
+    {
+        let existing_pos = self.glyph.position();
+        self.glyph.set_position(rusttype::point(
+            existing_pos.x.round(),
+            existing_pos.y.round()
+        ));
+    }
+}
+
+/// Represents a block of text which has been laid out.
+#[derive(Clone)]
+pub struct FormattedTextBlock
+{
+    lines: Arc<FormattedTextLineVec>,
+    width: f32,
+    height: f32,
+    truncated: bool
+}
+
+impl FormattedTextBlock
+{
+    /// Iterate over the lines of text in this block.
+    #[inline]
+    pub fn iter_lines(&self) -> Iter<'_, FormattedTextLine>
+    {
+        self.lines.iter()
+    }
+
+    /// Returns the `user_index` of the caret position closest to the
+    /// specified point, which is relative to the top left of the text block.
+    ///
+    /// Points above or below the text are treated as being in the first or
+    /// last line respectively. The caret may be placed before any glyph, or
+    /// at the end of a line.
+    ///
+    /// Returns `None` if the block contains no text.
+    #[must_use]
+    pub fn char_index_at_position(&self, position: Vec2) -> Option<UserGlyphIndex>
+    {
+        let line = self
+            .lines
+            .iter()
+            .find(|line| position.y < line.baseline_vertical_position + line.height)
+            .or_else(|| self.lines.last())?;
+
+        line.caret_stops()
+            .into_iter()
+            .min_by(|(_, a), (_, b)| {
+                (a - position.x)
+                    .abs()
+                    .partial_cmp(&(b - position.x).abs())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .map(|(user_index, _)| user_index)
+    }
+
+    /// Returns the caret rectangle for the specified `user_index`, relative
+    /// to the top left of the text block.
+    ///
+    /// The rectangle has zero width, and spans the height of the line
+    /// containing the caret. If the index is inside a glyph which represents
+    /// several characters (such as a ligature), the caret is placed at the
+    /// start of that glyph. An index at the end of a wrapped line is placed
+    /// at the start of the following line.
+    ///
+    /// Returns `None` if the index isn't within the text in this block.
+    #[must_use]
+    pub fn position_of_char_index(&self, index: UserGlyphIndex) -> Option<Rect>
+    {
+        let mut end_of_line = None;
+
+        for line in self.lines.iter() {
+            let stops = line.caret_stops();
+
+            for (i, (user_index, pos_x)) in stops.iter().enumerate() {
+                let last_stop = i + 1 == stops.len();
+
+                let next_user_index = stops
+                    .get(i + 1)
+                    .map(|(next_user_index, _)| *next_user_index)
+                    .unwrap_or(*user_index + 1);
+
+                if last_stop {
+                    if *user_index == index && end_of_line.is_none() {
+                        end_of_line = Some(line.caret_rect(*pos_x));
+                    }
+                } else if (*user_index..next_user_index).contains(&index) {
+                    return Some(line.caret_rect(*pos_x));
+                }
+            }
+        }
+
+        end_of_line
+    }
+
+    /// Returns the rectangles covering the glyphs which represent the
+    /// specified range of `user_index` values, relative to the top left of
+    /// the text block. This can be used to highlight selected text.
+    ///
+    /// One rectangle is returned for each line containing part of the range,
+    /// spanning the height of that line. A glyph which represents several
+    /// characters (such as a ligature) is included if any of those
+    /// characters are in the range.
+    #[must_use]
+    pub fn rects_for_range(&self, range: Range<UserGlyphIndex>) -> Vec<Rect>
+    {
+        let mut rects = Vec::new();
+
+        for line in self.lines.iter() {
+            let mut left: Option<f32> = None;
+            let mut right: Option<f32> = None;
+
+            for glyph in line.iter_glyphs() {
+                let glyph_range = glyph.user_index_range();
+
+                // Zero-length glyph ranges (such as an ellipsis at the end
+                // of the text) are treated as covering a single index
+                let glyph_end = glyph_range.end.max(glyph_range.start + 1);
+
+                if glyph_range.start < range.end && range.start < glyph_end {
+                    let glyph_left = glyph.position_x();
+                    let glyph_right = glyph_left + glyph.advance_width();
+
+                    left = Some(left.map_or(glyph_left, |x| x.min(glyph_left)));
+                    right = Some(right.map_or(glyph_right, |x| x.max(glyph_right)));
+                }
+            }
+
+            if let (Some(left), Some(right)) = (left, right) {
+                let top = line.baseline_vertical_position;
+                rects.push(Rect::from_tuples((left, top), (right, top + line.height)));
+            }
+        }
+
+        rects
+    }
+
+    /// Returns the areas behind the glyphs which have a background color,
+    /// relative to the top left of the text block. Consecutive glyphs on the
+    /// same line with the same background color are covered by a single
+    /// rectangle, spanning the height of the line.
+    pub(crate) fn background_rects(&self) -> Vec<(Rect, Color)>
+    {
+        let mut rects: Vec<(Rect, Color)> = Vec::new();
+
+        for line in self.lines.iter() {
+            let top = line.baseline_vertical_position;
+            let bottom = top + line.height;
+
+            let mut current_run: Option<(f32, f32, Color)> = None;
+
+            for glyph in line.iter_glyphs() {
+                let left = glyph.position_x();
+                let right = left + glyph.advance_width();
+
+                current_run = match (current_run, glyph.background_color) {
+                    (Some((start, _, run_color)), Some(color)) if run_color == color => {
+                        Some((start, right, color))
+                    }
+                    (run, color) => {
+                        if let Some((start, end, run_color)) = run {
+                            rects.push((
+                                Rect::from_tuples((start, top), (end, bottom)),
+                                run_color
+                            ));
+                        }
+
+                        color.map(|color| (left, right, color))
+                    }
+                };
+            }
+
+            if let Some((start, end, run_color)) = current_run {
+                rects.push((Rect::from_tuples((start, top), (end, bottom)), run_color));
+            }
+        }
+
+        rects
+    }
+
+    /// Iterate over the glyphs in every line of this block.
+    #[inline]
+    pub fn iter_glyphs(&self) -> impl Iterator<Item = &FormattedGlyph>
+    {
+        self.lines.iter().flat_map(|line| line.iter_glyphs())
+    }
+
+    /// The width (in pixels) of this text block.
+    #[inline]
+    #[must_use]
+    pub fn width(&self) -> f32
+    {
+        self.width
     }
 
     /// The height (in pixels) of this text block.
@@ -1027,6 +2673,45 @@ impl FormattedTextBlock
     {
         Vec2::new(self.width, self.height)
     }
+
+    /// True if some of the text was left out of this block, either because a
+    /// line was cut short by [TextOptions::with_truncate_to_width], or
+    /// because the limit set by [TextOptions::with_max_lines] was reached.
+    #[inline]
+    #[must_use]
+    pub fn is_truncated(&self) -> bool
+    {
+        self.truncated
+    }
+    /// Sets the range of bytes in the source text represented by each glyph.
+    /// The byte offset of each codepoint is given by `byte_offsets`, indexed
+    /// by `user_index`.
+    fn set_byte_ranges(&mut self, byte_offsets: &[usize], text_len: usize)
+    {
+        let byte_offset = |user_index: UserGlyphIndex| {
+            byte_offsets
+                .get(user_index as usize)
+                .copied()
+                .unwrap_or(text_len)
+        };
+
+        for line in Arc::make_mut(&mut self.lines).iter_mut() {
+            for glyph in Arc::make_mut(&mut line.glyphs).iter_mut() {
+                let start = byte_offset(glyph.user_index);
+
+                // Several codepoints may be formed from the same character,
+                // so the glyph ends at the next character after its start
+                let end = byte_offsets
+                    .iter()
+                    .skip(glyph.user_index_end as usize)
+                    .copied()
+                    .find(|offset| *offset > start)
+                    .unwrap_or(text_len);
+
+                glyph.byte_range = Some(start..end);
+            }
+        }
+    }
 }
 
 /// Represents a line of text which has been laid out as part of a block.
@@ -1039,7 +2724,16 @@ pub struct FormattedTextLine
     height: f32,
     ascent: f32,
     descent: f32,
-    line_gap: f32
+    line_gap: f32,
+    truncated: bool,
+
+    /// The horizontal position at which the line starts, after alignment.
+    start_x: f32,
+
+    /// The `user_index` of the newline which ended this line, if any.
+    newline_user_index: Option<UserGlyphIndex>,
+
+    user_index_range: Range<UserGlyphIndex>
 }
 
 impl FormattedTextLine
@@ -1060,7 +2754,8 @@ impl FormattedTextLine
         FormattedTextBlock {
             lines: Arc::new(smallvec![self.clone()]),
             width: self.width,
-            height: self.height
+            height: self.height,
+            truncated: self.truncated
         }
     }
 
@@ -1111,20 +2806,100 @@ impl FormattedTextLine
         self.line_gap
     }
 
-    /// The vertical position of this line's baseline within the block of text.
+    /// The vertical position of the top of this line within the block of
+    /// text. Despite its name, this is not the position of the baseline,
+    /// which is `ascent()` pixels lower, and is returned by
+    /// [FormattedTextLine::baseline_y].
     #[inline]
     #[must_use]
     pub fn baseline_position(&self) -> f32
     {
         self.baseline_vertical_position
     }
-}
 
-impl<T: Copy> From<&rusttype::Rect<T>> for Rectangle<T>
-{
+    /// The vertical position of this line's baseline within the block of
+    /// text.
     #[inline]
     #[must_use]
-    fn from(rect: &rusttype::Rect<T>) -> Self
+    pub fn baseline_y(&self) -> f32
+    {
+        self.baseline_vertical_position + self.ascent
+    }
+
+    /// The area occupied by this line within the block of text. This spans
+    /// the full height of the line, from the left edge of the first glyph
+    /// to the advance of the last glyph, after the line has been aligned.
+    #[inline]
+    #[must_use]
+    pub fn bounds(&self) -> Rect
+    {
+        let top = self.baseline_vertical_position;
+
+        Rect::from_tuples(
+            (self.start_x, top),
+            (self.start_x + self.width, top + self.height)
+        )
+    }
+
+    /// The range of `user_index` values covered by this line. This starts at
+    /// the first codepoint of the line, and ends at the first codepoint of
+    /// the next line (or one past the last `user_index`, at the end of the
+    /// input), so it includes any whitespace trimmed from the start of the
+    /// line, and the newline which ended it.
+    ///
+    /// If text was left out of the block (see
+    /// [FormattedTextBlock::is_truncated]), the range of the last line ends
+    /// where the omitted text begins.
+    #[inline]
+    #[must_use]
+    pub fn user_index_range(&self) -> Range<UserGlyphIndex>
+    {
+        self.user_index_range.clone()
+    }
+
+    /// Returns the places in this line where a caret may be positioned, as
+    /// pairs of `user_index` and horizontal position. The final caret
+    /// position is at the end of the line.
+    fn caret_stops(&self) -> Vec<(UserGlyphIndex, f32)>
+    {
+        let mut stops = Vec::with_capacity(self.glyphs.len() + 1);
+        let mut end_x = self.start_x;
+        let mut end_user_index = None;
+
+        for glyph in self.glyphs.iter() {
+            // Glyphs which share a user_index (such as combining marks) can't
+            // be separated by the caret
+            if stops.last().map(|(user_index, _)| *user_index) != Some(glyph.user_index) {
+                stops.push((glyph.user_index, glyph.position_x()));
+            }
+
+            end_x =
+                crate::numeric::max(end_x, glyph.position_x() + glyph.advance_width());
+            end_user_index = Some(glyph.user_index_end);
+        }
+
+        if let Some(end_user_index) = self.newline_user_index.or(end_user_index) {
+            stops.push((end_user_index, end_x));
+        }
+
+        stops
+    }
+
+    /// A zero-width rectangle spanning the height of this line, at the
+    /// specified horizontal position.
+    fn caret_rect(&self, pos_x: f32) -> Rect
+    {
+        // The vertical position of a line is that of its top edge
+        let top = self.baseline_vertical_position;
+
+        Rect::from_tuples((pos_x, top), (pos_x, top + self.height))
+    }
+}
+
+impl<T: Copy> From<&rusttype::Rect<T>> for Rectangle<T>
+{
+    #[inline]
+    fn from(rect: &rusttype::Rect<T>) -> Self
     {
         Rectangle::new(
             Vector2::new(rect.min.x, rect.min.y),
@@ -1143,21 +2918,24 @@ mod test
     {
         let codepoints = Codepoint::from_unindexed_codepoints(&['a', 'b', ' ', 'c', 'd']);
 
-        let words = Word::split_words(&codepoints);
+        let words = Word::split_words(&codepoints, 5);
 
         assert_eq!(
             vec![
                 Word::Renderable(RenderableWord {
                     codepoints: vec![Codepoint::new(0, 'a'), Codepoint::new(1, 'b')],
-                    is_whitespace: false
+                    is_whitespace: false,
+                    end_user_index: 2
                 }),
                 Word::Renderable(RenderableWord {
                     codepoints: vec![Codepoint::new(2, ' ')],
-                    is_whitespace: true
+                    is_whitespace: true,
+                    end_user_index: 3
                 }),
                 Word::Renderable(RenderableWord {
                     codepoints: vec![Codepoint::new(3, 'c'), Codepoint::new(4, 'd')],
-                    is_whitespace: false
+                    is_whitespace: false,
+                    end_user_index: 5
                 })
             ],
             words
@@ -1171,35 +2949,858 @@ mod test
             'a', 'b', '\t', ' ', '\n', 'c', 'd', '\n', '\n', ' '
         ]);
 
-        let words = Word::split_words(&codepoints);
+        let words = Word::split_words(&codepoints, 10);
 
         assert_eq!(
             vec![
                 Word::Renderable(RenderableWord {
                     codepoints: vec![Codepoint::new(0, 'a'), Codepoint::new(1, 'b')],
-                    is_whitespace: false
+                    is_whitespace: false,
+                    end_user_index: 2
                 }),
                 Word::Renderable(RenderableWord {
                     codepoints: vec![Codepoint::new(2, '\t'),],
-                    is_whitespace: true
+                    is_whitespace: true,
+                    end_user_index: 3
                 }),
                 Word::Renderable(RenderableWord {
                     codepoints: vec![Codepoint::new(3, ' '),],
-                    is_whitespace: true
+                    is_whitespace: true,
+                    end_user_index: 4
                 }),
-                Word::Newline,
+                Word::Newline(4),
                 Word::Renderable(RenderableWord {
                     codepoints: vec![Codepoint::new(5, 'c'), Codepoint::new(6, 'd')],
-                    is_whitespace: false
+                    is_whitespace: false,
+                    end_user_index: 7
                 }),
-                Word::Newline,
-                Word::Newline,
+                Word::Newline(7),
+                Word::Newline(8),
                 Word::Renderable(RenderableWord {
                     codepoints: vec![Codepoint::new(9, ' ')],
-                    is_whitespace: true
+                    is_whitespace: true,
+                    end_user_index: 10
                 })
             ],
             words
         )
     }
+
+    #[test]
+    fn test_font_metrics()
+    {
+        let font =
+            Font::new(include_bytes!("../assets/fonts/NotoSans-Regular.ttf")).unwrap();
+
+        let metrics = font.metrics(40.0);
+
+        assert!(metrics.ascent() > 0.0);
+        assert!(metrics.descent() < 0.0);
+        assert_eq!(
+            metrics.ascent() - metrics.descent() + metrics.line_gap(),
+            metrics.line_height()
+        );
+
+        let block = font.layout_text("Hg", 40.0, TextOptions::new());
+        let line = block.iter_lines().next().unwrap();
+
+        assert_eq!(metrics.ascent(), line.ascent());
+        assert_eq!(metrics.descent(), line.descent());
+        assert_eq!(metrics.line_gap(), line.line_gap());
+
+        let glyph_advance = line.iter_glyphs().next().unwrap().advance_width();
+
+        assert_eq!(glyph_advance, font.advance_width('H', 40.0));
+        assert_eq!(2.0 * font.advance_width('H', 20.0), glyph_advance);
+    }
+
+    #[test]
+    fn test_measure_text()
+    {
+        let font =
+            Font::new(include_bytes!("../assets/fonts/NotoSans-Regular.ttf")).unwrap();
+
+        let text = "  The quick brown fox\n\njumps over the\tlazy dog.  ";
+
+        let options_list = vec![
+            TextOptions::new(),
+            TextOptions::new().with_tracking(3.5),
+            TextOptions::new().with_wrap_to_width(100.0, TextAlignment::Left),
+            TextOptions::new().with_wrap_to_width(5.0, TextAlignment::Center),
+            TextOptions::new()
+                .with_wrap_to_width(120.0, TextAlignment::Right)
+                .with_trim_each_line(true)
+                .with_line_spacing_multiplier(1.5),
+            TextOptions::new()
+                .with_wrap_to_width(80.0, TextAlignment::Left)
+                .with_tracking(-2.0)
+                .with_trim_each_line(true),
+            TextOptions::new().with_truncate_to_width(90.0, "…"),
+            TextOptions::new()
+                .with_wrap_to_width(100.0, TextAlignment::Left)
+                .with_max_lines(2),
+            TextOptions::new()
+                .with_wrap_to_width(100.0, TextAlignment::Center)
+                .with_truncate_to_width(100.0, "…")
+                .with_max_lines(1),
+        ];
+
+        for options in options_list {
+            for text in &[text, "", "\n"] {
+                let measured = font.measure_text(text, 24.0, &options);
+                let block = font.layout_text(text, 24.0, options.clone());
+
+                assert_eq!(block.size(), measured);
+            }
+        }
+    }
+
+    #[test]
+    fn test_truncate_to_width()
+    {
+        let font =
+            Font::new(include_bytes!("../assets/fonts/NotoSans-Regular.ttf")).unwrap();
+
+        let full = font.layout_text("The quick brown fox", 24.0, TextOptions::new());
+
+        let fits = font.layout_text(
+            "The quick brown fox",
+            24.0,
+            TextOptions::new().with_truncate_to_width(full.width() + 1.0, "…")
+        );
+
+        assert_eq!(full.width(), fits.width());
+
+        let block = font.layout_text(
+            "The quick brown fox\nA",
+            24.0,
+            TextOptions::new().with_truncate_to_width(100.0, "…")
+        );
+
+        let lines: Vec<_> = block.iter_lines().collect();
+        assert_eq!(2, lines.len());
+
+        let first_line_glyphs: Vec<_> = lines[0].iter_glyphs().collect();
+        let ellipsis = first_line_glyphs.last().unwrap();
+
+        assert!(lines[0].width() <= 100.0);
+        assert!(lines[0].width() > 100.0 - font.advance_width('w', 24.0));
+        assert_eq!(ellipsis.advance_width(), font.advance_width('…', 24.0));
+        assert_eq!(first_line_glyphs.len() as u32 - 1, ellipsis.user_index());
+        assert_eq!(1, lines[1].iter_glyphs().count());
+    }
+
+    #[test]
+    fn test_max_lines()
+    {
+        let font =
+            Font::new(include_bytes!("../assets/fonts/NotoSans-Regular.ttf")).unwrap();
+
+        let text = "The quick brown fox jumps over the lazy dog";
+
+        let wrapped = TextOptions::new().with_wrap_to_width(100.0, TextAlignment::Left);
+
+        let all = font.layout_text(text, 24.0, wrapped.clone());
+        assert!(all.iter_lines().count() > 2);
+        assert!(!all.is_truncated());
+
+        let unlimited = font.layout_text(text, 24.0, wrapped.clone().with_max_lines(100));
+        assert_eq!(all.iter_lines().count(), unlimited.iter_lines().count());
+        assert!(!unlimited.is_truncated());
+
+        let limited = font.layout_text(text, 24.0, wrapped.clone().with_max_lines(2));
+        assert_eq!(2, limited.iter_lines().count());
+        assert!(limited.is_truncated());
+        assert!(limited.height() < all.height());
+
+        let none = font.layout_text(text, 24.0, wrapped.clone().with_max_lines(0));
+        assert_eq!(0, none.iter_lines().count());
+        assert!(none.is_truncated());
+
+        let ellipsized = font.layout_text(
+            text,
+            24.0,
+            wrapped.with_truncate_to_width(100.0, "…").with_max_lines(2)
+        );
+
+        let last_line = ellipsized.iter_lines().last().unwrap();
+        let ellipsis = last_line.iter_glyphs().last().unwrap();
+
+        assert_eq!(2, ellipsized.iter_lines().count());
+        assert!(ellipsized.is_truncated());
+        assert!(last_line.width() <= 100.0);
+        assert_eq!(ellipsis.advance_width(), font.advance_width('…', 24.0));
+    }
+
+    #[test]
+    fn test_glyph_user_index_range()
+    {
+        let font =
+            Font::new(include_bytes!("../assets/fonts/NotoSans-Regular.ttf")).unwrap();
+
+        // The decomposed "e" and acute accent are normalized into one glyph
+        let text = "ne\u{301}e £\nx";
+
+        let block = font.layout_text(text, 24.0, TextOptions::new());
+
+        let ranges: Vec<_> = block
+            .iter_glyphs()
+            .map(|glyph| glyph.user_index_range())
+            .collect();
+
+        assert_eq!(vec![0..1, 1..2, 2..3, 3..4, 4..5, 6..7], ranges);
+
+        let byte_ranges: Vec<_> = block
+            .iter_glyphs()
+            .map(|glyph| glyph.byte_range().unwrap())
+            .collect();
+
+        assert_eq!(vec![0..1, 1..4, 4..5, 5..6, 6..8, 9..10], byte_ranges);
+
+        let unindexed = font.layout_text_from_unindexed_codepoints(
+            &['a', 'b'],
+            24.0,
+            TextOptions::new()
+        );
+
+        assert!(unindexed
+            .iter_glyphs()
+            .all(|glyph| glyph.byte_range().is_none()));
+
+        let glyphs: Vec<_> = block.iter_glyphs().collect();
+        let lines: Vec<_> = block.iter_lines().collect();
+
+        assert_eq!(glyphs[1].position_x(), glyphs[1].position().x);
+        assert_eq!(lines[0].ascent(), glyphs[0].position().y);
+        assert_eq!(lines[0].ascent(), glyphs[4].position().y);
+        assert!(glyphs[5].position().y > lines[0].height());
+
+        let truncated = font.layout_text(
+            text,
+            24.0,
+            TextOptions::new().with_truncate_to_width(
+                glyphs[2].position_x() + font.advance_width('…', 24.0) + 1.0,
+                "…"
+            )
+        );
+
+        let ellipsis = truncated
+            .iter_lines()
+            .next()
+            .unwrap()
+            .iter_glyphs()
+            .last()
+            .unwrap();
+
+        assert_eq!(2..5, ellipsis.user_index_range());
+        assert_eq!(Some(4..8), ellipsis.byte_range());
+    }
+
+    #[test]
+    #[cfg(feature = "text-shaping")]
+    fn test_shaped_combining_mark()
+    {
+        let font =
+            Font::new(include_bytes!("../assets/fonts/NotoSans-Regular.ttf")).unwrap();
+
+        // There's no precomposed "q" with an acute accent, so the mark is kept
+        // as a separate glyph and positioned by the shaper
+        let text = "q\u{301}x";
+
+        let block = font.layout_text(text, 24.0, TextOptions::new());
+        let glyphs: Vec<_> = block.iter_glyphs().collect();
+
+        assert_eq!(3, glyphs.len());
+
+        // Without shaping, each codepoint would be its own cluster, and the
+        // mark would be placed after the "q"
+        let ranges: Vec<_> = glyphs
+            .iter()
+            .map(|glyph| glyph.user_index_range())
+            .collect();
+
+        assert_eq!(vec![0..2, 0..2, 2..3], ranges);
+        assert!(glyphs[1].position_x() < glyphs[2].position_x());
+        assert!(glyphs[1].position_x() < font.advance_width('q', 24.0));
+
+        let substrings: Vec<_> = glyphs
+            .iter()
+            .map(|glyph| &text[glyph.byte_range().unwrap()])
+            .collect();
+
+        assert_eq!(vec!["q\u{301}", "q\u{301}", "x"], substrings);
+    }
+
+    #[test]
+    fn test_hit_testing()
+    {
+        let font =
+            Font::new(include_bytes!("../assets/fonts/NotoSans-Regular.ttf")).unwrap();
+
+        let block = font.layout_text("ab\n\ncd", 24.0, TextOptions::new());
+
+        let lines: Vec<_> = block.iter_lines().collect();
+        let glyphs: Vec<_> = block.iter_glyphs().collect();
+        let line_y = |line: usize| lines[line].baseline_position() + 1.0;
+
+        assert_eq!(3, lines.len());
+
+        assert_eq!(
+            Some(0),
+            block.char_index_at_position(Vec2::new(-10.0, -10.0))
+        );
+        assert_eq!(
+            Some(1),
+            block.char_index_at_position(Vec2::new(
+                glyphs[1].position_x() + 1.0,
+                line_y(0)
+            ))
+        );
+        assert_eq!(
+            Some(2),
+            block.char_index_at_position(Vec2::new(500.0, line_y(0)))
+        );
+        assert_eq!(
+            Some(3),
+            block.char_index_at_position(Vec2::new(500.0, line_y(1)))
+        );
+        assert_eq!(
+            Some(6),
+            block.char_index_at_position(Vec2::new(500.0, 500.0))
+        );
+
+        let caret = block.position_of_char_index(1).unwrap();
+        assert_eq!(glyphs[1].position_x(), caret.left());
+        assert_eq!(0.0, caret.width());
+        assert_eq!(lines[0].height(), caret.height());
+
+        let caret = block.position_of_char_index(2).unwrap();
+        assert_eq!(lines[0].width(), caret.left());
+
+        let caret = block.position_of_char_index(3).unwrap();
+        assert_eq!(0.0, caret.left());
+        assert_eq!(lines[1].baseline_position(), caret.top());
+
+        let caret = block.position_of_char_index(6).unwrap();
+        assert_eq!(lines[2].width(), caret.left());
+        assert_eq!(lines[2].baseline_position(), caret.top());
+
+        assert_eq!(None, block.position_of_char_index(7));
+
+        // The index at the end of a wrapped line is at the start of the next
+        let wrapped = font.layout_text(
+            "ab cd",
+            24.0,
+            TextOptions::new().with_wrap_to_width(
+                font.layout_text("ab c", 24.0, TextOptions::new()).width() - 1.0,
+                TextAlignment::Left
+            )
+        );
+
+        let lines: Vec<_> = wrapped.iter_lines().collect();
+        let caret = wrapped.position_of_char_index(3).unwrap();
+
+        assert_eq!(2, lines.len());
+        assert_eq!(0.0, caret.left());
+        assert_eq!(lines[1].baseline_position(), caret.top());
+
+        let empty = font.layout_text("", 24.0, TextOptions::new());
+
+        assert_eq!(None, empty.char_index_at_position(Vec2::ZERO));
+        assert_eq!(None, empty.position_of_char_index(0));
+    }
+
+    #[test]
+    fn test_rich_text()
+    {
+        let font =
+            Font::new(include_bytes!("../assets/fonts/NotoSans-Regular.ttf")).unwrap();
+
+        let plain = font.layout_text("Hello world", 24.0, TextOptions::new());
+
+        let single = RichText::new()
+            .with_span(TextSpan::new("Hello world", &font, 24.0))
+            .layout(TextOptions::new());
+
+        assert_eq!(plain.size(), single.size());
+
+        for (a, b) in plain.iter_glyphs().zip(single.iter_glyphs()) {
+            assert_eq!(a.position(), b.position());
+            assert_eq!(a.user_index_range(), b.user_index_range());
+            assert_eq!(a.byte_range(), b.byte_range());
+        }
+
+        let red = Color::from_rgb(1.0, 0.0, 0.0);
+
+        let rich = RichText::new()
+            .with_span(TextSpan::new("Héllo ", &font, 24.0))
+            .with_span(TextSpan::new("big", &font, 48.0).with_color(red))
+            .with_span(TextSpan::new("word", &font, 24.0));
+
+        let block = rich.layout(TextOptions::new());
+        let glyphs: Vec<_> = block.iter_glyphs().collect();
+        let lines: Vec<_> = block.iter_lines().collect();
+
+        assert_eq!(1, lines.len());
+        assert_eq!(font.metrics(48.0).ascent(), lines[0].ascent());
+
+        // Every glyph shares the same baseline
+        for glyph in &glyphs {
+            assert_eq!(lines[0].ascent(), glyph.position().y);
+        }
+
+        assert_eq!(None, glyphs[0].color());
+        assert_eq!(Some(red), glyphs[6].color());
+        assert_eq!(None, glyphs[9].color());
+
+        assert_eq!(6..7, glyphs[6].user_index_range());
+        assert_eq!(9..10, glyphs[9].user_index_range());
+        assert_eq!(12..13, glyphs[12].user_index_range());
+
+        assert_eq!(Some(1..3), glyphs[1].byte_range());
+        assert_eq!(Some(7..8), glyphs[6].byte_range());
+        assert_eq!(Some(10..11), glyphs[9].byte_range());
+        assert_eq!(Some(13..14), glyphs[12].byte_range());
+
+        assert_eq!(glyphs[6].advance_width(), font.advance_width('b', 48.0));
+
+        // The spans "big" and "word" form a single word, which isn't split
+        let wrapped = rich.layout(
+            TextOptions::new()
+                .with_wrap_to_width(block.width() - 1.0, TextAlignment::Left)
+        );
+
+        let lines: Vec<_> = wrapped.iter_lines().collect();
+
+        assert_eq!(2, lines.len());
+        assert_eq!(7, lines[1].iter_glyphs().count());
+
+        for options in &[
+            TextOptions::new(),
+            TextOptions::new().with_wrap_to_width(50.0, TextAlignment::Center)
+        ] {
+            assert_eq!(rich.layout(options.clone()).size(), rich.measure(options));
+        }
+
+        assert_eq!(Vec2::ZERO, RichText::new().measure(&TextOptions::new()));
+    }
+
+    #[test]
+    fn test_glyph_outline()
+    {
+        let font =
+            Font::new(include_bytes!("../assets/fonts/NotoSans-Regular.ttf")).unwrap();
+
+        let outline = font.glyph_outline('H', 40.0).unwrap();
+        let polylines = outline.to_polylines();
+
+        // Two vertical stems joined by a crossbar form a single contour
+        assert_eq!(1, polylines.len());
+
+        let ascent = font.metrics(40.0).ascent();
+        let advance = font.advance_width('H', 40.0);
+
+        for point in polylines.iter().flatten() {
+            assert!(point.x > 0.0 && point.x < advance, "{:?}", point);
+            assert!(point.y <= 0.0 && point.y > -ascent, "{:?}", point);
+        }
+
+        // The ring of an 'o' is made from two contours
+        assert_eq!(
+            2,
+            font.glyph_outline('o', 40.0).unwrap().to_polylines().len()
+        );
+
+        assert!(font.glyph_outline(' ', 40.0).is_none());
+        assert!(font.glyph_outline('\u{10FFFD}', 40.0).is_none());
+
+        let text = font.layout_text("o", 40.0, TextOptions::new());
+        let glyph_id = text.iter_glyphs().next().unwrap().glyph_id();
+
+        assert_eq!(
+            2,
+            font.glyph_outline_by_id(glyph_id, 40.0)
+                .unwrap()
+                .to_polylines()
+                .len()
+        );
+
+        assert!(font.glyph_outline_by_id(u16::MAX, 40.0).is_none());
+    }
+
+    #[test]
+    fn test_word_wrap_style()
+    {
+        let font =
+            Font::new(include_bytes!("../assets/fonts/NotoSans-Regular.ttf")).unwrap();
+
+        let text = "See https://example.com/a/very/long/path";
+
+        let layout = |style: WordWrapStyle| {
+            font.layout_text(
+                text,
+                20.0,
+                TextOptions::new()
+                    .with_wrap_to_width(120.0, TextAlignment::Left)
+                    .with_word_wrap_style(style)
+            )
+        };
+
+        let line_lengths = |block: &FormattedTextBlock| -> Vec<usize> {
+            block
+                .iter_lines()
+                .map(|line| line.iter_glyphs().count())
+                .collect()
+        };
+
+        let word_boundary = layout(WordWrapStyle::WordBoundary);
+        assert_eq!(vec![4, 36], line_lengths(&word_boundary));
+        assert!(word_boundary.width() > 120.0);
+
+        let fallback = layout(WordWrapStyle::WordWithCharacterFallback);
+        assert_eq!(4, line_lengths(&fallback)[0]);
+        assert!(fallback.iter_lines().count() > 2);
+        assert!(fallback.iter_lines().all(|line| line.width() <= 120.0));
+
+        let anywhere = layout(WordWrapStyle::Anywhere);
+        assert!(line_lengths(&anywhere)[0] > 4);
+        assert!(anywhere.iter_lines().all(|line| line.width() <= 120.0));
+
+        // Every glyph is still laid out, whichever style is used
+        for block in [&word_boundary, &fallback, &anywhere] {
+            assert_eq!(40, line_lengths(block).iter().sum::<usize>());
+        }
+
+        assert_eq!(
+            font.measure_text(
+                text,
+                20.0,
+                &TextOptions::new()
+                    .with_wrap_to_width(120.0, TextAlignment::Left)
+                    .with_word_wrap_style(WordWrapStyle::Anywhere)
+            ),
+            Vec2::new(anywhere.width(), anywhere.height())
+        );
+    }
+
+    #[test]
+    fn test_line_bounds()
+    {
+        let font =
+            Font::new(include_bytes!("../assets/fonts/NotoSans-Regular.ttf")).unwrap();
+
+        let text = "Hello wide world\n\nEnd";
+
+        let block = font.layout_text(
+            text,
+            20.0,
+            TextOptions::new().with_wrap_to_width(100.0, TextAlignment::Center)
+        );
+
+        let lines: Vec<&FormattedTextLine> = block.iter_lines().collect();
+
+        let ranges: Vec<Range<UserGlyphIndex>> =
+            lines.iter().map(|line| line.user_index_range()).collect();
+
+        // The ranges cover the whole text, including trimmed whitespace and
+        // newlines
+        assert_eq!(vec![0..11, 11..17, 17..18, 18..21], ranges);
+        assert_eq!(
+            "world\n",
+            &text[ranges[1].start as usize..ranges[1].end as usize]
+        );
+
+        let mut previous_bottom = 0.0;
+
+        for line in &lines {
+            let bounds = line.bounds();
+
+            assert!((line.width() - bounds.width()).abs() < 0.01);
+            assert!((line.height() - bounds.height()).abs() < 0.01);
+            assert_eq!(line.baseline_position(), bounds.top_left().y);
+            assert_eq!(bounds.top_left().y + line.ascent(), line.baseline_y());
+
+            // Lines are centered within the wrap width
+            assert!((bounds.top_left().x + bounds.bottom_right().x - 100.0).abs() < 0.01);
+
+            assert!(bounds.top_left().y >= previous_bottom);
+            previous_bottom = bounds.bottom_right().y;
+
+            for glyph in line.iter_glyphs() {
+                assert_eq!(line.baseline_y(), glyph.position().y);
+                assert!(glyph.position_x() >= bounds.top_left().x);
+            }
+        }
+
+        let truncated =
+            font.layout_text(text, 20.0, TextOptions::new().with_max_lines(1));
+
+        assert!(truncated.is_truncated());
+        assert_eq!(
+            vec![0..17],
+            truncated
+                .iter_lines()
+                .map(|line| line.user_index_range())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_rects_for_range()
+    {
+        let font =
+            Font::new(include_bytes!("../assets/fonts/NotoSans-Regular.ttf")).unwrap();
+
+        let block = font.layout_text(
+            "Hello wide world",
+            20.0,
+            TextOptions::new().with_wrap_to_width(100.0, TextAlignment::Left)
+        );
+
+        let lines: Vec<&FormattedTextLine> = block.iter_lines().collect();
+        assert_eq!(2, lines.len());
+
+        // Nothing is selected
+        assert!(block.rects_for_range(3..3).is_empty());
+        assert!(block.rects_for_range(100..200).is_empty());
+
+        // Part of the first line
+        let rects = block.rects_for_range(1..3);
+        assert_eq!(1, rects.len());
+
+        let glyphs: Vec<&FormattedGlyph> = lines[0].iter_glyphs().collect();
+        assert_eq!(glyphs[1].position_x(), rects[0].top_left().x);
+        assert_eq!(glyphs[3].position_x(), rects[0].bottom_right().x);
+        assert_eq!(lines[0].bounds().top_left().y, rects[0].top_left().y);
+        assert_eq!(
+            lines[0].bounds().bottom_right().y,
+            rects[0].bottom_right().y
+        );
+
+        // Spanning the wrapped lines
+        let rects = block.rects_for_range(7..13);
+        assert_eq!(2, rects.len());
+
+        assert_eq!(glyphs[7].position_x(), rects[0].top_left().x);
+        assert_eq!(lines[0].bounds().top_left().y, rects[0].top_left().y);
+
+        assert_eq!(0.0, rects[1].top_left().x);
+        assert_eq!(lines[1].bounds().top_left().y, rects[1].top_left().y);
+        assert_eq!(
+            lines[1].iter_glyphs().nth(2).unwrap().position_x(),
+            rects[1].bottom_right().x
+        );
+    }
+
+    #[test]
+    fn test_font_cap_height_and_has_glyph()
+    {
+        let font =
+            Font::new(include_bytes!("../assets/fonts/NotoSans-Regular.ttf")).unwrap();
+
+        let metrics = font.metrics(40.0);
+
+        assert!(metrics.cap_height() < metrics.ascent());
+        assert!(metrics.x_height() > 0.0);
+        assert!(metrics.x_height() < metrics.cap_height());
+
+        // The heights match the outlines of the corresponding glyphs
+        assert!((metrics.cap_height() - font.glyph_height('H', 40.0)).abs() < 0.5);
+        assert!((metrics.x_height() - font.glyph_height('x', 40.0)).abs() < 0.5);
+
+        let doubled = font.metrics(80.0);
+        assert!((doubled.cap_height() - 2.0 * metrics.cap_height()).abs() < 0.01);
+
+        assert!(font.has_glyph('A'));
+        assert!(font.has_glyph('ö'));
+        assert!(!font.has_glyph('\u{10FFFD}'));
+    }
+
+    #[test]
+    fn test_font_variations()
+    {
+        let bytes = include_bytes!("../test/assets/fonts/TestHVARTwo.ttf");
+
+        let light = Font::new_with_variations(bytes, &[("wght", 0.0)]).unwrap();
+        let heavy = Font::new_with_variations(bytes, &[("wght", 1000.0)]).unwrap();
+
+        assert!(light.has_glyph('A'));
+
+        let light_advance = light.advance_width('A', 40.0);
+        let heavy_advance = heavy.advance_width('A', 40.0);
+
+        assert!(light_advance > 0.0);
+        assert!((light_advance - heavy_advance).abs() > 0.5);
+
+        let error = Font::new_with_variations(bytes, &[("wdth", 90.0)])
+            .err()
+            .unwrap();
+
+        assert!(error.error().to_string().contains("wdth"));
+
+        let error = Font::new_with_variations(bytes, &[("weight", 400.0)])
+            .err()
+            .unwrap();
+
+        assert!(error.error().to_string().contains("weight"));
+    }
+
+    #[test]
+    fn test_pixel_snapping()
+    {
+        let font =
+            Font::new(include_bytes!("../assets/fonts/NotoSans-Regular.ttf")).unwrap();
+
+        let text = "The quick brown fox\njumped over the lazy dog";
+
+        let is_whole = |value: f32| value == value.round();
+
+        let unsnapped = font.layout_text(text, 13.0, TextOptions::new());
+
+        assert!(!unsnapped
+            .iter_glyphs()
+            .all(|glyph| is_whole(glyph.position().x) && is_whole(glyph.position().y)));
+
+        let snapped = font.layout_text(
+            text,
+            13.0,
+            TextOptions::new()
+                .with_wrap_to_width(150.0, TextAlignment::Center)
+                .with_pixel_snapping(true)
+        );
+
+        for glyph in snapped.iter_glyphs() {
+            assert!(is_whole(glyph.position().x), "{:?}", glyph.position());
+            assert!(is_whole(glyph.position().y), "{:?}", glyph.position());
+        }
+
+        // Each glyph moves by less than a pixel
+        let snapped =
+            font.layout_text(text, 13.0, TextOptions::new().with_pixel_snapping(true));
+
+        for (a, b) in unsnapped.iter_glyphs().zip(snapped.iter_glyphs()) {
+            assert!((a.position().x - b.position().x).abs() <= 0.5);
+            assert!((a.position().y - b.position().y).abs() <= 0.5);
+        }
+    }
+
+    #[test]
+    fn test_paragraph_options()
+    {
+        let font =
+            Font::new(include_bytes!("../assets/fonts/NotoSans-Regular.ttf")).unwrap();
+
+        let text = "First paragraph, which wraps\nSecond";
+
+        let options = TextOptions::new()
+            .with_wrap_to_width(150.0, TextAlignment::Left)
+            .with_first_line_indent(20.0)
+            .with_paragraph_spacing(10.0);
+
+        let plain = font.layout_text(
+            text,
+            20.0,
+            TextOptions::new().with_wrap_to_width(150.0, TextAlignment::Left)
+        );
+        let block = font.layout_text(text, 20.0, options.clone());
+
+        let lines: Vec<&FormattedTextLine> = block.iter_lines().collect();
+        assert_eq!(3, lines.len());
+
+        let first_x =
+            |line: &FormattedTextLine| line.iter_glyphs().next().unwrap().position_x();
+
+        // Only the first line of each paragraph is indented
+        assert_eq!(20.0, first_x(lines[0]));
+        assert_eq!(0.0, first_x(lines[1]));
+        assert_eq!(20.0, first_x(lines[2]));
+        assert!(lines.iter().all(|line| line.width() <= 150.0));
+
+        // Paragraph spacing only applies after a newline
+        let plain_lines: Vec<&FormattedTextLine> = plain.iter_lines().collect();
+
+        assert_eq!(
+            plain_lines[1].baseline_position(),
+            lines[1].baseline_position()
+        );
+        assert_eq!(
+            plain_lines[2].baseline_position() + 10.0,
+            lines[2].baseline_position()
+        );
+        assert_eq!(plain.height() + 10.0, block.height());
+
+        assert_eq!(font.measure_text(text, 20.0, &options), block.size());
+
+        // Empty paragraphs aren't indented
+        let empty = font.layout_text("\n\nText", 20.0, options);
+        assert_eq!(0.0, empty.iter_lines().next().unwrap().width());
+    }
+
+    #[test]
+    fn test_background_rects()
+    {
+        let font =
+            Font::new(include_bytes!("../assets/fonts/NotoSans-Regular.ttf")).unwrap();
+
+        let yellow = Color::from_rgb(1.0, 1.0, 0.0);
+        let cyan = Color::from_rgb(0.0, 1.0, 1.0);
+
+        let rich = RichText::new()
+            .with_span(TextSpan::new("Plain ", &font, 20.0))
+            .with_span(
+                TextSpan::new("marked ", &font, 20.0).with_background_color(yellow)
+            )
+            .with_span(TextSpan::new("text", &font, 20.0).with_background_color(yellow))
+            .with_span(TextSpan::new(" and ", &font, 20.0))
+            .with_span(TextSpan::new("more", &font, 20.0).with_background_color(cyan));
+
+        let block = rich.layout(TextOptions::new());
+        let glyphs: Vec<&FormattedGlyph> = block.iter_glyphs().collect();
+        let line = block.iter_lines().next().unwrap();
+
+        assert_eq!(None, glyphs[0].background_color());
+        assert_eq!(Some(yellow), glyphs[6].background_color());
+
+        let rects = block.background_rects();
+
+        // Adjacent spans with the same color are merged
+        assert_eq!(2, rects.len());
+
+        assert_eq!(yellow, rects[0].1);
+        assert_eq!(glyphs[6].position_x(), rects[0].0.top_left().x);
+        assert_eq!(
+            glyphs[16].position_x() + glyphs[16].advance_width(),
+            rects[0].0.bottom_right().x
+        );
+        assert_eq!(line.bounds().top_left().y, rects[0].0.top_left().y);
+        assert_eq!(line.bounds().bottom_right().y, rects[0].0.bottom_right().y);
+
+        assert_eq!(cyan, rects[1].1);
+        assert_eq!(glyphs[22].position_x(), rects[1].0.top_left().x);
+
+        // Each wrapped line has its own rectangle
+        let wrapped =
+            rich.layout(TextOptions::new().with_wrap_to_width(70.0, TextAlignment::Left));
+
+        assert!(wrapped.iter_lines().count() > 2);
+
+        let lines_with_yellow = wrapped
+            .iter_lines()
+            .filter(|line| {
+                line.iter_glyphs()
+                    .any(|glyph| glyph.background_color() == Some(yellow))
+            })
+            .count();
+
+        let yellow_rects: Vec<Rect> = wrapped
+            .background_rects()
+            .into_iter()
+            .filter(|(_, color)| *color == yellow)
+            .map(|(rect, _)| rect)
+            .collect();
+
+        assert!(lines_with_yellow > 1);
+        assert_eq!(lines_with_yellow, yellow_rects.len());
+        assert!(yellow_rects[0].bottom_right().y <= yellow_rects[1].top_left().y);
+    }
 }
@@ -60,6 +60,70 @@ impl QuantizedDimension
     }
 }
 
+/// An effect which is applied to a glyph when it's rendered.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum GlyphEffect
+{
+    /// The glyph itself.
+    None,
+
+    /// The shape of the glyph, expanded by the specified width in pixels.
+    Outline(f32),
+
+    /// The shape of the glyph, blurred with the specified radius in pixels.
+    Blur(f32)
+}
+
+impl GlyphEffect
+{
+    /// The number of pixels by which the effect extends beyond each side of
+    /// the glyph.
+    fn padding(&self) -> u32
+    {
+        match self {
+            GlyphEffect::None => 0,
+            GlyphEffect::Outline(size) | GlyphEffect::Blur(size) => {
+                size.max(0.0).ceil() as u32
+            }
+        }
+    }
+}
+
+#[derive(Debug, Hash, Eq, PartialEq, Clone)]
+enum QuantizedGlyphEffect
+{
+    None,
+    Outline(QuantizedDimension),
+    Blur(QuantizedDimension)
+}
+
+impl QuantizedGlyphEffect
+{
+    fn from(effect: &GlyphEffect) -> Self
+    {
+        match effect {
+            GlyphEffect::None => QuantizedGlyphEffect::None,
+            GlyphEffect::Outline(width) => {
+                QuantizedGlyphEffect::Outline(QuantizedDimension::from_pixels(*width))
+            }
+            GlyphEffect::Blur(radius) => {
+                QuantizedGlyphEffect::Blur(QuantizedDimension::from_pixels(*radius))
+            }
+        }
+    }
+
+    fn to_effect(&self) -> GlyphEffect
+    {
+        match self {
+            QuantizedGlyphEffect::None => GlyphEffect::None,
+            QuantizedGlyphEffect::Outline(width) => {
+                GlyphEffect::Outline(width.to_pixels())
+            }
+            QuantizedGlyphEffect::Blur(radius) => GlyphEffect::Blur(radius.to_pixels())
+        }
+    }
+}
+
 #[derive(Debug, Hash, Eq, PartialEq, Clone)]
 struct GlyphCacheKey
 {
@@ -69,7 +133,9 @@ struct GlyphCacheKey
     subpixel_offset: (QuantizedDimension, QuantizedDimension),
 
     scale: QuantizedDimension,
-    glyph_id: rusttype::GlyphId
+    glyph_id: rusttype::GlyphId,
+
+    effect: QuantizedGlyphEffect
 }
 
 impl GlyphCacheKey
@@ -78,7 +144,8 @@ impl GlyphCacheKey
     fn from(
         font_id: usize,
         positioned_glyph: &rusttype::PositionedGlyph,
-        screen_offset: Vec2
+        screen_offset: Vec2,
+        effect: &GlyphEffect
     ) -> Self
     {
         // Assuming scale is uniform
@@ -98,7 +165,8 @@ impl GlyphCacheKey
             font_id,
             subpixel_offset,
             scale,
-            glyph_id: positioned_glyph.id()
+            glyph_id: positioned_glyph.id(),
+            effect: QuantizedGlyphEffect::from(effect)
         }
     }
 }
@@ -120,13 +188,15 @@ impl GlyphCache
         glyph: &font::FormattedGlyph,
         position: Vec2,
         color: Color,
+        effect: &GlyphEffect,
         crop_window: Option<&Rect>,
         runner: &mut impl FnMut(Renderer2DAction)
     )
     {
         let positioned_glyph = glyph.glyph();
 
-        let key = GlyphCacheKey::from(glyph.font_id(), positioned_glyph, position);
+        let key =
+            GlyphCacheKey::from(glyph.font_id(), positioned_glyph, position, effect);
 
         let entry = match self.cache_entries.get(&key) {
             None => return, // This is valid for many glyphs, e.g. space
@@ -135,6 +205,13 @@ impl GlyphCache
 
         let texture_cache = self.textures.get(entry.texture_id.unwrap()).unwrap();
 
+        // Colored glyphs keep their own colors, and only take the opacity of
+        // the text color
+        let color = match entry.colored {
+            true => Color::from_rgba(1.0, 1.0, 1.0, color.a()),
+            false => color
+        };
+
         let texture_entry = texture_cache.entries.get(&key).unwrap();
 
         let texture_size = GlyphCacheTexture::SIZE as f32;
@@ -257,13 +334,15 @@ impl GlyphCache
         &mut self,
         _context: &GLContextManager,
         formatted_glyph: &font::FormattedGlyph,
-        position: Vec2
+        position: Vec2,
+        effect: &GlyphEffect
     )
     {
         let key = GlyphCacheKey::from(
             formatted_glyph.font_id(),
             formatted_glyph.glyph(),
-            position
+            position,
+            effect
         );
 
         self.this_frame.insert(key.clone());
@@ -287,13 +366,34 @@ impl GlyphCache
                         key.subpixel_offset.1.to_pixels()
                     ));
 
+                let effect = key.effect.to_effect();
+
+                // Effects use the shape of the glyph, rather than its colors
+                #[cfg(feature = "image-loading")]
+                if effect == GlyphEffect::None {
+                    if let Some((bitmap, offset)) = BitmapRGBA::from_color_glyph(&glyph) {
+                        entry.insert(GlyphCacheEntry {
+                            glyph_bitmap: Rc::new(bitmap),
+                            bounding_box_offset: offset,
+                            colored: true,
+                            texture_id: None
+                        });
+
+                        return;
+                    }
+                }
+
                 let bounding_box = match glyph.pixel_bounding_box() {
                     None => return, // This is valid for some glyphs, e.g. space
                     Some(bounding_box) => bounding_box
                 };
 
-                let bounding_box_size =
-                    UVec2::new(bounding_box.width() as u32, bounding_box.height() as u32);
+                let padding = effect.padding() as i32;
+
+                let bounding_box_size = UVec2::new(
+                    (bounding_box.width() + 2 * padding) as u32,
+                    (bounding_box.height() + 2 * padding) as u32
+                );
 
                 if bounding_box_size.x > GlyphCacheTexture::SIZE
                     || bounding_box_size.y > GlyphCacheTexture::SIZE
@@ -310,14 +410,15 @@ impl GlyphCache
 
                 let mut bitmap = BitmapRGBA::new(bounding_box_size);
 
-                bitmap.draw_glyph(&glyph);
+                bitmap.draw_glyph_with_effect(&glyph, &effect);
 
                 entry.insert(GlyphCacheEntry {
                     glyph_bitmap: Rc::new(bitmap),
                     bounding_box_offset: IVec2::new(
-                        bounding_box.min.x,
-                        bounding_box.min.y
+                        bounding_box.min.x - padding,
+                        bounding_box.min.y - padding
                     ),
+                    colored: false,
                     texture_id: None
                 });
             }
@@ -500,6 +601,81 @@ impl GlyphCache
     }
 }
 
+/// Renders the glyphs in the text block into an RGBA bitmap, which is larger
+/// than the block by `padding` pixels on each side. Glyphs are drawn in
+/// `color`, unless they have their own color. Any parts of the glyphs which
+/// extend beyond the padding are cut off.
+pub(crate) fn rasterize_text_block(
+    text: &font::FormattedTextBlock,
+    color: Color,
+    padding: u32
+) -> (UVec2, Vec<u8>)
+{
+    let size = UVec2::new(
+        (text.width().ceil() as u32 + 2 * padding).max(1),
+        (text.height().ceil() as u32 + 2 * padding).max(1)
+    );
+
+    let mut output = BitmapRGBA::new(size);
+
+    for (rect, background_color) in text.background_rects() {
+        let rect = rect.with_offset(Vec2::new(padding as f32, padding as f32));
+        let top_left = rect.top_left().round().into_i32();
+        let bottom_right = rect.bottom_right().round().into_i32();
+
+        let mut bitmap = BitmapRGBA::new((bottom_right - top_left).into_u32());
+
+        bitmap.data.fill(255);
+        output.blend_bitmap_at(&bitmap, top_left, background_color);
+    }
+
+    for glyph in text.iter_glyphs() {
+        let glyph_color = glyph.color().unwrap_or(color);
+
+        let position = Vec2::from(glyph.glyph().position())
+            + Vec2::new(padding as f32, padding as f32);
+
+        let positioned_glyph = glyph
+            .glyph()
+            .unpositioned()
+            .clone()
+            .positioned(rusttype::point(position.x, position.y));
+
+        #[cfg(feature = "image-loading")]
+        if let Some((bitmap, offset)) = BitmapRGBA::from_color_glyph(&positioned_glyph) {
+            // Colored glyphs keep their own colors, and only take the opacity
+            // of the text color
+            output.blend_bitmap_at(
+                &bitmap,
+                position.round().into_i32() + offset,
+                Color::from_rgba(1.0, 1.0, 1.0, glyph_color.a())
+            );
+
+            continue;
+        }
+
+        let bounding_box = match positioned_glyph.pixel_bounding_box() {
+            None => continue, // This is valid for some glyphs, e.g. space
+            Some(bounding_box) => bounding_box
+        };
+
+        let mut bitmap = BitmapRGBA::new(UVec2::new(
+            bounding_box.width() as u32,
+            bounding_box.height() as u32
+        ));
+
+        bitmap.draw_glyph(&positioned_glyph);
+
+        output.blend_bitmap_at(
+            &bitmap,
+            IVec2::new(bounding_box.min.x, bounding_box.min.y),
+            glyph_color
+        );
+    }
+
+    (size, output.data)
+}
+
 struct BitmapRGBA
 {
     data: Vec<u8>,
@@ -532,6 +708,288 @@ impl BitmapRGBA
         })
     }
 
+    /// Returns the coverage of each pixel in the bitmap, with the glyph drawn
+    /// `padding` pixels from the top left corner.
+    fn padded_glyph_coverage(
+        &self,
+        glyph: &rusttype::PositionedGlyph,
+        padding: u32
+    ) -> Vec<f32>
+    {
+        let mut coverage = vec![0.0; (self.size.x * self.size.y) as usize];
+
+        glyph.draw(|x, y, alpha| {
+            coverage[(self.size.x * (y + padding) + x + padding) as usize] = alpha;
+        });
+
+        coverage
+    }
+
+    fn draw_coverage(&mut self, coverage: &[f32])
+    {
+        for (pixel, alpha) in self.data.chunks_exact_mut(4).zip(coverage) {
+            pixel[0] = 255;
+            pixel[1] = 255;
+            pixel[2] = 255;
+            pixel[3] = (alpha.clamp(0.0, 1.0) * 255.0).round() as u8;
+        }
+    }
+
+    /// Draws the glyph with the specified effect applied. The bitmap must be
+    /// larger than the glyph's bounding box by the effect's padding on each
+    /// side.
+    fn draw_glyph_with_effect(
+        &mut self,
+        glyph: &rusttype::PositionedGlyph,
+        effect: &GlyphEffect
+    )
+    {
+        match effect {
+            GlyphEffect::None => self.draw_glyph(glyph),
+            GlyphEffect::Outline(width) => self.draw_glyph_outline(glyph, *width),
+            GlyphEffect::Blur(radius) => self.draw_glyph_blurred(glyph, *radius)
+        }
+    }
+
+    /// Draws the outline of the glyph, by expanding its shape by
+    /// `outline_width` pixels in every direction.
+    fn draw_glyph_outline(
+        &mut self,
+        glyph: &rusttype::PositionedGlyph,
+        outline_width: f32
+    )
+    {
+        let padding = GlyphEffect::Outline(outline_width).padding();
+        let coverage = self.padded_glyph_coverage(glyph, padding);
+
+        let padding = padding as i32;
+        let width = self.size.x as i32;
+        let height = self.size.y as i32;
+
+        // The weight of each nearby pixel, which is antialiased at the edge
+        // of the outline
+        let mut kernel = Vec::new();
+
+        for dy in -padding..=padding {
+            for dx in -padding..=padding {
+                let distance = ((dx * dx + dy * dy) as f32).sqrt();
+                let weight = (outline_width + 0.5 - distance).clamp(0.0, 1.0);
+
+                if weight > 0.0 {
+                    kernel.push((dx, dy, weight));
+                }
+            }
+        }
+
+        let mut outline = vec![0.0; coverage.len()];
+
+        for y in 0..height {
+            for x in 0..width {
+                let mut alpha: f32 = 0.0;
+
+                for (dx, dy, weight) in &kernel {
+                    let (source_x, source_y) = (x + dx, y + dy);
+
+                    if source_x < 0
+                        || source_y < 0
+                        || source_x >= width
+                        || source_y >= height
+                    {
+                        continue;
+                    }
+
+                    alpha = alpha
+                        .max(coverage[(source_y * width + source_x) as usize] * weight);
+                }
+
+                outline[(y * width + x) as usize] = alpha;
+            }
+        }
+
+        self.draw_coverage(&outline);
+    }
+
+    /// Draws the glyph with a Gaussian blur of the specified radius applied.
+    fn draw_glyph_blurred(&mut self, glyph: &rusttype::PositionedGlyph, radius: f32)
+    {
+        let padding = GlyphEffect::Blur(radius).padding();
+        let coverage = self.padded_glyph_coverage(glyph, padding);
+
+        if padding == 0 {
+            self.draw_coverage(&coverage);
+            return;
+        }
+
+        let padding = padding as i32;
+        let width = self.size.x as i32;
+        let height = self.size.y as i32;
+
+        // The radius covers three standard deviations
+        let sigma = radius / 3.0;
+
+        let mut kernel: Vec<f32> = (-padding..=padding)
+            .map(|offset| (-((offset * offset) as f32) / (2.0 * sigma * sigma)).exp())
+            .collect();
+
+        let total: f32 = kernel.iter().sum();
+        kernel.iter_mut().for_each(|weight| *weight /= total);
+
+        // The blur is separable, so it's applied horizontally then vertically
+        let blur_pass = |source: &[f32], step_x: i32, step_y: i32| -> Vec<f32> {
+            let mut output = vec![0.0; source.len()];
+
+            for y in 0..height {
+                for x in 0..width {
+                    let mut value = 0.0;
+
+                    for (i, weight) in kernel.iter().enumerate() {
+                        let offset = i as i32 - padding;
+                        let (source_x, source_y) =
+                            (x + offset * step_x, y + offset * step_y);
+
+                        if source_x >= 0
+                            && source_y >= 0
+                            && source_x < width
+                            && source_y < height
+                        {
+                            value +=
+                                source[(source_y * width + source_x) as usize] * weight;
+                        }
+                    }
+
+                    output[(y * width + x) as usize] = value;
+                }
+            }
+
+            output
+        };
+
+        let blurred = blur_pass(&blur_pass(&coverage, 1, 0), 0, 1);
+
+        self.draw_coverage(&blurred);
+    }
+
+    /// Decodes the color bitmap for the glyph (from the `sbix` or `CBDT`
+    /// table), scaled to the size of the glyph. Returns the bitmap and the
+    /// offset of its top left corner from the glyph's origin, or `None` if
+    /// the font has no color bitmap for the glyph.
+    #[cfg(feature = "image-loading")]
+    fn from_color_glyph(glyph: &rusttype::PositionedGlyph) -> Option<(Self, IVec2)>
+    {
+        let font = glyph.font();
+
+        let pixels_per_em =
+            font.scale_for_pixel_height(glyph.scale().y) * font.units_per_em() as f32;
+
+        let raster_image = font::font_face(font)
+            .glyph_raster_image(glyph.id().into(), pixels_per_em.round() as u16)?;
+
+        if raster_image.format != owned_ttf_parser::RasterImageFormat::PNG {
+            return None;
+        }
+
+        let image = match image::load_from_memory_with_format(
+            raster_image.data,
+            image::ImageFormat::Png
+        ) {
+            Ok(image) => image.to_rgba8(),
+            Err(err) => {
+                log::error!("Failed to decode color glyph: {:?}", err);
+                return None;
+            }
+        };
+
+        let factor = pixels_per_em / raster_image.pixels_per_em as f32;
+
+        let size = UVec2::new(
+            ((image.width() as f32 * factor).round() as u32).max(1),
+            ((image.height() as f32 * factor).round() as u32).max(1)
+        );
+
+        if size.x > GlyphCacheTexture::SIZE || size.y > GlyphCacheTexture::SIZE {
+            log::error!(
+                "Color glyph too big to render ({}x{}). Limit is {} px.",
+                size.x,
+                size.y,
+                GlyphCacheTexture::SIZE
+            );
+
+            return None;
+        }
+
+        // The raster image offset is to its bottom left corner, with y pointing
+        // upwards
+        let offset = IVec2::new(
+            (raster_image.x as f32 * factor).round() as i32,
+            -((raster_image.y as f32 + image.height() as f32) * factor).round() as i32
+        );
+
+        let image = image::imageops::resize(
+            &image,
+            size.x,
+            size.y,
+            image::imageops::FilterType::Triangle
+        );
+
+        Some((
+            BitmapRGBA {
+                data: image.into_raw(),
+                size
+            },
+            offset
+        ))
+    }
+
+    /// Draws the bitmap on top of this one, multiplied by `color`, using
+    /// non-premultiplied alpha. Pixels outside this bitmap are skipped.
+    fn blend_bitmap_at(&mut self, bitmap: &Self, position: IVec2, color: Color)
+    {
+        for y in 0..bitmap.size.y {
+            let dest_y = position.y + y as i32;
+
+            if dest_y < 0 || dest_y >= self.size.y as i32 {
+                continue;
+            }
+
+            for x in 0..bitmap.size.x {
+                let dest_x = position.x + x as i32;
+
+                if dest_x < 0 || dest_x >= self.size.x as i32 {
+                    continue;
+                }
+
+                let src = (4 * (bitmap.size.x * y + x)) as usize;
+                let dest = (4 * (self.size.x * dest_y as u32 + dest_x as u32)) as usize;
+
+                let src_pixel = &bitmap.data[src..src + 4];
+                let dest_pixel = &mut self.data[dest..dest + 4];
+
+                let src_alpha = src_pixel[3] as f32 / 255.0 * color.a();
+                let dest_alpha = dest_pixel[3] as f32 / 255.0;
+                let out_alpha = src_alpha + dest_alpha * (1.0 - src_alpha);
+
+                if out_alpha <= 0.0 {
+                    continue;
+                }
+
+                let channels = [color.r(), color.g(), color.b()];
+
+                for channel in 0..3 {
+                    let src_value = src_pixel[channel] as f32 / 255.0 * channels[channel];
+                    let dest_value = dest_pixel[channel] as f32 / 255.0;
+
+                    let value = (src_value * src_alpha
+                        + dest_value * dest_alpha * (1.0 - src_alpha))
+                        / out_alpha;
+
+                    dest_pixel[channel] = (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+                }
+
+                dest_pixel[3] = (out_alpha.clamp(0.0, 1.0) * 255.0).round() as u8;
+            }
+        }
+    }
+
     #[inline]
     fn draw_bitmap_at(&mut self, bitmap: &Self, position: &UVec2)
     {
@@ -588,6 +1046,7 @@ struct GlyphCacheEntry
 {
     glyph_bitmap: Rc<BitmapRGBA>,
     bounding_box_offset: IVec2,
+    colored: bool,
     texture_id: Option<usize>
 }
 
@@ -707,3 +1166,47 @@ impl GlyphCacheTexture
         }
     }
 }
+
+#[cfg(all(test, feature = "image-loading"))]
+mod test
+{
+    use crate::color::Color;
+    use crate::font::{Font, TextLayout, TextOptions};
+    use crate::image::ImageDataType;
+    use crate::GLRenderer;
+
+    #[test]
+    fn test_color_glyph()
+    {
+        let font = Font::new(include_bytes!(
+            "../test/assets/fonts/NotoColorEmoji-Subset.ttf"
+        ))
+        .unwrap();
+
+        let text = font.layout_text("\u{1F42F}", 48.0, TextOptions::new());
+
+        let mut renderer = GLRenderer::new_software((64, 64)).unwrap();
+
+        // The glyph goes through the glyph cache texture, and is drawn in its
+        // own colors rather than in the text color
+        let capture = renderer.draw_frame(|graphics| {
+            graphics.clear_screen(Color::BLACK);
+            graphics.draw_text((0.0, 0.0), Color::WHITE, &text);
+            graphics.capture(ImageDataType::RGB)
+        });
+
+        let colored_pixels = capture
+            .data()
+            .chunks_exact(3)
+            .filter(|pixel| {
+                pixel[0].abs_diff(pixel[1]) > 32 || pixel[1].abs_diff(pixel[2]) > 32
+            })
+            .count();
+
+        assert!(
+            colored_pixels > 100,
+            "Only {} colored pixels",
+            colored_pixels
+        );
+    }
+}
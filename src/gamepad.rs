@@ -0,0 +1,112 @@
+/*
+ *  Copyright 2021 QuantumBadger
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+/// Identifies a gamepad. If a gamepad is disconnected and later reconnected,
+/// it will usually be given the same ID.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct GamepadId(usize);
+
+impl GamepadId
+{
+    #[cfg_attr(any(doc, doctest), allow(dead_code))]
+    #[inline]
+    pub(crate) fn new(id: usize) -> Self
+    {
+        GamepadId(id)
+    }
+
+    /// Returns the numeric value of this ID.
+    #[inline]
+    #[must_use]
+    pub fn value(&self) -> usize
+    {
+        self.0
+    }
+}
+
+/// Identifies a gamepad button, using the layout of a typical modern
+/// controller.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum GamepadButton
+{
+    /// The bottom action button (for example, A on an Xbox controller).
+    South,
+    /// The right action button (for example, B on an Xbox controller).
+    East,
+    /// The top action button (for example, Y on an Xbox controller).
+    North,
+    /// The left action button (for example, X on an Xbox controller).
+    West,
+    /// The additional C action button, found on some older controllers.
+    C,
+    /// The additional Z action button, found on some older controllers.
+    Z,
+    /// The left shoulder button.
+    LeftTrigger,
+    /// The second left shoulder button, usually an analog trigger.
+    LeftTrigger2,
+    /// The right shoulder button.
+    RightTrigger,
+    /// The second right shoulder button, usually an analog trigger.
+    RightTrigger2,
+    /// The select/back/share button.
+    Select,
+    /// The start/menu/options button.
+    Start,
+    /// The central button, such as the Xbox or PS button.
+    Mode,
+    /// Pressing down on the left stick.
+    LeftThumb,
+    /// Pressing down on the right stick.
+    RightThumb,
+    /// The up button on the directional pad.
+    DPadUp,
+    /// The down button on the directional pad.
+    DPadDown,
+    /// The left button on the directional pad.
+    DPadLeft,
+    /// The right button on the directional pad.
+    DPadRight
+}
+
+/// Identifies a gamepad axis. Axis values are in the range `-1.0` to `1.0`.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum GamepadAxis
+{
+    /// The horizontal position of the left stick. Positive values are to the
+    /// right.
+    LeftStickX,
+    /// The vertical position of the left stick. Positive values are upwards.
+    LeftStickY,
+    /// An additional left axis, used by some controllers for the trigger.
+    LeftZ,
+    /// The horizontal position of the right stick. Positive values are to
+    /// the right.
+    RightStickX,
+    /// The vertical position of the right stick. Positive values are
+    /// upwards.
+    RightStickY,
+    /// An additional right axis, used by some controllers for the trigger.
+    RightZ,
+    /// The horizontal position of the directional pad, on controllers which
+    /// report it as an axis.
+    DPadX,
+    /// The vertical position of the directional pad, on controllers which
+    /// report it as an axis.
+    DPadY
+}
@@ -34,6 +34,9 @@ pub mod types
     pub type GLTypeProgram = glow::Program;
     pub type GLTypeBuffer = glow::Buffer;
     pub type GLTypeTexture = glow::Texture;
+    pub type GLTypeFramebuffer = glow::Framebuffer;
+    pub type GLTypeRenderbuffer = glow::Renderbuffer;
+    pub type GLTypeVertexArray = glow::VertexArray;
     pub type GLTypeUniformLocation = glow::UniformLocation;
 }
 
@@ -51,21 +54,37 @@ pub mod constants
     pub const GL_BLEND: GLenum = glow::BLEND;
 
     pub const GL_SCISSOR_TEST: GLenum = glow::SCISSOR_TEST;
+    pub const GL_STENCIL_TEST: GLenum = glow::STENCIL_TEST;
+
+    pub const GL_ALWAYS: GLenum = glow::ALWAYS;
+    pub const GL_EQUAL: GLenum = glow::EQUAL;
+    pub const GL_KEEP: GLenum = glow::KEEP;
+    pub const GL_REPLACE: GLenum = glow::REPLACE;
 
     pub const GL_ONE: GLenum = glow::ONE;
-    pub const GL_SRC_ALPHA: GLenum = glow::SRC_ALPHA;
     pub const GL_ONE_MINUS_SRC_ALPHA: GLenum = glow::ONE_MINUS_SRC_ALPHA;
+    pub const GL_ONE_MINUS_SRC_COLOR: GLenum = glow::ONE_MINUS_SRC_COLOR;
+    pub const GL_DST_COLOR: GLenum = glow::DST_COLOR;
 
     pub const GL_NEAREST: GLenum = glow::NEAREST;
     pub const GL_LINEAR: GLenum = glow::LINEAR;
+    pub const GL_LINEAR_MIPMAP_LINEAR: GLenum = glow::LINEAR_MIPMAP_LINEAR;
 
     pub const GL_ARRAY_BUFFER: GLenum = glow::ARRAY_BUFFER;
     pub const GL_ELEMENT_ARRAY_BUFFER: GLenum = glow::ELEMENT_ARRAY_BUFFER;
 
     pub const GL_DYNAMIC_DRAW: GLenum = glow::DYNAMIC_DRAW;
 
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub const GL_PIXEL_PACK_BUFFER: GLenum = glow::PIXEL_PACK_BUFFER;
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub const GL_STREAM_READ: GLenum = glow::STREAM_READ;
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub const GL_MAP_READ_BIT: GLenum = glow::MAP_READ_BIT;
+
     pub const GL_FLOAT: GLenum = glow::FLOAT;
     pub const GL_UNSIGNED_BYTE: GLenum = glow::UNSIGNED_BYTE;
+    pub const GL_UNSIGNED_INT: GLenum = glow::UNSIGNED_INT;
 
     pub const GL_R8: GLenum = glow::R8;
     pub const GL_RGB8: GLenum = glow::RGB8;
@@ -80,10 +99,13 @@ pub mod constants
     pub const GL_TEXTURE_MIN_FILTER: GLenum = glow::TEXTURE_MIN_FILTER;
     pub const GL_TEXTURE_MAG_FILTER: GLenum = glow::TEXTURE_MAG_FILTER;
     pub const GL_CLAMP_TO_EDGE: GLenum = glow::CLAMP_TO_EDGE;
+    pub const GL_REPEAT: GLenum = glow::REPEAT;
+    pub const GL_MIRRORED_REPEAT: GLenum = glow::MIRRORED_REPEAT;
 
     pub const GL_TRIANGLES: GLenum = glow::TRIANGLES;
 
     pub const GL_COLOR_BUFFER_BIT: GLenum = glow::COLOR_BUFFER_BIT;
+    pub const GL_STENCIL_BUFFER_BIT: GLenum = glow::STENCIL_BUFFER_BIT;
 
     pub const GL_NO_ERROR: GLenum = glow::NO_ERROR;
     pub const GL_INVALID_ENUM: GLenum = glow::INVALID_ENUM;
@@ -102,6 +124,17 @@ pub mod constants
     pub const GL_INFO_LOG_LENGTH: GLenum = glow::INFO_LOG_LENGTH;
 
     pub const GL_UNPACK_ALIGNMENT: GLenum = glow::UNPACK_ALIGNMENT;
+    pub const GL_PACK_ALIGNMENT: GLenum = glow::PACK_ALIGNMENT;
+
+    pub const GL_FRAMEBUFFER: GLenum = glow::FRAMEBUFFER;
+    pub const GL_COLOR_ATTACHMENT0: GLenum = glow::COLOR_ATTACHMENT0;
+    pub const GL_FRAMEBUFFER_COMPLETE: GLenum = glow::FRAMEBUFFER_COMPLETE;
+    pub const GL_READ_FRAMEBUFFER: GLenum = glow::READ_FRAMEBUFFER;
+    pub const GL_DRAW_FRAMEBUFFER: GLenum = glow::DRAW_FRAMEBUFFER;
+    pub const GL_RENDERBUFFER: GLenum = glow::RENDERBUFFER;
+    pub const GL_STENCIL_INDEX8: GLenum = glow::STENCIL_INDEX8;
+    pub const GL_STENCIL_ATTACHMENT: GLenum = glow::STENCIL_ATTACHMENT;
+    pub const GL_MAX_SAMPLES: GLenum = glow::MAX_SAMPLES;
 }
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
@@ -148,10 +181,78 @@ pub trait GLBackend
     unsafe fn gl_delete_shader(&self, handle: GLTypeShader);
     unsafe fn gl_delete_buffer(&self, handle: GLTypeBuffer);
     unsafe fn gl_delete_texture(&self, handle: GLTypeTexture);
+    unsafe fn gl_delete_framebuffer(&self, handle: GLTypeFramebuffer);
+    unsafe fn gl_delete_renderbuffer(&self, handle: GLTypeRenderbuffer);
+    unsafe fn gl_delete_vertex_array(&self, handle: GLTypeVertexArray);
     unsafe fn gl_active_texture(&self, unit: GLenum);
     unsafe fn gl_bind_texture(&self, target: GLenum, handle: GLTypeTexture);
+    unsafe fn gl_bind_framebuffer(
+        &self,
+        target: GLenum,
+        handle: Option<GLTypeFramebuffer>
+    );
+    unsafe fn gl_framebuffer_texture_2d(
+        &self,
+        target: GLenum,
+        attachment: GLenum,
+        texture_target: GLenum,
+        texture: GLTypeTexture,
+        level: GLint
+    );
+    unsafe fn gl_check_framebuffer_status(&self, target: GLenum) -> GLenum;
+    unsafe fn gl_bind_renderbuffer(&self, target: GLenum, handle: GLTypeRenderbuffer);
+    unsafe fn gl_renderbuffer_storage(
+        &self,
+        target: GLenum,
+        internal_format: GLenum,
+        width: GLsizei,
+        height: GLsizei
+    );
+    unsafe fn gl_renderbuffer_storage_multisample(
+        &self,
+        target: GLenum,
+        samples: GLsizei,
+        internal_format: GLenum,
+        width: GLsizei,
+        height: GLsizei
+    );
+    unsafe fn gl_framebuffer_renderbuffer(
+        &self,
+        target: GLenum,
+        attachment: GLenum,
+        renderbuffer_target: GLenum,
+        renderbuffer: GLTypeRenderbuffer
+    );
+
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn gl_blit_framebuffer(
+        &self,
+        src_x0: GLint,
+        src_y0: GLint,
+        src_x1: GLint,
+        src_y1: GLint,
+        dst_x0: GLint,
+        dst_y0: GLint,
+        dst_x1: GLint,
+        dst_y1: GLint,
+        mask: GLenum,
+        filter: GLenum
+    );
+
+    #[must_use]
+    unsafe fn gl_get_parameter_i32(&self, parameter: GLenum) -> GLint;
     unsafe fn gl_enable(&self, cap: GLenum);
     unsafe fn gl_disable(&self, cap: GLenum);
+    unsafe fn gl_color_mask(&self, red: bool, green: bool, blue: bool, alpha: bool);
+    unsafe fn gl_stencil_func(&self, func: GLenum, reference: GLint, mask: GLuint);
+    unsafe fn gl_stencil_op(
+        &self,
+        stencil_fail: GLenum,
+        depth_fail: GLenum,
+        pass: GLenum
+    );
+    unsafe fn gl_stencil_mask(&self, mask: GLuint);
+    unsafe fn gl_clear_stencil(&self, stencil: GLint);
     #[allow(dead_code)]
     unsafe fn gl_blend_func(&self, sfactor: GLenum, dfactor: GLenum);
     unsafe fn gl_blend_func_separate(
@@ -164,16 +265,69 @@ pub trait GLBackend
     unsafe fn gl_use_program(&self, handle: GLTypeProgram);
     unsafe fn gl_enable_vertex_attrib_array(&self, handle: GLuint);
     unsafe fn gl_disable_vertex_attrib_array(&self, handle: GLuint);
+    unsafe fn gl_vertex_attrib_divisor(&self, handle: GLuint, divisor: GLuint);
     unsafe fn gl_uniform_1f(&self, handle: &GLTypeUniformLocation, value: f32);
     unsafe fn gl_uniform_1i(&self, handle: &GLTypeUniformLocation, value: GLint);
+    unsafe fn gl_uniform_3f(
+        &self,
+        handle: &GLTypeUniformLocation,
+        x: f32,
+        y: f32,
+        z: f32
+    );
+    unsafe fn gl_uniform_4f(
+        &self,
+        handle: &GLTypeUniformLocation,
+        x: f32,
+        y: f32,
+        z: f32,
+        w: f32
+    );
     unsafe fn gl_attach_shader(&self, program: GLTypeProgram, shader: GLTypeShader);
     unsafe fn gl_link_program(&self, program: GLTypeProgram);
     unsafe fn gl_shader_source(&self, handle: GLTypeShader, source: &str);
     unsafe fn gl_compile_shader(&self, handle: GLTypeShader);
     unsafe fn gl_tex_parameter_i(&self, target: GLenum, parameter: GLenum, value: GLint);
+    unsafe fn gl_generate_mipmap(&self, target: GLenum);
     unsafe fn gl_bind_buffer(&self, target: GLenum, handle: GLTypeBuffer);
     unsafe fn gl_buffer_data(&self, target: GLenum, data: &[u8], usage: GLenum);
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    unsafe fn gl_buffer_data_size(&self, target: GLenum, size: GLsizei, usage: GLenum);
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    unsafe fn gl_unbind_buffer(&self, target: GLenum);
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    unsafe fn gl_get_buffer_sub_data(
+        &self,
+        target: GLenum,
+        offset: GLint,
+        data: &mut [MaybeUninit<u8>]
+    );
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    unsafe fn gl_map_buffer_range(
+        &self,
+        target: GLenum,
+        offset: GLint,
+        length: GLsizei,
+        access: GLenum
+    ) -> *mut u8;
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    unsafe fn gl_unmap_buffer(&self, target: GLenum);
     unsafe fn gl_draw_arrays(&self, mode: GLenum, first: GLint, count: GLsizei);
+    unsafe fn gl_draw_arrays_instanced(
+        &self,
+        mode: GLenum,
+        first: GLint,
+        count: GLsizei,
+        instance_count: GLsizei
+    );
+    unsafe fn gl_draw_elements(
+        &self,
+        mode: GLenum,
+        count: GLsizei,
+        element_type: GLenum,
+        offset: GLint
+    );
+    unsafe fn gl_bind_vertex_array(&self, handle: Option<GLTypeVertexArray>);
     unsafe fn gl_clear_color(&self, r: f32, g: f32, b: f32, a: f32);
     unsafe fn gl_clear(&self, mask: GLenum);
     #[allow(dead_code)]
@@ -237,6 +391,18 @@ pub trait GLBackend
         &self
     ) -> Result<GLTypeTexture, BacktraceError<ErrorMessage>>;
 
+    unsafe fn gl_gen_framebuffer(
+        &self
+    ) -> Result<GLTypeFramebuffer, BacktraceError<ErrorMessage>>;
+
+    unsafe fn gl_gen_renderbuffer(
+        &self
+    ) -> Result<GLTypeRenderbuffer, BacktraceError<ErrorMessage>>;
+
+    unsafe fn gl_gen_vertex_array(
+        &self
+    ) -> Result<GLTypeVertexArray, BacktraceError<ErrorMessage>>;
+
     #[must_use]
     unsafe fn gl_get_error(&self) -> GLenum;
 
@@ -310,6 +476,16 @@ pub trait GLBackend
         self.gl_buffer_data(target, data, usage)
     }
 
+    unsafe fn gl_buffer_data_u32(&self, target: GLenum, data: &[u32], usage: GLenum)
+    {
+        let data = std::slice::from_raw_parts(
+            data.as_ptr() as *const u8,
+            std::mem::size_of_val(data)
+        );
+
+        self.gl_buffer_data(target, data, usage)
+    }
+
     #[allow(clippy::too_many_arguments)]
     unsafe fn gl_read_pixels(
         &self,
@@ -321,6 +497,19 @@ pub trait GLBackend
         data_type: GLenum,
         data: &mut [MaybeUninit<u8>]
     );
+
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn gl_read_pixels_to_buffer(
+        &self,
+        x: GLint,
+        y: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        format: GLenum,
+        data_type: GLenum,
+        offset: GLuint
+    );
 }
 
 pub struct GLBackendGlow
@@ -359,6 +548,21 @@ impl GLBackend for GLBackendGlow
         self.context.delete_texture(handle)
     }
 
+    unsafe fn gl_delete_framebuffer(&self, handle: GLTypeFramebuffer)
+    {
+        self.context.delete_framebuffer(handle)
+    }
+
+    unsafe fn gl_delete_renderbuffer(&self, handle: GLTypeRenderbuffer)
+    {
+        self.context.delete_renderbuffer(handle)
+    }
+
+    unsafe fn gl_delete_vertex_array(&self, handle: GLTypeVertexArray)
+    {
+        self.context.delete_vertex_array(handle)
+    }
+
     unsafe fn gl_active_texture(&self, unit: GLenum)
     {
         self.context.active_texture(unit)
@@ -369,11 +573,143 @@ impl GLBackend for GLBackendGlow
         self.context.bind_texture(target, Some(handle))
     }
 
+    unsafe fn gl_bind_framebuffer(
+        &self,
+        target: GLenum,
+        handle: Option<GLTypeFramebuffer>
+    )
+    {
+        self.context.bind_framebuffer(target, handle)
+    }
+
+    unsafe fn gl_framebuffer_texture_2d(
+        &self,
+        target: GLenum,
+        attachment: GLenum,
+        texture_target: GLenum,
+        texture: GLTypeTexture,
+        level: GLint
+    )
+    {
+        self.context.framebuffer_texture_2d(
+            target,
+            attachment,
+            texture_target,
+            Some(texture),
+            level
+        )
+    }
+
+    unsafe fn gl_check_framebuffer_status(&self, target: GLenum) -> GLenum
+    {
+        self.context.check_framebuffer_status(target)
+    }
+
+    unsafe fn gl_bind_renderbuffer(&self, target: GLenum, handle: GLTypeRenderbuffer)
+    {
+        self.context.bind_renderbuffer(target, Some(handle))
+    }
+
+    unsafe fn gl_renderbuffer_storage(
+        &self,
+        target: GLenum,
+        internal_format: GLenum,
+        width: GLsizei,
+        height: GLsizei
+    )
+    {
+        self.context
+            .renderbuffer_storage(target, internal_format, width, height)
+    }
+
+    unsafe fn gl_renderbuffer_storage_multisample(
+        &self,
+        target: GLenum,
+        samples: GLsizei,
+        internal_format: GLenum,
+        width: GLsizei,
+        height: GLsizei
+    )
+    {
+        self.context.renderbuffer_storage_multisample(
+            target,
+            samples,
+            internal_format,
+            width,
+            height
+        )
+    }
+
+    unsafe fn gl_framebuffer_renderbuffer(
+        &self,
+        target: GLenum,
+        attachment: GLenum,
+        renderbuffer_target: GLenum,
+        renderbuffer: GLTypeRenderbuffer
+    )
+    {
+        self.context.framebuffer_renderbuffer(
+            target,
+            attachment,
+            renderbuffer_target,
+            Some(renderbuffer)
+        )
+    }
+
+    unsafe fn gl_blit_framebuffer(
+        &self,
+        src_x0: GLint,
+        src_y0: GLint,
+        src_x1: GLint,
+        src_y1: GLint,
+        dst_x0: GLint,
+        dst_y0: GLint,
+        dst_x1: GLint,
+        dst_y1: GLint,
+        mask: GLenum,
+        filter: GLenum
+    )
+    {
+        self.context.blit_framebuffer(
+            src_x0, src_y0, src_x1, src_y1, dst_x0, dst_y0, dst_x1, dst_y1, mask, filter
+        )
+    }
+
+    unsafe fn gl_get_parameter_i32(&self, parameter: GLenum) -> GLint
+    {
+        self.context.get_parameter_i32(parameter)
+    }
+
     unsafe fn gl_enable(&self, cap: GLenum)
     {
         self.context.enable(cap)
     }
 
+    unsafe fn gl_color_mask(&self, red: bool, green: bool, blue: bool, alpha: bool)
+    {
+        self.context.color_mask(red, green, blue, alpha)
+    }
+
+    unsafe fn gl_stencil_func(&self, func: GLenum, reference: GLint, mask: GLuint)
+    {
+        self.context.stencil_func(func, reference, mask)
+    }
+
+    unsafe fn gl_stencil_op(&self, stencil_fail: GLenum, depth_fail: GLenum, pass: GLenum)
+    {
+        self.context.stencil_op(stencil_fail, depth_fail, pass)
+    }
+
+    unsafe fn gl_stencil_mask(&self, mask: GLuint)
+    {
+        self.context.stencil_mask(mask)
+    }
+
+    unsafe fn gl_clear_stencil(&self, stencil: GLint)
+    {
+        self.context.clear_stencil(stencil)
+    }
+
     unsafe fn gl_disable(&self, cap: GLenum)
     {
         self.context.disable(cap)
@@ -411,6 +747,11 @@ impl GLBackend for GLBackendGlow
         self.context.disable_vertex_attrib_array(handle)
     }
 
+    unsafe fn gl_vertex_attrib_divisor(&self, handle: GLuint, divisor: GLuint)
+    {
+        self.context.vertex_attrib_divisor(handle, divisor)
+    }
+
     unsafe fn gl_uniform_1f(&self, handle: &GLTypeUniformLocation, value: f32)
     {
         self.context.uniform_1_f32(Some(handle), value)
@@ -421,6 +762,23 @@ impl GLBackend for GLBackendGlow
         self.context.uniform_1_i32(Some(handle), value)
     }
 
+    unsafe fn gl_uniform_3f(&self, handle: &GLTypeUniformLocation, x: f32, y: f32, z: f32)
+    {
+        self.context.uniform_3_f32(Some(handle), x, y, z)
+    }
+
+    unsafe fn gl_uniform_4f(
+        &self,
+        handle: &GLTypeUniformLocation,
+        x: f32,
+        y: f32,
+        z: f32,
+        w: f32
+    )
+    {
+        self.context.uniform_4_f32(Some(handle), x, y, z, w)
+    }
+
     unsafe fn gl_attach_shader(&self, program: GLTypeProgram, shader: GLTypeShader)
     {
         self.context.attach_shader(program, shader)
@@ -446,6 +804,11 @@ impl GLBackend for GLBackendGlow
         self.context.tex_parameter_i32(target, parameter, value)
     }
 
+    unsafe fn gl_generate_mipmap(&self, target: u32)
+    {
+        self.context.generate_mipmap(target)
+    }
+
     unsafe fn gl_bind_buffer(&self, target: u32, handle: GLTypeBuffer)
     {
         self.context.bind_buffer(target, Some(handle))
@@ -456,11 +819,80 @@ impl GLBackend for GLBackendGlow
         self.context.buffer_data_u8_slice(target, data, usage)
     }
 
+    unsafe fn gl_buffer_data_size(&self, target: u32, size: i32, usage: u32)
+    {
+        self.context.buffer_data_size(target, size, usage)
+    }
+
+    unsafe fn gl_unbind_buffer(&self, target: u32)
+    {
+        self.context.bind_buffer(target, None)
+    }
+
+    unsafe fn gl_get_buffer_sub_data(
+        &self,
+        target: u32,
+        offset: i32,
+        data: &mut [MaybeUninit<u8>]
+    )
+    {
+        let data =
+            std::slice::from_raw_parts_mut(data.as_mut_ptr() as *mut u8, data.len());
+
+        self.context.get_buffer_sub_data(target, offset, data)
+    }
+
+    unsafe fn gl_map_buffer_range(
+        &self,
+        target: u32,
+        offset: i32,
+        length: i32,
+        access: u32
+    ) -> *mut u8
+    {
+        self.context
+            .map_buffer_range(target, offset, length, access)
+    }
+
+    unsafe fn gl_unmap_buffer(&self, target: u32)
+    {
+        self.context.unmap_buffer(target)
+    }
+
     unsafe fn gl_draw_arrays(&self, mode: u32, first: i32, count: i32)
     {
         self.context.draw_arrays(mode, first, count)
     }
 
+    unsafe fn gl_draw_arrays_instanced(
+        &self,
+        mode: u32,
+        first: i32,
+        count: i32,
+        instance_count: i32
+    )
+    {
+        self.context
+            .draw_arrays_instanced(mode, first, count, instance_count)
+    }
+
+    unsafe fn gl_draw_elements(
+        &self,
+        mode: GLenum,
+        count: GLsizei,
+        element_type: GLenum,
+        offset: GLint
+    )
+    {
+        self.context
+            .draw_elements(mode, count, element_type, offset)
+    }
+
+    unsafe fn gl_bind_vertex_array(&self, handle: Option<GLTypeVertexArray>)
+    {
+        self.context.bind_vertex_array(handle)
+    }
+
     unsafe fn gl_clear_color(&self, r: f32, g: f32, b: f32, a: f32)
     {
         self.context.clear_color(r, g, b, a)
@@ -629,6 +1061,39 @@ impl GLBackend for GLBackendGlow
         Ok(handle)
     }
 
+    unsafe fn gl_gen_framebuffer(
+        &self
+    ) -> Result<GLTypeFramebuffer, BacktraceError<ErrorMessage>>
+    {
+        let handle = self.context.create_framebuffer().map_err(|err| {
+            ErrorMessage::msg(format!("Failed to create framebuffer: {err}"))
+        })?;
+
+        Ok(handle)
+    }
+
+    unsafe fn gl_gen_renderbuffer(
+        &self
+    ) -> Result<GLTypeRenderbuffer, BacktraceError<ErrorMessage>>
+    {
+        let handle = self.context.create_renderbuffer().map_err(|err| {
+            ErrorMessage::msg(format!("Failed to create renderbuffer: {err}"))
+        })?;
+
+        Ok(handle)
+    }
+
+    unsafe fn gl_gen_vertex_array(
+        &self
+    ) -> Result<GLTypeVertexArray, BacktraceError<ErrorMessage>>
+    {
+        let handle = self.context.create_vertex_array().map_err(|err| {
+            ErrorMessage::msg(format!("Failed to create vertex array: {err}"))
+        })?;
+
+        Ok(handle)
+    }
+
     unsafe fn gl_get_error(&self) -> GLenum
     {
         self.context.get_error()
@@ -702,4 +1167,26 @@ impl GLBackend for GLBackendGlow
             PixelPackData::Slice(data)
         )
     }
+
+    unsafe fn gl_read_pixels_to_buffer(
+        &self,
+        x: GLint,
+        y: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        format: GLenum,
+        data_type: GLenum,
+        offset: GLuint
+    )
+    {
+        self.context.read_pixels(
+            x,
+            y,
+            width,
+            height,
+            format,
+            data_type,
+            PixelPackData::BufferOffset(offset)
+        )
+    }
 }
@@ -0,0 +1,1981 @@
+/*
+ *  Copyright 2021 QuantumBadger
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::mem::MaybeUninit;
+
+use crate::error::{BacktraceError, ErrorMessage};
+use crate::glbackend::constants::*;
+use crate::glbackend::types::*;
+use crate::glbackend::GLBackend;
+
+const ATTRIBUTE_NAMES: [&str; 5] = [
+    "in_Position",
+    "in_Color",
+    "in_TextureCoord",
+    "in_TextureMix",
+    "in_CircleMix"
+];
+
+const ATTR_POSITION: usize = 0;
+const ATTR_COLOR: usize = 1;
+const ATTR_TEXTURE_COORD: usize = 2;
+const ATTR_TEXTURE_MIX: usize = 3;
+const ATTR_CIRCLE_MIX: usize = 4;
+
+const UNIFORM_NAMES: [&str; 8] = [
+    "in_ScaleX",
+    "in_ScaleY",
+    "in_OffsetY",
+    "in_TransformRow0",
+    "in_TransformRow1",
+    "in_Tint",
+    "in_Texture",
+    "in_SmoothCircleEdges"
+];
+
+const UNIFORM_SCALE_X: usize = 0;
+const UNIFORM_SCALE_Y: usize = 1;
+const UNIFORM_OFFSET_Y: usize = 2;
+const UNIFORM_TRANSFORM_ROW_0: usize = 3;
+const UNIFORM_TRANSFORM_ROW_1: usize = 4;
+const UNIFORM_TINT: usize = 5;
+const UNIFORM_TEXTURE: usize = 6;
+const UNIFORM_SMOOTH_CIRCLE_EDGES: usize = 7;
+
+/// Vertex positions are snapped to this many subpixel steps, so that edges
+/// shared between triangles are rasterized exactly once.
+const SUBPIXEL_STEPS: f32 = 256.0;
+
+type Rgba = [u8; 4];
+
+#[derive(Debug, Clone, Default)]
+struct Plane<T>
+{
+    width: usize,
+    height: usize,
+    data: Vec<T>
+}
+
+impl<T: Copy + Default> Plane<T>
+{
+    fn new(width: usize, height: usize) -> Self
+    {
+        Plane {
+            width,
+            height,
+            data: vec![T::default(); width * height]
+        }
+    }
+
+    #[inline]
+    fn get(&self, x: usize, y: usize) -> T
+    {
+        self.data[y * self.width + x]
+    }
+
+    #[inline]
+    fn set(&mut self, x: usize, y: usize, value: T)
+    {
+        self.data[y * self.width + x] = value;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct IntRect
+{
+    x0: i32,
+    y0: i32,
+    x1: i32,
+    y1: i32
+}
+
+impl IntRect
+{
+    fn new(x: i32, y: i32, width: i32, height: i32) -> Self
+    {
+        IntRect {
+            x0: x,
+            y0: y,
+            x1: x + width,
+            y1: y + height
+        }
+    }
+
+    fn intersect(&self, other: &IntRect) -> Self
+    {
+        IntRect {
+            x0: self.x0.max(other.x0),
+            y0: self.y0.max(other.y0),
+            x1: self.x1.min(other.x1),
+            y1: self.y1.min(other.y1)
+        }
+    }
+}
+
+struct Texture
+{
+    /// The base image, followed by any mipmaps.
+    levels: Vec<Plane<Rgba>>,
+    wrap_s: GLenum,
+    wrap_t: GLenum,
+    min_filter: GLenum,
+    mag_filter: GLenum
+}
+
+impl Texture
+{
+    fn new() -> Self
+    {
+        Texture {
+            levels: vec![Plane::default()],
+            wrap_s: GL_REPEAT,
+            wrap_t: GL_REPEAT,
+            min_filter: GL_LINEAR_MIPMAP_LINEAR,
+            mag_filter: GL_LINEAR
+        }
+    }
+
+    fn generate_mipmaps(&mut self)
+    {
+        self.levels.truncate(1);
+
+        loop {
+            let previous = self.levels.last().unwrap();
+
+            if previous.width <= 1 && previous.height <= 1 {
+                break;
+            }
+
+            let mut level =
+                Plane::new((previous.width / 2).max(1), (previous.height / 2).max(1));
+
+            for y in 0..level.height {
+                for x in 0..level.width {
+                    let mut sum = [0u32; 4];
+
+                    for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                        let texel = previous.get(
+                            (x * 2 + dx).min(previous.width - 1),
+                            (y * 2 + dy).min(previous.height - 1)
+                        );
+
+                        for (sum, value) in sum.iter_mut().zip(texel) {
+                            *sum += value as u32;
+                        }
+                    }
+
+                    level.set(x, y, sum.map(|sum| ((sum + 2) / 4) as u8));
+                }
+            }
+
+            self.levels.push(level);
+        }
+    }
+
+    fn sample(&self, u: f32, v: f32, lod: f32) -> [f32; 4]
+    {
+        if self.levels[0].data.is_empty() {
+            return [0.0, 0.0, 0.0, 1.0];
+        }
+
+        if lod <= 0.0 {
+            return self.sample_level(0, self.mag_filter, u, v);
+        }
+
+        match self.min_filter {
+            GL_LINEAR_MIPMAP_LINEAR if self.levels.len() > 1 => {
+                let lod = lod.min((self.levels.len() - 1) as f32);
+                let lower = lod.floor() as usize;
+                let upper = lod.ceil() as usize;
+
+                let a = self.sample_level(lower, GL_LINEAR, u, v);
+                let b = self.sample_level(upper, GL_LINEAR, u, v);
+
+                lerp4(a, b, lod - lower as f32)
+            }
+            GL_NEAREST => self.sample_level(0, GL_NEAREST, u, v),
+            _ => self.sample_level(0, GL_LINEAR, u, v)
+        }
+    }
+
+    fn sample_level(&self, level: usize, filter: GLenum, u: f32, v: f32) -> [f32; 4]
+    {
+        let plane = &self.levels[level];
+
+        let x = u * plane.width as f32;
+        let y = v * plane.height as f32;
+
+        let texel = |x: i64, y: i64| -> [f32; 4] {
+            plane
+                .get(
+                    wrap_coord(x, plane.width, self.wrap_s),
+                    wrap_coord(y, plane.height, self.wrap_t)
+                )
+                .map(|value| value as f32 / 255.0)
+        };
+
+        if filter == GL_NEAREST {
+            return texel(x.floor() as i64, y.floor() as i64);
+        }
+
+        let x = x - 0.5;
+        let y = y - 0.5;
+
+        let x0 = x.floor();
+        let y0 = y.floor();
+
+        let (x_frac, y_frac) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+
+        lerp4(
+            lerp4(texel(x0, y0), texel(x0 + 1, y0), x_frac),
+            lerp4(texel(x0, y0 + 1), texel(x0 + 1, y0 + 1), x_frac),
+            y_frac
+        )
+    }
+}
+
+fn wrap_coord(coord: i64, size: usize, mode: GLenum) -> usize
+{
+    let size = size as i64;
+
+    let wrapped = match mode {
+        GL_REPEAT => coord.rem_euclid(size),
+        GL_MIRRORED_REPEAT => {
+            let coord = coord.rem_euclid(size * 2);
+            if coord < size {
+                coord
+            } else {
+                size * 2 - 1 - coord
+            }
+        }
+        _ => coord.clamp(0, size - 1)
+    };
+
+    wrapped as usize
+}
+
+#[inline]
+fn lerp4(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4]
+{
+    [
+        a[0] + (b[0] - a[0]) * t,
+        a[1] + (b[1] - a[1]) * t,
+        a[2] + (b[2] - a[2]) * t,
+        a[3] + (b[3] - a[3]) * t
+    ]
+}
+
+enum Renderbuffer
+{
+    Unallocated,
+    Color(Plane<Rgba>),
+    Stencil(Plane<u8>)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColorAttachment
+{
+    Texture(GLuint),
+    Renderbuffer(GLuint)
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Framebuffer
+{
+    color: Option<ColorAttachment>,
+    stencil: Option<GLuint>
+}
+
+#[derive(Debug, Clone, Copy)]
+struct AttributePointer
+{
+    buffer: GLuint,
+    components: usize,
+    stride: usize,
+    offset: usize
+}
+
+#[derive(Debug, Clone, Copy)]
+struct StencilState
+{
+    enabled: bool,
+    func: GLenum,
+    reference: u8,
+    mask: u8,
+    write_mask: u8,
+    op_fail: GLenum,
+    op_pass: GLenum,
+    clear_value: u8
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BlendState
+{
+    enabled: bool,
+    src_rgb: GLenum,
+    dst_rgb: GLenum,
+    src_alpha: GLenum,
+    dst_alpha: GLenum
+}
+
+impl BlendState
+{
+    fn blend(&self, src: [f32; 4], dst: [f32; 4]) -> [f32; 4]
+    {
+        if !self.enabled {
+            return src;
+        }
+
+        let factor = |factor: GLenum, channel: usize| -> f32 {
+            match factor {
+                GL_ONE => 1.0,
+                GL_ONE_MINUS_SRC_ALPHA => 1.0 - src[3],
+                GL_ONE_MINUS_SRC_COLOR => 1.0 - src[channel],
+                GL_DST_COLOR => dst[channel],
+                _ => 0.0
+            }
+        };
+
+        let mut result = [0.0; 4];
+
+        for channel in 0..4 {
+            let (src_factor, dst_factor) = match channel {
+                3 => (self.src_alpha, self.dst_alpha),
+                _ => (self.src_rgb, self.dst_rgb)
+            };
+
+            result[channel] = src[channel] * factor(src_factor, channel)
+                + dst[channel] * factor(dst_factor, channel);
+        }
+
+        result
+    }
+}
+
+/// The outputs of the vertex shader, in window coordinates.
+#[derive(Debug, Clone, Copy)]
+struct Vertex
+{
+    x: i64,
+    y: i64,
+    varyings: Varyings
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Varyings
+{
+    color: [f32; 4],
+    texture_coord: [f32; 2],
+    texture_mix: f32,
+    circle_mix: f32
+}
+
+impl Varyings
+{
+    fn interpolate(vertices: &[Vertex; 3], weights: [f32; 3]) -> Self
+    {
+        let mut result = Varyings::default();
+
+        for (vertex, weight) in vertices.iter().zip(weights) {
+            let varyings = &vertex.varyings;
+
+            for (result, value) in result.color.iter_mut().zip(varyings.color) {
+                *result += value * weight;
+            }
+
+            for (result, value) in
+                result.texture_coord.iter_mut().zip(varyings.texture_coord)
+            {
+                *result += value * weight;
+            }
+
+            result.texture_mix += varyings.texture_mix * weight;
+            result.circle_mix += varyings.circle_mix * weight;
+        }
+
+        result
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ColorSlot
+{
+    Default,
+    Texture(GLuint),
+    Renderbuffer(GLuint)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum StencilSlot
+{
+    Default,
+    Renderbuffer(GLuint)
+}
+
+struct SoftwareState
+{
+    next_handle: GLuint,
+
+    buffers: HashMap<GLuint, Vec<u8>>,
+    textures: HashMap<GLuint, Texture>,
+    framebuffers: HashMap<GLuint, Framebuffer>,
+    renderbuffers: HashMap<GLuint, Renderbuffer>,
+
+    default_color: Plane<Rgba>,
+    default_stencil: Plane<u8>,
+
+    bound_buffers: HashMap<GLenum, GLuint>,
+    bound_textures: HashMap<GLuint, GLuint>,
+    active_texture_unit: GLuint,
+    bound_renderbuffer: Option<GLuint>,
+    read_framebuffer: Option<GLuint>,
+    draw_framebuffer: Option<GLuint>,
+
+    attributes: [Option<AttributePointer>; ATTRIBUTE_NAMES.len()],
+    uniforms: [[f32; 4]; UNIFORM_NAMES.len()],
+
+    viewport: IntRect,
+    scissor: IntRect,
+    scissor_enabled: bool,
+    color_mask: [bool; 4],
+    clear_color: [f32; 4],
+    blend: BlendState,
+    stencil: StencilState,
+
+    unpack_alignment: usize,
+    pack_alignment: usize
+}
+
+impl SoftwareState
+{
+    fn gen_handle(&mut self) -> GLuint
+    {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        handle
+    }
+
+    fn color_slot(&self, framebuffer: Option<GLuint>) -> Option<ColorSlot>
+    {
+        match framebuffer {
+            None => Some(ColorSlot::Default),
+            Some(handle) => match self.framebuffers.get(&handle)?.color? {
+                ColorAttachment::Texture(texture) => Some(ColorSlot::Texture(texture)),
+                ColorAttachment::Renderbuffer(renderbuffer) => {
+                    Some(ColorSlot::Renderbuffer(renderbuffer))
+                }
+            }
+        }
+    }
+
+    fn stencil_slot(&self, framebuffer: Option<GLuint>) -> Option<StencilSlot>
+    {
+        match framebuffer {
+            None => Some(StencilSlot::Default),
+            Some(handle) => self
+                .framebuffers
+                .get(&handle)?
+                .stencil
+                .map(StencilSlot::Renderbuffer)
+        }
+    }
+
+    fn color_plane_mut(&mut self, slot: ColorSlot) -> Option<&mut Plane<Rgba>>
+    {
+        match slot {
+            ColorSlot::Default => Some(&mut self.default_color),
+            ColorSlot::Texture(handle) => {
+                self.textures.get_mut(&handle)?.levels.first_mut()
+            }
+            ColorSlot::Renderbuffer(handle) => {
+                match self.renderbuffers.get_mut(&handle)? {
+                    Renderbuffer::Color(plane) => Some(plane),
+                    _ => None
+                }
+            }
+        }
+    }
+
+    fn stencil_plane_mut(&mut self, slot: StencilSlot) -> Option<&mut Plane<u8>>
+    {
+        match slot {
+            StencilSlot::Default => Some(&mut self.default_stencil),
+            StencilSlot::Renderbuffer(handle) => {
+                match self.renderbuffers.get_mut(&handle)? {
+                    Renderbuffer::Stencil(plane) => Some(plane),
+                    _ => None
+                }
+            }
+        }
+    }
+
+    fn take_color_plane(
+        &mut self,
+        framebuffer: Option<GLuint>
+    ) -> Option<(ColorSlot, Plane<Rgba>)>
+    {
+        let slot = self.color_slot(framebuffer)?;
+        let plane = std::mem::take(self.color_plane_mut(slot)?);
+        Some((slot, plane))
+    }
+
+    fn restore_color_plane(&mut self, (slot, plane): (ColorSlot, Plane<Rgba>))
+    {
+        if let Some(target) = self.color_plane_mut(slot) {
+            *target = plane;
+        }
+    }
+
+    fn take_stencil_plane(
+        &mut self,
+        framebuffer: Option<GLuint>
+    ) -> Option<(StencilSlot, Plane<u8>)>
+    {
+        let slot = self.stencil_slot(framebuffer)?;
+        let plane = std::mem::take(self.stencil_plane_mut(slot)?);
+        Some((slot, plane))
+    }
+
+    fn restore_stencil_plane(&mut self, (slot, plane): (StencilSlot, Plane<u8>))
+    {
+        if let Some(target) = self.stencil_plane_mut(slot) {
+            *target = plane;
+        }
+    }
+
+    fn bound_buffer_mut(&mut self, target: GLenum) -> Option<&mut Vec<u8>>
+    {
+        let handle = *self.bound_buffers.get(&target)?;
+        self.buffers.get_mut(&handle)
+    }
+
+    fn bound_texture_mut(&mut self) -> Option<&mut Texture>
+    {
+        let handle = *self.bound_textures.get(&self.active_texture_unit)?;
+        self.textures.get_mut(&handle)
+    }
+
+    /// The area which may be modified by drawing or clearing, taking into
+    /// account the scissor test.
+    fn write_bounds(&self, width: usize, height: usize) -> IntRect
+    {
+        let bounds = IntRect::new(0, 0, width as i32, height as i32);
+
+        if self.scissor_enabled {
+            bounds.intersect(&self.scissor)
+        } else {
+            bounds
+        }
+    }
+
+    fn fetch_attribute(&self, index: usize, vertex: usize) -> [f32; 4]
+    {
+        let mut result = [0.0, 0.0, 0.0, 1.0];
+
+        let pointer = match &self.attributes[index] {
+            None => return result,
+            Some(pointer) => pointer
+        };
+
+        let buffer = match self.buffers.get(&pointer.buffer) {
+            None => return result,
+            Some(buffer) => buffer
+        };
+
+        let start = pointer.offset + vertex * pointer.stride;
+
+        for (component, result) in result.iter_mut().enumerate().take(pointer.components)
+        {
+            let offset = start + component * 4;
+
+            if let Some(bytes) = buffer.get(offset..offset + 4) {
+                *result = f32::from_ne_bytes(bytes.try_into().unwrap());
+            }
+        }
+
+        result
+    }
+
+    fn run_vertex_shader(&self, vertex: usize) -> Vertex
+    {
+        let position = self.fetch_attribute(ATTR_POSITION, vertex);
+        let color = self.fetch_attribute(ATTR_COLOR, vertex);
+        let texture_coord = self.fetch_attribute(ATTR_TEXTURE_COORD, vertex);
+        let texture_mix = self.fetch_attribute(ATTR_TEXTURE_MIX, vertex)[0];
+        let circle_mix = self.fetch_attribute(ATTR_CIRCLE_MIX, vertex)[0];
+
+        let row_0 = self.uniforms[UNIFORM_TRANSFORM_ROW_0];
+        let row_1 = self.uniforms[UNIFORM_TRANSFORM_ROW_1];
+        let tint = self.uniforms[UNIFORM_TINT];
+
+        let x = row_0[0] * position[0] + row_0[1] * position[1] + row_0[2];
+        let y = row_1[0] * position[0] + row_1[1] * position[1] + row_1[2];
+
+        let ndc_x = x * self.uniforms[UNIFORM_SCALE_X][0] - 1.0;
+        let ndc_y =
+            y * self.uniforms[UNIFORM_SCALE_Y][0] + self.uniforms[UNIFORM_OFFSET_Y][0];
+
+        let viewport = &self.viewport;
+
+        let window_x =
+            viewport.x0 as f32 + (ndc_x + 1.0) * 0.5 * (viewport.x1 - viewport.x0) as f32;
+        let window_y =
+            viewport.y0 as f32 + (ndc_y + 1.0) * 0.5 * (viewport.y1 - viewport.y0) as f32;
+
+        Vertex {
+            x: (window_x * SUBPIXEL_STEPS).round() as i64,
+            y: (window_y * SUBPIXEL_STEPS).round() as i64,
+            varyings: Varyings {
+                color: [
+                    color[0] * tint[0],
+                    color[1] * tint[1],
+                    color[2] * tint[2],
+                    color[3] * tint[3]
+                ],
+                texture_coord: [texture_coord[0], texture_coord[1]],
+                texture_mix,
+                circle_mix
+            }
+        }
+    }
+
+    fn draw_triangles(&mut self, first: usize, count: usize)
+    {
+        let vertices: Vec<Vertex> = (first..first + count)
+            .map(|vertex| self.run_vertex_shader(vertex))
+            .collect();
+
+        let framebuffer = self.draw_framebuffer;
+
+        let mut color = match self.take_color_plane(framebuffer) {
+            None => return,
+            Some(color) => color
+        };
+
+        let mut stencil = self.take_stencil_plane(framebuffer);
+
+        for triangle in vertices.chunks_exact(3) {
+            self.draw_triangle(
+                [triangle[0], triangle[1], triangle[2]],
+                &mut color.1,
+                stencil.as_mut().map(|(_, plane)| plane)
+            );
+        }
+
+        self.restore_color_plane(color);
+
+        if let Some(stencil) = stencil {
+            self.restore_stencil_plane(stencil);
+        }
+    }
+
+    fn draw_triangle(
+        &self,
+        mut vertices: [Vertex; 3],
+        color: &mut Plane<Rgba>,
+        mut stencil: Option<&mut Plane<u8>>
+    )
+    {
+        let mut area =
+            edge_function(&vertices[0], &vertices[1], (vertices[2].x, vertices[2].y));
+
+        if area == 0 {
+            return;
+        }
+
+        // Ensure counter-clockwise winding, so that the fill rule
+        // can be applied consistently.
+        if area < 0 {
+            vertices.swap(1, 2);
+            area = -area;
+        }
+
+        let bounds = self
+            .write_bounds(color.width, color.height)
+            .intersect(&self.viewport);
+
+        let steps = SUBPIXEL_STEPS as i64;
+
+        let min_x = vertices.iter().map(|v| v.x).min().unwrap();
+        let max_x = vertices.iter().map(|v| v.x).max().unwrap();
+        let min_y = vertices.iter().map(|v| v.y).min().unwrap();
+        let max_y = vertices.iter().map(|v| v.y).max().unwrap();
+
+        let x0 = (min_x.div_euclid(steps) as i32).max(bounds.x0);
+        let x1 = ((max_x.div_euclid(steps) + 1) as i32).min(bounds.x1);
+        let y0 = (min_y.div_euclid(steps) as i32).max(bounds.y0);
+        let y1 = ((max_y.div_euclid(steps) + 1) as i32).min(bounds.y1);
+
+        if x0 >= x1 || y0 >= y1 {
+            return;
+        }
+
+        let edges = [(1, 2), (2, 0), (0, 1)];
+        let bottom_left = edges.map(|(a, b)| is_bottom_left(&vertices[a], &vertices[b]));
+
+        let weights_at = |x: i32, y: i32| -> [i64; 3] {
+            let point = (x as i64 * steps + steps / 2, y as i64 * steps + steps / 2);
+            edges.map(|(a, b)| edge_function(&vertices[a], &vertices[b], point))
+        };
+
+        let varyings_at = |x: i32, y: i32| -> Varyings {
+            let weights = weights_at(x, y).map(|weight| weight as f32 / area as f32);
+            Varyings::interpolate(&vertices, weights)
+        };
+
+        let texture = self
+            .bound_textures
+            .get(&(self.uniforms[UNIFORM_TEXTURE][0] as GLuint))
+            .and_then(|handle| self.textures.get(handle));
+
+        let smooth_circle_edges = self.uniforms[UNIFORM_SMOOTH_CIRCLE_EDGES][0];
+
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let weights = weights_at(x, y);
+
+                let covered =
+                    weights
+                        .iter()
+                        .zip(bottom_left)
+                        .all(|(weight, bottom_left)| {
+                            *weight > 0 || (*weight == 0 && bottom_left)
+                        });
+
+                if !covered {
+                    continue;
+                }
+
+                let (ux, uy) = (x as usize, y as usize);
+
+                if let Some(stencil) = stencil.as_deref_mut() {
+                    if !self.stencil_test(stencil, ux, uy) {
+                        continue;
+                    }
+                }
+
+                if self.color_mask == [false; 4] {
+                    continue;
+                }
+
+                // Derivatives are calculated across 2x2 pixel quads, as on
+                // the GPU
+                let quad_x = x & !1;
+                let quad_y = y & !1;
+
+                let varyings = varyings_at(x, y);
+                let dx = [varyings_at(quad_x, y), varyings_at(quad_x + 1, y)];
+                let dy = [varyings_at(x, quad_y), varyings_at(x, quad_y + 1)];
+
+                let source = run_fragment_shader(
+                    &varyings,
+                    &dx,
+                    &dy,
+                    texture,
+                    smooth_circle_edges
+                );
+
+                let destination = color.get(ux, uy).map(|value| value as f32 / 255.0);
+                let result = self.blend.blend(source, destination);
+
+                let mut pixel = color.get(ux, uy);
+
+                for channel in 0..4 {
+                    if self.color_mask[channel] {
+                        pixel[channel] = to_u8(result[channel]);
+                    }
+                }
+
+                color.set(ux, uy, pixel);
+            }
+        }
+    }
+
+    fn stencil_test(&self, stencil: &mut Plane<u8>, x: usize, y: usize) -> bool
+    {
+        let state = &self.stencil;
+
+        if !state.enabled {
+            return true;
+        }
+
+        let value = stencil.get(x, y);
+
+        let passed = match state.func {
+            GL_ALWAYS => true,
+            GL_EQUAL => state.reference & state.mask == value & state.mask,
+            _ => false
+        };
+
+        let op = if passed { state.op_pass } else { state.op_fail };
+
+        let new_value = match op {
+            GL_REPLACE => state.reference,
+            _ => value
+        };
+
+        stencil.set(
+            x,
+            y,
+            (new_value & state.write_mask) | (value & !state.write_mask)
+        );
+
+        passed
+    }
+
+    fn clear(&mut self, mask: GLenum)
+    {
+        let framebuffer = self.draw_framebuffer;
+
+        if mask & GL_COLOR_BUFFER_BIT != 0 {
+            let clear_color = self.clear_color.map(to_u8);
+            let color_mask = self.color_mask;
+
+            if let Some((slot, mut plane)) = self.take_color_plane(framebuffer) {
+                let bounds = self.write_bounds(plane.width, plane.height);
+
+                for y in bounds.y0.max(0)..bounds.y1 {
+                    for x in bounds.x0.max(0)..bounds.x1 {
+                        let mut pixel = plane.get(x as usize, y as usize);
+
+                        for channel in 0..4 {
+                            if color_mask[channel] {
+                                pixel[channel] = clear_color[channel];
+                            }
+                        }
+
+                        plane.set(x as usize, y as usize, pixel);
+                    }
+                }
+
+                self.restore_color_plane((slot, plane));
+            }
+        }
+
+        if mask & GL_STENCIL_BUFFER_BIT != 0 {
+            let value = self.stencil.clear_value;
+            let write_mask = self.stencil.write_mask;
+
+            if let Some((slot, mut plane)) = self.take_stencil_plane(framebuffer) {
+                let bounds = self.write_bounds(plane.width, plane.height);
+
+                for y in bounds.y0.max(0)..bounds.y1 {
+                    for x in bounds.x0.max(0)..bounds.x1 {
+                        let old = plane.get(x as usize, y as usize);
+                        plane.set(
+                            x as usize,
+                            y as usize,
+                            (value & write_mask) | (old & !write_mask)
+                        );
+                    }
+                }
+
+                self.restore_stencil_plane((slot, plane));
+            }
+        }
+    }
+
+    fn blit(&mut self, src: IntRect, dst: IntRect)
+    {
+        let source = match self.take_color_plane(self.read_framebuffer) {
+            None => return,
+            Some(source) => source
+        };
+
+        let destination = self.take_color_plane(self.draw_framebuffer);
+
+        if let Some((slot, mut plane)) = destination {
+            let bounds =
+                self.write_bounds(plane.width, plane.height)
+                    .intersect(&IntRect {
+                        x0: dst.x0.min(dst.x1),
+                        y0: dst.y0.min(dst.y1),
+                        x1: dst.x0.max(dst.x1),
+                        y1: dst.y0.max(dst.y1)
+                    });
+
+            let map_coord = |coord: i32, src0: i32, src1: i32, dst0: i32, dst1: i32| {
+                let t = (coord as f32 + 0.5 - dst0 as f32) / (dst1 - dst0) as f32;
+                (src0 as f32 + t * (src1 - src0) as f32).floor() as i64
+            };
+
+            let source_plane = &source.1;
+
+            for y in bounds.y0.max(0)..bounds.y1 {
+                let sy = map_coord(y, src.y0, src.y1, dst.y0, dst.y1);
+
+                if sy < 0 || sy >= source_plane.height as i64 {
+                    continue;
+                }
+
+                for x in bounds.x0.max(0)..bounds.x1 {
+                    let sx = map_coord(x, src.x0, src.x1, dst.x0, dst.x1);
+
+                    if sx < 0 || sx >= source_plane.width as i64 {
+                        continue;
+                    }
+
+                    plane.set(
+                        x as usize,
+                        y as usize,
+                        source_plane.get(sx as usize, sy as usize)
+                    );
+                }
+            }
+
+            self.restore_color_plane((slot, plane));
+        }
+
+        self.restore_color_plane(source);
+    }
+
+    /// Reads pixels from the current read framebuffer, in the layout
+    /// produced by `glReadPixels`.
+    fn read_pixels(&mut self, region: IntRect, format: GLenum) -> Vec<u8>
+    {
+        let bytes_per_pixel = match format {
+            GL_RGB => 3,
+            _ => 4
+        };
+
+        let width = (region.x1 - region.x0).max(0) as usize;
+        let height = (region.y1 - region.y0).max(0) as usize;
+
+        let row_stride = align(width * bytes_per_pixel, self.pack_alignment);
+        let mut result = vec![0; row_stride * height];
+
+        let source = match self.take_color_plane(self.read_framebuffer) {
+            None => return result,
+            Some(source) => source
+        };
+
+        let plane = &source.1;
+
+        for row in 0..height {
+            let y = region.y0 + row as i32;
+
+            if y < 0 || y as usize >= plane.height {
+                continue;
+            }
+
+            for column in 0..width {
+                let x = region.x0 + column as i32;
+
+                if x < 0 || x as usize >= plane.width {
+                    continue;
+                }
+
+                let offset = row * row_stride + column * bytes_per_pixel;
+                let pixel = plane.get(x as usize, y as usize);
+
+                result[offset..offset + bytes_per_pixel]
+                    .copy_from_slice(&pixel[..bytes_per_pixel]);
+            }
+        }
+
+        self.restore_color_plane(source);
+
+        result
+    }
+}
+
+/// Twice the signed area of the triangle formed by the edge `a` to `b` and
+/// the specified point.
+#[inline]
+fn edge_function(a: &Vertex, b: &Vertex, point: (i64, i64)) -> i64
+{
+    (b.x - a.x) * (point.1 - a.y) - (b.y - a.y) * (point.0 - a.x)
+}
+
+/// For a counter-clockwise triangle with the Y axis pointing up, returns true
+/// if the edge `a` to `b` is a bottom or left edge. Pixels lying exactly on
+/// these edges are considered to be inside the triangle. With the Y axis
+/// pointing down, this is the usual top-left fill rule.
+#[inline]
+fn is_bottom_left(a: &Vertex, b: &Vertex) -> bool
+{
+    (a.y == b.y && b.x > a.x) || b.y < a.y
+}
+
+#[inline]
+fn to_u8(value: f32) -> u8
+{
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+#[inline]
+fn align(value: usize, alignment: usize) -> usize
+{
+    value.div_ceil(alignment.max(1)) * alignment.max(1)
+}
+
+fn texture_coord_mag_squared(varyings: &Varyings) -> f32
+{
+    let [u, v] = varyings.texture_coord;
+    u * u + v * v
+}
+
+/// A port of the `r2d` fragment shader. The values in `dx` and `dy` are the
+/// varyings at horizontally and vertically adjacent pixels respectively, and
+/// are used in place of `dFdx()` and `dFdy()`.
+fn run_fragment_shader(
+    varyings: &Varyings,
+    dx: &[Varyings; 2],
+    dy: &[Varyings; 2],
+    texture: Option<&Texture>,
+    smooth_circle_edges: f32
+) -> [f32; 4]
+{
+    let [u, v] = varyings.texture_coord;
+
+    let texture_color = match texture {
+        None => [0.0, 0.0, 0.0, 1.0],
+        Some(texture) => {
+            let size = &texture.levels[0];
+
+            let scale = |a: &Varyings, b: &Varyings| -> f32 {
+                let du = (b.texture_coord[0] - a.texture_coord[0]) * size.width as f32;
+                let dv = (b.texture_coord[1] - a.texture_coord[1]) * size.height as f32;
+                (du * du + dv * dv).sqrt()
+            };
+
+            let rho = scale(&dx[0], &dx[1]).max(scale(&dy[0], &dy[1]));
+
+            texture.sample(u, v, rho.log2())
+        }
+    };
+
+    let mag_squared = texture_coord_mag_squared(varyings);
+
+    let fwidth = (texture_coord_mag_squared(&dx[1]) - texture_coord_mag_squared(&dx[0]))
+        .abs()
+        + (texture_coord_mag_squared(&dy[1]) - texture_coord_mag_squared(&dy[0])).abs();
+
+    let circle_edge_width = fwidth.max(0.000001);
+
+    let hard_alpha = if mag_squared >= 1.0 { 0.0 } else { 1.0 };
+    let smooth_alpha = ((1.0 - mag_squared) / circle_edge_width + 0.5).clamp(0.0, 1.0);
+    let circle_alpha = hard_alpha + (smooth_alpha - hard_alpha) * smooth_circle_edges;
+
+    let solid_mix = 1.0 - varyings.texture_mix - varyings.circle_mix;
+
+    let mut color = [0.0; 4];
+
+    for channel in 0..4 {
+        let circle = if channel == 3 { circle_alpha } else { 1.0 };
+
+        color[channel] = varyings.color[channel]
+            * (solid_mix
+                + texture_color[channel] * varyings.texture_mix
+                + circle * varyings.circle_mix);
+    }
+
+    // Blending is performed using premultiplied alpha
+    let alpha = color[3];
+    [color[0] * alpha, color[1] * alpha, color[2] * alpha, alpha]
+}
+
+/// A [GLBackend] which rasterizes on the CPU, emulating the subset of OpenGL
+/// used by `Renderer2D`. Shader sources are ignored: draw calls always run a
+/// port of the `r2d` vertex and fragment shaders.
+pub struct GLBackendSoftware
+{
+    state: RefCell<SoftwareState>
+}
+
+impl GLBackendSoftware
+{
+    #[must_use]
+    pub fn new() -> Self
+    {
+        GLBackendSoftware {
+            state: RefCell::new(SoftwareState {
+                next_handle: 1,
+                buffers: HashMap::new(),
+                textures: HashMap::new(),
+                framebuffers: HashMap::new(),
+                renderbuffers: HashMap::new(),
+                default_color: Plane::default(),
+                default_stencil: Plane::default(),
+                bound_buffers: HashMap::new(),
+                bound_textures: HashMap::new(),
+                active_texture_unit: 0,
+                bound_renderbuffer: None,
+                read_framebuffer: None,
+                draw_framebuffer: None,
+                attributes: [None; ATTRIBUTE_NAMES.len()],
+                uniforms: [[0.0; 4]; UNIFORM_NAMES.len()],
+                viewport: IntRect::new(0, 0, 0, 0),
+                scissor: IntRect::new(0, 0, 0, 0),
+                scissor_enabled: false,
+                color_mask: [true; 4],
+                clear_color: [0.0; 4],
+                blend: BlendState {
+                    enabled: false,
+                    src_rgb: GL_ONE,
+                    dst_rgb: 0,
+                    src_alpha: GL_ONE,
+                    dst_alpha: 0
+                },
+                stencil: StencilState {
+                    enabled: false,
+                    func: GL_ALWAYS,
+                    reference: 0,
+                    mask: 0xFF,
+                    write_mask: 0xFF,
+                    op_fail: GL_KEEP,
+                    op_pass: GL_KEEP,
+                    clear_value: 0
+                },
+                unpack_alignment: 4,
+                pack_alignment: 4
+            })
+        }
+    }
+
+    fn set_uniform(&self, handle: &GLTypeUniformLocation, value: [f32; 4])
+    {
+        if let Some(uniform) = self.state.borrow_mut().uniforms.get_mut(*handle as usize)
+        {
+            *uniform = value;
+        }
+    }
+}
+
+impl GLBackend for GLBackendSoftware
+{
+    unsafe fn gl_delete_program(&self, _handle: GLTypeProgram) {}
+
+    unsafe fn gl_delete_shader(&self, _handle: GLTypeShader) {}
+
+    unsafe fn gl_delete_buffer(&self, handle: GLTypeBuffer)
+    {
+        self.state.borrow_mut().buffers.remove(&handle);
+    }
+
+    unsafe fn gl_delete_texture(&self, handle: GLTypeTexture)
+    {
+        self.state.borrow_mut().textures.remove(&handle);
+    }
+
+    unsafe fn gl_delete_framebuffer(&self, handle: GLTypeFramebuffer)
+    {
+        self.state.borrow_mut().framebuffers.remove(&handle);
+    }
+
+    unsafe fn gl_delete_renderbuffer(&self, handle: GLTypeRenderbuffer)
+    {
+        self.state.borrow_mut().renderbuffers.remove(&handle);
+    }
+
+    unsafe fn gl_delete_vertex_array(&self, _handle: GLTypeVertexArray) {}
+
+    unsafe fn gl_active_texture(&self, unit: GLenum)
+    {
+        self.state.borrow_mut().active_texture_unit = unit - GL_TEXTURE0;
+    }
+
+    unsafe fn gl_bind_texture(&self, _target: GLenum, handle: GLTypeTexture)
+    {
+        let mut state = self.state.borrow_mut();
+        let unit = state.active_texture_unit;
+        state.bound_textures.insert(unit, handle);
+    }
+
+    unsafe fn gl_bind_framebuffer(
+        &self,
+        target: GLenum,
+        handle: Option<GLTypeFramebuffer>
+    )
+    {
+        let mut state = self.state.borrow_mut();
+
+        match target {
+            GL_READ_FRAMEBUFFER => state.read_framebuffer = handle,
+            GL_DRAW_FRAMEBUFFER => state.draw_framebuffer = handle,
+            _ => {
+                state.read_framebuffer = handle;
+                state.draw_framebuffer = handle;
+            }
+        }
+    }
+
+    unsafe fn gl_framebuffer_texture_2d(
+        &self,
+        _target: GLenum,
+        _attachment: GLenum,
+        _texture_target: GLenum,
+        texture: GLTypeTexture,
+        _level: GLint
+    )
+    {
+        let mut state = self.state.borrow_mut();
+
+        if let Some(handle) = state.draw_framebuffer {
+            if let Some(framebuffer) = state.framebuffers.get_mut(&handle) {
+                framebuffer.color = Some(ColorAttachment::Texture(texture));
+            }
+        }
+    }
+
+    unsafe fn gl_check_framebuffer_status(&self, _target: GLenum) -> GLenum
+    {
+        GL_FRAMEBUFFER_COMPLETE
+    }
+
+    unsafe fn gl_bind_renderbuffer(&self, _target: GLenum, handle: GLTypeRenderbuffer)
+    {
+        self.state.borrow_mut().bound_renderbuffer = Some(handle);
+    }
+
+    unsafe fn gl_renderbuffer_storage(
+        &self,
+        target: GLenum,
+        internal_format: GLenum,
+        width: GLsizei,
+        height: GLsizei
+    )
+    {
+        self.gl_renderbuffer_storage_multisample(
+            target,
+            0,
+            internal_format,
+            width,
+            height
+        )
+    }
+
+    unsafe fn gl_renderbuffer_storage_multisample(
+        &self,
+        _target: GLenum,
+        _samples: GLsizei,
+        internal_format: GLenum,
+        width: GLsizei,
+        height: GLsizei
+    )
+    {
+        let mut state = self.state.borrow_mut();
+
+        let (width, height) = (width.max(0) as usize, height.max(0) as usize);
+
+        let storage = match internal_format {
+            GL_STENCIL_INDEX8 => Renderbuffer::Stencil(Plane::new(width, height)),
+            _ => Renderbuffer::Color(Plane::new(width, height))
+        };
+
+        if let Some(handle) = state.bound_renderbuffer {
+            state.renderbuffers.insert(handle, storage);
+        }
+    }
+
+    unsafe fn gl_framebuffer_renderbuffer(
+        &self,
+        _target: GLenum,
+        attachment: GLenum,
+        _renderbuffer_target: GLenum,
+        renderbuffer: GLTypeRenderbuffer
+    )
+    {
+        let mut state = self.state.borrow_mut();
+
+        if let Some(handle) = state.draw_framebuffer {
+            if let Some(framebuffer) = state.framebuffers.get_mut(&handle) {
+                match attachment {
+                    GL_STENCIL_ATTACHMENT => framebuffer.stencil = Some(renderbuffer),
+                    _ => {
+                        framebuffer.color =
+                            Some(ColorAttachment::Renderbuffer(renderbuffer))
+                    }
+                }
+            }
+        }
+    }
+
+    unsafe fn gl_blit_framebuffer(
+        &self,
+        src_x0: GLint,
+        src_y0: GLint,
+        src_x1: GLint,
+        src_y1: GLint,
+        dst_x0: GLint,
+        dst_y0: GLint,
+        dst_x1: GLint,
+        dst_y1: GLint,
+        mask: GLenum,
+        _filter: GLenum
+    )
+    {
+        if mask & GL_COLOR_BUFFER_BIT == 0 {
+            return;
+        }
+
+        self.state.borrow_mut().blit(
+            IntRect {
+                x0: src_x0,
+                y0: src_y0,
+                x1: src_x1,
+                y1: src_y1
+            },
+            IntRect {
+                x0: dst_x0,
+                y0: dst_y0,
+                x1: dst_x1,
+                y1: dst_y1
+            }
+        )
+    }
+
+    unsafe fn gl_get_parameter_i32(&self, _parameter: GLenum) -> GLint
+    {
+        // Notably, multisampling is unsupported, so GL_MAX_SAMPLES is zero
+        0
+    }
+
+    unsafe fn gl_enable(&self, cap: GLenum)
+    {
+        let mut state = self.state.borrow_mut();
+
+        match cap {
+            GL_BLEND => state.blend.enabled = true,
+            GL_SCISSOR_TEST => state.scissor_enabled = true,
+            GL_STENCIL_TEST => state.stencil.enabled = true,
+            _ => log::warn!("Software renderer: ignoring glEnable({cap})")
+        }
+    }
+
+    unsafe fn gl_disable(&self, cap: GLenum)
+    {
+        let mut state = self.state.borrow_mut();
+
+        match cap {
+            GL_BLEND => state.blend.enabled = false,
+            GL_SCISSOR_TEST => state.scissor_enabled = false,
+            GL_STENCIL_TEST => state.stencil.enabled = false,
+            _ => log::warn!("Software renderer: ignoring glDisable({cap})")
+        }
+    }
+
+    unsafe fn gl_color_mask(&self, red: bool, green: bool, blue: bool, alpha: bool)
+    {
+        self.state.borrow_mut().color_mask = [red, green, blue, alpha];
+    }
+
+    unsafe fn gl_stencil_func(&self, func: GLenum, reference: GLint, mask: GLuint)
+    {
+        let mut state = self.state.borrow_mut();
+        state.stencil.func = func;
+        state.stencil.reference = reference as u8;
+        state.stencil.mask = mask as u8;
+    }
+
+    unsafe fn gl_stencil_op(
+        &self,
+        stencil_fail: GLenum,
+        _depth_fail: GLenum,
+        pass: GLenum
+    )
+    {
+        let mut state = self.state.borrow_mut();
+        state.stencil.op_fail = stencil_fail;
+        state.stencil.op_pass = pass;
+    }
+
+    unsafe fn gl_stencil_mask(&self, mask: GLuint)
+    {
+        self.state.borrow_mut().stencil.write_mask = mask as u8;
+    }
+
+    unsafe fn gl_clear_stencil(&self, stencil: GLint)
+    {
+        self.state.borrow_mut().stencil.clear_value = stencil as u8;
+    }
+
+    unsafe fn gl_blend_func(&self, sfactor: GLenum, dfactor: GLenum)
+    {
+        self.gl_blend_func_separate(sfactor, dfactor, sfactor, dfactor)
+    }
+
+    unsafe fn gl_blend_func_separate(
+        &self,
+        sfactor: GLenum,
+        dfactor: GLenum,
+        sfactor_alpha: GLenum,
+        dfactor_alpha: GLenum
+    )
+    {
+        let mut state = self.state.borrow_mut();
+        state.blend.src_rgb = sfactor;
+        state.blend.dst_rgb = dfactor;
+        state.blend.src_alpha = sfactor_alpha;
+        state.blend.dst_alpha = dfactor_alpha;
+    }
+
+    unsafe fn gl_use_program(&self, _handle: GLTypeProgram) {}
+
+    unsafe fn gl_enable_vertex_attrib_array(&self, _handle: GLuint) {}
+
+    unsafe fn gl_disable_vertex_attrib_array(&self, _handle: GLuint) {}
+
+    // The version string reports OpenGL 2.0, so instanced drawing is never
+    // used with this backend
+    unsafe fn gl_vertex_attrib_divisor(&self, _handle: GLuint, _divisor: GLuint) {}
+
+    unsafe fn gl_uniform_1f(&self, handle: &GLTypeUniformLocation, value: f32)
+    {
+        self.set_uniform(handle, [value, 0.0, 0.0, 0.0])
+    }
+
+    unsafe fn gl_uniform_1i(&self, handle: &GLTypeUniformLocation, value: GLint)
+    {
+        self.set_uniform(handle, [value as f32, 0.0, 0.0, 0.0])
+    }
+
+    unsafe fn gl_uniform_3f(&self, handle: &GLTypeUniformLocation, x: f32, y: f32, z: f32)
+    {
+        self.set_uniform(handle, [x, y, z, 0.0])
+    }
+
+    unsafe fn gl_uniform_4f(
+        &self,
+        handle: &GLTypeUniformLocation,
+        x: f32,
+        y: f32,
+        z: f32,
+        w: f32
+    )
+    {
+        self.set_uniform(handle, [x, y, z, w])
+    }
+
+    unsafe fn gl_attach_shader(&self, _program: GLTypeProgram, _shader: GLTypeShader) {}
+
+    unsafe fn gl_link_program(&self, _program: GLTypeProgram) {}
+
+    unsafe fn gl_shader_source(&self, _handle: GLTypeShader, _source: &str) {}
+
+    unsafe fn gl_compile_shader(&self, _handle: GLTypeShader) {}
+
+    unsafe fn gl_tex_parameter_i(&self, _target: GLenum, parameter: GLenum, value: GLint)
+    {
+        let mut state = self.state.borrow_mut();
+
+        if let Some(texture) = state.bound_texture_mut() {
+            let value = value as GLenum;
+
+            match parameter {
+                GL_TEXTURE_WRAP_S => texture.wrap_s = value,
+                GL_TEXTURE_WRAP_T => texture.wrap_t = value,
+                GL_TEXTURE_MIN_FILTER => texture.min_filter = value,
+                GL_TEXTURE_MAG_FILTER => texture.mag_filter = value,
+                _ => {}
+            }
+        }
+    }
+
+    unsafe fn gl_generate_mipmap(&self, _target: GLenum)
+    {
+        if let Some(texture) = self.state.borrow_mut().bound_texture_mut() {
+            texture.generate_mipmaps();
+        }
+    }
+
+    unsafe fn gl_bind_buffer(&self, target: GLenum, handle: GLTypeBuffer)
+    {
+        self.state.borrow_mut().bound_buffers.insert(target, handle);
+    }
+
+    unsafe fn gl_buffer_data(&self, target: GLenum, data: &[u8], _usage: GLenum)
+    {
+        if let Some(buffer) = self.state.borrow_mut().bound_buffer_mut(target) {
+            buffer.clear();
+            buffer.extend_from_slice(data);
+        }
+    }
+
+    unsafe fn gl_buffer_data_size(&self, target: GLenum, size: GLsizei, _usage: GLenum)
+    {
+        if let Some(buffer) = self.state.borrow_mut().bound_buffer_mut(target) {
+            *buffer = vec![0; size.max(0) as usize];
+        }
+    }
+
+    unsafe fn gl_unbind_buffer(&self, target: GLenum)
+    {
+        self.state.borrow_mut().bound_buffers.remove(&target);
+    }
+
+    unsafe fn gl_get_buffer_sub_data(
+        &self,
+        target: GLenum,
+        offset: GLint,
+        data: &mut [MaybeUninit<u8>]
+    )
+    {
+        if let Some(buffer) = self.state.borrow_mut().bound_buffer_mut(target) {
+            let offset = offset.max(0) as usize;
+
+            for (output, input) in data.iter_mut().zip(buffer.iter().skip(offset)) {
+                output.write(*input);
+            }
+        }
+    }
+
+    unsafe fn gl_map_buffer_range(
+        &self,
+        target: GLenum,
+        offset: GLint,
+        _length: GLsizei,
+        _access: GLenum
+    ) -> *mut u8
+    {
+        match self.state.borrow_mut().bound_buffer_mut(target) {
+            None => std::ptr::null_mut(),
+            Some(buffer) => buffer.as_mut_ptr().add(offset.max(0) as usize)
+        }
+    }
+
+    unsafe fn gl_unmap_buffer(&self, _target: GLenum) {}
+
+    unsafe fn gl_draw_arrays(&self, mode: GLenum, first: GLint, count: GLsizei)
+    {
+        if mode != GL_TRIANGLES {
+            log::warn!("Software renderer: ignoring unsupported draw mode {mode}");
+            return;
+        }
+
+        self.state
+            .borrow_mut()
+            .draw_triangles(first.max(0) as usize, count.max(0) as usize)
+    }
+
+    unsafe fn gl_draw_arrays_instanced(
+        &self,
+        _mode: GLenum,
+        _first: GLint,
+        _count: GLsizei,
+        _instance_count: GLsizei
+    )
+    {
+        log::warn!("Software renderer: ignoring unsupported instanced draw");
+    }
+
+    unsafe fn gl_draw_elements(
+        &self,
+        _mode: GLenum,
+        _count: GLsizei,
+        _element_type: GLenum,
+        _offset: GLint
+    )
+    {
+        log::warn!("Software renderer: ignoring unsupported indexed draw");
+    }
+
+    // The version string reports OpenGL 2.0, so vertex arrays are never used
+    // with this backend
+    unsafe fn gl_bind_vertex_array(&self, _handle: Option<GLTypeVertexArray>) {}
+
+    unsafe fn gl_clear_color(&self, r: f32, g: f32, b: f32, a: f32)
+    {
+        self.state.borrow_mut().clear_color = [r, g, b, a];
+    }
+
+    unsafe fn gl_clear(&self, mask: GLenum)
+    {
+        self.state.borrow_mut().clear(mask)
+    }
+
+    unsafe fn gl_enable_debug_message_callback(&self) {}
+
+    unsafe fn gl_get_string(&self, parameter: GLenum) -> String
+    {
+        match parameter {
+            GL_VERSION => "2.0 Speedy2D software renderer".to_string(),
+            _ => String::new()
+        }
+    }
+
+    unsafe fn gl_viewport(&self, x: i32, y: i32, width: i32, height: i32)
+    {
+        let mut state = self.state.borrow_mut();
+
+        state.viewport = IntRect::new(x, y, width, height);
+
+        // The default framebuffer behaves like a window which is resized to
+        // match the viewport. Viewports with an offset, for example when
+        // letterboxing, only cover part of the window.
+        if state.draw_framebuffer.is_none() && x == 0 && y == 0 {
+            let width = width.max(0) as usize;
+            let height = height.max(0) as usize;
+
+            if state.default_color.width != width || state.default_color.height != height
+            {
+                state.default_color = Plane::new(width, height);
+                state.default_stencil = Plane::new(width, height);
+            }
+        }
+    }
+
+    unsafe fn gl_scissor(&self, x: GLint, y: GLint, width: GLsizei, height: GLsizei)
+    {
+        self.state.borrow_mut().scissor = IntRect::new(x, y, width, height);
+    }
+
+    unsafe fn gl_pixel_store_i(&self, param: GLenum, value: GLint)
+    {
+        let mut state = self.state.borrow_mut();
+
+        match param {
+            GL_UNPACK_ALIGNMENT => state.unpack_alignment = value.max(1) as usize,
+            GL_PACK_ALIGNMENT => state.pack_alignment = value.max(1) as usize,
+            _ => {}
+        }
+    }
+
+    unsafe fn gl_vertex_attrib_pointer_f32(
+        &self,
+        index: GLuint,
+        size: GLsizei,
+        _data_type: GLenum,
+        _normalized: bool,
+        stride: GLsizei,
+        offset: GLsizei
+    )
+    {
+        let mut state = self.state.borrow_mut();
+
+        let buffer = match state.bound_buffers.get(&GL_ARRAY_BUFFER) {
+            None => return,
+            Some(buffer) => *buffer
+        };
+
+        let components = size.clamp(1, 4) as usize;
+
+        let stride = match stride {
+            0 => components * 4,
+            stride => stride as usize
+        };
+
+        if let Some(attribute) = state.attributes.get_mut(index as usize) {
+            *attribute = Some(AttributePointer {
+                buffer,
+                components,
+                stride,
+                offset: offset.max(0) as usize
+            });
+        }
+    }
+
+    unsafe fn gl_tex_image_2d(
+        &self,
+        _target: GLenum,
+        level: GLint,
+        _internal_format: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        _border: GLint,
+        format: GLenum,
+        _data_type: GLenum,
+        pixels: Option<&[u8]>
+    )
+    {
+        if level != 0 {
+            return;
+        }
+
+        let mut state = self.state.borrow_mut();
+        let unpack_alignment = state.unpack_alignment;
+
+        let size = (width.max(0) as usize, height.max(0) as usize);
+        let mut plane = Plane::new(size.0, size.1);
+
+        if let Some(pixels) = pixels {
+            copy_pixels_into(&mut plane, (0, 0), size, format, unpack_alignment, pixels);
+        }
+
+        if let Some(texture) = state.bound_texture_mut() {
+            texture.levels = vec![plane];
+        }
+    }
+
+    unsafe fn gl_tex_sub_image_2d(
+        &self,
+        _target: GLenum,
+        level: GLint,
+        x: GLint,
+        y: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        format: GLenum,
+        _data_type: GLenum,
+        pixels: &[u8]
+    )
+    {
+        let mut state = self.state.borrow_mut();
+        let unpack_alignment = state.unpack_alignment;
+
+        if let Some(plane) = state
+            .bound_texture_mut()
+            .and_then(|texture| texture.levels.get_mut(level.max(0) as usize))
+        {
+            copy_pixels_into(
+                plane,
+                (x.max(0) as usize, y.max(0) as usize),
+                (width.max(0) as usize, height.max(0) as usize),
+                format,
+                unpack_alignment,
+                pixels
+            );
+        }
+    }
+
+    unsafe fn gl_create_program(
+        &self
+    ) -> Result<GLTypeProgram, BacktraceError<ErrorMessage>>
+    {
+        Ok(self.state.borrow_mut().gen_handle())
+    }
+
+    unsafe fn gl_create_shader(
+        &self,
+        _shader_type: GLenum
+    ) -> Result<GLTypeShader, BacktraceError<ErrorMessage>>
+    {
+        Ok(self.state.borrow_mut().gen_handle())
+    }
+
+    unsafe fn gl_gen_buffer(&self) -> Result<GLTypeBuffer, BacktraceError<ErrorMessage>>
+    {
+        let mut state = self.state.borrow_mut();
+        let handle = state.gen_handle();
+        state.buffers.insert(handle, Vec::new());
+        Ok(handle)
+    }
+
+    unsafe fn gl_gen_texture(&self)
+        -> Result<GLTypeTexture, BacktraceError<ErrorMessage>>
+    {
+        let mut state = self.state.borrow_mut();
+        let handle = state.gen_handle();
+        state.textures.insert(handle, Texture::new());
+        Ok(handle)
+    }
+
+    unsafe fn gl_gen_framebuffer(
+        &self
+    ) -> Result<GLTypeFramebuffer, BacktraceError<ErrorMessage>>
+    {
+        let mut state = self.state.borrow_mut();
+        let handle = state.gen_handle();
+        state.framebuffers.insert(handle, Framebuffer::default());
+        Ok(handle)
+    }
+
+    unsafe fn gl_gen_renderbuffer(
+        &self
+    ) -> Result<GLTypeRenderbuffer, BacktraceError<ErrorMessage>>
+    {
+        let mut state = self.state.borrow_mut();
+        let handle = state.gen_handle();
+        state
+            .renderbuffers
+            .insert(handle, Renderbuffer::Unallocated);
+        Ok(handle)
+    }
+
+    unsafe fn gl_gen_vertex_array(
+        &self
+    ) -> Result<GLTypeVertexArray, BacktraceError<ErrorMessage>>
+    {
+        Err(ErrorMessage::msg(
+            "Software renderer: vertex arrays are not supported"
+        ))
+    }
+
+    unsafe fn gl_get_error(&self) -> GLenum
+    {
+        GL_NO_ERROR
+    }
+
+    unsafe fn gl_get_attrib_location(
+        &self,
+        _program: GLTypeProgram,
+        name: &str
+    ) -> Option<GLuint>
+    {
+        ATTRIBUTE_NAMES
+            .iter()
+            .position(|attribute| *attribute == name)
+            .map(|index| index as GLuint)
+    }
+
+    unsafe fn gl_get_uniform_location(
+        &self,
+        _program: GLTypeProgram,
+        name: &str
+    ) -> Option<GLTypeUniformLocation>
+    {
+        UNIFORM_NAMES
+            .iter()
+            .position(|uniform| *uniform == name)
+            .map(|index| index as GLTypeUniformLocation)
+    }
+
+    unsafe fn gl_get_program_link_status(&self, _program: GLTypeProgram) -> bool
+    {
+        true
+    }
+
+    unsafe fn gl_get_shader_compile_status(&self, _shader: GLTypeShader) -> bool
+    {
+        true
+    }
+
+    unsafe fn gl_get_program_info_log(
+        &self,
+        _program: GLTypeProgram
+    ) -> Result<String, BacktraceError<ErrorMessage>>
+    {
+        Ok(String::new())
+    }
+
+    unsafe fn gl_get_shader_info_log(
+        &self,
+        _shader: GLTypeShader
+    ) -> Result<String, BacktraceError<ErrorMessage>>
+    {
+        Ok(String::new())
+    }
+
+    unsafe fn gl_read_pixels(
+        &self,
+        x: GLint,
+        y: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        format: GLenum,
+        _data_type: GLenum,
+        data: &mut [MaybeUninit<u8>]
+    )
+    {
+        let pixels = self
+            .state
+            .borrow_mut()
+            .read_pixels(IntRect::new(x, y, width, height), format);
+
+        for (output, input) in data.iter_mut().zip(pixels) {
+            output.write(input);
+        }
+    }
+
+    unsafe fn gl_read_pixels_to_buffer(
+        &self,
+        x: GLint,
+        y: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        format: GLenum,
+        _data_type: GLenum,
+        offset: GLuint
+    )
+    {
+        let mut state = self.state.borrow_mut();
+
+        let pixels = state.read_pixels(IntRect::new(x, y, width, height), format);
+
+        if let Some(buffer) = state.bound_buffer_mut(GL_PIXEL_PACK_BUFFER) {
+            let offset = offset as usize;
+
+            if buffer.len() < offset + pixels.len() {
+                buffer.resize(offset + pixels.len(), 0);
+            }
+
+            buffer[offset..offset + pixels.len()].copy_from_slice(&pixels);
+        }
+    }
+}
+
+/// Copies pixel data in the specified GL format into a region of `plane`,
+/// converting it to RGBA.
+fn copy_pixels_into(
+    plane: &mut Plane<Rgba>,
+    (x, y): (usize, usize),
+    (width, height): (usize, usize),
+    format: GLenum,
+    unpack_alignment: usize,
+    pixels: &[u8]
+)
+{
+    let bytes_per_pixel = match format {
+        GL_RED => 1,
+        GL_RGB => 3,
+        _ => 4
+    };
+
+    let row_stride = align(width * bytes_per_pixel, unpack_alignment);
+
+    for row in 0..height.min(plane.height.saturating_sub(y)) {
+        for column in 0..width.min(plane.width.saturating_sub(x)) {
+            let offset = row * row_stride + column * bytes_per_pixel;
+
+            let input = match pixels.get(offset..offset + bytes_per_pixel) {
+                None => continue,
+                Some(input) => input
+            };
+
+            let pixel = match bytes_per_pixel {
+                1 => [input[0], 0, 0, 255],
+                3 => [input[0], input[1], input[2], 255],
+                _ => [input[0], input[1], input[2], input[3]]
+            };
+
+            plane.set(x + column, y + row, pixel);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::color::Color;
+    use crate::image::ImageDataType;
+    use crate::shape::Rectangle;
+    use crate::GLRenderer;
+
+    #[test]
+    fn test_shared_edges_drawn_once()
+    {
+        let mut renderer = GLRenderer::new_software((8, 8)).unwrap();
+
+        // The diagonal shared by the quad's two triangles passes through pixel
+        // centers, which must only be blended once
+        let capture = renderer.draw_frame(|graphics| {
+            graphics.clear_screen(Color::BLACK);
+            graphics.draw_rectangle(
+                Rectangle::from_tuples((0.0, 0.0), (8.0, 8.0)),
+                Color::from_rgba(1.0, 1.0, 1.0, 0.5)
+            );
+            graphics.capture(ImageDataType::RGBA)
+        });
+
+        for pixel in capture.data().chunks_exact(4) {
+            assert_eq!(pixel, [128, 128, 128, 255]);
+        }
+    }
+}
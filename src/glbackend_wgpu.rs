@@ -0,0 +1,2667 @@
+/*
+ *  Copyright 2021 QuantumBadger
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::mem::MaybeUninit;
+
+use wgpu::util::DeviceExt;
+
+use crate::error::{BacktraceError, ErrorMessage};
+use crate::glbackend::constants::*;
+use crate::glbackend::types::*;
+use crate::glbackend::GLBackend;
+
+const ATTRIBUTE_NAMES: [&str; 5] = [
+    "in_Position",
+    "in_Color",
+    "in_TextureCoord",
+    "in_TextureMix",
+    "in_CircleMix"
+];
+
+/// The number of components in each attribute. The attributes are interleaved
+/// into vertices of `VERTEX_FLOATS` floats for the wgpu vertex shader.
+const ATTRIBUTE_COMPONENTS: [usize; ATTRIBUTE_NAMES.len()] = [2, 4, 2, 1, 1];
+const VERTEX_FLOATS: usize = 10;
+
+const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; 5] = wgpu::vertex_attr_array![
+    0 => Float32x2,
+    1 => Float32x4,
+    2 => Float32x2,
+    3 => Float32,
+    4 => Float32
+];
+
+const UNIFORM_NAMES: [&str; 8] = [
+    "in_ScaleX",
+    "in_ScaleY",
+    "in_OffsetY",
+    "in_TransformRow0",
+    "in_TransformRow1",
+    "in_Tint",
+    "in_Texture",
+    "in_SmoothCircleEdges"
+];
+
+const UNIFORM_SCALE_X: usize = 0;
+const UNIFORM_SCALE_Y: usize = 1;
+const UNIFORM_OFFSET_Y: usize = 2;
+const UNIFORM_TRANSFORM_ROW_0: usize = 3;
+const UNIFORM_TRANSFORM_ROW_1: usize = 4;
+const UNIFORM_TINT: usize = 5;
+const UNIFORM_TEXTURE: usize = 6;
+const UNIFORM_SMOOTH_CIRCLE_EDGES: usize = 7;
+
+/// The size of the `Uniforms` struct in `SHADER_SOURCE`, in floats.
+const UNIFORM_FLOATS: usize = 24;
+
+/// The only multisample count (other than one) which wgpu guarantees to
+/// support for color and stencil attachments.
+const MULTISAMPLE_COUNT: u32 = 4;
+
+const STENCIL_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Stencil8;
+const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+/// A port of the `r2d` vertex and fragment shaders, along with the shaders
+/// used to emulate `glClear()` and `glGenerateMipmap()`.
+const SHADER_SOURCE: &str = r#"
+struct Uniforms {
+    transform_row_0: vec4<f32>,
+    transform_row_1: vec4<f32>,
+    tint: vec4<f32>,
+    viewport_adjust: vec4<f32>,
+    scale_x: f32,
+    scale_y: f32,
+    offset_y: f32,
+    flip_y: f32,
+    smooth_circle_edges: f32,
+}
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+@group(0) @binding(1) var in_texture: texture_2d<f32>;
+@group(0) @binding(2) var in_sampler: sampler;
+
+struct VertexInput {
+    @location(0) position: vec2<f32>,
+    @location(1) color: vec4<f32>,
+    @location(2) texture_coord: vec2<f32>,
+    @location(3) texture_mix: f32,
+    @location(4) circle_mix: f32,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) texture_coord: vec2<f32>,
+    @location(2) texture_mix: f32,
+    @location(3) circle_mix: f32,
+}
+
+fn to_clip_space(position: vec2<f32>) -> vec4<f32> {
+    let flipped = vec2<f32>(position.x, position.y * uniforms.flip_y);
+    let adjusted = flipped * uniforms.viewport_adjust.xy + uniforms.viewport_adjust.zw;
+    return vec4<f32>(adjusted, 0.0, 1.0);
+}
+
+@vertex
+fn vs_main(input: VertexInput) -> VertexOutput {
+    let position = vec2<f32>(
+        dot(uniforms.transform_row_0.xyz, vec3<f32>(input.position, 1.0)),
+        dot(uniforms.transform_row_1.xyz, vec3<f32>(input.position, 1.0)));
+
+    var output: VertexOutput;
+
+    output.position = to_clip_space(vec2<f32>(
+        position.x * uniforms.scale_x - 1.0,
+        position.y * uniforms.scale_y + uniforms.offset_y));
+
+    output.color = input.color * uniforms.tint;
+    output.texture_coord = input.texture_coord;
+    output.texture_mix = input.texture_mix;
+    output.circle_mix = input.circle_mix;
+
+    return output;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let tex_col = textureSample(in_texture, in_sampler, input.texture_coord);
+
+    let tex_coord_mag_squared = dot(input.texture_coord, input.texture_coord);
+
+    let circle_edge_width = max(fwidth(tex_coord_mag_squared), 0.000001);
+
+    let circle_alpha = mix(
+        1.0 - step(1.0, tex_coord_mag_squared),
+        clamp((1.0 - tex_coord_mag_squared) / circle_edge_width + 0.5, 0.0, 1.0),
+        uniforms.smooth_circle_edges);
+
+    let color = input.color * (
+        vec4<f32>(1.0 - input.texture_mix - input.circle_mix)
+            + (tex_col * input.texture_mix)
+            + (vec4<f32>(vec3<f32>(1.0), circle_alpha)) * input.circle_mix);
+
+    // Blending is performed using premultiplied alpha
+    return vec4<f32>(color.rgb * color.a, color.a);
+}
+
+struct FullscreenOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) texture_coord: vec2<f32>,
+}
+
+@vertex
+fn vs_fullscreen(@builtin(vertex_index) index: u32) -> FullscreenOutput {
+    let position = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u)) * 2.0 - 1.0;
+
+    var output: FullscreenOutput;
+    output.position = vec4<f32>(position, 0.0, 1.0);
+    output.texture_coord = vec2<f32>(position.x + 1.0, 1.0 - position.y) * 0.5;
+    return output;
+}
+
+@fragment
+fn fs_clear() -> @location(0) vec4<f32> {
+    return uniforms.tint;
+}
+
+@fragment
+fn fs_mipmap(input: FullscreenOutput) -> @location(0) vec4<f32> {
+    return textureSample(in_texture, in_sampler, input.texture_coord);
+}
+"#;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct IntRect
+{
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32
+}
+
+impl IntRect
+{
+    fn new(x: i32, y: i32, width: i32, height: i32) -> Self
+    {
+        IntRect {
+            x,
+            y,
+            width,
+            height
+        }
+    }
+
+    fn intersect(&self, other: &IntRect) -> Option<IntRect>
+    {
+        let x0 = self.x.max(other.x);
+        let y0 = self.y.max(other.y);
+        let x1 = (self.x + self.width).min(other.x + other.width);
+        let y1 = (self.y + self.height).min(other.y + other.height);
+
+        if x1 <= x0 || y1 <= y0 {
+            return None;
+        }
+
+        Some(IntRect::new(x0, y0, x1 - x0, y1 - y0))
+    }
+}
+
+struct Texture
+{
+    storage: Option<wgpu::Texture>,
+    has_mipmaps: bool,
+    wrap_s: GLenum,
+    wrap_t: GLenum,
+    min_filter: GLenum,
+    mag_filter: GLenum
+}
+
+impl Texture
+{
+    fn new() -> Self
+    {
+        Texture {
+            storage: None,
+            has_mipmaps: false,
+            wrap_s: GL_REPEAT,
+            wrap_t: GL_REPEAT,
+            min_filter: GL_LINEAR,
+            mag_filter: GL_LINEAR
+        }
+    }
+
+    fn uses_mipmaps(&self) -> bool
+    {
+        self.has_mipmaps && self.min_filter == GL_LINEAR_MIPMAP_LINEAR
+    }
+
+    fn sampler_key(&self) -> SamplerKey
+    {
+        SamplerKey {
+            wrap_s: self.wrap_s,
+            wrap_t: self.wrap_t,
+            min_filter: self.min_filter,
+            mag_filter: self.mag_filter
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColorAttachment
+{
+    Texture(GLuint),
+    Renderbuffer(GLuint)
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Framebuffer
+{
+    color: Option<ColorAttachment>,
+    stencil: Option<GLuint>
+}
+
+#[derive(Debug, Clone, Copy)]
+struct AttributePointer
+{
+    buffer: GLuint,
+    components: usize,
+    stride: usize,
+    offset: usize
+}
+
+#[derive(Debug, Clone, Copy)]
+struct StencilState
+{
+    enabled: bool,
+    func: GLenum,
+    reference: u32,
+    mask: u32,
+    write_mask: u32,
+    op_fail: GLenum,
+    op_pass: GLenum,
+    clear_value: u32
+}
+
+impl StencilState
+{
+    fn to_wgpu(self) -> wgpu::StencilState
+    {
+        if !self.enabled {
+            return stencil_state(wgpu::StencilFaceState::IGNORE, 0, 0);
+        }
+
+        let compare = match self.func {
+            GL_EQUAL => wgpu::CompareFunction::Equal,
+            _ => wgpu::CompareFunction::Always
+        };
+
+        let operation = |op: GLenum| match op {
+            GL_REPLACE => wgpu::StencilOperation::Replace,
+            _ => wgpu::StencilOperation::Keep
+        };
+
+        stencil_state(
+            wgpu::StencilFaceState {
+                compare,
+                fail_op: operation(self.op_fail),
+                depth_fail_op: wgpu::StencilOperation::Keep,
+                pass_op: operation(self.op_pass)
+            },
+            self.mask,
+            self.write_mask
+        )
+    }
+}
+
+fn stencil_state(
+    face: wgpu::StencilFaceState,
+    read_mask: u32,
+    write_mask: u32
+) -> wgpu::StencilState
+{
+    wgpu::StencilState {
+        front: face,
+        back: face,
+        read_mask,
+        write_mask
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BlendState
+{
+    enabled: bool,
+    src_rgb: GLenum,
+    dst_rgb: GLenum,
+    src_alpha: GLenum,
+    dst_alpha: GLenum
+}
+
+impl BlendState
+{
+    fn to_wgpu(self) -> Option<wgpu::BlendState>
+    {
+        if !self.enabled {
+            return None;
+        }
+
+        let factor = |factor: GLenum| match factor {
+            GL_ONE => wgpu::BlendFactor::One,
+            GL_ONE_MINUS_SRC_ALPHA => wgpu::BlendFactor::OneMinusSrcAlpha,
+            GL_ONE_MINUS_SRC_COLOR => wgpu::BlendFactor::OneMinusSrc,
+            GL_DST_COLOR => wgpu::BlendFactor::Dst,
+            _ => wgpu::BlendFactor::Zero
+        };
+
+        let component = |src: GLenum, dst: GLenum| wgpu::BlendComponent {
+            src_factor: factor(src),
+            dst_factor: factor(dst),
+            operation: wgpu::BlendOperation::Add
+        };
+
+        Some(wgpu::BlendState {
+            color: component(self.src_rgb, self.dst_rgb),
+            alpha: component(self.src_alpha, self.dst_alpha)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+enum Program
+{
+    Draw,
+    Clear,
+    Mipmap
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+struct PipelineKey
+{
+    program: Program,
+    format: wgpu::TextureFormat,
+    samples: u32,
+    color_writes: wgpu::ColorWrites,
+    blend: Option<wgpu::BlendState>,
+    stencil: Option<wgpu::StencilState>
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+struct SamplerKey
+{
+    wrap_s: GLenum,
+    wrap_t: GLenum,
+    min_filter: GLenum,
+    mag_filter: GLenum
+}
+
+/// The attachments of the framebuffer being drawn to or read from.
+struct Target
+{
+    color: wgpu::Texture,
+    stencil: Option<wgpu::Texture>,
+
+    /// OpenGL stores rows from the bottom up. Textures keep this layout, so
+    /// that texture coordinates work as they do in OpenGL, but the default
+    /// framebuffer and renderbuffers store rows from the top down, so that
+    /// the output can be presented directly.
+    top_down: bool
+}
+
+impl Target
+{
+    fn bounds(&self) -> IntRect
+    {
+        IntRect::new(0, 0, self.color.width() as i32, self.color.height() as i32)
+    }
+
+    /// Converts a rectangle in OpenGL window coordinates into texture rows,
+    /// counted from the top of the texture.
+    fn to_texture_rows(&self, rect: IntRect) -> IntRect
+    {
+        if !self.top_down {
+            return rect;
+        }
+
+        IntRect::new(
+            rect.x,
+            self.color.height() as i32 - rect.y - rect.height,
+            rect.width,
+            rect.height
+        )
+    }
+
+    fn color_view(&self) -> wgpu::TextureView
+    {
+        single_level_view(&self.color, 0)
+    }
+
+    fn flip_y(&self) -> f32
+    {
+        if self.top_down {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+}
+
+fn single_level_view(texture: &wgpu::Texture, level: u32) -> wgpu::TextureView
+{
+    texture.create_view(&wgpu::TextureViewDescriptor {
+        base_mip_level: level,
+        mip_level_count: Some(1),
+        ..Default::default()
+    })
+}
+
+/// A draw call which is ready to be recorded in a render pass.
+struct PassDraw<'a>
+{
+    target: &'a Target,
+    pipeline: wgpu::RenderPipeline,
+    bind_group: wgpu::BindGroup,
+    viewport: IntRect,
+    scissor: IntRect,
+    stencil_reference: u32,
+    vertices: Option<wgpu::Buffer>,
+    vertex_count: u32
+}
+
+struct WgpuState
+{
+    next_handle: GLuint,
+
+    buffers: HashMap<GLuint, Vec<u8>>,
+    textures: HashMap<GLuint, Texture>,
+    framebuffers: HashMap<GLuint, Framebuffer>,
+    renderbuffers: HashMap<GLuint, Option<wgpu::Texture>>,
+
+    default_color: Option<wgpu::Texture>,
+    default_stencil: Option<wgpu::Texture>,
+
+    bound_buffers: HashMap<GLenum, GLuint>,
+    bound_textures: HashMap<GLuint, GLuint>,
+    active_texture_unit: GLuint,
+    bound_renderbuffer: Option<GLuint>,
+    read_framebuffer: Option<GLuint>,
+    draw_framebuffer: Option<GLuint>,
+
+    attributes: [Option<AttributePointer>; ATTRIBUTE_NAMES.len()],
+    uniforms: [[f32; 4]; UNIFORM_NAMES.len()],
+
+    viewport: IntRect,
+    scissor: IntRect,
+    scissor_enabled: bool,
+    color_mask: [bool; 4],
+    clear_color: [f32; 4],
+    blend: BlendState,
+    stencil: StencilState,
+
+    unpack_alignment: usize,
+    pack_alignment: usize,
+
+    encoder: Option<wgpu::CommandEncoder>,
+    pipelines: HashMap<PipelineKey, wgpu::RenderPipeline>,
+    samplers: HashMap<SamplerKey, wgpu::Sampler>
+}
+
+impl WgpuState
+{
+    fn gen_handle(&mut self) -> GLuint
+    {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        handle
+    }
+
+    fn bound_texture_mut(&mut self) -> Option<&mut Texture>
+    {
+        let handle = *self.bound_textures.get(&self.active_texture_unit)?;
+        self.textures.get_mut(&handle)
+    }
+
+    fn bound_buffer_mut(&mut self, target: GLenum) -> Option<&mut Vec<u8>>
+    {
+        let handle = *self.bound_buffers.get(&target)?;
+        self.buffers.get_mut(&handle)
+    }
+
+    fn target(&self, framebuffer: Option<GLuint>) -> Option<Target>
+    {
+        let framebuffer = match framebuffer {
+            None => {
+                return Some(Target {
+                    color: self.default_color.clone()?,
+                    stencil: self.default_stencil.clone(),
+                    top_down: true
+                })
+            }
+            Some(handle) => self.framebuffers.get(&handle)?
+        };
+
+        let (color, top_down) = match framebuffer.color? {
+            ColorAttachment::Texture(handle) => {
+                (self.textures.get(&handle)?.storage.clone()?, false)
+            }
+            ColorAttachment::Renderbuffer(handle) => {
+                (self.renderbuffers.get(&handle)?.clone()?, true)
+            }
+        };
+
+        let stencil = framebuffer
+            .stencil
+            .and_then(|handle| self.renderbuffers.get(&handle).cloned().flatten());
+
+        Some(Target {
+            color,
+            stencil,
+            top_down
+        })
+    }
+
+    fn color_writes(&self) -> wgpu::ColorWrites
+    {
+        let mut writes = wgpu::ColorWrites::empty();
+
+        for (enabled, channel) in self.color_mask.iter().zip([
+            wgpu::ColorWrites::RED,
+            wgpu::ColorWrites::GREEN,
+            wgpu::ColorWrites::BLUE,
+            wgpu::ColorWrites::ALPHA
+        ]) {
+            if *enabled {
+                writes |= channel;
+            }
+        }
+
+        writes
+    }
+
+    /// Returns the scissor rectangle in texture rows, or `None` if nothing
+    /// can be drawn.
+    fn scissor_region(&self, target: &Target) -> Option<IntRect>
+    {
+        if !self.scissor_enabled {
+            return Some(target.bounds());
+        }
+
+        target
+            .to_texture_rows(self.scissor)
+            .intersect(&target.bounds())
+    }
+
+    /// Interleaves the vertex attributes for the specified range of vertices.
+    fn gather_vertices(&self, first: usize, count: usize) -> Vec<f32>
+    {
+        let mut vertices = vec![0.0; count * VERTEX_FLOATS];
+        let mut attribute_offset = 0;
+
+        for (attribute, components) in self.attributes.iter().zip(ATTRIBUTE_COMPONENTS) {
+            let buffer = attribute.and_then(|attribute| {
+                Some((attribute, self.buffers.get(&attribute.buffer)?))
+            });
+
+            if let Some((attribute, buffer)) = buffer {
+                for vertex in 0..count {
+                    let start = attribute.offset + (first + vertex) * attribute.stride;
+
+                    for component in 0..components.min(attribute.components) {
+                        let offset = start + component * 4;
+
+                        if let Some(bytes) = buffer.get(offset..offset + 4) {
+                            vertices
+                                [vertex * VERTEX_FLOATS + attribute_offset + component] =
+                                f32::from_ne_bytes(bytes.try_into().unwrap());
+                        }
+                    }
+                }
+            }
+
+            attribute_offset += components;
+        }
+
+        vertices
+    }
+}
+
+/// A [GLBackend] which renders using wgpu, emulating the subset of OpenGL used
+/// by `Renderer2D`. As with the software renderer, shader sources are ignored:
+/// draw calls always run a WGSL port of the `r2d` shaders.
+///
+/// Commands are recorded into a single command encoder, which is submitted
+/// when pixels are read back, when texture data is uploaded, or when
+/// [GLBackendWgpu::flush] is called at the end of each frame.
+pub struct GLBackendWgpu
+{
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    default_format: wgpu::TextureFormat,
+    shader: wgpu::ShaderModule,
+    bind_group_layout: wgpu::BindGroupLayout,
+    pipeline_layout: wgpu::PipelineLayout,
+    placeholder_texture: wgpu::Texture,
+    state: RefCell<WgpuState>
+}
+
+impl GLBackendWgpu
+{
+    /// Creates a backend which draws the default framebuffer into a texture
+    /// with the specified format, which must be `Rgba8Unorm` or `Bgra8Unorm`.
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        default_format: wgpu::TextureFormat
+    ) -> Self
+    {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("speedy2d_shader"),
+            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(SHADER_SOURCE))
+        });
+
+        let bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("speedy2d_bind_group_layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None
+                        },
+                        count: None
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float {
+                                filterable: true
+                            },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false
+                        },
+                        count: None
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(
+                            wgpu::SamplerBindingType::Filtering
+                        ),
+                        count: None
+                    }
+                ]
+            });
+
+        let pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("speedy2d_pipeline_layout"),
+                bind_group_layouts: &[&bind_group_layout],
+                push_constant_ranges: &[]
+            });
+
+        // Sampled in place of incomplete textures, which OpenGL treats as
+        // opaque black
+        let placeholder_texture = device.create_texture_with_data(
+            queue,
+            &wgpu::TextureDescriptor {
+                label: Some("speedy2d_placeholder_texture"),
+                size: wgpu::Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 1
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: TEXTURE_FORMAT,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[]
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &[0, 0, 0, 255]
+        );
+
+        GLBackendWgpu {
+            device: device.clone(),
+            queue: queue.clone(),
+            default_format,
+            shader,
+            bind_group_layout,
+            pipeline_layout,
+            placeholder_texture,
+            state: RefCell::new(WgpuState {
+                next_handle: 1,
+                buffers: HashMap::new(),
+                textures: HashMap::new(),
+                framebuffers: HashMap::new(),
+                renderbuffers: HashMap::new(),
+                default_color: None,
+                default_stencil: None,
+                bound_buffers: HashMap::new(),
+                bound_textures: HashMap::new(),
+                active_texture_unit: 0,
+                bound_renderbuffer: None,
+                read_framebuffer: None,
+                draw_framebuffer: None,
+                attributes: [None; ATTRIBUTE_NAMES.len()],
+                uniforms: [[0.0; 4]; UNIFORM_NAMES.len()],
+                viewport: IntRect::new(0, 0, 0, 0),
+                scissor: IntRect::new(0, 0, 0, 0),
+                scissor_enabled: false,
+                color_mask: [true; 4],
+                clear_color: [0.0; 4],
+                blend: BlendState {
+                    enabled: false,
+                    src_rgb: GL_ONE,
+                    dst_rgb: 0,
+                    src_alpha: GL_ONE,
+                    dst_alpha: 0
+                },
+                stencil: StencilState {
+                    enabled: false,
+                    func: GL_ALWAYS,
+                    reference: 0,
+                    mask: 0xFF,
+                    write_mask: 0xFF,
+                    op_fail: GL_KEEP,
+                    op_pass: GL_KEEP,
+                    clear_value: 0
+                },
+                unpack_alignment: 4,
+                pack_alignment: 4,
+                encoder: None,
+                pipelines: HashMap::new(),
+                samplers: HashMap::new()
+            })
+        }
+    }
+
+    /// Returns the texture backing the default framebuffer, if the viewport
+    /// has been set to a non-zero size.
+    pub fn default_texture(&self) -> Option<wgpu::Texture>
+    {
+        self.state.borrow().default_color.clone()
+    }
+
+    /// Submits all commands recorded so far to the queue.
+    pub fn flush(&self)
+    {
+        Self::submit(&self.queue, &mut self.state.borrow_mut());
+    }
+
+    fn submit(queue: &wgpu::Queue, state: &mut WgpuState)
+    {
+        if let Some(encoder) = state.encoder.take() {
+            queue.submit(std::iter::once(encoder.finish()));
+        }
+    }
+
+    fn encoder<'a>(&self, state: &'a mut WgpuState) -> &'a mut wgpu::CommandEncoder
+    {
+        state.encoder.get_or_insert_with(|| {
+            self.device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("speedy2d_encoder")
+                })
+        })
+    }
+
+    fn set_uniform(&self, handle: &GLTypeUniformLocation, value: [f32; 4])
+    {
+        if let Some(uniform) = self.state.borrow_mut().uniforms.get_mut(*handle as usize)
+        {
+            *uniform = value;
+        }
+    }
+
+    fn pipeline(&self, state: &mut WgpuState, key: PipelineKey) -> wgpu::RenderPipeline
+    {
+        state
+            .pipelines
+            .entry(key.clone())
+            .or_insert_with(|| {
+                let (vertex_entry_point, fragment_entry_point) = match key.program {
+                    Program::Draw => ("vs_main", "fs_main"),
+                    Program::Clear => ("vs_fullscreen", "fs_clear"),
+                    Program::Mipmap => ("vs_fullscreen", "fs_mipmap")
+                };
+
+                let vertex_buffers = match key.program {
+                    Program::Draw => vec![wgpu::VertexBufferLayout {
+                        array_stride: (VERTEX_FLOATS * 4) as wgpu::BufferAddress,
+                        step_mode: wgpu::VertexStepMode::Vertex,
+                        attributes: &VERTEX_ATTRIBUTES
+                    }],
+                    Program::Clear | Program::Mipmap => vec![]
+                };
+
+                self.device
+                    .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                        label: Some("speedy2d_pipeline"),
+                        layout: Some(&self.pipeline_layout),
+                        vertex: wgpu::VertexState {
+                            module: &self.shader,
+                            entry_point: Some(vertex_entry_point),
+                            compilation_options: Default::default(),
+                            buffers: &vertex_buffers
+                        },
+                        primitive: wgpu::PrimitiveState::default(),
+                        depth_stencil: key.stencil.map(|stencil| {
+                            wgpu::DepthStencilState {
+                                format: STENCIL_FORMAT,
+                                depth_write_enabled: false,
+                                depth_compare: wgpu::CompareFunction::Always,
+                                stencil,
+                                bias: wgpu::DepthBiasState::default()
+                            }
+                        }),
+                        multisample: wgpu::MultisampleState {
+                            count: key.samples,
+                            ..Default::default()
+                        },
+                        fragment: Some(wgpu::FragmentState {
+                            module: &self.shader,
+                            entry_point: Some(fragment_entry_point),
+                            compilation_options: Default::default(),
+                            targets: &[Some(wgpu::ColorTargetState {
+                                format: key.format,
+                                blend: key.blend,
+                                write_mask: key.color_writes
+                            })]
+                        }),
+                        multiview: None,
+                        cache: None
+                    })
+            })
+            .clone()
+    }
+
+    fn sampler(&self, state: &mut WgpuState, key: SamplerKey) -> wgpu::Sampler
+    {
+        state
+            .samplers
+            .entry(key)
+            .or_insert_with(|| {
+                let address_mode = |wrap: GLenum| match wrap {
+                    GL_CLAMP_TO_EDGE => wgpu::AddressMode::ClampToEdge,
+                    GL_MIRRORED_REPEAT => wgpu::AddressMode::MirrorRepeat,
+                    _ => wgpu::AddressMode::Repeat
+                };
+
+                let filter_mode = |filter: GLenum| match filter {
+                    GL_NEAREST => wgpu::FilterMode::Nearest,
+                    _ => wgpu::FilterMode::Linear
+                };
+
+                self.device.create_sampler(&wgpu::SamplerDescriptor {
+                    label: Some("speedy2d_sampler"),
+                    address_mode_u: address_mode(key.wrap_s),
+                    address_mode_v: address_mode(key.wrap_t),
+                    mag_filter: filter_mode(key.mag_filter),
+                    min_filter: filter_mode(key.min_filter),
+                    mipmap_filter: wgpu::FilterMode::Linear,
+                    ..Default::default()
+                })
+            })
+            .clone()
+    }
+
+    fn bind_group(
+        &self,
+        uniforms: &[f32; UNIFORM_FLOATS],
+        texture: &wgpu::TextureView,
+        sampler: &wgpu::Sampler
+    ) -> wgpu::BindGroup
+    {
+        let uniforms: Vec<u8> = uniforms.iter().flat_map(|v| v.to_ne_bytes()).collect();
+
+        let buffer = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("speedy2d_uniforms"),
+                contents: &uniforms,
+                usage: wgpu::BufferUsages::UNIFORM
+            });
+
+        self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("speedy2d_bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding()
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(texture)
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(sampler)
+                }
+            ]
+        })
+    }
+
+    /// Returns the view and sampler for the texture bound to the specified
+    /// unit, falling back to the placeholder texture. wgpu doesn't allow a
+    /// texture to be sampled while it's being drawn to, so the placeholder is
+    /// also used if the texture is attached to the target.
+    fn texture_binding(
+        &self,
+        state: &mut WgpuState,
+        unit: GLuint,
+        target: &Target
+    ) -> (wgpu::TextureView, wgpu::Sampler)
+    {
+        let texture = state
+            .bound_textures
+            .get(&unit)
+            .and_then(|handle| state.textures.get(handle))
+            .and_then(|texture| Some((texture.storage.clone()?, texture)))
+            .filter(|(storage, _)| *storage != target.color);
+
+        match texture {
+            None => {
+                let key = Texture::new().sampler_key();
+
+                (
+                    self.placeholder_texture.create_view(&Default::default()),
+                    self.sampler(state, key)
+                )
+            }
+            Some((storage, texture)) => {
+                let mip_level_count = if texture.uses_mipmaps() {
+                    None
+                } else {
+                    Some(1)
+                };
+
+                let key = texture.sampler_key();
+
+                let view = storage.create_view(&wgpu::TextureViewDescriptor {
+                    mip_level_count,
+                    ..Default::default()
+                });
+
+                (view, self.sampler(state, key))
+            }
+        }
+    }
+
+    fn uniforms(
+        state: &WgpuState,
+        target: &Target,
+        viewport: IntRect
+    ) -> [f32; UNIFORM_FLOATS]
+    {
+        // wgpu requires the viewport to lie within the target, so the part of
+        // the OpenGL viewport which does is used instead, and positions are
+        // scaled and offset to compensate.
+        let gl_viewport = target.to_texture_rows(state.viewport);
+
+        let scale_x = gl_viewport.width as f32 / viewport.width as f32;
+        let scale_y = gl_viewport.height as f32 / viewport.height as f32;
+
+        let offset_x = (2 * (gl_viewport.x - viewport.x) + gl_viewport.width
+            - viewport.width) as f32
+            / viewport.width as f32;
+
+        let offset_y = (viewport.height
+            - gl_viewport.height
+            - 2 * (gl_viewport.y - viewport.y)) as f32
+            / viewport.height as f32;
+
+        let uniform = |index: usize| state.uniforms[index];
+
+        let mut uniforms = [0.0; UNIFORM_FLOATS];
+
+        uniforms[0..4].copy_from_slice(&uniform(UNIFORM_TRANSFORM_ROW_0));
+        uniforms[4..8].copy_from_slice(&uniform(UNIFORM_TRANSFORM_ROW_1));
+        uniforms[8..12].copy_from_slice(&uniform(UNIFORM_TINT));
+        uniforms[12..16].copy_from_slice(&[scale_x, scale_y, offset_x, offset_y]);
+        uniforms[16] = uniform(UNIFORM_SCALE_X)[0];
+        uniforms[17] = uniform(UNIFORM_SCALE_Y)[0];
+        uniforms[18] = uniform(UNIFORM_OFFSET_Y)[0];
+        uniforms[19] = target.flip_y();
+        uniforms[20] = uniform(UNIFORM_SMOOTH_CIRCLE_EDGES)[0];
+
+        uniforms
+    }
+
+    fn record_draw(&self, state: &mut WgpuState, draw: PassDraw)
+    {
+        let color_view = draw.target.color_view();
+
+        let stencil_view = draw
+            .target
+            .stencil
+            .as_ref()
+            .map(|stencil| stencil.create_view(&Default::default()));
+
+        let encoder = self.encoder(state);
+
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("speedy2d_pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &color_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store
+                }
+            })],
+            depth_stencil_attachment: stencil_view.as_ref().map(|view| {
+                wgpu::RenderPassDepthStencilAttachment {
+                    view,
+                    depth_ops: None,
+                    stencil_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store
+                    })
+                }
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None
+        });
+
+        pass.set_pipeline(&draw.pipeline);
+        pass.set_bind_group(0, &draw.bind_group, &[]);
+
+        pass.set_viewport(
+            draw.viewport.x as f32,
+            draw.viewport.y as f32,
+            draw.viewport.width as f32,
+            draw.viewport.height as f32,
+            0.0,
+            1.0
+        );
+
+        pass.set_scissor_rect(
+            draw.scissor.x as u32,
+            draw.scissor.y as u32,
+            draw.scissor.width as u32,
+            draw.scissor.height as u32
+        );
+
+        pass.set_stencil_reference(draw.stencil_reference);
+
+        if let Some(vertices) = &draw.vertices {
+            pass.set_vertex_buffer(0, vertices.slice(..));
+        }
+
+        pass.draw(0..draw.vertex_count, 0..1);
+    }
+
+    fn draw_triangles(&self, first: usize, count: usize)
+    {
+        let mut state = self.state.borrow_mut();
+
+        let target = match state.target(state.draw_framebuffer) {
+            None => return,
+            Some(target) => target
+        };
+
+        let viewport = match target
+            .to_texture_rows(state.viewport)
+            .intersect(&target.bounds())
+        {
+            None => return,
+            Some(viewport) => viewport
+        };
+
+        let scissor = match state.scissor_region(&target) {
+            None => return,
+            Some(scissor) => scissor
+        };
+
+        if count == 0 {
+            return;
+        }
+
+        let vertices = state.gather_vertices(first, count);
+        let vertices: Vec<u8> = vertices.iter().flat_map(|v| v.to_ne_bytes()).collect();
+
+        let vertices =
+            self.device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("speedy2d_vertices"),
+                    contents: &vertices,
+                    usage: wgpu::BufferUsages::VERTEX
+                });
+
+        let key = PipelineKey {
+            program: Program::Draw,
+            format: target.color.format(),
+            samples: target.color.sample_count(),
+            color_writes: state.color_writes(),
+            blend: state.blend.to_wgpu(),
+            stencil: target.stencil.as_ref().map(|_| state.stencil.to_wgpu())
+        };
+
+        let pipeline = self.pipeline(&mut state, key);
+        let uniforms = Self::uniforms(&state, &target, viewport);
+        let unit = state.uniforms[UNIFORM_TEXTURE][0] as GLuint;
+        let (texture, sampler) = self.texture_binding(&mut state, unit, &target);
+        let bind_group = self.bind_group(&uniforms, &texture, &sampler);
+        let stencil_reference = state.stencil.reference;
+
+        self.record_draw(
+            &mut state,
+            PassDraw {
+                target: &target,
+                pipeline,
+                bind_group,
+                viewport,
+                scissor,
+                stencil_reference,
+                vertices: Some(vertices),
+                vertex_count: count as u32
+            }
+        );
+    }
+
+    fn clear(&self, mask: GLenum)
+    {
+        let mut state = self.state.borrow_mut();
+
+        let target = match state.target(state.draw_framebuffer) {
+            None => return,
+            Some(target) => target
+        };
+
+        let scissor = match state.scissor_region(&target) {
+            None => return,
+            Some(scissor) => scissor
+        };
+
+        let color_writes = if mask & GL_COLOR_BUFFER_BIT != 0 {
+            state.color_writes()
+        } else {
+            wgpu::ColorWrites::empty()
+        };
+
+        let stencil = target.stencil.as_ref().map(|_| {
+            if mask & GL_STENCIL_BUFFER_BIT != 0 {
+                stencil_state(
+                    wgpu::StencilFaceState {
+                        compare: wgpu::CompareFunction::Always,
+                        fail_op: wgpu::StencilOperation::Keep,
+                        depth_fail_op: wgpu::StencilOperation::Keep,
+                        pass_op: wgpu::StencilOperation::Replace
+                    },
+                    0xFF,
+                    state.stencil.write_mask
+                )
+            } else {
+                stencil_state(wgpu::StencilFaceState::IGNORE, 0, 0)
+            }
+        });
+
+        let key = PipelineKey {
+            program: Program::Clear,
+            format: target.color.format(),
+            samples: target.color.sample_count(),
+            color_writes,
+            blend: None,
+            stencil
+        };
+
+        let pipeline = self.pipeline(&mut state, key);
+
+        let mut uniforms = [0.0; UNIFORM_FLOATS];
+        uniforms[8..12].copy_from_slice(&state.clear_color);
+
+        let (texture, sampler) = self.texture_binding(&mut state, 0, &target);
+        let bind_group = self.bind_group(&uniforms, &texture, &sampler);
+        let stencil_reference = state.stencil.clear_value;
+
+        self.record_draw(
+            &mut state,
+            PassDraw {
+                target: &target,
+                pipeline,
+                bind_group,
+                viewport: target.bounds(),
+                scissor,
+                stencil_reference,
+                vertices: None,
+                vertex_count: 3
+            }
+        );
+    }
+
+    fn generate_mipmaps(&self, storage: &wgpu::Texture)
+    {
+        let mut state = self.state.borrow_mut();
+
+        let key = PipelineKey {
+            program: Program::Mipmap,
+            format: storage.format(),
+            samples: 1,
+            color_writes: wgpu::ColorWrites::ALL,
+            blend: None,
+            stencil: None
+        };
+
+        let pipeline = self.pipeline(&mut state, key);
+
+        let sampler = self.sampler(
+            &mut state,
+            SamplerKey {
+                wrap_s: GL_CLAMP_TO_EDGE,
+                wrap_t: GL_CLAMP_TO_EDGE,
+                min_filter: GL_LINEAR,
+                mag_filter: GL_LINEAR
+            }
+        );
+
+        for level in 1..storage.mip_level_count() {
+            let source = single_level_view(storage, level - 1);
+            let destination = single_level_view(storage, level);
+
+            let bind_group = self.bind_group(&[0.0; UNIFORM_FLOATS], &source, &sampler);
+
+            let mut pass =
+                self.encoder(&mut state)
+                    .begin_render_pass(&wgpu::RenderPassDescriptor {
+                        label: Some("speedy2d_mipmap_pass"),
+                        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                            view: &destination,
+                            resolve_target: None,
+                            ops: wgpu::Operations {
+                                load: wgpu::LoadOp::Load,
+                                store: wgpu::StoreOp::Store
+                            }
+                        })],
+                        depth_stencil_attachment: None,
+                        timestamp_writes: None,
+                        occlusion_query_set: None
+                    });
+
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+    }
+
+    fn write_texture(
+        &self,
+        storage: &wgpu::Texture,
+        level: u32,
+        rect: IntRect,
+        format: GLenum,
+        pixels: &[u8]
+    )
+    {
+        let mut state = self.state.borrow_mut();
+
+        // Writes to the queue take effect before any commands which haven't
+        // been submitted yet, so earlier draws must be submitted first
+        Self::submit(&self.queue, &mut state);
+
+        let level_width = (storage.width() >> level).max(1) as i32;
+        let level_height = (storage.height() >> level).max(1) as i32;
+
+        let rect = match rect.intersect(&IntRect::new(0, 0, level_width, level_height)) {
+            None => return,
+            Some(clipped) => IntRect::new(rect.x, rect.y, clipped.width, clipped.height)
+        };
+
+        let data = to_rgba(
+            (rect.width as usize, rect.height as usize),
+            format,
+            state.unpack_alignment,
+            pixels
+        );
+
+        self.queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: storage,
+                mip_level: level,
+                origin: wgpu::Origin3d {
+                    x: rect.x as u32,
+                    y: rect.y as u32,
+                    z: 0
+                },
+                aspect: wgpu::TextureAspect::All
+            },
+            &data,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(rect.width as u32 * 4),
+                rows_per_image: None
+            },
+            wgpu::Extent3d {
+                width: rect.width as u32,
+                height: rect.height as u32,
+                depth_or_array_layers: 1
+            }
+        );
+    }
+
+    fn create_texture(
+        &self,
+        label: &str,
+        (width, height): (u32, u32),
+        format: wgpu::TextureFormat,
+        mip_level_count: u32,
+        sample_count: u32
+    ) -> wgpu::Texture
+    {
+        let mut usage = wgpu::TextureUsages::RENDER_ATTACHMENT;
+
+        if sample_count == 1 {
+            usage |= wgpu::TextureUsages::COPY_SRC
+                | wgpu::TextureUsages::COPY_DST
+                | wgpu::TextureUsages::TEXTURE_BINDING;
+        }
+
+        self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1
+            },
+            mip_level_count,
+            sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage,
+            view_formats: &[]
+        })
+    }
+
+    /// Reads a region of the read framebuffer, returning rows from the bottom
+    /// up in the specified format, as `glReadPixels()` does.
+    fn read_pixels(&self, rect: IntRect, format: GLenum) -> Vec<u8>
+    {
+        let mut state = self.state.borrow_mut();
+
+        let bytes_per_pixel = match format {
+            GL_RGB => 3,
+            _ => 4
+        };
+
+        let width = rect.width.max(0) as usize;
+        let height = rect.height.max(0) as usize;
+        let row_stride = align(width * bytes_per_pixel, state.pack_alignment);
+
+        let mut output = vec![0; row_stride * height];
+
+        let target = match state.target(state.read_framebuffer) {
+            None => return output,
+            Some(target) => target
+        };
+
+        if target.color.sample_count() > 1 {
+            log::error!("wgpu renderer: can't read pixels from a multisampled target");
+            return output;
+        }
+
+        let region = match rect.intersect(&target.bounds()) {
+            None => return output,
+            Some(region) => region
+        };
+
+        let texture_region = target.to_texture_rows(region);
+        let padded_row_bytes = align(
+            region.width as usize * 4,
+            wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize
+        );
+
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("speedy2d_read_pixels"),
+            size: (padded_row_bytes * region.height as usize) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false
+        });
+
+        self.encoder(&mut state).copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: &target.color,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: texture_region.x as u32,
+                    y: texture_region.y as u32,
+                    z: 0
+                },
+                aspect: wgpu::TextureAspect::All
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_bytes as u32),
+                    rows_per_image: None
+                }
+            },
+            wgpu::Extent3d {
+                width: region.width as u32,
+                height: region.height as u32,
+                depth_or_array_layers: 1
+            }
+        );
+
+        Self::submit(&self.queue, &mut state);
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                let _ignored = sender.send(result);
+            });
+
+        let _ignored = self.device.poll(wgpu::Maintain::Wait);
+
+        match receiver.try_recv() {
+            Ok(Ok(())) => {}
+            result => {
+                log::error!("wgpu renderer: failed to read pixels: {result:?}");
+                return output;
+            }
+        }
+
+        let swap_red_blue = matches!(
+            target.color.format(),
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb
+        );
+
+        {
+            let mapped = buffer.slice(..).get_mapped_range();
+
+            for row in 0..region.height as usize {
+                let source_row = if target.top_down {
+                    region.height as usize - 1 - row
+                } else {
+                    row
+                };
+
+                let output_row = (region.y - rect.y) as usize + row;
+
+                for column in 0..region.width as usize {
+                    let input = source_row * padded_row_bytes + column * 4;
+                    let mut pixel: [u8; 4] = mapped[input..input + 4].try_into().unwrap();
+
+                    if swap_red_blue {
+                        pixel.swap(0, 2);
+                    }
+
+                    let output_column = (region.x - rect.x) as usize + column;
+                    let offset =
+                        output_row * row_stride + output_column * bytes_per_pixel;
+
+                    output[offset..offset + bytes_per_pixel]
+                        .copy_from_slice(&pixel[..bytes_per_pixel]);
+                }
+            }
+        }
+
+        buffer.unmap();
+
+        output
+    }
+
+    fn blit(&self, source: IntRect, destination: IntRect)
+    {
+        let mut state = self.state.borrow_mut();
+
+        let (source_target, destination_target) = match (
+            state.target(state.read_framebuffer),
+            state.target(state.draw_framebuffer)
+        ) {
+            (Some(source), Some(destination)) => (source, destination),
+            _ => return
+        };
+
+        let same_size =
+            source.width == destination.width && source.height == destination.height;
+
+        if !same_size || source_target.top_down != destination_target.top_down {
+            log::warn!("wgpu renderer: ignoring unsupported framebuffer blit");
+            return;
+        }
+
+        if source_target.color.sample_count() > 1 {
+            // Multisampled textures can only be copied by resolving them
+            // into a texture of the same size
+            if source != source_target.bounds()
+                || destination != destination_target.bounds()
+                || source_target.bounds() != destination_target.bounds()
+            {
+                log::warn!("wgpu renderer: ignoring partial multisample resolve");
+                return;
+            }
+
+            let view = source_target.color_view();
+            let resolve_target = destination_target.color_view();
+
+            self.encoder(&mut state)
+                .begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("speedy2d_resolve_pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &view,
+                        resolve_target: Some(&resolve_target),
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: wgpu::StoreOp::Store
+                        }
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None
+                });
+
+            return;
+        }
+
+        let source = match source.intersect(&source_target.bounds()) {
+            None => return,
+            Some(source) => source_target.to_texture_rows(source)
+        };
+
+        let destination = destination_target.to_texture_rows(destination);
+
+        self.encoder(&mut state).copy_texture_to_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &source_target.color,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: source.x as u32,
+                    y: source.y as u32,
+                    z: 0
+                },
+                aspect: wgpu::TextureAspect::All
+            },
+            wgpu::TexelCopyTextureInfo {
+                texture: &destination_target.color,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: destination.x.max(0) as u32,
+                    y: destination.y.max(0) as u32,
+                    z: 0
+                },
+                aspect: wgpu::TextureAspect::All
+            },
+            wgpu::Extent3d {
+                width: source.width as u32,
+                height: source.height as u32,
+                depth_or_array_layers: 1
+            }
+        );
+    }
+}
+
+impl GLBackend for GLBackendWgpu
+{
+    unsafe fn gl_delete_program(&self, _handle: GLTypeProgram) {}
+
+    unsafe fn gl_delete_shader(&self, _handle: GLTypeShader) {}
+
+    unsafe fn gl_delete_buffer(&self, handle: GLTypeBuffer)
+    {
+        self.state.borrow_mut().buffers.remove(&handle);
+    }
+
+    unsafe fn gl_delete_texture(&self, handle: GLTypeTexture)
+    {
+        self.state.borrow_mut().textures.remove(&handle);
+    }
+
+    unsafe fn gl_delete_framebuffer(&self, handle: GLTypeFramebuffer)
+    {
+        self.state.borrow_mut().framebuffers.remove(&handle);
+    }
+
+    unsafe fn gl_delete_renderbuffer(&self, handle: GLTypeRenderbuffer)
+    {
+        self.state.borrow_mut().renderbuffers.remove(&handle);
+    }
+
+    unsafe fn gl_delete_vertex_array(&self, _handle: GLTypeVertexArray) {}
+
+    unsafe fn gl_active_texture(&self, unit: GLenum)
+    {
+        self.state.borrow_mut().active_texture_unit = unit - GL_TEXTURE0;
+    }
+
+    unsafe fn gl_bind_texture(&self, _target: GLenum, handle: GLTypeTexture)
+    {
+        let mut state = self.state.borrow_mut();
+        let unit = state.active_texture_unit;
+        state.bound_textures.insert(unit, handle);
+    }
+
+    unsafe fn gl_bind_framebuffer(
+        &self,
+        target: GLenum,
+        handle: Option<GLTypeFramebuffer>
+    )
+    {
+        let mut state = self.state.borrow_mut();
+
+        match target {
+            GL_READ_FRAMEBUFFER => state.read_framebuffer = handle,
+            GL_DRAW_FRAMEBUFFER => state.draw_framebuffer = handle,
+            _ => {
+                state.read_framebuffer = handle;
+                state.draw_framebuffer = handle;
+            }
+        }
+    }
+
+    unsafe fn gl_framebuffer_texture_2d(
+        &self,
+        _target: GLenum,
+        _attachment: GLenum,
+        _texture_target: GLenum,
+        texture: GLTypeTexture,
+        _level: GLint
+    )
+    {
+        let mut state = self.state.borrow_mut();
+
+        if let Some(handle) = state.draw_framebuffer {
+            if let Some(framebuffer) = state.framebuffers.get_mut(&handle) {
+                framebuffer.color = Some(ColorAttachment::Texture(texture));
+            }
+        }
+    }
+
+    unsafe fn gl_check_framebuffer_status(&self, _target: GLenum) -> GLenum
+    {
+        GL_FRAMEBUFFER_COMPLETE
+    }
+
+    unsafe fn gl_bind_renderbuffer(&self, _target: GLenum, handle: GLTypeRenderbuffer)
+    {
+        self.state.borrow_mut().bound_renderbuffer = Some(handle);
+    }
+
+    unsafe fn gl_renderbuffer_storage(
+        &self,
+        target: GLenum,
+        internal_format: GLenum,
+        width: GLsizei,
+        height: GLsizei
+    )
+    {
+        self.gl_renderbuffer_storage_multisample(
+            target,
+            0,
+            internal_format,
+            width,
+            height
+        )
+    }
+
+    unsafe fn gl_renderbuffer_storage_multisample(
+        &self,
+        _target: GLenum,
+        samples: GLsizei,
+        internal_format: GLenum,
+        width: GLsizei,
+        height: GLsizei
+    )
+    {
+        let sample_count = if samples > 1 { MULTISAMPLE_COUNT } else { 1 };
+
+        // Renderbuffers are only blitted to the default framebuffer, which
+        // requires the formats to match
+        let format = match internal_format {
+            GL_STENCIL_INDEX8 => STENCIL_FORMAT,
+            _ => self.default_format
+        };
+
+        let storage = (width > 0 && height > 0).then(|| {
+            self.create_texture(
+                "speedy2d_renderbuffer",
+                (width as u32, height as u32),
+                format,
+                1,
+                sample_count
+            )
+        });
+
+        let mut state = self.state.borrow_mut();
+
+        if let Some(handle) = state.bound_renderbuffer {
+            state.renderbuffers.insert(handle, storage);
+        }
+    }
+
+    unsafe fn gl_framebuffer_renderbuffer(
+        &self,
+        _target: GLenum,
+        attachment: GLenum,
+        _renderbuffer_target: GLenum,
+        renderbuffer: GLTypeRenderbuffer
+    )
+    {
+        let mut state = self.state.borrow_mut();
+
+        if let Some(handle) = state.draw_framebuffer {
+            if let Some(framebuffer) = state.framebuffers.get_mut(&handle) {
+                match attachment {
+                    GL_STENCIL_ATTACHMENT => framebuffer.stencil = Some(renderbuffer),
+                    _ => {
+                        framebuffer.color =
+                            Some(ColorAttachment::Renderbuffer(renderbuffer))
+                    }
+                }
+            }
+        }
+    }
+
+    unsafe fn gl_blit_framebuffer(
+        &self,
+        src_x0: GLint,
+        src_y0: GLint,
+        src_x1: GLint,
+        src_y1: GLint,
+        dst_x0: GLint,
+        dst_y0: GLint,
+        dst_x1: GLint,
+        dst_y1: GLint,
+        mask: GLenum,
+        _filter: GLenum
+    )
+    {
+        if mask & GL_COLOR_BUFFER_BIT == 0 {
+            return;
+        }
+
+        self.blit(
+            IntRect::new(src_x0, src_y0, src_x1 - src_x0, src_y1 - src_y0),
+            IntRect::new(dst_x0, dst_y0, dst_x1 - dst_x0, dst_y1 - dst_y0)
+        )
+    }
+
+    unsafe fn gl_get_parameter_i32(&self, parameter: GLenum) -> GLint
+    {
+        match parameter {
+            GL_MAX_SAMPLES => MULTISAMPLE_COUNT as GLint,
+            _ => 0
+        }
+    }
+
+    unsafe fn gl_enable(&self, cap: GLenum)
+    {
+        let mut state = self.state.borrow_mut();
+
+        match cap {
+            GL_BLEND => state.blend.enabled = true,
+            GL_SCISSOR_TEST => state.scissor_enabled = true,
+            GL_STENCIL_TEST => state.stencil.enabled = true,
+            _ => log::warn!("wgpu renderer: ignoring glEnable({cap})")
+        }
+    }
+
+    unsafe fn gl_disable(&self, cap: GLenum)
+    {
+        let mut state = self.state.borrow_mut();
+
+        match cap {
+            GL_BLEND => state.blend.enabled = false,
+            GL_SCISSOR_TEST => state.scissor_enabled = false,
+            GL_STENCIL_TEST => state.stencil.enabled = false,
+            _ => log::warn!("wgpu renderer: ignoring glDisable({cap})")
+        }
+    }
+
+    unsafe fn gl_color_mask(&self, red: bool, green: bool, blue: bool, alpha: bool)
+    {
+        self.state.borrow_mut().color_mask = [red, green, blue, alpha];
+    }
+
+    unsafe fn gl_stencil_func(&self, func: GLenum, reference: GLint, mask: GLuint)
+    {
+        let mut state = self.state.borrow_mut();
+        state.stencil.func = func;
+        state.stencil.reference = reference as u32 & 0xFF;
+        state.stencil.mask = mask & 0xFF;
+    }
+
+    unsafe fn gl_stencil_op(
+        &self,
+        stencil_fail: GLenum,
+        _depth_fail: GLenum,
+        pass: GLenum
+    )
+    {
+        let mut state = self.state.borrow_mut();
+        state.stencil.op_fail = stencil_fail;
+        state.stencil.op_pass = pass;
+    }
+
+    unsafe fn gl_stencil_mask(&self, mask: GLuint)
+    {
+        self.state.borrow_mut().stencil.write_mask = mask & 0xFF;
+    }
+
+    unsafe fn gl_clear_stencil(&self, stencil: GLint)
+    {
+        self.state.borrow_mut().stencil.clear_value = stencil as u32 & 0xFF;
+    }
+
+    unsafe fn gl_blend_func(&self, sfactor: GLenum, dfactor: GLenum)
+    {
+        self.gl_blend_func_separate(sfactor, dfactor, sfactor, dfactor)
+    }
+
+    unsafe fn gl_blend_func_separate(
+        &self,
+        sfactor: GLenum,
+        dfactor: GLenum,
+        sfactor_alpha: GLenum,
+        dfactor_alpha: GLenum
+    )
+    {
+        let mut state = self.state.borrow_mut();
+        state.blend.src_rgb = sfactor;
+        state.blend.dst_rgb = dfactor;
+        state.blend.src_alpha = sfactor_alpha;
+        state.blend.dst_alpha = dfactor_alpha;
+    }
+
+    unsafe fn gl_use_program(&self, _handle: GLTypeProgram) {}
+
+    unsafe fn gl_enable_vertex_attrib_array(&self, _handle: GLuint) {}
+
+    unsafe fn gl_disable_vertex_attrib_array(&self, _handle: GLuint) {}
+
+    // The version string reports OpenGL 2.0, so instanced drawing is never
+    // used with this backend
+    unsafe fn gl_vertex_attrib_divisor(&self, _handle: GLuint, _divisor: GLuint) {}
+
+    unsafe fn gl_uniform_1f(&self, handle: &GLTypeUniformLocation, value: f32)
+    {
+        self.set_uniform(handle, [value, 0.0, 0.0, 0.0])
+    }
+
+    unsafe fn gl_uniform_1i(&self, handle: &GLTypeUniformLocation, value: GLint)
+    {
+        self.set_uniform(handle, [value as f32, 0.0, 0.0, 0.0])
+    }
+
+    unsafe fn gl_uniform_3f(&self, handle: &GLTypeUniformLocation, x: f32, y: f32, z: f32)
+    {
+        self.set_uniform(handle, [x, y, z, 0.0])
+    }
+
+    unsafe fn gl_uniform_4f(
+        &self,
+        handle: &GLTypeUniformLocation,
+        x: f32,
+        y: f32,
+        z: f32,
+        w: f32
+    )
+    {
+        self.set_uniform(handle, [x, y, z, w])
+    }
+
+    unsafe fn gl_attach_shader(&self, _program: GLTypeProgram, _shader: GLTypeShader) {}
+
+    unsafe fn gl_link_program(&self, _program: GLTypeProgram) {}
+
+    unsafe fn gl_shader_source(&self, _handle: GLTypeShader, _source: &str) {}
+
+    unsafe fn gl_compile_shader(&self, _handle: GLTypeShader) {}
+
+    unsafe fn gl_tex_parameter_i(&self, _target: GLenum, parameter: GLenum, value: GLint)
+    {
+        let mut state = self.state.borrow_mut();
+
+        if let Some(texture) = state.bound_texture_mut() {
+            let value = value as GLenum;
+
+            match parameter {
+                GL_TEXTURE_WRAP_S => texture.wrap_s = value,
+                GL_TEXTURE_WRAP_T => texture.wrap_t = value,
+                GL_TEXTURE_MIN_FILTER => texture.min_filter = value,
+                GL_TEXTURE_MAG_FILTER => texture.mag_filter = value,
+                _ => {}
+            }
+        }
+    }
+
+    unsafe fn gl_generate_mipmap(&self, _target: GLenum)
+    {
+        let storage = self
+            .state
+            .borrow_mut()
+            .bound_texture_mut()
+            .and_then(|texture| {
+                texture.has_mipmaps = true;
+                texture.storage.clone()
+            });
+
+        if let Some(storage) = storage {
+            self.generate_mipmaps(&storage);
+        }
+    }
+
+    unsafe fn gl_bind_buffer(&self, target: GLenum, handle: GLTypeBuffer)
+    {
+        self.state.borrow_mut().bound_buffers.insert(target, handle);
+    }
+
+    unsafe fn gl_buffer_data(&self, target: GLenum, data: &[u8], _usage: GLenum)
+    {
+        if let Some(buffer) = self.state.borrow_mut().bound_buffer_mut(target) {
+            buffer.clear();
+            buffer.extend_from_slice(data);
+        }
+    }
+
+    unsafe fn gl_buffer_data_size(&self, target: GLenum, size: GLsizei, _usage: GLenum)
+    {
+        if let Some(buffer) = self.state.borrow_mut().bound_buffer_mut(target) {
+            *buffer = vec![0; size.max(0) as usize];
+        }
+    }
+
+    unsafe fn gl_unbind_buffer(&self, target: GLenum)
+    {
+        self.state.borrow_mut().bound_buffers.remove(&target);
+    }
+
+    unsafe fn gl_get_buffer_sub_data(
+        &self,
+        target: GLenum,
+        offset: GLint,
+        data: &mut [MaybeUninit<u8>]
+    )
+    {
+        if let Some(buffer) = self.state.borrow_mut().bound_buffer_mut(target) {
+            let offset = offset.max(0) as usize;
+
+            for (output, input) in data.iter_mut().zip(buffer.iter().skip(offset)) {
+                output.write(*input);
+            }
+        }
+    }
+
+    unsafe fn gl_map_buffer_range(
+        &self,
+        target: GLenum,
+        offset: GLint,
+        _length: GLsizei,
+        _access: GLenum
+    ) -> *mut u8
+    {
+        match self.state.borrow_mut().bound_buffer_mut(target) {
+            None => std::ptr::null_mut(),
+            Some(buffer) => buffer.as_mut_ptr().add(offset.max(0) as usize)
+        }
+    }
+
+    unsafe fn gl_unmap_buffer(&self, _target: GLenum) {}
+
+    unsafe fn gl_draw_arrays(&self, mode: GLenum, first: GLint, count: GLsizei)
+    {
+        if mode != GL_TRIANGLES {
+            log::warn!("wgpu renderer: ignoring unsupported draw mode {mode}");
+            return;
+        }
+
+        self.draw_triangles(first.max(0) as usize, count.max(0) as usize)
+    }
+
+    unsafe fn gl_draw_arrays_instanced(
+        &self,
+        _mode: GLenum,
+        _first: GLint,
+        _count: GLsizei,
+        _instance_count: GLsizei
+    )
+    {
+        log::warn!("wgpu renderer: ignoring unsupported instanced draw");
+    }
+
+    unsafe fn gl_draw_elements(
+        &self,
+        _mode: GLenum,
+        _count: GLsizei,
+        _element_type: GLenum,
+        _offset: GLint
+    )
+    {
+        log::warn!("wgpu renderer: ignoring unsupported indexed draw");
+    }
+
+    // The version string reports OpenGL 2.0, so vertex arrays are never used
+    // with this backend
+    unsafe fn gl_bind_vertex_array(&self, _handle: Option<GLTypeVertexArray>) {}
+
+    unsafe fn gl_clear_color(&self, r: f32, g: f32, b: f32, a: f32)
+    {
+        self.state.borrow_mut().clear_color = [r, g, b, a];
+    }
+
+    unsafe fn gl_clear(&self, mask: GLenum)
+    {
+        self.clear(mask)
+    }
+
+    unsafe fn gl_enable_debug_message_callback(&self) {}
+
+    unsafe fn gl_get_string(&self, parameter: GLenum) -> String
+    {
+        match parameter {
+            GL_VERSION => "2.0 Speedy2D wgpu renderer".to_string(),
+            _ => String::new()
+        }
+    }
+
+    unsafe fn gl_viewport(&self, x: i32, y: i32, width: i32, height: i32)
+    {
+        let mut state = self.state.borrow_mut();
+
+        state.viewport = IntRect::new(x, y, width, height);
+
+        // The default framebuffer behaves like a window which is resized to
+        // match the viewport. Viewports with an offset, for example when
+        // letterboxing, only cover part of the window.
+        if state.draw_framebuffer.is_some() || x != 0 || y != 0 {
+            return;
+        }
+
+        let size = (width.max(0) as u32, height.max(0) as u32);
+
+        let current_size = state
+            .default_color
+            .as_ref()
+            .map(|texture| (texture.width(), texture.height()));
+
+        if current_size == Some(size) {
+            return;
+        }
+
+        if size.0 == 0 || size.1 == 0 {
+            state.default_color = None;
+            state.default_stencil = None;
+            return;
+        }
+
+        state.default_color = Some(self.create_texture(
+            "speedy2d_default_color",
+            size,
+            self.default_format,
+            1,
+            1
+        ));
+
+        state.default_stencil = Some(self.create_texture(
+            "speedy2d_default_stencil",
+            size,
+            STENCIL_FORMAT,
+            1,
+            1
+        ));
+    }
+
+    unsafe fn gl_scissor(&self, x: GLint, y: GLint, width: GLsizei, height: GLsizei)
+    {
+        self.state.borrow_mut().scissor = IntRect::new(x, y, width, height);
+    }
+
+    unsafe fn gl_pixel_store_i(&self, param: GLenum, value: GLint)
+    {
+        let mut state = self.state.borrow_mut();
+
+        match param {
+            GL_UNPACK_ALIGNMENT => state.unpack_alignment = value.max(1) as usize,
+            GL_PACK_ALIGNMENT => state.pack_alignment = value.max(1) as usize,
+            _ => {}
+        }
+    }
+
+    unsafe fn gl_vertex_attrib_pointer_f32(
+        &self,
+        index: GLuint,
+        size: GLsizei,
+        _data_type: GLenum,
+        _normalized: bool,
+        stride: GLsizei,
+        offset: GLsizei
+    )
+    {
+        let mut state = self.state.borrow_mut();
+
+        let buffer = match state.bound_buffers.get(&GL_ARRAY_BUFFER) {
+            None => return,
+            Some(buffer) => *buffer
+        };
+
+        let components = size.clamp(1, 4) as usize;
+
+        let stride = match stride {
+            0 => components * 4,
+            stride => stride as usize
+        };
+
+        if let Some(attribute) = state.attributes.get_mut(index as usize) {
+            *attribute = Some(AttributePointer {
+                buffer,
+                components,
+                stride,
+                offset: offset.max(0) as usize
+            });
+        }
+    }
+
+    unsafe fn gl_tex_image_2d(
+        &self,
+        _target: GLenum,
+        level: GLint,
+        _internal_format: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        _border: GLint,
+        format: GLenum,
+        _data_type: GLenum,
+        pixels: Option<&[u8]>
+    )
+    {
+        if level != 0 {
+            return;
+        }
+
+        let size = (width.max(0) as u32, height.max(0) as u32);
+
+        // Textures always have a full mipmap chain, as wgpu can't add levels
+        // when glGenerateMipmap() is called later
+        let storage = (size.0 > 0 && size.1 > 0).then(|| {
+            self.create_texture(
+                "speedy2d_texture",
+                size,
+                TEXTURE_FORMAT,
+                u32::BITS - size.0.max(size.1).leading_zeros(),
+                1
+            )
+        });
+
+        if let Some(texture) = self.state.borrow_mut().bound_texture_mut() {
+            texture.storage.clone_from(&storage);
+            texture.has_mipmaps = false;
+        }
+
+        if let (Some(storage), Some(pixels)) = (storage, pixels) {
+            self.write_texture(
+                &storage,
+                0,
+                IntRect::new(0, 0, width, height),
+                format,
+                pixels
+            );
+        }
+    }
+
+    unsafe fn gl_tex_sub_image_2d(
+        &self,
+        _target: GLenum,
+        level: GLint,
+        x: GLint,
+        y: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        format: GLenum,
+        _data_type: GLenum,
+        pixels: &[u8]
+    )
+    {
+        let storage = self
+            .state
+            .borrow_mut()
+            .bound_texture_mut()
+            .and_then(|texture| texture.storage.clone());
+
+        let level = level.max(0) as u32;
+
+        if let Some(storage) = storage.filter(|storage| level < storage.mip_level_count())
+        {
+            self.write_texture(
+                &storage,
+                level,
+                IntRect::new(x.max(0), y.max(0), width, height),
+                format,
+                pixels
+            );
+        }
+    }
+
+    unsafe fn gl_create_program(
+        &self
+    ) -> Result<GLTypeProgram, BacktraceError<ErrorMessage>>
+    {
+        Ok(self.state.borrow_mut().gen_handle())
+    }
+
+    unsafe fn gl_create_shader(
+        &self,
+        _shader_type: GLenum
+    ) -> Result<GLTypeShader, BacktraceError<ErrorMessage>>
+    {
+        Ok(self.state.borrow_mut().gen_handle())
+    }
+
+    unsafe fn gl_gen_buffer(&self) -> Result<GLTypeBuffer, BacktraceError<ErrorMessage>>
+    {
+        let mut state = self.state.borrow_mut();
+        let handle = state.gen_handle();
+        state.buffers.insert(handle, Vec::new());
+        Ok(handle)
+    }
+
+    unsafe fn gl_gen_texture(&self)
+        -> Result<GLTypeTexture, BacktraceError<ErrorMessage>>
+    {
+        let mut state = self.state.borrow_mut();
+        let handle = state.gen_handle();
+        state.textures.insert(handle, Texture::new());
+        Ok(handle)
+    }
+
+    unsafe fn gl_gen_framebuffer(
+        &self
+    ) -> Result<GLTypeFramebuffer, BacktraceError<ErrorMessage>>
+    {
+        let mut state = self.state.borrow_mut();
+        let handle = state.gen_handle();
+        state.framebuffers.insert(handle, Framebuffer::default());
+        Ok(handle)
+    }
+
+    unsafe fn gl_gen_renderbuffer(
+        &self
+    ) -> Result<GLTypeRenderbuffer, BacktraceError<ErrorMessage>>
+    {
+        let mut state = self.state.borrow_mut();
+        let handle = state.gen_handle();
+        state.renderbuffers.insert(handle, None);
+        Ok(handle)
+    }
+
+    unsafe fn gl_gen_vertex_array(
+        &self
+    ) -> Result<GLTypeVertexArray, BacktraceError<ErrorMessage>>
+    {
+        Err(ErrorMessage::msg(
+            "wgpu renderer: vertex arrays are not supported"
+        ))
+    }
+
+    unsafe fn gl_get_error(&self) -> GLenum
+    {
+        GL_NO_ERROR
+    }
+
+    unsafe fn gl_get_attrib_location(
+        &self,
+        _program: GLTypeProgram,
+        name: &str
+    ) -> Option<GLuint>
+    {
+        ATTRIBUTE_NAMES
+            .iter()
+            .position(|attribute| *attribute == name)
+            .map(|index| index as GLuint)
+    }
+
+    unsafe fn gl_get_uniform_location(
+        &self,
+        _program: GLTypeProgram,
+        name: &str
+    ) -> Option<GLTypeUniformLocation>
+    {
+        UNIFORM_NAMES
+            .iter()
+            .position(|uniform| *uniform == name)
+            .map(|index| index as GLTypeUniformLocation)
+    }
+
+    unsafe fn gl_get_program_link_status(&self, _program: GLTypeProgram) -> bool
+    {
+        true
+    }
+
+    unsafe fn gl_get_shader_compile_status(&self, _shader: GLTypeShader) -> bool
+    {
+        true
+    }
+
+    unsafe fn gl_get_program_info_log(
+        &self,
+        _program: GLTypeProgram
+    ) -> Result<String, BacktraceError<ErrorMessage>>
+    {
+        Ok(String::new())
+    }
+
+    unsafe fn gl_get_shader_info_log(
+        &self,
+        _shader: GLTypeShader
+    ) -> Result<String, BacktraceError<ErrorMessage>>
+    {
+        Ok(String::new())
+    }
+
+    unsafe fn gl_read_pixels(
+        &self,
+        x: GLint,
+        y: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        format: GLenum,
+        _data_type: GLenum,
+        data: &mut [MaybeUninit<u8>]
+    )
+    {
+        let pixels = self.read_pixels(IntRect::new(x, y, width, height), format);
+
+        for (output, input) in data.iter_mut().zip(pixels) {
+            output.write(input);
+        }
+    }
+
+    unsafe fn gl_read_pixels_to_buffer(
+        &self,
+        x: GLint,
+        y: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        format: GLenum,
+        _data_type: GLenum,
+        offset: GLuint
+    )
+    {
+        let pixels = self.read_pixels(IntRect::new(x, y, width, height), format);
+
+        if let Some(buffer) = self
+            .state
+            .borrow_mut()
+            .bound_buffer_mut(GL_PIXEL_PACK_BUFFER)
+        {
+            let offset = offset as usize;
+
+            if buffer.len() < offset + pixels.len() {
+                buffer.resize(offset + pixels.len(), 0);
+            }
+
+            buffer[offset..offset + pixels.len()].copy_from_slice(&pixels);
+        }
+    }
+}
+
+#[inline]
+fn align(value: usize, alignment: usize) -> usize
+{
+    value.div_ceil(alignment.max(1)) * alignment.max(1)
+}
+
+/// Converts pixel data in the specified GL format into tightly packed RGBA.
+fn to_rgba(
+    (width, height): (usize, usize),
+    format: GLenum,
+    unpack_alignment: usize,
+    pixels: &[u8]
+) -> Vec<u8>
+{
+    let bytes_per_pixel = match format {
+        GL_RED => 1,
+        GL_RGB => 3,
+        _ => 4
+    };
+
+    let row_stride = align(width * bytes_per_pixel, unpack_alignment);
+
+    let mut output = vec![0; width * height * 4];
+
+    for row in 0..height {
+        for column in 0..width {
+            let offset = row * row_stride + column * bytes_per_pixel;
+
+            let input = match pixels.get(offset..offset + bytes_per_pixel) {
+                None => continue,
+                Some(input) => input
+            };
+
+            let pixel = match bytes_per_pixel {
+                1 => [input[0], 0, 0, 255],
+                3 => [input[0], input[1], input[2], 255],
+                _ => [input[0], input[1], input[2], input[3]]
+            };
+
+            let output_offset = (row * width + column) * 4;
+            output[output_offset..output_offset + 4].copy_from_slice(&pixel);
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod test
+{
+    use std::convert::TryInto;
+
+    use crate::color::Color;
+    use crate::dimen::Vec2;
+    use crate::image::{ImageDataType, ImageSmoothingMode, RawBitmapData};
+    use crate::shape::{ClipShape, Rectangle};
+    use crate::{Antialiasing, GLRendererOptions, WGPURenderer};
+
+    /// Creates a renderer using the default wgpu adapter, or returns `None` if
+    /// no adapter is available.
+    fn create_renderer(
+        size: (u32, u32),
+        format: wgpu::TextureFormat,
+        options: GLRendererOptions
+    ) -> Option<WGPURenderer>
+    {
+        let instance = wgpu::Instance::default();
+
+        let adapter = match pollster::block_on(
+            instance.request_adapter(&wgpu::RequestAdapterOptions::default())
+        ) {
+            None => {
+                log::warn!("Skipping wgpu test: no adapter available");
+                return None;
+            }
+            Some(adapter) => adapter
+        };
+
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                required_limits: adapter.limits(),
+                ..Default::default()
+            },
+            None
+        ))
+        .unwrap();
+
+        Some(
+            WGPURenderer::new_with_options(&device, &queue, format, size, options)
+                .unwrap()
+        )
+    }
+
+    fn pixel(capture: &RawBitmapData, x: u32, y: u32) -> [u8; 4]
+    {
+        let offset = ((y * capture.size().x + x) * 4) as usize;
+        capture.data()[offset..offset + 4].try_into().unwrap()
+    }
+
+    fn draw_quadrants(renderer: &mut WGPURenderer) -> RawBitmapData
+    {
+        renderer.draw_frame(|graphics| {
+            graphics.clear_screen(Color::WHITE);
+            graphics.draw_rectangle(
+                Rectangle::from_tuples((0.0, 0.0), (4.0, 4.0)),
+                Color::RED
+            );
+            graphics.draw_rectangle(
+                Rectangle::from_tuples((4.0, 4.0), (8.0, 8.0)),
+                Color::BLUE
+            );
+            graphics.capture(ImageDataType::RGBA)
+        })
+    }
+
+    #[test]
+    fn test_draw_and_capture()
+    {
+        for format in [
+            wgpu::TextureFormat::Rgba8Unorm,
+            wgpu::TextureFormat::Bgra8Unorm
+        ] {
+            let mut renderer = match create_renderer((8, 8), format, Default::default()) {
+                None => return,
+                Some(renderer) => renderer
+            };
+
+            let capture = draw_quadrants(&mut renderer);
+
+            assert_eq!(pixel(&capture, 1, 1), [255, 0, 0, 255]);
+            assert_eq!(pixel(&capture, 6, 6), [0, 0, 255, 255]);
+            assert_eq!(pixel(&capture, 6, 1), [255, 255, 255, 255]);
+            assert_eq!(pixel(&capture, 1, 6), [255, 255, 255, 255]);
+        }
+    }
+
+    #[test]
+    fn test_multisampling()
+    {
+        let options = GLRendererOptions::new().with_antialiasing(Antialiasing::Msaa4);
+
+        let mut renderer =
+            match create_renderer((8, 8), wgpu::TextureFormat::Rgba8Unorm, options) {
+                None => return,
+                Some(renderer) => renderer
+            };
+
+        let capture = draw_quadrants(&mut renderer);
+
+        assert_eq!(pixel(&capture, 1, 1), [255, 0, 0, 255]);
+        assert_eq!(pixel(&capture, 6, 6), [0, 0, 255, 255]);
+        assert_eq!(pixel(&capture, 6, 1), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn test_render_target_and_mipmaps()
+    {
+        let mut renderer = match create_renderer(
+            (8, 8),
+            wgpu::TextureFormat::Rgba8Unorm,
+            Default::default()
+        ) {
+            None => return,
+            Some(renderer) => renderer
+        };
+
+        let target = renderer
+            .create_render_target((16, 16), ImageSmoothingMode::Linear)
+            .unwrap();
+
+        let image = renderer
+            .create_image_from_raw_pixels(
+                ImageDataType::RGBA,
+                ImageSmoothingMode::Trilinear,
+                (16, 16).into(),
+                &[0, 255, 0, 255].repeat(16 * 16)
+            )
+            .unwrap();
+
+        let capture = renderer.draw_frame(|graphics| {
+            graphics.draw_to_target(&target, |graphics| {
+                graphics.clear_screen(Color::WHITE);
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((0.0, 0.0), (8.0, 8.0)),
+                    Color::RED
+                );
+            });
+
+            graphics.clear_screen(Color::BLACK);
+            graphics.draw_rectangle_image(
+                Rectangle::from_tuples((0.0, 0.0), (4.0, 4.0)),
+                target.image()
+            );
+
+            // Drawn at a quarter of its size, so sampled from the mipmaps
+            graphics.draw_rectangle_image(
+                Rectangle::from_tuples((4.0, 4.0), (8.0, 8.0)),
+                &image
+            );
+
+            graphics.capture(ImageDataType::RGBA)
+        });
+
+        // The render target is drawn the right way up
+        assert_eq!(pixel(&capture, 0, 0), [255, 0, 0, 255]);
+        assert_eq!(pixel(&capture, 3, 3), [255, 255, 255, 255]);
+        assert_eq!(pixel(&capture, 6, 6), [0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn test_clipping()
+    {
+        let mut renderer = match create_renderer(
+            (8, 8),
+            wgpu::TextureFormat::Rgba8Unorm,
+            Default::default()
+        ) {
+            None => return,
+            Some(renderer) => renderer
+        };
+
+        let capture = renderer.draw_frame(|graphics| {
+            graphics.clear_screen(Color::WHITE);
+
+            graphics.set_clip(Some(Rectangle::from_tuples((0, 0), (4, 8))));
+            graphics.draw_rectangle(
+                Rectangle::from_tuples((0.0, 0.0), (8.0, 8.0)),
+                Color::RED
+            );
+
+            graphics.set_clip(None);
+            graphics.set_clip_shape(Some(ClipShape::Circle {
+                center: Vec2::new(6.0, 6.0),
+                radius: 1.5
+            }));
+            graphics.draw_rectangle(
+                Rectangle::from_tuples((4.0, 0.0), (8.0, 8.0)),
+                Color::BLUE
+            );
+
+            graphics.capture(ImageDataType::RGBA)
+        });
+
+        assert_eq!(pixel(&capture, 1, 1), [255, 0, 0, 255]);
+        assert_eq!(pixel(&capture, 6, 1), [255, 255, 255, 255]);
+        assert_eq!(pixel(&capture, 6, 6), [0, 0, 255, 255]);
+    }
+}
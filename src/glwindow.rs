@@ -0,0 +1,193 @@
+/*
+ *  Copyright 2021 QuantumBadger
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+use std::ffi::CString;
+use std::num::NonZeroU32;
+
+use glutin::config::{Config, ConfigTemplateBuilder};
+use glutin::context::{
+    ContextApi,
+    ContextAttributesBuilder,
+    NotCurrentContext,
+    NotCurrentGlContext,
+    PossiblyCurrentContext,
+    Version
+};
+use glutin::display::{Display, DisplayApiPreference, GlDisplay};
+use glutin::surface::{GlSurface, Surface, SurfaceAttributesBuilder, WindowSurface};
+use raw_window_handle::{RawDisplayHandle, RawWindowHandle};
+
+use crate::dimen::UVec2;
+use crate::error::{BacktraceError, ErrorMessage};
+use crate::glbackend::GLBackendGlow;
+
+/// Creates a context for the specified config, falling back to OpenGL ES on
+/// platforms without desktop OpenGL, such as the Raspberry Pi or ANGLE.
+///
+/// If `shared_context` is specified, the new context shares resources with it.
+pub(crate) fn create_context_with_fallback(
+    display: &Display,
+    config: &Config,
+    window: Option<RawWindowHandle>,
+    shared_context: Option<&PossiblyCurrentContext>
+) -> Option<NotCurrentContext>
+{
+    [
+        ContextApi::OpenGl(Some(Version::new(2, 0))),
+        ContextApi::Gles(Some(Version::new(3, 0))),
+        ContextApi::Gles(Some(Version::new(2, 0)))
+    ]
+    .iter()
+    .find_map(|api| {
+        let mut context_attributes =
+            ContextAttributesBuilder::new().with_context_api(*api);
+
+        if let Some(shared_context) = shared_context {
+            context_attributes = context_attributes.with_sharing(shared_context);
+        }
+
+        let context_attributes = context_attributes.build(window);
+
+        match unsafe { display.create_context(config, &context_attributes) } {
+            Ok(context) => Some(context),
+            Err(err) => {
+                log::info!("Failed to create {api:?} context with error: {err:?}");
+                None
+            }
+        }
+    })
+}
+
+/// A GL context and surface for a window which was created outside of
+/// Speedy2D.
+pub(crate) struct GLWindowContext
+{
+    surface: Surface<WindowSurface>,
+    context: PossiblyCurrentContext
+}
+
+impl GLWindowContext
+{
+    /// Creates a context for the specified window and makes it current,
+    /// returning the context along with a backend for drawing to it.
+    ///
+    /// The window must outlive the returned context.
+    pub(crate) unsafe fn new(
+        display_handle: RawDisplayHandle,
+        window_handle: RawWindowHandle,
+        size: UVec2
+    ) -> Result<(Self, GLBackendGlow), BacktraceError<ErrorMessage>>
+    {
+        let display = Display::new(display_handle, display_api_preference(window_handle))
+            .map_err(|err| {
+                ErrorMessage::msg(format!("Failed to create GL display: {err}"))
+            })?;
+
+        let template = ConfigTemplateBuilder::new()
+            .with_stencil_size(8)
+            .compatible_with_native_window(window_handle)
+            .build();
+
+        let config = display
+            .find_configs(template)
+            .map_err(|err| {
+                ErrorMessage::msg(format!("Failed to find GL configs: {err}"))
+            })?
+            .next()
+            .ok_or_else(|| ErrorMessage::msg("No suitable GL config found"))?;
+
+        let context =
+            create_context_with_fallback(&display, &config, Some(window_handle), None)
+                .ok_or_else(|| ErrorMessage::msg("Failed to create GL context"))?;
+
+        let (width, height) = match (NonZeroU32::new(size.x), NonZeroU32::new(size.y)) {
+            (Some(width), Some(height)) => (width, height),
+            _ => return Err(ErrorMessage::msg("Window size must be non-zero"))
+        };
+
+        let surface = display
+            .create_window_surface(
+                &config,
+                &SurfaceAttributesBuilder::<WindowSurface>::new().build(
+                    window_handle,
+                    width,
+                    height
+                )
+            )
+            .map_err(|err| {
+                ErrorMessage::msg(format!("Failed to create window surface: {err}"))
+            })?;
+
+        let context = context.make_current(&surface).map_err(|err| {
+            ErrorMessage::msg(format!("Failed to make context current: {err}"))
+        })?;
+
+        let backend = GLBackendGlow::new(glow::Context::from_loader_function(|name| {
+            display.get_proc_address(
+                CString::new(name)
+                    .expect("Invalid GL function name string")
+                    .as_c_str()
+            ) as *const _
+        }));
+
+        Ok((GLWindowContext { surface, context }, backend))
+    }
+
+    pub(crate) fn resize(&self, size: UVec2)
+    {
+        if let (Some(width), Some(height)) =
+            (NonZeroU32::new(size.x), NonZeroU32::new(size.y))
+        {
+            self.surface.resize(&self.context, width, height);
+        }
+    }
+
+    pub(crate) fn swap_buffers(&self)
+    {
+        if let Err(err) = self.surface.swap_buffers(&self.context) {
+            log::error!("Failed to swap buffers: {err:?}");
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn display_api_preference(window_handle: RawWindowHandle) -> DisplayApiPreference
+{
+    DisplayApiPreference::WglThenEgl(Some(window_handle))
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+fn display_api_preference(_window_handle: RawWindowHandle) -> DisplayApiPreference
+{
+    DisplayApiPreference::Cgl
+}
+
+#[cfg(target_os = "android")]
+fn display_api_preference(_window_handle: RawWindowHandle) -> DisplayApiPreference
+{
+    DisplayApiPreference::Egl
+}
+
+#[cfg(all(
+    unix,
+    not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+))]
+fn display_api_preference(_window_handle: RawWindowHandle) -> DisplayApiPreference
+{
+    DisplayApiPreference::EglThenGlx(Box::new(
+        winit::platform::x11::register_xlib_error_hook
+    ))
+}
@@ -24,30 +24,116 @@ use std::ptr;
 use std::rc::{Rc, Weak};
 
 use crate::color::Color;
-use crate::dimen::UVec2;
+use crate::dimen::{IVec2, UVec2};
 use crate::error::{BacktraceError, Context, ErrorMessage};
 use crate::glbackend::constants::*;
 use crate::glbackend::types::{
     GLTypeBuffer,
+    GLTypeFramebuffer,
     GLTypeProgram,
+    GLTypeRenderbuffer,
     GLTypeShader,
     GLTypeTexture,
     GLTypeUniformLocation,
+    GLTypeVertexArray,
     GLenum,
     GLint,
+    GLsizei,
     GLuint
 };
 use crate::glbackend::GLBackend;
-use crate::{ImageDataType, RawBitmapData};
+use crate::shape::{IRect, URect};
+use crate::{BlendMode, ImageDataType, RawBitmapData};
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 #[allow(dead_code)]
 pub enum GLVersion
 {
     OpenGL2_0,
+    OpenGLES2_0,
+    OpenGLES3_0,
+    WebGL1_0,
     WebGL2_0
 }
 
+impl GLVersion
+{
+    /// Determines the version of a native GL context from its version string,
+    /// treating any desktop OpenGL context as OpenGL 2.0.
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub fn detect(backend: &dyn GLBackend) -> Self
+    {
+        let version = unsafe { backend.gl_get_string(GL_VERSION) };
+        Self::from_version_string(&version)
+    }
+
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    fn from_version_string(version: &str) -> Self
+    {
+        // OpenGL ES version strings have the form "OpenGL ES N.M <vendor>"
+        let es_version = match version.trim().strip_prefix("OpenGL ES") {
+            None => return GLVersion::OpenGL2_0,
+            Some(es_version) => es_version.trim_start()
+        };
+
+        let major_version: Option<u32> = es_version
+            .split('.')
+            .next()
+            .and_then(|major| major.parse().ok());
+
+        match major_version {
+            Some(major) if major >= 3 => GLVersion::OpenGLES3_0,
+            _ => GLVersion::OpenGLES2_0
+        }
+    }
+
+    /// Returns true for OpenGL ES and WebGL contexts, which only support
+    /// reading pixels in RGBA format, and can't read buffers using
+    /// `glGetBufferSubData`.
+    fn is_gles(&self) -> bool
+    {
+        *self != GLVersion::OpenGL2_0
+    }
+
+    /// OpenGL ES 2.0 and WebGL 1.0 lack multisampled renderbuffers, pixel
+    /// buffer objects, and sized texture formats.
+    fn is_gles2(&self) -> bool
+    {
+        matches!(self, GLVersion::OpenGLES2_0 | GLVersion::WebGL1_0)
+    }
+
+    /// Returns true if the context supports vertex array objects, 32-bit
+    /// element indices, and instanced drawing, which are all part of OpenGL
+    /// 3.3, OpenGL ES 3.0, and WebGL 2.0.
+    fn has_gl3_features(&self, backend: &dyn GLBackend) -> bool
+    {
+        match self {
+            GLVersion::OpenGLES3_0 | GLVersion::WebGL2_0 => true,
+            GLVersion::OpenGLES2_0 | GLVersion::WebGL1_0 => false,
+            GLVersion::OpenGL2_0 => {
+                let version = unsafe { backend.gl_get_string(GL_VERSION) };
+                Self::desktop_version_has_gl3_features(&version)
+            }
+        }
+    }
+
+    /// Desktop OpenGL version strings have the form "N.M[.R] <vendor>".
+    fn desktop_version_has_gl3_features(version: &str) -> bool
+    {
+        let mut numbers = version
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|number| number.parse::<u32>().ok());
+
+        match (numbers.next().flatten(), numbers.next().flatten()) {
+            (Some(major), Some(minor)) => (major, minor) >= (3, 3),
+            _ => false
+        }
+    }
+}
+
 impl From<TryFromIntError> for BacktraceError<ErrorMessage>
 {
     fn from(_: TryFromIntError) -> Self
@@ -79,7 +165,10 @@ enum GLHandleType
     Program,
     Shader,
     Buffer,
-    Texture
+    Texture,
+    Framebuffer,
+    Renderbuffer,
+    VertexArray
 }
 
 trait GLHandleId: Debug + Hash + PartialEq + Eq
@@ -112,6 +201,24 @@ struct GLHandleTypeTexture
     handle: GLTypeTexture
 }
 
+#[derive(Debug, Hash, PartialEq, Eq)]
+struct GLHandleTypeFramebuffer
+{
+    handle: GLTypeFramebuffer
+}
+
+#[derive(Debug, Hash, PartialEq, Eq)]
+struct GLHandleTypeRenderbuffer
+{
+    handle: GLTypeRenderbuffer
+}
+
+#[derive(Debug, Hash, PartialEq, Eq)]
+struct GLHandleTypeVertexArray
+{
+    handle: GLTypeVertexArray
+}
+
 struct GLHandle<HandleType: GLHandleId>
 {
     context: Weak<RefCell<GLContextManagerState>>,
@@ -177,6 +284,9 @@ impl<HandleType: GLHandleId> GLHandle<HandleType>
             GLHandleType::Shader => gl_clear_and_log_old_error(context),
             GLHandleType::Buffer => {}
             GLHandleType::Texture => {}
+            GLHandleType::Framebuffer => {}
+            GLHandleType::Renderbuffer => {}
+            GLHandleType::VertexArray => {}
         }
 
         let handle = handle_creator().context("Handle creation failed")?;
@@ -186,6 +296,9 @@ impl<HandleType: GLHandleId> GLHandle<HandleType>
             GLHandleType::Shader => gl_check_error_always(context)?,
             GLHandleType::Buffer => {}
             GLHandleType::Texture => {}
+            GLHandleType::Framebuffer => {}
+            GLHandleType::Renderbuffer => {}
+            GLHandleType::VertexArray => {}
         }
 
         Ok(GLHandle {
@@ -257,6 +370,42 @@ impl GLHandleId for GLHandleTypeTexture
     }
 }
 
+impl GLHandleId for GLHandleTypeFramebuffer
+{
+    type HandleRawType = GLTypeFramebuffer;
+
+    fn delete(&self, context: &GLContextManager)
+    {
+        context.with_gl_backend(|backend| unsafe {
+            backend.gl_delete_framebuffer(self.handle)
+        });
+    }
+}
+
+impl GLHandleId for GLHandleTypeRenderbuffer
+{
+    type HandleRawType = GLTypeRenderbuffer;
+
+    fn delete(&self, context: &GLContextManager)
+    {
+        context.with_gl_backend(|backend| unsafe {
+            backend.gl_delete_renderbuffer(self.handle)
+        });
+    }
+}
+
+impl GLHandleId for GLHandleTypeVertexArray
+{
+    type HandleRawType = GLTypeVertexArray;
+
+    fn delete(&self, context: &GLContextManager)
+    {
+        context.with_gl_backend(|backend| unsafe {
+            backend.gl_delete_vertex_array(self.handle)
+        });
+    }
+}
+
 #[derive(Debug)]
 pub struct GLProgram
 {
@@ -366,11 +515,18 @@ impl GLProgram
 
     fn enable(&self, context: &GLContextManager)
     {
+        // With vertex arrays, each vertex array enables its own attributes
+        let has_gl3_features = context.has_gl3_features();
+
         context.with_gl_backend(|backend| {
             unsafe {
                 backend.gl_use_program(self.get_handle());
             }
 
+            if has_gl3_features {
+                return;
+            }
+
             for attribute in self.attribute_handles.values() {
                 unsafe {
                     backend.gl_enable_vertex_attrib_array(attribute.handle);
@@ -381,6 +537,10 @@ impl GLProgram
 
     fn disable(&self, context: &GLContextManager)
     {
+        if context.has_gl3_features() {
+            return;
+        }
+
         context.with_gl_backend(|backend| {
             for attribute in self.attribute_handles.values() {
                 unsafe {
@@ -543,6 +703,27 @@ impl GLUniformHandle
             backend.gl_uniform_1i(&self.handle, value)
         })
     }
+
+    pub fn set_value_vec3(&self, context: &GLContextManager, x: f32, y: f32, z: f32)
+    {
+        context.with_gl_backend(|backend| unsafe {
+            backend.gl_uniform_3f(&self.handle, x, y, z)
+        })
+    }
+
+    pub fn set_value_vec4(
+        &self,
+        context: &GLContextManager,
+        x: f32,
+        y: f32,
+        z: f32,
+        w: f32
+    )
+    {
+        context.with_gl_backend(|backend| unsafe {
+            backend.gl_uniform_4f(&self.handle, x, y, z, w)
+        })
+    }
 }
 
 pub enum GLBufferTarget
@@ -628,13 +809,159 @@ impl GLBuffer
             )
         });
     }
+
+    /// Binds the data previously uploaded using [GLBuffer::set_data] to the
+    /// buffer's attribute, without uploading it again.
+    pub fn bind(&self, context: &GLContextManager)
+    {
+        if !context.is_valid() {
+            log::warn!("Ignoring buffer bind: invalid GL context");
+            return;
+        }
+
+        context.with_gl_backend(|backend| unsafe {
+            backend.gl_bind_buffer(self.target.gl_constant(), self.get_handle());
+
+            backend.gl_vertex_attrib_pointer_f32(
+                self.attrib_index.handle,
+                self.components_per_vertex,
+                GL_FLOAT,
+                false,
+                0,
+                0
+            )
+        });
+    }
+
+    /// Enables the buffer's attribute in the vertex array which is currently
+    /// bound. A `divisor` of 1 advances the attribute once per instance,
+    /// rather than once per vertex.
+    fn add_to_vertex_array(&self, context: &GLContextManager, divisor: GLuint)
+    {
+        context.with_gl_backend(|backend| unsafe {
+            backend.gl_enable_vertex_attrib_array(self.attrib_index.handle);
+            backend.gl_vertex_attrib_divisor(self.attrib_index.handle, divisor);
+        });
+
+        self.bind(context);
+    }
+}
+
+/// A buffer of 32-bit vertex indices, for use with
+/// [GLContextManager::draw_triangles_indexed].
+pub struct GLIndexBuffer
+{
+    handle: GLHandle<GLHandleTypeBuffer>
+}
+
+impl GLHandleOwner<GLHandleTypeBuffer> for GLIndexBuffer
+{
+    fn get_handle(&self) -> <GLHandleTypeBuffer as GLHandleId>::HandleRawType
+    {
+        self.handle.handle.handle
+    }
+}
+
+impl GLIndexBuffer
+{
+    fn new(context: &GLContextManager) -> Result<Self, BacktraceError<ErrorMessage>>
+    {
+        gl_clear_and_log_old_error(context);
+
+        let handle = GLHandle::wrap(context, GLHandleType::Buffer, || {
+            context.with_gl_backend(|backend| unsafe {
+                Ok(GLHandleTypeBuffer {
+                    handle: backend.gl_gen_buffer()?
+                })
+            })
+        })?;
+
+        Ok(GLIndexBuffer { handle })
+    }
+
+    /// Uploads the indices. The buffer is also bound to the vertex array
+    /// which is currently bound.
+    pub fn set_data(&mut self, context: &GLContextManager, data: &[u32])
+    {
+        if !context.is_valid() {
+            log::warn!("Ignoring index buffer set_data: invalid GL context");
+            return;
+        }
+
+        context.with_gl_backend(|backend| unsafe {
+            backend.gl_bind_buffer(GL_ELEMENT_ARRAY_BUFFER, self.get_handle());
+            backend.gl_buffer_data_u32(GL_ELEMENT_ARRAY_BUFFER, data, GL_DYNAMIC_DRAW);
+        });
+    }
+}
+
+/// A vertex array object, which records the layout of a set of attribute
+/// buffers so that they can all be bound at once. This requires OpenGL 3.3,
+/// OpenGL ES 3.0, or WebGL 2.0.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct GLVertexArray
+{
+    handle: Rc<GLHandle<GLHandleTypeVertexArray>>
+}
+
+impl GLHandleOwner<GLHandleTypeVertexArray> for GLVertexArray
+{
+    fn get_handle(&self) -> <GLHandleTypeVertexArray as GLHandleId>::HandleRawType
+    {
+        self.handle.handle.handle
+    }
+}
+
+impl GLVertexArray
+{
+    fn new(
+        context: &GLContextManager,
+        vertex_buffers: &[&GLBuffer],
+        instance_buffers: &[&GLBuffer]
+    ) -> Result<Self, BacktraceError<ErrorMessage>>
+    {
+        let handle = GLHandle::wrap(context, GLHandleType::VertexArray, || {
+            context.with_gl_backend(|backend| unsafe {
+                Ok(GLHandleTypeVertexArray {
+                    handle: backend.gl_gen_vertex_array()?
+                })
+            })
+        })?;
+
+        let vertex_array = GLVertexArray {
+            handle: Rc::new(handle)
+        };
+
+        context.bind_vertex_array(&vertex_array);
+
+        for buffer in vertex_buffers {
+            buffer.add_to_vertex_array(context, 0);
+        }
+
+        for buffer in instance_buffers {
+            buffer.add_to_vertex_array(context, 1);
+        }
+
+        gl_check_error_always(context).context("Failed to set up vertex array")?;
+
+        Ok(vertex_array)
+    }
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub enum GLTextureWrap
+{
+    ClampToEdge,
+    Repeat,
+    MirroredRepeat
 }
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
 pub enum GLTextureSmoothing
 {
     NearestNeighbour,
-    Linear
+    Linear,
+    Trilinear
 }
 
 #[allow(clippy::upper_case_acronyms)]
@@ -660,8 +987,13 @@ impl From<ImageDataType> for GLTextureImageFormatU8
 
 impl GLTextureImageFormatU8
 {
-    fn get_internal_format(&self) -> GLenum
+    fn get_internal_format(&self, version: GLVersion) -> GLenum
     {
+        // OpenGL ES 2.0 requires the internal format to match the format
+        if version.is_gles2() {
+            return self.get_format();
+        }
+
         match self {
             GLTextureImageFormatU8::Red => GL_R8,
             GLTextureImageFormatU8::RGB => GL_RGB8,
@@ -733,15 +1065,19 @@ impl GLTexture
             return Ok(());
         }
 
-        let smoothing_constant = match smoothing {
-            GLTextureSmoothing::NearestNeighbour => GL_NEAREST,
-            GLTextureSmoothing::Linear => GL_LINEAR
-        } as GLint;
+        let (min_filter, mag_filter) = match smoothing {
+            GLTextureSmoothing::NearestNeighbour => (GL_NEAREST, GL_NEAREST),
+            GLTextureSmoothing::Linear => (GL_LINEAR, GL_LINEAR),
+            GLTextureSmoothing::Trilinear => (GL_LINEAR_MIPMAP_LINEAR, GL_LINEAR)
+        };
 
         context.bind_texture(self);
 
+        let version = context.version();
         let width_stride_bytes = size.x as usize * format.get_bytes_per_pixel();
 
+        // `is_multiple_of()` would need Rust 1.87
+        #[allow(unknown_lints, clippy::manual_is_multiple_of)]
         let unpack_alignment = if width_stride_bytes % 8 == 0 {
             8
         } else if width_stride_bytes % 4 == 0 {
@@ -768,19 +1104,19 @@ impl GLTexture
                 backend.gl_tex_parameter_i(
                     GL_TEXTURE_2D,
                     GL_TEXTURE_MIN_FILTER,
-                    smoothing_constant
+                    min_filter as GLint
                 );
                 backend.gl_tex_parameter_i(
                     GL_TEXTURE_2D,
                     GL_TEXTURE_MAG_FILTER,
-                    smoothing_constant
+                    mag_filter as GLint
                 );
 
                 backend.gl_tex_image_2d(
                     GL_TEXTURE_2D,
                     0,
                     format
-                        .get_internal_format()
+                        .get_internal_format(version)
                         .try_into()
                         .context("Failed to cast internal format")?,
                     size.x.try_into()?,
@@ -791,72 +1127,328 @@ impl GLTexture
                     Some(data)
                 );
 
+                if smoothing == GLTextureSmoothing::Trilinear {
+                    backend.gl_generate_mipmap(GL_TEXTURE_2D);
+                }
+
                 Ok(())
             }
         )
     }
-}
 
-#[must_use]
-fn obtain_context_if_valid(
-    state: &RefCell<GLContextManagerState>
-) -> Option<GLContextManager>
-{
-    let state = state.borrow_mut();
+    pub fn set_wrap(&self, context: &GLContextManager, wrap: GLTextureWrap)
+    {
+        if !context.is_valid() {
+            log::warn!("Ignoring texture set_wrap: invalid GL context");
+            return;
+        }
 
-    if state.is_valid {
-        Some(GLContextManager {
-            state: state.weak_ref_to_self.upgrade().unwrap()
-        })
-    } else {
-        None
+        let wrap_constant = match wrap {
+            GLTextureWrap::ClampToEdge => GL_CLAMP_TO_EDGE,
+            GLTextureWrap::Repeat => GL_REPEAT,
+            GLTextureWrap::MirroredRepeat => GL_MIRRORED_REPEAT
+        } as GLint;
+
+        context.bind_texture(self);
+
+        context.with_gl_backend(|backend| unsafe {
+            backend.gl_tex_parameter_i(GL_TEXTURE_2D, GL_TEXTURE_WRAP_S, wrap_constant);
+            backend.gl_tex_parameter_i(GL_TEXTURE_2D, GL_TEXTURE_WRAP_T, wrap_constant);
+        });
     }
-}
 
-#[inline]
-#[must_use]
-fn obtain_context_from_weak_if_valid(
-    state: &Weak<RefCell<GLContextManagerState>>
-) -> Option<GLContextManager>
-{
-    match state.upgrade() {
-        None => None,
-        Some(state) => obtain_context_if_valid(&state)
+    /// Regenerates the smaller versions of the texture used for trilinear
+    /// filtering, after the full-size texture has been drawn to.
+    pub fn generate_mipmaps(&self, context: &GLContextManager)
+    {
+        if !context.is_valid() {
+            log::warn!("Ignoring texture generate_mipmaps: invalid GL context");
+            return;
+        }
+
+        context.bind_texture(self);
+
+        context.with_gl_backend(|backend| unsafe {
+            backend.gl_generate_mipmap(GL_TEXTURE_2D);
+        });
     }
 }
 
-struct GLContextManagerState
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+struct GLRenderbuffer
 {
-    is_valid: bool,
-    active_texture: Option<GLTexture>,
-    active_program: Option<Rc<GLProgram>>,
-    active_blend_mode: Option<GLBlendEnabled>,
-    viewport_size: Option<UVec2>,
-    scissor_enabled: bool,
-    gl_backend: Rc<dyn GLBackend + 'static>,
-    gl_version: GLVersion,
-    weak_ref_to_self: Weak<RefCell<GLContextManagerState>>
+    handle: Rc<GLHandle<GLHandleTypeRenderbuffer>>
 }
 
-impl PartialEq for GLContextManagerState
+impl GLHandleOwner<GLHandleTypeRenderbuffer> for GLRenderbuffer
 {
-    fn eq(&self, other: &Self) -> bool
+    fn get_handle(&self) -> <GLHandleTypeRenderbuffer as GLHandleId>::HandleRawType
     {
-        ptr::eq(self, other)
+        self.handle.handle.handle
     }
 }
 
-#[derive(Clone)]
-pub struct GLContextManager
-{
-    state: Rc<RefCell<GLContextManagerState>>
-}
-
-impl GLContextManager
+impl GLRenderbuffer
 {
-    pub fn create(
-        gl_backend: Rc<dyn GLBackend>,
-        gl_version: GLVersion
+    /// Creates a new renderbuffer. If `samples` is zero, the renderbuffer
+    /// isn't multisampled.
+    fn new(
+        context: &GLContextManager,
+        samples: u32,
+        internal_format: GLenum,
+        size: UVec2
+    ) -> Result<Self, BacktraceError<ErrorMessage>>
+    {
+        let handle = GLHandle::wrap(context, GLHandleType::Renderbuffer, || {
+            context.with_gl_backend(|backend| unsafe {
+                Ok(GLHandleTypeRenderbuffer {
+                    handle: backend.gl_gen_renderbuffer()?
+                })
+            })
+        })?;
+
+        let renderbuffer = GLRenderbuffer {
+            handle: Rc::new(handle)
+        };
+
+        context.with_gl_backend::<Result<(), BacktraceError<ErrorMessage>>, _>(
+            |backend| unsafe {
+                backend.gl_bind_renderbuffer(GL_RENDERBUFFER, renderbuffer.get_handle());
+
+                if samples == 0 {
+                    backend.gl_renderbuffer_storage(
+                        GL_RENDERBUFFER,
+                        internal_format,
+                        size.x.try_into()?,
+                        size.y.try_into()?
+                    );
+                } else {
+                    backend.gl_renderbuffer_storage_multisample(
+                        GL_RENDERBUFFER,
+                        samples.try_into()?,
+                        internal_format,
+                        size.x.try_into()?,
+                        size.y.try_into()?
+                    );
+                }
+                Ok(())
+            }
+        )?;
+
+        gl_check_error_always(context).context("Failed to allocate renderbuffer")?;
+
+        Ok(renderbuffer)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct GLFramebuffer
+{
+    handle: Rc<GLHandle<GLHandleTypeFramebuffer>>,
+    size: UVec2,
+
+    // Kept alive for as long as they're attached to the framebuffer
+    #[allow(dead_code)]
+    renderbuffers: Vec<GLRenderbuffer>
+}
+
+impl GLHandleOwner<GLHandleTypeFramebuffer> for GLFramebuffer
+{
+    fn get_handle(&self) -> <GLHandleTypeFramebuffer as GLHandleId>::HandleRawType
+    {
+        self.handle.handle.handle
+    }
+}
+
+impl GLFramebuffer
+{
+    fn new_with_attachment<F>(
+        context: &GLContextManager,
+        size: UVec2,
+        renderbuffers: Vec<GLRenderbuffer>,
+        attach: F
+    ) -> Result<Self, BacktraceError<ErrorMessage>>
+    where
+        F: FnOnce(&Rc<dyn GLBackend>)
+    {
+        let handle = GLHandle::wrap(context, GLHandleType::Framebuffer, || {
+            context.with_gl_backend(|backend| unsafe {
+                Ok(GLHandleTypeFramebuffer {
+                    handle: backend.gl_gen_framebuffer()?
+                })
+            })
+        })?;
+
+        let framebuffer = GLFramebuffer {
+            handle: Rc::new(handle),
+            size,
+            renderbuffers
+        };
+
+        let status = context.with_gl_backend(|backend| unsafe {
+            backend.gl_bind_framebuffer(GL_FRAMEBUFFER, Some(framebuffer.get_handle()));
+            attach(backend);
+            backend.gl_check_framebuffer_status(GL_FRAMEBUFFER)
+        });
+
+        context.rebind_active_framebuffer();
+
+        if status != GL_FRAMEBUFFER_COMPLETE {
+            return Err(ErrorMessage::msg(format!(
+                "Framebuffer incomplete, status 0x{status:X}"
+            )));
+        }
+
+        Ok(framebuffer)
+    }
+
+    fn new_for_texture(
+        context: &GLContextManager,
+        texture: &GLTexture,
+        size: UVec2
+    ) -> Result<Self, BacktraceError<ErrorMessage>>
+    {
+        let stencil = GLRenderbuffer::new(context, 0, GL_STENCIL_INDEX8, size)?;
+        let stencil_handle = stencil.get_handle();
+
+        Self::new_with_attachment(context, size, vec![stencil], |backend| unsafe {
+            backend.gl_framebuffer_texture_2d(
+                GL_FRAMEBUFFER,
+                GL_COLOR_ATTACHMENT0,
+                GL_TEXTURE_2D,
+                texture.get_handle(),
+                0
+            );
+            backend.gl_framebuffer_renderbuffer(
+                GL_FRAMEBUFFER,
+                GL_STENCIL_ATTACHMENT,
+                GL_RENDERBUFFER,
+                stencil_handle
+            );
+        })
+    }
+
+    fn new_multisampled(
+        context: &GLContextManager,
+        samples: u32,
+        size: UVec2
+    ) -> Result<Self, BacktraceError<ErrorMessage>>
+    {
+        let color = GLRenderbuffer::new(context, samples, GL_RGBA8, size)?;
+        let color_handle = color.get_handle();
+
+        let stencil = GLRenderbuffer::new(context, samples, GL_STENCIL_INDEX8, size)?;
+        let stencil_handle = stencil.get_handle();
+
+        Self::new_with_attachment(context, size, vec![color, stencil], |backend| unsafe {
+            backend.gl_framebuffer_renderbuffer(
+                GL_FRAMEBUFFER,
+                GL_COLOR_ATTACHMENT0,
+                GL_RENDERBUFFER,
+                color_handle
+            );
+            backend.gl_framebuffer_renderbuffer(
+                GL_FRAMEBUFFER,
+                GL_STENCIL_ATTACHMENT,
+                GL_RENDERBUFFER,
+                stencil_handle
+            );
+        })
+    }
+}
+
+#[must_use]
+fn obtain_context_if_valid(
+    state: &RefCell<GLContextManagerState>
+) -> Option<GLContextManager>
+{
+    let state = state.borrow_mut();
+
+    if state.is_valid {
+        Some(GLContextManager {
+            state: state.weak_ref_to_self.upgrade().unwrap()
+        })
+    } else {
+        None
+    }
+}
+
+#[inline]
+#[must_use]
+fn obtain_context_from_weak_if_valid(
+    state: &Weak<RefCell<GLContextManagerState>>
+) -> Option<GLContextManager>
+{
+    match state.upgrade() {
+        None => None,
+        Some(state) => obtain_context_if_valid(&state)
+    }
+}
+
+/// The area of the render destination which is drawn to.
+#[derive(Debug, Clone)]
+struct GLViewport
+{
+    framebuffer_size: UVec2,
+
+    /// Relative to the top left of the render destination.
+    area: URect
+}
+
+impl GLViewport
+{
+    fn area_i32(&self) -> IRect
+    {
+        IRect::new(
+            self.area.top_left().into_i32(),
+            self.area.bottom_right().into_i32()
+        )
+    }
+
+    /// Returns true if the viewport only covers part of the render
+    /// destination, for example when letterboxing.
+    fn is_partial(&self) -> bool
+    {
+        self.area != URect::new(UVec2::ZERO, self.framebuffer_size)
+    }
+}
+
+struct GLContextManagerState
+{
+    is_valid: bool,
+    active_texture: Option<GLTexture>,
+    active_program: Option<Rc<GLProgram>>,
+    active_vertex_array: Option<GLVertexArray>,
+    active_blend_mode: Option<GLBlendEnabled>,
+    active_framebuffer: Option<GLFramebuffer>,
+    multisample_framebuffer: Option<GLFramebuffer>,
+    viewport: Option<GLViewport>,
+    scissor_enabled: bool,
+    gl_backend: Rc<dyn GLBackend + 'static>,
+    gl_version: GLVersion,
+    has_gl3_features: bool,
+    weak_ref_to_self: Weak<RefCell<GLContextManagerState>>
+}
+
+impl PartialEq for GLContextManagerState
+{
+    fn eq(&self, other: &Self) -> bool
+    {
+        ptr::eq(self, other)
+    }
+}
+
+#[derive(Clone)]
+pub struct GLContextManager
+{
+    state: Rc<RefCell<GLContextManagerState>>
+}
+
+impl GLContextManager
+{
+    pub fn create(
+        gl_backend: Rc<dyn GLBackend>,
+        gl_version: GLVersion
     ) -> Result<Self, BacktraceError<ErrorMessage>>
     {
         let manager = GLContextManager {
@@ -864,9 +1456,13 @@ impl GLContextManager
                 is_valid: true,
                 active_texture: None,
                 active_program: None,
+                active_vertex_array: None,
                 active_blend_mode: None,
-                viewport_size: None,
+                active_framebuffer: None,
+                multisample_framebuffer: None,
+                viewport: None,
                 scissor_enabled: false,
+                has_gl3_features: gl_version.has_gl3_features(gl_backend.as_ref()),
                 gl_backend,
                 gl_version,
                 weak_ref_to_self: Weak::new()
@@ -925,28 +1521,248 @@ impl GLContextManager
         )?))
     }
 
+    /// Creates a vertex array containing the specified buffers. The buffers in
+    /// `instance_buffers` advance once per instance, when drawing using
+    /// [GLContextManager::draw_triangles_instanced].
+    pub fn new_vertex_array(
+        &self,
+        vertex_buffers: &[&GLBuffer],
+        instance_buffers: &[&GLBuffer]
+    ) -> Result<GLVertexArray, BacktraceError<ErrorMessage>>
+    {
+        self.ensure_valid()?;
+
+        if !self.has_gl3_features() {
+            return Err(ErrorMessage::msg("Vertex arrays are not supported"));
+        }
+
+        GLVertexArray::new(self, vertex_buffers, instance_buffers)
+    }
+
+    pub fn new_index_buffer(&self)
+        -> Result<GLIndexBuffer, BacktraceError<ErrorMessage>>
+    {
+        self.ensure_valid()?;
+        GLIndexBuffer::new(self)
+    }
+
     pub fn new_texture(&self) -> Result<GLTexture, BacktraceError<ErrorMessage>>
     {
         self.ensure_valid()?;
         GLTexture::new(self)
     }
 
+    pub fn new_framebuffer(
+        &self,
+        texture: &GLTexture,
+        size: UVec2
+    ) -> Result<GLFramebuffer, BacktraceError<ErrorMessage>>
+    {
+        self.ensure_valid()?;
+        GLFramebuffer::new_for_texture(self, texture, size)
+    }
+
+    /// Renders to an offscreen multisampled framebuffer of the specified
+    /// size, rather than directly to the default framebuffer. A `samples`
+    /// value of zero disables multisampling. The result is copied to the
+    /// default framebuffer by [GLContextManager::resolve_multisampling].
+    pub fn set_multisampling(
+        &self,
+        samples: u32,
+        size: UVec2
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        self.ensure_valid()?;
+
+        let samples = if samples == 0 {
+            0
+        } else if self.version().is_gles2() {
+            log::warn!("Multisampling is not supported on OpenGL ES 2.0");
+            0
+        } else {
+            let max_samples = self.with_gl_backend(|backend| unsafe {
+                backend.gl_get_parameter_i32(GL_MAX_SAMPLES)
+            });
+
+            let max_samples: u32 = max_samples.try_into().unwrap_or(0);
+
+            if samples > max_samples {
+                log::warn!(
+                    "Requested {samples}x multisampling, but only {max_samples}x is \
+                     supported"
+                );
+            }
+
+            samples.min(max_samples)
+        };
+
+        log::info!("Setting multisampling level to {samples}x");
+
+        let framebuffer = match samples {
+            0 => None,
+            _ => Some(
+                GLFramebuffer::new_multisampled(self, samples, size)
+                    .context("Failed to create multisampled framebuffer")?
+            )
+        };
+
+        // Drop separately to avoid a duplicate borrow of `state`.
+        let old_framebuffer = RefCell::borrow_mut(&self.state)
+            .multisample_framebuffer
+            .take();
+        drop(old_framebuffer);
+
+        RefCell::borrow_mut(&self.state).multisample_framebuffer = framebuffer;
+
+        self.rebind_active_framebuffer();
+
+        Ok(())
+    }
+
+    fn multisample_framebuffer(&self) -> Option<GLFramebuffer>
+    {
+        RefCell::borrow(&self.state).multisample_framebuffer.clone()
+    }
+
+    /// Copies the contents of the multisampled framebuffer (if any) to the
+    /// default framebuffer.
+    pub fn resolve_multisampling(&self)
+    {
+        if !self.is_valid() {
+            log::warn!("Ignoring resolve_multisampling: invalid GL context");
+            return;
+        }
+
+        let framebuffer = match self.multisample_framebuffer() {
+            None => return,
+            Some(framebuffer) => framebuffer
+        };
+
+        let scissor_enabled = self.state.borrow().scissor_enabled;
+        self.set_scissor_test(false);
+
+        let width = framebuffer.size.x as GLint;
+        let height = framebuffer.size.y as GLint;
+
+        self.with_gl_backend(|backend| unsafe {
+            backend
+                .gl_bind_framebuffer(GL_READ_FRAMEBUFFER, Some(framebuffer.get_handle()));
+            backend.gl_bind_framebuffer(GL_DRAW_FRAMEBUFFER, None);
+            backend.gl_blit_framebuffer(
+                0,
+                0,
+                width,
+                height,
+                0,
+                0,
+                width,
+                height,
+                GL_COLOR_BUFFER_BIT,
+                GL_NEAREST
+            );
+        });
+
+        self.rebind_active_framebuffer();
+        self.set_scissor_test(scissor_enabled);
+    }
+
+    /// Binds the specified framebuffer as the render destination, or the
+    /// default framebuffer if `None`. Returns the previously bound framebuffer.
+    pub fn bind_framebuffer(
+        &self,
+        framebuffer: Option<&GLFramebuffer>
+    ) -> Option<GLFramebuffer>
+    {
+        if !self.is_valid() {
+            log::warn!("Ignoring bind_framebuffer: invalid GL context");
+            return None;
+        }
+
+        let previous = RefCell::borrow_mut(&self.state).active_framebuffer.take();
+
+        RefCell::borrow_mut(&self.state).active_framebuffer = framebuffer.cloned();
+
+        if previous.as_ref() != framebuffer {
+            self.rebind_active_framebuffer();
+        }
+
+        previous
+    }
+
+    /// Binds the active framebuffer if there is one, otherwise the
+    /// multisampled framebuffer if enabled, otherwise the default framebuffer.
+    fn rebind_active_framebuffer(&self)
+    {
+        let framebuffer = {
+            let state = RefCell::borrow(&self.state);
+            state
+                .active_framebuffer
+                .as_ref()
+                .or(state.multisample_framebuffer.as_ref())
+                .map(|framebuffer| framebuffer.get_handle())
+        };
+
+        self.with_gl_backend(|backend| unsafe {
+            backend.gl_bind_framebuffer(GL_FRAMEBUFFER, framebuffer);
+        });
+    }
+
+    /// Offscreen framebuffers are rendered with an inverted Y axis, so that
+    /// the first row of the attached texture is the top of the image.
+    fn is_y_axis_inverted(&self) -> bool
+    {
+        RefCell::borrow(&self.state).active_framebuffer.is_some()
+    }
+
     pub fn set_viewport_size(&self, size: UVec2)
+    {
+        self.set_viewport(size, URect::new(UVec2::ZERO, size));
+    }
+
+    /// Restricts drawing to `area` of a render destination of size
+    /// `framebuffer_size`. Clip rectangles and captured regions are relative
+    /// to the top left of the area, and can't extend outside it.
+    pub fn set_viewport(&self, framebuffer_size: UVec2, area: URect)
     {
         if !self.is_valid() {
-            log::warn!("Ignoring set_viewport_size: invalid GL context");
+            log::warn!("Ignoring set_viewport: invalid GL context");
             return;
         }
 
-        log::info!("Setting viewport size to {}x{}", size.x, size.y);
+        log::info!(
+            "Setting viewport size to {}x{}",
+            area.width(),
+            area.height()
+        );
+
+        let viewport = GLViewport {
+            framebuffer_size,
+            area
+        };
+
+        let area = viewport.area_i32();
+        let (x, y) = self.framebuffer_origin(&viewport, &area);
 
-        self.state.borrow_mut().viewport_size = Some(size);
+        self.state.borrow_mut().viewport = Some(viewport);
 
         self.with_gl_backend(|backend| unsafe {
-            backend.gl_viewport(0, 0, size.x as i32, size.y as i32);
+            backend.gl_viewport(x, y, area.width(), area.height());
         });
     }
 
+    /// Converts a rectangle relative to the top left of the render
+    /// destination into the bottom left corner expected by GL.
+    fn framebuffer_origin(&self, viewport: &GLViewport, rect: &IRect) -> (i32, i32)
+    {
+        let y = if self.is_y_axis_inverted() {
+            rect.top_left().y
+        } else {
+            viewport.framebuffer_size.y as i32 - rect.bottom_right().y
+        };
+
+        (rect.top_left().x, y)
+    }
+
     pub fn bind_texture(&self, texture: &GLTexture)
     {
         if !self.is_valid() {
@@ -1012,7 +1828,11 @@ impl GLContextManager
             return;
         }
 
-        if let Some(existing_program) = &RefCell::borrow_mut(&self.state).active_program {
+        // Take the existing program separately to avoid a duplicate borrow of
+        // `state` while disabling it.
+        let existing_program = RefCell::borrow_mut(&self.state).active_program.take();
+
+        if let Some(existing_program) = existing_program {
             existing_program.disable(self);
         }
 
@@ -1020,6 +1840,31 @@ impl GLContextManager
         program.enable(self);
     }
 
+    pub fn bind_vertex_array(&self, vertex_array: &GLVertexArray)
+    {
+        if !self.is_valid() {
+            log::warn!("Ignoring bind_vertex_array: invalid GL context");
+            return;
+        }
+
+        if RefCell::borrow(&self.state).active_vertex_array.as_ref() == Some(vertex_array)
+        {
+            // Already bound
+            return;
+        }
+
+        // Drop separately to avoid a duplicate borrow of `state`.
+        let old_vertex_array =
+            RefCell::borrow_mut(&self.state).active_vertex_array.take();
+        drop(old_vertex_array);
+
+        RefCell::borrow_mut(&self.state).active_vertex_array = Some(vertex_array.clone());
+
+        self.with_gl_backend(|backend| unsafe {
+            backend.gl_bind_vertex_array(Some(vertex_array.get_handle()));
+        });
+    }
+
     fn set_blend_mode(&self, blend_mode: GLBlendEnabled)
     {
         if RefCell::borrow(&self.state).active_blend_mode == Some(blend_mode.clone()) {
@@ -1029,17 +1874,25 @@ impl GLContextManager
         RefCell::borrow_mut(&self.state).active_blend_mode = Some(blend_mode.clone());
 
         match blend_mode {
-            GLBlendEnabled::Enabled(mode) => match mode {
-                GLBlendMode::OneMinusSrcAlpha => self.with_gl_backend(|backend| unsafe {
+            GLBlendEnabled::Enabled(mode) => {
+                // The fragment shader outputs premultiplied alpha
+                let (src_rgb, dst_rgb) = match mode {
+                    GLBlendMode::OneMinusSrcAlpha => (GL_ONE, GL_ONE_MINUS_SRC_ALPHA),
+                    GLBlendMode::Additive => (GL_ONE, GL_ONE),
+                    GLBlendMode::Multiply => (GL_DST_COLOR, GL_ONE_MINUS_SRC_ALPHA),
+                    GLBlendMode::Screen => (GL_ONE, GL_ONE_MINUS_SRC_COLOR)
+                };
+
+                self.with_gl_backend(|backend| unsafe {
                     backend.gl_enable(GL_BLEND);
                     backend.gl_blend_func_separate(
-                        GL_SRC_ALPHA,
-                        GL_ONE_MINUS_SRC_ALPHA,
+                        src_rgb,
+                        dst_rgb,
                         GL_ONE,
                         GL_ONE_MINUS_SRC_ALPHA
                     );
                 })
-            },
+            }
 
             GLBlendEnabled::Disabled => self.with_gl_backend(|backend| unsafe {
                 backend.gl_disable(GL_BLEND);
@@ -1047,7 +1900,25 @@ impl GLContextManager
         }
     }
 
+    /// Enables or disables clipping to the rectangle set using
+    /// [GLContextManager::set_clip]. While the viewport only covers part of
+    /// the render destination, the scissor test is kept enabled, so that
+    /// clearing the screen doesn't affect the area outside the viewport.
     pub fn set_enable_scissor(&self, enabled: bool)
+    {
+        let viewport = self.state.borrow().viewport.clone();
+
+        match viewport {
+            Some(viewport) if !enabled && viewport.is_partial() => {
+                let size = viewport.area.size();
+                self.set_clip(0, 0, size.x as i32, size.y as i32);
+                self.set_scissor_test(true);
+            }
+            _ => self.set_scissor_test(enabled)
+        }
+    }
+
+    fn set_scissor_test(&self, enabled: bool)
     {
         if enabled != self.state.borrow().scissor_enabled {
             self.with_gl_backend(|backend| unsafe {
@@ -1060,14 +1931,68 @@ impl GLContextManager
         }
     }
 
+    /// Clears the stencil buffer, and directs subsequent draw calls to mark
+    /// the area they cover in the stencil buffer instead of drawing to the
+    /// color buffer. Call [GLContextManager::end_stencil_clip] afterwards.
+    pub fn begin_stencil_clip(&self)
+    {
+        self.with_gl_backend(|backend| unsafe {
+            backend.gl_enable(GL_STENCIL_TEST);
+            backend.gl_stencil_mask(0xFF);
+            backend.gl_clear_stencil(0);
+            backend.gl_clear(GL_STENCIL_BUFFER_BIT);
+            backend.gl_stencil_func(GL_ALWAYS, 1, 0xFF);
+            backend.gl_stencil_op(GL_KEEP, GL_KEEP, GL_REPLACE);
+            backend.gl_color_mask(false, false, false, false);
+        });
+    }
+
+    /// Restores drawing to the color buffer, restricted to the area marked
+    /// since [GLContextManager::begin_stencil_clip].
+    pub fn end_stencil_clip(&self)
+    {
+        self.with_gl_backend(|backend| unsafe {
+            backend.gl_color_mask(true, true, true, true);
+        });
+
+        self.set_enable_stencil_clip(true);
+    }
+
+    /// Enables or disables clipping to the area marked in the stencil buffer.
+    pub fn set_enable_stencil_clip(&self, enabled: bool)
+    {
+        self.with_gl_backend(|backend| unsafe {
+            if enabled {
+                backend.gl_enable(GL_STENCIL_TEST);
+                backend.gl_stencil_mask(0);
+                backend.gl_stencil_func(GL_EQUAL, 1, 0xFF);
+                backend.gl_stencil_op(GL_KEEP, GL_KEEP, GL_KEEP);
+            } else {
+                backend.gl_disable(GL_STENCIL_TEST);
+            }
+        });
+    }
+
+    /// Sets the clip rectangle, relative to the top left of the viewport.
+    /// The rectangle is cropped to fit inside the viewport.
     pub fn set_clip(&self, x: i32, y: i32, width: i32, height: i32)
     {
-        let vp_height = match self.state.borrow().viewport_size {
+        let viewport = match self.state.borrow().viewport.clone() {
             None => panic!("Call to set_clip before viewport size set"),
-            Some(viewport_size) => viewport_size.y as i32
+            Some(viewport) => viewport
         };
+
+        let area = viewport.area_i32();
+
+        let clip = IRect::new(IVec2::new(x, y), IVec2::new(x + width, y + height))
+            .with_offset(*area.top_left())
+            .intersect(&area)
+            .unwrap_or_else(|| IRect::new(*area.top_left(), *area.top_left()));
+
+        let (x, y) = self.framebuffer_origin(&viewport, &clip);
+
         self.with_gl_backend(|backend| unsafe {
-            backend.gl_scissor(x, vp_height - y - height, width, height);
+            backend.gl_scissor(x, y, clip.width(), clip.height());
         });
     }
 
@@ -1085,6 +2010,53 @@ impl GLContextManager
         });
     }
 
+    /// Draws `instance_count` copies of the first `vertex_count` vertices,
+    /// using a vertex array created with instance buffers.
+    pub fn draw_triangles_instanced(
+        &self,
+        blend_mode: GLBlendEnabled,
+        vertex_count: usize,
+        instance_count: usize
+    )
+    {
+        if !self.is_valid() {
+            log::warn!("Ignoring draw_triangles_instanced: invalid GL context");
+            return;
+        }
+
+        self.set_blend_mode(blend_mode);
+
+        self.with_gl_backend(|backend| unsafe {
+            backend.gl_draw_arrays_instanced(
+                GL_TRIANGLES,
+                0,
+                vertex_count.try_into().unwrap(),
+                instance_count.try_into().unwrap()
+            );
+        });
+    }
+
+    /// Draws the triangles described by the first `index_count` indices in
+    /// the index buffer of the vertex array which is currently bound.
+    pub fn draw_triangles_indexed(&self, blend_mode: GLBlendEnabled, index_count: usize)
+    {
+        if !self.is_valid() {
+            log::warn!("Ignoring draw_triangles_indexed: invalid GL context");
+            return;
+        }
+
+        self.set_blend_mode(blend_mode);
+
+        self.with_gl_backend(|backend| unsafe {
+            backend.gl_draw_elements(
+                GL_TRIANGLES,
+                index_count.try_into().unwrap(),
+                GL_UNSIGNED_INT,
+                0
+            );
+        });
+    }
+
     pub fn clear_screen(&self, color: Color)
     {
         if !self.is_valid() {
@@ -1125,68 +2097,402 @@ impl GLContextManager
         self.state.borrow().gl_version
     }
 
+    /// Returns true if vertex arrays, 32-bit index buffers, and
+    /// [GLContextManager::draw_triangles_instanced] can be used.
+    pub fn has_gl3_features(&self) -> bool
+    {
+        self.state.borrow().has_gl3_features
+    }
+
     pub fn capture(&mut self, format: ImageDataType) -> RawBitmapData
     {
-        let viewport_size = match self.state.borrow().viewport_size {
+        let viewport_size = match &self.state.borrow().viewport {
             None => return RawBitmapData::new(vec![], (0, 0), format),
-            Some(value) => value
+            Some(viewport) => viewport.area.size()
         };
 
-        let width: usize = viewport_size.x.try_into().unwrap();
-        let height: usize = viewport_size.y.try_into().unwrap();
+        self.capture_region(format, URect::new(UVec2::ZERO, viewport_size))
+    }
 
-        let gl_format = GLTextureImageFormatU8::from(format);
+    pub fn capture_async(&mut self, format: ImageDataType) -> GLPendingCapture
+    {
+        let viewport_size = match &self.state.borrow().viewport {
+            None => {
+                return GLPendingCaptureState::Complete(RawBitmapData::new(
+                    vec![],
+                    (0, 0),
+                    format
+                ))
+                .into()
+            }
+            Some(viewport) => viewport.area.size()
+        };
 
-        let bpp = gl_format.get_bytes_per_pixel();
-        let gl_format = gl_format.get_format();
+        self.capture_region_async(format, URect::new(UVec2::ZERO, viewport_size))
+    }
 
-        let bytes = width * height * bpp;
+    /// Reads back the pixels in the specified region, relative to the top left
+    /// of the viewport. The region is cropped to fit inside the viewport.
+    pub fn capture_region(
+        &mut self,
+        format: ImageDataType,
+        region: URect
+    ) -> RawBitmapData
+    {
+        let read = match self.begin_read_pixels(format, &region) {
+            None => return RawBitmapData::new(vec![], (0, 0), format),
+            Some(read) => read
+        };
 
-        let mut buf: Vec<u8> = Vec::with_capacity(bytes);
+        let mut buf: Vec<u8> = Vec::with_capacity(read.byte_len());
 
         self.with_gl_backend(|backend| unsafe {
             backend.gl_read_pixels(
-                0,
-                0,
-                width.try_into().unwrap(),
-                height.try_into().unwrap(),
-                gl_format,
+                read.x,
+                read.y,
+                read.width,
+                read.height,
+                read.gl_format,
                 GL_UNSIGNED_BYTE,
                 buf.spare_capacity_mut()
             );
         });
 
         unsafe {
-            buf.set_len(bytes);
+            buf.set_len(read.byte_len());
         }
 
-        let row_bytes = width * bpp;
+        self.end_read_pixels(&read);
+
+        read.into_bitmap(buf)
+    }
 
-        let buf_ptr = buf.as_mut_ptr();
+    /// Starts reading back the pixels in the specified region into a pixel
+    /// buffer, without waiting for the GPU to finish rendering. The pixels
+    /// can be retrieved later using [GLPendingCapture::finish].
+    ///
+    /// On WebGL and OpenGL ES 2.0, reading into a pixel buffer isn't supported,
+    /// so the capture is performed immediately instead.
+    pub fn capture_region_async(
+        &mut self,
+        format: ImageDataType,
+        region: URect
+    ) -> GLPendingCapture
+    {
+        #[cfg(target_arch = "wasm32")]
+        {
+            GLPendingCaptureState::Complete(self.capture_region(format, region)).into()
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if self.version().is_gles2() {
+                // Pixel buffer objects aren't available
+                return GLPendingCaptureState::Complete(
+                    self.capture_region(format, region)
+                )
+                .into();
+            }
 
-        for row in 0..(height / 2) {
-            let bottom_row = height - row - 1;
+            let read = match self.begin_read_pixels(format, &region) {
+                None => {
+                    return GLPendingCaptureState::Complete(RawBitmapData::new(
+                        vec![],
+                        (0, 0),
+                        format
+                    ))
+                    .into()
+                }
+                Some(read) => read
+            };
 
-            let top_start = row * row_bytes;
-            let bottom_start = bottom_row * row_bytes;
+            let buffer = GLHandle::wrap(self, GLHandleType::Buffer, || {
+                self.with_gl_backend(|backend| unsafe {
+                    Ok(GLHandleTypeBuffer {
+                        handle: backend.gl_gen_buffer()?
+                    })
+                })
+            });
 
-            unsafe {
-                ptr::swap_nonoverlapping(
-                    buf_ptr.add(top_start),
-                    buf_ptr.add(bottom_start),
-                    row_bytes
+            let buffer = match buffer {
+                Ok(buffer) => buffer,
+                Err(err) => {
+                    log::error!(
+                        "Failed to create pixel buffer, capturing now: {:?}",
+                        err
+                    );
+                    self.end_read_pixels(&read);
+                    return GLPendingCaptureState::Complete(
+                        self.capture_region(format, region)
+                    )
+                    .into();
+                }
+            };
+
+            self.with_gl_backend(|backend| unsafe {
+                backend.gl_bind_buffer(GL_PIXEL_PACK_BUFFER, buffer.handle.handle);
+
+                backend.gl_buffer_data_size(
+                    GL_PIXEL_PACK_BUFFER,
+                    read.byte_len().try_into().unwrap(),
+                    GL_STREAM_READ
+                );
+
+                backend.gl_read_pixels_to_buffer(
+                    read.x,
+                    read.y,
+                    read.width,
+                    read.height,
+                    read.gl_format,
+                    GL_UNSIGNED_BYTE,
+                    0
                 );
+
+                backend.gl_unbind_buffer(GL_PIXEL_PACK_BUFFER);
+            });
+
+            self.end_read_pixels(&read);
+
+            GLPendingCaptureState::Buffer { buffer, read }.into()
+        }
+    }
+
+    fn begin_read_pixels(
+        &mut self,
+        format: ImageDataType,
+        region: &URect
+    ) -> Option<GLPixelRead>
+    {
+        let viewport = self.state.borrow().viewport.clone()?;
+
+        let region = region
+            .intersect(&URect::new(UVec2::ZERO, viewport.area.size()))?
+            .with_offset(*viewport.area.top_left());
+
+        let region_size = region.size();
+
+        let (x, y) = self.framebuffer_origin(
+            &viewport,
+            &IRect::new(
+                region.top_left().into_i32(),
+                region.bottom_right().into_i32()
+            )
+        );
+
+        let gl_format = if self.version().is_gles() {
+            GLTextureImageFormatU8::RGBA
+        } else {
+            GLTextureImageFormatU8::from(format)
+        };
+
+        // Multisampled framebuffers can't be read directly, so read the
+        // resolved image from the default framebuffer instead.
+        let read_resolved =
+            !self.is_y_axis_inverted() && self.multisample_framebuffer().is_some();
+
+        if read_resolved {
+            self.resolve_multisampling();
+            self.with_gl_backend(|backend| unsafe {
+                backend.gl_bind_framebuffer(GL_FRAMEBUFFER, None);
+            });
+        }
+
+        self.with_gl_backend(|backend| unsafe {
+            // Rows must be tightly packed, as the buffer has no room for padding
+            backend.gl_pixel_store_i(GL_PACK_ALIGNMENT, 1);
+        });
+
+        Some(GLPixelRead {
+            x,
+            y,
+            width: region_size.x.try_into().unwrap(),
+            height: region_size.y.try_into().unwrap(),
+            gl_format: gl_format.get_format(),
+            bytes_per_pixel: gl_format.get_bytes_per_pixel(),
+            format,
+            flip_rows: !self.is_y_axis_inverted(),
+            read_resolved
+        })
+    }
+
+    fn end_read_pixels(&self, read: &GLPixelRead)
+    {
+        if read.read_resolved {
+            self.rebind_active_framebuffer();
+        }
+    }
+}
+
+/// The parameters of a single `glReadPixels` call.
+struct GLPixelRead
+{
+    x: GLint,
+    y: GLint,
+    width: GLsizei,
+    height: GLsizei,
+    gl_format: GLenum,
+    bytes_per_pixel: usize,
+    format: ImageDataType,
+    flip_rows: bool,
+    read_resolved: bool
+}
+
+impl GLPixelRead
+{
+    fn byte_len(&self) -> usize
+    {
+        self.width as usize * self.height as usize * self.bytes_per_pixel
+    }
+
+    fn into_bitmap(self, mut buf: Vec<u8>) -> RawBitmapData
+    {
+        let width = self.width as usize;
+        let height = self.height as usize;
+
+        let size = UVec2::new(width as u32, height as u32);
+
+        if self.flip_rows {
+            let row_bytes = width * self.bytes_per_pixel;
+
+            let buf_ptr = buf.as_mut_ptr();
+
+            for row in 0..(height / 2) {
+                let bottom_row = height - row - 1;
+
+                let top_start = row * row_bytes;
+                let bottom_start = bottom_row * row_bytes;
+
+                unsafe {
+                    ptr::swap_nonoverlapping(
+                        buf_ptr.add(top_start),
+                        buf_ptr.add(bottom_start),
+                        row_bytes
+                    );
+                }
+            }
+        }
+
+        if self.format == ImageDataType::RGB && self.bytes_per_pixel == 4 {
+            // The pixels were read as RGBA, so remove the alpha channel
+            let pixel_count = width * height;
+
+            for pixel in 0..pixel_count {
+                buf.copy_within(pixel * 4..pixel * 4 + 3, pixel * 3);
             }
+
+            buf.truncate(pixel_count * 3);
         }
 
-        RawBitmapData::new(buf, viewport_size, format)
+        RawBitmapData::new(buf, size, self.format)
+    }
+}
+
+/// A capture started using [GLContextManager::capture_region_async].
+pub struct GLPendingCapture
+{
+    state: GLPendingCaptureState
+}
+
+impl From<GLPendingCaptureState> for GLPendingCapture
+{
+    fn from(state: GLPendingCaptureState) -> Self
+    {
+        GLPendingCapture { state }
+    }
+}
+
+enum GLPendingCaptureState
+{
+    Complete(RawBitmapData),
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    Buffer
+    {
+        buffer: GLHandle<GLHandleTypeBuffer>,
+        read: GLPixelRead
+    }
+}
+
+impl GLPendingCapture
+{
+    /// Retrieves the captured pixels. If the GPU hasn't yet finished
+    /// rendering the captured frame, this blocks until it has.
+    pub fn finish(self, context: &GLContextManager) -> RawBitmapData
+    {
+        match self.state {
+            GLPendingCaptureState::Complete(bitmap) => bitmap,
+            GLPendingCaptureState::Buffer { buffer, read } => {
+                if !context.is_valid() {
+                    log::warn!("Ignoring capture finish: invalid GL context");
+                    return RawBitmapData::new(vec![], (0, 0), read.format);
+                }
+
+                let mut buf: Vec<u8> = Vec::with_capacity(read.byte_len());
+
+                let use_map_buffer = context.version().is_gles();
+
+                context.with_gl_backend(|backend| unsafe {
+                    backend.gl_bind_buffer(GL_PIXEL_PACK_BUFFER, buffer.handle.handle);
+
+                    if use_map_buffer {
+                        let mapped = backend.gl_map_buffer_range(
+                            GL_PIXEL_PACK_BUFFER,
+                            0,
+                            read.byte_len() as GLsizei,
+                            GL_MAP_READ_BIT
+                        );
+
+                        if mapped.is_null() {
+                            log::error!("Failed to map pixel buffer");
+                            buf.resize(read.byte_len(), 0);
+                        } else {
+                            ptr::copy_nonoverlapping(
+                                mapped,
+                                buf.as_mut_ptr(),
+                                read.byte_len()
+                            );
+                            backend.gl_unmap_buffer(GL_PIXEL_PACK_BUFFER);
+                        }
+                    } else {
+                        backend.gl_get_buffer_sub_data(
+                            GL_PIXEL_PACK_BUFFER,
+                            0,
+                            buf.spare_capacity_mut()
+                        );
+                    }
+
+                    backend.gl_unbind_buffer(GL_PIXEL_PACK_BUFFER);
+                });
+
+                unsafe {
+                    buf.set_len(read.byte_len());
+                }
+
+                read.into_bitmap(buf)
+            }
+        }
     }
 }
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
 pub enum GLBlendMode
 {
-    OneMinusSrcAlpha
+    OneMinusSrcAlpha,
+    Additive,
+    Multiply,
+    Screen
+}
+
+impl From<BlendMode> for GLBlendMode
+{
+    fn from(blend_mode: BlendMode) -> Self
+    {
+        match blend_mode {
+            BlendMode::Alpha => GLBlendMode::OneMinusSrcAlpha,
+            BlendMode::Additive => GLBlendMode::Additive,
+            BlendMode::Multiply => GLBlendMode::Multiply,
+            BlendMode::Screen => GLBlendMode::Screen
+        }
+    }
 }
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
@@ -1196,3 +2502,49 @@ pub enum GLBlendEnabled
     #[allow(dead_code)]
     Disabled
 }
+
+#[cfg(test)]
+mod test
+{
+    use crate::glwrapper::GLVersion;
+
+    #[test]
+    pub fn test_gl_version_from_version_string()
+    {
+        for (version, expected) in &[
+            ("2.1 Mesa 23.2.1", GLVersion::OpenGL2_0),
+            ("4.6.0 NVIDIA 535.104.05", GLVersion::OpenGL2_0),
+            ("OpenGL ES 2.0 Mesa 23.2.1", GLVersion::OpenGLES2_0),
+            ("OpenGL ES 3.2 Mesa 23.2.1", GLVersion::OpenGLES3_0),
+            ("OpenGL ES 3.0.0 (ANGLE 2.1.0)", GLVersion::OpenGLES3_0),
+            ("OpenGL ES-CM 1.1", GLVersion::OpenGLES2_0)
+        ] {
+            assert_eq!(
+                *expected,
+                GLVersion::from_version_string(version),
+                "{}",
+                version
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_gl3_features_from_desktop_version_string()
+    {
+        for (version, expected) in &[
+            ("2.1 Metal - 83.1", false),
+            ("3.2.0 NVIDIA 535.113.01", false),
+            ("3.3.0 NVIDIA 535.113.01", true),
+            ("4.5 (Compatibility Profile) Mesa 23.2.1", true),
+            ("2.0 Speedy2D software renderer", false),
+            ("", false)
+        ] {
+            assert_eq!(
+                *expected,
+                GLVersion::desktop_version_has_gl3_features(version),
+                "{}",
+                version
+            );
+        }
+    }
+}
@@ -14,8 +14,23 @@
  *  limitations under the License.
  */
 
-use crate::dimen::UVec2;
-use crate::glwrapper::GLTexture;
+use std::collections::HashMap;
+
+#[cfg(any(feature = "image-loading", doc, doctest))]
+use {
+    crate::error::Context,
+    std::cell::RefCell,
+    std::path::Path,
+    std::rc::Rc,
+    std::time::Duration
+};
+
+use crate::color::Color;
+use crate::dimen::{UVec2, Vec2};
+use crate::error::{BacktraceError, ErrorMessage};
+use crate::glwrapper::{GLFramebuffer, GLPendingCapture, GLTexture};
+use crate::shape::{Rect, Rectangle, URect};
+use crate::texture_packer::{TexturePacker, TexturePackerError};
 
 /// The data type of the pixels making up the raw image data.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
@@ -38,11 +53,54 @@ pub enum ImageDataType
 pub struct ImageHandle
 {
     pub(crate) size: UVec2,
-    pub(crate) texture: GLTexture
+    pub(crate) texture: GLTexture,
+    texture_offset: UVec2,
+    texture_size: UVec2
 }
 
 impl ImageHandle
 {
+    pub(crate) fn new(size: UVec2, texture: GLTexture) -> Self
+    {
+        ImageHandle {
+            size,
+            texture,
+            texture_offset: UVec2::ZERO,
+            texture_size: size
+        }
+    }
+
+    /// Returns a handle to the specified region of this image, in pixels,
+    /// which shares the same texture.
+    pub(crate) fn sub_image(&self, region: &URect) -> Self
+    {
+        ImageHandle {
+            size: region.size(),
+            texture: self.texture.clone(),
+            texture_offset: self.texture_offset + *region.top_left(),
+            texture_size: self.texture_size
+        }
+    }
+
+    /// Converts normalized coordinates within this image to normalized
+    /// coordinates within the underlying texture.
+    #[inline]
+    pub(crate) fn texture_coords(&self, image_coords_normalized: Vec2) -> Vec2
+    {
+        if self.texture_offset == UVec2::ZERO && self.texture_size == self.size {
+            return image_coords_normalized;
+        }
+
+        let offset = self.texture_offset.into_f32();
+        let size = self.size.into_f32();
+        let texture_size = self.texture_size.into_f32();
+
+        Vec2::new(
+            (offset.x + image_coords_normalized.x * size.x) / texture_size.x,
+            (offset.y + image_coords_normalized.y * size.y) / texture_size.y
+        )
+    }
+
     /// Returns the size of the image in pixels.
     pub fn size(&self) -> &UVec2
     {
@@ -50,6 +108,442 @@ impl ImageHandle
     }
 }
 
+/// Collects many small images, so that they can be packed into a single GPU
+/// texture using [crate::Graphics2D::create_image_atlas].
+///
+/// Drawing many images from the same texture is faster than drawing the same
+/// number of separate images, as the texture doesn't need to be changed
+/// between each one.
+pub struct ImageAtlasBuilder
+{
+    size: UVec2,
+    pixels: Vec<u8>,
+    packer: TexturePacker,
+    regions: Vec<URect>
+}
+
+impl ImageAtlasBuilder
+{
+    /// Creates a new, empty atlas with the specified texture size in pixels.
+    pub fn new<S: Into<UVec2>>(size: S) -> Self
+    {
+        let size = size.into();
+
+        ImageAtlasBuilder {
+            size,
+            pixels: vec![0; size.x as usize * size.y as usize * 4],
+            packer: TexturePacker::new(size.x, size.y),
+            regions: Vec::new()
+        }
+    }
+
+    /// Adds an image to the atlas, returning its index. Once the atlas has
+    /// been created, the index can be passed to [ImageAtlas::get] to get a
+    /// handle for the image.
+    ///
+    /// An error is returned if the data is the wrong length for the specified
+    /// size, or if there isn't enough space left in the atlas.
+    pub fn add_image_from_raw_pixels<S: Into<UVec2>>(
+        &mut self,
+        data_type: ImageDataType,
+        size: S,
+        data: &[u8]
+    ) -> Result<usize, BacktraceError<ErrorMessage>>
+    {
+        let size = size.into();
+
+        let pixel_bytes = match data_type {
+            ImageDataType::RGB => 3,
+            ImageDataType::RGBA => 4
+        };
+
+        let expected_bytes = pixel_bytes * size.x as usize * size.y as usize;
+
+        if expected_bytes != data.len() {
+            return Err(ErrorMessage::msg(format!(
+                "Expecting {} bytes ({}x{}x{}), got {}",
+                expected_bytes,
+                size.x,
+                size.y,
+                pixel_bytes,
+                data.len()
+            )));
+        }
+
+        let region = self.packer.try_allocate(size).map_err(|err| match err {
+            TexturePackerError::NotEnoughSpace => ErrorMessage::msg(format!(
+                "Not enough space in {}x{} atlas for {}x{} image",
+                self.size.x, self.size.y, size.x, size.y
+            ))
+        })?;
+
+        for (y, row) in data.chunks_exact(size.x as usize * pixel_bytes).enumerate() {
+            let start = ((region.top_left().y as usize + y) * self.size.x as usize
+                + region.top_left().x as usize)
+                * 4;
+
+            let dest = &mut self.pixels[start..start + size.x as usize * 4];
+
+            for (dest, src) in dest.chunks_exact_mut(4).zip(row.chunks_exact(pixel_bytes))
+            {
+                dest[..pixel_bytes].copy_from_slice(src);
+
+                if pixel_bytes == 3 {
+                    dest[3] = 255;
+                }
+            }
+        }
+
+        self.regions.push(region);
+
+        Ok(self.regions.len() - 1)
+    }
+
+    /// Returns the number of images in the atlas.
+    #[inline]
+    pub fn len(&self) -> usize
+    {
+        self.regions.len()
+    }
+
+    /// Returns `true` if no images have been added to the atlas.
+    #[inline]
+    pub fn is_empty(&self) -> bool
+    {
+        self.regions.is_empty()
+    }
+
+    #[inline]
+    pub(crate) fn size(&self) -> UVec2
+    {
+        self.size
+    }
+
+    #[inline]
+    pub(crate) fn pixels(&self) -> &[u8]
+    {
+        &self.pixels
+    }
+
+    #[inline]
+    pub(crate) fn regions(&self) -> &[URect]
+    {
+        &self.regions
+    }
+}
+
+/// A single GPU texture containing many images, created using
+/// [crate::Graphics2D::create_image_atlas].
+///
+/// Note: the handles in the atlas can only be used in the graphics context in
+/// which it was created.
+#[derive(Debug, Clone)]
+pub struct ImageAtlas
+{
+    pub(crate) image: ImageHandle,
+    pub(crate) images: Vec<ImageHandle>
+}
+
+impl ImageAtlas
+{
+    /// Returns a handle for the image with the specified index, as returned
+    /// by [ImageAtlasBuilder::add_image_from_raw_pixels]. The handle can be
+    /// used with any of the normal image drawing functions.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&ImageHandle>
+    {
+        self.images.get(index)
+    }
+
+    /// Returns a handle for the whole atlas texture.
+    #[inline]
+    pub fn image(&self) -> &ImageHandle
+    {
+        &self.image
+    }
+
+    /// Returns the number of images in the atlas.
+    #[inline]
+    pub fn len(&self) -> usize
+    {
+        self.images.len()
+    }
+
+    /// Returns `true` if the atlas contains no images.
+    #[inline]
+    pub fn is_empty(&self) -> bool
+    {
+        self.images.is_empty()
+    }
+}
+
+/// The position, scale, rotation, and tint of a single copy of an image, for
+/// use with [crate::Graphics2D::draw_image_instances].
+///
+/// By default, the whole image is drawn at its original size, with no
+/// rotation, and with no tint.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ImageInstance
+{
+    center: Vec2,
+    scale: f32,
+    rotation_radians: f32,
+    tint: Color,
+    source_rect: Option<Rect>
+}
+
+impl ImageInstance
+{
+    /// Creates a new instance of an image, centered on the specified pixel
+    /// location.
+    #[inline]
+    pub fn new<P: Into<Vec2>>(center: P) -> Self
+    {
+        ImageInstance {
+            center: center.into(),
+            scale: 1.0,
+            rotation_radians: 0.0,
+            tint: Color::WHITE,
+            source_rect: None
+        }
+    }
+
+    /// Draws only the specified part of the image, given in pixels relative
+    /// to the top left of the image. This is useful when many sprites are
+    /// stored in a single image.
+    #[inline]
+    #[must_use]
+    pub fn with_source_rect(mut self, source_rect: impl AsRef<Rect>) -> Self
+    {
+        self.source_rect = Some(source_rect.as_ref().clone());
+        self
+    }
+
+    /// Scales the image by the specified factor, where `1.0` is the original
+    /// size.
+    #[inline]
+    #[must_use]
+    pub fn with_scale(mut self, scale: f32) -> Self
+    {
+        self.scale = scale;
+        self
+    }
+
+    /// Rotates the image clockwise around its center by the specified angle
+    /// (in radians).
+    #[inline]
+    #[must_use]
+    pub fn with_rotation(mut self, rotation_radians: f32) -> Self
+    {
+        self.rotation_radians = rotation_radians;
+        self
+    }
+
+    /// Tints the image by multiplying each color component in the image
+    /// pixels by the corresponding component in the specified color.
+    #[inline]
+    #[must_use]
+    pub fn with_tint(mut self, tint: Color) -> Self
+    {
+        self.tint = tint;
+        self
+    }
+
+    /// Returns the location of the center of the image.
+    #[inline]
+    pub fn center(&self) -> Vec2
+    {
+        self.center
+    }
+
+    /// Returns the tint color of the image.
+    #[inline]
+    pub fn tint(&self) -> Color
+    {
+        self.tint
+    }
+
+    /// Returns the rotation of the image clockwise around its center, in
+    /// radians.
+    #[inline]
+    pub(crate) fn rotation_radians(&self) -> f32
+    {
+        self.rotation_radians
+    }
+
+    /// Returns the size of the drawn part of an image of the specified size,
+    /// after scaling.
+    #[inline]
+    pub(crate) fn scaled_size(&self, image_size: Vec2) -> Vec2
+    {
+        let size = match &self.source_rect {
+            None => image_size,
+            Some(source_rect) => source_rect.size()
+        };
+
+        size * self.scale
+    }
+
+    /// Returns the corners of an image of the specified size, in clockwise
+    /// order starting from the top left of the image.
+    #[inline]
+    pub(crate) fn corners(&self, image_size: Vec2) -> [Vec2; 4]
+    {
+        let half_size = self.scaled_size(image_size) / 2.0;
+        let (sin, cos) = self.rotation_radians.sin_cos();
+
+        let corner = |x: f32, y: f32| {
+            self.center + Vec2::new(x * cos - y * sin, x * sin + y * cos)
+        };
+
+        [
+            corner(-half_size.x, -half_size.y),
+            corner(half_size.x, -half_size.y),
+            corner(half_size.x, half_size.y),
+            corner(-half_size.x, half_size.y)
+        ]
+    }
+
+    /// Returns the normalized texture coordinates for each of the corners
+    /// returned by [ImageInstance::corners].
+    #[inline]
+    pub(crate) fn texture_coords(&self, image_size: Vec2) -> [Vec2; 4]
+    {
+        let rect = self.texture_rect(image_size);
+
+        [
+            *rect.top_left(),
+            rect.top_right(),
+            *rect.bottom_right(),
+            rect.bottom_left()
+        ]
+    }
+
+    /// Returns the drawn part of an image of the specified size, in
+    /// normalized texture coordinates.
+    #[inline]
+    pub(crate) fn texture_rect(&self, image_size: Vec2) -> Rect
+    {
+        match &self.source_rect {
+            None => Rectangle::from_tuples((0.0, 0.0), (1.0, 1.0)),
+            Some(source_rect) => Rectangle::new(
+                Vec2::new(
+                    source_rect.top_left().x / image_size.x,
+                    source_rect.top_left().y / image_size.y
+                ),
+                Vec2::new(
+                    source_rect.bottom_right().x / image_size.x,
+                    source_rect.bottom_right().y / image_size.y
+                )
+            )
+        }
+    }
+}
+
+/// A collection of sprites, each of which is an [ImageInstance] of a
+/// particular image, which can be drawn together using
+/// [crate::Graphics2D::draw_sprite_batch].
+///
+/// When drawn, the sprites are grouped by image, so that all the sprites using
+/// the same image (such as a sprite sheet or texture atlas) are submitted to
+/// the GPU together. Sprites using the same image are drawn in the order they
+/// were added, but sprites using different images may be drawn out of order.
+#[derive(Debug, Clone, Default)]
+pub struct SpriteBatch
+{
+    sprites: Vec<(ImageHandle, ImageInstance)>
+}
+
+impl SpriteBatch
+{
+    /// Creates a new, empty sprite batch.
+    #[inline]
+    pub fn new() -> Self
+    {
+        SpriteBatch::default()
+    }
+
+    /// Adds a sprite to the batch.
+    #[inline]
+    pub fn push(&mut self, image: &ImageHandle, sprite: ImageInstance)
+    {
+        self.sprites.push((image.clone(), sprite));
+    }
+
+    /// Removes all the sprites from the batch, so that it can be reused.
+    #[inline]
+    pub fn clear(&mut self)
+    {
+        self.sprites.clear();
+    }
+
+    /// Returns the number of sprites in the batch.
+    #[inline]
+    pub fn len(&self) -> usize
+    {
+        self.sprites.len()
+    }
+
+    /// Returns `true` if the batch contains no sprites.
+    #[inline]
+    pub fn is_empty(&self) -> bool
+    {
+        self.sprites.is_empty()
+    }
+
+    /// Returns the sprites grouped by image, with the groups ordered by the
+    /// first appearance of each image.
+    pub(crate) fn grouped_by_image(&self) -> Vec<(&ImageHandle, Vec<ImageInstance>)>
+    {
+        let mut groups: Vec<(&ImageHandle, Vec<ImageInstance>)> = Vec::new();
+        let mut group_indices = HashMap::new();
+
+        for (image, sprite) in &self.sprites {
+            let index = *group_indices.entry(image).or_insert_with(|| {
+                groups.push((image, Vec::new()));
+                groups.len() - 1
+            });
+
+            groups[index].1.push(sprite.clone());
+        }
+
+        groups
+    }
+}
+
+/// An offscreen surface which can be drawn to using
+/// [crate::Graphics2D::draw_to_target], and then drawn like any other image
+/// using the handle returned by [RenderTarget::image].
+///
+/// The contents of the target are preserved between frames, so it can be
+/// used to cache the result of expensive drawing operations.
+///
+/// Note: this handle can only be used in the graphics context in which it was
+/// created.
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub struct RenderTarget
+{
+    pub(crate) image: ImageHandle,
+    pub(crate) framebuffer: GLFramebuffer,
+    pub(crate) smoothing_mode: ImageSmoothingMode
+}
+
+impl RenderTarget
+{
+    /// Returns the size of the render target in pixels.
+    pub fn size(&self) -> &UVec2
+    {
+        self.image.size()
+    }
+
+    /// Returns a handle which can be used to draw the contents of this render
+    /// target, for example using [crate::Graphics2D::draw_image].
+    pub fn image(&self) -> &ImageHandle
+    {
+        &self.image
+    }
+}
+
 /// `ImageSmoothingMode` defines how images are rendered when the pixels of the
 /// source image don't align perfectly with the pixels of the screen. This could
 /// be because the image is a different size, or because it is rendered at a
@@ -66,7 +560,36 @@ pub enum ImageSmoothingMode
     /// nearest pixels in the source image. This produces a smoother result
     /// than `NearestNeighbor`, but in cases where the image is intended to
     /// be pixel-aligned it may cause unnecessary blurriness.
-    Linear
+    Linear,
+
+    /// Smaller versions of the image (mipmaps) are generated when it is
+    /// created, and the pixel drawn on the screen is blended from the two
+    /// versions closest in size to the size at which it is drawn. This
+    /// avoids the shimmering and aliasing which `Linear` can produce when an
+    /// image is drawn at a much smaller size than the original, at the cost
+    /// of using a third more GPU memory.
+    Trilinear
+}
+
+/// `ImageWrapMode` defines how an image is drawn when the image coordinates
+/// are outside the range `0.0` to `1.0`, for example when using
+/// [crate::Graphics2D::draw_rectangle_image_subset_tinted].
+///
+/// Note: wrapping applies to the whole texture, so for images in an
+/// [ImageAtlas], the neighboring images will be drawn instead.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum ImageWrapMode
+{
+    /// The pixels at the edge of the image are extended indefinitely. This is
+    /// the default.
+    ClampToEdge,
+
+    /// The image is repeated, so that it tiles seamlessly.
+    Repeat,
+
+    /// The image is repeated, with every other copy mirrored, so that the
+    /// edges of adjacent copies match.
+    MirroredRepeat
 }
 
 /// Supported image formats.
@@ -103,6 +626,186 @@ pub enum ImageFileFormat
     Farbfeld
 }
 
+#[cfg(any(feature = "image-loading", doc, doctest))]
+impl ImageFileFormat
+{
+    pub(crate) fn to_image_format(self) -> image::ImageFormat
+    {
+        match self {
+            ImageFileFormat::PNG => image::ImageFormat::Png,
+            ImageFileFormat::JPEG => image::ImageFormat::Jpeg,
+            ImageFileFormat::GIF => image::ImageFormat::Gif,
+            ImageFileFormat::BMP => image::ImageFormat::Bmp,
+            ImageFileFormat::ICO => image::ImageFormat::Ico,
+            ImageFileFormat::TIFF => image::ImageFormat::Tiff,
+            ImageFileFormat::WebP => image::ImageFormat::WebP,
+            ImageFileFormat::AVIF => image::ImageFormat::Avif,
+            ImageFileFormat::PNM => image::ImageFormat::Pnm,
+            ImageFileFormat::DDS => image::ImageFormat::Dds,
+            ImageFileFormat::TGA => image::ImageFormat::Tga,
+            ImageFileFormat::Farbfeld => image::ImageFormat::Farbfeld
+        }
+    }
+}
+
+/// A sequence of frames loaded from an animated image file, for example an
+/// animated GIF or PNG.
+///
+/// The frames are kept in memory after decoding, and each one is uploaded to
+/// the GPU the first time it's drawn. Use
+/// [crate::Graphics2D::animated_image_frame] to get an [ImageHandle] for the
+/// frame which should be displayed at a given point in the animation.
+///
+/// Note: once frames have been uploaded, this image can only be used in the
+/// graphics context in which they were uploaded.
+#[cfg(any(feature = "image-loading", doc, doctest))]
+pub struct AnimatedImage
+{
+    size: UVec2,
+    smoothing_mode: ImageSmoothingMode,
+    frames: Vec<AnimatedImageFrame>,
+    duration: Duration
+}
+
+#[cfg(any(feature = "image-loading", doc, doctest))]
+struct AnimatedImageFrame
+{
+    delay: Duration,
+    pixels: AnimatedImageFramePixels
+}
+
+#[cfg(any(feature = "image-loading", doc, doctest))]
+enum AnimatedImageFramePixels
+{
+    Decoded(Vec<u8>),
+    Uploaded(ImageHandle)
+}
+
+#[cfg(any(feature = "image-loading", doc, doctest))]
+impl AnimatedImage
+{
+    /// Creates an animation from a list of RGBA frames, each of which is
+    /// shown for the specified delay. All frames must have the specified
+    /// size.
+    pub(crate) fn new(
+        size: UVec2,
+        smoothing_mode: ImageSmoothingMode,
+        frames: Vec<(Vec<u8>, Duration)>
+    ) -> Self
+    {
+        let frames: Vec<AnimatedImageFrame> = frames
+            .into_iter()
+            .map(|(data, delay)| AnimatedImageFrame {
+                delay,
+                pixels: AnimatedImageFramePixels::Decoded(data)
+            })
+            .collect();
+
+        let duration = frames.iter().map(|frame| frame.delay).sum();
+
+        AnimatedImage {
+            size,
+            smoothing_mode,
+            frames,
+            duration
+        }
+    }
+
+    /// Returns the size of each frame in pixels.
+    #[inline]
+    #[must_use]
+    pub fn size(&self) -> UVec2
+    {
+        self.size
+    }
+
+    /// Returns the number of frames in the animation.
+    #[inline]
+    #[must_use]
+    pub fn frame_count(&self) -> usize
+    {
+        self.frames.len()
+    }
+
+    /// Returns how long the specified frame is shown for, or `None` if the
+    /// index is out of range.
+    #[inline]
+    #[must_use]
+    pub fn frame_delay(&self, index: usize) -> Option<Duration>
+    {
+        self.frames.get(index).map(|frame| frame.delay)
+    }
+
+    /// Returns the total length of one loop of the animation.
+    #[inline]
+    #[must_use]
+    pub fn duration(&self) -> Duration
+    {
+        self.duration
+    }
+
+    /// Returns the index of the frame which is shown once the specified
+    /// amount of time has passed since the start of the animation. The
+    /// animation loops forever.
+    #[must_use]
+    pub fn frame_index_at(&self, elapsed: Duration) -> usize
+    {
+        if self.duration.is_zero() {
+            return 0;
+        }
+
+        let mut remaining =
+            Duration::from_nanos((elapsed.as_nanos() % self.duration.as_nanos()) as u64);
+
+        for (index, frame) in self.frames.iter().enumerate() {
+            if remaining < frame.delay {
+                return index;
+            }
+
+            remaining -= frame.delay;
+        }
+
+        self.frames.len() - 1
+    }
+
+    /// Returns the handle for the specified frame, using `upload` to create
+    /// it if the frame hasn't been uploaded yet.
+    pub(crate) fn frame_handle<F>(
+        &mut self,
+        index: usize,
+        upload: F
+    ) -> Result<ImageHandle, BacktraceError<ErrorMessage>>
+    where
+        F: FnOnce(
+            UVec2,
+            ImageSmoothingMode,
+            &[u8]
+        ) -> Result<ImageHandle, BacktraceError<ErrorMessage>>
+    {
+        let frame = match self.frames.get_mut(index) {
+            None => {
+                return Err(ErrorMessage::msg(format!(
+                    "Frame index {} out of range (frame count {})",
+                    index,
+                    self.frames.len()
+                )))
+            }
+            Some(frame) => frame
+        };
+
+        let handle = match &frame.pixels {
+            AnimatedImageFramePixels::Uploaded(handle) => return Ok(handle.clone()),
+            AnimatedImageFramePixels::Decoded(data) => {
+                upload(self.size, self.smoothing_mode, data)?
+            }
+        };
+
+        frame.pixels = AnimatedImageFramePixels::Uploaded(handle.clone());
+
+        Ok(handle)
+    }
+}
+
 /// A type to represent some raw pixel data, with an associated width and height
 /// in pixels.
 #[derive(Clone)]
@@ -151,4 +854,182 @@ impl RawBitmapData
     {
         self.data
     }
+
+    /// Encodes the pixel data in the specified file format, and writes it to
+    /// the specified path. If the file already exists, it is overwritten.
+    ///
+    /// Not all formats support encoding. In particular, `WebP`, `AVIF`, and
+    /// `DDS` files cannot be saved.
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn save_to_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+        format: ImageFileFormat
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        let color_type = match self.format {
+            ImageDataType::RGB => image::ColorType::Rgb8,
+            ImageDataType::RGBA => image::ColorType::Rgba8
+        };
+
+        image::save_buffer_with_format(
+            path.as_ref(),
+            &self.data,
+            self.size.x,
+            self.size.y,
+            color_type,
+            format.to_image_format()
+        )
+        .context(format!("Failed to save image to '{:?}'", path.as_ref()))
+    }
+}
+
+/// An image which is being loaded in the background, returned by
+/// [crate::Graphics2D::create_image_from_file_path_async].
+///
+/// The image file is decoded on a worker thread, and then uploaded to the GPU
+/// at the start of the next frame drawn after decoding completes. Use
+/// [PendingImage::poll] to check whether the image is ready.
+#[cfg(any(feature = "image-loading", doc, doctest))]
+#[derive(Clone)]
+pub struct PendingImage
+{
+    pub(crate) result: PendingImageResult
+}
+
+/// The result of loading a [PendingImage], which is `None` while the image
+/// is still loading.
+#[cfg(any(feature = "image-loading", doc, doctest))]
+pub(crate) type PendingImageResult =
+    Rc<RefCell<Option<Result<ImageHandle, BacktraceError<ErrorMessage>>>>>;
+
+#[cfg(any(feature = "image-loading", doc, doctest))]
+impl PendingImage
+{
+    /// Returns `None` if the image is still loading. Otherwise, returns the
+    /// loaded image, or the error which caused loading to fail.
+    ///
+    /// The returned [ImageHandle] is valid only for the graphics context in
+    /// which loading was started.
+    #[must_use]
+    pub fn poll(&self) -> Option<Result<ImageHandle, BacktraceError<ErrorMessage>>>
+    {
+        self.result.borrow().clone()
+    }
+
+    /// Returns true if the image is still loading.
+    #[must_use]
+    pub fn is_loading(&self) -> bool
+    {
+        self.result.borrow().is_none()
+    }
+}
+
+/// A capture of the render window which has been started, but not yet read
+/// back from the GPU. This is returned by [crate::Graphics2D::capture_async],
+/// and the pixels can be retrieved using [crate::Graphics2D::finish_capture].
+pub struct PendingCapture
+{
+    pub(crate) capture: GLPendingCapture
+}
+
+#[cfg(test)]
+mod test
+{
+    use super::*;
+
+    #[test]
+    pub fn test_image_atlas_builder()
+    {
+        let mut builder = ImageAtlasBuilder::new((8, 4));
+
+        assert_eq!(
+            Ok(0),
+            builder
+                .add_image_from_raw_pixels(ImageDataType::RGB, (2, 2), &[10; 12])
+                .map_err(|_| ())
+        );
+
+        assert_eq!(
+            Ok(1),
+            builder
+                .add_image_from_raw_pixels(ImageDataType::RGBA, (2, 2), &[20; 16])
+                .map_err(|_| ())
+        );
+
+        assert_eq!(2, builder.len());
+
+        // Each image is surrounded by a transparent one-pixel border
+        let pixel = |x: usize, y: usize| &builder.pixels()[(y * 8 + x) * 4..][..4];
+
+        assert_eq!(&[0, 0, 0, 0], pixel(0, 0));
+        assert_eq!(&[10, 10, 10, 255], pixel(1, 1));
+        assert_eq!(&[10, 10, 10, 255], pixel(2, 2));
+        assert_eq!(&[0, 0, 0, 0], pixel(3, 3));
+        assert_eq!(&[20, 20, 20, 20], pixel(5, 1));
+        assert_eq!(&[20, 20, 20, 20], pixel(6, 2));
+
+        assert!(builder
+            .add_image_from_raw_pixels(ImageDataType::RGB, (2, 2), &[0; 11])
+            .is_err());
+
+        assert!(builder
+            .add_image_from_raw_pixels(ImageDataType::RGB, (4, 4), &[0; 48])
+            .is_err());
+
+        assert_eq!(2, builder.len());
+    }
+
+    #[cfg(feature = "image-loading")]
+    #[test]
+    pub fn test_animated_image_frame_index()
+    {
+        let image = AnimatedImage::new(
+            UVec2::new(1, 1),
+            ImageSmoothingMode::NearestNeighbor,
+            vec![
+                (vec![0; 4], Duration::from_millis(100)),
+                (vec![0; 4], Duration::from_millis(0)),
+                (vec![0; 4], Duration::from_millis(50)),
+            ]
+        );
+
+        assert_eq!(3, image.frame_count());
+        assert_eq!(Duration::from_millis(150), image.duration());
+        assert_eq!(Some(Duration::from_millis(50)), image.frame_delay(2));
+        assert_eq!(None, image.frame_delay(3));
+
+        assert_eq!(0, image.frame_index_at(Duration::ZERO));
+        assert_eq!(0, image.frame_index_at(Duration::from_millis(99)));
+        assert_eq!(2, image.frame_index_at(Duration::from_millis(100)));
+        assert_eq!(2, image.frame_index_at(Duration::from_millis(149)));
+
+        // The animation loops
+        assert_eq!(0, image.frame_index_at(Duration::from_millis(150)));
+        assert_eq!(2, image.frame_index_at(Duration::from_millis(1020)));
+    }
+
+    #[cfg(feature = "image-loading")]
+    #[test]
+    pub fn test_raw_bitmap_data_save_to_file()
+    {
+        let path = std::env::temp_dir().join("speedy2d_test_save_to_file.png");
+
+        let data = RawBitmapData::new(
+            vec![255, 0, 0, 255, 0, 0, 255, 128],
+            (2, 1),
+            ImageDataType::RGBA
+        );
+
+        data.save_to_file(&path, ImageFileFormat::PNG).unwrap();
+
+        let loaded = image::open(&path).unwrap().into_rgba8();
+
+        assert_eq!((2, 1), loaded.dimensions());
+        assert_eq!(data.data(), &loaded.into_raw());
+
+        assert!(data.save_to_file(&path, ImageFileFormat::DDS).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
 }
@@ -21,8 +21,8 @@
 //!
 //!  - The simplest Rust API for creating a window, rendering graphics/text, and
 //!    handling input
-//!  - Compatible with any device supporting OpenGL 2.0+ or WebGL 2.0. Support
-//!    for OpenGL ES 2.0+ is planned.
+//!  - Compatible with any device supporting OpenGL 2.0+, OpenGL ES 2.0+, or
+//!    WebGL 1.0+.
 //!  - Very fast
 //!
 //! Supports Windows, Mac, Linux, and WebGL. Support for Android and iOS is in
@@ -32,6 +32,21 @@
 //! context. If you'd like to handle this yourself, and use Speedy2D only for
 //! rendering, you can disable the `windowing` feature.
 //!
+//! Gamepad input is available on Windows, Mac, and Linux by enabling the
+//! `gamepad` feature. On Linux, this requires `libudev` to be installed.
+//!
+//! Fonts installed on the system can be loaded by enabling the `system-fonts`
+//! feature, and using [font::Font::new_from_system].
+//!
+//! Enabling the `text-shaping` feature applies ligatures, contextual forms,
+//! and mark positioning when laying out text, which is required for scripts
+//! such as Arabic and Devanagari.
+//!
+//! Enabling the `serde` feature allows [color::Color], [dimen::Vector2], and
+//! the [shape::Rectangle], [shape::RoundedRectangle], and [shape::Polygon]
+//! shapes to be serialized and deserialized using
+//! [serde](https://crates.io/crates/serde).
+//!
 //! # Useful Links
 //!
 //! * [Source repository](https://github.com/QuantumBadger/Speedy2D)
@@ -119,7 +134,7 @@
 //!     fn on_draw(&mut self, helper: &mut WindowHelper, graphics: &mut Graphics2D)
 //!     {
 //!         graphics.clear_screen(Color::from_rgb(0.8, 0.9, 1.0));
-//!         graphics.draw_circle((100.0, 100.0), 75.0, Color::BLUE);
+//!         graphics.draw_circle(((100.0, 100.0), 75.0), Color::BLUE);
 //!
 //!         // Request that we draw another frame once this one has finished
 //!         helper.request_redraw();
@@ -178,10 +193,23 @@
 //! # }.unwrap();
 //! renderer.draw_frame(|graphics| {
 //!     graphics.clear_screen(Color::WHITE);
-//!     graphics.draw_circle((100.0, 100.0), 75.0, Color::BLUE);
+//!     graphics.draw_circle(((100.0, 100.0), 75.0), Color::BLUE);
 //! });
 //! ```
 //!
+//! Alternatively, if you already have a window from another library (such as
+//! SDL2 or tao) which supports `raw-window-handle`, Speedy2D can create the GL
+//! context for you using `GLRenderer::new_for_raw_window_handle()`. This
+//! requires the `windowing` feature.
+//!
+//! ## Alternative: Rendering with wgpu
+//!
+//! With the `wgpu` feature enabled, a `WGPURenderer` can be created from a
+//! [wgpu](https://wgpu.rs) device, which runs on Vulkan, Metal, or DirectX 12
+//! instead of OpenGL. It provides the same [Graphics2D] API, and draws each
+//! frame into a texture which can be copied to a surface. This renderer is
+//! not available on WebAssembly, where [WebCanvas] should be used instead.
+//!
 //! # Laying out text
 //!
 //! To render text, a font must be created. Call [font::Font::new()] with the
@@ -242,6 +270,26 @@
 //!     TextOptions::new().with_wrap_to_width(300.0, TextAlignment::Left));
 //! ```
 //!
+//! ## Mixed styles
+//!
+//! To lay out text containing several fonts, sizes, or colors as a single
+//! block, use [font::RichText]:
+//!
+//! ```rust,no_run
+//! # use speedy2d::color::Color;
+//! # use speedy2d::font::{Font, TextOptions};
+//! # let font = Font::new(&[]).unwrap();
+//! use speedy2d::font::{RichText, TextSpan};
+//!
+//! let brown = Color::from_rgb(0.6, 0.3, 0.1);
+//!
+//! let block = RichText::new()
+//!     .with_span(TextSpan::new("The quick ", &font, 32.0))
+//!     .with_span(TextSpan::new("brown", &font, 32.0).with_color(brown))
+//!     .with_span(TextSpan::new(" fox", &font, 32.0))
+//!     .layout(TextOptions::new());
+//! ```
+//!
 //! # Loading images
 //!
 //! Image files (in formats such as PNG, JPG, and BMP) can be loaded using the
@@ -298,26 +346,61 @@
 use std::fmt::{Display, Formatter};
 #[cfg(any(doc, doctest, all(target_arch = "wasm32", feature = "windowing")))]
 use std::marker::PhantomData;
+#[cfg(all(feature = "wgpu", not(target_arch = "wasm32")))]
+use std::ops::{Deref, DerefMut};
 use std::rc::Rc;
 
+#[cfg(all(feature = "wgpu", not(target_arch = "wasm32")))]
+pub use wgpu;
 #[cfg(any(feature = "image-loading", doc, doctest))]
 use {
-    crate::image::ImageFileFormat,
+    crate::image::{AnimatedImage, ImageFileFormat, PendingImage},
     std::io::{BufRead, Seek},
-    std::path::Path
+    std::path::Path,
+    std::time::Duration
 };
 
-use crate::color::Color;
-use crate::dimen::{UVec2, Vec2};
+use crate::color::{Color, Gradient};
+use crate::dimen::{Matrix2D, UVec2, Vec2};
 use crate::error::{BacktraceError, ErrorMessage};
-use crate::font::FormattedTextBlock;
+use crate::font::{Font, FormattedTextBlock, TextShadow};
 use crate::glbackend::GLBackend;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::glbackend::GLBackendGlow;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::glbackend_software::GLBackendSoftware;
+#[cfg(all(feature = "wgpu", not(target_arch = "wasm32")))]
+use crate::glbackend_wgpu::GLBackendWgpu;
+#[cfg(all(feature = "windowing", not(target_arch = "wasm32")))]
+use crate::glwindow::GLWindowContext;
 use crate::glwrapper::{GLContextManager, GLVersion};
-use crate::image::{ImageDataType, ImageHandle, ImageSmoothingMode, RawBitmapData};
+use crate::image::{
+    ImageAtlas,
+    ImageAtlasBuilder,
+    ImageDataType,
+    ImageHandle,
+    ImageInstance,
+    ImageSmoothingMode,
+    ImageWrapMode,
+    PendingCapture,
+    RawBitmapData,
+    RenderTarget,
+    SpriteBatch
+};
 use crate::renderer2d::Renderer2D;
-use crate::shape::{Polygon, Rect, Rectangle, RoundedRectangle};
+use crate::shape::{
+    Circle,
+    ClipShape,
+    Ellipse,
+    GeometryHandle,
+    Insets,
+    Polygon,
+    Rect,
+    Rectangle,
+    RotatedRectangle,
+    RoundedRectangle,
+    StrokeOptions
+};
 #[cfg(target_arch = "wasm32")]
 use crate::web::WebCanvasElement;
 #[cfg(any(doc, doctest, feature = "windowing"))]
@@ -369,6 +452,10 @@ pub mod time;
 #[cfg(any(doc, doctest, feature = "windowing"))]
 pub mod window;
 
+/// Types relating to gamepad input. This requires the `gamepad` feature.
+#[cfg(any(doc, doctest, all(feature = "gamepad", not(target_arch = "wasm32"))))]
+pub mod gamepad;
+
 #[cfg(all(
     feature = "windowing",
     not(target_arch = "wasm32"),
@@ -387,6 +474,12 @@ mod web;
 
 mod font_cache;
 mod glbackend;
+#[cfg(not(target_arch = "wasm32"))]
+mod glbackend_software;
+#[cfg(all(feature = "wgpu", not(target_arch = "wasm32")))]
+mod glbackend_wgpu;
+#[cfg(all(feature = "windowing", not(target_arch = "wasm32")))]
+mod glwindow;
 mod glwrapper;
 mod renderer2d;
 mod texture_packer;
@@ -434,6 +527,104 @@ impl Display for GLRendererCreationError
     }
 }
 
+/// The anti-aliasing technique used by a [GLRenderer] to smooth the edges of
+/// shapes.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum Antialiasing
+{
+    /// No anti-aliasing is performed by Speedy2D. Note that the GL context
+    /// itself may still be multisampled.
+    None,
+    /// Multisample anti-aliasing, with 2 samples per pixel.
+    Msaa2,
+    /// Multisample anti-aliasing, with 4 samples per pixel.
+    Msaa4,
+    /// Multisample anti-aliasing, with 8 samples per pixel.
+    Msaa8,
+    /// Multisample anti-aliasing, with 16 samples per pixel.
+    Msaa16
+}
+
+impl Antialiasing
+{
+    fn samples(&self) -> u32
+    {
+        match self {
+            Antialiasing::None => 0,
+            Antialiasing::Msaa2 => 2,
+            Antialiasing::Msaa4 => 4,
+            Antialiasing::Msaa8 => 8,
+            Antialiasing::Msaa16 => 16
+        }
+    }
+}
+
+/// Determines how the colors of new drawing operations are combined with the
+/// existing contents of the window (or render target). Set using
+/// [Graphics2D::set_blend_mode].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum BlendMode
+{
+    /// Standard alpha blending, where new pixels are drawn over the existing
+    /// contents according to their opacity. This is the default.
+    Alpha,
+    /// The new colors are added to the existing contents, which brightens
+    /// them. Useful for particles, glows, and lighting effects.
+    Additive,
+    /// The existing contents are multiplied by the new colors, which darkens
+    /// them. Useful for shadows and tinting.
+    Multiply,
+    /// The inverse of the existing contents and the new colors are multiplied,
+    /// which brightens the existing contents without exceeding white.
+    Screen
+}
+
+/// Options used during the creation of a [GLRenderer].
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub struct GLRendererOptions
+{
+    antialiasing: Antialiasing
+}
+
+impl GLRendererOptions
+{
+    /// Instantiates a new `GLRendererOptions` structure with the default
+    /// options.
+    #[must_use]
+    pub fn new() -> Self
+    {
+        GLRendererOptions {
+            antialiasing: Antialiasing::None
+        }
+    }
+
+    /// Sets the anti-aliasing technique used by the renderer. This is
+    /// independent of any multisampling configured for the GL context itself.
+    ///
+    /// When multisample anti-aliasing is enabled, the renderer draws to an
+    /// offscreen multisampled framebuffer, which is copied to the default
+    /// framebuffer at the end of each frame. If the GL implementation supports
+    /// fewer samples than requested, the maximum supported number of samples
+    /// is used instead.
+    ///
+    /// The default is `Antialiasing::None`.
+    #[inline]
+    #[must_use]
+    pub fn with_antialiasing(mut self, antialiasing: Antialiasing) -> Self
+    {
+        self.antialiasing = antialiasing;
+        self
+    }
+}
+
+impl Default for GLRendererOptions
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
 /// A graphics renderer using an OpenGL backend.
 ///
 /// Note: There is no need to use this struct if you are letting Speedy2D create
@@ -441,7 +632,9 @@ impl Display for GLRendererCreationError
 pub struct GLRenderer
 {
     context: GLContextManager,
-    renderer: Graphics2D
+    renderer: Graphics2D,
+    #[cfg(all(feature = "windowing", not(target_arch = "wasm32")))]
+    window_context: Option<GLWindowContext>
 }
 
 impl GLRenderer
@@ -452,6 +645,11 @@ impl GLRenderer
     /// the initial viewport size, however this can be changed later using
     /// [GLRenderer:: set_viewport_size_pixels()].
     ///
+    /// Both desktop OpenGL (version 2.0 or later) and OpenGL ES (version 2.0 or
+    /// later) contexts are supported, and the type of context is detected
+    /// automatically. On OpenGL ES 2.0, multisample anti-aliasing is
+    /// unavailable, and [Graphics2D::capture_async] captures immediately.
+    ///
     /// Note: This function must not be called if you are letting Speedy2D
     /// create a window for you.
     ///
@@ -467,6 +665,33 @@ impl GLRenderer
         viewport_size_pixels: V,
         loader_function: F
     ) -> Result<Self, BacktraceError<GLRendererCreationError>>
+    where
+        V: Into<UVec2>,
+        F: FnMut(&str) -> *const std::os::raw::c_void
+    {
+        Self::new_for_gl_context_with_options(
+            viewport_size_pixels,
+            loader_function,
+            GLRendererOptions::default()
+        )
+    }
+
+    /// Creates a `GLRenderer` with the specified OpenGL loader function and
+    /// options. See [GLRenderer::new_for_gl_context()] for details.
+    ///
+    /// # Safety
+    ///
+    /// While a `GLRenderer` object is active, you must not make any changes to
+    /// the active GL context. Doing so may lead to undefined behavior,
+    /// which is why this function is marked `unsafe`. It is strongly
+    /// advised not to use any other OpenGL libraries in the same thread
+    /// as `GLRenderer`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub unsafe fn new_for_gl_context_with_options<V, F>(
+        viewport_size_pixels: V,
+        loader_function: F,
+        options: GLRendererOptions
+    ) -> Result<Self, BacktraceError<GLRendererCreationError>>
     where
         V: Into<UVec2>,
         F: FnMut(&str) -> *const std::os::raw::c_void
@@ -474,10 +699,126 @@ impl GLRenderer
         let backend =
             GLBackendGlow::new(glow::Context::from_loader_function(loader_function));
 
+        let gl_version = GLVersion::detect(&backend);
+
         Self::new_with_gl_backend(
             viewport_size_pixels,
             Rc::new(backend),
-            GLVersion::OpenGL2_0
+            gl_version,
+            options
+        )
+    }
+
+    /// Creates a `GLRenderer` which draws to an existing window, such as one
+    /// created using SDL2, tao, or a platform's native API. An OpenGL (or
+    /// OpenGL ES) context is created for the window automatically.
+    ///
+    /// [GLRenderer::draw_frame()] presents each frame by swapping the window's
+    /// buffers. When the window is resized, call
+    /// [GLRenderer::set_viewport_size_pixels()] with the new size.
+    ///
+    /// # Safety
+    ///
+    /// The window must remain valid for the lifetime of the `GLRenderer`. As
+    /// with [GLRenderer::new_for_gl_context()], you must not make any changes
+    /// to the GL context while the `GLRenderer` is active.
+    #[cfg(all(feature = "windowing", not(target_arch = "wasm32")))]
+    pub unsafe fn new_for_raw_window_handle<W, V>(
+        window: &W,
+        viewport_size_pixels: V
+    ) -> Result<Self, BacktraceError<GLRendererCreationError>>
+    where
+        W: raw_window_handle::HasRawWindowHandle + raw_window_handle::HasRawDisplayHandle,
+        V: Into<UVec2>
+    {
+        Self::new_for_raw_window_handle_with_options(
+            window,
+            viewport_size_pixels,
+            GLRendererOptions::default()
+        )
+    }
+
+    /// Creates a `GLRenderer` which draws to an existing window, using the
+    /// specified options. See [GLRenderer::new_for_raw_window_handle()] for
+    /// details.
+    ///
+    /// # Safety
+    ///
+    /// The window must remain valid for the lifetime of the `GLRenderer`. As
+    /// with [GLRenderer::new_for_gl_context()], you must not make any changes
+    /// to the GL context while the `GLRenderer` is active.
+    #[cfg(all(feature = "windowing", not(target_arch = "wasm32")))]
+    pub unsafe fn new_for_raw_window_handle_with_options<W, V>(
+        window: &W,
+        viewport_size_pixels: V,
+        options: GLRendererOptions
+    ) -> Result<Self, BacktraceError<GLRendererCreationError>>
+    where
+        W: raw_window_handle::HasRawWindowHandle + raw_window_handle::HasRawDisplayHandle,
+        V: Into<UVec2>
+    {
+        let viewport_size_pixels = viewport_size_pixels.into();
+
+        let (window_context, backend) = GLWindowContext::new(
+            window.raw_display_handle(),
+            window.raw_window_handle(),
+            viewport_size_pixels
+        )
+        .map_err(|err| {
+            GLRendererCreationError::msg_with_cause(
+                "Failed to create GL context for window",
+                err
+            )
+        })?;
+
+        let gl_version = GLVersion::detect(&backend);
+
+        let mut renderer = Self::new_with_gl_backend(
+            viewport_size_pixels,
+            Rc::new(backend),
+            gl_version,
+            options
+        )?;
+
+        renderer.window_context = Some(window_context);
+
+        Ok(renderer)
+    }
+
+    /// Creates a `GLRenderer` which draws into a pixel buffer using the CPU,
+    /// rather than using an OpenGL context. No GPU or graphics drivers are
+    /// required, which makes this useful for generating images on headless
+    /// servers.
+    ///
+    /// The rendered frame can be retrieved using [Graphics2D::capture()]. The
+    /// output closely matches the OpenGL renderer, but is not guaranteed to
+    /// be pixel-identical. Multisample anti-aliasing is not supported.
+    ///
+    /// ```rust
+    /// use speedy2d::color::Color;
+    /// use speedy2d::image::ImageDataType;
+    /// use speedy2d::GLRenderer;
+    ///
+    /// let mut renderer = GLRenderer::new_software((640, 480)).unwrap();
+    ///
+    /// let frame = renderer.draw_frame(|graphics| {
+    ///     graphics.clear_screen(Color::WHITE);
+    ///     graphics.draw_circle(((320.0, 240.0), 100.0), Color::BLUE);
+    ///     graphics.capture(ImageDataType::RGBA)
+    /// });
+    ///
+    /// assert_eq!(frame.data().len(), 640 * 480 * 4);
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new_software<V: Into<UVec2>>(
+        viewport_size_pixels: V
+    ) -> Result<Self, BacktraceError<GLRendererCreationError>>
+    {
+        Self::new_with_gl_backend(
+            viewport_size_pixels,
+            Rc::new(GLBackendSoftware::new()),
+            GLVersion::OpenGL2_0,
+            GLRendererOptions::default()
         )
     }
 
@@ -487,6 +828,10 @@ impl GLRenderer
     /// The parameter `viewport_size_pixels` should be set to
     /// the initial canvas size, however this can be changed later using
     /// [GLRenderer:: set_viewport_size_pixels()].
+    ///
+    /// A WebGL2 context is used if the browser supports it, falling back to
+    /// WebGL1 otherwise. With WebGL2, vertex array objects are used, and
+    /// image instances and triangle meshes are drawn with fewer draw calls.
     #[cfg(any(doc, doctest, target_arch = "wasm32"))]
     pub fn new_for_web_canvas_by_id<V, S>(
         viewport_size_pixels: V,
@@ -500,13 +845,14 @@ impl GLRenderer
             .map_err(|err| {
                 GLRendererCreationError::msg_with_cause("Failed to get canvas", err)
             })?
-            .get_webgl2_context(viewport_size_pixels)
+            .get_webgl_context(viewport_size_pixels)
     }
 
     fn new_with_gl_backend<V: Into<UVec2>>(
         viewport_size_pixels: V,
         gl_backend: Rc<dyn GLBackend>,
-        gl_version: GLVersion
+        gl_version: GLVersion,
+        options: GLRendererOptions
     ) -> Result<Self, BacktraceError<GLRendererCreationError>>
     {
         let viewport_size_pixels = viewport_size_pixels.into();
@@ -520,18 +866,33 @@ impl GLRenderer
             })?;
 
         let renderer = Graphics2D {
-            renderer: Renderer2D::new(&context, viewport_size_pixels).map_err(|err| {
+            renderer: Renderer2D::new(
+                &context,
+                viewport_size_pixels,
+                options.antialiasing.samples()
+            )
+            .map_err(|err| {
                 GLRendererCreationError::msg_with_cause("Renderer2D creation failed", err)
             })?
         };
 
-        Ok(GLRenderer { context, renderer })
+        Ok(GLRenderer {
+            context,
+            renderer,
+            #[cfg(all(feature = "windowing", not(target_arch = "wasm32")))]
+            window_context: None
+        })
     }
 
     /// Sets the renderer viewport to the specified pixel size, in response to a
     /// change in the window size.
     pub fn set_viewport_size_pixels(&mut self, viewport_size_pixels: UVec2)
     {
+        #[cfg(all(feature = "windowing", not(target_arch = "wasm32")))]
+        if let Some(window_context) = &self.window_context {
+            window_context.resize(viewport_size_pixels);
+        }
+
         self.renderer
             .renderer
             .set_viewport_size_pixels(viewport_size_pixels)
@@ -577,6 +938,31 @@ impl GLRenderer
             .create_image_from_file_path(data_type, smoothing_mode, path)
     }
 
+    /// Loads an image from the specified file path in the background, without
+    /// blocking the render thread.
+    ///
+    /// The file is decoded on a worker thread, and the image is uploaded to
+    /// the GPU at the start of the next call to [GLRenderer::draw_frame] after
+    /// decoding completes. Use [PendingImage::poll] to retrieve the image once
+    /// it's ready.
+    ///
+    /// On WebGL, threads are not available, so the file is decoded
+    /// immediately.
+    ///
+    /// If no `data_type` is provided, an attempt will be made to guess the file
+    /// format.
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn create_image_from_file_path_async<S: AsRef<Path>>(
+        &mut self,
+        data_type: Option<ImageFileFormat>,
+        smoothing_mode: ImageSmoothingMode,
+        path: S
+    ) -> PendingImage
+    {
+        self.renderer
+            .create_image_from_file_path_async(data_type, smoothing_mode, path)
+    }
+
     /// Loads an image from the provided encoded image file data.
     ///
     /// If no `data_type` is provided, an attempt will be made to guess the file
@@ -621,18 +1007,152 @@ impl GLRenderer
             .create_image_from_file_bytes(data_type, smoothing_mode, file_bytes)
     }
 
+    /// Loads an animated image from the specified file path. Animated GIF and
+    /// PNG (APNG) files are supported. Files in other formats are loaded as
+    /// an animation with a single frame.
+    ///
+    /// If no `data_type` is provided, an attempt will be made to guess the file
+    /// format.
+    ///
+    /// The frames are uploaded to the GPU when they are first drawn, using
+    /// [Graphics2D::animated_image_frame].
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn create_animated_image_from_file_path<S: AsRef<Path>>(
+        &mut self,
+        data_type: Option<ImageFileFormat>,
+        smoothing_mode: ImageSmoothingMode,
+        path: S
+    ) -> Result<AnimatedImage, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.create_animated_image_from_file_path(
+            data_type,
+            smoothing_mode,
+            path
+        )
+    }
+
+    /// Loads an animated image from the provided encoded image file data, in
+    /// the same way as [Self::create_animated_image_from_file_path].
+    ///
+    /// The data source must implement `std::io::BufRead` and `std::io::Seek`,
+    /// as for [Self::create_image_from_file_bytes].
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn create_animated_image_from_file_bytes<R: Seek + BufRead>(
+        &mut self,
+        data_type: Option<ImageFileFormat>,
+        smoothing_mode: ImageSmoothingMode,
+        file_bytes: R
+    ) -> Result<AnimatedImage, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.create_animated_image_from_file_bytes(
+            data_type,
+            smoothing_mode,
+            file_bytes
+        )
+    }
+
+    /// Rasterizes the provided SVG document into a new image of the specified
+    /// size in pixels. The SVG is scaled to fill the whole image, so the
+    /// size should usually have the same aspect ratio as the document.
+    ///
+    /// Text elements in the SVG are not rendered.
+    ///
+    /// This requires the `svg-loading` feature.
+    ///
+    /// The returned [ImageHandle] is valid only for the current graphics
+    /// context.
+    #[cfg(feature = "svg-loading")]
+    pub fn create_image_from_svg_bytes<S: Into<UVec2>>(
+        &mut self,
+        svg_bytes: &[u8],
+        size: S,
+        smoothing_mode: ImageSmoothingMode
+    ) -> Result<ImageHandle, BacktraceError<ErrorMessage>>
+    {
+        self.renderer
+            .create_image_from_svg_bytes(svg_bytes, size.into(), smoothing_mode)
+    }
+
+    /// Creates a new offscreen [RenderTarget] of the specified size in pixels.
+    /// The target is initially fully transparent.
+    ///
+    /// Use [Graphics2D::draw_to_target] to draw to the target, and
+    /// [RenderTarget::image] to draw its contents.
+    ///
+    /// The returned [RenderTarget] is valid only for the current graphics
+    /// context.
+    pub fn create_render_target<S: Into<UVec2>>(
+        &mut self,
+        size: S,
+        smoothing_mode: ImageSmoothingMode
+    ) -> Result<RenderTarget, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.create_render_target(size, smoothing_mode)
+    }
+
+    /// Uploads the images collected by an [ImageAtlasBuilder] to the GPU as a
+    /// single texture. The handles in the returned [ImageAtlas] can be used
+    /// with any of the normal image drawing functions.
+    ///
+    /// The returned [ImageAtlas] is valid only for the current graphics
+    /// context.
+    pub fn create_image_atlas(
+        &mut self,
+        builder: &ImageAtlasBuilder,
+        smoothing_mode: ImageSmoothingMode
+    ) -> Result<ImageAtlas, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.create_image_atlas(builder, smoothing_mode)
+    }
+
+    /// Sets how the specified image is drawn when the image coordinates are
+    /// outside the range `0.0` to `1.0`. By default, images use
+    /// [ImageWrapMode::ClampToEdge].
+    ///
+    /// The mode applies to every handle for the same image, and to any
+    /// drawing which takes place after this call.
+    pub fn set_image_wrap_mode(&mut self, image: &ImageHandle, wrap_mode: ImageWrapMode)
+    {
+        self.renderer.set_image_wrap_mode(image, wrap_mode);
+    }
+
+    /// Uploads the triangles making up the specified polygon to the GPU, so
+    /// that it can be drawn repeatedly using [Graphics2D::draw_geometry]
+    /// without the cost of sending the vertices again each frame.
+    ///
+    /// The returned [GeometryHandle] is valid only for the current graphics
+    /// context.
+    pub fn create_geometry(
+        &mut self,
+        polygon: &Polygon
+    ) -> Result<GeometryHandle, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.create_geometry(polygon)
+    }
+
     /// Starts the process of drawing a frame. A `Graphics2D` object will be
     /// provided to the callback. When the callback returns, the internal
     /// render queue will be flushed.
     ///
     /// Note: if calling this method, you are responsible for swapping the
-    /// window context buffers if necessary.
+    /// window context buffers if necessary, unless the renderer was created
+    /// using `GLRenderer::new_for_raw_window_handle()`.
     #[inline]
     pub fn draw_frame<F: FnOnce(&mut Graphics2D) -> R, R>(&mut self, callback: F) -> R
     {
         self.renderer.set_clip(None);
+        self.renderer.set_blend_mode(BlendMode::Alpha);
+        self.renderer.renderer.reset_transform();
+        #[cfg(any(feature = "image-loading", doc, doctest))]
+        self.renderer.renderer.upload_loaded_images();
         let result = callback(&mut self.renderer);
         self.renderer.renderer.finish_frame();
+
+        #[cfg(all(feature = "windowing", not(target_arch = "wasm32")))]
+        if let Some(window_context) = &self.window_context {
+            window_context.swap_buffers();
+        }
+
         result
     }
 }
@@ -645,64 +1165,278 @@ impl Drop for GLRenderer
     }
 }
 
-/// A `Graphics2D` object allows you to draw shapes, images, and text to the
-/// screen.
+/// A graphics renderer using a [wgpu](https://wgpu.rs) backend, which runs on
+/// Vulkan, Metal, or DirectX 12, depending on the platform.
 ///
-/// An instance is provided in the [window::WindowHandler::on_draw] callback.
+/// Each frame is drawn into a texture owned by the renderer, which has the
+/// same size as the viewport. To display the frame, copy it to a surface
+/// texture using [WGPURenderer::copy_frame_to_texture()].
 ///
-/// If you are managing the GL context yourself, you must invoke
-/// [GLRenderer::draw_frame] to obtain an instance.
-pub struct Graphics2D
+/// `WGPURenderer` dereferences to [GLRenderer], so the same methods are
+/// available for creating images and render targets. Internally, the OpenGL
+/// calls made by the renderer are translated to wgpu commands.
+///
+/// ```rust,no_run
+/// use speedy2d::color::Color;
+/// use speedy2d::{wgpu, WGPURenderer};
+/// # let (device, queue): (wgpu::Device, wgpu::Queue) = unimplemented!();
+/// # let surface_texture: wgpu::SurfaceTexture = unimplemented!();
+///
+/// let mut renderer = WGPURenderer::new(
+///     &device,
+///     &queue,
+///     wgpu::TextureFormat::Bgra8Unorm,
+///     (640, 480)
+/// )
+/// .unwrap();
+///
+/// renderer.draw_frame(|graphics| {
+///     graphics.clear_screen(Color::WHITE);
+///     graphics.draw_circle(((100.0, 100.0), 75.0), Color::BLUE);
+/// });
+///
+/// renderer.copy_frame_to_texture(&surface_texture.texture);
+/// surface_texture.present();
+/// ```
+#[cfg(all(feature = "wgpu", not(target_arch = "wasm32")))]
+pub struct WGPURenderer
 {
-    renderer: Renderer2D
+    renderer: GLRenderer,
+    backend: Rc<GLBackendWgpu>,
+    queue: wgpu::Queue,
+    device: wgpu::Device
 }
 
-impl Graphics2D
+#[cfg(all(feature = "wgpu", not(target_arch = "wasm32")))]
+impl WGPURenderer
 {
-    /// Creates a new [ImageHandle] from the specified raw pixel data.
-    ///
-    /// The data provided in the `data` parameter must be in the format
-    /// specified by `data_type`.
-    ///
-    /// The returned [ImageHandle] is valid only for the current graphics
-    /// context.
-    pub fn create_image_from_raw_pixels<S: Into<UVec2>>(
-        &mut self,
-        data_type: ImageDataType,
-        smoothing_mode: ImageSmoothingMode,
-        size: S,
-        data: &[u8]
-    ) -> Result<ImageHandle, BacktraceError<ErrorMessage>>
+    /// Creates a `WGPURenderer` which draws using the specified wgpu device
+    /// and queue. Frames are drawn into a texture with the specified
+    /// `format`, which must be `Rgba8Unorm` or `Bgra8Unorm`, or an sRGB
+    /// variant of those (in which case the non-sRGB variant is used, as
+    /// colors are not gamma-corrected when drawing).
+    ///
+    /// `viewport_size_pixels` should be set to the initial viewport size,
+    /// however this can be changed later using
+    /// [GLRenderer::set_viewport_size_pixels()].
+    pub fn new<V: Into<UVec2>>(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        format: wgpu::TextureFormat,
+        viewport_size_pixels: V
+    ) -> Result<Self, BacktraceError<GLRendererCreationError>>
     {
-        self.renderer.create_image_from_raw_pixels(
-            data_type,
-            smoothing_mode,
-            size.into(),
-            data
+        Self::new_with_options(
+            device,
+            queue,
+            format,
+            viewport_size_pixels,
+            GLRendererOptions::default()
         )
     }
 
-    /// Loads an image from the specified file path.
-    ///
-    /// If no `data_type` is provided, an attempt will be made to guess the file
-    /// format.
-    ///
-    /// For a list of supported image types, see [image::ImageFileFormat].
+    /// Creates a `WGPURenderer` with the specified options. See
+    /// [WGPURenderer::new()] for details.
     ///
-    /// The returned [ImageHandle] is valid only for the current graphics
-    /// context.
-    #[cfg(any(feature = "image-loading", doc, doctest))]
-    pub fn create_image_from_file_path<S: AsRef<Path>>(
-        &mut self,
-        data_type: Option<ImageFileFormat>,
-        smoothing_mode: ImageSmoothingMode,
-        path: S
+    /// When multisample anti-aliasing is enabled, 4x multisampling is used,
+    /// as this is the only sample count supported on all wgpu backends.
+    pub fn new_with_options<V: Into<UVec2>>(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        format: wgpu::TextureFormat,
+        viewport_size_pixels: V,
+        options: GLRendererOptions
+    ) -> Result<Self, BacktraceError<GLRendererCreationError>>
+    {
+        let format = format.remove_srgb_suffix();
+
+        if !matches!(
+            format,
+            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Bgra8Unorm
+        ) {
+            return Err(GLRendererCreationError::msg(format!(
+                "Unsupported texture format {format:?}"
+            )));
+        }
+
+        let backend = Rc::new(GLBackendWgpu::new(device, queue, format));
+
+        let renderer = GLRenderer::new_with_gl_backend(
+            viewport_size_pixels,
+            backend.clone(),
+            GLVersion::OpenGL2_0,
+            options
+        )?;
+
+        backend.flush();
+
+        Ok(WGPURenderer {
+            renderer,
+            backend,
+            queue: queue.clone(),
+            device: device.clone()
+        })
+    }
+
+    /// Starts the process of drawing a frame. A `Graphics2D` object will be
+    /// provided to the callback. When the callback returns, the internal
+    /// render queue will be flushed, and the frame will be submitted to the
+    /// wgpu queue.
+    pub fn draw_frame<F: FnOnce(&mut Graphics2D) -> R, R>(&mut self, callback: F) -> R
+    {
+        let result = self.renderer.draw_frame(callback);
+        self.backend.flush();
+        result
+    }
+
+    /// Returns the texture containing the most recently drawn frame. This has
+    /// the same size as the viewport, and is replaced with a new texture when
+    /// the viewport is resized.
+    ///
+    /// The texture may be used as a copy source or a texture binding.
+    #[must_use]
+    pub fn texture(&self) -> Option<wgpu::Texture>
+    {
+        self.backend.default_texture()
+    }
+
+    /// Copies the most recently drawn frame to the top left corner of the
+    /// specified texture, for example a texture obtained using
+    /// `wgpu::Surface::get_current_texture()`.
+    ///
+    /// The texture must have the `COPY_DST` usage, and its format must match
+    /// the format passed to [WGPURenderer::new()], ignoring any sRGB suffix.
+    /// If the texture is smaller than the frame, the frame is cropped.
+    pub fn copy_frame_to_texture(&self, target: &wgpu::Texture)
+    {
+        let frame = match self.texture() {
+            None => return,
+            Some(frame) => frame
+        };
+
+        let mut encoder =
+            self.device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("speedy2d_copy_frame")
+                });
+
+        encoder.copy_texture_to_texture(
+            frame.as_image_copy(),
+            target.as_image_copy(),
+            wgpu::Extent3d {
+                width: frame.width().min(target.width()),
+                height: frame.height().min(target.height()),
+                depth_or_array_layers: 1
+            }
+        );
+
+        self.queue.submit(std::iter::once(encoder.finish()));
+    }
+}
+
+#[cfg(all(feature = "wgpu", not(target_arch = "wasm32")))]
+impl Deref for WGPURenderer
+{
+    type Target = GLRenderer;
+
+    fn deref(&self) -> &Self::Target
+    {
+        &self.renderer
+    }
+}
+
+#[cfg(all(feature = "wgpu", not(target_arch = "wasm32")))]
+impl DerefMut for WGPURenderer
+{
+    fn deref_mut(&mut self) -> &mut Self::Target
+    {
+        &mut self.renderer
+    }
+}
+
+/// A `Graphics2D` object allows you to draw shapes, images, and text to the
+/// screen.
+///
+/// An instance is provided in the [window::WindowHandler::on_draw] callback.
+///
+/// If you are managing the GL context yourself, you must invoke
+/// [GLRenderer::draw_frame] to obtain an instance.
+pub struct Graphics2D
+{
+    renderer: Renderer2D
+}
+
+impl Graphics2D
+{
+    /// Creates a new [ImageHandle] from the specified raw pixel data.
+    ///
+    /// The data provided in the `data` parameter must be in the format
+    /// specified by `data_type`.
+    ///
+    /// The returned [ImageHandle] is valid only for the current graphics
+    /// context.
+    pub fn create_image_from_raw_pixels<S: Into<UVec2>>(
+        &mut self,
+        data_type: ImageDataType,
+        smoothing_mode: ImageSmoothingMode,
+        size: S,
+        data: &[u8]
+    ) -> Result<ImageHandle, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.create_image_from_raw_pixels(
+            data_type,
+            smoothing_mode,
+            size.into(),
+            data
+        )
+    }
+
+    /// Loads an image from the specified file path.
+    ///
+    /// If no `data_type` is provided, an attempt will be made to guess the file
+    /// format.
+    ///
+    /// For a list of supported image types, see [image::ImageFileFormat].
+    ///
+    /// The returned [ImageHandle] is valid only for the current graphics
+    /// context.
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn create_image_from_file_path<S: AsRef<Path>>(
+        &mut self,
+        data_type: Option<ImageFileFormat>,
+        smoothing_mode: ImageSmoothingMode,
+        path: S
     ) -> Result<ImageHandle, BacktraceError<ErrorMessage>>
     {
         self.renderer
             .create_image_from_file_path(data_type, smoothing_mode, path)
     }
 
+    /// Loads an image from the specified file path in the background, without
+    /// blocking the render thread.
+    ///
+    /// The file is decoded on a worker thread, and the image is uploaded to
+    /// the GPU at the start of the next call to [GLRenderer::draw_frame] after
+    /// decoding completes. Use [PendingImage::poll] to retrieve the image once
+    /// it's ready.
+    ///
+    /// On WebGL, threads are not available, so the file is decoded
+    /// immediately.
+    ///
+    /// If no `data_type` is provided, an attempt will be made to guess the file
+    /// format.
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn create_image_from_file_path_async<S: AsRef<Path>>(
+        &mut self,
+        data_type: Option<ImageFileFormat>,
+        smoothing_mode: ImageSmoothingMode,
+        path: S
+    ) -> PendingImage
+    {
+        self.renderer
+            .create_image_from_file_path_async(data_type, smoothing_mode, path)
+    }
+
     /// Loads an image from the provided encoded image file data.
     ///
     /// If no `data_type` is provided, an attempt will be made to guess the file
@@ -749,6 +1483,167 @@ impl Graphics2D
             .create_image_from_file_bytes(data_type, smoothing_mode, file_bytes)
     }
 
+    /// Loads an animated image from the specified file path. Animated GIF and
+    /// PNG (APNG) files are supported. Files in other formats are loaded as
+    /// an animation with a single frame.
+    ///
+    /// If no `data_type` is provided, an attempt will be made to guess the file
+    /// format.
+    ///
+    /// The frames are uploaded to the GPU when they are first drawn, using
+    /// [Graphics2D::animated_image_frame].
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn create_animated_image_from_file_path<S: AsRef<Path>>(
+        &mut self,
+        data_type: Option<ImageFileFormat>,
+        smoothing_mode: ImageSmoothingMode,
+        path: S
+    ) -> Result<AnimatedImage, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.create_animated_image_from_file_path(
+            data_type,
+            smoothing_mode,
+            path
+        )
+    }
+
+    /// Loads an animated image from the provided encoded image file data, in
+    /// the same way as [Self::create_animated_image_from_file_path].
+    ///
+    /// The data source must implement `std::io::BufRead` and `std::io::Seek`,
+    /// as for [Self::create_image_from_file_bytes].
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn create_animated_image_from_file_bytes<R: Seek + BufRead>(
+        &mut self,
+        data_type: Option<ImageFileFormat>,
+        smoothing_mode: ImageSmoothingMode,
+        file_bytes: R
+    ) -> Result<AnimatedImage, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.create_animated_image_from_file_bytes(
+            data_type,
+            smoothing_mode,
+            file_bytes
+        )
+    }
+
+    /// Rasterizes the provided SVG document into a new image of the specified
+    /// size in pixels. The SVG is scaled to fill the whole image, so the
+    /// size should usually have the same aspect ratio as the document.
+    ///
+    /// Text elements in the SVG are not rendered.
+    ///
+    /// This requires the `svg-loading` feature.
+    ///
+    /// The returned [ImageHandle] is valid only for the current graphics
+    /// context.
+    #[cfg(feature = "svg-loading")]
+    pub fn create_image_from_svg_bytes<S: Into<UVec2>>(
+        &mut self,
+        svg_bytes: &[u8],
+        size: S,
+        smoothing_mode: ImageSmoothingMode
+    ) -> Result<ImageHandle, BacktraceError<ErrorMessage>>
+    {
+        self.renderer
+            .create_image_from_svg_bytes(svg_bytes, size.into(), smoothing_mode)
+    }
+
+    /// Returns the handle of the frame which should be shown once `elapsed`
+    /// time has passed since the start of the animation. The animation loops
+    /// forever. The frame is uploaded to the GPU if this is the first time
+    /// it has been requested.
+    ///
+    /// The returned handle can be drawn using any of the normal image
+    /// drawing functions, such as [Graphics2D::draw_image].
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn animated_image_frame(
+        &mut self,
+        image: &mut AnimatedImage,
+        elapsed: Duration
+    ) -> Result<ImageHandle, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.animated_image_frame(image, elapsed)
+    }
+
+    /// Creates a new offscreen [RenderTarget] of the specified size in pixels.
+    /// The target is initially fully transparent.
+    ///
+    /// Use [Graphics2D::draw_to_target] to draw to the target, and
+    /// [RenderTarget::image] to draw its contents.
+    ///
+    /// The returned [RenderTarget] is valid only for the current graphics
+    /// context.
+    pub fn create_render_target<S: Into<UVec2>>(
+        &mut self,
+        size: S,
+        smoothing_mode: ImageSmoothingMode
+    ) -> Result<RenderTarget, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.create_render_target(size, smoothing_mode)
+    }
+
+    /// Uploads the images collected by an [ImageAtlasBuilder] to the GPU as a
+    /// single texture. The handles in the returned [ImageAtlas] can be used
+    /// with any of the normal image drawing functions.
+    ///
+    /// The returned [ImageAtlas] is valid only for the current graphics
+    /// context.
+    pub fn create_image_atlas(
+        &mut self,
+        builder: &ImageAtlasBuilder,
+        smoothing_mode: ImageSmoothingMode
+    ) -> Result<ImageAtlas, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.create_image_atlas(builder, smoothing_mode)
+    }
+
+    /// Sets how the specified image is drawn when the image coordinates are
+    /// outside the range `0.0` to `1.0`. By default, images use
+    /// [ImageWrapMode::ClampToEdge].
+    ///
+    /// The mode applies to every handle for the same image, and to any
+    /// drawing which takes place after this call.
+    pub fn set_image_wrap_mode(&mut self, image: &ImageHandle, wrap_mode: ImageWrapMode)
+    {
+        self.renderer.set_image_wrap_mode(image, wrap_mode);
+    }
+
+    /// Uploads the triangles making up the specified polygon to the GPU, so
+    /// that it can be drawn repeatedly using [Graphics2D::draw_geometry]
+    /// without the cost of sending the vertices again each frame.
+    ///
+    /// The returned [GeometryHandle] is valid only for the current graphics
+    /// context.
+    pub fn create_geometry(
+        &mut self,
+        polygon: &Polygon
+    ) -> Result<GeometryHandle, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.create_geometry(polygon)
+    }
+
+    /// Redirects all drawing operations performed in the callback to the
+    /// specified [RenderTarget], rather than the screen.
+    ///
+    /// Inside the callback, the origin is at the top left of the target, and
+    /// the target has the same orientation as the screen. The clip is reset
+    /// when drawing to the target starts, and the previous clip is restored
+    /// afterwards.
+    ///
+    /// The target must not be drawn to itself inside the callback.
+    pub fn draw_to_target<F: FnOnce(&mut Graphics2D) -> R, R>(
+        &mut self,
+        target: &RenderTarget,
+        callback: F
+    ) -> R
+    {
+        let previous_state = self.renderer.begin_render_target(target);
+        let result = callback(self);
+        self.renderer.end_render_target(previous_state);
+        result
+    }
+
     /// Fills the screen with the specified color.
     pub fn clear_screen(&mut self, color: Color)
     {
@@ -780,9 +1675,142 @@ impl Graphics2D
         text: &FormattedTextBlock
     )
     {
+        let position = position.into();
+        self.draw_text_backgrounds(position, text, None);
         self.renderer.draw_text(position, color, text);
     }
 
+    /// Draws the provided block of text at the specified position, with a
+    /// shadow behind it.
+    ///
+    /// The shadow is drawn behind all the glyphs in the block, and can be
+    /// blurred to give it a soft edge. See [crate::font::TextShadow].
+    ///
+    /// See the documentation for [Graphics2D::draw_text] for more details.
+    pub fn draw_text_with_shadow<V: Into<Vec2>>(
+        &mut self,
+        position: V,
+        color: Color,
+        shadow: &TextShadow,
+        text: &FormattedTextBlock
+    )
+    {
+        let position = position.into();
+        self.draw_text_backgrounds(position, text, None);
+        self.renderer
+            .draw_text_with_shadow(position, color, shadow, text);
+    }
+
+    /// Renders the provided block of text into a new image, which can then be
+    /// drawn using [Graphics2D::draw_image]. This is useful for text which
+    /// rarely changes, as the image is drawn as a single rectangle, rather
+    /// than one for each glyph.
+    ///
+    /// The image is larger than the text block by `padding` pixels on each
+    /// side, to leave room for any parts of the glyphs which extend outside
+    /// the block. Drawing the image at `position - padding` gives the same
+    /// result as calling [Graphics2D::draw_text] at `position`, as long as
+    /// `position` is a whole number of pixels.
+    ///
+    /// Glyphs are drawn in `color`, unless they have their own color (see
+    /// [crate::font::TextSpan::with_color]).
+    ///
+    /// The returned [ImageHandle] is valid only for the current graphics
+    /// context.
+    pub fn rasterize_text_to_image(
+        &mut self,
+        text: &FormattedTextBlock,
+        color: Color,
+        padding: u32
+    ) -> Result<ImageHandle, BacktraceError<ErrorMessage>>
+    {
+        self.renderer.rasterize_text_to_image(text, color, padding)
+    }
+
+    /// Renders the glyphs for each character in `charset` using the specified
+    /// font and scale, and uploads them to the GPU. This allows the work to
+    /// be done in advance (for example, during a loading screen), rather than
+    /// causing a delay the first time each glyph is drawn. This is most
+    /// noticeable at large font sizes.
+    ///
+    /// Glyphs are prepared for text which is drawn at an integer pixel
+    /// position using [Graphics2D::draw_text], without any shadow or
+    /// outline. Prepared glyphs may be discarded if they're not drawn, and
+    /// space is needed for other glyphs.
+    ///
+    /// ```rust,no_run
+    /// # use speedy2d::font::Font;
+    /// # use speedy2d::Graphics2D;
+    /// # fn draw(graphics: &mut Graphics2D, font: &Font) {
+    /// graphics.precache_glyphs(font, 64.0, "0123456789:");
+    /// # }
+    /// ```
+    pub fn precache_glyphs(&mut self, font: &Font, scale: f32, charset: &str)
+    {
+        self.renderer.precache_glyphs(font, scale, charset);
+    }
+
+    /// Draws the provided block of text along a path, for example to create
+    /// circular labels.
+    ///
+    /// The start of the path is used as the origin of the text's first
+    /// baseline. Each glyph is centered on the point of the path at the
+    /// same horizontal distance, and rotated to follow the direction of the
+    /// path at that point. Further lines of text are offset to the right
+    /// of the direction of travel. Glyphs that don't fit on the path are
+    /// not drawn, so nothing is drawn if the path has fewer than two
+    /// distinct points.
+    ///
+    /// To draw text along a curve, build a [crate::shape::Path] and use
+    /// [crate::shape::Path::to_polylines] to obtain its points. To center
+    /// the text on the path, lay it out with a wrap width equal to the
+    /// length of the path, and [crate::font::TextAlignment::Center].
+    ///
+    /// Background colors set using
+    /// [crate::font::TextSpan::with_background_color] aren't drawn.
+    ///
+    /// See the documentation for [Graphics2D::draw_text] for more details.
+    pub fn draw_text_along_path(
+        &mut self,
+        path: &[Vec2],
+        color: Color,
+        text: &FormattedTextBlock
+    )
+    {
+        self.renderer.draw_text_along_path(path, color, text);
+    }
+
+    /// Draws the provided block of text at the specified position, surrounded
+    /// by an outline. This keeps the text readable when it's drawn over an
+    /// arbitrary background.
+    ///
+    /// The outline extends `outline_width` pixels beyond the edge of each
+    /// glyph. Outlines are drawn behind all the glyphs in the block, so they
+    /// don't overlap neighbouring characters. Glyphs with a color set using
+    /// [crate::font::TextSpan::with_color] are filled with that color
+    /// instead of `fill_color`.
+    ///
+    /// See the documentation for [Graphics2D::draw_text] for more details.
+    pub fn draw_text_outlined<V: Into<Vec2>>(
+        &mut self,
+        position: V,
+        fill_color: Color,
+        outline_color: Color,
+        outline_width: f32,
+        text: &FormattedTextBlock
+    )
+    {
+        let position = position.into();
+        self.draw_text_backgrounds(position, text, None);
+        self.renderer.draw_text_outlined(
+            position,
+            fill_color,
+            outline_color,
+            outline_width,
+            text
+        );
+    }
+
     /// Draws the provided block of text at the specified position, cropped to
     /// the specified window. Characters outside this window will not be
     /// rendered. Characters partially inside the window will be cropped.
@@ -799,10 +1827,36 @@ impl Graphics2D
         text: &FormattedTextBlock
     )
     {
+        let position = position.into();
+        self.draw_text_backgrounds(position, text, Some(&crop_window));
         self.renderer
             .draw_text_cropped(position, crop_window, color, text);
     }
 
+    /// Draws the background colors set using
+    /// [crate::font::TextSpan::with_background_color], behind the text.
+    fn draw_text_backgrounds(
+        &mut self,
+        position: Vec2,
+        text: &FormattedTextBlock,
+        crop_window: Option<&Rect>
+    )
+    {
+        for (rect, color) in text.background_rects() {
+            let rect = rect.with_offset(position);
+
+            let rect = match crop_window {
+                None => rect,
+                Some(crop_window) => match rect.intersect(crop_window) {
+                    None => continue,
+                    Some(rect) => rect
+                }
+            };
+
+            self.draw_rectangle(rect, color);
+        }
+    }
+
     /// Draws a polygon with a single color, with the specified offset in
     /// pixels.
     pub fn draw_polygon<V: Into<Vec2>>(
@@ -815,6 +1869,139 @@ impl Graphics2D
         self.renderer.draw_polygon(polygon, offset, color)
     }
 
+    /// Draws geometry previously uploaded using [Graphics2D::create_geometry],
+    /// with a single color, and with the specified offset in pixels. The
+    /// current transform is applied to the geometry.
+    ///
+    /// This is faster than [Graphics2D::draw_polygon] for complex polygons
+    /// which are drawn many times, as the vertices are already on the GPU.
+    pub fn draw_geometry<V: Into<Vec2>>(
+        &mut self,
+        geometry: &GeometryHandle,
+        offset: V,
+        color: Color
+    )
+    {
+        self.renderer.draw_geometry(geometry, offset, color)
+    }
+
+    /// Draws the outline of a polygon with a single color, with the specified
+    /// offset and line thickness in pixels. Corners are drawn with miter
+    /// joins. The outlines of any holes in the polygon are also drawn.
+    ///
+    /// The outline is triangulated each time this is called. If the same
+    /// outline is drawn repeatedly, consider storing the result of
+    /// [Polygon::to_outline_polygon] and drawing it with
+    /// [Graphics2D::draw_polygon] instead.
+    pub fn draw_polygon_outline<V: Into<Vec2>>(
+        &mut self,
+        polygon: &Polygon,
+        offset: V,
+        thickness: f32,
+        color: Color
+    )
+    {
+        self.draw_polygon(
+            &polygon.to_outline_polygon(&StrokeOptions::new(thickness)),
+            offset,
+            color
+        );
+    }
+
+    /// Draws a polygon filled with the specified gradient, with the specified
+    /// offset in pixels. The gradient's coordinates are relative to the
+    /// position where the polygon is drawn (after the offset is applied).
+    pub fn draw_polygon_gradient<V: Into<Vec2>>(
+        &mut self,
+        polygon: &Polygon,
+        offset: V,
+        gradient: &Gradient
+    )
+    {
+        self.renderer
+            .draw_polygon_gradient(polygon, offset, gradient)
+    }
+
+    /// Fills the area enclosed by the specified path with a single color.
+    ///
+    /// The path is triangulated each time this is called. If the same path is
+    /// drawn repeatedly, consider storing the result of
+    /// [shape::Path::to_filled_polygon] and drawing it with
+    /// [Graphics2D::draw_polygon] instead.
+    pub fn draw_path_filled(&mut self, path: &shape::Path, color: Color)
+    {
+        self.draw_polygon(&path.to_filled_polygon(), Vec2::ZERO, color);
+    }
+
+    /// Fills the area enclosed by the specified path with a gradient.
+    pub fn draw_path_filled_gradient(&mut self, path: &shape::Path, gradient: &Gradient)
+    {
+        self.draw_polygon_gradient(&path.to_filled_polygon(), Vec2::ZERO, gradient);
+    }
+
+    /// Draws the outline of the specified path with a single color, and the
+    /// specified line thickness in pixels.
+    ///
+    /// The path is triangulated each time this is called. If the same path is
+    /// drawn repeatedly, consider storing the result of
+    /// [shape::Path::to_stroked_polygon] and drawing it with
+    /// [Graphics2D::draw_polygon] instead.
+    pub fn draw_path_stroked(&mut self, path: &shape::Path, thickness: f32, color: Color)
+    {
+        self.draw_polygon(&path.to_stroked_polygon(thickness), Vec2::ZERO, color);
+    }
+
+    /// Draws the outline of the specified path with a single color, using the
+    /// line thickness, join style, and cap style given in `options`.
+    pub fn draw_path_stroked_with_options(
+        &mut self,
+        path: &shape::Path,
+        options: &StrokeOptions,
+        color: Color
+    )
+    {
+        self.draw_polygon(
+            &path.to_stroked_polygon_with_options(options),
+            Vec2::ZERO,
+            color
+        );
+    }
+
+    /// Draws a series of connected straight lines through the specified
+    /// points, with the specified thickness in pixels. Corners are drawn with
+    /// miter joins, and the ends are cut off square at the first and last
+    /// points.
+    ///
+    /// Unlike drawing each segment separately using [Graphics2D::draw_line],
+    /// the corners contain no gaps, and no pixel is drawn more than once,
+    /// so the result is correct when using a transparent color.
+    pub fn draw_polyline<V: Into<Vec2> + Copy>(
+        &mut self,
+        points: &[V],
+        thickness: f32,
+        color: Color
+    )
+    {
+        self.draw_polyline_with_options(points, &StrokeOptions::new(thickness), color);
+    }
+
+    /// Draws a series of connected straight lines through the specified
+    /// points, using the line thickness, join style, and cap style given in
+    /// `options`.
+    pub fn draw_polyline_with_options<V: Into<Vec2> + Copy>(
+        &mut self,
+        points: &[V],
+        options: &StrokeOptions,
+        color: Color
+    )
+    {
+        let path = points
+            .iter()
+            .fold(shape::Path::new(), |path, point| path.line_to(*point));
+
+        self.draw_path_stroked_with_options(&path, options, color);
+    }
+
     /// Draws a triangle with the specified colors (one color for each corner).
     ///
     /// The vertex positions (and associated colors) must be provided in
@@ -870,6 +2057,65 @@ impl Graphics2D
         self.draw_triangle_three_color(vertex_positions_clockwise, [color, color, color]);
     }
 
+    /// Draws a mesh of triangles. Each vertex has a position, and a color
+    /// from the corresponding element of `vertex_colors`. Every three
+    /// elements of `indices` refer to the vertices making up one triangle.
+    ///
+    /// Nothing is drawn if `vertex_colors` is a different length to
+    /// `vertex_positions`, if the length of `indices` is not a multiple of
+    /// three, or if an index refers to a vertex which does not exist.
+    ///
+    /// On OpenGL 3.3, OpenGL ES 3.0, and WebGL 2.0, the mesh is drawn using a
+    /// 32-bit index buffer, so each vertex is only uploaded once.
+    pub fn draw_triangles(
+        &mut self,
+        vertex_positions: &[Vec2],
+        vertex_colors: &[Color],
+        indices: &[u32]
+    )
+    {
+        if !validate_mesh(vertex_positions.len(), &[vertex_colors.len()], indices) {
+            return;
+        }
+
+        self.renderer
+            .draw_mesh(vertex_positions, vertex_colors, None, indices);
+    }
+
+    /// Draws a mesh of triangles, textured using the specified image. Each
+    /// vertex has a position, a tint color, and a normalized image
+    /// coordinate, taken from the corresponding elements of the three
+    /// vertex slices. Every three elements of `indices` refer to the
+    /// vertices making up one triangle.
+    ///
+    /// See [Graphics2D::draw_triangle_image_tinted_three_color] for details
+    /// on the image coordinates and tinting, and [Graphics2D::draw_triangles]
+    /// for the conditions under which nothing is drawn.
+    pub fn draw_triangles_image_tinted(
+        &mut self,
+        vertex_positions: &[Vec2],
+        vertex_colors: &[Color],
+        image_coords_normalized: &[Vec2],
+        indices: &[u32],
+        image: &ImageHandle
+    )
+    {
+        if !validate_mesh(
+            vertex_positions.len(),
+            &[vertex_colors.len(), image_coords_normalized.len()],
+            indices
+        ) {
+            return;
+        }
+
+        self.renderer.draw_mesh(
+            vertex_positions,
+            vertex_colors,
+            Some((image_coords_normalized, image)),
+            indices
+        );
+    }
+
     /// Draws a quadrilateral with the specified colors (one color for each
     /// corner).
     ///
@@ -1012,26 +2258,318 @@ impl Graphics2D
     /// Draws an image at the specified location. The image will be
     /// scaled to fill the pixel coordinates in the provided rectangle.
     #[inline]
+    #[doc(alias = "draw_image_rect")]
     pub fn draw_rectangle_image(
         &mut self,
         rect: impl AsRef<Rectangle>,
-        image: &ImageHandle
+        image: &ImageHandle
+    )
+    {
+        self.draw_rectangle_image_tinted(rect, Color::WHITE, image);
+    }
+
+    /// Fills the specified rectangle with copies of an image, drawn at its
+    /// original size. The first copy is placed in the top left corner of the
+    /// rectangle, and the copies along the right and bottom edges are cropped
+    /// to fit.
+    ///
+    /// This works for any image, including images in an [ImageAtlas], and
+    /// doesn't depend on the [ImageWrapMode] of the image.
+    pub fn draw_image_tiled(&mut self, rect: impl AsRef<Rectangle>, image: &ImageHandle)
+    {
+        let rect = rect.as_ref();
+        let tile_size = image.size().into_f32();
+
+        if tile_size.x <= 0.0 || tile_size.y <= 0.0 {
+            return;
+        }
+
+        let mut y = rect.top_left().y;
+
+        while y < rect.bottom_right().y {
+            let height = tile_size.y.min(rect.bottom_right().y - y);
+            let mut x = rect.top_left().x;
+
+            while x < rect.bottom_right().x {
+                let width = tile_size.x.min(rect.bottom_right().x - x);
+
+                self.draw_rectangle_image_subset_tinted(
+                    Rectangle::new(Vec2::new(x, y), Vec2::new(x + width, y + height)),
+                    Color::WHITE,
+                    Rectangle::new(
+                        Vec2::ZERO,
+                        Vec2::new(width / tile_size.x, height / tile_size.y)
+                    ),
+                    image
+                );
+
+                x += tile_size.x;
+            }
+
+            y += tile_size.y;
+        }
+    }
+
+    /// Draws an image, tinted with the provided color, stretched to fill the
+    /// specified rectangle without distorting its borders. This is useful for
+    /// drawing UI panels and buttons of any size.
+    ///
+    /// The `insets` give the size of the border on each edge of the image, in
+    /// pixels. The corners of the image are drawn at their original size, the
+    /// edges are stretched in one direction, and the center is stretched in
+    /// both directions. If the rectangle is too small to fit the borders,
+    /// they are scaled down to fit.
+    ///
+    /// The tinting is performed by for each pixel by multiplying each color
+    /// component in the image pixel by the corresponding color component in
+    /// the `color` parameter.
+    pub fn draw_image_nine_slice_tinted(
+        &mut self,
+        rect: impl AsRef<Rectangle>,
+        insets: Insets,
+        color: Color,
+        image: &ImageHandle
+    )
+    {
+        let rect = rect.as_ref();
+        let image_size = image.size().into_f32();
+
+        let (dest_x, image_x) = nine_slice_edges(
+            rect.top_left().x,
+            rect.bottom_right().x,
+            insets.left(),
+            insets.right(),
+            image_size.x
+        );
+
+        let (dest_y, image_y) = nine_slice_edges(
+            rect.top_left().y,
+            rect.bottom_right().y,
+            insets.top(),
+            insets.bottom(),
+            image_size.y
+        );
+
+        for row in 0..3 {
+            for column in 0..3 {
+                let dest = Rectangle::new(
+                    Vec2::new(dest_x[column], dest_y[row]),
+                    Vec2::new(dest_x[column + 1], dest_y[row + 1])
+                );
+
+                if dest.width() <= 0.0 || dest.height() <= 0.0 {
+                    continue;
+                }
+
+                self.draw_rectangle_image_subset_tinted(
+                    dest,
+                    color,
+                    Rectangle::new(
+                        Vec2::new(image_x[column], image_y[row]),
+                        Vec2::new(image_x[column + 1], image_y[row + 1])
+                    ),
+                    image
+                );
+            }
+        }
+    }
+
+    /// Draws an image stretched to fill the specified rectangle without
+    /// distorting its borders.
+    ///
+    /// See [Graphics2D::draw_image_nine_slice_tinted] for more details.
+    #[inline]
+    pub fn draw_image_nine_slice(
+        &mut self,
+        rect: impl AsRef<Rectangle>,
+        insets: Insets,
+        image: &ImageHandle
+    )
+    {
+        self.draw_image_nine_slice_tinted(rect, insets, Color::WHITE, image);
+    }
+
+    /// Draws an image at the specified pixel location. The image will be
+    /// drawn at its original size with no scaling.
+    #[inline]
+    pub fn draw_image<P: Into<Vec2>>(&mut self, position: P, image: &ImageHandle)
+    {
+        self.draw_image_tinted(position, Color::WHITE, image);
+    }
+
+    /// Draws an image, tinted with the provided color, at the specified pixel
+    /// location. The image will be drawn at its original size with no
+    /// scaling.
+    ///
+    /// The tinting is performed by for each pixel by multiplying each color
+    /// component in the image pixel by the corresponding color component in
+    /// the `color` parameter. For example, an image can be faded out by
+    /// reducing the alpha component of a white tint.
+    #[inline]
+    pub fn draw_image_tinted<P: Into<Vec2>>(
+        &mut self,
+        position: P,
+        color: Color,
+        image: &ImageHandle
+    )
+    {
+        let position = position.into();
+
+        self.draw_rectangle_image_tinted(
+            Rectangle::new(position, position + image.size().into_f32()),
+            color,
+            image
+        );
+    }
+
+    /// Draws an image, tinted with the provided color, centered on the
+    /// specified pixel location. The image is scaled by `scale` (where `1.0`
+    /// is the original size), and then rotated clockwise by the specified
+    /// angle (in radians) around its center.
+    ///
+    /// The tinting is performed by for each pixel by multiplying each color
+    /// component in the image pixel by the corresponding color component in
+    /// the `color` parameter.
+    #[inline]
+    pub fn draw_image_rotated_tinted<P: Into<Vec2>>(
+        &mut self,
+        center: P,
+        color: Color,
+        image: &ImageHandle,
+        rotation_radians: f32,
+        scale: f32
+    )
+    {
+        let corners = ImageInstance::new(center)
+            .with_scale(scale)
+            .with_rotation(rotation_radians)
+            .corners(image.size().into_f32());
+
+        self.draw_quad_image_tinted_four_color(
+            corners,
+            [color, color, color, color],
+            [
+                Vec2::new(0.0, 0.0),
+                Vec2::new(1.0, 0.0),
+                Vec2::new(1.0, 1.0),
+                Vec2::new(0.0, 1.0)
+            ],
+            image
+        );
+    }
+
+    /// Draws many copies of the same image, each with its own position, scale,
+    /// rotation, and tint. This is well suited to particle effects.
+    ///
+    /// On OpenGL 3.3, OpenGL ES 3.0, and WebGL 2.0, the copies are drawn
+    /// using instanced rendering, so only the position, scale, rotation, and
+    /// tint of each copy are uploaded to the GPU. On older versions, and with
+    /// the software renderer, the vertices of each copy are generated on the
+    /// CPU instead, and drawn in a single batch along with any neighbouring
+    /// draws using the same image.
+    pub fn draw_image_instances(
+        &mut self,
+        image: &ImageHandle,
+        instances: &[ImageInstance]
+    )
+    {
+        self.renderer.draw_image_instances(image, instances);
+    }
+
+    /// Draws all the sprites in the specified [SpriteBatch]. The sprites are
+    /// grouped by image, and each group is submitted to the GPU together.
+    pub fn draw_sprite_batch(&mut self, batch: &SpriteBatch)
+    {
+        for (image, sprites) in batch.grouped_by_image() {
+            self.renderer.draw_image_instances(image, &sprites);
+        }
+    }
+
+    /// Draws an image centered on the specified pixel location. The image is
+    /// scaled by `scale` (where `1.0` is the original size), and then rotated
+    /// clockwise by the specified angle (in radians) around its center.
+    ///
+    /// To scale an image to fill a rectangle, see
+    /// [Graphics2D::draw_rectangle_image].
+    #[inline]
+    pub fn draw_image_rotated<P: Into<Vec2>>(
+        &mut self,
+        center: P,
+        image: &ImageHandle,
+        rotation_radians: f32,
+        scale: f32
+    )
+    {
+        self.draw_image_rotated_tinted(
+            center,
+            Color::WHITE,
+            image,
+            rotation_radians,
+            scale
+        );
+    }
+
+    /// Draws a rectangle with the specified colors (one color for each
+    /// corner). The colors are blended smoothly across the rectangle.
+    ///
+    /// The colors must be provided in clockwise order, starting from the top
+    /// left corner: top left, top right, bottom right, bottom left.
+    pub fn draw_rectangle_four_color(
+        &mut self,
+        rect: impl AsRef<Rectangle>,
+        colors_clockwise: [Color; 4]
+    )
+    {
+        let rect = rect.as_ref();
+
+        let corners = [
+            *rect.top_left(),
+            rect.top_right(),
+            *rect.bottom_right(),
+            rect.bottom_left()
+        ];
+
+        // Splitting the rectangle into four triangles around its center gives
+        // a closer approximation of bilinear blending than two triangles,
+        // which would leave a visible seam along one of the diagonals.
+        let center = (corners[0] + corners[2]) / 2.0;
+        let center_color = Color::from_rgba(
+            colors_clockwise.iter().map(|c| c.r()).sum::<f32>() / 4.0,
+            colors_clockwise.iter().map(|c| c.g()).sum::<f32>() / 4.0,
+            colors_clockwise.iter().map(|c| c.b()).sum::<f32>() / 4.0,
+            colors_clockwise.iter().map(|c| c.a()).sum::<f32>() / 4.0
+        );
+
+        for i in 0..4 {
+            let next = (i + 1) % 4;
+
+            self.draw_triangle_three_color(
+                [corners[i], corners[next], center],
+                [colors_clockwise[i], colors_clockwise[next], center_color]
+            );
+        }
+    }
+
+    /// Draws a rectangle filled with the specified gradient. The coordinates
+    /// of the rectangle and the gradient are specified in pixels.
+    pub fn draw_rectangle_gradient(
+        &mut self,
+        rect: impl AsRef<Rectangle>,
+        gradient: &Gradient
     )
     {
-        self.draw_rectangle_image_tinted(rect, Color::WHITE, image);
-    }
+        let rect = rect.as_ref();
 
-    /// Draws an image at the specified pixel location. The image will be
-    /// drawn at its original size with no scaling.
-    #[inline]
-    pub fn draw_image<P: Into<Vec2>>(&mut self, position: P, image: &ImageHandle)
-    {
-        let position = position.into();
+        let triangles = [
+            [*rect.top_left(), rect.top_right(), *rect.bottom_right()],
+            [*rect.bottom_right(), rect.bottom_left(), *rect.top_left()]
+        ];
 
-        self.draw_rectangle_image(
-            Rectangle::new(position, position + image.size().into_f32()),
-            image
-        );
+        for triangle in triangles.iter() {
+            gradient.split_triangle(triangle, &mut |positions, colors| {
+                self.renderer.draw_triangle_three_color(positions, colors)
+            });
+        }
     }
 
     /// Draws a single-color rectangle at the specified location. The
@@ -1054,6 +2592,9 @@ impl Graphics2D
 
     /// Draws a single-color rounded rectangle at the specified location. The
     /// coordinates of the rounded rectangle are specified in pixels.
+    ///
+    /// Each corner may have a different radius, including zero (see
+    /// [crate::shape::CornerRadii]).
     #[inline]
     pub fn draw_rounded_rectangle(
         &mut self,
@@ -1062,90 +2603,33 @@ impl Graphics2D
     )
     {
         let round_rect = round_rect.as_ref();
-
-        //create 3 rectangles (the middle one is taller)
-        //draw middle quad (the taller one)
-        self.draw_quad(
-            [
-                round_rect.top_left() + Vec2::new(round_rect.radius(), 0.0),
-                round_rect.top_right() + Vec2::new(-round_rect.radius(), 0.0),
-                round_rect.bottom_right() + Vec2::new(-round_rect.radius(), 0.0),
-                round_rect.bottom_left() + Vec2::new(round_rect.radius(), 0.0)
-            ],
-            color
-        );
-
-        //draw left quad
-        self.draw_quad(
-            [
-                round_rect.top_left() + Vec2::new(0.0, round_rect.radius()),
-                round_rect.top_left()
-                    + Vec2::new(round_rect.radius(), round_rect.radius()),
-                round_rect.bottom_left()
-                    + Vec2::new(round_rect.radius(), -round_rect.radius()),
-                round_rect.bottom_left() + Vec2::new(0.0, -round_rect.radius())
-            ],
-            color
-        );
-
-        //draw right quad
-        self.draw_quad(
-            [
-                round_rect.top_right() + Vec2::new(0.0, round_rect.radius()),
-                round_rect.top_right()
-                    + Vec2::new(-round_rect.radius(), round_rect.radius()),
-                round_rect.bottom_right()
-                    + Vec2::new(-round_rect.radius(), -round_rect.radius()),
-                round_rect.bottom_right() + Vec2::new(0.0, -round_rect.radius())
-            ],
-            color
-        );
-
-        //draw triangles
-        self.draw_triangle(
-            [
-                round_rect.top_left() + Vec2::new(round_rect.radius(), 0.0),
-                round_rect.top_left()
-                    + Vec2::new(round_rect.radius(), round_rect.radius()),
-                round_rect.top_left() + Vec2::new(0.0, round_rect.radius())
-            ],
-            color
-        );
-        self.draw_triangle(
-            [
-                round_rect.top_right() + Vec2::new(-round_rect.radius(), 0.0),
-                round_rect.top_right()
-                    + Vec2::new(-round_rect.radius(), round_rect.radius()),
-                round_rect.top_right() + Vec2::new(0.0, round_rect.radius())
-            ],
-            color
-        );
-        self.draw_triangle(
-            [
-                round_rect.bottom_left() + Vec2::new(round_rect.radius(), 0.0),
-                round_rect.bottom_left() + Vec2::new(0.0, -round_rect.radius()),
-                round_rect.bottom_left()
-                    + Vec2::new(round_rect.radius(), -round_rect.radius())
-            ],
-            color
-        );
-        self.draw_triangle(
-            [
-                round_rect.bottom_right() + Vec2::new(-round_rect.radius(), 0.0),
-                round_rect.bottom_right()
-                    + Vec2::new(-round_rect.radius(), -round_rect.radius()),
-                round_rect.bottom_right() + Vec2::new(0.0, -round_rect.radius())
-            ],
-            color
-        );
+        let radii = round_rect.radii();
+
+        let top_left = *round_rect.top_left();
+        let top_right = round_rect.top_right();
+        let bottom_right = *round_rect.bottom_right();
+        let bottom_left = round_rect.bottom_left();
+
+        //cut each corner off diagonally, leaving an octagon (some of whose
+        //vertices may coincide), and draw it as a fan of triangles
+        let octagon = [
+            top_left + Vec2::new(radii.top_left(), 0.0),
+            top_right + Vec2::new(-radii.top_right(), 0.0),
+            top_right + Vec2::new(0.0, radii.top_right()),
+            bottom_right + Vec2::new(0.0, -radii.bottom_right()),
+            bottom_right + Vec2::new(-radii.bottom_right(), 0.0),
+            bottom_left + Vec2::new(radii.bottom_left(), 0.0),
+            bottom_left + Vec2::new(0.0, -radii.bottom_left()),
+            top_left + Vec2::new(0.0, radii.top_left())
+        ];
+
+        for i in 1..octagon.len() - 1 {
+            self.draw_triangle([octagon[0], octagon[i], octagon[i + 1]], color);
+        }
 
         //draw top right circle
         self.draw_circle_section_triangular_three_color(
-            [
-                round_rect.top_right() + Vec2::new(-round_rect.radius(), 0.0),
-                round_rect.top_right(),
-                round_rect.top_right() + Vec2::new(0.0, round_rect.radius())
-            ],
+            [octagon[1], top_right, octagon[2]],
             [color; 3],
             [
                 Vec2::new(0.0, 1.0),
@@ -1156,11 +2640,7 @@ impl Graphics2D
 
         //draw top left circle
         self.draw_circle_section_triangular_three_color(
-            [
-                round_rect.top_left() + Vec2::new(0.0, round_rect.radius()),
-                *round_rect.top_left(),
-                round_rect.top_left() + Vec2::new(round_rect.radius(), 0.0)
-            ],
+            [octagon[7], top_left, octagon[0]],
             [color; 3],
             [
                 Vec2::new(-1.0, 0.0),
@@ -1171,11 +2651,7 @@ impl Graphics2D
 
         //draw bottom left circle
         self.draw_circle_section_triangular_three_color(
-            [
-                round_rect.bottom_left() + Vec2::new(round_rect.radius(), 0.0),
-                round_rect.bottom_left(),
-                round_rect.bottom_left() + Vec2::new(0.0, -round_rect.radius())
-            ],
+            [octagon[5], bottom_left, octagon[6]],
             [color; 3],
             [
                 Vec2::new(0.0, -1.0),
@@ -1186,11 +2662,7 @@ impl Graphics2D
 
         // draw bottom right circle
         self.draw_circle_section_triangular_three_color(
-            [
-                round_rect.bottom_right() + Vec2::new(0.0, -round_rect.radius()),
-                *round_rect.bottom_right(),
-                round_rect.bottom_right() + Vec2::new(-round_rect.radius(), 0.0)
-            ],
+            [octagon[3], bottom_right, octagon[4]],
             [color; 3],
             [
                 Vec2::new(1.0, 0.0),
@@ -1260,21 +2732,45 @@ impl Graphics2D
         );
     }
 
-    /// Draws a circle, filled with a single color, at the specified pixel
-    /// location.
-    pub fn draw_circle<V: Into<Vec2>>(
+    /// Draws a circle, filled with a single color. The circle may be either a
+    /// [Circle] shape, or a tuple containing its center position and radius
+    /// in pixels.
+    ///
+    /// ```rust,no_run
+    /// # use speedy2d::Graphics2D;
+    /// # use speedy2d::color::Color;
+    /// # use speedy2d::shape::Circle;
+    /// # fn draw(graphics: &mut Graphics2D) {
+    /// graphics.draw_circle(((100.0, 100.0), 75.0), Color::BLUE);
+    /// graphics.draw_circle(&Circle::new((300.0, 100.0), 50.0), Color::RED);
+    /// # }
+    /// ```
+    pub fn draw_circle(&mut self, circle: impl Into<Circle>, color: Color)
+    {
+        let circle = circle.into();
+        self.draw_ellipse(circle.center(), (circle.radius(), circle.radius()), color);
+    }
+
+    /// Draws an axis-aligned ellipse, filled with a single color, at the
+    /// specified pixel location. The `radii` parameter specifies the
+    /// horizontal (`x`) and vertical (`y`) radius of the ellipse.
+    ///
+    /// An ellipse may also be drawn from an [Ellipse] shape using
+    /// [Graphics2D::draw_ellipse_shape].
+    pub fn draw_ellipse<C: Into<Vec2>, R: Into<Vec2>>(
         &mut self,
-        center_position: V,
-        radius: f32,
+        center_position: C,
+        radii: R,
         color: Color
     )
     {
         let center_position = center_position.into();
+        let radii = radii.into();
 
-        let top_left = center_position + Vec2::new(-radius, -radius);
-        let top_right = center_position + Vec2::new(radius, -radius);
-        let bottom_right = center_position + Vec2::new(radius, radius);
-        let bottom_left = center_position + Vec2::new(-radius, radius);
+        let top_left = center_position + Vec2::new(-radii.x, -radii.y);
+        let top_right = center_position + Vec2::new(radii.x, -radii.y);
+        let bottom_right = center_position + Vec2::new(radii.x, radii.y);
+        let bottom_left = center_position + Vec2::new(-radii.x, radii.y);
 
         self.renderer.draw_circle_section(
             [top_left, top_right, bottom_right],
@@ -1297,6 +2793,201 @@ impl Graphics2D
         );
     }
 
+    /// Draws a circle filled with the specified gradient. The coordinates of
+    /// the circle and the gradient are specified in pixels.
+    ///
+    /// For example, a soft glow can be drawn using a radial gradient which
+    /// fades to transparent at the edge of the circle:
+    ///
+    /// ```rust,no_run
+    /// # use speedy2d::color::{Color, Gradient};
+    /// # use speedy2d::Graphics2D;
+    /// # fn draw(graphics: &mut Graphics2D) {
+    /// let glow = Gradient::radial((100.0, 100.0), 50.0)
+    ///     .with_stop(0.0, Color::YELLOW)
+    ///     .with_stop(1.0, Color::from_rgba(1.0, 1.0, 0.0, 0.0));
+    ///
+    /// graphics.draw_circle_gradient((100.0, 100.0), 50.0, &glow);
+    /// # }
+    /// ```
+    pub fn draw_circle_gradient<V: Into<Vec2>>(
+        &mut self,
+        center_position: V,
+        radius: f32,
+        gradient: &Gradient
+    )
+    {
+        let center_position = center_position.into();
+
+        if radius <= 0.0 {
+            return;
+        }
+
+        let top_left = center_position + Vec2::new(-radius, -radius);
+        let top_right = center_position + Vec2::new(radius, -radius);
+        let bottom_right = center_position + Vec2::new(radius, radius);
+        let bottom_left = center_position + Vec2::new(-radius, radius);
+
+        let triangles = [
+            [top_left, top_right, bottom_right],
+            [bottom_right, bottom_left, top_left]
+        ];
+
+        for triangle in triangles.iter() {
+            gradient.split_triangle(triangle, &mut |positions, colors| {
+                self.renderer.draw_circle_section(
+                    positions,
+                    colors,
+                    positions.map(|position| (position - center_position) / radius)
+                )
+            });
+        }
+    }
+
+    /// Draws a circle whose color fades smoothly from `center_color` at its
+    /// center to `edge_color` at its edge.
+    #[inline]
+    pub fn draw_circle_two_color<V: Into<Vec2>>(
+        &mut self,
+        center_position: V,
+        radius: f32,
+        center_color: Color,
+        edge_color: Color
+    )
+    {
+        let center_position = center_position.into();
+
+        self.draw_circle_gradient(
+            center_position,
+            radius,
+            &Gradient::radial(center_position, radius)
+                .with_stop(0.0, center_color)
+                .with_stop(1.0, edge_color)
+        );
+    }
+
+    /// Draws the specified [Ellipse], filled with a single color.
+    #[inline]
+    pub fn draw_ellipse_shape(&mut self, ellipse: impl AsRef<Ellipse>, color: Color)
+    {
+        let ellipse = ellipse.as_ref();
+        self.draw_ellipse(ellipse.center(), ellipse.radii(), color);
+    }
+
+    /// Draws the specified [crate::shape::RotatedRectangle], filled with a
+    /// single color.
+    #[inline]
+    pub fn draw_rotated_rectangle(
+        &mut self,
+        rect: impl AsRef<RotatedRectangle>,
+        color: Color
+    )
+    {
+        self.draw_quad(rect.as_ref().vertices(), color);
+    }
+
+    /// Draws a section of a circle (a "pie slice"), filled with a single color.
+    ///
+    /// Angles are specified in radians, where `0.0` points in the direction of
+    /// the positive `x` axis, and angles increase in a clockwise direction. The
+    /// section covers every angle between `start_angle` and `end_angle`. If
+    /// the difference between the angles is `2π` or more, a full circle is
+    /// drawn.
+    pub fn draw_circle_section<V: Into<Vec2>>(
+        &mut self,
+        center_position: V,
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        color: Color
+    )
+    {
+        let center_position = center_position.into();
+        let (start_angle, sweep) = normalize_arc_angles(start_angle, end_angle);
+
+        if radius <= 0.0 || sweep <= 0.0 {
+            return;
+        }
+
+        // The outer edge of each triangle is pushed out so that it touches the
+        // circle at a tangent, allowing the shader to draw the curved edge.
+        let segments = (sweep / std::f32::consts::FRAC_PI_4).ceil().max(1.0);
+        let segment_sweep = sweep / segments;
+        let extent = 1.0 / (segment_sweep / 2.0).cos();
+
+        for i in 0..(segments as u32) {
+            let angle_start = start_angle + segment_sweep * i as f32;
+            let angle_end = angle_start + segment_sweep;
+
+            let start_normalized =
+                Vec2::new(angle_start.cos(), angle_start.sin()) * extent;
+            let end_normalized = Vec2::new(angle_end.cos(), angle_end.sin()) * extent;
+
+            self.renderer.draw_circle_section(
+                [
+                    center_position,
+                    center_position + start_normalized * radius,
+                    center_position + end_normalized * radius
+                ],
+                [color, color, color],
+                [Vec2::ZERO, start_normalized, end_normalized]
+            );
+        }
+    }
+
+    /// Draws an arc (part of the outline of a circle) with the specified
+    /// thickness, centered on the circle with the specified radius.
+    ///
+    /// Angles are specified in the same way as for
+    /// [Graphics2D::draw_circle_section]. The number of segments used to
+    /// draw the arc is chosen automatically, based on its radius.
+    pub fn draw_arc<V: Into<Vec2>>(
+        &mut self,
+        center_position: V,
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        thickness: f32,
+        color: Color
+    )
+    {
+        let center_position = center_position.into();
+        let (start_angle, sweep) = normalize_arc_angles(start_angle, end_angle);
+
+        let outer_radius = radius + thickness / 2.0;
+        let inner_radius = (radius - thickness / 2.0).max(0.0);
+
+        if outer_radius <= 0.0 || thickness <= 0.0 || sweep <= 0.0 {
+            return;
+        }
+
+        // Keep the distance between each straight segment and the true curve
+        // below a quarter of a pixel.
+        let max_segment_sweep =
+            2.0 * (1.0 - (0.25 / outer_radius).min(1.0)).acos().max(0.01);
+        let segments = (sweep / max_segment_sweep).ceil().clamp(1.0, 1000.0);
+        let segment_sweep = sweep / segments;
+
+        let point_at = |angle: f32, radius: f32| {
+            center_position + Vec2::new(angle.cos(), angle.sin()) * radius
+        };
+
+        for i in 0..(segments as u32) {
+            let angle_start = start_angle + segment_sweep * i as f32;
+            let angle_end = angle_start + segment_sweep;
+
+            self.draw_quad(
+                [
+                    point_at(angle_start, inner_radius),
+                    point_at(angle_start, outer_radius),
+                    point_at(angle_end, outer_radius),
+                    point_at(angle_end, inner_radius)
+                ],
+                color
+            );
+        }
+    }
+
     /// Draws a triangular subset of a circle.
     ///
     /// Put simply, this function will draw a triangle on the screen, textured
@@ -1345,6 +3036,70 @@ impl Graphics2D
         );
     }
 
+    /// Returns the transformation which is currently applied to everything
+    /// drawn using this `Graphics2D` object.
+    #[inline]
+    #[must_use]
+    pub fn transform(&self) -> Matrix2D
+    {
+        self.renderer.transform()
+    }
+
+    /// Replaces the transformation which is applied to everything drawn using
+    /// this `Graphics2D` object. This does not affect the transform stack: a
+    /// later call to [Graphics2D::pop_transform] will restore the
+    /// transformation which was active when the corresponding
+    /// [Graphics2D::push_transform] was called.
+    ///
+    /// The transformation is reset to [Matrix2D::IDENTITY] at the start of
+    /// each frame.
+    #[inline]
+    pub fn set_transform(&mut self, transform: Matrix2D)
+    {
+        self.renderer.set_transform(transform);
+    }
+
+    /// Saves the current transformation on the transform stack, and then
+    /// combines it with the specified transformation. Everything drawn until
+    /// the matching call to [Graphics2D::pop_transform] will have `transform`
+    /// applied to it, followed by any previously active transformation.
+    ///
+    /// For example, to draw a rectangle rotated around its center:
+    ///
+    /// ```rust,no_run
+    /// # use speedy2d::color::Color;
+    /// # use speedy2d::dimen::Matrix2D;
+    /// # use speedy2d::shape::Rectangle;
+    /// # use speedy2d::Graphics2D;
+    /// # fn draw(graphics: &mut Graphics2D) {
+    /// graphics.push_transform(
+    ///     Matrix2D::rotate(0.5).then(&Matrix2D::translate((100.0, 100.0)))
+    /// );
+    /// graphics.draw_rectangle(
+    ///     Rectangle::from_tuples((-20.0, -20.0), (20.0, 20.0)),
+    ///     Color::RED
+    /// );
+    /// graphics.pop_transform();
+    /// # }
+    /// ```
+    ///
+    /// Note that the clipping area set using [Graphics2D::set_clip] is not
+    /// affected by the transformation. Use [Graphics2D::set_transformed_clip]
+    /// to specify a clipping area in transformed coordinates.
+    #[inline]
+    pub fn push_transform(&mut self, transform: Matrix2D)
+    {
+        self.renderer.push_transform(transform);
+    }
+
+    /// Restores the transformation which was active before the most recent
+    /// call to [Graphics2D::push_transform].
+    #[inline]
+    pub fn pop_transform(&mut self)
+    {
+        self.renderer.pop_transform();
+    }
+
     /// Sets the current clip to the rectangle specified by the given
     /// coordinates. Rendering operations have no effect outside of the
     /// clipping area.
@@ -1353,6 +3108,96 @@ impl Graphics2D
         self.renderer.set_clip(rect);
     }
 
+    /// Restricts drawing to the specified area of the window, which becomes
+    /// the origin for drawing, clipping, and capturing. The clip is reset.
+    #[cfg(any(doc, doctest, feature = "windowing"))]
+    pub(crate) fn set_content_area(&mut self, area: Option<Rectangle<u32>>)
+    {
+        self.renderer.set_content_area(area);
+    }
+
+    /// Sets the current clip to the specified rectangle, interpreted in the
+    /// coordinate space of the current transformation (see
+    /// [Graphics2D::push_transform]), rather than in window pixels. This
+    /// replaces any existing clip.
+    ///
+    /// The clipping area is calculated when this method is called, so it is
+    /// not affected by later changes to the transformation. If the
+    /// transformation contains a rotation or skew, the clip is applied as if
+    /// set using [Graphics2D::set_clip_shape].
+    ///
+    /// ```rust,no_run
+    /// # use speedy2d::Graphics2D;
+    /// # use speedy2d::color::Color;
+    /// # use speedy2d::dimen::Matrix2D;
+    /// # use speedy2d::shape::Rectangle;
+    /// # fn draw(graphics: &mut Graphics2D) {
+    /// graphics.push_transform(Matrix2D::scale((2.0, 2.0)));
+    ///
+    /// // Clips to the area between (20, 20) and (120, 120) in window pixels
+    /// graphics.set_transformed_clip(Some(Rectangle::from_tuples(
+    ///     (10.0, 10.0),
+    ///     (60.0, 60.0)
+    /// )));
+    ///
+    /// graphics.draw_circle(((35.0, 35.0), 40.0), Color::BLUE);
+    /// graphics.pop_transform();
+    /// # }
+    /// ```
+    pub fn set_transformed_clip(&mut self, rect: Option<Rect>)
+    {
+        self.renderer.set_transformed_clip(rect);
+    }
+
+    /// Restricts subsequent drawing operations to the area covered by the
+    /// specified shape, or removes the clip if `None` is passed. This replaces
+    /// any clip set using [Graphics2D::set_clip], and vice versa.
+    ///
+    /// Unlike [Graphics2D::set_clip], the shape is affected by the current
+    /// transformation (see [Graphics2D::push_transform]). The edges of the
+    /// clipping area are not antialiased, and [Graphics2D::clear_screen] is
+    /// not affected by the clip.
+    ///
+    /// ```rust,no_run
+    /// # use speedy2d::Graphics2D;
+    /// # use speedy2d::color::Color;
+    /// # use speedy2d::dimen::Vec2;
+    /// # use speedy2d::shape::{ClipShape, Rectangle};
+    /// # fn draw(graphics: &mut Graphics2D) {
+    /// graphics.set_clip_shape(Some(ClipShape::Circle {
+    ///     center: Vec2::new(100.0, 100.0),
+    ///     radius: 50.0
+    /// }));
+    /// graphics.draw_rectangle(
+    ///     Rectangle::from_tuples((0.0, 0.0), (200.0, 200.0)),
+    ///     Color::RED
+    /// );
+    /// graphics.set_clip_shape(None);
+    /// # }
+    /// ```
+    pub fn set_clip_shape(&mut self, shape: Option<ClipShape>)
+    {
+        self.renderer.set_clip_shape(shape.as_ref());
+    }
+
+    /// Sets how subsequent drawing operations are combined with the existing
+    /// contents of the window (or render target).
+    ///
+    /// The blend mode is reset to [BlendMode::Alpha] at the start of each
+    /// frame.
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode)
+    {
+        self.renderer.set_blend_mode(blend_mode);
+    }
+
+    /// Returns the blend mode set using [Graphics2D::set_blend_mode].
+    #[inline]
+    #[must_use]
+    pub fn blend_mode(&self) -> BlendMode
+    {
+        self.renderer.blend_mode()
+    }
+
     /// Captures a screenshot of the render window. The returned data contains
     /// the color of each pixel. Pixels are represented using a `u8` for each
     /// component (red, green, blue, and alpha). Use the `format` parameter to
@@ -1361,6 +3206,169 @@ impl Graphics2D
     {
         self.renderer.capture(format)
     }
+
+    /// Captures part of the render window, in the same way as
+    /// [Graphics2D::capture]. The `region` is given in pixels relative to the
+    /// top left of the window (or render target), and is cropped to fit
+    /// inside it.
+    ///
+    /// This is much faster than capturing the whole window when only a small
+    /// area is needed.
+    pub fn capture_region(
+        &mut self,
+        format: ImageDataType,
+        region: Rectangle<u32>
+    ) -> RawBitmapData
+    {
+        self.renderer.capture_region(format, region)
+    }
+
+    /// Starts capturing the render window, without waiting for the GPU to
+    /// finish drawing the frame.
+    ///
+    /// Unlike [Graphics2D::capture], this doesn't stall the rendering
+    /// pipeline. Instead, the pixels are copied into a buffer on the GPU,
+    /// and can be retrieved using [Graphics2D::finish_capture]. Ideally,
+    /// this should be done a frame or two later, once the GPU has caught up.
+    /// Finishing the capture sooner will block until the pixels are
+    /// available.
+    ///
+    /// On WebGL and OpenGL ES 2.0, the capture is performed immediately.
+    pub fn capture_async(&mut self, format: ImageDataType) -> PendingCapture
+    {
+        self.renderer.capture_async(format)
+    }
+
+    /// Starts capturing part of the render window, in the same way as
+    /// [Graphics2D::capture_async]. The `region` is interpreted in the same way
+    /// as for [Graphics2D::capture_region].
+    pub fn capture_region_async(
+        &mut self,
+        format: ImageDataType,
+        region: Rectangle<u32>
+    ) -> PendingCapture
+    {
+        self.renderer.capture_region_async(format, region)
+    }
+
+    /// Retrieves the pixels of a capture started using
+    /// [Graphics2D::capture_async]. If the GPU hasn't yet finished drawing
+    /// the captured frame, this will block until it has.
+    pub fn finish_capture(&mut self, capture: PendingCapture) -> RawBitmapData
+    {
+        self.renderer.finish_capture(capture)
+    }
+
+    /// Captures the render window in the same way as [Graphics2D::capture],
+    /// and saves it to the specified path in the specified file format. If
+    /// the file already exists, it is overwritten.
+    ///
+    /// See [RawBitmapData::save_to_file] for the supported formats.
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn capture_to_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        format: ImageFileFormat
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        self.capture(ImageDataType::RGBA).save_to_file(path, format)
+    }
+}
+
+/// Returns the start angle and the clockwise sweep of an arc between the two
+/// specified angles, with the sweep limited to one full turn.
+fn normalize_arc_angles(start_angle: f32, end_angle: f32) -> (f32, f32)
+{
+    let (start_angle, end_angle) = if end_angle < start_angle {
+        (end_angle, start_angle)
+    } else {
+        (start_angle, end_angle)
+    };
+
+    (
+        start_angle,
+        (end_angle - start_angle).min(std::f32::consts::PI * 2.0)
+    )
+}
+
+/// Returns the positions of the four edges of a nine-slice image along one
+/// axis, both on the screen and as normalized image coordinates. The insets
+/// are scaled down if they don't fit between `start` and `end`.
+fn nine_slice_edges(
+    start: f32,
+    end: f32,
+    start_inset: f32,
+    end_inset: f32,
+    image_size: f32
+) -> ([f32; 4], [f32; 4])
+{
+    let available = end - start;
+
+    let scale = if start_inset + end_inset > available {
+        available / (start_inset + end_inset)
+    } else {
+        1.0
+    };
+
+    (
+        [
+            start,
+            start + start_inset * scale,
+            end - end_inset * scale,
+            end
+        ],
+        [
+            0.0,
+            start_inset / image_size,
+            1.0 - end_inset / image_size,
+            1.0
+        ]
+    )
+}
+
+/// Checks that the per-vertex attributes and indices of a triangle mesh are
+/// consistent, logging an error if not.
+fn validate_mesh(
+    vertex_count: usize,
+    attribute_lengths: &[usize],
+    indices: &[u32]
+) -> bool
+{
+    if let Some(length) = attribute_lengths
+        .iter()
+        .find(|length| **length != vertex_count)
+    {
+        log::error!(
+            "Ignoring mesh: expected {} values for each vertex attribute, got {}",
+            vertex_count,
+            length
+        );
+        return false;
+    }
+
+    // `is_multiple_of()` would need Rust 1.87
+    #[allow(unknown_lints, clippy::manual_is_multiple_of)]
+    if indices.len() % 3 != 0 {
+        log::error!(
+            "Ignoring mesh: index count {} is not a multiple of three",
+            indices.len()
+        );
+        return false;
+    }
+
+    if let Some(index) = indices
+        .iter()
+        .find(|index| **index as usize >= vertex_count)
+    {
+        log::error!(
+            "Ignoring mesh: index {} is out of range for {} vertices",
+            index,
+            vertex_count
+        );
+        return false;
+    }
+
+    true
 }
 
 /// Struct representing a window.
@@ -1369,8 +3377,7 @@ pub struct Window<UserEventType = ()>
 where
     UserEventType: 'static
 {
-    window_impl: WindowGlutin<UserEventType>,
-    renderer: GLRenderer
+    window_impl: WindowGlutin<UserEventType>
 }
 
 #[cfg(any(doc, doctest, all(feature = "windowing", not(target_arch = "wasm32"))))]
@@ -1430,23 +3437,8 @@ impl<UserEventType: 'static> Window<UserEventType>
         options: WindowCreationOptions
     ) -> Result<Self, BacktraceError<WindowCreationError>>
     {
-        let window_impl = WindowGlutin::new(title, options)?;
-
-        let renderer = GLRenderer::new_with_gl_backend(
-            window_impl.get_inner_size_pixels(),
-            window_impl.gl_backend().clone(),
-            GLVersion::OpenGL2_0
-        )
-        .map_err(|err| {
-            BacktraceError::new_with_cause(
-                WindowCreationError::RendererCreationFailed,
-                err
-            )
-        })?;
-
         Ok(Window {
-            window_impl,
-            renderer
+            window_impl: WindowGlutin::new(title, options)?
         })
     }
 
@@ -1471,7 +3463,7 @@ impl<UserEventType: 'static> Window<UserEventType>
     where
         H: WindowHandler<UserEventType> + 'static
     {
-        self.window_impl.run_loop(handler, self.renderer);
+        self.window_impl.run_loop(handler);
     }
 }
 
@@ -18,21 +18,44 @@ use std::rc::Rc;
 
 #[cfg(any(feature = "image-loading", doc, doctest))]
 use {
-    crate::image::ImageFileFormat,
-    image::GenericImageView,
+    crate::image::{AnimatedImage, ImageFileFormat, PendingImage, PendingImageResult},
+    image::codecs::gif::GifDecoder,
+    image::codecs::png::PngDecoder,
+    image::{AnimationDecoder, GenericImageView},
+    std::cell::RefCell,
     std::fs::File,
     std::io::{BufRead, BufReader, Seek},
-    std::path::Path
+    std::path::Path,
+    std::sync::mpsc::{channel, Receiver, TryRecvError},
+    std::time::Duration
 };
 
-use crate::color::Color;
-use crate::dimen::{UVec2, Vec2};
+use crate::color::{Color, Gradient};
+use crate::dimen::{Matrix2D, UVec2, Vec2};
 use crate::error::{BacktraceError, Context, ErrorMessage};
-use crate::font::{FormattedGlyph, FormattedTextBlock};
-use crate::font_cache::GlyphCache;
+use crate::font::{
+    Font,
+    FormattedGlyph,
+    FormattedTextBlock,
+    TextLayout,
+    TextOptions,
+    TextShadow
+};
+use crate::font_cache::{self, GlyphCache, GlyphEffect};
 use crate::glwrapper::*;
-use crate::image::{ImageDataType, ImageHandle, ImageSmoothingMode};
-use crate::{Polygon, RawBitmapData, Rect, Rectangle};
+use crate::image::{
+    ImageAtlas,
+    ImageAtlasBuilder,
+    ImageDataType,
+    ImageHandle,
+    ImageInstance,
+    ImageSmoothingMode,
+    ImageWrapMode,
+    PendingCapture,
+    RenderTarget
+};
+use crate::shape::{ClipShape, GeometryHandle};
+use crate::{BlendMode, Polygon, RawBitmapData, Rect, Rectangle};
 
 struct AttributeBuffers
 {
@@ -46,7 +69,11 @@ struct AttributeBuffers
     glbuf_color: GLBuffer,
     glbuf_texture_coord: GLBuffer,
     glbuf_texture_mix: GLBuffer,
-    glbuf_circle_mix: GLBuffer
+    glbuf_circle_mix: GLBuffer,
+
+    /// Records the layout of the buffers above, if the context supports
+    /// vertex arrays.
+    vertex_array: Option<GLVertexArray>
 }
 
 impl AttributeBuffers
@@ -56,7 +83,7 @@ impl AttributeBuffers
         program: &GLProgram
     ) -> Result<Self, BacktraceError<ErrorMessage>>
     {
-        Ok(AttributeBuffers {
+        let mut buffers = AttributeBuffers {
             position: Vec::new(),
             color: Vec::new(),
             texture_coord: Vec::new(),
@@ -111,8 +138,29 @@ impl AttributeBuffers
                         .get_attribute_handle(Renderer2D::ATTR_NAME_CIRCLE_MIX)
                         .context("Failed to get attribute CIRCLE_MIX")?
                 )
-                .context("Failed to create buffer for attribute CIRCLE_MIX")?
-        })
+                .context("Failed to create buffer for attribute CIRCLE_MIX")?,
+
+            vertex_array: None
+        };
+
+        if context.has_gl3_features() {
+            buffers.vertex_array = Some(
+                context
+                    .new_vertex_array(
+                        &[
+                            &buffers.glbuf_position,
+                            &buffers.glbuf_color,
+                            &buffers.glbuf_texture_coord,
+                            &buffers.glbuf_texture_mix,
+                            &buffers.glbuf_circle_mix
+                        ],
+                        &[]
+                    )
+                    .context("Failed to create vertex array")?
+            );
+        }
+
+        Ok(buffers)
     }
 
     #[inline]
@@ -121,8 +169,28 @@ impl AttributeBuffers
         self.texture_mix.len()
     }
 
+    /// Binds the data most recently uploaded to the GPU, without uploading
+    /// it again.
+    pub fn bind(&self, context: &GLContextManager)
+    {
+        if let Some(vertex_array) = &self.vertex_array {
+            context.bind_vertex_array(vertex_array);
+            return;
+        }
+
+        self.glbuf_position.bind(context);
+        self.glbuf_color.bind(context);
+        self.glbuf_texture_coord.bind(context);
+        self.glbuf_texture_mix.bind(context);
+        self.glbuf_circle_mix.bind(context);
+    }
+
     pub fn upload_and_clear(&mut self, context: &GLContextManager)
     {
+        if let Some(vertex_array) = &self.vertex_array {
+            context.bind_vertex_array(vertex_array);
+        }
+
         self.glbuf_position.set_data(context, &self.position);
         self.glbuf_color.set_data(context, &self.color);
         self.glbuf_texture_coord
@@ -175,49 +243,352 @@ impl AttributeBuffers
     }
 }
 
-struct Uniforms
+/// The vertices of a polygon which have been uploaded to the GPU, and can be
+/// drawn repeatedly using [Renderer2D::draw_geometry].
+pub(crate) struct GeometryBuffers
+{
+    attribute_buffers: AttributeBuffers,
+    pub(crate) vertex_count: usize
+}
+
+/// The uniforms which position each vertex in the viewport. These are used by
+/// both the main program and the instanced image program.
+struct PositionUniforms
 {
     scale_x: GLUniformHandle,
     scale_y: GLUniformHandle,
-    texture: GLUniformHandle
+    offset_y: GLUniformHandle,
+    transform_row_0: GLUniformHandle,
+    transform_row_1: GLUniformHandle
 }
 
-impl Uniforms
+impl PositionUniforms
 {
     fn new(
         context: &GLContextManager,
         program: &Rc<GLProgram>
-    ) -> Result<Uniforms, BacktraceError<ErrorMessage>>
+    ) -> Result<PositionUniforms, BacktraceError<ErrorMessage>>
     {
-        Ok(Uniforms {
+        Ok(PositionUniforms {
             scale_x: program
                 .get_uniform_handle(context, Renderer2D::UNIFORM_NAME_SCALE_X)
                 .context("Failed to find SCALE_X uniform")?,
             scale_y: program
                 .get_uniform_handle(context, Renderer2D::UNIFORM_NAME_SCALE_Y)
                 .context("Failed to find SCALE_Y uniform")?,
-            texture: program
-                .get_uniform_handle(context, Renderer2D::UNIFORM_NAME_TEXTURE)
-                .context("Failed to find TEXTURE uniform")?
+            offset_y: program
+                .get_uniform_handle(context, Renderer2D::UNIFORM_NAME_OFFSET_Y)
+                .context("Failed to find OFFSET_Y uniform")?,
+            transform_row_0: program
+                .get_uniform_handle(context, Renderer2D::UNIFORM_NAME_TRANSFORM_ROW_0)
+                .context("Failed to find TRANSFORM_ROW_0 uniform")?,
+            transform_row_1: program
+                .get_uniform_handle(context, Renderer2D::UNIFORM_NAME_TRANSFORM_ROW_1)
+                .context("Failed to find TRANSFORM_ROW_1 uniform")?
         })
     }
 
     fn set_viewport_size_pixels(
         &self,
         context: &GLContextManager,
-        viewport_size_pixels: UVec2
+        viewport_size_pixels: UVec2,
+        invert_y_axis: bool
     )
     {
+        let y_direction = if invert_y_axis { -1.0 } else { 1.0 };
+
         self.scale_x
             .set_value_float(context, 2.0 / viewport_size_pixels.x as f32);
         self.scale_y
-            .set_value_float(context, -2.0 / viewport_size_pixels.y as f32);
+            .set_value_float(context, -2.0 * y_direction / viewport_size_pixels.y as f32);
+        self.offset_y.set_value_float(context, y_direction);
+    }
+
+    fn set_transform(&self, context: &GLContextManager, transform: &Matrix2D)
+    {
+        let origin = transform.transform_point(Vec2::ZERO);
+        let x_axis = transform.transform_vector(Vec2::new(1.0, 0.0));
+        let y_axis = transform.transform_vector(Vec2::new(0.0, 1.0));
+
+        self.transform_row_0
+            .set_value_vec3(context, x_axis.x, y_axis.x, origin.x);
+        self.transform_row_1
+            .set_value_vec3(context, x_axis.y, y_axis.y, origin.y);
+    }
+}
+
+struct Uniforms
+{
+    position: PositionUniforms,
+    tint: GLUniformHandle,
+    texture: GLUniformHandle,
+    smooth_circle_edges: GLUniformHandle
+}
+
+impl Uniforms
+{
+    fn new(
+        context: &GLContextManager,
+        program: &Rc<GLProgram>
+    ) -> Result<Uniforms, BacktraceError<ErrorMessage>>
+    {
+        Ok(Uniforms {
+            position: PositionUniforms::new(context, program)?,
+            tint: program
+                .get_uniform_handle(context, Renderer2D::UNIFORM_NAME_TINT)
+                .context("Failed to find TINT uniform")?,
+            texture: program
+                .get_uniform_handle(context, Renderer2D::UNIFORM_NAME_TEXTURE)
+                .context("Failed to find TEXTURE uniform")?,
+            smooth_circle_edges: program
+                .get_uniform_handle(context, Renderer2D::UNIFORM_NAME_SMOOTH_CIRCLE_EDGES)
+                .context("Failed to find SMOOTH_CIRCLE_EDGES uniform")?
+        })
+    }
+
+    fn set_viewport_size_pixels(
+        &self,
+        context: &GLContextManager,
+        viewport_size_pixels: UVec2,
+        invert_y_axis: bool
+    )
+    {
+        self.position.set_viewport_size_pixels(
+            context,
+            viewport_size_pixels,
+            invert_y_axis
+        );
+    }
+
+    /// Sets the transformation applied to each vertex by the GPU. This is the
+    /// identity for vertices in the render queue, which are transformed
+    /// before being uploaded.
+    fn set_transform(&self, context: &GLContextManager, transform: &Matrix2D)
+    {
+        self.position.set_transform(context, transform);
+    }
+
+    /// Sets the color by which the color of each vertex is multiplied.
+    fn set_tint(&self, context: &GLContextManager, color: Color)
+    {
+        self.tint
+            .set_value_vec4(context, color.r(), color.g(), color.b(), color.a());
     }
 
     fn set_texture_unit(&self, context: &GLContextManager, texture_unit: i32)
     {
         self.texture.set_value_int(context, texture_unit);
     }
+
+    fn set_smooth_circle_edges(&self, context: &GLContextManager, enabled: bool)
+    {
+        self.smooth_circle_edges
+            .set_value_float(context, if enabled { 1.0 } else { 0.0 });
+    }
+}
+
+/// Draws [ImageInstance]s using instanced rendering, so that only the
+/// attributes of each instance are uploaded, rather than the vertices of
+/// every copy. This requires OpenGL 3.3, OpenGL ES 3.0, or WebGL 2.0: on older
+/// versions, the vertices are generated on the CPU instead.
+struct InstancedImageRenderer
+{
+    program: Rc<GLProgram>,
+    uniforms: PositionUniforms,
+
+    center: Vec<f32>,
+    size: Vec<f32>,
+    rotation_sin_cos: Vec<f32>,
+    color: Vec<f32>,
+    texture_rect: Vec<f32>,
+
+    /// Only read through the vertex array, after being uploaded once.
+    #[allow(dead_code)]
+    glbuf_corner: GLBuffer,
+    glbuf_center: GLBuffer,
+    glbuf_size: GLBuffer,
+    glbuf_rotation_sin_cos: GLBuffer,
+    glbuf_color: GLBuffer,
+    glbuf_texture_rect: GLBuffer,
+
+    vertex_array: GLVertexArray
+}
+
+impl InstancedImageRenderer
+{
+    /// The corners of the unit square, as two clockwise triangles.
+    const CORNERS: [f32; 12] =
+        [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0];
+
+    fn new(context: &GLContextManager) -> Result<Self, BacktraceError<ErrorMessage>>
+    {
+        let (vertex_shader_src, fragment_shader_src) = match context.version() {
+            GLVersion::OpenGL2_0 => (
+                include_str!("shaders/r2d_instanced_vertex_v110.glsl"),
+                include_str!("shaders/r2d_instanced_fragment_v110.glsl")
+            ),
+            GLVersion::OpenGLES3_0 | GLVersion::WebGL2_0 => (
+                include_str!("shaders/r2d_instanced_vertex_v300es.glsl"),
+                include_str!("shaders/r2d_instanced_fragment_v300es.glsl")
+            ),
+            GLVersion::OpenGLES2_0 | GLVersion::WebGL1_0 => {
+                return Err(ErrorMessage::msg("Instanced drawing is not supported"))
+            }
+        };
+
+        let vertex_shader = context
+            .new_shader(GLShaderType::Vertex, vertex_shader_src)
+            .context("Failed to create instanced vertex shader")?;
+
+        let fragment_shader = context
+            .new_shader(GLShaderType::Fragment, fragment_shader_src)
+            .context("Failed to create instanced fragment shader")?;
+
+        let program = context
+            .new_program(
+                &vertex_shader,
+                &fragment_shader,
+                &Renderer2D::INSTANCED_ATTRIBUTES
+            )
+            .context("Failed to create instanced program")?;
+
+        let new_buffer = |name: &str, components_per_vertex: i32| {
+            let attribute = program
+                .get_attribute_handle(name)
+                .context(format!("Failed to get attribute {name}"))?;
+
+            context
+                .new_buffer(GLBufferTarget::Array, components_per_vertex, attribute)
+                .context(format!("Failed to create buffer for attribute {name}"))
+        };
+
+        let mut glbuf_corner = new_buffer(Renderer2D::ATTR_NAME_CORNER, 2)?;
+        let glbuf_center = new_buffer(Renderer2D::ATTR_NAME_INSTANCE_CENTER, 2)?;
+        let glbuf_size = new_buffer(Renderer2D::ATTR_NAME_INSTANCE_SIZE, 2)?;
+        let glbuf_rotation_sin_cos =
+            new_buffer(Renderer2D::ATTR_NAME_INSTANCE_ROTATION, 2)?;
+        let glbuf_color = new_buffer(Renderer2D::ATTR_NAME_COLOR, 4)?;
+        let glbuf_texture_rect =
+            new_buffer(Renderer2D::ATTR_NAME_INSTANCE_TEXTURE_RECT, 4)?;
+
+        let vertex_array = context
+            .new_vertex_array(
+                &[&glbuf_corner],
+                &[
+                    &glbuf_center,
+                    &glbuf_size,
+                    &glbuf_rotation_sin_cos,
+                    &glbuf_color,
+                    &glbuf_texture_rect
+                ]
+            )
+            .context("Failed to create instanced vertex array")?;
+
+        // The corners never change, so they're uploaded once
+        glbuf_corner.set_data(context, &InstancedImageRenderer::CORNERS);
+
+        let renderer = InstancedImageRenderer {
+            uniforms: PositionUniforms::new(context, &program)?,
+
+            center: Vec::new(),
+            size: Vec::new(),
+            rotation_sin_cos: Vec::new(),
+            color: Vec::new(),
+            texture_rect: Vec::new(),
+
+            glbuf_corner,
+            glbuf_center,
+            glbuf_size,
+            glbuf_rotation_sin_cos,
+            glbuf_color,
+            glbuf_texture_rect,
+
+            vertex_array,
+
+            program
+        };
+
+        let texture = renderer
+            .program
+            .get_uniform_handle(context, Renderer2D::UNIFORM_NAME_TEXTURE)
+            .context("Failed to find TEXTURE uniform")?;
+
+        context.use_program(&renderer.program);
+        texture.set_value_int(context, 0);
+
+        Ok(renderer)
+    }
+
+    /// Draws the instances using the specified blend mode. Afterwards, the
+    /// instanced program is still in use.
+    #[allow(clippy::too_many_arguments)]
+    fn draw(
+        &mut self,
+        context: &GLContextManager,
+        image: &ImageHandle,
+        instances: &[ImageInstance],
+        transform: &Matrix2D,
+        viewport_size_pixels: UVec2,
+        invert_y_axis: bool,
+        blend_mode: BlendMode
+    )
+    {
+        let image_size = image.size.into_f32();
+
+        for instance in instances {
+            let center = instance.center();
+            let size = instance.scaled_size(image_size);
+            let (sin, cos) = instance.rotation_radians().sin_cos();
+            let tint = instance.tint();
+            let texture_rect = instance.texture_rect(image_size);
+            let top_left = image.texture_coords(*texture_rect.top_left());
+            let bottom_right = image.texture_coords(*texture_rect.bottom_right());
+
+            self.center.extend_from_slice(&[center.x, center.y]);
+            self.size.extend_from_slice(&[size.x, size.y]);
+            self.rotation_sin_cos.extend_from_slice(&[sin, cos]);
+            self.color
+                .extend_from_slice(&[tint.r(), tint.g(), tint.b(), tint.a()]);
+            self.texture_rect.extend_from_slice(&[
+                top_left.x,
+                top_left.y,
+                bottom_right.x,
+                bottom_right.y
+            ]);
+        }
+
+        context.use_program(&self.program);
+
+        self.uniforms.set_viewport_size_pixels(
+            context,
+            viewport_size_pixels,
+            invert_y_axis
+        );
+        self.uniforms.set_transform(context, transform);
+
+        context.bind_vertex_array(&self.vertex_array);
+        self.glbuf_center.set_data(context, &self.center);
+        self.glbuf_size.set_data(context, &self.size);
+        self.glbuf_rotation_sin_cos
+            .set_data(context, &self.rotation_sin_cos);
+        self.glbuf_color.set_data(context, &self.color);
+        self.glbuf_texture_rect
+            .set_data(context, &self.texture_rect);
+
+        self.center.clear();
+        self.size.clear();
+        self.rotation_sin_cos.clear();
+        self.color.clear();
+        self.texture_rect.clear();
+
+        context.bind_texture(&image.texture);
+
+        context.draw_triangles_instanced(
+            GLBlendEnabled::Enabled(blend_mode.into()),
+            InstancedImageRenderer::CORNERS.len() / 2,
+            instances.len()
+        );
+    }
 }
 
 pub(crate) struct Renderer2DVertex
@@ -271,6 +642,25 @@ impl Renderer2DAction
         }
     }
 
+    /// Wraps the specified runner, so that the vertices of each action are
+    /// transformed before being passed on.
+    #[inline]
+    fn transformed_runner<'a>(
+        transform: &'a Matrix2D,
+        runner: &'a mut impl FnMut(Renderer2DAction)
+    ) -> impl FnMut(Renderer2DAction) + 'a
+    {
+        move |mut action: Renderer2DAction| {
+            if !transform.is_identity() {
+                for vertex in action.vertices_clockwise.iter_mut() {
+                    vertex.position = transform.transform_point(vertex.position);
+                }
+            }
+
+            runner(action)
+        }
+    }
+
     #[inline]
     fn append_to_attribute_buffers(&self, attribute_buffers: &mut AttributeBuffers)
     {
@@ -280,13 +670,124 @@ impl Renderer2DAction
     }
 }
 
+#[derive(Clone)]
+struct TextOutline
+{
+    color: Color,
+    width: f32
+}
+
+/// A pass over every glyph in a block of text.
+struct TextPass
+{
+    offset: Vec2,
+    effect: GlyphEffect,
+
+    /// Overrides the color of every glyph, if set.
+    color: Option<Color>
+}
+
+/// Returns the passes needed to draw a block of text, in the order they
+/// should be drawn. Every shadow and outline is drawn before the glyphs
+/// themselves, so that they don't overlap neighbouring glyphs.
+fn text_passes(
+    shadow: &Option<TextShadow>,
+    outline: &Option<TextOutline>
+) -> Vec<TextPass>
+{
+    let mut passes = Vec::with_capacity(3);
+
+    if let Some(shadow) = shadow {
+        passes.push(TextPass {
+            offset: shadow.offset(),
+            effect: GlyphEffect::Blur(shadow.blur_radius()),
+            color: Some(shadow.color())
+        });
+    }
+
+    if let Some(outline) = outline {
+        passes.push(TextPass {
+            offset: Vec2::ZERO,
+            effect: GlyphEffect::Outline(outline.width),
+            color: Some(outline.color)
+        });
+    }
+
+    passes.push(TextPass {
+        offset: Vec2::ZERO,
+        effect: GlyphEffect::None,
+        color: None
+    });
+
+    passes
+}
+
+/// The segments of a path which have a non-zero length, used to find the
+/// point at a given distance along the path.
+struct PathSegments
+{
+    /// The distance along the path at which each segment starts, its length,
+    /// and its start and end points.
+    segments: Vec<(f32, f32, Vec2, Vec2)>,
+    length: f32
+}
+
+impl PathSegments
+{
+    fn new(path: &[Vec2]) -> Self
+    {
+        let mut segments = Vec::new();
+        let mut length = 0.0;
+
+        for pair in path.windows(2) {
+            let segment_length = (pair[1] - pair[0]).magnitude();
+            if segment_length > 0.0 {
+                segments.push((length, segment_length, pair[0], pair[1]));
+                length += segment_length;
+            }
+        }
+
+        PathSegments { segments, length }
+    }
+
+    /// True if the path has no length, for example if it has fewer than two
+    /// points, or all of its points are the same.
+    fn is_empty(&self) -> bool
+    {
+        self.segments.is_empty()
+    }
+
+    /// Returns the point at the specified distance along the path, and the
+    /// direction of the path at that point as a unit vector. Returns `None`
+    /// if the distance is outside the path.
+    fn point_at_distance(&self, distance: f32) -> Option<(Vec2, Vec2)>
+    {
+        if self.is_empty() || distance < 0.0 || distance > self.length {
+            return None;
+        }
+
+        let segment_index = self
+            .segments
+            .partition_point(|(start, length, ..)| start + length < distance)
+            .min(self.segments.len() - 1);
+
+        let (start, length, from, to) = self.segments[segment_index];
+        let direction = (to - from) / length;
+
+        Some((from + direction * (distance - start), direction))
+    }
+}
+
 enum RenderQueueItem
 {
     FormattedTextBlock
     {
         position: Vec2,
         color: Color,
-        block: FormattedTextBlock
+        shadow: Option<TextShadow>,
+        outline: Option<TextOutline>,
+        block: FormattedTextBlock,
+        transform: Matrix2D
     },
 
     FormattedTextGlyph
@@ -294,7 +795,8 @@ enum RenderQueueItem
         position: Vec2,
         color: Color,
         glyph: FormattedGlyph,
-        crop_window: Rect
+        crop_window: Option<Rect>,
+        transform: Matrix2D
     },
 
     CircleSectionColored
@@ -316,6 +818,13 @@ enum RenderQueueItem
         vertex_colors_clockwise: [Color; 3],
         vertex_texture_coords_clockwise: [Vec2; 3],
         texture: GLTexture
+    },
+
+    ImageInstances
+    {
+        instances: Vec<ImageInstance>,
+        image: ImageHandle,
+        transform: Matrix2D
     }
 }
 
@@ -332,12 +841,22 @@ impl RenderQueueItem
             RenderQueueItem::FormattedTextBlock {
                 position,
                 color,
-                block
+                shadow,
+                outline,
+                block,
+                transform
             } => {
-                for line in block.iter_lines() {
-                    for glyph in line.iter_glyphs() {
+                let runner = &mut Renderer2DAction::transformed_runner(transform, runner);
+
+                for pass in text_passes(shadow, outline) {
+                    for glyph in block.iter_glyphs() {
                         glyph_cache.get_renderer2d_actions(
-                            glyph, *position, *color, None, runner
+                            glyph,
+                            *position + pass.offset,
+                            pass.color.or_else(|| glyph.color()).unwrap_or(*color),
+                            &pass.effect,
+                            None,
+                            runner
                         );
                     }
                 }
@@ -347,14 +866,16 @@ impl RenderQueueItem
                 glyph,
                 position,
                 color,
-                crop_window
+                crop_window,
+                transform
             } => {
                 glyph_cache.get_renderer2d_actions(
                     glyph,
                     *position,
-                    *color,
-                    Some(crop_window),
-                    runner
+                    glyph.color().unwrap_or(*color),
+                    &GlyphEffect::None,
+                    crop_window.as_ref(),
+                    &mut Renderer2DAction::transformed_runner(transform, runner)
                 );
             }
 
@@ -449,11 +970,61 @@ impl RenderQueueItem
                         circle_mix: 0.0
                     }
                 ]
-            })
+            }),
+
+            RenderQueueItem::ImageInstances {
+                instances,
+                image,
+                transform
+            } => {
+                let image_size = image.size.into_f32();
+
+                let runner = &mut Renderer2DAction::transformed_runner(transform, runner);
+
+                for instance in instances {
+                    let corners = instance.corners(image_size);
+                    let texture_coords = instance
+                        .texture_coords(image_size)
+                        .map(|coords| image.texture_coords(coords));
+
+                    let vertex = |index: usize| Renderer2DVertex {
+                        position: corners[index],
+                        texture_coord: texture_coords[index],
+                        color: instance.tint(),
+                        texture_mix: 1.0,
+                        circle_mix: 0.0
+                    };
+
+                    runner(Renderer2DAction {
+                        texture: Some(image.texture.clone()),
+                        vertices_clockwise: [vertex(0), vertex(1), vertex(2)]
+                    });
+
+                    runner(Renderer2DAction {
+                        texture: Some(image.texture.clone()),
+                        vertices_clockwise: [vertex(2), vertex(3), vertex(0)]
+                    });
+                }
+            }
         }
     }
 }
 
+/// The render destination state which is replaced while drawing to a
+/// [RenderTarget], and restored afterwards.
+pub(crate) struct Renderer2DTargetState
+{
+    framebuffer: Option<GLFramebuffer>,
+    viewport_size_pixels: UVec2,
+    content_area: Option<Rectangle<u32>>,
+    clip: Option<Rectangle<i32>>,
+    stencil_clip: bool,
+
+    /// The texture of the target, if it uses trilinear filtering and so its
+    /// mipmaps must be regenerated once drawing has finished.
+    mipmapped_texture: Option<GLTexture>
+}
+
 pub struct Renderer2D
 {
     context: GLContextManager,
@@ -466,10 +1037,43 @@ pub struct Renderer2D
     attribute_buffers: AttributeBuffers,
     current_texture: Option<GLTexture>,
 
+    /// Draws image instances, if the context supports instanced rendering.
+    instanced_image_renderer: Option<InstancedImageRenderer>,
+
+    /// Holds the indices of triangle meshes, if the context supports 32-bit
+    /// indices. Otherwise, the vertices of each triangle are queued
+    /// separately.
+    index_buffer: Option<GLIndexBuffer>,
+
+    viewport_size_pixels: UVec2,
+    invert_y_axis: bool,
+
+    /// The area of the window which is drawn to, if it's letterboxed.
+    content_area: Option<Rectangle<u32>>,
+
+    clip: Option<Rectangle<i32>>,
+    stencil_clip: bool,
+    multisampling: u32,
+    blend_mode: BlendMode,
+
+    transform: Matrix2D,
+    transform_stack: Vec<Matrix2D>,
+
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pending_image_loads: Vec<PendingImageLoad>,
+
     #[allow(dead_code)]
     uniforms: Uniforms
 }
 
+#[cfg(any(feature = "image-loading", doc, doctest))]
+struct PendingImageLoad
+{
+    receiver: Receiver<Result<(UVec2, Vec<u8>), String>>,
+    smoothing_mode: ImageSmoothingMode,
+    result: PendingImageResult
+}
+
 impl Renderer2D
 {
     const ATTR_NAME_POSITION: &'static str = "in_Position";
@@ -480,7 +1084,27 @@ impl Renderer2D
 
     const UNIFORM_NAME_SCALE_X: &'static str = "in_ScaleX";
     const UNIFORM_NAME_SCALE_Y: &'static str = "in_ScaleY";
+    const UNIFORM_NAME_OFFSET_Y: &'static str = "in_OffsetY";
+    const UNIFORM_NAME_TRANSFORM_ROW_0: &'static str = "in_TransformRow0";
+    const UNIFORM_NAME_TRANSFORM_ROW_1: &'static str = "in_TransformRow1";
+    const UNIFORM_NAME_TINT: &'static str = "in_Tint";
     const UNIFORM_NAME_TEXTURE: &'static str = "in_Texture";
+    const UNIFORM_NAME_SMOOTH_CIRCLE_EDGES: &'static str = "in_SmoothCircleEdges";
+
+    const ATTR_NAME_CORNER: &'static str = "in_Corner";
+    const ATTR_NAME_INSTANCE_CENTER: &'static str = "in_Center";
+    const ATTR_NAME_INSTANCE_SIZE: &'static str = "in_Size";
+    const ATTR_NAME_INSTANCE_ROTATION: &'static str = "in_RotationSinCos";
+    const ATTR_NAME_INSTANCE_TEXTURE_RECT: &'static str = "in_TextureRect";
+
+    const INSTANCED_ATTRIBUTES: [&'static str; 6] = [
+        Renderer2D::ATTR_NAME_CORNER,
+        Renderer2D::ATTR_NAME_INSTANCE_CENTER,
+        Renderer2D::ATTR_NAME_INSTANCE_SIZE,
+        Renderer2D::ATTR_NAME_INSTANCE_ROTATION,
+        Renderer2D::ATTR_NAME_COLOR,
+        Renderer2D::ATTR_NAME_INSTANCE_TEXTURE_RECT
+    ];
 
     const ALL_ATTRIBUTES: [&'static str; 5] = [
         Renderer2D::ATTR_NAME_POSITION,
@@ -492,7 +1116,8 @@ impl Renderer2D
 
     pub fn new(
         context: &GLContextManager,
-        viewport_size_pixels: UVec2
+        viewport_size_pixels: UVec2,
+        multisampling: u32
     ) -> Result<Self, BacktraceError<ErrorMessage>>
     {
         log::info!("Creating vertex shader");
@@ -505,8 +1130,15 @@ impl Renderer2D
                     include_str!("shaders/r2d_fragment_v110.glsl")
                 )
             }
-            GLVersion::WebGL2_0 => {
-                log::info!("Using WebGL 2.0 shaders");
+            GLVersion::OpenGLES2_0 | GLVersion::WebGL1_0 => {
+                log::info!("Using OpenGL ES 2.0 shaders");
+                (
+                    include_str!("shaders/r2d_vertex_v100es.glsl"),
+                    include_str!("shaders/r2d_fragment_v100es.glsl")
+                )
+            }
+            GLVersion::OpenGLES3_0 | GLVersion::WebGL2_0 => {
+                log::info!("Using OpenGL ES 3.0 shaders");
                 (
                     include_str!("shaders/r2d_vertex_v300es.glsl"),
                     include_str!("shaders/r2d_fragment_v300es.glsl")
@@ -537,14 +1169,50 @@ impl Renderer2D
         let attribute_buffers = AttributeBuffers::new(context, &program)?;
         let uniforms = Uniforms::new(context, &program)?;
 
+        let index_buffer = match context.has_gl3_features() {
+            false => None,
+            true => Some(
+                context
+                    .new_index_buffer()
+                    .context("Failed to create index buffer")?
+            )
+        };
+
+        let instanced_image_renderer = match context.has_gl3_features() {
+            false => {
+                log::info!("Instanced rendering unavailable, using CPU vertices");
+                None
+            }
+            true => match InstancedImageRenderer::new(context) {
+                Ok(renderer) => {
+                    log::info!("Using instanced rendering for image instances");
+                    Some(renderer)
+                }
+                Err(err) => {
+                    log::warn!("Failed to create instanced program, continuing: {err:?}");
+                    None
+                }
+            }
+        };
+
         context.use_program(&program);
 
         uniforms.set_texture_unit(context, 0);
+        uniforms.set_transform(context, &Matrix2D::IDENTITY);
+        uniforms.set_tint(context, Color::WHITE);
+
+        // Multisampling only smooths the edges of triangles, so circles need
+        // to be smoothed in the fragment shader instead.
+        uniforms.set_smooth_circle_edges(context, multisampling > 0);
 
-        uniforms.set_viewport_size_pixels(context, viewport_size_pixels);
+        uniforms.set_viewport_size_pixels(context, viewport_size_pixels, false);
 
         context.set_viewport_size(viewport_size_pixels);
 
+        context
+            .set_multisampling(multisampling, viewport_size_pixels)
+            .context("Failed to enable multisampling")?;
+
         Ok(Renderer2D {
             context: context.clone(),
             program,
@@ -552,45 +1220,204 @@ impl Renderer2D
             glyph_cache: GlyphCache::new(),
             attribute_buffers,
             current_texture: None,
+            instanced_image_renderer,
+            index_buffer,
+            viewport_size_pixels,
+            invert_y_axis: false,
+            content_area: None,
+            clip: None,
+            stencil_clip: false,
+            multisampling,
+            blend_mode: BlendMode::Alpha,
+            transform: Matrix2D::IDENTITY,
+            transform_stack: Vec::new(),
+            #[cfg(any(feature = "image-loading", doc, doctest))]
+            pending_image_loads: Vec::new(),
             uniforms
         })
     }
 
-    pub fn set_viewport_size_pixels(&self, viewport_size_pixels: UVec2)
+    pub fn set_viewport_size_pixels(&mut self, viewport_size_pixels: UVec2)
     {
-        self.uniforms
-            .set_viewport_size_pixels(&self.context, viewport_size_pixels);
+        self.viewport_size_pixels = viewport_size_pixels;
+        self.invert_y_axis = false;
+        self.content_area = None;
+
+        self.apply_viewport();
+
+        if self.multisampling > 0 {
+            if let Err(err) = self
+                .context
+                .set_multisampling(self.multisampling, viewport_size_pixels)
+            {
+                log::error!("Failed to resize multisampled framebuffer: {:?}", err);
+            }
+        }
+    }
+
+    pub(crate) fn create_render_target<S: Into<UVec2>>(
+        &self,
+        size: S,
+        smoothing_mode: ImageSmoothingMode
+    ) -> Result<RenderTarget, BacktraceError<ErrorMessage>>
+    {
+        let size = size.into();
 
-        self.context.set_viewport_size(viewport_size_pixels);
+        if size.x == 0 || size.y == 0 {
+            return Err(ErrorMessage::msg(format!(
+                "Invalid render target size {}x{}",
+                size.x, size.y
+            )));
+        }
+
+        let image = self.create_image_from_raw_pixels(
+            ImageDataType::RGBA,
+            smoothing_mode,
+            size,
+            &vec![0; size.x as usize * size.y as usize * 4]
+        )?;
+
+        let framebuffer = self
+            .context
+            .new_framebuffer(&image.texture, size)
+            .context("Failed to create framebuffer")?;
+
+        Ok(RenderTarget {
+            image,
+            framebuffer,
+            smoothing_mode
+        })
     }
 
-    pub fn finish_frame(&mut self)
+    /// Redirects subsequent drawing operations to the specified target. The
+    /// returned state must later be passed to [Renderer2D::end_render_target].
+    pub(crate) fn begin_render_target(
+        &mut self,
+        target: &RenderTarget
+    ) -> Renderer2DTargetState
     {
         self.flush_render_queue();
-        self.glyph_cache.on_new_frame_start();
+
+        let previous_state = Renderer2DTargetState {
+            framebuffer: self.context.bind_framebuffer(Some(&target.framebuffer)),
+            viewport_size_pixels: self.viewport_size_pixels,
+            content_area: self.content_area.take(),
+            clip: self.clip.clone(),
+            stencil_clip: self.stencil_clip,
+            mipmapped_texture: match target.smoothing_mode {
+                ImageSmoothingMode::Trilinear => Some(target.image.texture.clone()),
+                ImageSmoothingMode::NearestNeighbor | ImageSmoothingMode::Linear => None
+            }
+        };
+
+        self.apply_target_viewport(target.image.size, true);
+        self.set_clip(None);
+
+        previous_state
     }
 
-    fn flush_render_queue(&mut self)
+    pub(crate) fn end_render_target(&mut self, previous_state: Renderer2DTargetState)
     {
-        if self.render_queue.is_empty() {
-            return;
-        }
+        self.flush_render_queue();
 
-        self.attribute_buffers.clear();
+        let invert_y_axis = previous_state.framebuffer.is_some();
 
-        let mut has_text = false;
+        self.context
+            .bind_framebuffer(previous_state.framebuffer.as_ref());
 
-        for item in &self.render_queue {
+        self.content_area = previous_state.content_area;
+        self.apply_target_viewport(previous_state.viewport_size_pixels, invert_y_axis);
+        self.set_clip(previous_state.clip);
+
+        // The stencil buffer of the previous framebuffer still contains the
+        // clip shape, so it only needs to be re-enabled
+        if previous_state.stencil_clip {
+            self.stencil_clip = true;
+            self.context.set_enable_stencil_clip(true);
+        }
+
+        if let Some(texture) = previous_state.mipmapped_texture {
+            texture.generate_mipmaps(&self.context);
+        }
+    }
+
+    fn apply_target_viewport(&mut self, viewport_size_pixels: UVec2, invert_y_axis: bool)
+    {
+        self.viewport_size_pixels = viewport_size_pixels;
+        self.invert_y_axis = invert_y_axis;
+
+        self.apply_viewport();
+    }
+
+    /// The area of the render destination which is drawn to, relative to its
+    /// top left.
+    fn viewport_area(&self) -> Rectangle<u32>
+    {
+        match &self.content_area {
+            None => Rectangle::new(UVec2::ZERO, self.viewport_size_pixels),
+            Some(area) => area.clone()
+        }
+    }
+
+    fn apply_viewport(&mut self)
+    {
+        let area = self.viewport_area();
+
+        self.uniforms.set_viewport_size_pixels(
+            &self.context,
+            area.size(),
+            self.invert_y_axis
+        );
+
+        self.context.set_viewport(self.viewport_size_pixels, area);
+    }
+
+    /// Restricts drawing to the specified area of the window, for example
+    /// when letterboxing. Subsequent drawing, clipping, and capturing is
+    /// relative to the top left of the area, and the clip is reset.
+    #[cfg(any(doc, doctest, feature = "windowing"))]
+    pub(crate) fn set_content_area(&mut self, area: Option<Rectangle<u32>>)
+    {
+        self.flush_render_queue();
+
+        self.content_area = area;
+        self.apply_viewport();
+        self.set_clip(None);
+    }
+
+    pub fn finish_frame(&mut self)
+    {
+        self.flush_render_queue();
+        self.context.resolve_multisampling();
+        self.glyph_cache.on_new_frame_start();
+    }
+
+    fn flush_render_queue(&mut self)
+    {
+        if self.render_queue.is_empty() {
+            return;
+        }
+
+        self.attribute_buffers.clear();
+
+        let mut has_text = false;
+
+        for item in &self.render_queue {
             match item {
                 RenderQueueItem::FormattedTextBlock {
-                    block, position, ..
+                    block,
+                    position,
+                    shadow,
+                    outline,
+                    ..
                 } => {
-                    for line in block.iter_lines() {
-                        for glyph in line.iter_glyphs() {
+                    for pass in text_passes(shadow, outline) {
+                        for glyph in block.iter_glyphs() {
                             self.glyph_cache.add_to_cache(
                                 &self.context,
                                 glyph,
-                                *position
+                                *position + pass.offset,
+                                &pass.effect
                             );
                         }
                     }
@@ -600,13 +1427,18 @@ impl Renderer2D
                 RenderQueueItem::FormattedTextGlyph {
                     glyph, position, ..
                 } => {
-                    self.glyph_cache
-                        .add_to_cache(&self.context, glyph, *position);
+                    self.glyph_cache.add_to_cache(
+                        &self.context,
+                        glyph,
+                        *position,
+                        &GlyphEffect::None
+                    );
                     has_text = true;
                 }
                 RenderQueueItem::CircleSectionColored { .. }
                 | RenderQueueItem::TriangleColored { .. }
-                | RenderQueueItem::TriangleTextured { .. } => {}
+                | RenderQueueItem::TriangleTextured { .. }
+                | RenderQueueItem::ImageInstances { .. } => {}
             }
         }
 
@@ -617,19 +1449,55 @@ impl Renderer2D
         }
 
         {
+            let viewport_area_size = self.viewport_area().size();
             let current_texture = &mut self.current_texture;
             let context = &self.context;
             let program = &self.program;
             let attribute_buffers = &mut self.attribute_buffers;
+            let instanced_image_renderer = &mut self.instanced_image_renderer;
+            let blend_mode = self.blend_mode;
 
             for item in &self.render_queue {
+                if let (
+                    Some(instanced_image_renderer),
+                    RenderQueueItem::ImageInstances {
+                        instances,
+                        image,
+                        transform
+                    }
+                ) = (instanced_image_renderer.as_mut(), item)
+                {
+                    // Draw the preceding items first, to preserve the order
+                    Renderer2D::draw_buffers(
+                        context,
+                        program,
+                        attribute_buffers,
+                        current_texture,
+                        blend_mode
+                    );
+
+                    instanced_image_renderer.draw(
+                        context,
+                        image,
+                        instances,
+                        transform,
+                        viewport_area_size,
+                        self.invert_y_axis,
+                        blend_mode
+                    );
+
+                    context.use_program(program);
+                    continue;
+                }
+
                 item.generate_actions(&self.glyph_cache, &mut |action| {
                     if !action.update_current_texture_if_empty(current_texture) {
                         Renderer2D::draw_buffers(
                             context,
                             program,
                             attribute_buffers,
-                            current_texture
+                            current_texture,
+                            blend_mode
                         );
 
                         current_texture.clone_from(&action.texture);
@@ -646,7 +1514,8 @@ impl Renderer2D
             &self.context,
             &self.program,
             &mut self.attribute_buffers,
-            &mut self.current_texture
+            &mut self.current_texture,
+            self.blend_mode
         );
     }
 
@@ -654,7 +1523,8 @@ impl Renderer2D
         context: &GLContextManager,
         program: &Rc<GLProgram>,
         attribute_buffers: &mut AttributeBuffers,
-        current_texture: &mut Option<GLTexture>
+        current_texture: &mut Option<GLTexture>,
+        blend_mode: BlendMode
     )
     {
         let vertex_count = attribute_buffers.get_vertex_count();
@@ -674,10 +1544,7 @@ impl Renderer2D
             Some(texture) => context.bind_texture(texture)
         }
 
-        context.draw_triangles(
-            GLBlendEnabled::Enabled(GLBlendMode::OneMinusSrcAlpha),
-            vertex_count
-        );
+        context.draw_triangles(GLBlendEnabled::Enabled(blend_mode.into()), vertex_count);
     }
 
     pub(crate) fn create_image_from_raw_pixels<S: Into<UVec2>>(
@@ -714,7 +1581,8 @@ impl Renderer2D
 
         let gl_smoothing = match smoothing_mode {
             ImageSmoothingMode::NearestNeighbor => GLTextureSmoothing::NearestNeighbour,
-            ImageSmoothingMode::Linear => GLTextureSmoothing::Linear
+            ImageSmoothingMode::Linear => GLTextureSmoothing::Linear,
+            ImageSmoothingMode::Trilinear => GLTextureSmoothing::Trilinear
         };
 
         let texture = self
@@ -726,7 +1594,73 @@ impl Renderer2D
             .set_image_data(&self.context, gl_format, gl_smoothing, &size, data)
             .context("Failed to upload image data")?;
 
-        Ok(ImageHandle { size, texture })
+        Ok(ImageHandle::new(size, texture))
+    }
+
+    pub(crate) fn set_image_wrap_mode(
+        &mut self,
+        image: &ImageHandle,
+        wrap_mode: ImageWrapMode
+    )
+    {
+        // Items already in the queue must be drawn using the previous mode
+        self.flush_render_queue();
+
+        image.texture.set_wrap(
+            &self.context,
+            match wrap_mode {
+                ImageWrapMode::ClampToEdge => GLTextureWrap::ClampToEdge,
+                ImageWrapMode::Repeat => GLTextureWrap::Repeat,
+                ImageWrapMode::MirroredRepeat => GLTextureWrap::MirroredRepeat
+            }
+        );
+    }
+
+    pub(crate) fn create_image_atlas(
+        &self,
+        builder: &ImageAtlasBuilder,
+        smoothing_mode: ImageSmoothingMode
+    ) -> Result<ImageAtlas, BacktraceError<ErrorMessage>>
+    {
+        let image = self.create_image_from_raw_pixels(
+            ImageDataType::RGBA,
+            smoothing_mode,
+            builder.size(),
+            builder.pixels()
+        )?;
+
+        let images = builder
+            .regions()
+            .iter()
+            .map(|region| image.sub_image(region))
+            .collect();
+
+        Ok(ImageAtlas { image, images })
+    }
+
+    pub(crate) fn create_geometry(
+        &self,
+        polygon: &Polygon
+    ) -> Result<GeometryHandle, BacktraceError<ErrorMessage>>
+    {
+        let mut attribute_buffers = AttributeBuffers::new(&self.context, &self.program)?;
+
+        for triangle in polygon.triangles.iter() {
+            for vertex in triangle.iter() {
+                attribute_buffers.append(vertex, &Color::WHITE, &Vec2::ZERO, 0.0, 0.0);
+            }
+        }
+
+        let vertex_count = attribute_buffers.get_vertex_count();
+
+        attribute_buffers.upload_and_clear(&self.context);
+
+        Ok(GeometryHandle {
+            buffers: Rc::new(GeometryBuffers {
+                attribute_buffers,
+                vertex_count
+            })
+        })
     }
 
     #[cfg(any(feature = "image-loading", doc, doctest))]
@@ -736,13 +1670,100 @@ impl Renderer2D
         smoothing_mode: ImageSmoothingMode,
         path: P
     ) -> Result<ImageHandle, BacktraceError<ErrorMessage>>
+    {
+        let file_bytes = Self::open_image_file(path)?;
+
+        self.create_image_from_file_bytes(data_type, smoothing_mode, file_bytes)
+    }
+
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn create_image_from_file_path_async<P: AsRef<Path>>(
+        &mut self,
+        data_type: Option<ImageFileFormat>,
+        smoothing_mode: ImageSmoothingMode,
+        path: P
+    ) -> PendingImage
+    {
+        let path = path.as_ref().to_path_buf();
+
+        let (sender, receiver) = channel();
+
+        let load = move || {
+            let result = Self::open_image_file(&path)
+                .and_then(|file_bytes| Self::decode_image_file(data_type, file_bytes))
+                .map_err(|err| match err.cause() {
+                    None => err.to_string(),
+                    Some(cause) => format!("{}: {}", err, cause)
+                });
+
+            // The receiver may have been dropped along with the renderer
+            sender.send(result).ok();
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        std::thread::spawn(load);
+
+        // Threads aren't available, so load the image immediately
+        #[cfg(target_arch = "wasm32")]
+        load();
+
+        let pending = PendingImage {
+            result: Rc::new(RefCell::new(None))
+        };
+
+        self.pending_image_loads.push(PendingImageLoad {
+            receiver,
+            smoothing_mode,
+            result: pending.result.clone()
+        });
+
+        pending
+    }
+
+    /// Uploads any images which have finished decoding since the last call.
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn upload_loaded_images(&mut self)
+    {
+        for load in std::mem::take(&mut self.pending_image_loads) {
+            let result = match load.receiver.try_recv() {
+                Err(TryRecvError::Empty) => {
+                    self.pending_image_loads.push(load);
+                    continue;
+                }
+                Err(TryRecvError::Disconnected) => Err(ErrorMessage::msg(
+                    "Image loading thread exited unexpectedly"
+                )),
+                Ok(Err(err)) => Err(ErrorMessage::msg(err)),
+                Ok(Ok((size, bytes_rgba8))) => {
+                    // Nothing is waiting for the image
+                    if Rc::strong_count(&load.result) == 1 {
+                        continue;
+                    }
+
+                    self.create_image_from_raw_pixels(
+                        ImageDataType::RGBA,
+                        load.smoothing_mode,
+                        size,
+                        bytes_rgba8.as_slice()
+                    )
+                }
+            };
+
+            *load.result.borrow_mut() = Some(result);
+        }
+    }
+
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    fn open_image_file<P: AsRef<Path>>(
+        path: P
+    ) -> Result<BufReader<File>, BacktraceError<ErrorMessage>>
     {
         let file = File::open(path.as_ref()).context(format!(
             "Failed to open file '{:?}' for reading",
             path.as_ref()
         ))?;
 
-        self.create_image_from_file_bytes(data_type, smoothing_mode, BufReader::new(file))
+        Ok(BufReader::new(file))
     }
 
     #[cfg(any(feature = "image-loading", doc, doctest))]
@@ -752,6 +1773,39 @@ impl Renderer2D
         smoothing_mode: ImageSmoothingMode,
         file_bytes: R
     ) -> Result<ImageHandle, BacktraceError<ErrorMessage>>
+    {
+        let (size, bytes_rgba8) = Self::decode_image_file(data_type, file_bytes)?;
+
+        self.create_image_from_raw_pixels(
+            ImageDataType::RGBA,
+            smoothing_mode,
+            size,
+            bytes_rgba8.as_slice()
+        )
+    }
+
+    /// Decodes the image file into RGBA pixels, returning the size and the
+    /// pixel data.
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    fn decode_image_file<R: Seek + BufRead>(
+        data_type: Option<ImageFileFormat>,
+        file_bytes: R
+    ) -> Result<(UVec2, Vec<u8>), BacktraceError<ErrorMessage>>
+    {
+        let reader = Self::image_reader(data_type, file_bytes)?;
+
+        let image = reader.decode().context("Failed to parse image data")?;
+
+        let dimensions = image.dimensions();
+
+        Ok((dimensions.into(), image.into_rgba8().into_raw()))
+    }
+
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    fn image_reader<R: Seek + BufRead>(
+        data_type: Option<ImageFileFormat>,
+        file_bytes: R
+    ) -> Result<image::io::Reader<R>, BacktraceError<ErrorMessage>>
     {
         let mut reader = image::io::Reader::new(file_bytes);
 
@@ -761,32 +1815,146 @@ impl Renderer2D
                     .with_guessed_format()
                     .context("Could not guess file format")?
             }
-            Some(format) => reader.set_format(match format {
-                ImageFileFormat::PNG => image::ImageFormat::Png,
-                ImageFileFormat::JPEG => image::ImageFormat::Jpeg,
-                ImageFileFormat::GIF => image::ImageFormat::Gif,
-                ImageFileFormat::BMP => image::ImageFormat::Bmp,
-                ImageFileFormat::ICO => image::ImageFormat::Ico,
-                ImageFileFormat::TIFF => image::ImageFormat::Tiff,
-                ImageFileFormat::WebP => image::ImageFormat::WebP,
-                ImageFileFormat::AVIF => image::ImageFormat::Avif,
-                ImageFileFormat::PNM => image::ImageFormat::Pnm,
-                ImageFileFormat::DDS => image::ImageFormat::Dds,
-                ImageFileFormat::TGA => image::ImageFormat::Tga,
-                ImageFileFormat::Farbfeld => image::ImageFormat::Farbfeld
-            })
+            Some(format) => reader.set_format(format.to_image_format())
         }
 
-        let image = reader.decode().context("Failed to parse image data")?;
+        Ok(reader)
+    }
 
-        let dimensions = image.dimensions();
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn create_animated_image_from_file_path<P: AsRef<Path>>(
+        &mut self,
+        data_type: Option<ImageFileFormat>,
+        smoothing_mode: ImageSmoothingMode,
+        path: P
+    ) -> Result<AnimatedImage, BacktraceError<ErrorMessage>>
+    {
+        let file_bytes = Self::open_image_file(path)?;
+
+        self.create_animated_image_from_file_bytes(data_type, smoothing_mode, file_bytes)
+    }
+
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn create_animated_image_from_file_bytes<R: Seek + BufRead>(
+        &mut self,
+        data_type: Option<ImageFileFormat>,
+        smoothing_mode: ImageSmoothingMode,
+        file_bytes: R
+    ) -> Result<AnimatedImage, BacktraceError<ErrorMessage>>
+    {
+        let reader = Self::image_reader(data_type, file_bytes)?;
+
+        let frames = match reader.format() {
+            Some(image::ImageFormat::Gif) => GifDecoder::new(reader.into_inner())
+                .context("Failed to parse GIF header")?
+                .into_frames()
+                .collect_frames()
+                .context("Failed to parse GIF frames")?,
+
+            Some(image::ImageFormat::Png) => {
+                let decoder = PngDecoder::new(reader.into_inner())
+                    .context("Failed to parse PNG header")?;
+
+                if decoder.is_apng() {
+                    decoder
+                        .apng()
+                        .into_frames()
+                        .collect_frames()
+                        .context("Failed to parse APNG frames")?
+                } else {
+                    let image = image::DynamicImage::from_decoder(decoder)
+                        .context("Failed to parse image data")?;
+
+                    vec![image::Frame::new(image.into_rgba8())]
+                }
+            }
+
+            // Other formats don't support animation, so load them as a single
+            // frame
+            _ => {
+                let image = reader.decode().context("Failed to parse image data")?;
+                vec![image::Frame::new(image.into_rgba8())]
+            }
+        };
+
+        let size = match frames.first() {
+            None => return Err(ErrorMessage::msg("Animated image contains no frames")),
+            Some(frame) => frame.buffer().dimensions()
+        };
 
-        let bytes_rgba8 = image.into_rgba8().into_raw();
+        let frames = frames
+            .into_iter()
+            .map(|frame| {
+                let delay = frame.delay().into();
+                (frame.into_buffer().into_raw(), delay)
+            })
+            .collect();
+
+        Ok(AnimatedImage::new(size.into(), smoothing_mode, frames))
+    }
+
+    #[cfg(any(feature = "image-loading", doc, doctest))]
+    pub fn animated_image_frame(
+        &mut self,
+        image: &mut AnimatedImage,
+        elapsed: Duration
+    ) -> Result<ImageHandle, BacktraceError<ErrorMessage>>
+    {
+        let index = image.frame_index_at(elapsed);
+
+        image.frame_handle(index, |size, smoothing_mode, data| {
+            self.create_image_from_raw_pixels(
+                ImageDataType::RGBA,
+                smoothing_mode,
+                size,
+                data
+            )
+        })
+    }
+
+    #[cfg(feature = "svg-loading")]
+    pub fn create_image_from_svg_bytes(
+        &mut self,
+        svg_bytes: &[u8],
+        size: UVec2,
+        smoothing_mode: ImageSmoothingMode
+    ) -> Result<ImageHandle, BacktraceError<ErrorMessage>>
+    {
+        let tree =
+            resvg::usvg::Tree::from_data(svg_bytes, &resvg::usvg::Options::default())
+                .context("Failed to parse SVG data")?;
+
+        let mut pixmap =
+            resvg::tiny_skia::Pixmap::new(size.x, size.y).ok_or_else(|| {
+                ErrorMessage::msg(format!("Invalid SVG image size: {:?}", size))
+            })?;
+
+        let svg_size = tree.size();
+
+        resvg::render(
+            &tree,
+            resvg::tiny_skia::Transform::from_scale(
+                size.x as f32 / svg_size.width(),
+                size.y as f32 / svg_size.height()
+            ),
+            &mut pixmap.as_mut()
+        );
+
+        // The rendered pixels have premultiplied alpha, but images are blended
+        // using straight alpha
+        let bytes_rgba8: Vec<u8> = pixmap
+            .pixels()
+            .iter()
+            .flat_map(|pixel| {
+                let pixel = pixel.demultiply();
+                [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+            })
+            .collect();
 
         self.create_image_from_raw_pixels(
             ImageDataType::RGBA,
             smoothing_mode,
-            dimensions,
+            size,
             bytes_rgba8.as_slice()
         )
     }
@@ -803,6 +1971,50 @@ impl Renderer2D
         self.context.clear_screen(color);
     }
 
+    #[inline]
+    fn apply_transform(&self, vertex_positions: [Vec2; 3]) -> [Vec2; 3]
+    {
+        if self.transform.is_identity() {
+            vertex_positions
+        } else {
+            vertex_positions.map(|position| self.transform.transform_point(position))
+        }
+    }
+
+    #[inline]
+    pub(crate) fn transform(&self) -> Matrix2D
+    {
+        self.transform
+    }
+
+    #[inline]
+    pub(crate) fn set_transform(&mut self, transform: Matrix2D)
+    {
+        self.transform = transform;
+    }
+
+    pub(crate) fn push_transform(&mut self, transform: Matrix2D)
+    {
+        self.transform_stack.push(self.transform);
+        self.transform = transform.then(&self.transform);
+    }
+
+    pub(crate) fn pop_transform(&mut self)
+    {
+        match self.transform_stack.pop() {
+            Some(transform) => self.transform = transform,
+            None => {
+                log::warn!("pop_transform() called without a matching push_transform()")
+            }
+        }
+    }
+
+    pub(crate) fn reset_transform(&mut self)
+    {
+        self.transform = Matrix2D::IDENTITY;
+        self.transform_stack.clear();
+    }
+
     #[inline]
     fn add_to_render_queue(&mut self, item: RenderQueueItem)
     {
@@ -831,6 +2043,62 @@ impl Renderer2D
         }
     }
 
+    pub(crate) fn draw_polygon_gradient<V: Into<Vec2>>(
+        &mut self,
+        polygon: &Polygon,
+        offset: V,
+        gradient: &Gradient
+    )
+    {
+        let offset = offset.into();
+
+        for triangle in polygon.triangles.iter() {
+            let triangle = triangle.map(|vertex| vertex + offset);
+
+            gradient.split_triangle(&triangle, &mut |positions, colors| {
+                self.draw_triangle_three_color(positions, colors)
+            });
+        }
+    }
+
+    /// Draws geometry which has already been uploaded to the GPU. The render
+    /// queue is flushed first, so that the geometry is drawn in order.
+    pub(crate) fn draw_geometry<V: Into<Vec2>>(
+        &mut self,
+        geometry: &GeometryHandle,
+        offset: V,
+        color: Color
+    )
+    {
+        let vertex_count = geometry.buffers.vertex_count;
+
+        if vertex_count == 0 {
+            return;
+        }
+
+        self.flush_render_queue();
+
+        self.context.use_program(&self.program);
+
+        self.uniforms.set_transform(
+            &self.context,
+            &Matrix2D::translate(offset).then(&self.transform)
+        );
+        self.uniforms.set_tint(&self.context, color);
+
+        geometry.buffers.attribute_buffers.bind(&self.context);
+        self.context.unbind_texture();
+
+        self.context.draw_triangles(
+            GLBlendEnabled::Enabled(self.blend_mode.into()),
+            vertex_count
+        );
+
+        self.uniforms
+            .set_transform(&self.context, &Matrix2D::IDENTITY);
+        self.uniforms.set_tint(&self.context, Color::WHITE);
+    }
+
     #[inline]
     pub(crate) fn draw_triangle_three_color(
         &mut self,
@@ -839,7 +2107,7 @@ impl Renderer2D
     )
     {
         self.add_to_render_queue(RenderQueueItem::TriangleColored {
-            vertex_positions_clockwise,
+            vertex_positions_clockwise: self.apply_transform(vertex_positions_clockwise),
             vertex_colors_clockwise
         })
     }
@@ -854,13 +2122,116 @@ impl Renderer2D
     )
     {
         self.add_to_render_queue(RenderQueueItem::TriangleTextured {
-            vertex_positions_clockwise,
+            vertex_positions_clockwise: self.apply_transform(vertex_positions_clockwise),
             vertex_colors_clockwise,
-            vertex_texture_coords_clockwise,
+            vertex_texture_coords_clockwise: vertex_texture_coords_clockwise
+                .map(|coords| image.texture_coords(coords)),
             texture: image.texture.clone()
         })
     }
 
+    /// Draws a mesh of triangles, which has already been validated. If the
+    /// context supports 32-bit indices, the render queue is flushed, and the
+    /// mesh is drawn using an index buffer, so that each vertex is only
+    /// uploaded once.
+    pub(crate) fn draw_mesh(
+        &mut self,
+        vertex_positions: &[Vec2],
+        vertex_colors: &[Color],
+        image: Option<(&[Vec2], &ImageHandle)>,
+        indices: &[u32]
+    )
+    {
+        if indices.is_empty() {
+            return;
+        }
+
+        if self.index_buffer.is_none() {
+            for triangle in indices.chunks_exact(3) {
+                let triangle = [
+                    triangle[0] as usize,
+                    triangle[1] as usize,
+                    triangle[2] as usize
+                ];
+
+                let positions = triangle.map(|index| vertex_positions[index]);
+                let colors = triangle.map(|index| vertex_colors[index]);
+
+                match image {
+                    None => self.draw_triangle_three_color(positions, colors),
+                    Some((image_coords, image)) => self.draw_triangle_image_tinted(
+                        positions,
+                        colors,
+                        triangle.map(|index| image_coords[index]),
+                        image
+                    )
+                }
+            }
+
+            return;
+        }
+
+        self.flush_render_queue();
+
+        for (index, position) in vertex_positions.iter().enumerate() {
+            match image {
+                None => self.attribute_buffers.append(
+                    position,
+                    &vertex_colors[index],
+                    &Vec2::ZERO,
+                    0.0,
+                    0.0
+                ),
+                Some((image_coords, image)) => self.attribute_buffers.append(
+                    position,
+                    &vertex_colors[index],
+                    &image.texture_coords(image_coords[index]),
+                    1.0,
+                    0.0
+                )
+            }
+        }
+
+        self.context.use_program(&self.program);
+        self.uniforms.set_transform(&self.context, &self.transform);
+
+        self.attribute_buffers.upload_and_clear(&self.context);
+
+        if let Some(index_buffer) = &mut self.index_buffer {
+            index_buffer.set_data(&self.context, indices);
+        }
+
+        match image {
+            None => self.context.unbind_texture(),
+            Some((_, image)) => self.context.bind_texture(&image.texture)
+        }
+
+        self.context.draw_triangles_indexed(
+            GLBlendEnabled::Enabled(self.blend_mode.into()),
+            indices.len()
+        );
+
+        self.uniforms
+            .set_transform(&self.context, &Matrix2D::IDENTITY);
+    }
+
+    pub(crate) fn draw_image_instances(
+        &mut self,
+        image: &ImageHandle,
+        instances: &[ImageInstance]
+    )
+    {
+        if instances.is_empty() {
+            return;
+        }
+
+        self.add_to_render_queue(RenderQueueItem::ImageInstances {
+            instances: instances.to_vec(),
+            image: image.clone(),
+            transform: self.transform
+        })
+    }
+
     #[inline]
     pub(crate) fn draw_text<V: Into<Vec2>>(
         &mut self,
@@ -872,10 +2243,139 @@ impl Renderer2D
         self.add_to_render_queue(RenderQueueItem::FormattedTextBlock {
             position: position.into(),
             color,
-            block: text.clone()
+            shadow: None,
+            outline: None,
+            block: text.clone(),
+            transform: self.transform
+        })
+    }
+
+    #[inline]
+    pub(crate) fn draw_text_with_shadow<V: Into<Vec2>>(
+        &mut self,
+        position: V,
+        color: Color,
+        shadow: &TextShadow,
+        text: &FormattedTextBlock
+    )
+    {
+        self.add_to_render_queue(RenderQueueItem::FormattedTextBlock {
+            position: position.into(),
+            color,
+            shadow: Some(shadow.clone()),
+            outline: None,
+            block: text.clone(),
+            transform: self.transform
+        })
+    }
+
+    #[inline]
+    pub(crate) fn draw_text_outlined<V: Into<Vec2>>(
+        &mut self,
+        position: V,
+        fill_color: Color,
+        outline_color: Color,
+        outline_width: f32,
+        text: &FormattedTextBlock
+    )
+    {
+        self.add_to_render_queue(RenderQueueItem::FormattedTextBlock {
+            position: position.into(),
+            color: fill_color,
+            shadow: None,
+            outline: Some(TextOutline {
+                color: outline_color,
+                width: outline_width
+            }),
+            block: text.clone(),
+            transform: self.transform
         })
     }
 
+    pub(crate) fn rasterize_text_to_image(
+        &mut self,
+        text: &FormattedTextBlock,
+        color: Color,
+        padding: u32
+    ) -> Result<ImageHandle, BacktraceError<ErrorMessage>>
+    {
+        let (size, data) = font_cache::rasterize_text_block(text, color, padding);
+
+        self.create_image_from_raw_pixels(
+            ImageDataType::RGBA,
+            ImageSmoothingMode::NearestNeighbor,
+            size,
+            &data
+        )
+    }
+
+    pub(crate) fn precache_glyphs(&mut self, font: &Font, scale: f32, charset: &str)
+    {
+        let charset: String = charset.chars().filter(|c| !c.is_control()).collect();
+        let text = font.layout_text(&charset, scale, TextOptions::new());
+
+        for glyph in text.iter_glyphs() {
+            // Glyphs are cached separately for each horizontal subpixel
+            // offset, in steps of a tenth of a pixel
+            for offset in -5..=5 {
+                self.glyph_cache.add_to_cache(
+                    &self.context,
+                    glyph,
+                    Vec2::new(offset as f32 / 10.0, 0.0),
+                    &GlyphEffect::None
+                );
+            }
+        }
+
+        if let Err(err) = self.glyph_cache.prepare_for_draw(&self.context) {
+            log::error!("Error updating font texture, continuing anyway: {:?}", err);
+        }
+    }
+
+    pub(crate) fn draw_text_along_path(
+        &mut self,
+        path: &[Vec2],
+        color: Color,
+        text: &FormattedTextBlock
+    )
+    {
+        let first_baseline = match text.iter_lines().next() {
+            None => return,
+            Some(line) => line.baseline_position() + line.ascent()
+        };
+
+        let segments = PathSegments::new(path);
+
+        // There's nowhere to place the glyphs on an empty or degenerate path
+        if segments.is_empty() {
+            return;
+        }
+
+        for glyph in text.iter_glyphs() {
+            let distance = glyph.position_x() + glyph.advance_width() / 2.0;
+
+            let (anchor, direction) = match segments.point_at_distance(distance) {
+                None => continue,
+                Some(point) => point
+            };
+
+            // Rotate the glyph around the point where its horizontal center
+            // meets the first baseline, then move that point onto the path
+            let transform = Matrix2D::translate(Vec2::new(-distance, -first_baseline))
+                .then(&Matrix2D::rotate(direction.y.atan2(direction.x)))
+                .then(&Matrix2D::translate(anchor))
+                .then(&self.transform);
+
+            self.add_to_render_queue(RenderQueueItem::FormattedTextGlyph {
+                position: Vec2::ZERO,
+                color,
+                glyph: glyph.clone(),
+                crop_window: None,
+                transform
+            })
+        }
+    }
+
     #[inline]
     pub(crate) fn draw_text_cropped<V: Into<Vec2>>(
         &mut self,
@@ -896,7 +2396,8 @@ impl Renderer2D
                             position,
                             color,
                             glyph: glyph.clone(),
-                            crop_window: crop_window.clone()
+                            crop_window: Some(crop_window.clone()),
+                            transform: self.transform
                         })
                     }
                 }
@@ -913,7 +2414,7 @@ impl Renderer2D
     )
     {
         self.add_to_render_queue(RenderQueueItem::CircleSectionColored {
-            vertex_positions_clockwise,
+            vertex_positions_clockwise: self.apply_transform(vertex_positions_clockwise),
             vertex_colors_clockwise,
             vertex_normalized_circle_coords_clockwise
         })
@@ -925,6 +2426,13 @@ impl Renderer2D
         // If we change the clip area, we need to draw everything in a queue
         // through the current clip before setting new one.
         self.flush_render_queue();
+
+        if self.stencil_clip {
+            self.stencil_clip = false;
+            self.context.set_enable_stencil_clip(false);
+        }
+
+        self.clip.clone_from(&rect);
         match rect {
             None => self.context.set_enable_scissor(false),
             Some(rect) => {
@@ -939,9 +2447,165 @@ impl Renderer2D
         }
     }
 
+    pub(crate) fn set_transformed_clip(&mut self, rect: Option<Rect>)
+    {
+        let rect = match rect {
+            None => return self.set_clip(None),
+            Some(rect) => rect
+        };
+
+        let x_axis = self.transform.transform_vector((1.0, 0.0));
+        let y_axis = self.transform.transform_vector((0.0, 1.0));
+
+        let axis_aligned =
+            (x_axis.y == 0.0 && y_axis.x == 0.0) || (x_axis.x == 0.0 && y_axis.y == 0.0);
+
+        if !axis_aligned {
+            // The transformed rectangle can't be represented by the scissor
+            // test, so fall back to the stencil buffer
+            return self.set_clip_shape(Some(&ClipShape::Rectangle(rect)));
+        }
+
+        let corner_a = self.transform.transform_point(*rect.top_left());
+        let corner_b = self.transform.transform_point(*rect.bottom_right());
+
+        self.set_clip(Some(Rectangle::from_tuples(
+            (
+                corner_a.x.min(corner_b.x).round() as i32,
+                corner_a.y.min(corner_b.y).round() as i32
+            ),
+            (
+                corner_a.x.max(corner_b.x).round() as i32,
+                corner_a.y.max(corner_b.y).round() as i32
+            )
+        )));
+    }
+
+    pub(crate) fn set_clip_shape(&mut self, shape: Option<&ClipShape>)
+    {
+        self.set_clip(None);
+
+        let shape = match shape {
+            None => return,
+            Some(shape) => shape
+        };
+
+        // Mark the area covered by the shape in the stencil buffer, and then
+        // restrict subsequent drawing to that area
+        self.context.begin_stencil_clip();
+        self.draw_polygon(&shape.to_polygon(), Vec2::ZERO, Color::WHITE);
+        self.flush_render_queue();
+        self.context.end_stencil_clip();
+
+        self.stencil_clip = true;
+    }
+
+    pub(crate) fn set_blend_mode(&mut self, blend_mode: BlendMode)
+    {
+        if self.blend_mode == blend_mode {
+            return;
+        }
+
+        // Draw everything in the queue using the previous blend mode
+        self.flush_render_queue();
+        self.blend_mode = blend_mode;
+    }
+
+    #[inline]
+    pub(crate) fn blend_mode(&self) -> BlendMode
+    {
+        self.blend_mode
+    }
+
     pub(crate) fn capture(&mut self, format: ImageDataType) -> RawBitmapData
     {
         self.flush_render_queue();
         self.context.capture(format)
     }
+
+    pub(crate) fn capture_region(
+        &mut self,
+        format: ImageDataType,
+        region: Rectangle<u32>
+    ) -> RawBitmapData
+    {
+        self.flush_render_queue();
+        self.context.capture_region(format, region)
+    }
+
+    pub(crate) fn capture_async(&mut self, format: ImageDataType) -> PendingCapture
+    {
+        self.flush_render_queue();
+        PendingCapture {
+            capture: self.context.capture_async(format)
+        }
+    }
+
+    pub(crate) fn capture_region_async(
+        &mut self,
+        format: ImageDataType,
+        region: Rectangle<u32>
+    ) -> PendingCapture
+    {
+        self.flush_render_queue();
+        PendingCapture {
+            capture: self.context.capture_region_async(format, region)
+        }
+    }
+
+    pub(crate) fn finish_capture(&mut self, capture: PendingCapture) -> RawBitmapData
+    {
+        capture.capture.finish(&self.context)
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::dimen::Vec2;
+    use crate::renderer2d::PathSegments;
+
+    #[test]
+    fn test_path_segments()
+    {
+        let empty = PathSegments::new(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(None, empty.point_at_distance(0.0));
+
+        let single = PathSegments::new(&[Vec2::new(5.0, 5.0)]);
+        assert!(single.is_empty());
+        assert_eq!(None, single.point_at_distance(0.0));
+
+        let degenerate = PathSegments::new(&[Vec2::new(5.0, 5.0); 3]);
+        assert!(degenerate.is_empty());
+        assert_eq!(None, degenerate.point_at_distance(0.0));
+
+        // The repeated point is skipped
+        let path = PathSegments::new(&[
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 20.0)
+        ]);
+
+        assert!(!path.is_empty());
+        assert_eq!(
+            Some((Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0))),
+            path.point_at_distance(0.0)
+        );
+        assert_eq!(
+            Some((Vec2::new(10.0, 0.0), Vec2::new(1.0, 0.0))),
+            path.point_at_distance(10.0)
+        );
+        assert_eq!(
+            Some((Vec2::new(10.0, 5.0), Vec2::new(0.0, 1.0))),
+            path.point_at_distance(15.0)
+        );
+        assert_eq!(
+            Some((Vec2::new(10.0, 20.0), Vec2::new(0.0, 1.0))),
+            path.point_at_distance(30.0)
+        );
+        assert_eq!(None, path.point_at_distance(-1.0));
+        assert_eq!(None, path.point_at_distance(30.5));
+    }
 }
@@ -17,6 +17,7 @@
  */
 
 uniform sampler2D in_Texture;
+uniform float in_SmoothCircleEdges;
 
 varying vec4 pass_Color;
 varying vec2 pass_TextureCoord;
@@ -30,10 +31,18 @@ void main(void) {
     float texCoordMagSquared = pass_TextureCoord.x * pass_TextureCoord.x
             + pass_TextureCoord.y * pass_TextureCoord.y;
 
-    float circleAlpha = 1.0 - step(1.0, texCoordMagSquared);
+    float circleEdgeWidth = max(fwidth(texCoordMagSquared), 0.000001);
 
-    gl_FragColor = pass_Color * (
+    float circleAlpha = mix(
+            1.0 - step(1.0, texCoordMagSquared),
+            clamp((1.0 - texCoordMagSquared) / circleEdgeWidth + 0.5, 0.0, 1.0),
+            in_SmoothCircleEdges);
+
+    vec4 color = pass_Color * (
             vec4(1.0 - pass_TextureMix - pass_CircleMix)
                     + (texCol * pass_TextureMix)
                     + (vec4(vec3(1.0), circleAlpha)) * pass_CircleMix);
+
+    // Blending is performed using premultiplied alpha
+    gl_FragColor = vec4(color.rgb * color.a, color.a);
 }
\ No newline at end of file
@@ -16,9 +16,10 @@
  *  limitations under the License.
  */
 
-precision mediump float;
+precision highp float;
 
 uniform sampler2D in_Texture;
+uniform float in_SmoothCircleEdges;
 
 in vec4 pass_Color;
 in vec2 pass_TextureCoord;
@@ -34,10 +35,18 @@ void main(void) {
     float texCoordMagSquared = pass_TextureCoord.x * pass_TextureCoord.x
             + pass_TextureCoord.y * pass_TextureCoord.y;
 
-    float circleAlpha = 1.0 - step(1.0, texCoordMagSquared);
+    float circleEdgeWidth = max(fwidth(texCoordMagSquared), 0.000001);
 
-    out_FragColor = pass_Color * (
+    float circleAlpha = mix(
+            1.0 - step(1.0, texCoordMagSquared),
+            clamp((1.0 - texCoordMagSquared) / circleEdgeWidth + 0.5, 0.0, 1.0),
+            in_SmoothCircleEdges);
+
+    vec4 color = pass_Color * (
             vec4(1.0 - pass_TextureMix - pass_CircleMix)
                     + (texCol * pass_TextureMix)
                     + (vec4(vec3(1.0), circleAlpha)) * pass_CircleMix);
+
+    // Blending is performed using premultiplied alpha
+    out_FragColor = vec4(color.rgb * color.a, color.a);
 }
\ No newline at end of file
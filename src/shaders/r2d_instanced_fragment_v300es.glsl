@@ -0,0 +1,34 @@
+#version 300 es
+
+/*
+ *  Copyright 2021 QuantumBadger
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+precision highp float;
+
+uniform sampler2D in_Texture;
+
+in vec4 pass_Color;
+in vec2 pass_TextureCoord;
+
+out vec4 out_FragColor;
+
+void main(void) {
+
+    vec4 color = pass_Color * texture(in_Texture, pass_TextureCoord);
+
+    // Blending is performed using premultiplied alpha
+    out_FragColor = vec4(color.rgb * color.a, color.a);
+}
\ No newline at end of file
@@ -0,0 +1,59 @@
+#version 300 es
+
+/*
+ *  Copyright 2021 QuantumBadger
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+// Draws a copy of an image for each instance. The per-vertex attribute is a
+// corner of the unit square, and the other attributes are per-instance.
+
+in vec2 in_Corner;
+
+in vec2 in_Center;
+in vec2 in_Size;
+in vec2 in_RotationSinCos;
+in vec4 in_Color;
+in vec4 in_TextureRect;
+
+uniform float in_ScaleX;
+uniform float in_ScaleY;
+uniform float in_OffsetY;
+uniform vec3 in_TransformRow0;
+uniform vec3 in_TransformRow1;
+
+out vec4 pass_Color;
+out vec2 pass_TextureCoord;
+
+void main(void) {
+
+    vec2 offset = (in_Corner - vec2(0.5)) * in_Size;
+
+    vec2 local = in_Center + vec2(
+            offset.x * in_RotationSinCos.y - offset.y * in_RotationSinCos.x,
+            offset.x * in_RotationSinCos.x + offset.y * in_RotationSinCos.y);
+
+    vec2 position = vec2(
+            dot(in_TransformRow0, vec3(local, 1.0)),
+            dot(in_TransformRow1, vec3(local, 1.0)));
+
+    gl_Position = vec4(
+            position.x * in_ScaleX - 1.0,
+            position.y * in_ScaleY + in_OffsetY,
+            0.0,
+            1.0);
+
+    pass_Color = in_Color;
+    pass_TextureCoord = mix(in_TextureRect.xy, in_TextureRect.zw, in_Corner);
+}
\ No newline at end of file
@@ -24,6 +24,10 @@ in float in_CircleMix;
 
 uniform float in_ScaleX;
 uniform float in_ScaleY;
+uniform float in_OffsetY;
+uniform vec3 in_TransformRow0;
+uniform vec3 in_TransformRow1;
+uniform vec4 in_Tint;
 
 out vec4 pass_Color;
 out vec2 pass_TextureCoord;
@@ -32,13 +36,17 @@ out float pass_CircleMix;
 
 void main(void) {
 
+    vec2 position = vec2(
+            dot(in_TransformRow0, vec3(in_Position, 1.0)),
+            dot(in_TransformRow1, vec3(in_Position, 1.0)));
+
     gl_Position = vec4(
-            in_Position.x * in_ScaleX - 1.0,
-            in_Position.y * in_ScaleY + 1.0,
+            position.x * in_ScaleX - 1.0,
+            position.y * in_ScaleY + in_OffsetY,
             0.0,
             1.0);
 
-    pass_Color = in_Color;
+    pass_Color = in_Color * in_Tint;
     pass_TextureCoord = in_TextureCoord;
     pass_TextureMix = in_TextureMix;
     pass_CircleMix = in_CircleMix;
@@ -14,10 +14,14 @@
  *  limitations under the License.
  */
 
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
 use num_traits::Zero;
 
-use crate::dimen::{Vec2, Vector2};
+use crate::dimen::{Matrix2D, Vec2, Vector2};
 use crate::numeric::{max, min, PrimitiveZero};
+use crate::renderer2d::GeometryBuffers;
 
 /// A struct representing an axis-aligned rectangle. Two points are stored: the
 /// top left vertex, and the bottom right vertex.
@@ -40,6 +44,7 @@ pub type Rect = Rectangle<f32>;
 /// A struct representing an axis-aligned rectangle. Two points are stored: the
 /// top left vertex, and the bottom right vertex.
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Rectangle<T = f32>
 {
@@ -103,6 +108,15 @@ impl<T: Copy> Rectangle<T>
     {
         RoundedRectangle::from_rectangle(self.clone(), radius)
     }
+
+    /// Returns a new `RoundedRectangle` which has the same sizes of `Self`,
+    /// with a separate radius for each corner.
+    #[inline]
+    pub fn rounded_with_radii(&self, radii: CornerRadii<T>) -> RoundedRectangle<T>
+    {
+        RoundedRectangle::from_rectangle_with_radii(self.clone(), radii)
+    }
+
     /// Returns a vector representing the top right vertex.
     #[inline]
     pub fn top_right(&self) -> Vector2<T>
@@ -146,15 +160,31 @@ impl<T: Copy> Rectangle<T>
     }
 }
 
-impl<T: Copy + std::ops::Neg<Output = T> + std::ops::Add<Output = T>> RoundedRectangle<T>
+impl<
+        T: Copy
+            + std::cmp::PartialOrd
+            + std::ops::Neg<Output = T>
+            + std::ops::Add<Output = T>
+    > RoundedRectangle<T>
 {
     /// returns a `Rectangle` representing the inner rectangle of this rounded
-    /// rectangle.
+    /// rectangle. Each edge is moved inwards by the larger radius of the two
+    /// corners at either end of it.
     pub fn inner(&self) -> Rectangle<T>
     {
+        let radii = &self.radii;
+
         Rectangle::new(
-            *self.top_left() + Vector2::new(self.radius, self.radius),
-            self.bottom_right() + Vector2::new(-self.radius, -self.radius)
+            *self.top_left()
+                + Vector2::new(
+                    max(radii.top_left, radii.bottom_left),
+                    max(radii.top_left, radii.top_right)
+                ),
+            self.bottom_right()
+                + Vector2::new(
+                    -max(radii.top_right, radii.bottom_right),
+                    -max(radii.bottom_left, radii.bottom_right)
+                )
         )
     }
 }
@@ -197,6 +227,33 @@ impl<T: std::cmp::PartialOrd<T> + Copy> Rectangle<T>
             && point.x < self.bottom_right.x
             && point.y < self.bottom_right.y
     }
+
+    /// Returns true if the specified rectangle lies entirely inside this
+    /// rectangle. A rectangle sharing one or more edges with this rectangle
+    /// is still considered to be contained by it.
+    #[inline]
+    #[must_use]
+    pub fn contains_rect(&self, other: &Self) -> bool
+    {
+        other.top_left.x >= self.top_left.x
+            && other.top_left.y >= self.top_left.y
+            && other.bottom_right.x <= self.bottom_right.x
+            && other.bottom_right.y <= self.bottom_right.y
+    }
+
+    /// Returns true if the two rectangles have some area in common. Rectangles
+    /// which only touch along an edge do not overlap.
+    ///
+    /// This is equivalent to `self.intersect(other).is_some()`.
+    #[inline]
+    #[must_use]
+    pub fn overlaps(&self, other: &Self) -> bool
+    {
+        self.top_left.x < other.bottom_right.x
+            && other.top_left.x < self.bottom_right.x
+            && self.top_left.y < other.bottom_right.y
+            && other.top_left.y < self.bottom_right.y
+    }
 }
 
 impl<T: std::cmp::PartialOrd + Copy> Rectangle<T>
@@ -227,6 +284,58 @@ impl<T: std::cmp::PartialOrd + Copy> Rectangle<T>
             None
         }
     }
+
+    /// Returns the smallest rectangle which contains both of the specified
+    /// rectangles.
+    #[inline]
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self
+    {
+        Self {
+            top_left: Vector2::new(
+                min(self.top_left.x, other.top_left.x),
+                min(self.top_left.y, other.top_left.y)
+            ),
+            bottom_right: Vector2::new(
+                max(self.bottom_right.x, other.bottom_right.x),
+                max(self.bottom_right.y, other.bottom_right.y)
+            )
+        }
+    }
+}
+
+impl<T: std::ops::Add<Output = T> + std::ops::Sub<Output = T> + Copy> Rectangle<T>
+{
+    /// Returns a new rectangle, with each edge moved outwards by the specified
+    /// amount. The width and height of the result are each increased by twice
+    /// `amount`.
+    #[inline]
+    #[must_use]
+    pub fn inflate(&self, amount: T) -> Self
+    {
+        Rectangle::new(
+            Vector2::new(self.top_left.x - amount, self.top_left.y - amount),
+            Vector2::new(self.bottom_right.x + amount, self.bottom_right.y + amount)
+        )
+    }
+
+    /// Returns a new rectangle, with each edge moved inwards by the specified
+    /// amount. The width and height of the result are each reduced by twice
+    /// `amount`.
+    ///
+    /// If `amount` is more than half the width or height, the resulting
+    /// rectangle will not have a positive area (see
+    /// [Rectangle::is_positive_area]). For unsigned types, the caller must
+    /// also ensure that the subtraction does not underflow.
+    #[inline]
+    #[must_use]
+    pub fn deflate(&self, amount: T) -> Self
+    {
+        Rectangle::new(
+            Vector2::new(self.top_left.x + amount, self.top_left.y + amount),
+            Vector2::new(self.bottom_right.x - amount, self.bottom_right.y - amount)
+        )
+    }
 }
 
 impl<T: PrimitiveZero> Rectangle<T>
@@ -318,122 +427,103 @@ impl<T: num_traits::AsPrimitive<f32> + Copy> Rectangle<T>
     }
 }
 
-/// A struct representing a polygon.
-#[derive(Debug, Clone)]
-pub struct Polygon
+/// A struct representing an axis-aligned ellipse, stored as a center point and
+/// the horizontal and vertical radii.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Ellipse
 {
-    pub(crate) triangles: Vec<[Vec2; 3]>
+    center: Vec2,
+    radii: Vec2
 }
 
-impl Polygon
+impl AsRef<Ellipse> for Ellipse
 {
-    /// Generate a new polygon given points that describe it's outline.
-    ///
-    /// The points must be in either clockwise or couter-clockwise order.
-    pub fn new<Point: Into<Vec2> + Copy>(vertices: &[Point]) -> Self
+    fn as_ref(&self) -> &Self
     {
-        // We have to flatten the vertices in order for
-        // [earcutr](https://github.com/frewsxcv/earcutr/) to accept it.
-        // In the future, we can add a triangulation algorithm directly into Speed2D if
-        // performance is an issue, but for now, this is simpler and easier
-        let mut flattened = Vec::with_capacity(vertices.len() * 2);
-
-        for vertex in vertices {
-            let vertex: Vec2 = (*vertex).into();
-
-            flattened.push(vertex.x);
-            flattened.push(vertex.y);
-        }
-
-        let mut triangulation = earcutr::earcut(&flattened, &Vec::new(), 2);
-        let mut triangles = Vec::with_capacity(triangulation.len() / 3);
-
-        while !triangulation.is_empty() {
-            triangles.push([
-                vertices[triangulation.pop().unwrap()].into(),
-                vertices[triangulation.pop().unwrap()].into(),
-                vertices[triangulation.pop().unwrap()].into()
-            ])
-        }
-
-        Polygon { triangles }
+        self
     }
 }
 
-#[cfg(test)]
-mod test
+impl Ellipse
 {
-    use crate::shape::URect;
-
-    #[test]
-    pub fn test_intersect_1()
+    /// Constructs a new `Ellipse` with the specified center point, and the
+    /// specified horizontal (`x`) and vertical (`y`) radii.
+    #[inline]
+    pub fn new<C: Into<Vec2>, R: Into<Vec2>>(center: C, radii: R) -> Self
     {
-        let r1 = URect::from_tuples((100, 100), (200, 200));
-        let r2 = URect::from_tuples((100, 300), (200, 400));
-        let r3 = URect::from_tuples((125, 50), (175, 500));
-
-        assert_eq!(None, r1.intersect(&r2));
-
-        assert_eq!(
-            Some(URect::from_tuples((125, 100), (175, 200))),
-            r1.intersect(&r3)
-        );
+        Ellipse {
+            center: center.into(),
+            radii: radii.into()
+        }
+    }
 
-        assert_eq!(
-            Some(URect::from_tuples((125, 300), (175, 400))),
-            r2.intersect(&r3)
-        );
+    /// Constructs the largest `Ellipse` which fits inside the specified
+    /// rectangle.
+    #[inline]
+    pub fn from_bounding_rect(rect: &Rect) -> Self
+    {
+        Ellipse {
+            center: (*rect.top_left() + *rect.bottom_right()) / 2.0,
+            radii: rect.size() / 2.0
+        }
+    }
 
-        assert_eq!(Some(r1.clone()), r1.intersect(&r1));
-        assert_eq!(Some(r2.clone()), r2.intersect(&r2));
-        assert_eq!(Some(r3.clone()), r3.intersect(&r3));
+    /// Returns the center point of the ellipse.
+    #[inline]
+    pub fn center(&self) -> Vec2
+    {
+        self.center
     }
 
-    #[test]
-    pub fn test_intersect_2()
+    /// Returns the horizontal (`x`) and vertical (`y`) radii of the ellipse.
+    #[inline]
+    pub fn radii(&self) -> Vec2
     {
-        let r1 = URect::from_tuples((100, 100), (200, 200));
-        let r2 = URect::from_tuples((100, 200), (200, 300));
+        self.radii
+    }
 
-        assert_eq!(None, r1.intersect(&r2));
+    /// Returns the smallest rectangle which contains this ellipse.
+    #[inline]
+    pub fn bounding_rect(&self) -> Rect
+    {
+        Rect::new(self.center - self.radii, self.center + self.radii)
     }
-}
 
-///////////////////////////////////
+    /// Returns a new ellipse, moved by the specified offset.
+    #[inline]
+    pub fn with_offset(&self, offset: impl Into<Vec2>) -> Self
+    {
+        Ellipse {
+            center: self.center + offset.into(),
+            radii: self.radii
+        }
+    }
 
-/// A struct representing an axis-aligned rounded rectangle. Two points and an
-/// 'u32' are stored: the top left vertex, the bottom right vertex and the
-/// radius of the rounded corners.
-///
-/// Alias for a rectangle with u32 coordinates.
-pub type URoundRect = RoundedRectangle<u32>;
+    /// Returns true if the specified point is inside this ellipse. Points on
+    /// the edge of the ellipse are considered to be inside.
+    #[must_use]
+    pub fn contains(&self, point: Vec2) -> bool
+    {
+        if self.radii.x <= 0.0 || self.radii.y <= 0.0 {
+            return false;
+        }
 
-/// A struct representing an axis-aligned rounded rectangle. Two points and an
-/// 'i32' are stored: the top left vertex, the bottom right vertex and the
-/// radius of the rounded corners.
-///
-/// Alias for a rectangle with i32 coordinates.
-pub type IRoundRect = RoundedRectangle<i32>;
+        let offset = point - self.center;
+        let normalized = Vec2::new(offset.x / self.radii.x, offset.y / self.radii.y);
 
-/// A struct representing an axis-aligned rounded rectangle. Two points and an
-/// 'f32' are stored: the top left vertex, the bottom right vertex and the
-/// radius of the rounded corners.
-///
-/// Alias for a rectangle with f32 coordinates.
-pub type RoundRect = RoundedRectangle<f32>;
+        normalized.magnitude_squared() <= 1.0
+    }
+}
 
-/// A struct representing an axis-aligned rounded rectangle. Two points and a
-/// value of type 'T' are stored: the top left vertex, the bottom right vertex
-/// and the radius of the rounded corners.
-#[derive(Debug, PartialEq, Eq, Clone)]
-#[repr(C)]
-pub struct RoundedRectangle<T = f32>
+/// A struct representing a circle, stored as a center point and a radius.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Circle
 {
-    rect: Rectangle<T>,
-    radius: T
+    center: Vec2,
+    radius: f32
 }
 
-impl<T> AsRef<RoundedRectangle<T>> for RoundedRectangle<T>
+impl AsRef<Circle> for Circle
 {
     fn as_ref(&self) -> &Self
     {
@@ -441,141 +531,2480 @@ impl<T> AsRef<RoundedRectangle<T>> for RoundedRectangle<T>
     }
 }
 
-impl<T> RoundedRectangle<T>
+impl<C: Into<Vec2>> From<(C, f32)> for Circle
 {
-    /// Constructs a new `RoundedRectangle`. The top left vertex must be above
-    /// and to the left of the bottom right vertex. A negative radius won't be
-    /// checked. A big radius (larger than half the width or height)
-    /// might produce unexpected behavior but it won't be checked.
+    /// Constructs a circle from a tuple containing its center point and
+    /// radius.
     #[inline]
-    pub const fn new(top_left: Vector2<T>, bottom_right: Vector2<T>, radius: T) -> Self
+    fn from((center, radius): (C, f32)) -> Self
     {
-        RoundedRectangle {
-            rect: Rectangle::new(top_left, bottom_right),
-            radius
-        }
+        Circle::new(center, radius)
     }
+}
 
-    /// Constructs a new `RoundedRectangle`. The top left vertex must be above
-    /// and to the left of the bottom right vertex. A negative radius won't be
-    /// checked. A big radius (larger than half the width or height)
-    /// might produce unexpected behavior but it won't be checked.
-    ///
-    /// Note: a negative radius won't be checked at runtime.
+impl From<&Circle> for Circle
+{
     #[inline]
-    pub fn from_tuples(top_left: (T, T), bottom_right: (T, T), radius: T) -> Self
+    fn from(circle: &Circle) -> Self
     {
-        RoundedRectangle {
-            rect: Rectangle::from_tuples(top_left, bottom_right),
-            radius
-        }
+        circle.clone()
     }
+}
 
-    /// Constructs a new `RoundedRectangle` from a `Rectangle` and a radius.
-    /// A negative radius won't be checked.
-    /// A big radius (larger than half the width or height) might produce
-    /// unexpected behavior but it won't be checked.
+impl Circle
+{
+    /// Constructs a new `Circle` with the specified center point and radius.
     #[inline]
-    pub fn from_rectangle(rect: Rectangle<T>, radius: T) -> Self
+    pub fn new<C: Into<Vec2>>(center: C, radius: f32) -> Self
     {
-        RoundedRectangle { rect, radius }
+        Circle {
+            center: center.into(),
+            radius
+        }
     }
 
-    /// Returns a reference to the top left vertex.
+    /// Returns the center point of the circle.
     #[inline]
-    pub const fn top_left(&self) -> &Vector2<T>
+    pub fn center(&self) -> Vec2
     {
-        &self.rect.top_left
+        self.center
     }
 
-    /// Returns a reference to the bottom right vertex.
+    /// Returns the radius of the circle.
     #[inline]
-    pub const fn bottom_right(&self) -> &Vector2<T>
+    pub fn radius(&self) -> f32
     {
-        &self.rect.bottom_right
+        self.radius
     }
-}
 
-impl<T: Copy> RoundedRectangle<T>
-{
-    /// Returns a vector representing the top right vertex.
+    /// Returns the smallest rectangle which contains this circle.
     #[inline]
-    pub fn top_right(&self) -> Vector2<T>
+    pub fn bounding_rect(&self) -> Rect
     {
-        Vector2::new(self.rect.bottom_right.x, self.rect.top_left.y)
+        let radii = Vec2::new(self.radius, self.radius);
+        Rect::new(self.center - radii, self.center + radii)
     }
 
-    /// Returns a vector representing the bottom left vertex.
+    /// Returns a new circle, moved by the specified offset.
     #[inline]
-    pub fn bottom_left(&self) -> Vector2<T>
+    pub fn with_offset(&self, offset: impl Into<Vec2>) -> Self
     {
-        Vector2::new(self.rect.top_left.x, self.rect.bottom_right.y)
+        Circle {
+            center: self.center + offset.into(),
+            radius: self.radius
+        }
     }
 
-    /// Returns the radius of the rounded corners.
-    #[inline]
-    pub fn radius(&self) -> T
+    /// Returns true if the specified point is inside this circle. Points on
+    /// the edge of the circle are considered to be inside.
+    #[must_use]
+    pub fn contains(&self, point: Vec2) -> bool
     {
-        self.radius
+        self.radius > 0.0
+            && (point - self.center).magnitude_squared() <= self.radius * self.radius
     }
 
-    /// Returns the x value of the left border
-    #[inline]
-    pub fn left(&self) -> T
+    /// Returns true if this circle overlaps the specified circle. Circles
+    /// which touch at a single point are considered to overlap.
+    #[must_use]
+    pub fn intersects(&self, other: &Circle) -> bool
     {
-        self.rect.top_left.x
+        if self.radius <= 0.0 || other.radius <= 0.0 {
+            return false;
+        }
+
+        let radii = self.radius + other.radius;
+
+        (other.center - self.center).magnitude_squared() <= radii * radii
     }
+}
 
-    /// Returns the x value of the right border
+impl From<&Circle> for Ellipse
+{
     #[inline]
-    pub fn right(&self) -> T
+    fn from(circle: &Circle) -> Self
     {
-        self.rect.bottom_right.x
+        Ellipse::new(circle.center, (circle.radius, circle.radius))
     }
+}
 
-    /// Returns the y value of the top border
-    #[inline]
-    pub fn top(&self) -> T
+/// A struct representing a rectangle which has been rotated around its center
+/// point, also known as an oriented bounding box. It is stored as a center
+/// point, a size, and a clockwise rotation in radians.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RotatedRectangle
+{
+    center: Vec2,
+    size: Vec2,
+    rotation: f32
+}
+
+impl AsRef<RotatedRectangle> for RotatedRectangle
+{
+    fn as_ref(&self) -> &Self
     {
-        self.rect.top_left.y
+        self
     }
+}
 
-    /// Returns the y value of the bottom border
+impl RotatedRectangle
+{
+    /// Constructs a new `RotatedRectangle` with the specified center point,
+    /// width and height, and clockwise rotation in radians.
     #[inline]
-    pub fn bottom(&self) -> T
+    pub fn new<C: Into<Vec2>, S: Into<Vec2>>(center: C, size: S, rotation: f32) -> Self
     {
-        self.rect.bottom_right.y
+        RotatedRectangle {
+            center: center.into(),
+            size: size.into(),
+            rotation
+        }
     }
 
-    /// Returns a `Rectangle` representing the rectangle that encloses this
-    /// rounded rectangle.
+    /// Constructs a new `RotatedRectangle` by rotating the specified
+    /// rectangle clockwise around its center, by an angle in radians.
     #[inline]
-    pub fn as_rectangle(&self) -> &Rectangle<T>
+    pub fn from_rectangle(rect: &Rect, rotation: f32) -> Self
     {
-        &self.rect
+        RotatedRectangle::new(
+            (*rect.top_left() + *rect.bottom_right()) / 2.0,
+            rect.size(),
+            rotation
+        )
     }
-}
 
-impl<T: std::ops::Sub<Output = T> + Copy> RoundedRectangle<T>
-{
-    /// Returns the width of the rounded rectangle.
+    /// Returns the center point of the rectangle.
     #[inline]
-    pub fn width(&self) -> T
+    pub fn center(&self) -> Vec2
     {
-        self.rect.bottom_right.x - self.rect.top_left.x
+        self.center
     }
 
-    /// Returns the height of the rounded rectangle.
+    /// Returns the width and height of the rectangle, before it is rotated.
     #[inline]
-    pub fn height(&self) -> T
+    pub fn size(&self) -> Vec2
     {
-        self.rect.bottom_right.y - self.rect.top_left.y
+        self.size
     }
 
-    /// Returns a `Vector2` containing the width and height of the rounded
-    /// rectangle.
+    /// Returns the clockwise rotation of the rectangle, in radians.
     #[inline]
-    pub fn size(&self) -> Vector2<T>
+    pub fn rotation(&self) -> f32
+    {
+        self.rotation
+    }
+
+    /// Returns the four vertices of the rectangle, in clockwise order. The
+    /// first vertex is the one which would be at the top left if the
+    /// rectangle were not rotated.
+    pub fn vertices(&self) -> [Vec2; 4]
+    {
+        let (x_axis, y_axis) = self.axes();
+        let x = x_axis * (self.size.x / 2.0);
+        let y = y_axis * (self.size.y / 2.0);
+
+        [
+            self.center - x - y,
+            self.center + x - y,
+            self.center + x + y,
+            self.center - x + y
+        ]
+    }
+
+    /// Returns the smallest axis-aligned rectangle which contains this
+    /// rectangle.
+    pub fn bounding_rect(&self) -> Rect
+    {
+        let (x_axis, y_axis) = self.axes();
+
+        let extent = Vec2::new(
+            (x_axis.x * self.size.x).abs() + (y_axis.x * self.size.y).abs(),
+            (x_axis.y * self.size.x).abs() + (y_axis.y * self.size.y).abs()
+        ) / 2.0;
+
+        Rect::new(self.center - extent, self.center + extent)
+    }
+
+    /// Returns a new rotated rectangle, moved by the specified offset.
+    #[inline]
+    pub fn with_offset(&self, offset: impl Into<Vec2>) -> Self
+    {
+        RotatedRectangle {
+            center: self.center + offset.into(),
+            size: self.size,
+            rotation: self.rotation
+        }
+    }
+
+    /// Returns true if the specified point is inside this rectangle. Points on
+    /// the edges of the rectangle are considered to be inside.
+    #[must_use]
+    pub fn contains(&self, point: Vec2) -> bool
+    {
+        let (x_axis, y_axis) = self.axes();
+        let offset = point - self.center;
+
+        dot(offset, x_axis).abs() <= self.size.x / 2.0
+            && dot(offset, y_axis).abs() <= self.size.y / 2.0
+    }
+
+    /// Returns true if this rectangle overlaps the specified rectangle.
+    /// Rectangles which only touch along an edge or at a vertex are
+    /// considered to overlap.
+    #[must_use]
+    pub fn intersects(&self, other: &RotatedRectangle) -> bool
+    {
+        // Separating axis theorem: two convex shapes are disjoint if and only if
+        // there's an axis on which their projections don't overlap. For two
+        // rectangles, only the normals of their edges need to be checked.
+        let (self_x, self_y) = self.axes();
+        let (other_x, other_y) = other.axes();
+
+        let offset = other.center - self.center;
+
+        [self_x, self_y, other_x, other_y].iter().all(|axis| {
+            dot(offset, *axis).abs()
+                <= self.projected_half_extent(*axis) + other.projected_half_extent(*axis)
+        })
+    }
+
+    /// Returns unit vectors along the rotated horizontal and vertical edges.
+    #[inline]
+    fn axes(&self) -> (Vec2, Vec2)
+    {
+        let (sin, cos) = self.rotation.sin_cos();
+        (Vec2::new(cos, sin), Vec2::new(-sin, cos))
+    }
+
+    /// Returns half the length of this rectangle when projected onto the
+    /// specified unit vector.
+    #[inline]
+    fn projected_half_extent(&self, axis: Vec2) -> f32
+    {
+        let (x_axis, y_axis) = self.axes();
+
+        ((dot(x_axis, axis) * self.size.x).abs()
+            + (dot(y_axis, axis) * self.size.y).abs())
+            / 2.0
+    }
+}
+
+/// A struct representing a straight line between two points.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LineSegment
+{
+    start: Vec2,
+    end: Vec2
+}
+
+impl AsRef<LineSegment> for LineSegment
+{
+    fn as_ref(&self) -> &Self
+    {
+        self
+    }
+}
+
+impl LineSegment
+{
+    /// Constructs a new `LineSegment` between the specified points.
+    #[inline]
+    pub fn new<S: Into<Vec2>, E: Into<Vec2>>(start: S, end: E) -> Self
+    {
+        LineSegment {
+            start: start.into(),
+            end: end.into()
+        }
+    }
+
+    /// Returns the start point of the line segment.
+    #[inline]
+    pub fn start(&self) -> Vec2
+    {
+        self.start
+    }
+
+    /// Returns the end point of the line segment.
+    #[inline]
+    pub fn end(&self) -> Vec2
+    {
+        self.end
+    }
+
+    /// Returns the length of the line segment.
+    #[inline]
+    pub fn length(&self) -> f32
+    {
+        (self.end - self.start).magnitude()
+    }
+
+    /// Returns the smallest rectangle which contains this line segment.
+    #[inline]
+    pub fn bounding_rect(&self) -> Rect
+    {
+        Rect::new(
+            Vec2::new(self.start.x.min(self.end.x), self.start.y.min(self.end.y)),
+            Vec2::new(self.start.x.max(self.end.x), self.start.y.max(self.end.y))
+        )
+    }
+
+    /// Returns a new line segment, moved by the specified offset.
+    #[inline]
+    pub fn with_offset(&self, offset: impl Into<Vec2>) -> Self
+    {
+        let offset = offset.into();
+        LineSegment::new(self.start + offset, self.end + offset)
+    }
+
+    /// Returns the point on this line segment which is closest to the
+    /// specified point.
+    #[must_use]
+    pub fn closest_point(&self, point: Vec2) -> Vec2
+    {
+        let direction = self.end - self.start;
+        let length_squared = direction.magnitude_squared();
+
+        if length_squared == 0.0 {
+            return self.start;
+        }
+
+        let t = dot(point - self.start, direction) / length_squared;
+
+        self.start + direction * t.clamp(0.0, 1.0)
+    }
+
+    /// Returns the shortest distance between the specified point and any point
+    /// on this line segment.
+    #[must_use]
+    pub fn distance_to_point(&self, point: Vec2) -> f32
+    {
+        (point - self.closest_point(point)).magnitude()
+    }
+
+    /// Returns the point at which this line segment crosses the specified line
+    /// segment, or `None` if they don't meet. Segments which touch at an end
+    /// point are considered to intersect.
+    ///
+    /// If the segments are collinear and overlap, there is more than one
+    /// intersection, and the one closest to the start of `self` is returned.
+    #[must_use]
+    pub fn intersection(&self, other: &LineSegment) -> Option<Vec2>
+    {
+        let d1 = self.end - self.start;
+        let d2 = other.end - other.start;
+        let offset = other.start - self.start;
+
+        let denominator = cross(d1, d2);
+
+        if denominator != 0.0 {
+            let t = cross(offset, d2) / denominator;
+            let u = cross(offset, d1) / denominator;
+
+            return if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+                Some(self.start + d1 * t)
+            } else {
+                None
+            };
+        }
+
+        // The segments are parallel, or at least one of them is a single point
+        let length_squared = d1.magnitude_squared();
+
+        if length_squared == 0.0 {
+            return if other.closest_point(self.start) == self.start {
+                Some(self.start)
+            } else {
+                None
+            };
+        }
+
+        if cross(offset, d1) != 0.0 {
+            return None;
+        }
+
+        // The segments are collinear, so find the overlap of the other segment
+        // with this one, in terms of the distance along this segment
+        let t0 = dot(offset, d1) / length_squared;
+        let t1 = dot(other.end - self.start, d1) / length_squared;
+
+        let start = t0.min(t1).max(0.0);
+        let end = t0.max(t1).min(1.0);
+
+        if start <= end {
+            Some(self.start + d1 * start)
+        } else {
+            None
+        }
+    }
+}
+
+/// The rule used to determine which regions of a self-intersecting polygon, or
+/// a polygon with holes, are filled.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FillRule
+{
+    /// A point is inside the polygon if a ray from that point crosses the
+    /// outline an odd number of times. Holes are always cut out, regardless of
+    /// the order of their vertices.
+    EvenOdd,
+
+    /// A point is inside the polygon if the outline winds around that point a
+    /// non-zero number of times. Holes are only cut out if their vertices are
+    /// ordered in the opposite direction to the outer outline.
+    NonZero
+}
+
+impl FillRule
+{
+    #[inline]
+    fn is_inside(&self, winding_number: i32) -> bool
+    {
+        match self {
+            FillRule::EvenOdd => winding_number % 2 != 0,
+            FillRule::NonZero => winding_number != 0
+        }
+    }
+}
+
+/// A struct representing a polygon.
+///
+/// When the `serde` feature is enabled, only the outlines of the polygon and
+/// its fill rule are serialized. The polygon is triangulated again when it is
+/// deserialized.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(from = "PolygonOutlines", into = "PolygonOutlines")
+)]
+pub struct Polygon
+{
+    pub(crate) triangles: Vec<[Vec2; 3]>,
+    contours: Vec<Vec<Vec2>>,
+    fill_rule: FillRule
+}
+
+/// The serialized form of a [Polygon].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PolygonOutlines
+{
+    contours: Vec<Vec<Vec2>>,
+    fill_rule: FillRule
+}
+
+#[cfg(feature = "serde")]
+impl From<PolygonOutlines> for Polygon
+{
+    fn from(outlines: PolygonOutlines) -> Self
+    {
+        Polygon::new_with_contours(&outlines.contours, outlines.fill_rule)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Polygon> for PolygonOutlines
+{
+    fn from(polygon: Polygon) -> Self
+    {
+        PolygonOutlines {
+            contours: polygon.contours,
+            fill_rule: polygon.fill_rule
+        }
+    }
+}
+
+impl Polygon
+{
+    /// Generate a new polygon given points that describe it's outline.
+    ///
+    /// The points must be in either clockwise or couter-clockwise order.
+    pub fn new<Point: Into<Vec2> + Copy>(vertices: &[Point]) -> Self
+    {
+        // We have to flatten the vertices in order for
+        // [earcutr](https://github.com/frewsxcv/earcutr/) to accept it.
+        // In the future, we can add a triangulation algorithm directly into Speed2D if
+        // performance is an issue, but for now, this is simpler and easier
+        let mut flattened = Vec::with_capacity(vertices.len() * 2);
+        let mut contour = Vec::with_capacity(vertices.len());
+
+        for vertex in vertices {
+            let vertex: Vec2 = (*vertex).into();
+
+            flattened.push(vertex.x);
+            flattened.push(vertex.y);
+            contour.push(vertex);
+        }
+
+        let mut triangulation = earcutr::earcut(&flattened, &Vec::new(), 2);
+        let mut triangles = Vec::with_capacity(triangulation.len() / 3);
+
+        while !triangulation.is_empty() {
+            triangles.push([
+                vertices[triangulation.pop().unwrap()].into(),
+                vertices[triangulation.pop().unwrap()].into(),
+                vertices[triangulation.pop().unwrap()].into()
+            ])
+        }
+
+        // The outline doesn't intersect itself, so both fill rules give the
+        // same result
+        Polygon {
+            triangles,
+            contours: vec![contour],
+            fill_rule: FillRule::NonZero
+        }
+    }
+
+    /// Generate a new polygon given points that describe its outline. The
+    /// outline may intersect itself, in which case the specified fill rule
+    /// determines which regions are filled.
+    ///
+    /// For example, the center of a five-pointed star drawn using a single
+    /// outline is filled when using [FillRule::NonZero], but left empty when
+    /// using [FillRule::EvenOdd].
+    pub fn new_with_fill_rule<Point: Into<Vec2> + Copy>(
+        vertices: &[Point],
+        fill_rule: FillRule
+    ) -> Self
+    {
+        Self::new_with_holes(vertices, &[] as &[&[Point]], fill_rule)
+    }
+
+    /// Generate a new polygon given points that describe its outer outline,
+    /// and the outlines of any holes inside it. Any of the outlines may
+    /// intersect themselves or each other, and the specified fill rule
+    /// determines which regions are filled.
+    pub fn new_with_holes<Point, Hole>(
+        outer: &[Point],
+        holes: &[Hole],
+        fill_rule: FillRule
+    ) -> Self
+    where
+        Point: Into<Vec2> + Copy,
+        Hole: AsRef<[Point]>
+    {
+        let contours: Vec<&[Point]> = std::iter::once(outer)
+            .chain(holes.iter().map(|hole| hole.as_ref()))
+            .collect();
+
+        Self::new_with_contours(&contours, fill_rule)
+    }
+
+    /// Generate a new polygon given any number of closed outlines. The
+    /// outlines may be disjoint, nested, or overlapping, and the specified
+    /// fill rule determines which regions are filled.
+    ///
+    /// This is useful for shapes such as letters, where a single glyph may
+    /// consist of several separate pieces, each of which may have counters.
+    pub fn new_with_contours<Point, Contour>(
+        contours: &[Contour],
+        fill_rule: FillRule
+    ) -> Self
+    where
+        Point: Into<Vec2> + Copy,
+        Contour: AsRef<[Point]>
+    {
+        let contours: Vec<Vec<Vec2>> = contours
+            .iter()
+            .map(|contour| {
+                contour
+                    .as_ref()
+                    .iter()
+                    .map(|vertex| (*vertex).into())
+                    .collect()
+            })
+            .collect();
+
+        let mut edges = Vec::new();
+
+        for contour in &contours {
+            for (i, start) in contour.iter().enumerate() {
+                if let Some(edge) =
+                    PolygonEdge::new(*start, contour[(i + 1) % contour.len()])
+                {
+                    edges.push(edge);
+                }
+            }
+        }
+
+        Polygon {
+            triangles: tessellate_edges(&edges, fill_rule),
+            contours,
+            fill_rule
+        }
+    }
+
+    /// Returns a polygon covering the outline of this polygon, drawn using the
+    /// specified stroke options. Every contour used to construct this polygon
+    /// (including the outlines of any holes) is treated as closed.
+    #[must_use]
+    pub fn to_outline_polygon(&self, options: &StrokeOptions) -> Polygon
+    {
+        let mut path = Path::new();
+
+        for contour in &self.contours {
+            let mut vertices = contour.iter();
+
+            if let Some(first) = vertices.next() {
+                path = vertices
+                    .fold(path.move_to(*first), |path, vertex| path.line_to(*vertex))
+                    .close();
+            }
+        }
+
+        path.to_stroked_polygon_with_options(options)
+    }
+
+    /// Returns true if the specified point is inside the filled area of this
+    /// polygon, taking into account any holes and the fill rule used to
+    /// construct it. Points lying exactly on an edge are considered to be
+    /// inside. A polygon with no area, for example one scaled to zero height,
+    /// contains no points.
+    #[must_use]
+    pub fn contains(&self, point: impl Into<Vec2>) -> bool
+    {
+        let point = point.into();
+
+        self.triangles.iter().any(|[a, b, c]| {
+            // Every point on the line through a zero-area triangle would
+            // otherwise lie on all three of its edges
+            if triangle_signed_area(*a, *b, *c) == 0.0 {
+                return false;
+            }
+
+            let ab = triangle_signed_area(*a, *b, point);
+            let bc = triangle_signed_area(*b, *c, point);
+            let ca = triangle_signed_area(*c, *a, point);
+
+            (ab >= 0.0 && bc >= 0.0 && ca >= 0.0) || (ab <= 0.0 && bc <= 0.0 && ca <= 0.0)
+        })
+    }
+
+    /// Returns the area of the filled region of this polygon. Holes, and
+    /// regions left unfilled by the fill rule, do not contribute to the area.
+    #[must_use]
+    pub fn area(&self) -> f32
+    {
+        self.triangles
+            .iter()
+            .map(|[a, b, c]| triangle_signed_area(*a, *b, *c).abs())
+            .sum()
+    }
+
+    /// Returns the centroid (center of mass) of the filled region of this
+    /// polygon, or `None` if the polygon has no area.
+    ///
+    /// Note that for concave polygons, or polygons with holes, the centroid
+    /// may lie outside the filled region.
+    #[must_use]
+    pub fn centroid(&self) -> Option<Vec2>
+    {
+        let mut total_area = 0.0;
+        let mut weighted_sum = Vec2::ZERO;
+
+        for [a, b, c] in &self.triangles {
+            let area = triangle_signed_area(*a, *b, *c).abs();
+            total_area += area;
+            weighted_sum += (*a + *b + *c) * (area / 3.0);
+        }
+
+        if total_area > 0.0 {
+            Some(weighted_sum / total_area)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the smallest axis-aligned rectangle containing every vertex
+    /// used to construct this polygon, or `None` if the polygon has no
+    /// vertices.
+    #[must_use]
+    pub fn bounding_rect(&self) -> Option<Rect>
+    {
+        let mut vertices = self.contours.iter().flatten();
+        let first = *vertices.next()?;
+
+        Some(vertices.fold(Rect::new(first, first), |rect, vertex| {
+            Rect::new(
+                Vec2::new(rect.left().min(vertex.x), rect.top().min(vertex.y)),
+                Vec2::new(rect.right().max(vertex.x), rect.bottom().max(vertex.y))
+            )
+        }))
+    }
+}
+
+#[inline]
+fn triangle_signed_area(a: Vec2, b: Vec2, c: Vec2) -> f32
+{
+    let ab = b - a;
+    let ac = c - a;
+    (ab.x * ac.y - ab.y * ac.x) * 0.5
+}
+
+/// Represents a handle for a [Polygon] which has been uploaded to the GPU, so
+/// that it can be drawn repeatedly without being sent again each frame. See
+/// [crate::Graphics2D::create_geometry] and
+/// [crate::Graphics2D::draw_geometry].
+///
+/// Note: this handle can only be used in the graphics context in which it was
+/// created.
+#[derive(Clone)]
+pub struct GeometryHandle
+{
+    pub(crate) buffers: Rc<GeometryBuffers>
+}
+
+impl GeometryHandle
+{
+    /// Returns the number of triangles making up this geometry.
+    pub fn triangle_count(&self) -> usize
+    {
+        self.buffers.vertex_count / 3
+    }
+}
+
+impl Debug for GeometryHandle
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result
+    {
+        f.debug_struct("GeometryHandle")
+            .field("triangle_count", &self.triangle_count())
+            .finish()
+    }
+}
+
+/// The distances from each edge of a rectangle to a point inside it, for
+/// example the size of the fixed borders used by
+/// [crate::Graphics2D::draw_image_nine_slice].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Insets
+{
+    left: f32,
+    top: f32,
+    right: f32,
+    bottom: f32
+}
+
+impl Insets
+{
+    /// Creates a new set of insets, with a distance for each edge.
+    #[inline]
+    pub const fn new(left: f32, top: f32, right: f32, bottom: f32) -> Self
+    {
+        Insets {
+            left,
+            top,
+            right,
+            bottom
+        }
+    }
+
+    /// Creates a new set of insets, with the same distance for every edge.
+    #[inline]
+    pub const fn uniform(inset: f32) -> Self
+    {
+        Insets::new(inset, inset, inset, inset)
+    }
+
+    /// Returns the distance from the left edge.
+    #[inline]
+    pub const fn left(&self) -> f32
+    {
+        self.left
+    }
+
+    /// Returns the distance from the top edge.
+    #[inline]
+    pub const fn top(&self) -> f32
+    {
+        self.top
+    }
+
+    /// Returns the distance from the right edge.
+    #[inline]
+    pub const fn right(&self) -> f32
+    {
+        self.right
+    }
+
+    /// Returns the distance from the bottom edge.
+    #[inline]
+    pub const fn bottom(&self) -> f32
+    {
+        self.bottom
+    }
+}
+
+/// The maximum distance, in pixels, between a curve and the straight line
+/// segments used to approximate it.
+const PATH_FLATTENING_TOLERANCE: f32 = 0.1;
+
+#[derive(Debug, Clone)]
+struct PathContour
+{
+    points: Vec<Vec2>,
+    closed: bool
+}
+
+/// The shape used to join two line segments when stroking a path.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum LineJoin
+{
+    /// The outer edges of the two segments are extended until they meet,
+    /// forming a sharp corner. If the corner would extend further than the
+    /// miter limit, a bevel join is used instead.
+    Miter,
+    /// The corner is rounded, with a radius of half the line thickness.
+    Round,
+    /// The outer corners of the two segments are joined with a straight line.
+    Bevel
+}
+
+/// The shape used at the start and end of an unclosed path when stroking it.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum LineCap
+{
+    /// The line ends exactly at the end point.
+    Butt,
+    /// The line ends with a semicircle, centered on the end point.
+    Round,
+    /// The line is extended past the end point by half the line thickness.
+    Square
+}
+
+/// A series of options for specifying how a path or polyline should be
+/// stroked.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrokeOptions
+{
+    thickness: f32,
+    line_join: LineJoin,
+    line_cap: LineCap,
+    miter_limit: f32
+}
+
+impl StrokeOptions
+{
+    /// Creates a new `StrokeOptions` object with the specified line thickness
+    /// in pixels, miter joins, butt caps, and a miter limit of `4.0`.
+    #[inline]
+    #[must_use]
+    pub fn new(thickness: f32) -> Self
+    {
+        StrokeOptions {
+            thickness,
+            line_join: LineJoin::Miter,
+            line_cap: LineCap::Butt,
+            miter_limit: 4.0
+        }
+    }
+
+    /// Sets the shape used to join line segments.
+    #[inline]
+    #[must_use]
+    pub fn with_line_join(mut self, line_join: LineJoin) -> Self
+    {
+        self.line_join = line_join;
+        self
+    }
+
+    /// Sets the shape used at the ends of unclosed lines.
+    #[inline]
+    #[must_use]
+    pub fn with_line_cap(mut self, line_cap: LineCap) -> Self
+    {
+        self.line_cap = line_cap;
+        self
+    }
+
+    /// Sets the maximum length of a miter join, as a multiple of half the
+    /// line thickness. Sharper corners are drawn using a bevel join.
+    #[inline]
+    #[must_use]
+    pub fn with_miter_limit(mut self, miter_limit: f32) -> Self
+    {
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    /// Returns the line thickness in pixels.
+    #[inline]
+    #[must_use]
+    pub fn thickness(&self) -> f32
+    {
+        self.thickness
+    }
+}
+
+/// A path made up of straight lines and curves, which may be filled or
+/// stroked using [crate::Graphics2D::draw_path_filled] and
+/// [crate::Graphics2D::draw_path_stroked].
+///
+/// A path contains one or more contours. A new contour is started by calling
+/// [Path::move_to], and is optionally closed by calling [Path::close].
+///
+/// ```rust
+/// # use speedy2d::shape::Path;
+/// let heart = Path::new()
+///     .move_to((50.0, 90.0))
+///     .cubic_to((0.0, 50.0), (20.0, 0.0), (50.0, 30.0))
+///     .cubic_to((80.0, 0.0), (100.0, 50.0), (50.0, 90.0))
+///     .close();
+/// ```
+///
+/// Curves are converted into straight line segments when they are added to
+/// the path. If the path will be drawn many times, the result of
+/// [Path::to_filled_polygon] or [Path::to_stroked_polygon] may be stored and
+/// drawn using [crate::Graphics2D::draw_polygon], to avoid repeating the
+/// triangulation each frame.
+#[derive(Debug, Clone)]
+pub struct Path
+{
+    contours: Vec<PathContour>,
+    fill_rule: FillRule
+}
+
+impl Default for Path
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl Path
+{
+    /// Creates a new, empty path, with a fill rule of [FillRule::NonZero].
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self
+    {
+        Path {
+            contours: Vec::new(),
+            fill_rule: FillRule::NonZero
+        }
+    }
+
+    /// Sets the rule used to determine which regions of the path are filled
+    /// when the path overlaps itself.
+    #[inline]
+    #[must_use]
+    pub fn with_fill_rule(mut self, fill_rule: FillRule) -> Self
+    {
+        self.fill_rule = fill_rule;
+        self
+    }
+
+    /// Starts a new contour at the specified point.
+    #[must_use]
+    pub fn move_to<V: Into<Vec2>>(mut self, point: V) -> Self
+    {
+        self.contours.push(PathContour {
+            points: vec![point.into()],
+            closed: false
+        });
+        self
+    }
+
+    /// Adds a straight line from the current point to the specified point. If
+    /// there is no current contour, this is equivalent to [Path::move_to].
+    #[must_use]
+    pub fn line_to<V: Into<Vec2>>(mut self, point: V) -> Self
+    {
+        let point = point.into();
+
+        match self.current_point() {
+            None => self.move_to(point),
+            Some(_) => {
+                self.current_contour_points().push(point);
+                self
+            }
+        }
+    }
+
+    /// Adds a quadratic Bézier curve from the current point to `end`, using
+    /// the specified control point.
+    #[must_use]
+    pub fn quad_to<C: Into<Vec2>, E: Into<Vec2>>(self, control: C, end: E) -> Self
+    {
+        let (control, end) = (control.into(), end.into());
+
+        let start = match self.current_point() {
+            None => return self.move_to(end),
+            Some(start) => start
+        };
+
+        let max_second_difference = (start - control * 2.0 + end).magnitude();
+
+        self.add_curve_points(
+            (max_second_difference / (4.0 * PATH_FLATTENING_TOLERANCE)).sqrt(),
+            |t| {
+                let mt = 1.0 - t;
+                start * (mt * mt) + control * (2.0 * mt * t) + end * (t * t)
+            }
+        )
+    }
+
+    /// Adds a cubic Bézier curve from the current point to `end`, using the
+    /// two specified control points.
+    #[must_use]
+    pub fn cubic_to<C1: Into<Vec2>, C2: Into<Vec2>, E: Into<Vec2>>(
+        self,
+        control1: C1,
+        control2: C2,
+        end: E
+    ) -> Self
+    {
+        let (control1, control2, end) = (control1.into(), control2.into(), end.into());
+
+        let start = match self.current_point() {
+            None => return self.move_to(end),
+            Some(start) => start
+        };
+
+        let max_second_difference = crate::numeric::max(
+            (start - control1 * 2.0 + control2).magnitude(),
+            (control1 - control2 * 2.0 + end).magnitude()
+        );
+
+        self.add_curve_points(
+            (3.0 * max_second_difference / (4.0 * PATH_FLATTENING_TOLERANCE)).sqrt(),
+            |t| {
+                let mt = 1.0 - t;
+                start * (mt * mt * mt)
+                    + control1 * (3.0 * mt * mt * t)
+                    + control2 * (3.0 * mt * t * t)
+                    + end * (t * t * t)
+            }
+        )
+    }
+
+    /// Closes the current contour, by joining its last point to its first
+    /// point. Any further lines or curves will start a new contour at the
+    /// first point of the closed contour.
+    #[must_use]
+    pub fn close(mut self) -> Self
+    {
+        if let Some(contour) = self.contours.last_mut() {
+            contour.closed = true;
+        }
+        self
+    }
+
+    /// Returns true if this path contains no contours.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool
+    {
+        self.contours.is_empty()
+    }
+
+    /// Returns the points making up each contour of the path, with any curves
+    /// approximated by straight line segments. Closed contours end with a
+    /// copy of their first point.
+    ///
+    /// This is useful for laying out content along the path, for example
+    /// using [crate::Graphics2D::draw_text_along_path].
+    #[must_use]
+    pub fn to_polylines(&self) -> Vec<Vec<Vec2>>
+    {
+        self.contours
+            .iter()
+            .map(|contour| {
+                let mut points = contour.points.clone();
+                if contour.closed {
+                    points.push(contour.points[0]);
+                }
+                points
+            })
+            .collect()
+    }
+
+    /// Returns a polygon covering the area enclosed by the path, according to
+    /// the path's fill rule. Every contour is treated as if it were closed.
+    #[must_use]
+    pub fn to_filled_polygon(&self) -> Polygon
+    {
+        let contours: Vec<&[Vec2]> = self
+            .contours
+            .iter()
+            .map(|contour| contour.points.as_slice())
+            .collect();
+
+        Polygon::new_with_contours(&contours, self.fill_rule)
+    }
+
+    /// Returns a polygon covering the outline of the path, drawn with the
+    /// specified thickness. Corners between line segments are rounded, and
+    /// the ends of unclosed contours are cut off square.
+    ///
+    /// To control the style of the corners and ends, use
+    /// [Path::to_stroked_polygon_with_options].
+    #[must_use]
+    pub fn to_stroked_polygon(&self, thickness: f32) -> Polygon
+    {
+        self.to_stroked_polygon_with_options(
+            &StrokeOptions::new(thickness).with_line_join(LineJoin::Round)
+        )
+    }
+
+    /// Returns a polygon covering the outline of the path, drawn using the
+    /// specified thickness, line join, and line cap.
+    #[must_use]
+    pub fn to_stroked_polygon_with_options(&self, options: &StrokeOptions) -> Polygon
+    {
+        let half_thickness = options.thickness / 2.0;
+        let mut stroke_contours: Vec<Vec<Vec2>> = Vec::new();
+
+        if half_thickness > 0.0 {
+            for contour in &self.contours {
+                stroke_contour(contour, half_thickness, options, &mut stroke_contours);
+            }
+        }
+
+        // Ensure every contour winds in the same direction, so that
+        // overlapping regions are merged rather than cancelled out
+        for contour in stroke_contours.iter_mut() {
+            if signed_area(contour) < 0.0 {
+                contour.reverse();
+            }
+        }
+
+        Polygon::new_with_contours(&stroke_contours, FillRule::NonZero)
+    }
+
+    fn current_point(&self) -> Option<Vec2>
+    {
+        self.contours.last().map(|contour| {
+            if contour.closed {
+                contour.points[0]
+            } else {
+                *contour.points.last().unwrap()
+            }
+        })
+    }
+
+    fn current_contour_points(&mut self) -> &mut Vec<Vec2>
+    {
+        if self.contours.last().unwrap().closed {
+            let start = self.contours.last().unwrap().points[0];
+            self.contours.push(PathContour {
+                points: vec![start],
+                closed: false
+            });
+        }
+
+        &mut self.contours.last_mut().unwrap().points
+    }
+
+    fn add_curve_points(
+        mut self,
+        segment_count: f32,
+        point_at: impl Fn(f32) -> Vec2
+    ) -> Self
+    {
+        let segment_count = segment_count.ceil().clamp(1.0, 1000.0) as usize;
+        let points = self.current_contour_points();
+
+        for i in 1..=segment_count {
+            points.push(point_at(i as f32 / segment_count as f32));
+        }
+
+        self
+    }
+}
+
+fn stroke_contour(
+    contour: &PathContour,
+    half_thickness: f32,
+    options: &StrokeOptions,
+    output: &mut Vec<Vec<Vec2>>
+)
+{
+    let mut points = contour.points.clone();
+    points.dedup();
+
+    if contour.closed && points.len() > 2 && points.first() == points.last() {
+        points.pop();
+    }
+
+    let closed = contour.closed && points.len() > 2;
+
+    let segment_count = if closed {
+        points.len()
+    } else {
+        points.len() - 1
+    };
+
+    if segment_count == 0 {
+        return;
+    }
+
+    // The direction of each segment, and the offset from the center of the
+    // segment to its edge
+    let segments: Vec<(Vec2, Vec2)> = (0..segment_count)
+        .map(|i| {
+            let direction = (points[(i + 1) % points.len()] - points[i])
+                .normalize()
+                .unwrap();
+            (
+                direction,
+                direction.rotate_90_degrees_clockwise() * half_thickness
+            )
+        })
+        .collect();
+
+    for (i, (_, normal)) in segments.iter().enumerate() {
+        let start = points[i];
+        let end = points[(i + 1) % points.len()];
+
+        output.push(vec![
+            start + normal,
+            end + normal,
+            end - normal,
+            start - normal,
+        ]);
+    }
+
+    let join_count = if closed {
+        segment_count
+    } else {
+        segment_count - 1
+    };
+
+    for i in 0..join_count {
+        let point = points[(i + 1) % points.len()];
+        let (_, normal_before) = segments[i];
+        let (direction_after, normal_after) = segments[(i + 1) % segment_count];
+
+        // The join is only needed on the outside of the corner
+        let outer_sign = if dot(direction_after, normal_before) > 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
+
+        let outer_before = normal_before * outer_sign;
+        let outer_after = normal_after * outer_sign;
+
+        match options.line_join {
+            LineJoin::Round => output.push(stroke_circle(point, half_thickness)),
+
+            LineJoin::Bevel => {
+                output.push(vec![point, point + outer_before, point + outer_after])
+            }
+
+            LineJoin::Miter => {
+                let miter_direction = match (outer_before + outer_after).normalize() {
+                    None => continue,
+                    Some(direction) => direction
+                };
+
+                let cos_half_angle = dot(miter_direction, outer_before) / half_thickness;
+                let miter_ratio = 1.0 / cos_half_angle;
+
+                if cos_half_angle <= 0.0 || miter_ratio > options.miter_limit {
+                    output.push(vec![point, point + outer_before, point + outer_after]);
+                } else {
+                    output.push(vec![
+                        point,
+                        point + outer_before,
+                        point + miter_direction * (half_thickness * miter_ratio),
+                        point + outer_after,
+                    ]);
+                }
+            }
+        }
+    }
+
+    if !closed {
+        let (start_direction, start_normal) = segments[0];
+        let (end_direction, end_normal) = segments[segment_count - 1];
+
+        let caps = [
+            (points[0], start_direction * -1.0, start_normal),
+            (points[points.len() - 1], end_direction, end_normal)
+        ];
+
+        for (point, outward, normal) in caps.iter() {
+            match options.line_cap {
+                LineCap::Butt => {}
+
+                LineCap::Round => output.push(stroke_circle(*point, half_thickness)),
+
+                LineCap::Square => {
+                    let extension = *outward * half_thickness;
+                    output.push(vec![
+                        *point + normal,
+                        *point + normal + extension,
+                        *point - normal + extension,
+                        *point - normal,
+                    ]);
+                }
+            }
+        }
+    }
+}
+
+#[inline]
+fn dot(a: Vec2, b: Vec2) -> f32
+{
+    a.x * b.x + a.y * b.y
+}
+
+#[inline]
+fn cross(a: Vec2, b: Vec2) -> f32
+{
+    a.x * b.y - a.y * b.x
+}
+
+fn stroke_circle(center: Vec2, radius: f32) -> Vec<Vec2>
+{
+    // Keep the distance between each straight segment and the true curve
+    // below a quarter of a pixel.
+    let segment_count = (std::f32::consts::PI
+        / (1.0 - (0.25 / radius).min(1.0)).acos().max(0.01))
+    .ceil()
+    .clamp(4.0, 256.0) as usize;
+
+    (0..segment_count)
+        .map(|i| {
+            let angle = std::f32::consts::PI * 2.0 * i as f32 / segment_count as f32;
+            center + Vec2::new(angle.cos(), angle.sin()) * radius
+        })
+        .collect()
+}
+
+fn signed_area(contour: &[Vec2]) -> f32
+{
+    contour
+        .iter()
+        .zip(contour.iter().cycle().skip(1))
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum::<f32>()
+        / 2.0
+}
+
+/// A non-horizontal edge of a polygon outline, stored with `top.y < bottom.y`.
+struct PolygonEdge
+{
+    top: Vec2,
+    bottom: Vec2,
+    winding: i32
+}
+
+impl PolygonEdge
+{
+    fn new(start: Vec2, end: Vec2) -> Option<Self>
+    {
+        if start.y < end.y {
+            Some(PolygonEdge {
+                top: start,
+                bottom: end,
+                winding: 1
+            })
+        } else if start.y > end.y {
+            Some(PolygonEdge {
+                top: end,
+                bottom: start,
+                winding: -1
+            })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn x_at(&self, y: f32) -> f32
+    {
+        let t = (y - self.top.y) / (self.bottom.y - self.top.y);
+        self.top.x + (self.bottom.x - self.top.x) * t
+    }
+
+    fn intersection_y(&self, other: &PolygonEdge) -> Option<f32>
+    {
+        let d1 = self.bottom - self.top;
+        let d2 = other.bottom - other.top;
+
+        let denominator = d1.x * d2.y - d1.y * d2.x;
+
+        if denominator == 0.0 {
+            return None;
+        }
+
+        let offset = other.top - self.top;
+        let t = (offset.x * d2.y - offset.y * d2.x) / denominator;
+        let u = (offset.x * d1.y - offset.y * d1.x) / denominator;
+
+        if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+            Some(self.top.y + d1.y * t)
+        } else {
+            None
+        }
+    }
+}
+
+/// Splits the polygon into horizontal slabs at every vertex and intersection,
+/// so that no edges cross within a slab. Each slab is then filled by walking
+/// the edges from left to right, and emitting a trapezoid for each span which
+/// is inside the polygon according to the fill rule.
+fn tessellate_edges(edges: &[PolygonEdge], fill_rule: FillRule) -> Vec<[Vec2; 3]>
+{
+    let mut slab_boundaries: Vec<f32> = Vec::with_capacity(edges.len() * 2);
+
+    for (i, edge) in edges.iter().enumerate() {
+        slab_boundaries.push(edge.top.y);
+        slab_boundaries.push(edge.bottom.y);
+
+        for other in &edges[i + 1..] {
+            if let Some(y) = edge.intersection_y(other) {
+                slab_boundaries.push(y);
+            }
+        }
+    }
+
+    slab_boundaries.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    slab_boundaries.dedup();
+
+    let mut triangles = Vec::new();
+    let mut crossings: Vec<(f32, f32, f32, i32)> = Vec::new();
+
+    for slab in slab_boundaries.windows(2) {
+        let (y_top, y_bottom) = (slab[0], slab[1]);
+        let y_mid = (y_top + y_bottom) / 2.0;
+
+        crossings.clear();
+
+        for edge in edges {
+            if edge.top.y <= y_top && edge.bottom.y >= y_bottom {
+                crossings.push((
+                    edge.x_at(y_mid),
+                    edge.x_at(y_top),
+                    edge.x_at(y_bottom),
+                    edge.winding
+                ));
+            }
+        }
+
+        crossings.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        let mut winding_number = 0;
+
+        for pair in crossings.windows(2) {
+            let (left, right) = (pair[0], pair[1]);
+
+            winding_number += left.3;
+
+            if !fill_rule.is_inside(winding_number) {
+                continue;
+            }
+
+            let top_left = Vec2::new(left.1, y_top);
+            let top_right = Vec2::new(right.1, y_top);
+            let bottom_right = Vec2::new(right.2, y_bottom);
+            let bottom_left = Vec2::new(left.2, y_bottom);
+
+            if top_left.x != top_right.x {
+                triangles.push([top_left, top_right, bottom_right]);
+            }
+
+            if bottom_left.x != bottom_right.x {
+                triangles.push([bottom_right, bottom_left, top_left]);
+            }
+        }
+    }
+
+    triangles
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::dimen::{Matrix2D, Vec2};
+    use crate::shape::{
+        Circle,
+        ClipShape,
+        CornerRadii,
+        Ellipse,
+        FillRule,
+        LineCap,
+        LineJoin,
+        LineSegment,
+        Path,
+        Polygon,
+        Rect,
+        RotatedRectangle,
+        StrokeOptions,
+        Transformable,
+        URect
+    };
+
+    fn polygon_area(polygon: &Polygon) -> f32
+    {
+        polygon
+            .triangles
+            .iter()
+            .map(|[a, b, c]| {
+                let ab = *b - *a;
+                let ac = *c - *a;
+                (ab.x * ac.y - ab.y * ac.x).abs() / 2.0
+            })
+            .sum()
+    }
+
+    #[test]
+    pub fn test_intersect_1()
+    {
+        let r1 = URect::from_tuples((100, 100), (200, 200));
+        let r2 = URect::from_tuples((100, 300), (200, 400));
+        let r3 = URect::from_tuples((125, 50), (175, 500));
+
+        assert_eq!(None, r1.intersect(&r2));
+
+        assert_eq!(
+            Some(URect::from_tuples((125, 100), (175, 200))),
+            r1.intersect(&r3)
+        );
+
+        assert_eq!(
+            Some(URect::from_tuples((125, 300), (175, 400))),
+            r2.intersect(&r3)
+        );
+
+        assert_eq!(Some(r1.clone()), r1.intersect(&r1));
+        assert_eq!(Some(r2.clone()), r2.intersect(&r2));
+        assert_eq!(Some(r3.clone()), r3.intersect(&r3));
+    }
+
+    #[test]
+    pub fn test_intersect_2()
+    {
+        let r1 = URect::from_tuples((100, 100), (200, 200));
+        let r2 = URect::from_tuples((100, 200), (200, 300));
+
+        assert_eq!(None, r1.intersect(&r2));
+    }
+
+    #[test]
+    pub fn test_rect_set_operations()
+    {
+        let r1 = URect::from_tuples((100, 100), (200, 200));
+        let r2 = URect::from_tuples((150, 50), (300, 120));
+        let r3 = URect::from_tuples((200, 100), (300, 200));
+
+        assert_eq!(URect::from_tuples((100, 50), (300, 200)), r1.union(&r2));
+        assert_eq!(r1, r1.union(&r1));
+
+        assert!(r1.overlaps(&r2));
+        assert!(r2.overlaps(&r1));
+        assert!(!r1.overlaps(&r3));
+        assert_eq!(r1.overlaps(&r2), r1.intersect(&r2).is_some());
+
+        assert!(r1.contains_rect(&r1));
+        assert!(r1.contains_rect(&URect::from_tuples((120, 100), (200, 150))));
+        assert!(!r1.contains_rect(&r2));
+        assert!(r1.union(&r2).contains_rect(&r2));
+
+        assert_eq!(URect::from_tuples((90, 90), (210, 210)), r1.inflate(10));
+        assert_eq!(URect::from_tuples((110, 110), (190, 190)), r1.deflate(10));
+        assert_eq!(r1, r1.inflate(25).deflate(25));
+
+        let r4 = Rect::from_tuples((0.0, 0.0), (10.0, 10.0));
+        assert!(!r4.deflate(5.0).is_positive_area());
+        assert_eq!(
+            Rect::from_tuples((-2.5, -2.5), (12.5, 12.5)),
+            r4.inflate(2.5)
+        );
+    }
+
+    #[test]
+    pub fn test_ellipse_contains()
+    {
+        let ellipse = Ellipse::new((100.0, 50.0), (40.0, 10.0));
+
+        assert!(ellipse.contains(Vec2::new(100.0, 50.0)));
+        assert!(ellipse.contains(Vec2::new(140.0, 50.0)));
+        assert!(ellipse.contains(Vec2::new(100.0, 40.0)));
+        assert!(ellipse.contains(Vec2::new(125.0, 55.0)));
+
+        assert!(!ellipse.contains(Vec2::new(141.0, 50.0)));
+        assert!(!ellipse.contains(Vec2::new(100.0, 39.0)));
+        assert!(!ellipse.contains(Vec2::new(135.0, 58.0)));
+
+        assert_eq!(
+            Rect::from_tuples((60.0, 40.0), (140.0, 60.0)),
+            ellipse.bounding_rect()
+        );
+        assert_eq!(
+            ellipse,
+            Ellipse::from_bounding_rect(&ellipse.bounding_rect())
+        );
+    }
+
+    #[test]
+    pub fn test_circle()
+    {
+        let circle = Circle::new((100.0, 50.0), 10.0);
+
+        assert!(circle.contains(Vec2::new(100.0, 50.0)));
+        assert!(circle.contains(Vec2::new(110.0, 50.0)));
+        assert!(circle.contains(Vec2::new(106.0, 58.0)));
+
+        assert!(!circle.contains(Vec2::new(111.0, 50.0)));
+        assert!(!circle.contains(Vec2::new(108.0, 58.0)));
+        assert!(!Circle::new((0.0, 0.0), 0.0).contains(Vec2::ZERO));
+
+        assert!(circle.intersects(&Circle::new((125.0, 50.0), 15.0)));
+        assert!(circle.intersects(&Circle::new((100.0, 50.0), 2.0)));
+        assert!(!circle.intersects(&Circle::new((125.0, 50.0), 14.0)));
+        assert!(!circle.intersects(&Circle::new((100.0, 50.0), 0.0)));
+
+        assert_eq!(
+            Rect::from_tuples((90.0, 40.0), (110.0, 60.0)),
+            circle.bounding_rect()
+        );
+        assert_eq!(
+            Circle::new((105.0, 45.0), 10.0),
+            circle.with_offset((5.0, -5.0))
+        );
+        assert_eq!(
+            Ellipse::new((100.0, 50.0), (10.0, 10.0)),
+            Ellipse::from(&circle)
+        );
+        assert_eq!(circle, Circle::from(((100.0, 50.0), 10.0)));
+        assert_eq!(circle, Circle::from(&circle));
+    }
+
+    #[test]
+    pub fn test_rotated_rectangle()
+    {
+        let quarter_turn = std::f32::consts::FRAC_PI_2;
+        let eighth_turn = std::f32::consts::FRAC_PI_4;
+
+        let rect = RotatedRectangle::new((50.0, 50.0), (40.0, 20.0), quarter_turn);
+
+        assert!(rect.contains(Vec2::new(50.0, 69.0)));
+        assert!(rect.contains(Vec2::new(59.0, 50.0)));
+        assert!(!rect.contains(Vec2::new(69.0, 50.0)));
+
+        let bounds = rect.bounding_rect();
+        assert!((bounds.top_left().x - 40.0).abs() < 0.001);
+        assert!((bounds.top_left().y - 30.0).abs() < 0.001);
+        assert!((bounds.bottom_right().x - 60.0).abs() < 0.001);
+        assert!((bounds.bottom_right().y - 70.0).abs() < 0.001);
+
+        // A clockwise quarter turn moves the top left vertex to the top right
+        let vertices = rect.vertices();
+        assert!((vertices[0] - Vec2::new(60.0, 30.0)).magnitude() < 0.001);
+        assert!((vertices[2] - Vec2::new(40.0, 70.0)).magnitude() < 0.001);
+
+        let diamond = RotatedRectangle::new((0.0, 0.0), (20.0, 20.0), eighth_turn);
+        let half_diagonal = 10.0 * std::f32::consts::SQRT_2;
+
+        assert!(diamond.contains(Vec2::new(half_diagonal - 0.1, 0.0)));
+        assert!(!diamond.contains(Vec2::new(9.0, 9.0)));
+
+        // The bounding boxes of these overlap, but the shapes don't
+        let corner = RotatedRectangle::from_rectangle(
+            &Rect::from_tuples((9.0, 9.0), (19.0, 19.0)),
+            0.0
+        );
+
+        assert!(!diamond.intersects(&corner));
+        assert!(!corner.intersects(&diamond));
+        assert!(diamond
+            .bounding_rect()
+            .intersect(&corner.bounding_rect())
+            .is_some());
+
+        let near = corner.with_offset((-3.0, -3.0));
+
+        assert!(diamond.intersects(&near));
+        assert!(near.intersects(&diamond));
+        assert!(diamond.intersects(&diamond));
+    }
+
+    #[test]
+    pub fn test_line_segment()
+    {
+        let horizontal = LineSegment::new((0.0, 0.0), (10.0, 0.0));
+        let vertical = LineSegment::new((4.0, -5.0), (4.0, 5.0));
+
+        assert_eq!(10.0, horizontal.length());
+        assert_eq!(
+            Some(Vec2::new(4.0, 0.0)),
+            horizontal.intersection(&vertical)
+        );
+        assert_eq!(
+            Some(Vec2::new(4.0, 0.0)),
+            vertical.intersection(&horizontal)
+        );
+
+        // Touching at an end point
+        let touching = LineSegment::new((10.0, 0.0), (20.0, 20.0));
+        assert_eq!(
+            Some(Vec2::new(10.0, 0.0)),
+            horizontal.intersection(&touching)
+        );
+
+        // The lines cross, but not within the segments
+        let short = LineSegment::new((4.0, 1.0), (4.0, 5.0));
+        assert_eq!(None, horizontal.intersection(&short));
+
+        // Parallel
+        let parallel = horizontal.with_offset((0.0, 1.0));
+        assert_eq!(None, horizontal.intersection(&parallel));
+
+        // Collinear
+        let overlapping = LineSegment::new((15.0, 0.0), (5.0, 0.0));
+        let disjoint = LineSegment::new((11.0, 0.0), (15.0, 0.0));
+        assert_eq!(
+            Some(Vec2::new(5.0, 0.0)),
+            horizontal.intersection(&overlapping)
+        );
+        assert_eq!(
+            Some(Vec2::new(10.0, 0.0)),
+            overlapping.intersection(&horizontal)
+        );
+        assert_eq!(None, horizontal.intersection(&disjoint));
+
+        // A single point
+        let point = LineSegment::new((3.0, 0.0), (3.0, 0.0));
+        assert_eq!(Some(Vec2::new(3.0, 0.0)), point.intersection(&horizontal));
+        assert_eq!(Some(Vec2::new(3.0, 0.0)), horizontal.intersection(&point));
+        assert_eq!(None, point.intersection(&vertical));
+
+        assert_eq!(
+            Vec2::new(4.0, 0.0),
+            horizontal.closest_point(Vec2::new(4.0, 3.0))
+        );
+        assert_eq!(
+            Vec2::new(0.0, 0.0),
+            horizontal.closest_point(Vec2::new(-3.0, 4.0))
+        );
+        assert_eq!(3.0, horizontal.distance_to_point(Vec2::new(4.0, 3.0)));
+        assert_eq!(5.0, horizontal.distance_to_point(Vec2::new(-3.0, 4.0)));
+        assert_eq!(0.0, horizontal.distance_to_point(Vec2::new(7.0, 0.0)));
+        assert_eq!(5.0, point.distance_to_point(Vec2::new(6.0, 4.0)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn test_serde_round_trip()
+    {
+        fn round_trip<T: serde::Serialize + serde::de::DeserializeOwned>(value: &T) -> T
+        {
+            serde_json::from_str(&serde_json::to_string(value).unwrap()).unwrap()
+        }
+
+        let rect = Rect::from_tuples((10.0, 20.0), (30.5, 40.0));
+        assert_eq!(rect, round_trip(&rect));
+
+        let urect = URect::from_tuples((1, 2), (3, 4));
+        assert_eq!(urect, round_trip(&urect));
+
+        let rounded = rect.rounded_with_radii(CornerRadii::new(1.0, 2.0, 3.0, 0.0));
+        assert_eq!(rounded, round_trip(&rounded));
+
+        let frame = Polygon::new_with_holes(
+            &[(0.0, 0.0), (100.0, 0.0), (100.0, 100.0), (0.0, 100.0)],
+            &[[(25.0, 25.0), (75.0, 25.0), (75.0, 75.0), (25.0, 75.0)]],
+            FillRule::EvenOdd
+        );
+
+        // Only the outlines and fill rule are stored
+        let json = serde_json::to_string(&frame).unwrap();
+        assert!(!json.contains("triangles"));
+        assert!(json.contains("EvenOdd"));
+
+        let frame = round_trip(&frame);
+        assert_eq!(100.0 * 100.0 - 50.0 * 50.0, frame.area());
+        assert!(frame.contains((10.0, 50.0)));
+        assert!(!frame.contains((50.0, 50.0)));
+
+        // The center of a star drawn with a single outline is only filled
+        // when using the non-zero fill rule
+        let star = [
+            (50.0, 0.0),
+            (79.0, 90.0),
+            (2.0, 35.0),
+            (98.0, 35.0),
+            (21.0, 90.0)
+        ];
+
+        let even_odd = round_trip(&Polygon::new_with_fill_rule(&star, FillRule::EvenOdd));
+        let non_zero = round_trip(&Polygon::new_with_fill_rule(&star, FillRule::NonZero));
+        assert!(!even_odd.contains((50.0, 50.0)));
+        assert!(non_zero.contains((50.0, 50.0)));
+
+        let triangle = round_trip(&Polygon::new(&[(0.0, 0.0), (30.0, 0.0), (0.0, 30.0)]));
+        assert_eq!(450.0, triangle.area());
+    }
+
+    #[test]
+    pub fn test_transformable()
+    {
+        let rect = Rect::from_tuples((0.0, 0.0), (20.0, 10.0));
+
+        let moved = rect.translated((5.0, 5.0));
+        assert_eq!(200.0, polygon_area(&moved));
+        assert!(moved.contains((24.0, 14.0)));
+        assert!(!moved.contains((2.0, 2.0)));
+
+        let scaled = rect.scaled((2.0, 3.0));
+        assert_eq!(1200.0, polygon_area(&scaled));
+        assert_eq!(
+            Some(Rect::from_tuples((0.0, 0.0), (40.0, 30.0))),
+            scaled.bounding_rect()
+        );
+
+        // A reflection reverses the order of the vertices
+        let mirrored = rect.scaled((-1.0, 1.0));
+        assert_eq!(200.0, polygon_area(&mirrored));
+        assert!(mirrored.contains((-10.0, 5.0)));
+
+        // Rotating a quarter turn clockwise around the origin
+        let rotated = RotatedRectangle::from_rectangle(&rect, 0.0)
+            .rotated(std::f32::consts::FRAC_PI_2);
+        let bounds = rotated.bounding_rect().unwrap();
+        assert!((bounds.top_left().x - -10.0).abs() < 0.001);
+        assert!((bounds.bottom_right().y - 20.0).abs() < 0.001);
+
+        let transform = Matrix2D::rotate(0.3).then(&Matrix2D::translate((7.0, 3.0)));
+
+        let polygon = Polygon::new(&[(0.0, 0.0), (30.0, 0.0), (0.0, 30.0)]);
+        let transformed = polygon.transformed(&transform);
+        assert!((transformed.area() - 450.0).abs() < 0.01);
+        let centroid = transformed.centroid().unwrap();
+        assert!((centroid - transform.transform_point((10.0, 10.0))).magnitude() < 0.001);
+
+        let segment = LineSegment::new((0.0, 0.0), (10.0, 0.0));
+        let transformed = segment.transformed(&transform);
+        assert_eq!(transform.transform_point((10.0, 0.0)), transformed.end());
+        assert!((transformed.length() - 10.0).abs() < 0.001);
+
+        // Curves are flattened after the transformation, so stay accurate
+        // when scaled up
+        let ellipse = Circle::new((1.0, 1.0), 1.0).scaled((100.0, 50.0));
+        let expected_area = std::f32::consts::PI * 100.0 * 50.0;
+        assert!((ellipse.area() - expected_area).abs() < expected_area * 0.01);
+        assert!(ellipse.contains((100.0, 50.0)));
+
+        let rounded = Rect::from_tuples((0.0, 0.0), (50.0, 50.0))
+            .rounded(10.0)
+            .translated((100.0, 0.0));
+        let expected_area = 50.0 * 50.0 - (4.0 - std::f32::consts::PI) * 100.0;
+        assert!((rounded.area() - expected_area).abs() < 5.0);
+        assert!(!rounded.contains((101.0, 1.0)));
+
+        let path = Path::new()
+            .move_to((0.0, 0.0))
+            .line_to((10.0, 0.0))
+            .line_to((10.0, 10.0))
+            .close()
+            .translated((0.0, 10.0));
+        assert_eq!(50.0, path.to_filled_polygon().area());
+        assert!(path.to_filled_polygon().contains((9.0, 11.0)));
+    }
+
+    #[test]
+    pub fn test_path_fill()
+    {
+        let square = Path::new()
+            .move_to((0.0, 0.0))
+            .line_to((100.0, 0.0))
+            .line_to((100.0, 100.0))
+            .line_to((0.0, 100.0))
+            .close();
+
+        assert_eq!(10000.0, polygon_area(&square.to_filled_polygon()));
+
+        // A closed quadratic curve enclosing the area under a parabola
+        let curve = Path::new()
+            .move_to((0.0, 0.0))
+            .quad_to((50.0, 100.0), (100.0, 0.0))
+            .close();
+
+        let expected_area = 2.0 / 3.0 * 100.0 * 50.0;
+        assert!((polygon_area(&curve.to_filled_polygon()) - expected_area).abs() < 20.0);
+
+        assert_eq!(0.0, polygon_area(&Path::new().to_filled_polygon()));
+    }
+
+    #[test]
+    pub fn test_clip_shape_to_polygon()
+    {
+        let rect = ClipShape::from(Rect::from_tuples((10.0, 10.0), (110.0, 60.0)));
+        assert_eq!(5000.0, polygon_area(&rect.to_polygon()));
+
+        let circle = ClipShape::Circle {
+            center: Vec2::new(50.0, 50.0),
+            radius: 40.0
+        };
+        let expected_area = std::f32::consts::PI * 40.0 * 40.0;
+        assert!((polygon_area(&circle.to_polygon()) - expected_area).abs() < 50.0);
+
+        // The radius is clamped to half of the smaller side, making this a
+        // circle of radius 25
+        let rounded =
+            ClipShape::from(Rect::from_tuples((0.0, 0.0), (50.0, 50.0)).rounded(100.0));
+        let expected_area = std::f32::consts::PI * 25.0 * 25.0;
+        assert!((polygon_area(&rounded.to_polygon()) - expected_area).abs() < 20.0);
+
+        // Only the top left corner is rounded
+        let rounded = ClipShape::from(
+            Rect::from_tuples((0.0, 0.0), (50.0, 50.0))
+                .rounded_with_radii(CornerRadii::new(20.0, 0.0, 0.0, 0.0))
+        );
+        let expected_area = 50.0 * 50.0 - (1.0 - std::f32::consts::PI / 4.0) * 400.0;
+        assert!((polygon_area(&rounded.to_polygon()) - expected_area).abs() < 5.0);
+    }
+
+    #[test]
+    pub fn test_rounded_rect_corner_radii()
+    {
+        let rect = Rect::from_tuples((0.0, 0.0), (100.0, 50.0))
+            .rounded_with_radii(CornerRadii::new(20.0, 0.0, 10.0, 0.0));
+
+        assert_eq!(20.0, rect.radius());
+        assert_eq!(CornerRadii::new(20.0, 0.0, 10.0, 0.0), rect.radii());
+        assert_eq!(Rect::from_tuples((20.0, 20.0), (90.0, 40.0)), rect.inner());
+
+        assert!(!rect.contains(Vec2::new(1.0, 1.0)));
+        assert!(rect.contains(Vec2::new(6.0, 6.0)));
+        assert!(rect.contains(Vec2::new(99.0, 1.0)));
+        assert!(rect.contains(Vec2::new(1.0, 49.0)));
+        assert!(!rect.contains(Vec2::new(99.0, 49.0)));
+        assert!(rect.contains(Vec2::new(95.0, 45.0)));
+
+        let uniform = Rect::from_tuples((0.0, 0.0), (100.0, 50.0)).rounded(10.0);
+
+        assert_eq!(CornerRadii::uniform(10.0), uniform.radii());
+        assert_eq!(
+            uniform,
+            Rect::from_tuples((0.0, 0.0), (100.0, 50.0))
+                .rounded_with_radii(CornerRadii::uniform(10.0))
+        );
+    }
+
+    #[test]
+    pub fn test_path_stroke()
+    {
+        let line = Path::new().move_to((0.0, 0.0)).line_to((100.0, 0.0));
+        assert_eq!(1000.0, polygon_area(&line.to_stroked_polygon(10.0)));
+
+        // The join between the two segments overlaps both, and must not
+        // produce any gaps or extra area beyond the rounded corner
+        let corner = Path::new()
+            .move_to((0.0, 0.0))
+            .line_to((100.0, 0.0))
+            .line_to((100.0, 100.0));
+
+        let corner_area = polygon_area(&corner.to_stroked_polygon(10.0));
+        let expected_area = 2000.0 - 25.0 + 25.0 * std::f32::consts::PI / 4.0;
+
+        assert!((corner_area - expected_area).abs() < 2.0, "{}", corner_area);
+
+        // A closed square outline is a frame
+        let frame = Path::new()
+            .move_to((0.0, 0.0))
+            .line_to((100.0, 0.0))
+            .line_to((100.0, 100.0))
+            .line_to((0.0, 100.0))
+            .close();
+
+        let frame_area = polygon_area(&frame.to_stroked_polygon(10.0));
+        let expected_area =
+            110.0 * 110.0 - 90.0 * 90.0 - (100.0 - 25.0 * std::f32::consts::PI);
+
+        assert!((frame_area - expected_area).abs() < 6.0, "{}", frame_area);
+    }
+
+    #[test]
+    pub fn test_path_stroke_joins_and_caps()
+    {
+        let corner = Path::new()
+            .move_to((0.0, 0.0))
+            .line_to((100.0, 0.0))
+            .line_to((100.0, 100.0));
+
+        let stroke_area = |path: &Path, options: StrokeOptions| {
+            polygon_area(&path.to_stroked_polygon_with_options(&options))
+        };
+
+        assert_eq!(2000.0, stroke_area(&corner, StrokeOptions::new(10.0)));
+
+        assert_eq!(
+            1987.5,
+            stroke_area(
+                &corner,
+                StrokeOptions::new(10.0).with_line_join(LineJoin::Bevel)
+            )
+        );
+
+        // A right angle has a miter ratio of sqrt(2), so is beveled if the limit
+        // is lower
+        assert_eq!(
+            1987.5,
+            stroke_area(&corner, StrokeOptions::new(10.0).with_miter_limit(1.4))
+        );
+
+        let line = Path::new().move_to((0.0, 0.0)).line_to((100.0, 0.0));
+
+        assert_eq!(
+            1100.0,
+            stroke_area(
+                &line,
+                StrokeOptions::new(10.0).with_line_cap(LineCap::Square)
+            )
+        );
+
+        let round_cap_area = stroke_area(
+            &line,
+            StrokeOptions::new(10.0).with_line_cap(LineCap::Round)
+        );
+        let expected_area = 1000.0 + 25.0 * std::f32::consts::PI;
+
+        assert!(
+            (round_cap_area - expected_area).abs() < 6.0,
+            "{}",
+            round_cap_area
+        );
+    }
+
+    #[test]
+    pub fn test_polygon_queries()
+    {
+        let triangle = Polygon::new(&[(0.0, 0.0), (30.0, 0.0), (0.0, 30.0)]);
+
+        assert_eq!(450.0, triangle.area());
+        assert_eq!(Some(Vec2::new(10.0, 10.0)), triangle.centroid());
+        assert_eq!(
+            Some(Rect::from_tuples((0.0, 0.0), (30.0, 30.0))),
+            triangle.bounding_rect()
+        );
+        assert!(triangle.contains((5.0, 5.0)));
+        assert!(triangle.contains((0.0, 0.0)));
+        assert!(!triangle.contains((20.0, 20.0)));
+        assert!(!triangle.contains((-1.0, 5.0)));
+
+        // The flattened triangle has no area, so it doesn't contain points
+        // which are collinear with its edges, either within or outside its
+        // extent
+        let flattened = triangle.scaled((1.0, 0.0));
+
+        assert_eq!(0.0, flattened.area());
+        assert!(!flattened.contains((10.0, 0.0)));
+        assert!(!flattened.contains((100.0, 0.0)));
+
+        let frame = Polygon::new_with_holes(
+            &[(0.0, 0.0), (100.0, 0.0), (100.0, 100.0), (0.0, 100.0)],
+            &[[(25.0, 25.0), (75.0, 25.0), (75.0, 75.0), (25.0, 75.0)]],
+            FillRule::EvenOdd
+        );
+
+        assert_eq!(100.0 * 100.0 - 50.0 * 50.0, frame.area());
+        assert!(frame.contains((10.0, 50.0)));
+        assert!(!frame.contains((50.0, 50.0)));
+
+        let centroid = frame.centroid().unwrap();
+        assert!((centroid - Vec2::new(50.0, 50.0)).magnitude() < 0.001);
+
+        let empty = Polygon::new(&[] as &[Vec2]);
+
+        assert_eq!(0.0, empty.area());
+        assert_eq!(None, empty.centroid());
+        assert_eq!(None, empty.bounding_rect());
+        assert!(!empty.contains((0.0, 0.0)));
+    }
+
+    #[test]
+    pub fn test_polygon_outline()
+    {
+        let square =
+            Polygon::new(&[(0.0, 0.0), (100.0, 0.0), (100.0, 100.0), (0.0, 100.0)]);
+        let outline = square.to_outline_polygon(&StrokeOptions::new(10.0));
+
+        assert_eq!(110.0 * 110.0 - 90.0 * 90.0, polygon_area(&outline));
+
+        let frame = Polygon::new_with_holes(
+            &[(0.0, 0.0), (100.0, 0.0), (100.0, 100.0), (0.0, 100.0)],
+            &[[(25.0, 25.0), (75.0, 25.0), (75.0, 75.0), (25.0, 75.0)]],
+            FillRule::EvenOdd
+        );
+        let outline = frame.to_outline_polygon(&StrokeOptions::new(10.0));
+
+        assert_eq!(
+            110.0 * 110.0 - 90.0 * 90.0 + 60.0 * 60.0 - 40.0 * 40.0,
+            polygon_area(&outline)
+        );
+    }
+
+    #[test]
+    pub fn test_polygon_with_hole()
+    {
+        let outer = [(0.0, 0.0), (100.0, 0.0), (100.0, 100.0), (0.0, 100.0)];
+        let hole = [(25.0, 25.0), (75.0, 25.0), (75.0, 75.0), (25.0, 75.0)];
+        let hole_reversed = [(25.0, 25.0), (25.0, 75.0), (75.0, 75.0), (75.0, 25.0)];
+
+        let even_odd = Polygon::new_with_holes(&outer, &[hole], FillRule::EvenOdd);
+        assert_eq!(7500.0, polygon_area(&even_odd));
+
+        let non_zero = Polygon::new_with_holes(&outer, &[hole], FillRule::NonZero);
+        assert_eq!(10000.0, polygon_area(&non_zero));
+
+        let non_zero_reversed =
+            Polygon::new_with_holes(&outer, &[hole_reversed], FillRule::NonZero);
+        assert_eq!(7500.0, polygon_area(&non_zero_reversed));
+    }
+
+    #[test]
+    pub fn test_polygon_with_contours()
+    {
+        // Two separate squares, one of which has a hole, like the letters "oi"
+        let contours: [&[(f32, f32)]; 3] = [
+            &[(0.0, 0.0), (100.0, 0.0), (100.0, 100.0), (0.0, 100.0)],
+            &[(25.0, 25.0), (25.0, 75.0), (75.0, 75.0), (75.0, 25.0)],
+            &[(200.0, 0.0), (220.0, 0.0), (220.0, 100.0), (200.0, 100.0)]
+        ];
+
+        for fill_rule in [FillRule::EvenOdd, FillRule::NonZero] {
+            let polygon = Polygon::new_with_contours(&contours, fill_rule);
+            assert_eq!(9500.0, polygon_area(&polygon));
+        }
+    }
+
+    #[test]
+    pub fn test_polygon_self_intersecting()
+    {
+        // A figure-eight made of two triangles which touch at (50, 50)
+        let figure_eight = [(0.0, 0.0), (100.0, 0.0), (0.0, 100.0), (100.0, 100.0)];
+
+        for fill_rule in [FillRule::EvenOdd, FillRule::NonZero] {
+            let polygon = Polygon::new_with_fill_rule(&figure_eight, fill_rule);
+            assert_eq!(5000.0, polygon_area(&polygon));
+        }
+
+        // A square which loops around twice: even-odd leaves the overlapping
+        // region empty, while non-zero fills it
+        let double_loop: Vec<Vec2> =
+            [(0.0, 0.0), (100.0, 0.0), (100.0, 100.0), (0.0, 100.0)]
+                .iter()
+                .cycle()
+                .take(8)
+                .map(|point| Vec2::from(*point))
+                .collect();
+
+        let even_odd = Polygon::new_with_fill_rule(&double_loop, FillRule::EvenOdd);
+        assert_eq!(0.0, polygon_area(&even_odd));
+
+        let non_zero = Polygon::new_with_fill_rule(&double_loop, FillRule::NonZero);
+        assert_eq!(10000.0, polygon_area(&non_zero));
+    }
+}
+
+///////////////////////////////////
+
+/// A struct representing an axis-aligned rounded rectangle. Two points and an
+/// 'u32' are stored: the top left vertex, the bottom right vertex and the
+/// radius of the rounded corners.
+///
+/// Alias for a rectangle with u32 coordinates.
+pub type URoundRect = RoundedRectangle<u32>;
+
+/// A struct representing an axis-aligned rounded rectangle. Two points and an
+/// 'i32' are stored: the top left vertex, the bottom right vertex and the
+/// radius of the rounded corners.
+///
+/// Alias for a rectangle with i32 coordinates.
+pub type IRoundRect = RoundedRectangle<i32>;
+
+/// A struct representing an axis-aligned rounded rectangle. Two points and an
+/// 'f32' are stored: the top left vertex, the bottom right vertex and the
+/// radius of the rounded corners.
+///
+/// Alias for a rectangle with f32 coordinates.
+pub type RoundRect = RoundedRectangle<f32>;
+
+/// The radius of each corner of a [RoundedRectangle]. A corner with a radius
+/// of zero is left square.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct CornerRadii<T = f32>
+{
+    top_left: T,
+    top_right: T,
+    bottom_right: T,
+    bottom_left: T
+}
+
+impl<T> CornerRadii<T>
+{
+    /// Creates a new set of corner radii, with a radius for each corner.
+    #[inline]
+    pub const fn new(top_left: T, top_right: T, bottom_right: T, bottom_left: T) -> Self
+    {
+        CornerRadii {
+            top_left,
+            top_right,
+            bottom_right,
+            bottom_left
+        }
+    }
+}
+
+impl<T: Copy> CornerRadii<T>
+{
+    /// Creates a new set of corner radii, with the same radius for every
+    /// corner.
+    #[inline]
+    pub const fn uniform(radius: T) -> Self
+    {
+        CornerRadii::new(radius, radius, radius, radius)
+    }
+
+    /// Returns the radius of the top left corner.
+    #[inline]
+    pub fn top_left(&self) -> T
+    {
+        self.top_left
+    }
+
+    /// Returns the radius of the top right corner.
+    #[inline]
+    pub fn top_right(&self) -> T
+    {
+        self.top_right
+    }
+
+    /// Returns the radius of the bottom right corner.
+    #[inline]
+    pub fn bottom_right(&self) -> T
+    {
+        self.bottom_right
+    }
+
+    /// Returns the radius of the bottom left corner.
+    #[inline]
+    pub fn bottom_left(&self) -> T
+    {
+        self.bottom_left
+    }
+}
+
+impl<T: num_traits::AsPrimitive<f32>> CornerRadii<T>
+{
+    /// Returns a new set of corner radii where each radius has been cast to
+    /// an `f32` value, using the `as` operator.
+    #[inline]
+    #[must_use]
+    pub fn into_f32(self) -> CornerRadii<f32>
+    {
+        CornerRadii::new(
+            self.top_left.as_(),
+            self.top_right.as_(),
+            self.bottom_right.as_(),
+            self.bottom_left.as_()
+        )
+    }
+}
+
+/// A struct representing an axis-aligned rounded rectangle. Two points and
+/// four values of type 'T' are stored: the top left vertex, the bottom right
+/// vertex and the radius of each of the rounded corners.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct RoundedRectangle<T = f32>
+{
+    rect: Rectangle<T>,
+    radii: CornerRadii<T>
+}
+
+impl<T> AsRef<RoundedRectangle<T>> for RoundedRectangle<T>
+{
+    fn as_ref(&self) -> &Self
+    {
+        self
+    }
+}
+
+impl<T: Copy> RoundedRectangle<T>
+{
+    /// Constructs a new `RoundedRectangle`. The top left vertex must be above
+    /// and to the left of the bottom right vertex. A negative radius won't be
+    /// checked. A big radius (larger than half the width or height)
+    /// might produce unexpected behavior but it won't be checked.
+    #[inline]
+    pub const fn new(top_left: Vector2<T>, bottom_right: Vector2<T>, radius: T) -> Self
+    {
+        RoundedRectangle::new_with_radii(
+            top_left,
+            bottom_right,
+            CornerRadii::uniform(radius)
+        )
+    }
+
+    /// Constructs a new `RoundedRectangle`. The top left vertex must be above
+    /// and to the left of the bottom right vertex. A negative radius won't be
+    /// checked. A big radius (larger than half the width or height)
+    /// might produce unexpected behavior but it won't be checked.
+    ///
+    /// Note: a negative radius won't be checked at runtime.
+    #[inline]
+    pub fn from_tuples(top_left: (T, T), bottom_right: (T, T), radius: T) -> Self
+    {
+        RoundedRectangle::from_rectangle(
+            Rectangle::from_tuples(top_left, bottom_right),
+            radius
+        )
+    }
+
+    /// Constructs a new `RoundedRectangle` from a `Rectangle` and a radius.
+    /// A negative radius won't be checked.
+    /// A big radius (larger than half the width or height) might produce
+    /// unexpected behavior but it won't be checked.
+    #[inline]
+    pub fn from_rectangle(rect: Rectangle<T>, radius: T) -> Self
+    {
+        RoundedRectangle::from_rectangle_with_radii(rect, CornerRadii::uniform(radius))
+    }
+}
+
+impl<T> RoundedRectangle<T>
+{
+    /// Constructs a new `RoundedRectangle`, with a separate radius for each
+    /// corner. The top left vertex must be above and to the left of the bottom
+    /// right vertex. As with [RoundedRectangle::new], the radii won't be
+    /// checked.
+    #[inline]
+    pub const fn new_with_radii(
+        top_left: Vector2<T>,
+        bottom_right: Vector2<T>,
+        radii: CornerRadii<T>
+    ) -> Self
+    {
+        RoundedRectangle {
+            rect: Rectangle::new(top_left, bottom_right),
+            radii
+        }
+    }
+
+    /// Constructs a new `RoundedRectangle` from a `Rectangle` and a separate
+    /// radius for each corner. As with [RoundedRectangle::from_rectangle],
+    /// the radii won't be checked.
+    #[inline]
+    pub fn from_rectangle_with_radii(rect: Rectangle<T>, radii: CornerRadii<T>) -> Self
+    {
+        RoundedRectangle { rect, radii }
+    }
+
+    /// Returns a reference to the top left vertex.
+    #[inline]
+    pub const fn top_left(&self) -> &Vector2<T>
+    {
+        &self.rect.top_left
+    }
+
+    /// Returns a reference to the bottom right vertex.
+    #[inline]
+    pub const fn bottom_right(&self) -> &Vector2<T>
+    {
+        &self.rect.bottom_right
+    }
+}
+
+impl<T: Copy> RoundedRectangle<T>
+{
+    /// Returns a vector representing the top right vertex.
+    #[inline]
+    pub fn top_right(&self) -> Vector2<T>
+    {
+        Vector2::new(self.rect.bottom_right.x, self.rect.top_left.y)
+    }
+
+    /// Returns a vector representing the bottom left vertex.
+    #[inline]
+    pub fn bottom_left(&self) -> Vector2<T>
+    {
+        Vector2::new(self.rect.top_left.x, self.rect.bottom_right.y)
+    }
+
+    /// Returns the radius of the rounded corners. If the corners have
+    /// different radii, this is the radius of the top left corner.
+    #[inline]
+    pub fn radius(&self) -> T
+    {
+        self.radii.top_left
+    }
+
+    /// Returns the radius of each of the rounded corners.
+    #[inline]
+    pub fn radii(&self) -> CornerRadii<T>
+    {
+        self.radii
+    }
+
+    /// Returns the x value of the left border
+    #[inline]
+    pub fn left(&self) -> T
+    {
+        self.rect.top_left.x
+    }
+
+    /// Returns the x value of the right border
+    #[inline]
+    pub fn right(&self) -> T
+    {
+        self.rect.bottom_right.x
+    }
+
+    /// Returns the y value of the top border
+    #[inline]
+    pub fn top(&self) -> T
+    {
+        self.rect.top_left.y
+    }
+
+    /// Returns the y value of the bottom border
+    #[inline]
+    pub fn bottom(&self) -> T
+    {
+        self.rect.bottom_right.y
+    }
+
+    /// Returns a `Rectangle` representing the rectangle that encloses this
+    /// rounded rectangle.
+    #[inline]
+    pub fn as_rectangle(&self) -> &Rectangle<T>
+    {
+        &self.rect
+    }
+}
+
+impl<T: std::ops::Sub<Output = T> + Copy> RoundedRectangle<T>
+{
+    /// Returns the width of the rounded rectangle.
+    #[inline]
+    pub fn width(&self) -> T
+    {
+        self.rect.bottom_right.x - self.rect.top_left.x
+    }
+
+    /// Returns the height of the rounded rectangle.
+    #[inline]
+    pub fn height(&self) -> T
+    {
+        self.rect.bottom_right.y - self.rect.top_left.y
+    }
+
+    /// Returns a `Vector2` containing the width and height of the rounded
+    /// rectangle.
+    #[inline]
+    pub fn size(&self) -> Vector2<T>
     {
         Vector2::new(self.width(), self.height())
     }
@@ -602,28 +3031,33 @@ where
         if !self.rect.contains(point) {
             return false;
         }
-        let inner = self.inner();
-        if inner.contains(point) {
-            return true;
-        }
 
-        let radius_squared = self.radius * self.radius;
-
-        //get distance from the 4 angles of the inner rectangle.
-        let dx = max(
-            max(inner.left() - point.x, point.x - inner.right()),
-            T::zero()
-        );
-        let dy = max(
-            max(inner.top() - point.y, point.y - inner.bottom()),
-            T::zero()
-        );
-
-        if dx * dx + dy * dy <= radius_squared {
-            return true;
-        }
+        // The point is only outside a corner if it's beyond the center of that
+        // corner's circle on both axes, and further away than the radius. Each
+        // distance is measured outwards from the center.
+        let outside_corner = |radius: T, dx: T, dy: T| {
+            dx > T::zero() && dy > T::zero() && dx * dx + dy * dy > radius * radius
+        };
 
-        false
+        let radii = &self.radii;
+
+        !(outside_corner(
+            radii.top_left,
+            self.left() + radii.top_left - point.x,
+            self.top() + radii.top_left - point.y
+        ) || outside_corner(
+            radii.top_right,
+            point.x - (self.right() - radii.top_right),
+            self.top() + radii.top_right - point.y
+        ) || outside_corner(
+            radii.bottom_right,
+            point.x - (self.right() - radii.bottom_right),
+            point.y - (self.bottom() - radii.bottom_right)
+        ) || outside_corner(
+            radii.bottom_left,
+            self.left() + radii.bottom_left - point.x,
+            point.y - (self.bottom() - radii.bottom_left)
+        ))
     }
 }
 
@@ -660,10 +3094,10 @@ where
     pub fn with_offset(&self, offset: impl Into<Vector2<T>>) -> Self
     {
         let offset = offset.into();
-        RoundedRectangle::new(
+        RoundedRectangle::new_with_radii(
             self.rect.top_left + offset,
             self.rect.bottom_right + offset,
-            self.radius
+            self.radii
         )
     }
 }
@@ -679,10 +3113,10 @@ where
     pub fn with_negative_offset(&self, offset: impl Into<Vector2<T>>) -> Self
     {
         let offset = offset.into();
-        RoundedRectangle::new(
+        RoundedRectangle::new_with_radii(
             self.rect.top_left - offset,
             self.rect.bottom_right - offset,
-            self.radius
+            self.radii
         )
     }
 }
@@ -695,10 +3129,10 @@ impl<T: num_traits::AsPrimitive<f32>> RoundedRectangle<T>
     #[must_use]
     pub fn into_f32(self) -> RoundedRectangle<f32>
     {
-        RoundedRectangle::new(
+        RoundedRectangle::new_with_radii(
             self.rect.top_left.into_f32(),
             self.rect.bottom_right.into_f32(),
-            self.radius.as_()
+            self.radii.into_f32()
         )
     }
 }
@@ -711,10 +3145,359 @@ impl<T: num_traits::AsPrimitive<f32> + Copy> RoundedRectangle<T>
     #[must_use]
     pub fn as_f32(&self) -> RoundedRectangle<f32>
     {
-        RoundedRectangle::new(
+        RoundedRectangle::new_with_radii(
             self.rect.top_left.into_f32(),
             self.rect.bottom_right.into_f32(),
-            self.radius.as_()
+            self.radii.into_f32()
+        )
+    }
+}
+
+/// A shape which drawing operations can be clipped to, using
+/// [crate::Graphics2D::set_clip_shape].
+#[derive(Debug, Clone)]
+pub enum ClipShape
+{
+    /// A rectangle.
+    Rectangle(Rectangle),
+
+    /// A rectangle with rounded corners.
+    RoundedRectangle(RoundedRectangle),
+
+    /// A circle.
+    Circle
+    {
+        /// The center point of the circle.
+        center: Vec2,
+
+        /// The radius of the circle.
+        radius: f32
+    },
+
+    /// An arbitrary polygon.
+    Polygon(Polygon)
+}
+
+impl From<Rectangle> for ClipShape
+{
+    fn from(rect: Rectangle) -> Self
+    {
+        ClipShape::Rectangle(rect)
+    }
+}
+
+impl From<RoundedRectangle> for ClipShape
+{
+    fn from(rect: RoundedRectangle) -> Self
+    {
+        ClipShape::RoundedRectangle(rect)
+    }
+}
+
+impl From<Polygon> for ClipShape
+{
+    fn from(polygon: Polygon) -> Self
+    {
+        ClipShape::Polygon(polygon)
+    }
+}
+
+impl ClipShape
+{
+    pub(crate) fn to_polygon(&self) -> Polygon
+    {
+        match self {
+            ClipShape::Rectangle(rect) => Polygon::new(&[
+                *rect.top_left(),
+                rect.top_right(),
+                *rect.bottom_right(),
+                rect.bottom_left()
+            ]),
+
+            ClipShape::RoundedRectangle(rect) => {
+                rounded_rect_polygon(rect, &Matrix2D::IDENTITY)
+            }
+
+            ClipShape::Circle { center, radius } => {
+                let radius = max(*radius, 0.0);
+                let rect = Rectangle::new(
+                    *center - Vec2::new(radius, radius),
+                    *center + Vec2::new(radius, radius)
+                );
+
+                rounded_rect_path(
+                    &rect,
+                    &CornerRadii::uniform(radius),
+                    &Matrix2D::IDENTITY
+                )
+                .to_filled_polygon()
+            }
+
+            ClipShape::Polygon(polygon) => polygon.clone()
+        }
+    }
+}
+
+fn rounded_rect_path(rect: &Rect, radii: &CornerRadii, transform: &Matrix2D) -> Path
+{
+    // Distance of the control points from each end of a cubic Bézier curve
+    // approximating a quarter circle
+    let control = |radius: f32| radius * 0.552_284_8;
+
+    let (left, top, right, bottom) =
+        (rect.left(), rect.top(), rect.right(), rect.bottom());
+
+    let (tl, tr, br, bl) = (
+        radii.top_left,
+        radii.top_right,
+        radii.bottom_right,
+        radii.bottom_left
+    );
+
+    // Bézier curves are unaffected by affine transformations of their control
+    // points, so transform those rather than the flattened path
+    let point = |x: f32, y: f32| transform.transform_point((x, y));
+
+    Path::new()
+        .move_to(point(left + tl, top))
+        .line_to(point(right - tr, top))
+        .cubic_to(
+            point(right - tr + control(tr), top),
+            point(right, top + tr - control(tr)),
+            point(right, top + tr)
+        )
+        .line_to(point(right, bottom - br))
+        .cubic_to(
+            point(right, bottom - br + control(br)),
+            point(right - br + control(br), bottom),
+            point(right - br, bottom)
+        )
+        .line_to(point(left + bl, bottom))
+        .cubic_to(
+            point(left + bl - control(bl), bottom),
+            point(left, bottom - bl + control(bl)),
+            point(left, bottom - bl)
+        )
+        .line_to(point(left, top + tl))
+        .cubic_to(
+            point(left, top + tl - control(tl)),
+            point(left + tl - control(tl), top),
+            point(left + tl, top)
+        )
+        .close()
+}
+
+/// Returns a polygon covering the rounded rectangle after it has been
+/// transformed. Each radius is clamped to between zero and half of the smaller
+/// side of the rectangle.
+fn rounded_rect_polygon(rect: &RoundedRectangle, transform: &Matrix2D) -> Polygon
+{
+    let limit = min(rect.width(), rect.height()) / 2.0;
+    let clamp = |radius: f32| min(max(radius, 0.0), limit);
+    let radii = rect.radii();
+
+    let radii = CornerRadii::new(
+        clamp(radii.top_left()),
+        clamp(radii.top_right()),
+        clamp(radii.bottom_right()),
+        clamp(radii.bottom_left())
+    );
+
+    rounded_rect_path(rect.as_rectangle(), &radii, transform).to_filled_polygon()
+}
+
+/// Returns a polygon covering the ellipse after it has been transformed.
+fn ellipse_polygon(center: Vec2, radii: Vec2, transform: &Matrix2D) -> Polygon
+{
+    let unit_circle = Rect::from_tuples((-1.0, -1.0), (1.0, 1.0));
+
+    let transform = Matrix2D::scale(Vec2::new(max(radii.x, 0.0), max(radii.y, 0.0)))
+        .then(&Matrix2D::translate(center))
+        .then(transform);
+
+    rounded_rect_path(&unit_circle, &CornerRadii::uniform(1.0), &transform)
+        .to_filled_polygon()
+}
+
+/// A shape which can be moved, scaled, rotated, or otherwise transformed
+/// using a [Matrix2D], the same type used by
+/// [crate::Graphics2D::set_transform].
+///
+/// Shapes which can't represent the result of an arbitrary transformation
+/// (for example, a [Rectangle] which has been rotated) return a [Polygon]
+/// instead.
+///
+/// ```rust
+/// use speedy2d::dimen::Vec2;
+/// use speedy2d::shape::{Rect, Transformable};
+///
+/// let rect = Rect::from_tuples((0.0, 0.0), (100.0, 50.0));
+/// let rotated = rect.rotated(std::f32::consts::FRAC_PI_4);
+///
+/// assert!(rotated.contains(Vec2::new(10.0, 20.0)));
+/// assert!(!rotated.contains(Vec2::new(20.0, 10.0)));
+/// ```
+pub trait Transformable
+{
+    /// The type of shape produced by transforming this shape.
+    type Output;
+
+    /// Returns a copy of this shape, with the specified transformation applied
+    /// to it.
+    #[must_use]
+    fn transformed(&self, transform: &Matrix2D) -> Self::Output;
+
+    /// Returns a copy of this shape, moved by the specified offset.
+    #[inline]
+    #[must_use]
+    fn translated<V: Into<Vec2>>(&self, offset: V) -> Self::Output
+    {
+        self.transformed(&Matrix2D::translate(offset))
+    }
+
+    /// Returns a copy of this shape, scaled relative to the origin by the
+    /// specified horizontal and vertical factors.
+    #[inline]
+    #[must_use]
+    fn scaled<V: Into<Vec2>>(&self, factor: V) -> Self::Output
+    {
+        self.transformed(&Matrix2D::scale(factor))
+    }
+
+    /// Returns a copy of this shape, rotated clockwise around the origin by the
+    /// specified angle in radians.
+    #[inline]
+    #[must_use]
+    fn rotated(&self, radians: f32) -> Self::Output
+    {
+        self.transformed(&Matrix2D::rotate(radians))
+    }
+}
+
+impl Transformable for Polygon
+{
+    type Output = Polygon;
+
+    fn transformed(&self, transform: &Matrix2D) -> Polygon
+    {
+        Polygon {
+            triangles: self
+                .triangles
+                .iter()
+                .map(|triangle| triangle.map(|vertex| transform.transform_point(vertex)))
+                .collect(),
+            contours: self
+                .contours
+                .iter()
+                .map(|contour| {
+                    contour
+                        .iter()
+                        .map(|vertex| transform.transform_point(*vertex))
+                        .collect()
+                })
+                .collect(),
+            fill_rule: self.fill_rule
+        }
+    }
+}
+
+/// Curves in the path have already been approximated using straight lines,
+/// so scaling a path up may make those lines visible.
+impl Transformable for Path
+{
+    type Output = Path;
+
+    fn transformed(&self, transform: &Matrix2D) -> Path
+    {
+        Path {
+            contours: self
+                .contours
+                .iter()
+                .map(|contour| PathContour {
+                    points: contour
+                        .points
+                        .iter()
+                        .map(|point| transform.transform_point(*point))
+                        .collect(),
+                    closed: contour.closed
+                })
+                .collect(),
+            fill_rule: self.fill_rule
+        }
+    }
+}
+
+impl Transformable for Rect
+{
+    type Output = Polygon;
+
+    fn transformed(&self, transform: &Matrix2D) -> Polygon
+    {
+        Polygon::new(
+            &[
+                *self.top_left(),
+                self.top_right(),
+                *self.bottom_right(),
+                self.bottom_left()
+            ]
+            .map(|vertex| transform.transform_point(vertex))
+        )
+    }
+}
+
+impl Transformable for RoundedRectangle
+{
+    type Output = Polygon;
+
+    fn transformed(&self, transform: &Matrix2D) -> Polygon
+    {
+        rounded_rect_polygon(self, transform)
+    }
+}
+
+impl Transformable for RotatedRectangle
+{
+    type Output = Polygon;
+
+    fn transformed(&self, transform: &Matrix2D) -> Polygon
+    {
+        Polygon::new(
+            &self
+                .vertices()
+                .map(|vertex| transform.transform_point(vertex))
+        )
+    }
+}
+
+impl Transformable for Ellipse
+{
+    type Output = Polygon;
+
+    fn transformed(&self, transform: &Matrix2D) -> Polygon
+    {
+        ellipse_polygon(self.center(), self.radii(), transform)
+    }
+}
+
+impl Transformable for Circle
+{
+    type Output = Polygon;
+
+    fn transformed(&self, transform: &Matrix2D) -> Polygon
+    {
+        ellipse_polygon(self.center, Vec2::new(self.radius, self.radius), transform)
+    }
+}
+
+impl Transformable for LineSegment
+{
+    type Output = LineSegment;
+
+    fn transformed(&self, transform: &Matrix2D) -> LineSegment
+    {
+        LineSegment::new(
+            transform.transform_point(self.start),
+            transform.transform_point(self.end)
         )
     }
 }
@@ -40,7 +40,7 @@ use crate::glbackend::GLBackendGlow;
 use crate::glwrapper::GLVersion;
 #[cfg(feature = "windowing")]
 use crate::web::WebPendingStatus::{Active, AlreadyTriggered};
-use crate::{GLRenderer, GLRendererCreationError};
+use crate::{GLRenderer, GLRendererCreationError, GLRendererOptions};
 
 #[allow(dead_code)]
 #[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
@@ -144,6 +144,14 @@ impl WebWindow
         })
     }
 
+    #[cfg(feature = "windowing")]
+    pub fn clipboard_write_text(&self, text: &str)
+    {
+        // The returned promise is ignored, as the browser reports failures in
+        // the console
+        let _ = self.window.navigator().clipboard().write_text(text);
+    }
+
     #[cfg(feature = "windowing")]
     pub fn match_media(
         &self,
@@ -191,12 +199,25 @@ impl WebWindow
         &self,
         callback: &RefCell<Closure<T>>
     ) -> Result<WebPending, BacktraceError<ErrorMessage>>
+    {
+        self.set_timeout(callback, 0)
+    }
+
+    #[cfg(feature = "windowing")]
+    pub fn set_timeout<T: ?Sized + 'static>(
+        &self,
+        callback: &RefCell<Closure<T>>,
+        timeout_millis: i32
+    ) -> Result<WebPending, BacktraceError<ErrorMessage>>
     {
         let timeout_id: i32 = self
             .window
-            .set_timeout_with_callback(callback.borrow_mut().as_ref().unchecked_ref())
+            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                callback.borrow_mut().as_ref().unchecked_ref(),
+                timeout_millis
+            )
             .map_err(|err| {
-                ErrorMessage::msg(format!("Failed to request animation frame: {err:?}"))
+                ErrorMessage::msg(format!("Failed to set timeout: {err:?}"))
             })?;
 
         let window = self.window.clone();
@@ -262,6 +283,15 @@ impl WebDocument
             })
     }
 
+    #[cfg(feature = "windowing")]
+    pub fn active_element(&self) -> Option<WebElement>
+    {
+        self.document.active_element().map(|element| WebElement {
+            document: self.clone(),
+            element
+        })
+    }
+
     #[cfg(feature = "windowing")]
     pub fn fullscreen_element(&self) -> Option<WebElement>
     {
@@ -458,7 +488,7 @@ impl WebCanvasElement
         UVec2::new(width, height)
     }
 
-    pub fn get_webgl2_context<V>(
+    pub fn get_webgl_context<V>(
         &self,
         viewport_size_pixels: V
     ) -> Result<GLRenderer, BacktraceError<GLRendererCreationError>>
@@ -468,33 +498,68 @@ impl WebCanvasElement
         let viewport_size_pixels = viewport_size_pixels.into();
 
         log::info!(
-            "Getting WebGL2 context for viewport size {:?}",
+            "Getting WebGL context for viewport size {:?}",
             viewport_size_pixels
         );
 
-        let context = self
-            .canvas
-            .get_context("webgl2")
+        // A stencil buffer is required for clipping to arbitrary shapes
+        let attributes = web_sys::WebGlContextAttributes::new();
+        attributes.set_stencil(true);
+
+        let (gl_context, gl_version) = match self
+            .get_context::<web_sys::WebGl2RenderingContext>("webgl2", &attributes)
+        {
+            Ok(context) => (
+                glow::Context::from_webgl2_context(context),
+                GLVersion::WebGL2_0
+            ),
+            Err(err) => {
+                log::warn!("WebGL2 unavailable, falling back to WebGL1: {:?}", err);
+
+                let context = self.get_context::<web_sys::WebGlRenderingContext>(
+                    "webgl",
+                    &attributes
+                )?;
+
+                (
+                    glow::Context::from_webgl1_context(context),
+                    GLVersion::WebGL1_0
+                )
+            }
+        };
+
+        GLRenderer::new_with_gl_backend(
+            viewport_size_pixels,
+            Rc::new(GLBackendGlow::new(gl_context)),
+            gl_version,
+            GLRendererOptions::default()
+        )
+    }
+
+    fn get_context<T: JsCast>(
+        &self,
+        context_type: &str,
+        attributes: &web_sys::WebGlContextAttributes
+    ) -> Result<T, BacktraceError<GLRendererCreationError>>
+    {
+        self.canvas
+            .get_context_with_context_options(context_type, attributes)
             .map_err(|err| {
                 GLRendererCreationError::msg(format!(
-                    "Failed to get WebGL2 context: '{err:?}'"
+                    "Failed to get {context_type} context: '{err:?}'"
                 ))
             })?
-            .ok_or_else(|| GLRendererCreationError::msg("WebGL2 context not available"))?
-            .dyn_into::<web_sys::WebGl2RenderingContext>()
+            .ok_or_else(|| {
+                GLRendererCreationError::msg(format!(
+                    "{context_type} context not available"
+                ))
+            })?
+            .dyn_into::<T>()
             .map_err(|err| {
                 GLRendererCreationError::msg(format!(
                     "Failed to convert object to rendering context: '{err:?}'"
                 ))
-            })?;
-
-        let gl_context = glow::Context::from_webgl2_context(context);
-
-        GLRenderer::new_with_gl_backend(
-            viewport_size_pixels,
-            Rc::new(GLBackendGlow::new(gl_context)),
-            GLVersion::WebGL2_0
-        )
+            })
     }
 
     #[cfg(feature = "windowing")]
@@ -537,6 +602,15 @@ impl WebCanvasElement
         }
     }
 
+    #[cfg(feature = "windowing")]
+    pub fn is_focused(&self) -> bool
+    {
+        match self.html_element.document().active_element() {
+            None => false,
+            Some(active_elem) => active_elem == *self.html_element().element()
+        }
+    }
+
     #[cfg(feature = "windowing")]
     pub fn is_fullscreen_active(&self) -> bool
     {
@@ -689,11 +763,14 @@ impl WebEventTarget
     {
         let closure = Closure::wrap(callback);
 
+        let options = AddEventListenerOptions::new();
+        options.set_once(once);
+
         self.target
             .add_event_listener_with_callback_and_add_event_listener_options(
                 listener_type,
                 closure.as_ref().unchecked_ref(),
-                AddEventListenerOptions::new().once(once)
+                &options
             )
             .map_err(|err| {
                 ErrorMessage::msg(format!(
@@ -14,12 +14,20 @@
  *  limitations under the License.
  */
 
+use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
 use std::marker::PhantomData;
+use std::time::Duration;
+#[cfg(any(doc, doctest, not(target_arch = "wasm32")))]
+use std::time::Instant;
 
-use crate::dimen::{IVec2, UVec2, Vec2};
+use crate::color::Color;
+use crate::dimen::{IVec2, Matrix2D, UVec2, Vec2};
 use crate::error::{BacktraceError, ErrorMessage};
-use crate::{GLRenderer, Graphics2D};
+#[cfg(any(doc, doctest, all(feature = "gamepad", not(target_arch = "wasm32"))))]
+use crate::gamepad::{GamepadAxis, GamepadButton, GamepadId};
+use crate::shape::{Rect, Rectangle};
+use crate::{Antialiasing, GLRenderer, Graphics2D};
 
 #[cfg(all(not(target_arch = "wasm32"), not(any(doc, doctest))))]
 type WindowHelperInnerType<UserEventType> =
@@ -192,7 +200,60 @@ pub trait WindowHandler<UserEventType = ()>
     {
     }
 
-    /// Invoked when the window scale factor changes.
+    /// Invoked when the window gains or loses keyboard focus. For example,
+    /// games may wish to pause and release the cursor when focus is lost.
+    ///
+    /// For `WebCanvas`, this reports the focus of the canvas element.
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_window_focus_changed(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        focused: bool
+    )
+    {
+    }
+
+    /// Invoked when the window is minimized. Rendering and audio may be
+    /// paused until the window is restored.
+    ///
+    /// For `WebCanvas`, this callback is never invoked.
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_window_minimized(&mut self, helper: &mut WindowHelper<UserEventType>) {}
+
+    /// Invoked when the window is maximized, including when a minimized window
+    /// is restored to its previously maximized state.
+    ///
+    /// For `WebCanvas`, this callback is never invoked.
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_window_maximized(&mut self, helper: &mut WindowHelper<UserEventType>) {}
+
+    /// Invoked when the window returns to its normal size after being
+    /// minimized or maximized.
+    ///
+    /// For `WebCanvas`, this callback is never invoked.
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_window_restored(&mut self, helper: &mut WindowHelper<UserEventType>) {}
+
+    /// Invoked when the user attempts to close the window, for example using
+    /// the close button in the title bar. Call [WindowHelper::cancel_close]
+    /// from this callback to keep the window open, for example to ask the
+    /// user to save their changes first.
+    ///
+    /// If the close is not cancelled, closing the first window ends the event
+    /// loop. For `WebCanvas`, this callback is never invoked.
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_window_close_requested(&mut self, helper: &mut WindowHelper<UserEventType>) {}
+
+    /// Invoked when the window scale factor changes, for example when the
+    /// window is moved to a monitor with a different DPI.
+    ///
+    /// To avoid having to rescale the drawing manually, see
+    /// [WindowCreationOptions::with_automatic_scaling()].
     #[allow(unused_variables)]
     #[inline]
     fn on_scale_factor_changed(
@@ -231,6 +292,23 @@ pub trait WindowHandler<UserEventType = ()>
     {
     }
 
+    /// Invoked when the mouse moves, providing the raw relative movement
+    /// since the last event.
+    ///
+    /// Unlike [WindowHandler::on_mouse_move], this is not limited by the edges
+    /// of the window or screen, which makes it suitable for camera controls in
+    /// combination with [WindowHelper::set_cursor_grab]. The movement may not
+    /// have pointer acceleration applied, so the units depend on the platform.
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_mouse_motion_relative(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        delta: Vec2
+    )
+    {
+    }
+
     /// Invoked when a mouse button is pressed.
     #[allow(unused_variables)]
     #[inline]
@@ -315,6 +393,375 @@ pub trait WindowHandler<UserEventType = ()>
     )
     {
     }
+
+    /// Invoked when the text being composed using an input method editor (IME)
+    /// changes. An empty string means that composition has ended.
+    ///
+    /// The text should be displayed at the insertion point, but not yet
+    /// inserted. If present, `cursor_range` contains the byte offsets of the
+    /// start and end of the cursor within the text.
+    ///
+    /// IME events are only delivered after calling
+    /// [WindowHelper::set_ime_allowed].
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_ime_preedit(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        text: &str,
+        cursor_range: Option<(usize, usize)>
+    )
+    {
+    }
+
+    /// Invoked when text composed using an input method editor (IME) should be
+    /// inserted. [WindowHandler::on_keyboard_char] is not invoked for this
+    /// text.
+    ///
+    /// IME events are only delivered after calling
+    /// [WindowHelper::set_ime_allowed].
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_ime_commit(&mut self, helper: &mut WindowHelper<UserEventType>, text: &str) {}
+
+    /// Invoked when a gamepad is connected. Gamepads which were connected
+    /// before the event loop started can be found using
+    /// [WindowHelper::get_gamepads].
+    ///
+    /// Gamepad events are delivered to the focused window, or to the first
+    /// window if no window has focus. This requires the `gamepad` feature.
+    #[cfg(any(doc, doctest, all(feature = "gamepad", not(target_arch = "wasm32"))))]
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_gamepad_connected(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad: GamepadId
+    )
+    {
+    }
+
+    /// Invoked when a gamepad is disconnected.
+    #[cfg(any(doc, doctest, all(feature = "gamepad", not(target_arch = "wasm32"))))]
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_gamepad_disconnected(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad: GamepadId
+    )
+    {
+    }
+
+    /// Invoked when a gamepad button is pressed.
+    #[cfg(any(doc, doctest, all(feature = "gamepad", not(target_arch = "wasm32"))))]
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_gamepad_button_down(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad: GamepadId,
+        button: GamepadButton
+    )
+    {
+    }
+
+    /// Invoked when a gamepad button is released.
+    #[cfg(any(doc, doctest, all(feature = "gamepad", not(target_arch = "wasm32"))))]
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_gamepad_button_up(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad: GamepadId,
+        button: GamepadButton
+    )
+    {
+    }
+
+    /// Invoked when the value of a gamepad axis changes. The value is in the
+    /// range `-1.0` to `1.0`.
+    #[cfg(any(doc, doctest, all(feature = "gamepad", not(target_arch = "wasm32"))))]
+    #[allow(unused_variables)]
+    #[inline]
+    fn on_gamepad_axis(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad: GamepadId,
+        axis: GamepadAxis,
+        value: f32
+    )
+    {
+    }
+}
+
+impl<UserEventType, H> WindowHandler<UserEventType> for Box<H>
+where
+    H: WindowHandler<UserEventType> + ?Sized
+{
+    #[inline]
+    fn on_start(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        info: WindowStartupInfo
+    )
+    {
+        (**self).on_start(helper, info)
+    }
+
+    #[inline]
+    fn on_user_event(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        user_event: UserEventType
+    )
+    {
+        (**self).on_user_event(helper, user_event)
+    }
+
+    #[inline]
+    fn on_resize(&mut self, helper: &mut WindowHelper<UserEventType>, size_pixels: UVec2)
+    {
+        (**self).on_resize(helper, size_pixels)
+    }
+
+    #[inline]
+    fn on_mouse_grab_status_changed(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        mouse_grabbed: bool
+    )
+    {
+        (**self).on_mouse_grab_status_changed(helper, mouse_grabbed)
+    }
+
+    #[inline]
+    fn on_fullscreen_status_changed(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        fullscreen: bool
+    )
+    {
+        (**self).on_fullscreen_status_changed(helper, fullscreen)
+    }
+
+    #[inline]
+    fn on_window_focus_changed(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        focused: bool
+    )
+    {
+        (**self).on_window_focus_changed(helper, focused)
+    }
+
+    #[inline]
+    fn on_window_minimized(&mut self, helper: &mut WindowHelper<UserEventType>)
+    {
+        (**self).on_window_minimized(helper)
+    }
+
+    #[inline]
+    fn on_window_maximized(&mut self, helper: &mut WindowHelper<UserEventType>)
+    {
+        (**self).on_window_maximized(helper)
+    }
+
+    #[inline]
+    fn on_window_restored(&mut self, helper: &mut WindowHelper<UserEventType>)
+    {
+        (**self).on_window_restored(helper)
+    }
+
+    #[inline]
+    fn on_window_close_requested(&mut self, helper: &mut WindowHelper<UserEventType>)
+    {
+        (**self).on_window_close_requested(helper)
+    }
+
+    #[inline]
+    fn on_scale_factor_changed(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        scale_factor: f64
+    )
+    {
+        (**self).on_scale_factor_changed(helper, scale_factor)
+    }
+
+    #[inline]
+    fn on_draw(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        graphics: &mut Graphics2D
+    )
+    {
+        (**self).on_draw(helper, graphics)
+    }
+
+    #[inline]
+    fn on_mouse_move(&mut self, helper: &mut WindowHelper<UserEventType>, position: Vec2)
+    {
+        (**self).on_mouse_move(helper, position)
+    }
+
+    #[inline]
+    fn on_mouse_motion_relative(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        delta: Vec2
+    )
+    {
+        (**self).on_mouse_motion_relative(helper, delta)
+    }
+
+    #[inline]
+    fn on_mouse_button_down(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        button: MouseButton
+    )
+    {
+        (**self).on_mouse_button_down(helper, button)
+    }
+
+    #[inline]
+    fn on_mouse_button_up(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        button: MouseButton
+    )
+    {
+        (**self).on_mouse_button_up(helper, button)
+    }
+
+    #[inline]
+    fn on_mouse_wheel_scroll(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        distance: MouseScrollDistance
+    )
+    {
+        (**self).on_mouse_wheel_scroll(helper, distance)
+    }
+
+    #[inline]
+    fn on_key_down(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        virtual_key_code: Option<VirtualKeyCode>,
+        scancode: KeyScancode
+    )
+    {
+        (**self).on_key_down(helper, virtual_key_code, scancode)
+    }
+
+    #[inline]
+    fn on_key_up(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        virtual_key_code: Option<VirtualKeyCode>,
+        scancode: KeyScancode
+    )
+    {
+        (**self).on_key_up(helper, virtual_key_code, scancode)
+    }
+
+    #[inline]
+    fn on_keyboard_char(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        unicode_codepoint: char
+    )
+    {
+        (**self).on_keyboard_char(helper, unicode_codepoint)
+    }
+
+    #[inline]
+    fn on_ime_preedit(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        text: &str,
+        cursor_range: Option<(usize, usize)>
+    )
+    {
+        (**self).on_ime_preedit(helper, text, cursor_range)
+    }
+
+    #[inline]
+    fn on_ime_commit(&mut self, helper: &mut WindowHelper<UserEventType>, text: &str)
+    {
+        (**self).on_ime_commit(helper, text)
+    }
+
+    #[inline]
+    fn on_keyboard_modifiers_changed(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        state: ModifiersState
+    )
+    {
+        (**self).on_keyboard_modifiers_changed(helper, state)
+    }
+
+    #[cfg(any(doc, doctest, all(feature = "gamepad", not(target_arch = "wasm32"))))]
+    #[inline]
+    fn on_gamepad_connected(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad: GamepadId
+    )
+    {
+        (**self).on_gamepad_connected(helper, gamepad)
+    }
+
+    #[cfg(any(doc, doctest, all(feature = "gamepad", not(target_arch = "wasm32"))))]
+    #[inline]
+    fn on_gamepad_disconnected(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad: GamepadId
+    )
+    {
+        (**self).on_gamepad_disconnected(helper, gamepad)
+    }
+
+    #[cfg(any(doc, doctest, all(feature = "gamepad", not(target_arch = "wasm32"))))]
+    #[inline]
+    fn on_gamepad_button_down(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad: GamepadId,
+        button: GamepadButton
+    )
+    {
+        (**self).on_gamepad_button_down(helper, gamepad, button)
+    }
+
+    #[cfg(any(doc, doctest, all(feature = "gamepad", not(target_arch = "wasm32"))))]
+    #[inline]
+    fn on_gamepad_button_up(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad: GamepadId,
+        button: GamepadButton
+    )
+    {
+        (**self).on_gamepad_button_up(helper, gamepad, button)
+    }
+
+    #[cfg(any(doc, doctest, all(feature = "gamepad", not(target_arch = "wasm32"))))]
+    #[inline]
+    fn on_gamepad_axis(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad: GamepadId,
+        axis: GamepadAxis,
+        value: f32
+    )
+    {
+        (**self).on_gamepad_axis(helper, gamepad, axis, value)
+    }
 }
 
 pub(crate) struct DrawingWindowHandler<UserEventType, H>
@@ -324,23 +771,118 @@ where
 {
     window_handler: H,
     renderer: GLRenderer,
+    automatic_scaling: bool,
+    scale_factor: f64,
+    fixed_aspect_ratio: Option<UVec2>,
+    viewport_size_pixels: UVec2,
     phantom: PhantomData<UserEventType>
 }
 
+/// Converts a mouse position reported by the window into the coordinates
+/// seen by the window handler, which are relative to the letterboxed content
+/// area (if any) and divided by the scale factor (if automatic scaling is
+/// enabled). While the mouse is grabbed, the position is a relative movement,
+/// so only the scaling applies.
+fn handler_mouse_position(
+    position: Vec2,
+    letterbox_offset: Option<UVec2>,
+    scaling: Option<f32>,
+    mouse_grabbed: bool
+) -> Vec2
+{
+    let position = match letterbox_offset {
+        Some(offset) if !mouse_grabbed => position - offset.into_f32(),
+        _ => position
+    };
+
+    match scaling {
+        Some(scale_factor) => position / scale_factor,
+        None => position
+    }
+}
+
+/// Prepares a frame for the window handler, and then invokes `draw`. If the
+/// window is letterboxed, the space around the content area is filled with
+/// black, and the viewport is restricted to the content area while drawing.
+fn draw_handler_frame<R>(
+    graphics: &mut Graphics2D,
+    letterbox: Option<Rectangle<u32>>,
+    scaling: Option<f32>,
+    draw: impl FnOnce(&mut Graphics2D) -> R
+) -> R
+{
+    let letterboxed = letterbox.is_some();
+
+    if letterboxed {
+        graphics.clear_screen(Color::BLACK);
+        graphics.set_content_area(letterbox);
+    }
+
+    if let Some(scale_factor) = scaling {
+        graphics.push_transform(Matrix2D::scale((scale_factor, scale_factor)));
+    }
+
+    let result = draw(graphics);
+
+    // The black bars must be cleared at the start of the next frame
+    if letterboxed {
+        graphics.set_content_area(None);
+    }
+
+    result
+}
+
 impl<UserEventType, H> DrawingWindowHandler<UserEventType, H>
 where
     H: WindowHandler<UserEventType>,
     UserEventType: 'static
 {
-    pub fn new(window_handler: H, renderer: GLRenderer) -> Self
+    pub fn new(
+        window_handler: H,
+        renderer: GLRenderer,
+        automatic_scaling: bool,
+        fixed_aspect_ratio: Option<UVec2>
+    ) -> Self
     {
         DrawingWindowHandler {
             window_handler,
             renderer,
+            automatic_scaling,
+            scale_factor: 1.0,
+            fixed_aspect_ratio,
+            viewport_size_pixels: UVec2::ZERO,
             phantom: PhantomData
         }
     }
 
+    /// The area of the viewport which is made available to the window
+    /// handler. If the aspect ratio is fixed, this is the largest centered
+    /// area with that aspect ratio, otherwise it's the whole viewport.
+    fn content_area(&self) -> Rectangle<u32>
+    {
+        let viewport = self.viewport_size_pixels;
+
+        let size = match self.fixed_aspect_ratio {
+            None => viewport,
+            Some(ratio) => {
+                let width_for_height =
+                    viewport.y as u64 * ratio.x as u64 / ratio.y as u64;
+
+                if width_for_height <= viewport.x as u64 {
+                    UVec2::new(width_for_height as u32, viewport.y)
+                } else {
+                    let height_for_width =
+                        viewport.x as u64 * ratio.y as u64 / ratio.x as u64;
+                    UVec2::new(viewport.x, height_for_width as u32)
+                }
+            }
+        };
+
+        let top_left = UVec2::new((viewport.x - size.x) / 2, (viewport.y - size.y) / 2);
+
+        Rectangle::new(top_left, top_left + size)
+    }
+
     #[inline]
     pub fn on_start(
         &mut self,
@@ -348,6 +890,12 @@ where
         info: WindowStartupInfo
     )
     {
+        self.scale_factor = info.scale_factor();
+        self.viewport_size_pixels = *info.viewport_size_pixels();
+
+        let info =
+            WindowStartupInfo::new(self.content_area().size(), info.scale_factor());
+
         self.window_handler.on_start(helper, info);
     }
 
@@ -369,7 +917,10 @@ where
     )
     {
         self.renderer.set_viewport_size_pixels(size_pixels);
-        self.window_handler.on_resize(helper, size_pixels)
+        self.viewport_size_pixels = size_pixels;
+
+        self.window_handler
+            .on_resize(helper, self.content_area().size())
     }
 
     #[inline]
@@ -394,6 +945,49 @@ where
             .on_fullscreen_status_changed(helper, fullscreen)
     }
 
+    #[inline]
+    pub fn on_window_focus_changed(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        focused: bool
+    )
+    {
+        // Keys released while the window isn't focused aren't reported
+        if !focused {
+            helper.keys_down.clear();
+        }
+
+        self.window_handler.on_window_focus_changed(helper, focused)
+    }
+
+    #[inline]
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub fn on_window_minimized(&mut self, helper: &mut WindowHelper<UserEventType>)
+    {
+        self.window_handler.on_window_minimized(helper)
+    }
+
+    #[inline]
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub fn on_window_maximized(&mut self, helper: &mut WindowHelper<UserEventType>)
+    {
+        self.window_handler.on_window_maximized(helper)
+    }
+
+    #[inline]
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub fn on_window_restored(&mut self, helper: &mut WindowHelper<UserEventType>)
+    {
+        self.window_handler.on_window_restored(helper)
+    }
+
+    #[inline]
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub fn on_window_close_requested(&mut self, helper: &mut WindowHelper<UserEventType>)
+    {
+        self.window_handler.on_window_close_requested(helper)
+    }
+
     #[inline]
     pub fn on_scale_factor_changed(
         &mut self,
@@ -401,6 +995,12 @@ where
         scale_factor: f64
     )
     {
+        self.scale_factor = scale_factor;
+
+        if self.automatic_scaling {
+            helper.request_redraw();
+        }
+
         self.window_handler
             .on_scale_factor_changed(helper, scale_factor)
     }
@@ -408,22 +1008,49 @@ where
     #[inline]
     pub fn on_draw(&mut self, helper: &mut WindowHelper<UserEventType>)
     {
+        let scaling = self.automatic_scaling.then_some(self.scale_factor as f32);
+        let letterbox = self.fixed_aspect_ratio.map(|_| self.content_area());
         let renderer = &mut self.renderer;
         let window_handler = &mut self.window_handler;
 
-        renderer.draw_frame(|graphics| window_handler.on_draw(helper, graphics))
+        renderer.draw_frame(|graphics| {
+            draw_handler_frame(graphics, letterbox, scaling, |graphics| {
+                window_handler.on_draw(helper, graphics)
+            })
+        })
     }
 
+    /// If the mouse is grabbed, `position` is the movement of the cursor
+    /// relative to the center of the window.
     #[inline]
     pub fn on_mouse_move(
         &mut self,
         helper: &mut WindowHelper<UserEventType>,
-        position: Vec2
+        position: Vec2,
+        mouse_grabbed: bool
     )
     {
+        let position = handler_mouse_position(
+            position,
+            self.fixed_aspect_ratio
+                .map(|_| *self.content_area().top_left()),
+            self.automatic_scaling.then_some(self.scale_factor as f32),
+            mouse_grabbed
+        );
+
         self.window_handler.on_mouse_move(helper, position)
     }
 
+    #[inline]
+    pub fn on_mouse_motion_relative(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        delta: Vec2
+    )
+    {
+        self.window_handler.on_mouse_motion_relative(helper, delta)
+    }
+
     #[inline]
     pub fn on_mouse_button_down(
         &mut self,
@@ -462,6 +1089,10 @@ where
         scancode: KeyScancode
     )
     {
+        if let Some(virtual_key_code) = virtual_key_code {
+            helper.keys_down.insert(virtual_key_code);
+        }
+
         self.window_handler
             .on_key_down(helper, virtual_key_code, scancode)
     }
@@ -474,6 +1105,10 @@ where
         scancode: KeyScancode
     )
     {
+        if let Some(virtual_key_code) = virtual_key_code {
+            helper.keys_down.remove(&virtual_key_code);
+        }
+
         self.window_handler
             .on_key_up(helper, virtual_key_code, scancode)
     }
@@ -489,6 +1124,26 @@ where
             .on_keyboard_char(helper, unicode_codepoint)
     }
 
+    #[inline]
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub fn on_ime_preedit(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        text: &str,
+        cursor_range: Option<(usize, usize)>
+    )
+    {
+        self.window_handler
+            .on_ime_preedit(helper, text, cursor_range)
+    }
+
+    #[inline]
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub fn on_ime_commit(&mut self, helper: &mut WindowHelper<UserEventType>, text: &str)
+    {
+        self.window_handler.on_ime_commit(helper, text)
+    }
+
     #[inline]
     pub fn on_keyboard_modifiers_changed(
         &mut self,
@@ -496,9 +1151,73 @@ where
         state: ModifiersState
     )
     {
+        helper.modifiers = state.clone();
+
         self.window_handler
             .on_keyboard_modifiers_changed(helper, state)
     }
+
+    #[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+    #[inline]
+    pub fn on_gamepad_connected(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad: GamepadId
+    )
+    {
+        self.window_handler.on_gamepad_connected(helper, gamepad)
+    }
+
+    #[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+    #[inline]
+    pub fn on_gamepad_disconnected(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad: GamepadId
+    )
+    {
+        self.window_handler.on_gamepad_disconnected(helper, gamepad)
+    }
+
+    #[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+    #[inline]
+    pub fn on_gamepad_button_down(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad: GamepadId,
+        button: GamepadButton
+    )
+    {
+        self.window_handler
+            .on_gamepad_button_down(helper, gamepad, button)
+    }
+
+    #[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+    #[inline]
+    pub fn on_gamepad_button_up(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad: GamepadId,
+        button: GamepadButton
+    )
+    {
+        self.window_handler
+            .on_gamepad_button_up(helper, gamepad, button)
+    }
+
+    #[cfg(all(feature = "gamepad", not(target_arch = "wasm32")))]
+    #[inline]
+    pub fn on_gamepad_axis(
+        &mut self,
+        helper: &mut WindowHelper<UserEventType>,
+        gamepad: GamepadId,
+        axis: GamepadAxis,
+        value: f32
+    )
+    {
+        self.window_handler
+            .on_gamepad_axis(helper, gamepad, axis, value)
+    }
 }
 
 /// A set of helper methods to perform actions on a [crate::Window].
@@ -506,14 +1225,20 @@ pub struct WindowHelper<UserEventType = ()>
 where
     UserEventType: 'static
 {
-    inner: WindowHelperInnerType<UserEventType>
+    inner: WindowHelperInnerType<UserEventType>,
+    keys_down: HashSet<VirtualKeyCode>,
+    modifiers: ModifiersState
 }
 
 impl<UserEventType> WindowHelper<UserEventType>
 {
     pub(crate) fn new(inner: WindowHelperInnerType<UserEventType>) -> Self
     {
-        WindowHelper { inner }
+        WindowHelper {
+            inner,
+            keys_down: HashSet::new(),
+            modifiers: ModifiersState::default()
+        }
     }
 
     #[inline]
@@ -538,47 +1263,154 @@ impl<UserEventType> WindowHelper<UserEventType>
     /// No further callbacks will be given once this function has been called.
     pub fn terminate_loop(&mut self)
     {
-        self.inner.terminate_loop()
+        self.inner.terminate_loop()
+    }
+
+    /// Keeps the window open when called from
+    /// [WindowHandler::on_window_close_requested]. Calling this at any other
+    /// time has no effect.
+    pub fn cancel_close(&mut self)
+    {
+        self.inner.cancel_close()
+    }
+
+    /// Sets the window icon from the provided RGBA pixels.
+    ///
+    /// On Windows, the base icon size is 16x16, however a multiple of this
+    /// (e.g. 32x32) should be provided for high-resolution displays.
+    ///
+    /// For `WebCanvas`, this function has no effect.
+    pub fn set_icon_from_rgba_pixels<S>(
+        &self,
+        data: Vec<u8>,
+        size: S
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    where
+        S: Into<UVec2>
+    {
+        self.inner.set_icon_from_rgba_pixels(data, size.into())
+    }
+
+    /// Sets the visibility of the mouse cursor.
+    pub fn set_cursor_visible(&self, visible: bool)
+    {
+        self.inner.set_cursor_visible(visible)
+    }
+
+    /// Sets the icon of the mouse cursor when it is over the window.
+    pub fn set_cursor_icon(&self, icon: CursorIcon)
+    {
+        self.inner.set_cursor_icon(icon)
+    }
+
+    /// Sets whether text may be entered using an input method editor (IME),
+    /// for example to type Chinese, Japanese, or Korean text. The default is
+    /// `false`.
+    ///
+    /// When enabled, composed text is delivered to
+    /// [WindowHandler::on_ime_preedit] and [WindowHandler::on_ime_commit].
+    ///
+    /// For `WebCanvas`, this function has no effect.
+    pub fn set_ime_allowed(&self, allowed: bool)
+    {
+        self.inner.set_ime_allowed(allowed)
+    }
+
+    /// Sets the area of the window containing the text being edited, in
+    /// pixels, so that the IME candidate window can be placed next to it.
+    ///
+    /// For `WebCanvas`, this function has no effect.
+    pub fn set_ime_position(&self, area: Rect)
+    {
+        self.inner.set_ime_position(area)
+    }
+
+    /// Grabs the cursor, preventing it from leaving the window.
+    pub fn set_cursor_grab(
+        &self,
+        grabbed: bool
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        self.inner.set_cursor_grab(grabbed)
+    }
+
+    /// Set to false to prevent the user from resizing the window.
+    ///
+    /// For `WebCanvas`, this function has no effect.
+    pub fn set_resizable(&self, resizable: bool)
+    {
+        self.inner.set_resizable(resizable)
+    }
+
+    /// Returns `true` if the specified key is currently held down. This allows
+    /// the keyboard state to be polled, for example from
+    /// [WindowHandler::on_draw], rather than tracking it using
+    /// [WindowHandler::on_key_down] and [WindowHandler::on_key_up].
+    ///
+    /// All keys are treated as released when the window loses focus.
+    #[inline]
+    #[must_use]
+    pub fn is_key_down(&self, key: VirtualKeyCode) -> bool
+    {
+        self.keys_down.contains(&key)
+    }
+
+    /// Returns the current state of the modifier keys. See
+    /// [WindowHandler::on_keyboard_modifiers_changed].
+    #[inline]
+    #[must_use]
+    pub fn get_modifiers(&self) -> &ModifiersState
+    {
+        &self.modifiers
+    }
+
+    /// Returns `true` if the window currently has keyboard focus. See
+    /// [WindowHandler::on_window_focus_changed].
+    #[must_use]
+    pub fn is_focused(&self) -> bool
+    {
+        self.inner.is_focused()
     }
 
-    /// Sets the window icon from the provided RGBA pixels.
-    ///
-    /// On Windows, the base icon size is 16x16, however a multiple of this
-    /// (e.g. 32x32) should be provided for high-resolution displays.
+    /// Sets whether the window has a border and title bar. See
+    /// [WindowCreationOptions::with_decorations].
     ///
     /// For `WebCanvas`, this function has no effect.
-    pub fn set_icon_from_rgba_pixels<S>(
-        &self,
-        data: Vec<u8>,
-        size: S
-    ) -> Result<(), BacktraceError<ErrorMessage>>
-    where
-        S: Into<UVec2>
+    pub fn set_decorations(&self, decorations: bool)
     {
-        self.inner.set_icon_from_rgba_pixels(data, size.into())
+        self.inner.set_decorations(decorations)
     }
 
-    /// Sets the visibility of the mouse cursor.
-    pub fn set_cursor_visible(&self, visible: bool)
+    /// Starts moving the window with the mouse, as if the user had started
+    /// dragging its title bar. This allows windows without decorations to
+    /// implement their own title bar.
+    ///
+    /// This should be called from [WindowHandler::on_mouse_button_down], while
+    /// the left mouse button is held. The move ends when the button is
+    /// released.
+    ///
+    /// For `WebCanvas`, this function has no effect.
+    pub fn start_window_drag(&self) -> Result<(), BacktraceError<ErrorMessage>>
     {
-        self.inner.set_cursor_visible(visible)
+        self.inner.start_window_drag()
     }
 
-    /// Grabs the cursor, preventing it from leaving the window.
-    pub fn set_cursor_grab(
-        &self,
-        grabbed: bool
-    ) -> Result<(), BacktraceError<ErrorMessage>>
+    /// Minimizes the window, or restores it if `minimized` is `false`. See
+    /// [WindowHandler::on_window_minimized].
+    ///
+    /// For `WebCanvas`, this function has no effect.
+    pub fn set_minimized(&self, minimized: bool)
     {
-        self.inner.set_cursor_grab(grabbed)
+        self.inner.set_minimized(minimized)
     }
 
-    /// Set to false to prevent the user from resizing the window.
+    /// Maximizes the window, or restores it if `maximized` is `false`. See
+    /// [WindowHandler::on_window_maximized].
     ///
     /// For `WebCanvas`, this function has no effect.
-    pub fn set_resizable(&self, resizable: bool)
+    pub fn set_maximized(&self, maximized: bool)
     {
-        self.inner.set_resizable(resizable)
+        self.inner.set_maximized(maximized)
     }
 
     /// Request that the window is redrawn.
@@ -591,6 +1423,32 @@ impl<UserEventType> WindowHelper<UserEventType>
         self.inner.request_redraw()
     }
 
+    /// Request that the window is redrawn once the specified amount of time
+    /// has passed, allowing animations to run at a chosen rate without
+    /// redrawing continuously. The event loop sleeps until then, unless
+    /// other events arrive.
+    ///
+    /// If a redraw is already scheduled for an earlier time, this has no
+    /// effect. Redraws requested using [WindowHelper::request_redraw] still
+    /// happen on the next frame.
+    #[inline]
+    pub fn request_redraw_after(&self, delay: Duration)
+    {
+        self.inner.request_redraw_after(delay)
+    }
+
+    /// Request that the window is redrawn at the specified time. See
+    /// [WindowHelper::request_redraw_after].
+    ///
+    /// This is not available for `WebCanvas`, as `Instant` is not supported
+    /// in the browser.
+    #[cfg(any(doc, doctest, not(target_arch = "wasm32")))]
+    #[inline]
+    pub fn request_redraw_at(&self, time: Instant)
+    {
+        self.inner.request_redraw_at(time)
+    }
+
     /// Sets the window title.
     pub fn set_title<S: AsRef<str>>(&self, title: S)
     {
@@ -660,6 +1518,29 @@ impl<UserEventType> WindowHelper<UserEventType>
         self.inner.get_scale_factor()
     }
 
+    /// Gets the video modes supported by the monitor containing the window,
+    /// for use with [WindowFullscreenMode::FullscreenExclusive].
+    ///
+    /// For `WebCanvas`, this returns an empty list.
+    pub fn get_video_modes(&self) -> Vec<VideoMode>
+    {
+        self.inner.get_video_modes()
+    }
+
+    /// Gets the monitors which are currently available. The index of a
+    /// monitor in this list may be passed to
+    /// [WindowCreationOptions::with_monitor()].
+    ///
+    /// To move the window to another monitor, see
+    /// [WindowHelper::set_position_pixels()], which uses the same coordinate
+    /// space as [Monitor::position_pixels()].
+    ///
+    /// For `WebCanvas`, this returns an empty list.
+    pub fn get_monitors(&self) -> Vec<Monitor>
+    {
+        self.inner.get_monitors()
+    }
+
     /// Creates a [UserEventSender], which can be used to post custom events to
     /// this event loop from another thread.
     ///
@@ -668,6 +1549,90 @@ impl<UserEventType> WindowHelper<UserEventType>
     {
         self.inner.create_user_event_sender()
     }
+
+    /// Gets the text currently on the system clipboard.
+    ///
+    /// For `WebCanvas`, this always returns an error, as browsers only allow
+    /// the clipboard to be read asynchronously.
+    pub fn clipboard_get_text(&self) -> Result<String, BacktraceError<ErrorMessage>>
+    {
+        self.inner.clipboard_get_text()
+    }
+
+    /// Places the specified text on the system clipboard.
+    ///
+    /// For `WebCanvas`, the text is written asynchronously, and the browser
+    /// may deny permission depending on where this is called from.
+    pub fn clipboard_set_text<S: AsRef<str>>(
+        &self,
+        text: S
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        self.inner.clipboard_set_text(text.as_ref())
+    }
+
+    /// Returns the IDs of the currently connected gamepads. This requires the
+    /// `gamepad` feature.
+    #[cfg(any(doc, doctest, all(feature = "gamepad", not(target_arch = "wasm32"))))]
+    pub fn get_gamepads(&self) -> Vec<GamepadId>
+    {
+        self.inner.get_gamepads()
+    }
+
+    /// Returns the name of the specified gamepad, or `None` if it is not
+    /// connected.
+    #[cfg(any(doc, doctest, all(feature = "gamepad", not(target_arch = "wasm32"))))]
+    pub fn get_gamepad_name(&self, gamepad: GamepadId) -> Option<String>
+    {
+        self.inner.get_gamepad_name(gamepad)
+    }
+
+    /// Returns `true` if the specified gamepad button is currently pressed.
+    ///
+    /// The state is updated before each frame is drawn, so this can be
+    /// polled from [WindowHandler::on_draw].
+    #[cfg(any(doc, doctest, all(feature = "gamepad", not(target_arch = "wasm32"))))]
+    pub fn is_gamepad_button_pressed(
+        &self,
+        gamepad: GamepadId,
+        button: GamepadButton
+    ) -> bool
+    {
+        self.inner.is_gamepad_button_pressed(gamepad, button)
+    }
+
+    /// Returns the current value of the specified gamepad axis, in the range
+    /// `-1.0` to `1.0`. If the gamepad is not connected, or does not have
+    /// the axis, `0.0` is returned.
+    #[cfg(any(doc, doctest, all(feature = "gamepad", not(target_arch = "wasm32"))))]
+    pub fn get_gamepad_axis_value(&self, gamepad: GamepadId, axis: GamepadAxis) -> f32
+    {
+        self.inner.get_gamepad_axis_value(gamepad, axis)
+    }
+
+    /// Opens an additional window, with its own `WindowHandler` and renderer.
+    /// The window is created once the current callback has returned, after
+    /// which the handler's [WindowHandler::on_start] callback is invoked.
+    ///
+    /// Closing an additional window drops its handler, while closing the
+    /// first window ends the event loop. User events are only delivered to
+    /// the handler of the first window.
+    ///
+    /// Additional windows use the same GL configuration as the first window,
+    /// so the multisampling option is ignored. To draw the same images in
+    /// multiple windows, see [WindowCreationOptions::with_shared_resources()].
+    ///
+    /// For `WebCanvas`, this function has no effect.
+    pub fn create_window<H>(
+        &self,
+        title: &str,
+        options: WindowCreationOptions,
+        handler: H
+    ) where
+        H: WindowHandler<UserEventType> + 'static
+    {
+        self.inner.create_window(title, options, Box::new(handler))
+    }
 }
 
 #[cfg(any(doc, doctest, not(target_arch = "wasm32")))]
@@ -718,6 +1683,59 @@ impl WindowStartupInfo
     }
 }
 
+/// The icon of the mouse cursor. See [WindowHelper::set_cursor_icon()].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum CursorIcon
+{
+    /// The platform's default cursor, normally an arrow.
+    Default,
+    /// A pointer indicating a link, normally a hand.
+    Pointer,
+    /// The app is busy, but may still be interacted with.
+    Progress,
+    /// The app is busy, and the user should wait.
+    Wait,
+    /// A cell or set of cells may be selected.
+    Cell,
+    /// A crosshair, often used for selecting a point in an image.
+    Crosshair,
+    /// Text may be selected, normally an I-beam.
+    Text,
+    /// Vertical text may be selected.
+    VerticalText,
+    /// An alias or shortcut is to be created.
+    Alias,
+    /// Something is to be copied.
+    Copy,
+    /// Something is to be moved.
+    Move,
+    /// The dragged item cannot be dropped here.
+    NoDrop,
+    /// The requested action will not be carried out.
+    NotAllowed,
+    /// Something can be grabbed, normally an open hand.
+    Grab,
+    /// Something is being grabbed, normally a closed hand.
+    Grabbing,
+    /// A column can be resized horizontally.
+    ColResize,
+    /// A row can be resized vertically.
+    RowResize,
+    /// Resizing to the east or west.
+    EwResize,
+    /// Resizing to the north or south.
+    NsResize,
+    /// Resizing to the north-east or south-west.
+    NeswResize,
+    /// Resizing to the north-west or south-east.
+    NwseResize,
+    /// Something can be zoomed in.
+    ZoomIn,
+    /// Something can be zoomed out.
+    ZoomOut
+}
+
 /// Identifies a mouse button.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 #[non_exhaustive]
@@ -785,6 +1803,29 @@ pub enum MouseScrollDistance
     }
 }
 
+impl MouseScrollDistance
+{
+    /// Converts the horizontal and vertical scroll distance into pixels,
+    /// using the specified line and page heights for devices which don't
+    /// report pixel-precise values. This allows smooth scrolling on devices
+    /// such as touchpads, while still handling coarser mouse wheel input.
+    ///
+    /// As with the original event, positive `y` values indicate scrolling up.
+    #[must_use]
+    pub fn to_pixels(&self, line_height_pixels: f32, page_height_pixels: f32) -> Vec2
+    {
+        match self {
+            MouseScrollDistance::Lines { x, y, .. } => {
+                Vec2::new(*x as f32, *y as f32) * line_height_pixels
+            }
+            MouseScrollDistance::Pixels { x, y, .. } => Vec2::new(*x as f32, *y as f32),
+            MouseScrollDistance::Pages { x, y, .. } => {
+                Vec2::new(*x as f32, *y as f32) * page_height_pixels
+            }
+        }
+    }
+}
+
 /// A virtual key code.
 #[allow(missing_docs)]
 #[derive(Debug, Hash, Ord, PartialOrd, PartialEq, Eq, Clone, Copy)]
@@ -1028,7 +2069,10 @@ pub(crate) enum WindowCreationMode
     },
 
     /// Create the window in fullscreen borderless mode.
-    FullscreenBorderless
+    FullscreenBorderless,
+
+    /// Create the window in exclusive fullscreen mode.
+    FullscreenExclusive(VideoMode)
 }
 
 /// The size of the window to create.
@@ -1051,10 +2095,12 @@ pub enum WindowSize
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
 pub enum WindowPosition
 {
-    /// Place the window in the center of the primary monitor.
+    /// Place the window in the center of the primary monitor, or the monitor
+    /// selected using [WindowCreationOptions::with_monitor()].
     Center,
     /// Place the window at the specified pixel location from the top left of
-    /// the primary monitor.
+    /// the primary monitor, or the monitor selected using
+    /// [WindowCreationOptions::with_monitor()].
     PrimaryMonitorPixelsFromTopLeft(IVec2)
 }
 
@@ -1065,7 +2111,144 @@ pub enum WindowFullscreenMode
     /// Non-fullscreen mode.
     Windowed,
     /// Fullscreen borderless mode.
-    FullscreenBorderless
+    FullscreenBorderless,
+    /// Exclusive fullscreen mode, changing the resolution and refresh rate of
+    /// the monitor. See [WindowHelper::get_video_modes()].
+    ///
+    /// If the monitor doesn't support the requested resolution, borderless
+    /// fullscreen mode is used instead. When using a web canvas, this is the
+    /// same as [WindowFullscreenMode::FullscreenBorderless].
+    FullscreenExclusive(VideoMode)
+}
+
+/// A resolution and refresh rate supported by a monitor, for use in exclusive
+/// fullscreen mode.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct VideoMode
+{
+    size_pixels: UVec2,
+    refresh_rate_millihertz: u32
+}
+
+impl VideoMode
+{
+    /// Creates a video mode with the specified resolution and refresh rate.
+    ///
+    /// When entering fullscreen mode, the monitor's video mode with the same
+    /// resolution and the closest refresh rate is used. For example, a refresh
+    /// rate of 60 Hz is specified as `60000`.
+    #[inline]
+    #[must_use]
+    pub fn new<S: Into<UVec2>>(size_pixels: S, refresh_rate_millihertz: u32) -> Self
+    {
+        VideoMode {
+            size_pixels: size_pixels.into(),
+            refresh_rate_millihertz
+        }
+    }
+
+    /// The resolution of the monitor in this video mode.
+    #[inline]
+    #[must_use]
+    pub fn size_pixels(&self) -> UVec2
+    {
+        self.size_pixels
+    }
+
+    /// The refresh rate of the monitor in this video mode, in millihertz.
+    #[inline]
+    #[must_use]
+    pub fn refresh_rate_millihertz(&self) -> u32
+    {
+        self.refresh_rate_millihertz
+    }
+}
+
+/// Information about a monitor. See [WindowHelper::get_monitors()].
+#[derive(Debug, PartialEq, Clone)]
+pub struct Monitor
+{
+    name: Option<String>,
+    position_pixels: IVec2,
+    size_pixels: UVec2,
+    scale_factor: f64,
+    refresh_rate_millihertz: Option<u32>,
+    is_primary: bool
+}
+
+impl Monitor
+{
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(
+        name: Option<String>,
+        position_pixels: IVec2,
+        size_pixels: UVec2,
+        scale_factor: f64,
+        refresh_rate_millihertz: Option<u32>,
+        is_primary: bool
+    ) -> Self
+    {
+        Monitor {
+            name,
+            position_pixels,
+            size_pixels,
+            scale_factor,
+            refresh_rate_millihertz,
+            is_primary
+        }
+    }
+
+    /// The human-readable name of the monitor, if available.
+    #[inline]
+    #[must_use]
+    pub fn name(&self) -> Option<&str>
+    {
+        self.name.as_deref()
+    }
+
+    /// The position of the top left corner of the monitor on the desktop, in
+    /// pixels.
+    #[inline]
+    #[must_use]
+    pub fn position_pixels(&self) -> IVec2
+    {
+        self.position_pixels
+    }
+
+    /// The resolution of the monitor, in pixels.
+    #[inline]
+    #[must_use]
+    pub fn size_pixels(&self) -> UVec2
+    {
+        self.size_pixels
+    }
+
+    /// The scale factor of the monitor. See
+    /// [WindowHelper::get_scale_factor()].
+    #[inline]
+    #[must_use]
+    pub fn scale_factor(&self) -> f64
+    {
+        self.scale_factor
+    }
+
+    /// The current refresh rate of the monitor in millihertz, if available.
+    #[inline]
+    #[must_use]
+    pub fn refresh_rate_millihertz(&self) -> Option<u32>
+    {
+        self.refresh_rate_millihertz
+    }
+
+    /// Returns `true` if this is the primary monitor.
+    #[inline]
+    #[must_use]
+    pub fn is_primary(&self) -> bool
+    {
+        self.is_primary
+    }
 }
 
 /// Options used during the creation of a window.
@@ -1074,12 +2257,19 @@ pub struct WindowCreationOptions
 {
     pub(crate) mode: WindowCreationMode,
     pub(crate) multisampling: u16,
+    pub(crate) antialiasing: Antialiasing,
     pub(crate) vsync: bool,
     pub(crate) always_on_top: bool,
     pub(crate) resizable: bool,
     pub(crate) maximized: bool,
     pub(crate) transparent: bool,
-    pub(crate) decorations: bool
+    pub(crate) decorations: bool,
+    pub(crate) shared_resources: bool,
+    pub(crate) icon: Option<(Vec<u8>, UVec2)>,
+    pub(crate) monitor: Option<usize>,
+    pub(crate) automatic_scaling: bool,
+    pub(crate) fixed_aspect_ratio: Option<UVec2>,
+    pub(crate) target_fps: Option<u32>
 }
 
 impl WindowCreationOptions
@@ -1100,6 +2290,18 @@ impl WindowCreationOptions
         Self::new(WindowCreationMode::FullscreenBorderless)
     }
 
+    /// Instantiates a new `WindowCreationOptions` structure with the default
+    /// options, in exclusive fullscreen mode on the primary monitor (see
+    /// [WindowCreationOptions::with_monitor()]), using the closest supported
+    /// video mode. See
+    /// [WindowFullscreenMode::FullscreenExclusive].
+    #[inline]
+    #[must_use]
+    pub fn new_fullscreen_exclusive(video_mode: VideoMode) -> Self
+    {
+        Self::new(WindowCreationMode::FullscreenExclusive(video_mode))
+    }
+
     #[inline]
     #[must_use]
     fn new(mode: WindowCreationMode) -> Self
@@ -1107,12 +2309,19 @@ impl WindowCreationOptions
         WindowCreationOptions {
             mode,
             multisampling: 16,
+            antialiasing: Antialiasing::None,
             vsync: true,
             always_on_top: false,
             resizable: true,
             maximized: false,
             decorations: true,
-            transparent: false
+            transparent: false,
+            shared_resources: false,
+            icon: None,
+            monitor: None,
+            automatic_scaling: false,
+            fixed_aspect_ratio: None,
+            target_fps: None
         }
     }
 
@@ -1129,6 +2338,21 @@ impl WindowCreationOptions
         self
     }
 
+    /// Sets the anti-aliasing technique used by the renderer. Unlike
+    /// [WindowCreationOptions::with_multisampling], this doesn't depend on the
+    /// platform's support for multisampled windows, as the renderer draws to
+    /// its own multisampled framebuffer. See
+    /// [crate::GLRendererOptions::with_antialiasing] for details.
+    ///
+    /// By default this is set to `Antialiasing::None`.
+    #[inline]
+    #[must_use]
+    pub fn with_antialiasing(mut self, antialiasing: Antialiasing) -> Self
+    {
+        self.antialiasing = antialiasing;
+        self
+    }
+
     /// Sets whether or not vsync should be enabled. This can increase latency,
     /// but should eliminate tearing. By default this is set to `true`.
     ///
@@ -1142,6 +2366,22 @@ impl WindowCreationOptions
         self
     }
 
+    /// Limits the rate at which [WindowHandler::on_draw] is invoked when
+    /// redraws are requested continuously, sleeping between frames rather
+    /// than drawing as quickly as possible. By default this is `None`, so
+    /// the frame rate is only limited by vsync.
+    ///
+    /// This is useful when vsync is disabled or not supported by the
+    /// platform. Frames are scheduled at a fixed interval, so that occasional
+    /// slow frames don't lower the average frame rate.
+    #[inline]
+    #[must_use]
+    pub fn with_target_fps(mut self, target_fps: Option<u32>) -> Self
+    {
+        self.target_fps = target_fps;
+        self
+    }
+
     /// Sets whether or not the window can be resized by the user. The default
     /// is `true`.
     #[inline]
@@ -1185,6 +2425,11 @@ impl WindowCreationOptions
     /// Sets whether the background of the window should be transparent. The
     /// default is `false`.
     ///
+    /// If enabled, the window is created with an alpha channel, and any areas
+    /// cleared using [Graphics2D::clear_screen()] with a transparent color
+    /// (such as [crate::color::Color::TRANSPARENT]) will show the content
+    /// behind the window.
+    ///
     /// Note that this depends on platform support, and setting this may have no
     /// effect.
     #[inline]
@@ -1194,7 +2439,224 @@ impl WindowCreationOptions
         self.transparent = transparent;
         self
     }
+
+    /// Sets the window icon from the provided RGBA pixels. By default, the
+    /// platform's default icon is used.
+    ///
+    /// If the pixel data is invalid, the window is created without an icon.
+    /// See [WindowHelper::set_icon_from_rgba_pixels()] to change the icon
+    /// after the window is created.
+    #[inline]
+    #[must_use]
+    pub fn with_icon<S>(mut self, rgba_pixels: Vec<u8>, size: S) -> Self
+    where
+        S: Into<UVec2>
+    {
+        self.icon = Some((rgba_pixels, size.into()));
+        self
+    }
+
+    /// If set to `true`, everything drawn in [WindowHandler::on_draw] is
+    /// scaled by the window's scale factor, and the positions given to
+    /// [WindowHandler::on_mouse_move] are divided by it. This allows drawing
+    /// in device-independent logical pixels, which stay the same physical
+    /// size when the window is moved between monitors with different DPIs.
+    /// The default is `false`.
+    ///
+    /// The scaling is applied as the initial transformation of each frame, so
+    /// it is combined with any transformation passed to
+    /// [Graphics2D::push_transform()], but replaced by
+    /// [Graphics2D::set_transform()]. Sizes given to
+    /// [WindowHandler::on_resize] and clipping areas set using
+    /// [Graphics2D::set_clip()] remain in physical pixels.
+    #[inline]
+    #[must_use]
+    pub fn with_automatic_scaling(mut self, automatic_scaling: bool) -> Self
+    {
+        self.automatic_scaling = automatic_scaling;
+        self
+    }
+
+    /// Fixes the aspect ratio of the area available for drawing, for example
+    /// `16:9` for a game with a fixed-size canvas. When the window is resized
+    /// to a different aspect ratio, the drawing area is letterboxed: it's
+    /// centered in the window, and the space around it is filled with black.
+    /// If either value is zero, the aspect ratio is not fixed, which is the
+    /// default.
+    ///
+    /// The sizes given to [WindowHandler::on_start] and
+    /// [WindowHandler::on_resize] are the size of the drawing area, and the
+    /// positions given to [WindowHandler::on_mouse_move] are relative to its
+    /// top left corner. While drawing, the viewport is restricted to the
+    /// drawing area: clipping areas set using [Graphics2D::set_clip()],
+    /// [Graphics2D::clear_screen()], and [Graphics2D::capture()] are all
+    /// relative to the drawing area, and can't affect the space around it.
+    #[inline]
+    #[must_use]
+    pub fn with_fixed_aspect_ratio(mut self, width: u32, height: u32) -> Self
+    {
+        self.fixed_aspect_ratio =
+            (width > 0 && height > 0).then(|| UVec2::new(width, height));
+        self
+    }
+
+    /// Sets the monitor on which the window is created, using its index in
+    /// the list returned by [WindowHelper::get_monitors()]. The window size,
+    /// position, and fullscreen mode are then relative to this monitor
+    /// rather than the primary monitor.
+    ///
+    /// Monitors are listed in the order reported by the platform. If the
+    /// index is out of range, the primary monitor is used.
+    #[inline]
+    #[must_use]
+    pub fn with_monitor(mut self, index: usize) -> Self
+    {
+        self.monitor = Some(index);
+        self
+    }
+
+    /// When creating an additional window using
+    /// [WindowHelper::create_window()], sets whether or not the new window
+    /// shares resources with the first window. If enabled, images created in
+    /// one window may be drawn in the other. The default is `false`.
+    ///
+    /// This has no effect on the first window.
+    #[inline]
+    #[must_use]
+    pub fn with_shared_resources(mut self, shared_resources: bool) -> Self
+    {
+        self.shared_resources = shared_resources;
+        self
+    }
+
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub(crate) fn frame_interval(&self) -> Option<Duration>
+    {
+        self.target_fps
+            .filter(|target_fps| *target_fps > 0)
+            .map(|target_fps| Duration::from_secs_f64(1.0 / target_fps as f64))
+    }
 }
 
 /// Type representing a keyboard scancode.
 pub type KeyScancode = u32;
+
+#[cfg(test)]
+mod test
+{
+    #[cfg(not(target_arch = "wasm32"))]
+    use crate::color::Color;
+    use crate::dimen::{UVec2, Vec2};
+    #[cfg(not(target_arch = "wasm32"))]
+    use crate::image::{ImageDataType, RawBitmapData};
+    #[cfg(not(target_arch = "wasm32"))]
+    use crate::shape::Rectangle;
+    #[cfg(not(target_arch = "wasm32"))]
+    use crate::window::draw_handler_frame;
+    use crate::window::handler_mouse_position;
+    #[cfg(not(target_arch = "wasm32"))]
+    use crate::{GLRenderer, Graphics2D};
+
+    /// Draws a frame in a 300x100 window, letterboxed to the 100x100 area in
+    /// the middle. Returns the capture of the whole window, and the capture
+    /// made by `draw`.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn draw_letterboxed<F>(draw: F) -> (RawBitmapData, RawBitmapData)
+    where
+        F: FnOnce(&mut Graphics2D) -> RawBitmapData
+    {
+        let mut renderer = GLRenderer::new_software((300, 100)).unwrap();
+        let letterbox = Some(Rectangle::from_tuples((100, 0), (200, 100)));
+
+        renderer.draw_frame(|graphics| {
+            let handler_capture = draw_handler_frame(graphics, letterbox, None, draw);
+            (graphics.capture(ImageDataType::RGB), handler_capture)
+        })
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn pixel(capture: &RawBitmapData, x: u32, y: u32) -> [u8; 3]
+    {
+        let index = ((y * capture.size().x + x) * 3) as usize;
+        let data = capture.data();
+        [data[index], data[index + 1], data[index + 2]]
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn test_letterboxed_clear_screen()
+    {
+        let (window, handler_capture) = draw_letterboxed(|graphics| {
+            graphics.clear_screen(Color::RED);
+            graphics.capture(ImageDataType::RGB)
+        });
+
+        assert_eq!(UVec2::new(100, 100), handler_capture.size());
+        assert_eq!([255, 0, 0], pixel(&handler_capture, 0, 0));
+        assert_eq!([255, 0, 0], pixel(&handler_capture, 99, 99));
+
+        assert_eq!(UVec2::new(300, 100), window.size());
+        assert_eq!([0, 0, 0], pixel(&window, 99, 50));
+        assert_eq!([255, 0, 0], pixel(&window, 100, 50));
+        assert_eq!([255, 0, 0], pixel(&window, 199, 50));
+        assert_eq!([0, 0, 0], pixel(&window, 200, 50));
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn test_letterboxed_clip()
+    {
+        let (window, _) = draw_letterboxed(|graphics| {
+            graphics.clear_screen(Color::WHITE);
+
+            // The clip is relative to the content area, and is cropped to it
+            graphics.set_clip(Some(Rectangle::from_tuples((-50, -50), (20, 30))));
+            graphics.draw_rectangle(
+                Rectangle::from_tuples((-200.0, -100.0), (400.0, 200.0)),
+                Color::GREEN
+            );
+
+            graphics.capture(ImageDataType::RGB)
+        });
+
+        assert_eq!([0, 255, 0], pixel(&window, 100, 0));
+        assert_eq!([0, 255, 0], pixel(&window, 119, 29));
+        assert_eq!([255, 255, 255], pixel(&window, 120, 29));
+        assert_eq!([255, 255, 255], pixel(&window, 119, 30));
+        assert_eq!([0, 0, 0], pixel(&window, 99, 0));
+        assert_eq!([0, 0, 0], pixel(&window, 99, 29));
+    }
+
+    #[test]
+    fn test_handler_mouse_position()
+    {
+        let letterbox = Some(UVec2::new(100, 0));
+
+        assert_eq!(
+            Vec2::new(300.0, 50.0),
+            handler_mouse_position(Vec2::new(300.0, 50.0), None, None, false)
+        );
+
+        assert_eq!(
+            Vec2::new(200.0, 50.0),
+            handler_mouse_position(Vec2::new(300.0, 50.0), letterbox, None, false)
+        );
+
+        assert_eq!(
+            Vec2::new(100.0, 25.0),
+            handler_mouse_position(Vec2::new(300.0, 50.0), letterbox, Some(2.0), false)
+        );
+
+        // While grabbed, the position is a movement which isn't affected by
+        // the letterboxing
+        assert_eq!(
+            Vec2::new(10.0, -4.0),
+            handler_mouse_position(Vec2::new(10.0, -4.0), letterbox, None, true)
+        );
+
+        assert_eq!(
+            Vec2::new(5.0, -2.0),
+            handler_mouse_position(Vec2::new(10.0, -4.0), letterbox, Some(2.0), true)
+        );
+    }
+}
@@ -14,19 +14,18 @@
  *  limitations under the License.
  */
 
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::convert::{TryFrom, TryInto};
 use std::ffi::CString;
 use std::num::NonZeroU32;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
-use glutin::config::{Config, ConfigTemplateBuilder};
+use glutin::config::{Config, ConfigTemplateBuilder, GlConfig};
 use glutin::context::{
-    ContextApi,
-    ContextAttributesBuilder,
     NotCurrentGlContext,
     PossiblyCurrentContext,
-    Version
+    PossiblyCurrentGlContext
 };
 use glutin::display::{GetGlDisplay, GlDisplay};
 use glutin::surface::{
@@ -41,8 +40,10 @@ use raw_window_handle::HasRawWindowHandle;
 use winit::dpi::{LogicalSize, PhysicalPosition, PhysicalSize};
 use winit::error::EventLoopError;
 use winit::event::{
+    DeviceEvent,
     ElementState as GlutinElementState,
     Event as GlutinEvent,
+    Ime,
     KeyEvent,
     MouseScrollDelta as GlutinMouseScrollDelta,
     TouchPhase,
@@ -53,7 +54,8 @@ use winit::event_loop::{
     EventLoop,
     EventLoopBuilder,
     EventLoopClosed,
-    EventLoopProxy
+    EventLoopProxy,
+    EventLoopWindowTarget
 };
 use winit::keyboard::{Key, KeyLocation, NamedKey};
 use winit::monitor::MonitorHandle;
@@ -64,20 +66,29 @@ use winit::window::{
     Window as GlutinWindow,
     Window,
     WindowBuilder,
+    WindowId,
     WindowLevel
 };
 
 use crate::dimen::{IVec2, UVec2, Vec2, Vector2};
 use crate::error::{BacktraceError, ErrorMessage};
+#[cfg(feature = "gamepad")]
+use crate::gamepad::{GamepadAxis, GamepadButton, GamepadId};
 use crate::glbackend::constants::GL_VERSION;
 use crate::glbackend::{GLBackend, GLBackendGlow};
+use crate::glwindow::create_context_with_fallback;
+use crate::glwrapper::GLVersion;
+use crate::shape::Rect;
 use crate::window::{
+    CursorIcon,
     DrawingWindowHandler,
     EventLoopSendError,
     ModifiersState,
+    Monitor,
     MouseButton,
     MouseScrollDistance,
     UserEventSender,
+    VideoMode,
     VirtualKeyCode,
     WindowCreationError,
     WindowCreationMode,
@@ -90,16 +101,22 @@ use crate::window::{
     WindowSize,
     WindowStartupInfo
 };
-use crate::GLRenderer;
+use crate::{GLRenderer, GLRendererOptions};
 
 pub(crate) struct WindowHelperGlutin<UserEventType: 'static>
 {
     window: Rc<Window>,
     event_proxy: EventLoopProxy<UserEventGlutin<UserEventType>>,
     redraw_requested: Cell<bool>,
+    scheduled_redraw_time: Cell<Option<Instant>>,
     terminate_requested: bool,
+    close_cancelled: bool,
     physical_size: UVec2,
-    is_mouse_grabbed: Cell<bool>
+    is_mouse_grabbed: Cell<bool>,
+    pending_windows: PendingWindowsGlutin<UserEventType>,
+    clipboard: RefCell<Option<arboard::Clipboard>>,
+    #[cfg(feature = "gamepad")]
+    gamepads: GamepadsGlutin
 }
 
 impl<UserEventType> WindowHelperGlutin<UserEventType>
@@ -108,16 +125,24 @@ impl<UserEventType> WindowHelperGlutin<UserEventType>
     pub fn new(
         window: &Rc<Window>,
         event_proxy: EventLoopProxy<UserEventGlutin<UserEventType>>,
-        initial_physical_size: UVec2
+        initial_physical_size: UVec2,
+        pending_windows: &PendingWindowsGlutin<UserEventType>,
+        #[cfg(feature = "gamepad")] gamepads: &GamepadsGlutin
     ) -> Self
     {
         WindowHelperGlutin {
             window: Rc::clone(window),
             event_proxy,
             redraw_requested: Cell::new(false),
+            scheduled_redraw_time: Cell::new(None),
             terminate_requested: false,
+            close_cancelled: false,
             physical_size: initial_physical_size,
-            is_mouse_grabbed: Cell::new(false)
+            is_mouse_grabbed: Cell::new(false),
+            pending_windows: Rc::clone(pending_windows),
+            clipboard: RefCell::new(None),
+            #[cfg(feature = "gamepad")]
+            gamepads: gamepads.clone()
         }
     }
 
@@ -134,6 +159,22 @@ impl<UserEventType> WindowHelperGlutin<UserEventType>
         self.redraw_requested.set(redraw_requested);
     }
 
+    #[inline]
+    #[must_use]
+    pub fn get_scheduled_redraw_time(&self) -> Option<Instant>
+    {
+        self.scheduled_redraw_time.get()
+    }
+
+    /// Clears the scheduled redraw if its time has been reached.
+    #[inline]
+    pub fn clear_scheduled_redraw_if_due(&mut self, now: Instant)
+    {
+        if matches!(self.scheduled_redraw_time.get(), Some(time) if time <= now) {
+            self.scheduled_redraw_time.set(None);
+        }
+    }
+
     #[inline]
     pub fn get_event_loop_action(&self) -> WindowEventLoopAction
     {
@@ -148,6 +189,19 @@ impl<UserEventType> WindowHelperGlutin<UserEventType>
         self.terminate_requested = true;
     }
 
+    pub fn cancel_close(&mut self)
+    {
+        self.close_cancelled = true;
+    }
+
+    /// Returns true if [WindowHelperGlutin::cancel_close] was called since the
+    /// last call to this function.
+    #[inline]
+    pub fn take_close_cancelled(&mut self) -> bool
+    {
+        std::mem::take(&mut self.close_cancelled)
+    }
+
     pub fn set_icon_from_rgba_pixels(
         &self,
         data: Vec<u8>,
@@ -168,6 +222,24 @@ impl<UserEventType> WindowHelperGlutin<UserEventType>
         self.window.set_cursor_visible(visible);
     }
 
+    pub fn set_cursor_icon(&self, icon: CursorIcon)
+    {
+        self.window.set_cursor_icon(icon.into());
+    }
+
+    pub fn set_ime_allowed(&self, allowed: bool)
+    {
+        self.window.set_ime_allowed(allowed);
+    }
+
+    pub fn set_ime_position(&self, area: Rect)
+    {
+        self.window.set_ime_cursor_area(
+            PhysicalPosition::new(area.top_left().x, area.top_left().y),
+            PhysicalSize::new(area.width(), area.height())
+        );
+    }
+
     pub fn set_cursor_grab(
         &self,
         grabbed: bool
@@ -199,7 +271,10 @@ impl<UserEventType> WindowHelperGlutin<UserEventType>
                 self.is_mouse_grabbed.set(grabbed);
                 if self
                     .event_proxy
-                    .send_event(UserEventGlutin::MouseGrabStatusChanged(grabbed))
+                    .send_event(UserEventGlutin::MouseGrabStatusChanged(
+                        self.window.id(),
+                        grabbed
+                    ))
                     .is_err()
                 {
                     log::error!("Failed to notify app of cursor grab: event loop closed");
@@ -215,12 +290,54 @@ impl<UserEventType> WindowHelperGlutin<UserEventType>
         self.window.set_resizable(resizable);
     }
 
+    pub fn is_focused(&self) -> bool
+    {
+        self.window.has_focus()
+    }
+
+    pub fn set_decorations(&self, decorations: bool)
+    {
+        self.window.set_decorations(decorations);
+    }
+
+    pub fn start_window_drag(&self) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        self.window
+            .drag_window()
+            .map_err(|err| ErrorMessage::msg_with_cause("Could not drag window", err))
+    }
+
+    pub fn set_minimized(&self, minimized: bool)
+    {
+        self.window.set_minimized(minimized);
+    }
+
+    pub fn set_maximized(&self, maximized: bool)
+    {
+        self.window.set_maximized(maximized);
+    }
+
     #[inline]
     pub fn request_redraw(&self)
     {
         self.redraw_requested.set(true);
     }
 
+    pub fn request_redraw_after(&self, delay: Duration)
+    {
+        self.request_redraw_at(Instant::now() + delay)
+    }
+
+    pub fn request_redraw_at(&self, time: Instant)
+    {
+        let time = match self.scheduled_redraw_time.get() {
+            Some(existing_time) => existing_time.min(time),
+            None => time
+        };
+
+        self.scheduled_redraw_time.set(Some(time));
+    }
+
     pub fn set_title(&self, title: &str)
     {
         self.window.set_title(title);
@@ -230,21 +347,31 @@ impl<UserEventType> WindowHelperGlutin<UserEventType>
     {
         let window = &self.window;
 
-        window.set_fullscreen(match mode {
+        window.set_fullscreen(match &mode {
             WindowFullscreenMode::Windowed => None,
             WindowFullscreenMode::FullscreenBorderless => {
                 Some(winit::window::Fullscreen::Borderless(None))
             }
+            WindowFullscreenMode::FullscreenExclusive(video_mode) => {
+                match window.current_monitor() {
+                    Some(monitor) => Some(exclusive_fullscreen(&monitor, video_mode)),
+                    None => Some(winit::window::Fullscreen::Borderless(None))
+                }
+            }
         });
 
         let is_fullscreen = match mode {
             WindowFullscreenMode::Windowed => false,
-            WindowFullscreenMode::FullscreenBorderless => true
+            WindowFullscreenMode::FullscreenBorderless
+            | WindowFullscreenMode::FullscreenExclusive(_) => true
         };
 
         if self
             .event_proxy
-            .send_event(UserEventGlutin::FullscreenStatusChanged(is_fullscreen))
+            .send_event(UserEventGlutin::FullscreenStatusChanged(
+                self.window.id(),
+                is_fullscreen
+            ))
             .is_err()
         {
             log::error!(
@@ -301,19 +428,185 @@ impl<UserEventType> WindowHelperGlutin<UserEventType>
         self.window.scale_factor()
     }
 
+    pub fn get_video_modes(&self) -> Vec<VideoMode>
+    {
+        let mut video_modes: Vec<VideoMode> = self
+            .window
+            .current_monitor()
+            .into_iter()
+            .flat_map(|monitor| monitor.video_modes())
+            .map(|video_mode| {
+                VideoMode::new(video_mode.size(), video_mode.refresh_rate_millihertz())
+            })
+            .collect();
+
+        // Modes may be listed once for each supported bit depth
+        video_modes.sort_by_key(|video_mode| {
+            (
+                video_mode.size_pixels().x,
+                video_mode.size_pixels().y,
+                video_mode.refresh_rate_millihertz()
+            )
+        });
+        video_modes.dedup();
+
+        video_modes
+    }
+
+    pub fn get_monitors(&self) -> Vec<Monitor>
+    {
+        let primary_monitor = self.window.primary_monitor();
+
+        self.window
+            .available_monitors()
+            .map(|monitor| {
+                Monitor::new(
+                    monitor.name(),
+                    IVec2::new(monitor.position().x, monitor.position().y),
+                    monitor.size().into(),
+                    monitor.scale_factor(),
+                    monitor.refresh_rate_millihertz(),
+                    Some(&monitor) == primary_monitor.as_ref()
+                )
+            })
+            .collect()
+    }
+
     pub fn create_user_event_sender(&self) -> UserEventSender<UserEventType>
     {
         UserEventSender::new(UserEventSenderGlutin::new(self.event_proxy.clone()))
     }
+
+    pub fn clipboard_get_text(&self) -> Result<String, BacktraceError<ErrorMessage>>
+    {
+        self.with_clipboard(|clipboard| clipboard.get_text())
+    }
+
+    pub fn clipboard_set_text(
+        &self,
+        text: &str
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        self.with_clipboard(|clipboard| clipboard.set_text(text))
+    }
+
+    fn with_clipboard<R, F>(&self, callback: F) -> Result<R, BacktraceError<ErrorMessage>>
+    where
+        F: FnOnce(&mut arboard::Clipboard) -> Result<R, arboard::Error>
+    {
+        let mut clipboard = self.clipboard.borrow_mut();
+
+        // The clipboard is kept open, as on some platforms the copied text is
+        // lost once it is closed
+        if clipboard.is_none() {
+            *clipboard = Some(arboard::Clipboard::new().map_err(|err| {
+                ErrorMessage::msg_with_cause("Failed to open clipboard", err)
+            })?);
+        }
+
+        callback(clipboard.as_mut().unwrap()).map_err(|err| {
+            ErrorMessage::msg_with_cause("Failed to access clipboard", err)
+        })
+    }
+
+    #[cfg(feature = "gamepad")]
+    pub fn get_gamepads(&self) -> Vec<GamepadId>
+    {
+        match &self.gamepads {
+            None => Vec::new(),
+            Some(gamepads) => gamepads
+                .borrow()
+                .gamepads()
+                .map(|(id, _)| GamepadId::new(id.into()))
+                .collect()
+        }
+    }
+
+    #[cfg(feature = "gamepad")]
+    pub fn get_gamepad_name(&self, gamepad: GamepadId) -> Option<String>
+    {
+        self.with_gamepad(gamepad, |gamepad| gamepad.name().to_string())
+    }
+
+    #[cfg(feature = "gamepad")]
+    pub fn is_gamepad_button_pressed(
+        &self,
+        gamepad: GamepadId,
+        button: GamepadButton
+    ) -> bool
+    {
+        self.with_gamepad(gamepad, |gamepad| gamepad.is_pressed(button.into()))
+            .unwrap_or(false)
+    }
+
+    #[cfg(feature = "gamepad")]
+    pub fn get_gamepad_axis_value(&self, gamepad: GamepadId, axis: GamepadAxis) -> f32
+    {
+        self.with_gamepad(gamepad, |gamepad| gamepad.value(axis.into()))
+            .unwrap_or(0.0)
+    }
+
+    #[cfg(feature = "gamepad")]
+    fn with_gamepad<R, F>(&self, gamepad: GamepadId, callback: F) -> Option<R>
+    where
+        F: FnOnce(gilrs::Gamepad) -> R
+    {
+        let gamepads = self.gamepads.as_ref()?.borrow();
+
+        let result = gamepads
+            .gamepads()
+            .find(|(id, _)| usize::from(*id) == gamepad.value())
+            .map(|(_, gamepad)| callback(gamepad));
+
+        result
+    }
+
+    pub fn create_window(
+        &self,
+        title: &str,
+        options: WindowCreationOptions,
+        handler: Box<dyn WindowHandler<UserEventType>>
+    )
+    {
+        self.pending_windows.borrow_mut().push(PendingWindowGlutin {
+            title: title.to_string(),
+            options,
+            handler
+        });
+    }
+}
+
+pub(crate) struct PendingWindowGlutin<UserEventType: 'static>
+{
+    title: String,
+    options: WindowCreationOptions,
+    handler: Box<dyn WindowHandler<UserEventType>>
 }
 
+type PendingWindowsGlutin<UserEventType> =
+    Rc<RefCell<Vec<PendingWindowGlutin<UserEventType>>>>;
+
+/// The gamepad state shared by all windows, or `None` if gamepad support
+/// could not be initialized.
+#[cfg(feature = "gamepad")]
+type GamepadsGlutin = Option<Rc<RefCell<gilrs::Gilrs>>>;
+
+/// Gamepad input doesn't wake the event loop, so it is polled at this
+/// interval while no redraw is pending.
+#[cfg(feature = "gamepad")]
+const GAMEPAD_POLL_INTERVAL: Duration = Duration::from_millis(8);
+
 pub(crate) struct WindowGlutin<UserEventType: 'static>
 {
     event_loop: EventLoop<UserEventGlutin<UserEventType>>,
-    window: Rc<Window>,
-    context: Rc<PossiblyCurrentContext>,
-    surface: Rc<Surface<WindowSurface>>,
-    gl_backend: Rc<dyn GLBackend>
+    window: Window,
+    context: PossiblyCurrentContext,
+    surface: Surface<WindowSurface>,
+    gl_config: Config,
+    renderer: GLRenderer,
+    automatic_scaling: bool,
+    fixed_aspect_ratio: Option<UVec2>,
+    frame_interval: Option<Duration>
 }
 
 impl<UserEventType: 'static> WindowGlutin<UserEventType>
@@ -326,17 +619,8 @@ impl<UserEventType: 'static> WindowGlutin<UserEventType>
         let event_loop: EventLoop<UserEventGlutin<UserEventType>> =
             EventLoopBuilder::with_user_event().build()?;
 
-        let primary_monitor = event_loop
-            .primary_monitor()
-            .or_else(|| {
-                log::error!(
-                    "Couldn't find primary monitor. Using first available monitor."
-                );
-                event_loop.available_monitors().next()
-            })
-            .ok_or_else(|| {
-                BacktraceError::new(WindowCreationError::PrimaryMonitorNotFound)
-            })?;
+        let primary_monitor = find_primary_monitor(&event_loop)?;
+        let monitor = find_monitor(&event_loop, &options, &primary_monitor);
 
         for (num, monitor) in event_loop.available_monitors().enumerate() {
             log::debug!(
@@ -354,92 +638,27 @@ impl<UserEventType: 'static> WindowGlutin<UserEventType>
             );
         }
 
-        let mut window_builder = WindowBuilder::new()
-            .with_title(title)
-            .with_resizable(options.resizable)
-            .with_window_level(
-                if options.always_on_top {
-                    WindowLevel::AlwaysOnTop
-                } else {
-                    WindowLevel::Normal
-                }
-            )
-            .with_maximized(options.maximized)
-            .with_visible(false)
-            .with_transparent(options.transparent)
-            .with_decorations(options.decorations);
-
-        match &options.mode {
-            WindowCreationMode::Windowed { size, .. } => {
-                window_builder = window_builder
-                    .with_inner_size(compute_window_size(&primary_monitor, size));
-            }
-
-            WindowCreationMode::FullscreenBorderless => {
-                window_builder = window_builder.with_fullscreen(Some(
-                    winit::window::Fullscreen::Borderless(Some(primary_monitor.clone()))
-                ));
-            }
-        }
+        let window_builder = create_window_builder(title, &options, &monitor);
 
-        let (context, window, surface) =
+        let (context, window, surface, gl_config) =
             create_best_context(&window_builder, &event_loop, &options).ok_or_else(
                 || BacktraceError::new(WindowCreationError::SuitableContextNotFound)
             )?;
 
-        if let WindowCreationMode::Windowed {
-            position: Some(position),
-            ..
-        } = &options.mode
-        {
-            position_window(&primary_monitor, &window, position);
-        }
-
-        // Show window after positioning to avoid the window jumping around
-        window.set_visible(true);
-
-        // Set the position again to work around an issue on Linux
-        if let WindowCreationMode::Windowed {
-            position: Some(position),
-            ..
-        } = &options.mode
-        {
-            position_window(&primary_monitor, &window, position);
-        }
-
-        let glow_context = unsafe {
-            glow::Context::from_loader_function(|ptr| {
-                context.display().get_proc_address(
-                    CString::new(ptr)
-                        .expect("Invalid GL function name string")
-                        .as_c_str()
-                ) as *const _
-            })
-        };
-
-        let gl_backend = Rc::new(GLBackendGlow::new(glow_context));
+        show_window(&monitor, &window, &options);
 
-        if let Some(error_name) = gl_backend.gl_get_error_name() {
-            log::warn!(
-                "Ignoring error in GL bindings during startup: {}",
-                error_name
-            );
-        }
-
-        let version = unsafe { gl_backend.gl_get_string(GL_VERSION) };
-
-        log::info!("Using OpenGL version: {}", version);
-
-        unsafe {
-            gl_backend.gl_enable_debug_message_callback();
-        };
+        let renderer = create_renderer(&context, &window, &options)?;
 
         Ok(WindowGlutin {
             event_loop,
-            window: Rc::new(window),
-            context: Rc::new(context),
-            surface: Rc::new(surface),
-            gl_backend
+            window,
+            context,
+            surface,
+            gl_config,
+            renderer,
+            automatic_scaling: options.automatic_scaling,
+            fixed_aspect_ratio: options.fixed_aspect_ratio,
+            frame_interval: options.frame_interval()
         })
     }
 
@@ -448,264 +667,906 @@ impl<UserEventType: 'static> WindowGlutin<UserEventType>
         UserEventSender::new(UserEventSenderGlutin::new(self.event_loop.create_proxy()))
     }
 
-    pub fn get_inner_size_pixels(&self) -> UVec2
-    {
-        self.window.inner_size().into()
-    }
-
-    fn loop_handle_event<Handler>(
-        window: &Rc<Window>,
-        context: &Rc<PossiblyCurrentContext>,
-        surface: &Rc<Surface<WindowSurface>>,
-        handler: &mut DrawingWindowHandler<UserEventType, Handler>,
-        event: GlutinEvent<UserEventGlutin<UserEventType>>,
-        helper: &mut WindowHelper<UserEventType>
-    ) -> WindowEventLoopAction
+    pub fn run_loop<Handler>(self, handler: Handler) -> !
     where
         Handler: WindowHandler<UserEventType> + 'static
     {
-        match event {
-            GlutinEvent::LoopExiting => return WindowEventLoopAction::Exit,
+        let event_loop = self.event_loop;
 
-            GlutinEvent::UserEvent(event) => match event {
-                UserEventGlutin::MouseGrabStatusChanged(grabbed) => {
-                    handler.on_mouse_grab_status_changed(helper, grabbed)
-                }
-                UserEventGlutin::FullscreenStatusChanged(fullscreen) => {
-                    handler.on_fullscreen_status_changed(helper, fullscreen)
-                }
-                UserEventGlutin::UserEvent(event) => handler.on_user_event(helper, event)
-            },
+        let mut windows = WindowSetGlutin {
+            windows: Vec::new(),
+            current_window: Some(self.window.id()),
+            focused_window: None,
+            pending_windows: Rc::new(RefCell::new(Vec::new())),
+            event_proxy: event_loop.create_proxy(),
+            gl_config: self.gl_config,
+            #[cfg(feature = "gamepad")]
+            gamepads: create_gamepads()
+        };
+
+        let action = windows.add_window(
+            self.window,
+            self.context,
+            self.surface,
+            DrawingWindowHandler::new(
+                Box::new(handler),
+                self.renderer,
+                self.automatic_scaling,
+                self.fixed_aspect_ratio
+            ),
+            self.frame_interval
+        );
+
+        if action == WindowEventLoopAction::Exit {
+            log::info!("Start callback requested exit!");
+            windows.close_all();
+            std::process::exit(0);
+        }
 
-            GlutinEvent::WindowEvent { event, .. } => match event {
-                GlutinWindowEvent::ScaleFactorChanged { scale_factor, .. } => {
-                    log::info!("Scale factor changed: {:?}", scale_factor);
-                    handler.on_scale_factor_changed(helper, scale_factor)
+        let mut windows = Some(windows);
+
+        let result = event_loop.run(
+            move |event: GlutinEvent<UserEventGlutin<UserEventType>>, target| {
+                let active_windows = match windows.as_mut() {
+                    None => {
+                        target.exit();
+                        return;
+                    }
+                    Some(active_windows) => active_windows
+                };
+
+                let mut action = active_windows.handle_event(event);
+
+                if action == WindowEventLoopAction::Continue {
+                    action = active_windows.create_pending_windows(target);
                 }
 
-                GlutinWindowEvent::Resized(physical_size) => {
-                    log::info!("Resized: {:?}", physical_size);
-                    if let (Ok(w), Ok(h)) = (
-                        NonZeroU32::try_from(physical_size.width),
-                        NonZeroU32::try_from(physical_size.height)
-                    ) {
-                        surface.resize(context, w, h);
+                match action {
+                    WindowEventLoopAction::Continue => {
+                        target.set_control_flow(active_windows.get_control_flow())
+                    }
+                    WindowEventLoopAction::Exit => {
+                        active_windows.close_all();
+                        windows = None;
+                        target.exit();
                     }
-                    helper.inner().physical_size = physical_size.into();
-                    handler.on_resize(helper, physical_size.into())
                 }
+            }
+        );
+
+        if let Err(err) = result {
+            log::error!("Exited loop with error: {err:?}");
+            std::process::exit(1);
+        }
 
-                GlutinWindowEvent::CloseRequested => return WindowEventLoopAction::Exit,
+        std::process::exit(0);
+    }
+}
 
-                GlutinWindowEvent::CursorMoved { position, .. } => {
-                    let position = Vector2::new(position.x, position.y).into_f32();
+struct WindowStateGlutin<UserEventType: 'static>
+{
+    // Dropped in declaration order, so the renderer is destroyed before its
+    // context and window
+    handler: DrawingWindowHandler<UserEventType, Box<dyn WindowHandler<UserEventType>>>,
+    helper: WindowHelper<UserEventType>,
+    surface: Surface<WindowSurface>,
+    context: PossiblyCurrentContext,
+    window: Rc<Window>,
+    frame_interval: Option<Duration>,
+    next_frame_time: Instant,
+    size_state: WindowSizeState,
+    resize_pending: bool
+}
 
-                    if helper.inner().is_mouse_grabbed.get() {
-                        let central_position = helper.inner().physical_size / 2;
-                        window
-                            .set_cursor_position(PhysicalPosition::new(
-                                central_position.x as i32,
-                                central_position.y as i32
-                            ))
-                            .unwrap();
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WindowSizeState
+{
+    Normal,
+    Minimized,
+    Maximized
+}
 
-                        let position = position - central_position.into_f32();
+impl WindowSizeState
+{
+    fn of(window: &Window) -> Self
+    {
+        if window.is_minimized() == Some(true) {
+            WindowSizeState::Minimized
+        } else if window.is_maximized() {
+            WindowSizeState::Maximized
+        } else {
+            WindowSizeState::Normal
+        }
+    }
+}
 
-                        if position.magnitude_squared() > 0.0001 {
-                            handler.on_mouse_move(helper, position);
-                        }
-                    } else {
-                        handler.on_mouse_move(helper, position);
-                    };
+impl<UserEventType: 'static> WindowStateGlutin<UserEventType>
+{
+    fn handle_window_event(&mut self, event: GlutinWindowEvent)
+    {
+        // Minimizing and maximizing aren't reported as events, so the state is
+        // checked whenever it might have changed
+        let check_size_state = matches!(
+            event,
+            GlutinWindowEvent::Resized(_)
+                | GlutinWindowEvent::Moved(_)
+                | GlutinWindowEvent::Occluded(_)
+                | GlutinWindowEvent::Focused(_)
+        );
+
+        let handler = &mut self.handler;
+        let helper = &mut self.helper;
+
+        match event {
+            GlutinWindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                log::info!("Scale factor changed: {:?}", scale_factor);
+                handler.on_scale_factor_changed(helper, scale_factor)
+            }
+
+            GlutinWindowEvent::Resized(physical_size) => {
+                log::info!("Resized: {:?}", physical_size);
+                if let (Ok(w), Ok(h)) = (
+                    NonZeroU32::try_from(physical_size.width),
+                    NonZeroU32::try_from(physical_size.height)
+                ) {
+                    self.surface.resize(&self.context, w, h);
                 }
+                helper.inner().physical_size = physical_size.into();
+                handler.on_resize(helper, physical_size.into());
+
+                // While the window is being resized, the platform may not
+                // return to the event loop until the user releases the mouse
+                // (for example, the modal resize loop on Windows). Redraw
+                // requests from the platform are still delivered, so the new
+                // size is drawn when the next one arrives.
+                self.resize_pending = true;
+                self.window.request_redraw();
+            }
 
-                GlutinWindowEvent::MouseInput { state, button, .. } => match state {
-                    GlutinElementState::Pressed => {
-                        handler.on_mouse_button_down(helper, button.into())
-                    }
-                    GlutinElementState::Released => {
-                        handler.on_mouse_button_up(helper, button.into())
+            GlutinWindowEvent::CursorMoved { position, .. } => {
+                let position = Vector2::new(position.x, position.y).into_f32();
+
+                if helper.inner().is_mouse_grabbed.get() {
+                    let central_position = helper.inner().physical_size / 2;
+                    self.window
+                        .set_cursor_position(PhysicalPosition::new(
+                            central_position.x as i32,
+                            central_position.y as i32
+                        ))
+                        .unwrap();
+
+                    let position = position - central_position.into_f32();
+
+                    if position.magnitude_squared() > 0.0001 {
+                        handler.on_mouse_move(helper, position, true);
                     }
-                },
+                } else {
+                    handler.on_mouse_move(helper, position, false);
+                };
+            }
+
+            GlutinWindowEvent::MouseInput { state, button, .. } => match state {
+                GlutinElementState::Pressed => {
+                    handler.on_mouse_button_down(helper, button.into())
+                }
+                GlutinElementState::Released => {
+                    handler.on_mouse_button_up(helper, button.into())
+                }
+            },
 
-                GlutinWindowEvent::MouseWheel {
-                    delta,
-                    phase: TouchPhase::Moved,
-                    ..
-                } => {
-                    let distance = match delta {
-                        GlutinMouseScrollDelta::LineDelta(x, y) => {
-                            MouseScrollDistance::Lines {
-                                x: x as f64,
-                                y: y as f64,
-                                z: 0.0
-                            }
+            GlutinWindowEvent::MouseWheel {
+                delta,
+                phase: TouchPhase::Moved,
+                ..
+            } => {
+                let distance = match delta {
+                    GlutinMouseScrollDelta::LineDelta(x, y) => {
+                        MouseScrollDistance::Lines {
+                            x: x as f64,
+                            y: y as f64,
+                            z: 0.0
                         }
-                        GlutinMouseScrollDelta::PixelDelta(pos) => {
-                            MouseScrollDistance::Pixels {
-                                x: pos.x,
-                                y: pos.y,
-                                z: 0.0
-                            }
+                    }
+                    GlutinMouseScrollDelta::PixelDelta(pos) => {
+                        MouseScrollDistance::Pixels {
+                            x: pos.x,
+                            y: pos.y,
+                            z: 0.0
                         }
-                    };
+                    }
+                };
 
-                    handler.on_mouse_wheel_scroll(helper, distance);
-                }
+                handler.on_mouse_wheel_scroll(helper, distance);
+            }
+
+            GlutinWindowEvent::KeyboardInput { event, .. } => {
+                let virtual_key_code = VirtualKeyCode::try_from(&event).ok();
 
-                GlutinWindowEvent::KeyboardInput { event, .. } => {
-                    let virtual_key_code = VirtualKeyCode::try_from(&event).ok();
-
-                    match event.state {
-                        GlutinElementState::Pressed => {
-                            if let Some(text) = event.text {
-                                text.chars().for_each(|c| {
-                                    handler.on_keyboard_char(helper, c);
-                                });
-                            }
-
-                            if !event.repeat {
-                                handler.on_key_down(
-                                    helper,
-                                    virtual_key_code,
-                                    event.physical_key.to_scancode().unwrap_or(0)
-                                );
-                            }
+                match event.state {
+                    GlutinElementState::Pressed => {
+                        if let Some(text) = event.text {
+                            text.chars().for_each(|c| {
+                                handler.on_keyboard_char(helper, c);
+                            });
                         }
-                        GlutinElementState::Released => {
-                            handler.on_key_up(
+
+                        if !event.repeat {
+                            handler.on_key_down(
                                 helper,
                                 virtual_key_code,
                                 event.physical_key.to_scancode().unwrap_or(0)
                             );
                         }
                     }
+                    GlutinElementState::Released => {
+                        handler.on_key_up(
+                            helper,
+                            virtual_key_code,
+                            event.physical_key.to_scancode().unwrap_or(0)
+                        );
+                    }
                 }
+            }
 
-                GlutinWindowEvent::ModifiersChanged(state) => {
-                    handler.on_keyboard_modifiers_changed(helper, state.state().into())
-                }
+            GlutinWindowEvent::ModifiersChanged(state) => {
+                handler.on_keyboard_modifiers_changed(helper, state.state().into())
+            }
 
-                GlutinWindowEvent::RedrawRequested => {
-                    helper.inner().set_redraw_requested(true);
-                }
+            GlutinWindowEvent::Ime(Ime::Preedit(text, cursor_range)) => {
+                handler.on_ime_preedit(helper, &text, cursor_range)
+            }
 
-                _ => {}
-            },
+            GlutinWindowEvent::Ime(Ime::Commit(text)) => {
+                handler.on_ime_commit(helper, &text)
+            }
 
-            GlutinEvent::AboutToWait => {
-                if helper.inner().is_redraw_requested() {
-                    helper.inner().set_redraw_requested(false);
-                    handler.on_draw(helper);
-                    surface.swap_buffers(context).unwrap();
-                }
+            GlutinWindowEvent::Focused(focused) => {
+                handler.on_window_focus_changed(helper, focused)
             }
 
+            GlutinWindowEvent::RedrawRequested => match self.resize_pending {
+                true => self.draw(),
+                false => helper.inner().set_redraw_requested(true)
+            },
+
             _ => {}
         }
 
-        helper.inner().get_event_loop_action()
+        if check_size_state {
+            self.update_size_state();
+        }
     }
 
-    pub fn run_loop<Handler>(self, handler: Handler, renderer: GLRenderer) -> !
-    where
-        Handler: WindowHandler<UserEventType> + 'static
+    fn update_size_state(&mut self)
     {
-        let window = self.window;
-        let context = self.context;
-        let surface = self.surface;
-        let event_loop = self.event_loop;
-
-        let initial_viewport_size_pixels = window.inner_size().into();
+        let size_state = WindowSizeState::of(&self.window);
 
-        let mut handler = DrawingWindowHandler::new(handler, renderer);
-
-        let mut helper = WindowHelper::new(WindowHelperGlutin::new(
-            &window,
-            event_loop.create_proxy(),
-            initial_viewport_size_pixels
-        ));
+        if size_state == self.size_state {
+            return;
+        }
 
-        handler.on_start(
-            &mut helper,
-            WindowStartupInfo::new(initial_viewport_size_pixels, window.scale_factor())
-        );
+        log::info!("Window state changed: {:?}", size_state);
+        self.size_state = size_state;
 
-        match helper.inner().get_event_loop_action() {
-            WindowEventLoopAction::Continue => {
-                // Do nothing
+        match size_state {
+            WindowSizeState::Normal => self.handler.on_window_restored(&mut self.helper),
+            WindowSizeState::Minimized => {
+                self.handler.on_window_minimized(&mut self.helper)
             }
-            WindowEventLoopAction::Exit => {
-                log::info!("Start callback requested exit!");
-                drop(handler);
-                std::process::exit(0);
+            WindowSizeState::Maximized => {
+                self.handler.on_window_maximized(&mut self.helper)
             }
         }
+    }
 
-        let mut handler = Some(handler);
+    /// Invokes the handler's close callback, returning false if the close was
+    /// cancelled.
+    fn handle_close_requested(&mut self) -> bool
+    {
+        self.helper.inner().take_close_cancelled();
+        self.handler.on_window_close_requested(&mut self.helper);
+        !self.helper.inner().take_close_cancelled()
+    }
 
-        let result = event_loop.run(
-            move |event: GlutinEvent<UserEventGlutin<UserEventType>>, target| {
-                if handler.is_none() {
-                    target.exit();
-                } else {
-                    let action = WindowGlutin::loop_handle_event(
-                        &window,
-                        &context,
-                        &surface,
-                        handler.as_mut().unwrap(),
-                        event,
-                        &mut helper
-                    );
-
-                    match action {
-                        WindowEventLoopAction::Continue => {
-                            if helper.inner().is_redraw_requested() {
-                                target.set_control_flow(ControlFlow::Poll)
-                            } else {
-                                target.set_control_flow(ControlFlow::Wait)
-                            }
-                        }
-                        WindowEventLoopAction::Exit => {
-                            handler = None;
-                            target.exit();
+    fn draw(&mut self)
+    {
+        self.resize_pending = false;
+        self.helper.inner().set_redraw_requested(false);
+        self.helper
+            .inner()
+            .clear_scheduled_redraw_if_due(Instant::now());
+        self.handler.on_draw(&mut self.helper);
+        self.surface.swap_buffers(&self.context).unwrap();
+
+        if let Some(frame_interval) = self.frame_interval {
+            // Keep to the schedule, unless drawing has fallen behind it
+            self.next_frame_time =
+                (self.next_frame_time + frame_interval).max(Instant::now());
+        }
+    }
+
+    /// Returns the time at which the next frame should be drawn, if a redraw
+    /// has been requested or scheduled.
+    fn get_pending_frame_time(&mut self) -> Option<Instant>
+    {
+        let helper = self.helper.inner();
+
+        match helper.is_redraw_requested() {
+            true => Some(self.next_frame_time),
+            false => helper
+                .get_scheduled_redraw_time()
+                .map(|time| time.max(self.next_frame_time))
+        }
+    }
+}
+
+/// The windows belonging to the event loop. The first window is the one
+/// created using `Window::new_*()`, and closing it ends the event loop.
+struct WindowSetGlutin<UserEventType: 'static>
+{
+    windows: Vec<WindowStateGlutin<UserEventType>>,
+    current_window: Option<WindowId>,
+    focused_window: Option<WindowId>,
+    pending_windows: PendingWindowsGlutin<UserEventType>,
+    event_proxy: EventLoopProxy<UserEventGlutin<UserEventType>>,
+    gl_config: Config,
+    #[cfg(feature = "gamepad")]
+    gamepads: GamepadsGlutin
+}
+
+impl<UserEventType: 'static> WindowSetGlutin<UserEventType>
+{
+    /// Adds the window to the set and invokes its `on_start` callback. The
+    /// window's context must be current.
+    fn add_window(
+        &mut self,
+        window: Window,
+        context: PossiblyCurrentContext,
+        surface: Surface<WindowSurface>,
+        mut handler: DrawingWindowHandler<
+            UserEventType,
+            Box<dyn WindowHandler<UserEventType>>
+        >,
+        frame_interval: Option<Duration>
+    ) -> WindowEventLoopAction
+    {
+        let window = Rc::new(window);
+        let initial_viewport_size_pixels = window.inner_size().into();
+
+        let mut helper = WindowHelper::new(WindowHelperGlutin::new(
+            &window,
+            self.event_proxy.clone(),
+            initial_viewport_size_pixels,
+            &self.pending_windows,
+            #[cfg(feature = "gamepad")]
+            &self.gamepads
+        ));
+
+        handler.on_start(
+            &mut helper,
+            WindowStartupInfo::new(initial_viewport_size_pixels, window.scale_factor())
+        );
+
+        self.current_window = Some(window.id());
+
+        let action = helper.inner().get_event_loop_action();
+        let size_state = WindowSizeState::of(&window);
+
+        self.windows.push(WindowStateGlutin {
+            handler,
+            helper,
+            surface,
+            context,
+            window,
+            frame_interval,
+            next_frame_time: Instant::now(),
+            size_state,
+            resize_pending: false
+        });
+
+        action
+    }
+
+    fn create_pending_windows(
+        &mut self,
+        target: &EventLoopWindowTarget<UserEventGlutin<UserEventType>>
+    ) -> WindowEventLoopAction
+    {
+        loop {
+            let pending_window = {
+                let mut pending_windows = self.pending_windows.borrow_mut();
+
+                if pending_windows.is_empty() {
+                    return WindowEventLoopAction::Continue;
+                }
+
+                pending_windows.remove(0)
+            };
+
+            let result = create_additional_window(
+                target,
+                &self.gl_config,
+                &self.windows[0].context,
+                &pending_window.title,
+                &pending_window.options
+            );
+
+            let (window, context, surface, renderer) = match result {
+                Ok(result) => result,
+                Err(err) => {
+                    log::error!("Failed to create window: {err:?}");
+                    self.current_window = None;
+                    continue;
+                }
+            };
+
+            let handler = DrawingWindowHandler::new(
+                pending_window.handler,
+                renderer,
+                pending_window.options.automatic_scaling,
+                pending_window.options.fixed_aspect_ratio
+            );
+
+            let frame_interval = pending_window.options.frame_interval();
+
+            if self.add_window(window, context, surface, handler, frame_interval)
+                == WindowEventLoopAction::Exit
+            {
+                return WindowEventLoopAction::Exit;
+            }
+        }
+    }
+
+    fn index_of(&self, window_id: WindowId) -> Option<usize>
+    {
+        self.windows
+            .iter()
+            .position(|window| window.window.id() == window_id)
+    }
+
+    /// Makes the context of the specified window current, so that its
+    /// handler may use the renderer.
+    fn activate(&mut self, index: usize) -> &mut WindowStateGlutin<UserEventType>
+    {
+        let window = &mut self.windows[index];
+        let window_id = window.window.id();
+
+        if self.current_window != Some(window_id) {
+            if let Err(err) = window.context.make_current(&window.surface) {
+                log::error!("Failed to make context current: {err:?}");
+            }
+            self.current_window = Some(window_id);
+        }
+
+        window
+    }
+
+    fn handle_event(
+        &mut self,
+        event: GlutinEvent<UserEventGlutin<UserEventType>>
+    ) -> WindowEventLoopAction
+    {
+        match event {
+            GlutinEvent::LoopExiting => return WindowEventLoopAction::Exit,
+
+            GlutinEvent::UserEvent(event) => match event {
+                UserEventGlutin::MouseGrabStatusChanged(window_id, grabbed) => {
+                    if let Some(index) = self.index_of(window_id) {
+                        let window = self.activate(index);
+                        window
+                            .handler
+                            .on_mouse_grab_status_changed(&mut window.helper, grabbed)
+                    }
+                }
+                UserEventGlutin::FullscreenStatusChanged(window_id, fullscreen) => {
+                    if let Some(index) = self.index_of(window_id) {
+                        let window = self.activate(index);
+                        window
+                            .handler
+                            .on_fullscreen_status_changed(&mut window.helper, fullscreen)
+                    }
+                }
+                UserEventGlutin::UserEvent(event) => {
+                    let window = self.activate(0);
+                    window.handler.on_user_event(&mut window.helper, event)
+                }
+            },
+
+            GlutinEvent::WindowEvent { window_id, event } => {
+                if let GlutinWindowEvent::Focused(focused) = event {
+                    if focused {
+                        self.focused_window = Some(window_id);
+                    } else if self.focused_window == Some(window_id) {
+                        self.focused_window = None;
+                    }
+                }
+
+                if let Some(index) = self.index_of(window_id) {
+                    if let GlutinWindowEvent::CloseRequested = event {
+                        if !self.activate(index).handle_close_requested() {
+                            return self.get_event_loop_action();
+                        }
+                        if index == 0 {
+                            return WindowEventLoopAction::Exit;
                         }
+                        self.close_window(index);
+                    } else {
+                        self.activate(index).handle_window_event(event);
                     }
                 }
             }
-        );
 
-        if let Err(err) = result {
-            log::error!("Exited loop with error: {err:?}");
-            std::process::exit(1);
+            GlutinEvent::DeviceEvent {
+                event: DeviceEvent::MouseMotion { delta },
+                ..
+            } => {
+                // Device events aren't associated with a window, so they are
+                // delivered to the focused window
+                if let Some(index) = self.focused_window.and_then(|id| self.index_of(id))
+                {
+                    let window = self.activate(index);
+                    window.handler.on_mouse_motion_relative(
+                        &mut window.helper,
+                        Vector2::new(delta.0, delta.1).into_f32()
+                    )
+                }
+            }
+
+            GlutinEvent::AboutToWait => {
+                #[cfg(feature = "gamepad")]
+                self.handle_gamepad_events();
+
+                let now = Instant::now();
+
+                for index in 0..self.windows.len() {
+                    if let Some(frame_time) = self.windows[index].get_pending_frame_time()
+                    {
+                        if frame_time <= now {
+                            self.activate(index).draw();
+                        }
+                    }
+                }
+            }
+
+            _ => {}
         }
 
-        std::process::exit(0);
+        self.get_event_loop_action()
     }
 
-    #[inline]
-    #[must_use]
-    pub fn gl_backend(&self) -> &Rc<dyn GLBackend>
+    fn get_event_loop_action(&mut self) -> WindowEventLoopAction
+    {
+        let terminate_requested = self.windows.iter_mut().any(|window| {
+            window.helper.inner().get_event_loop_action() == WindowEventLoopAction::Exit
+        });
+
+        match terminate_requested {
+            true => WindowEventLoopAction::Exit,
+            false => WindowEventLoopAction::Continue
+        }
+    }
+
+    fn get_control_flow(&mut self) -> ControlFlow
     {
-        &self.gl_backend
+        let next_frame_time = self
+            .windows
+            .iter_mut()
+            .filter_map(|window| window.get_pending_frame_time())
+            .min();
+
+        if let Some(next_frame_time) = next_frame_time {
+            return match next_frame_time > Instant::now() {
+                true => ControlFlow::WaitUntil(next_frame_time),
+                false => ControlFlow::Poll
+            };
+        }
+
+        #[cfg(feature = "gamepad")]
+        if self.gamepads.is_some() {
+            return ControlFlow::wait_duration(GAMEPAD_POLL_INTERVAL);
+        }
+
+        ControlFlow::Wait
     }
+
+    /// Delivers pending gamepad events to the focused window, or to the first
+    /// window if no window has focus.
+    #[cfg(feature = "gamepad")]
+    fn handle_gamepad_events(&mut self)
+    {
+        let gamepads = match &self.gamepads {
+            None => return,
+            Some(gamepads) => Rc::clone(gamepads)
+        };
+
+        loop {
+            // The borrow must end before invoking the handler, as the handler
+            // may query the gamepad state
+            let event = match gamepads.borrow_mut().next_event() {
+                None => return,
+                Some(event) => event
+            };
+
+            let gamepad = GamepadId::new(event.id.into());
+
+            let index = self
+                .focused_window
+                .and_then(|id| self.index_of(id))
+                .unwrap_or(0);
+
+            let window = self.activate(index);
+            let handler = &mut window.handler;
+            let helper = &mut window.helper;
+
+            match event.event {
+                gilrs::EventType::Connected => {
+                    handler.on_gamepad_connected(helper, gamepad)
+                }
+                gilrs::EventType::Disconnected => {
+                    handler.on_gamepad_disconnected(helper, gamepad)
+                }
+                gilrs::EventType::ButtonPressed(button, _) => {
+                    if let Ok(button) = button.try_into() {
+                        handler.on_gamepad_button_down(helper, gamepad, button)
+                    }
+                }
+                gilrs::EventType::ButtonReleased(button, _) => {
+                    if let Ok(button) = button.try_into() {
+                        handler.on_gamepad_button_up(helper, gamepad, button)
+                    }
+                }
+                gilrs::EventType::AxisChanged(axis, value, _) => {
+                    if let Ok(axis) = axis.try_into() {
+                        handler.on_gamepad_axis(helper, gamepad, axis, value)
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn close_window(&mut self, index: usize)
+    {
+        // The handler's GL resources are deleted using its own context
+        self.activate(index);
+        drop(self.windows.remove(index));
+        self.current_window = None;
+    }
+
+    fn close_all(&mut self)
+    {
+        while !self.windows.is_empty() {
+            self.close_window(self.windows.len() - 1);
+        }
+    }
+}
+
+#[cfg(feature = "gamepad")]
+fn create_gamepads() -> GamepadsGlutin
+{
+    match gilrs::Gilrs::new() {
+        Ok(gamepads) => Some(Rc::new(RefCell::new(gamepads))),
+        Err(err) => {
+            log::error!("Failed to initialize gamepad support: {err:?}");
+            None
+        }
+    }
+}
+
+/// Picks the first config, preferring one which supports transparency if a
+/// transparent window was requested.
+fn pick_gl_config(
+    configs: Box<dyn Iterator<Item = Config> + '_>,
+    transparent: bool
+) -> Config
+{
+    let configs: Vec<Config> = configs.collect();
+
+    let index = if transparent {
+        configs
+            .iter()
+            .position(|config| config.supports_transparency() == Some(true))
+            .unwrap_or_else(|| {
+                log::warn!("No GL config supports transparency");
+                0
+            })
+    } else {
+        0
+    };
+
+    configs.into_iter().nth(index).unwrap()
+}
+
+fn find_primary_monitor<T>(
+    event_loop: &EventLoopWindowTarget<T>
+) -> Result<MonitorHandle, BacktraceError<WindowCreationError>>
+{
+    event_loop
+        .primary_monitor()
+        .or_else(|| {
+            log::error!("Couldn't find primary monitor. Using first available monitor.");
+            event_loop.available_monitors().next()
+        })
+        .ok_or_else(|| BacktraceError::new(WindowCreationError::PrimaryMonitorNotFound))
+}
+
+/// Returns the monitor selected using
+/// [WindowCreationOptions::with_monitor()], or the primary monitor if none was
+/// selected.
+fn find_monitor<T>(
+    event_loop: &EventLoopWindowTarget<T>,
+    options: &WindowCreationOptions,
+    primary_monitor: &MonitorHandle
+) -> MonitorHandle
+{
+    match options.monitor {
+        None => primary_monitor.clone(),
+        Some(index) => event_loop
+            .available_monitors()
+            .nth(index)
+            .unwrap_or_else(|| {
+                log::error!("Monitor {index} not found. Using primary monitor.");
+                primary_monitor.clone()
+            })
+    }
+}
+
+fn create_window_builder(
+    title: &str,
+    options: &WindowCreationOptions,
+    monitor: &MonitorHandle
+) -> WindowBuilder
+{
+    let mut window_builder = WindowBuilder::new()
+        .with_title(title)
+        .with_resizable(options.resizable)
+        .with_window_level(
+            if options.always_on_top {
+                WindowLevel::AlwaysOnTop
+            } else {
+                WindowLevel::Normal
+            }
+        )
+        .with_maximized(options.maximized)
+        .with_visible(false)
+        .with_transparent(options.transparent)
+        .with_decorations(options.decorations);
+
+    if let Some((data, size)) = &options.icon {
+        match Icon::from_rgba(data.clone(), size.x, size.y) {
+            Ok(icon) => window_builder = window_builder.with_window_icon(Some(icon)),
+            Err(err) => log::error!("Ignoring invalid window icon: {err:?}")
+        }
+    }
+
+    match &options.mode {
+        WindowCreationMode::Windowed { size, .. } => {
+            window_builder =
+                window_builder.with_inner_size(compute_window_size(monitor, size));
+        }
+
+        WindowCreationMode::FullscreenBorderless => {
+            window_builder = window_builder.with_fullscreen(Some(
+                winit::window::Fullscreen::Borderless(Some(monitor.clone()))
+            ));
+        }
+
+        WindowCreationMode::FullscreenExclusive(video_mode) => {
+            window_builder = window_builder
+                .with_fullscreen(Some(exclusive_fullscreen(monitor, video_mode)));
+        }
+    }
+
+    window_builder
 }
 
-fn gl_config_picker(mut configs: Box<dyn Iterator<Item = Config> + '_>) -> Config
+fn show_window(
+    monitor: &MonitorHandle,
+    window: &GlutinWindow,
+    options: &WindowCreationOptions
+)
 {
-    configs.next().unwrap()
+    if let WindowCreationMode::Windowed {
+        position: Some(position),
+        ..
+    } = &options.mode
+    {
+        position_window(monitor, window, position);
+    }
+
+    // Show window after positioning to avoid the window jumping around
+    window.set_visible(true);
+
+    // Set the position again to work around an issue on Linux
+    if let WindowCreationMode::Windowed {
+        position: Some(position),
+        ..
+    } = &options.mode
+    {
+        position_window(monitor, window, position);
+    }
+}
+
+/// Creates a renderer for the window. The window's context must be current.
+fn create_renderer(
+    context: &PossiblyCurrentContext,
+    window: &GlutinWindow,
+    options: &WindowCreationOptions
+) -> Result<GLRenderer, BacktraceError<WindowCreationError>>
+{
+    let glow_context = unsafe {
+        glow::Context::from_loader_function(|ptr| {
+            context.display().get_proc_address(
+                CString::new(ptr)
+                    .expect("Invalid GL function name string")
+                    .as_c_str()
+            ) as *const _
+        })
+    };
+
+    let gl_backend = Rc::new(GLBackendGlow::new(glow_context));
+
+    if let Some(error_name) = gl_backend.gl_get_error_name() {
+        log::warn!(
+            "Ignoring error in GL bindings during startup: {}",
+            error_name
+        );
+    }
+
+    let version = unsafe { gl_backend.gl_get_string(GL_VERSION) };
+
+    log::info!("Using OpenGL version: {}", version);
+
+    unsafe {
+        gl_backend.gl_enable_debug_message_callback();
+    };
+
+    GLRenderer::new_with_gl_backend(
+        UVec2::from(window.inner_size()),
+        gl_backend.clone(),
+        GLVersion::detect(gl_backend.as_ref()),
+        GLRendererOptions::new().with_antialiasing(options.antialiasing)
+    )
+    .map_err(|err| {
+        BacktraceError::new_with_cause(WindowCreationError::RendererCreationFailed, err)
+    })
+}
+
+fn set_vsync(
+    context: &PossiblyCurrentContext,
+    surface: &Surface<WindowSurface>,
+    options: &WindowCreationOptions
+)
+{
+    if options.vsync {
+        if let Err(err) = surface
+            .set_swap_interval(context, SwapInterval::Wait(NonZeroU32::new(1).unwrap()))
+        {
+            log::error!("Error setting vsync, continuing anyway: {err:?}");
+        }
+    }
 }
 
 fn create_best_context<UserEventType>(
     window_builder: &WindowBuilder,
     event_loop: &EventLoop<UserEventType>,
     options: &WindowCreationOptions
-) -> Option<(PossiblyCurrentContext, Window, Surface<WindowSurface>)>
+) -> Option<(
+    PossiblyCurrentContext,
+    Window,
+    Surface<WindowSurface>,
+    Config
+)>
 {
     for multisampling in &[options.multisampling, 16, 8, 4, 2, 1, 0] {
         log::info!("Trying multisampling={}...", multisampling);
 
-        let mut template = ConfigTemplateBuilder::new();
+        // CGL only makes the surface transparent if requested in the template.
+        // Other platforms may not return any configs if transparency is
+        // required, so the picker chooses a suitable config instead.
+        let mut template = ConfigTemplateBuilder::new()
+            .with_stencil_size(8)
+            .with_transparency(options.transparent && cfg!(target_os = "macos"));
 
         if *multisampling > 1 {
             template = template.with_multisampling(
@@ -717,7 +1578,9 @@ fn create_best_context<UserEventType>(
 
         let result = DisplayBuilder::new()
             .with_window_builder(Some(window_builder.clone()))
-            .build(event_loop, template, gl_config_picker);
+            .build(event_loop, template, |configs| {
+                pick_gl_config(configs, options.transparent)
+            });
 
         let (window, gl_config) = match result {
             Ok((Some(window), config)) => {
@@ -736,18 +1599,17 @@ fn create_best_context<UserEventType>(
 
         let gl_display = gl_config.display();
 
-        let context_attributes = ContextAttributesBuilder::new()
-            .with_context_api(ContextApi::OpenGl(Some(Version::new(2, 0))))
-            .build(Some(window.raw_window_handle()));
+        let context = create_context_with_fallback(
+            &gl_display,
+            &gl_config,
+            Some(window.raw_window_handle()),
+            None
+        );
 
-        let context =
-            match unsafe { gl_display.create_context(&gl_config, &context_attributes) } {
-                Ok(context) => context,
-                Err(err) => {
-                    log::info!("Failed to create context with error: {err:?}");
-                    continue;
-                }
-            };
+        let context = match context {
+            Some(context) => context,
+            None => continue
+        };
 
         let window = match glutin_winit::finalize_window(
             event_loop,
@@ -783,22 +1645,80 @@ fn create_best_context<UserEventType>(
             }
         };
 
-        if options.vsync {
-            if let Err(err) = surface.set_swap_interval(
-                &context,
-                SwapInterval::Wait(NonZeroU32::new(1).unwrap())
-            ) {
-                log::error!("Error setting vsync, continuing anyway: {err:?}");
-            }
-        }
+        set_vsync(&context, &surface, options);
 
-        return Some((context, window, surface));
+        return Some((context, window, surface, gl_config));
     }
 
     log::error!("Failed to create any context.");
     None
 }
 
+/// Creates a window using the existing GL config, optionally sharing resources
+/// with the specified context. The new window's context is made current.
+fn create_additional_window<UserEventType>(
+    target: &EventLoopWindowTarget<UserEventGlutin<UserEventType>>,
+    gl_config: &Config,
+    shared_context: &PossiblyCurrentContext,
+    title: &str,
+    options: &WindowCreationOptions
+) -> Result<
+    (
+        Window,
+        PossiblyCurrentContext,
+        Surface<WindowSurface>,
+        GLRenderer
+    ),
+    BacktraceError<WindowCreationError>
+>
+{
+    let primary_monitor = find_primary_monitor(target)?;
+    let monitor = find_monitor(target, options, &primary_monitor);
+    let window_builder = create_window_builder(title, options, &monitor);
+
+    let window = glutin_winit::finalize_window(target, window_builder, gl_config)
+        .map_err(|err| {
+            BacktraceError::new_with_cause(
+                WindowCreationError::SuitableContextNotFound,
+                err
+            )
+        })?;
+
+    let context = create_context_with_fallback(
+        &gl_config.display(),
+        gl_config,
+        Some(window.raw_window_handle()),
+        if options.shared_resources {
+            Some(shared_context)
+        } else {
+            None
+        }
+    )
+    .ok_or_else(|| BacktraceError::new(WindowCreationError::SuitableContextNotFound))?;
+
+    let attrs = window.build_surface_attributes(SurfaceAttributesBuilder::default());
+
+    let surface = unsafe { gl_config.display().create_window_surface(gl_config, &attrs) }
+        .map_err(|err| {
+            BacktraceError::new_with_cause(
+                WindowCreationError::SuitableContextNotFound,
+                err
+            )
+        })?;
+
+    let context = context.make_current(&surface).map_err(|err| {
+        BacktraceError::new_with_cause(WindowCreationError::MakeContextCurrentFailed, err)
+    })?;
+
+    set_vsync(&context, &surface, options);
+
+    show_window(&monitor, &window, options);
+
+    let renderer = create_renderer(&context, &window, options)?;
+
+    Ok((window, context, surface, renderer))
+}
+
 fn position_window(
     monitor: &MonitorHandle,
     window: &GlutinWindow,
@@ -834,6 +1754,41 @@ fn position_window(
     }
 }
 
+/// Finds the monitor's video mode with the requested resolution and the
+/// closest refresh rate, falling back to borderless fullscreen if the
+/// resolution is not supported.
+fn exclusive_fullscreen(
+    monitor: &MonitorHandle,
+    video_mode: &VideoMode
+) -> winit::window::Fullscreen
+{
+    let best_video_mode = monitor
+        .video_modes()
+        .filter(|candidate| UVec2::from(candidate.size()) == video_mode.size_pixels())
+        .min_by_key(|candidate| {
+            (
+                (candidate.refresh_rate_millihertz() as i64
+                    - video_mode.refresh_rate_millihertz() as i64)
+                    .abs(),
+                std::cmp::Reverse(candidate.bit_depth())
+            )
+        });
+
+    match best_video_mode {
+        Some(best_video_mode) => {
+            log::info!("Using video mode: {}", best_video_mode);
+            winit::window::Fullscreen::Exclusive(best_video_mode)
+        }
+        None => {
+            log::warn!(
+                "Video mode {:?} not supported, using borderless fullscreen",
+                video_mode
+            );
+            winit::window::Fullscreen::Borderless(Some(monitor.clone()))
+        }
+    }
+}
+
 fn compute_window_size(monitor: &MonitorHandle, size: &WindowSize) -> PhysicalSize<u32>
 {
     let monitor_size = monitor.size();
@@ -871,6 +1826,38 @@ fn compute_window_size(monitor: &MonitorHandle, size: &WindowSize) -> PhysicalSi
     }
 }
 
+impl From<CursorIcon> for winit::window::CursorIcon
+{
+    fn from(icon: CursorIcon) -> Self
+    {
+        match icon {
+            CursorIcon::Default => winit::window::CursorIcon::Default,
+            CursorIcon::Pointer => winit::window::CursorIcon::Pointer,
+            CursorIcon::Progress => winit::window::CursorIcon::Progress,
+            CursorIcon::Wait => winit::window::CursorIcon::Wait,
+            CursorIcon::Cell => winit::window::CursorIcon::Cell,
+            CursorIcon::Crosshair => winit::window::CursorIcon::Crosshair,
+            CursorIcon::Text => winit::window::CursorIcon::Text,
+            CursorIcon::VerticalText => winit::window::CursorIcon::VerticalText,
+            CursorIcon::Alias => winit::window::CursorIcon::Alias,
+            CursorIcon::Copy => winit::window::CursorIcon::Copy,
+            CursorIcon::Move => winit::window::CursorIcon::Move,
+            CursorIcon::NoDrop => winit::window::CursorIcon::NoDrop,
+            CursorIcon::NotAllowed => winit::window::CursorIcon::NotAllowed,
+            CursorIcon::Grab => winit::window::CursorIcon::Grab,
+            CursorIcon::Grabbing => winit::window::CursorIcon::Grabbing,
+            CursorIcon::ColResize => winit::window::CursorIcon::ColResize,
+            CursorIcon::RowResize => winit::window::CursorIcon::RowResize,
+            CursorIcon::EwResize => winit::window::CursorIcon::EwResize,
+            CursorIcon::NsResize => winit::window::CursorIcon::NsResize,
+            CursorIcon::NeswResize => winit::window::CursorIcon::NeswResize,
+            CursorIcon::NwseResize => winit::window::CursorIcon::NwseResize,
+            CursorIcon::ZoomIn => winit::window::CursorIcon::ZoomIn,
+            CursorIcon::ZoomOut => winit::window::CursorIcon::ZoomOut
+        }
+    }
+}
+
 impl From<winit::event::MouseButton> for MouseButton
 {
     fn from(button: winit::event::MouseButton) -> Self
@@ -886,6 +1873,106 @@ impl From<winit::event::MouseButton> for MouseButton
     }
 }
 
+#[cfg(feature = "gamepad")]
+impl TryFrom<gilrs::Button> for GamepadButton
+{
+    type Error = ();
+
+    fn try_from(value: gilrs::Button) -> Result<Self, Self::Error>
+    {
+        match value {
+            gilrs::Button::South => Ok(GamepadButton::South),
+            gilrs::Button::East => Ok(GamepadButton::East),
+            gilrs::Button::North => Ok(GamepadButton::North),
+            gilrs::Button::West => Ok(GamepadButton::West),
+            gilrs::Button::C => Ok(GamepadButton::C),
+            gilrs::Button::Z => Ok(GamepadButton::Z),
+            gilrs::Button::LeftTrigger => Ok(GamepadButton::LeftTrigger),
+            gilrs::Button::LeftTrigger2 => Ok(GamepadButton::LeftTrigger2),
+            gilrs::Button::RightTrigger => Ok(GamepadButton::RightTrigger),
+            gilrs::Button::RightTrigger2 => Ok(GamepadButton::RightTrigger2),
+            gilrs::Button::Select => Ok(GamepadButton::Select),
+            gilrs::Button::Start => Ok(GamepadButton::Start),
+            gilrs::Button::Mode => Ok(GamepadButton::Mode),
+            gilrs::Button::LeftThumb => Ok(GamepadButton::LeftThumb),
+            gilrs::Button::RightThumb => Ok(GamepadButton::RightThumb),
+            gilrs::Button::DPadUp => Ok(GamepadButton::DPadUp),
+            gilrs::Button::DPadDown => Ok(GamepadButton::DPadDown),
+            gilrs::Button::DPadLeft => Ok(GamepadButton::DPadLeft),
+            gilrs::Button::DPadRight => Ok(GamepadButton::DPadRight),
+            gilrs::Button::Unknown => Err(())
+        }
+    }
+}
+
+#[cfg(feature = "gamepad")]
+impl From<GamepadButton> for gilrs::Button
+{
+    fn from(value: GamepadButton) -> Self
+    {
+        match value {
+            GamepadButton::South => gilrs::Button::South,
+            GamepadButton::East => gilrs::Button::East,
+            GamepadButton::North => gilrs::Button::North,
+            GamepadButton::West => gilrs::Button::West,
+            GamepadButton::C => gilrs::Button::C,
+            GamepadButton::Z => gilrs::Button::Z,
+            GamepadButton::LeftTrigger => gilrs::Button::LeftTrigger,
+            GamepadButton::LeftTrigger2 => gilrs::Button::LeftTrigger2,
+            GamepadButton::RightTrigger => gilrs::Button::RightTrigger,
+            GamepadButton::RightTrigger2 => gilrs::Button::RightTrigger2,
+            GamepadButton::Select => gilrs::Button::Select,
+            GamepadButton::Start => gilrs::Button::Start,
+            GamepadButton::Mode => gilrs::Button::Mode,
+            GamepadButton::LeftThumb => gilrs::Button::LeftThumb,
+            GamepadButton::RightThumb => gilrs::Button::RightThumb,
+            GamepadButton::DPadUp => gilrs::Button::DPadUp,
+            GamepadButton::DPadDown => gilrs::Button::DPadDown,
+            GamepadButton::DPadLeft => gilrs::Button::DPadLeft,
+            GamepadButton::DPadRight => gilrs::Button::DPadRight
+        }
+    }
+}
+
+#[cfg(feature = "gamepad")]
+impl TryFrom<gilrs::Axis> for GamepadAxis
+{
+    type Error = ();
+
+    fn try_from(value: gilrs::Axis) -> Result<Self, Self::Error>
+    {
+        match value {
+            gilrs::Axis::LeftStickX => Ok(GamepadAxis::LeftStickX),
+            gilrs::Axis::LeftStickY => Ok(GamepadAxis::LeftStickY),
+            gilrs::Axis::LeftZ => Ok(GamepadAxis::LeftZ),
+            gilrs::Axis::RightStickX => Ok(GamepadAxis::RightStickX),
+            gilrs::Axis::RightStickY => Ok(GamepadAxis::RightStickY),
+            gilrs::Axis::RightZ => Ok(GamepadAxis::RightZ),
+            gilrs::Axis::DPadX => Ok(GamepadAxis::DPadX),
+            gilrs::Axis::DPadY => Ok(GamepadAxis::DPadY),
+            gilrs::Axis::Unknown => Err(())
+        }
+    }
+}
+
+#[cfg(feature = "gamepad")]
+impl From<GamepadAxis> for gilrs::Axis
+{
+    fn from(value: GamepadAxis) -> Self
+    {
+        match value {
+            GamepadAxis::LeftStickX => gilrs::Axis::LeftStickX,
+            GamepadAxis::LeftStickY => gilrs::Axis::LeftStickY,
+            GamepadAxis::LeftZ => gilrs::Axis::LeftZ,
+            GamepadAxis::RightStickX => gilrs::Axis::RightStickX,
+            GamepadAxis::RightStickY => gilrs::Axis::RightStickY,
+            GamepadAxis::RightZ => gilrs::Axis::RightZ,
+            GamepadAxis::DPadX => gilrs::Axis::DPadX,
+            GamepadAxis::DPadY => gilrs::Axis::DPadY
+        }
+    }
+}
+
 impl TryFrom<&KeyEvent> for VirtualKeyCode
 {
     type Error = ();
@@ -1062,8 +2149,8 @@ impl From<PhysicalSize<u32>> for UVec2
 
 pub(crate) enum UserEventGlutin<UserEventType: 'static>
 {
-    MouseGrabStatusChanged(bool),
-    FullscreenStatusChanged(bool),
+    MouseGrabStatusChanged(WindowId, bool),
+    FullscreenStatusChanged(WindowId, bool),
     UserEvent(UserEventType)
 }
 
@@ -19,6 +19,7 @@ use std::cell::{Cell, RefCell};
 use std::convert::TryInto;
 use std::ops::{Deref, DerefMut, Mul};
 use std::rc::Rc;
+use std::time::Duration;
 
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::JsCast;
@@ -27,16 +28,21 @@ use web_sys::{KeyboardEvent, MouseEvent, WheelEvent};
 use crate::dimen::{IVec2, UVec2, Vec2};
 use crate::error::{BacktraceError, ErrorMessage};
 use crate::numeric::RoundFloat;
+use crate::shape::Rect;
 use crate::web::{WebCanvasElement, WebCursorType, WebDocument, WebPending, WebWindow};
 use crate::window::{
+    CursorIcon,
     DrawingWindowHandler,
     EventLoopSendError,
     KeyScancode,
     ModifiersState,
+    Monitor,
     MouseButton,
     MouseScrollDistance,
     UserEventSender,
+    VideoMode,
     VirtualKeyCode,
+    WindowCreationOptions,
     WindowFullscreenMode,
     WindowHandler,
     WindowHelper,
@@ -383,8 +389,12 @@ where
 {
     redraw_pending: RefCell<Option<WebPending>>,
     redraw_request_action: Option<Box<RefCell<dyn FnMut() -> WebPending>>>,
+    scheduled_redraw: RefCell<Option<(f64, WebPending)>>,
+    scheduled_redraw_action: Option<Box<RefCell<ScheduledRedrawActionType>>>,
     post_user_event_action: Option<Rc<RefCell<UserEventSenderActionType<UserEventType>>>>,
     terminate_loop_action: Option<Box<dyn FnOnce()>>,
+    cursor_visible: Cell<bool>,
+    cursor: Cell<WebCursorType>,
     canvas: WebCanvasElement,
     document: WebDocument,
     window: WebWindow
@@ -397,8 +407,12 @@ impl<UserEventType: 'static> WindowHelperWeb<UserEventType>
         Self {
             redraw_pending: RefCell::new(None),
             redraw_request_action: None,
+            scheduled_redraw: RefCell::new(None),
+            scheduled_redraw_action: None,
             post_user_event_action: None,
             terminate_loop_action: None,
+            cursor_visible: Cell::new(true),
+            cursor: Cell::new(WebCursorType::Auto),
             canvas,
             document,
             window
@@ -412,6 +426,14 @@ impl<UserEventType: 'static> WindowHelperWeb<UserEventType>
         self.redraw_request_action = Some(Box::new(RefCell::new(redraw_request_action)));
     }
 
+    pub fn set_scheduled_redraw_action<F>(&mut self, scheduled_redraw_action: F)
+    where
+        F: FnMut(i32) -> WebPending + 'static
+    {
+        self.scheduled_redraw_action =
+            Some(Box::new(RefCell::new(scheduled_redraw_action)));
+    }
+
     pub fn set_post_user_event_action<F>(&mut self, post_user_event_action: F)
     where
         F: FnMut(UserEventType) -> Result<(), BacktraceError<ErrorMessage>> + 'static
@@ -434,10 +456,22 @@ impl<UserEventType: 'static> WindowHelperWeb<UserEventType>
         self.redraw_pending.replace(None);
     }
 
+    pub fn clear_scheduled_redraw(&self)
+    {
+        if let Some((_, pending)) = self.scheduled_redraw.borrow_mut().deref_mut() {
+            pending.mark_as_triggered()
+        }
+        self.scheduled_redraw.replace(None);
+    }
+
+    pub fn cancel_close(&mut self) {}
+
     pub fn terminate_loop(&mut self)
     {
         self.redraw_pending.replace(None);
         self.redraw_request_action = None;
+        self.scheduled_redraw.replace(None);
+        self.scheduled_redraw_action = None;
         if let Some(action) = self.terminate_loop_action.take() {
             action();
         }
@@ -457,13 +491,34 @@ impl<UserEventType: 'static> WindowHelperWeb<UserEventType>
 
     pub fn set_cursor_visible(&self, visible: bool)
     {
+        self.cursor_visible.set(visible);
+
         if visible {
-            self.canvas.set_cursor(WebCursorType::Auto);
+            self.canvas.set_cursor(self.cursor.get());
         } else {
             self.canvas.set_cursor(WebCursorType::None);
         }
     }
 
+    pub fn set_cursor_icon(&self, icon: CursorIcon)
+    {
+        self.cursor.set(icon.into());
+
+        if self.cursor_visible.get() {
+            self.canvas.set_cursor(self.cursor.get());
+        }
+    }
+
+    pub fn set_ime_allowed(&self, _allowed: bool)
+    {
+        // Do nothing
+    }
+
+    pub fn set_ime_position(&self, _area: Rect)
+    {
+        // Do nothing
+    }
+
     pub fn set_cursor_grab(
         &self,
         grabbed: bool
@@ -483,6 +538,32 @@ impl<UserEventType: 'static> WindowHelperWeb<UserEventType>
         // Do nothing
     }
 
+    pub fn is_focused(&self) -> bool
+    {
+        self.canvas.is_focused()
+    }
+
+    pub fn set_decorations(&self, _decorations: bool)
+    {
+        // Do nothing
+    }
+
+    pub fn start_window_drag(&self) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        // Do nothing
+        Ok(())
+    }
+
+    pub fn set_minimized(&self, _minimized: bool)
+    {
+        // Do nothing
+    }
+
+    pub fn set_maximized(&self, _maximized: bool)
+    {
+        // Do nothing
+    }
+
     #[inline]
     pub fn request_redraw(&self)
     {
@@ -501,6 +582,40 @@ impl<UserEventType: 'static> WindowHelperWeb<UserEventType>
         }
     }
 
+    pub fn request_redraw_after(&self, delay: Duration)
+    {
+        let scheduled_redraw_action = match &self.scheduled_redraw_action {
+            None => {
+                log::warn!("Ignoring call to request_redraw_after() in invalid state");
+                return;
+            }
+            Some(action) => action
+        };
+
+        let now = match self.window.performance() {
+            Ok(performance) => performance.now(),
+            Err(err) => {
+                log::error!("Failed to schedule redraw: {err:?}");
+                return;
+            }
+        };
+
+        let delay_millis = delay.as_secs_f64() * 1000.0;
+        let time = now + delay_millis;
+
+        if let Some((scheduled_time, _)) = self.scheduled_redraw.borrow().deref() {
+            if *scheduled_time <= time {
+                return;
+            }
+        }
+
+        let pending = scheduled_redraw_action.borrow_mut()(
+            delay_millis.ceil().min(i32::MAX as f64) as i32
+        );
+
+        self.scheduled_redraw.replace(Some((time, pending)));
+    }
+
     pub fn set_title(&self, title: &str)
     {
         self.window.document().unwrap().set_title(title);
@@ -512,7 +627,8 @@ impl<UserEventType: 'static> WindowHelperWeb<UserEventType>
             WindowFullscreenMode::Windowed => {
                 self.document.exit_fullscreen();
             }
-            WindowFullscreenMode::FullscreenBorderless => {
+            WindowFullscreenMode::FullscreenBorderless
+            | WindowFullscreenMode::FullscreenExclusive(_) => {
                 self.canvas.request_fullscreen();
             }
         }
@@ -549,14 +665,54 @@ impl<UserEventType: 'static> WindowHelperWeb<UserEventType>
         self.window.device_pixel_ratio()
     }
 
+    pub fn get_video_modes(&self) -> Vec<VideoMode>
+    {
+        Vec::new()
+    }
+
+    pub fn get_monitors(&self) -> Vec<Monitor>
+    {
+        Vec::new()
+    }
+
     pub fn create_user_event_sender(&self) -> UserEventSender<UserEventType>
     {
         UserEventSender::new(UserEventSenderWeb::new(
             self.post_user_event_action.as_ref().unwrap().clone()
         ))
     }
+
+    pub fn clipboard_get_text(&self) -> Result<String, BacktraceError<ErrorMessage>>
+    {
+        Err(ErrorMessage::msg(
+            "Reading the clipboard synchronously is not supported on the web"
+        ))
+    }
+
+    pub fn clipboard_set_text(
+        &self,
+        text: &str
+    ) -> Result<(), BacktraceError<ErrorMessage>>
+    {
+        self.window.clipboard_write_text(text);
+        Ok(())
+    }
+
+    pub fn create_window(
+        &self,
+        _title: &str,
+        _options: WindowCreationOptions,
+        _handler: Box<dyn WindowHandler<UserEventType>>
+    )
+    {
+        log::error!("Creating additional windows is not supported on the web");
+    }
 }
 
+/// Sets a timeout to redraw the window after the specified number of
+/// milliseconds.
+type ScheduledRedrawActionType = dyn FnMut(i32) -> WebPending;
+
 type UserEventSenderActionType<UserEventType> =
     dyn FnMut(UserEventType) -> Result<(), BacktraceError<ErrorMessage>>;
 
@@ -698,7 +854,9 @@ impl WebCanvasImpl
                     ErrorMessage::msg_with_cause("Failed to create renderer", err)
                 })?;
 
-        let handler = Rc::new(RefCell::new(DrawingWindowHandler::new(handler, renderer)));
+        let handler = Rc::new(RefCell::new(DrawingWindowHandler::new(
+            handler, renderer, false, None
+        )));
 
         let helper = {
             Rc::new(RefCell::new(WindowHelper::new(WindowHelperWeb::new(
@@ -730,6 +888,28 @@ impl WebCanvasImpl
                 .set_redraw_request_action(redraw_request_action);
         }
 
+        {
+            let helper_inner = helper.clone();
+            let window = window.clone();
+
+            let timeout_callback = RefCell::new(Closure::wrap(Box::new(move || {
+                let mut helper = RefCell::borrow_mut(Rc::borrow(&helper_inner));
+                helper.inner().clear_scheduled_redraw();
+                helper.request_redraw();
+            })
+                as Box<dyn FnMut()>));
+
+            let scheduled_redraw_action = move |timeout_millis| {
+                window
+                    .set_timeout(&timeout_callback, timeout_millis)
+                    .unwrap()
+            };
+
+            RefCell::borrow_mut(Rc::borrow(&helper))
+                .inner()
+                .set_scheduled_redraw_action(scheduled_redraw_action);
+        }
+
         {
             let user_event_queue = Rc::new(RefCell::new(Vec::new()));
             let user_event_callback_pending = Rc::new(RefCell::new(None));
@@ -780,6 +960,24 @@ impl WebCanvasImpl
             .clone()
             .dyn_into_event_target()?;
 
+        for (event_name, focused) in [("focus", true), ("blur", false)] {
+            let handler = handler.clone();
+            let helper = helper.clone();
+
+            event_listeners_to_clean_up.push(
+                canvas_event_target.register_event_listener_void(
+                    event_name,
+                    move || {
+                        RefCell::borrow_mut(Rc::borrow(&handler))
+                            .on_window_focus_changed(
+                                RefCell::borrow_mut(Rc::borrow(&helper)).deref_mut(),
+                                focused
+                            );
+                    }
+                )?
+            );
+        }
+
         match canvas_event_target
             .register_event_listener_mouse("contextmenu", move |event| {
                 event.prevent_default()
@@ -889,8 +1087,17 @@ impl WebCanvasImpl
 
                         RefCell::borrow_mut(Rc::borrow(&handler)).on_mouse_move(
                             RefCell::borrow_mut(Rc::borrow(&helper)).deref_mut(),
-                            position
+                            position,
+                            is_pointer_locked.get()
                         );
+
+                        RefCell::borrow_mut(Rc::borrow(&handler))
+                            .on_mouse_motion_relative(
+                                RefCell::borrow_mut(Rc::borrow(&helper)).deref_mut(),
+                                IVec2::new(event.movement_x(), event.movement_y())
+                                    .into_f32()
+                                    .mul(current_dpr)
+                            );
                     }
                 )?
             );
@@ -1150,3 +1357,35 @@ fn mouse_button_from_event(event: &MouseEvent) -> Option<MouseButton>
         _ => Some(MouseButton::Other(button.try_into().unwrap()))
     }
 }
+
+impl From<CursorIcon> for WebCursorType
+{
+    fn from(icon: CursorIcon) -> Self
+    {
+        match icon {
+            CursorIcon::Default => WebCursorType::Default,
+            CursorIcon::Pointer => WebCursorType::Pointer,
+            CursorIcon::Progress => WebCursorType::Progress,
+            CursorIcon::Wait => WebCursorType::Wait,
+            CursorIcon::Cell => WebCursorType::Cell,
+            CursorIcon::Crosshair => WebCursorType::Crosshair,
+            CursorIcon::Text => WebCursorType::Text,
+            CursorIcon::VerticalText => WebCursorType::VerticalText,
+            CursorIcon::Alias => WebCursorType::Alias,
+            CursorIcon::Copy => WebCursorType::Copy,
+            CursorIcon::Move => WebCursorType::Move,
+            CursorIcon::NoDrop => WebCursorType::NoDrop,
+            CursorIcon::NotAllowed => WebCursorType::NotAllowed,
+            CursorIcon::Grab => WebCursorType::Grab,
+            CursorIcon::Grabbing => WebCursorType::Grabbing,
+            CursorIcon::ColResize => WebCursorType::ColResize,
+            CursorIcon::RowResize => WebCursorType::RowResize,
+            CursorIcon::EwResize => WebCursorType::EWResize,
+            CursorIcon::NsResize => WebCursorType::NSResize,
+            CursorIcon::NeswResize => WebCursorType::NESWResize,
+            CursorIcon::NwseResize => WebCursorType::NWSEResize,
+            CursorIcon::ZoomIn => WebCursorType::ZoomIn,
+            CursorIcon::ZoomOut => WebCursorType::ZoomOut
+        }
+    }
+}
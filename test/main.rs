@@ -22,6 +22,8 @@ compile_error!("The automated tests currently support Linux x86_64 only");
 use std::convert::TryInto;
 use std::ffi::CString;
 use std::sync::mpsc::channel;
+#[cfg(feature = "image-loading")]
+use std::time::Duration;
 
 use glutin::config::ConfigTemplateBuilder;
 use glutin::context::{
@@ -35,12 +37,41 @@ use glutin::surface::{PbufferSurface, SurfaceAttributesBuilder};
 use glutin_winit::DisplayBuilder;
 use image::{ColorType, GenericImageView, ImageFormat};
 use num_traits::ToPrimitive;
-use speedy2d::color::Color;
-use speedy2d::dimen::{Vec2, Vector2};
-use speedy2d::font::{Font, TextAlignment, TextLayout, TextOptions};
-use speedy2d::image::{ImageDataType, ImageSmoothingMode};
-use speedy2d::shape::{Polygon, Rect, Rectangle};
-use speedy2d::GLRenderer;
+use speedy2d::color::{Color, Gradient};
+use speedy2d::dimen::{Matrix2D, Vec2, Vector2};
+use speedy2d::font::{
+    Font,
+    RichText,
+    TextAlignment,
+    TextLayout,
+    TextOptions,
+    TextShadow,
+    TextSpan
+};
+use speedy2d::image::{
+    ImageAtlasBuilder,
+    ImageDataType,
+    ImageInstance,
+    ImageSmoothingMode,
+    ImageWrapMode,
+    SpriteBatch
+};
+use speedy2d::shape::{
+    ClipShape,
+    CornerRadii,
+    Ellipse,
+    FillRule,
+    Insets,
+    LineCap,
+    LineJoin,
+    Path,
+    Polygon,
+    Rect,
+    Rectangle,
+    RotatedRectangle,
+    StrokeOptions
+};
+use speedy2d::{Antialiasing, BlendMode, GLRenderer, GLRendererOptions};
 use winit::event_loop::EventLoop;
 
 const NOTO_SANS_REGULAR_BYTES: &[u8] =
@@ -72,24 +103,57 @@ fn read_png_argb8<S: AsRef<str>>(name: S) -> Option<Vec<u8>>
         .map(|image| image.into_rgba8().into_raw())
 }
 
+/// If this environment variable is set, the tests are run using the software
+/// renderer, so no GL context is required.
+const SOFTWARE_RENDERER_ENV_VAR: &str = "SPEEDY2D_TEST_SOFTWARE";
+
+fn use_software_renderer() -> bool
+{
+    std::env::var_os(SOFTWARE_RENDERER_ENV_VAR).is_some()
+}
+
+/// The output of the software renderer differs slightly from OpenGL due to
+/// rounding, so a small number of differences are tolerated.
+fn software_render_matches(expected: &[u8], actual: &[u8]) -> bool
+{
+    let large_differences = expected
+        .iter()
+        .zip(actual)
+        .filter(|(expected, actual)| expected.abs_diff(**actual) > 1)
+        .count();
+
+    expected.len() == actual.len() && large_differences * 10000 <= expected.len()
+}
+
 fn write_framebuffer_to_png<S: AsRef<str>>(name: S, width: u32, height: u32, data: &[u8])
 {
     write_rgba_to_png(name, width, height, data);
 }
 
 fn create_context_and_run<R, F>(
-    event_loop: &EventLoop<()>,
+    event_loop: Option<&EventLoop<()>>,
     width: u32,
     height: u32,
+    options: GLRendererOptions,
     action: F
 ) -> R
 where
     F: FnOnce(&mut GLRenderer) -> R
 {
+    let event_loop = match event_loop {
+        Some(event_loop) => event_loop,
+        None => {
+            let mut renderer = GLRenderer::new_software((width, height)).unwrap();
+            return action(&mut renderer);
+        }
+    };
+
     let (_window, config) = DisplayBuilder::new()
-        .build(event_loop, ConfigTemplateBuilder::new(), |mut configs| {
-            configs.next().unwrap()
-        })
+        .build(
+            event_loop,
+            ConfigTemplateBuilder::new().with_stencil_size(8),
+            |mut configs| configs.next().unwrap()
+        )
         .unwrap();
 
     let gl_display = config.display();
@@ -112,10 +176,14 @@ where
     let _context = context.make_current(&surface).unwrap();
 
     let mut renderer = unsafe {
-        GLRenderer::new_for_gl_context((width, height), |name| {
-            gl_display.get_proc_address(CString::new(name).unwrap().as_c_str())
-                as *const _
-        })
+        GLRenderer::new_for_gl_context_with_options(
+            (width, height),
+            |name| {
+                gl_display.get_proc_address(CString::new(name).unwrap().as_c_str())
+                    as *const _
+            },
+            options
+        )
         .unwrap()
     };
 
@@ -123,34 +191,41 @@ where
 }
 
 fn run_test_with_new_context<S: AsRef<str>, F: FnOnce(&mut GLRenderer)>(
-    event_loop: &EventLoop<()>,
+    event_loop: Option<&EventLoop<()>>,
     expected_image_name: S,
     width: u32,
     height: u32,
+    options: GLRendererOptions,
     action: F
 )
 {
     let expected_image = read_png_argb8(expected_image_name.as_ref());
 
-    let actual_image = create_context_and_run(event_loop, width, height, |renderer| {
-        action(renderer);
-
-        let actual_image =
-            renderer.draw_frame(|graphics| graphics.capture(ImageDataType::RGBA));
+    let images_match = |expected: &[u8], actual: &[u8]| match event_loop {
+        Some(_) => expected == actual,
+        None => software_render_matches(expected, actual)
+    };
 
-        if expected_image.is_none()
-            || (&expected_image).as_ref().unwrap() != actual_image.data()
-        {
-            write_framebuffer_to_png(
-                format!("{}_ACTUAL", expected_image_name.as_ref()),
-                width,
-                height,
-                actual_image.data().as_slice()
-            );
-        }
+    let actual_image =
+        create_context_and_run(event_loop, width, height, options, |renderer| {
+            action(renderer);
+
+            let actual_image =
+                renderer.draw_frame(|graphics| graphics.capture(ImageDataType::RGBA));
+
+            if expected_image.is_none()
+                || !images_match(expected_image.as_ref().unwrap(), actual_image.data())
+            {
+                write_framebuffer_to_png(
+                    format!("{}_ACTUAL", expected_image_name.as_ref()),
+                    width,
+                    height,
+                    actual_image.data().as_slice()
+                );
+            }
 
-        actual_image
-    });
+            actual_image
+        });
 
     assert!(expected_image.is_some(), "Expected image does not exist");
 
@@ -168,12 +243,20 @@ fn run_test_with_new_context<S: AsRef<str>, F: FnOnce(&mut GLRenderer)>(
         "Actual image size mismatch"
     );
 
-    assert_eq!(
-        expected_image,
-        actual_image.into_data(),
-        "Generated image did not match expected ({})",
-        expected_image_name.as_ref()
-    );
+    if event_loop.is_some() {
+        assert_eq!(
+            expected_image,
+            actual_image.into_data(),
+            "Generated image did not match expected ({})",
+            expected_image_name.as_ref()
+        );
+    } else {
+        assert!(
+            images_match(&expected_image, actual_image.data()),
+            "Generated image did not match expected ({})",
+            expected_image_name.as_ref()
+        );
+    }
 }
 
 struct GLTest
@@ -181,6 +264,7 @@ struct GLTest
     width: u32,
     height: u32,
     name: String,
+    options: GLRendererOptions,
     action: Box<dyn FnOnce(&mut GLRenderer)>
 }
 
@@ -188,7 +272,10 @@ fn main()
 {
     simple_logger::SimpleLogger::new().init().unwrap();
 
-    let event_loop = EventLoop::new().unwrap();
+    let event_loop = match use_software_renderer() {
+        true => None,
+        false => Some(EventLoop::new().unwrap())
+    };
 
     let mut tests = Vec::new();
 
@@ -196,6 +283,7 @@ fn main()
         width: 500,
         height: 500,
         name: "issue_90_pass_text_between_threads".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
 
@@ -225,6 +313,7 @@ fn main()
         width: 500,
         height: 500,
         name: "issue_74_image_handle_drop".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             for i in 0..4 {
                 renderer.draw_frame(|graphics| {
@@ -238,8 +327,7 @@ fn main()
                             .unwrap();
                     }
                     graphics.draw_circle(
-                        Vec2::new(100.0, 150.0),
-                        50.0,
+                        (Vec2::new(100.0, 150.0), 50.0),
                         Color::from_gray(0.0)
                     );
                 });
@@ -251,6 +339,7 @@ fn main()
         width: 500,
         height: 500,
         name: "issue_55_text_cache_empty".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
 
@@ -281,6 +370,7 @@ fn main()
         width: 500,
         height: 500,
         name: "semitransparent_on_opaque".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             renderer.draw_frame(|graphics| {
                 graphics.clear_screen(Color::BLACK);
@@ -311,6 +401,7 @@ fn main()
         width: 500,
         height: 500,
         name: "semitransparent_on_semitransparent".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             renderer.draw_frame(|graphics| {
                 graphics.clear_screen(Color::from_hex_argb(0x55888888));
@@ -341,6 +432,7 @@ fn main()
         width: 500,
         height: 500,
         name: "opaque_on_semitransparent".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             renderer.draw_frame(|graphics| {
                 graphics.clear_screen(Color::from_hex_argb(0x55888888));
@@ -371,6 +463,7 @@ fn main()
         width: 50,
         height: 50,
         name: "basic_rectangles".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             renderer.draw_frame(|graphics| {
                 graphics.clear_screen(Color::BLUE);
@@ -382,6 +475,7 @@ fn main()
                 );
 
                 // Passes a reference to the rectangle
+                #[allow(clippy::needless_borrows_for_generic_args)]
                 graphics.draw_rectangle(
                     &Rectangle::from_tuples((15.0, 30.0), (49.0, 48.0)),
                     Color::GREEN
@@ -394,6 +488,7 @@ fn main()
         width: 50,
         height: 50,
         name: "lines_horizontal".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             renderer.draw_frame(|graphics| {
                 graphics.clear_screen(Color::WHITE);
@@ -411,6 +506,7 @@ fn main()
         width: 50,
         height: 50,
         name: "lines_vertical".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             renderer.draw_frame(|graphics| {
                 graphics.clear_screen(Color::WHITE);
@@ -428,13 +524,14 @@ fn main()
         width: 50,
         height: 50,
         name: "basic_circles".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             renderer.draw_frame(|graphics| {
                 graphics.clear_screen(Color::WHITE);
 
-                graphics.draw_circle((20.0, 20.0), 10.0, Color::RED);
+                graphics.draw_circle(((20.0, 20.0), 10.0), Color::RED);
 
-                graphics.draw_circle((40.0, 40.0), 5.0, Color::BLUE);
+                graphics.draw_circle(((40.0, 40.0), 5.0), Color::BLUE);
             });
         })
     });
@@ -443,10 +540,12 @@ fn main()
         width: 300,
         height: 300,
         name: "half_circle".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             renderer.draw_frame(|graphics| {
                 graphics.clear_screen(Color::WHITE);
 
+                #[allow(clippy::clone_on_copy)]
                 graphics.draw_circle_section_triangular_three_color(
                     [
                         Vec2::new(100.0, 100.0),
@@ -472,6 +571,7 @@ fn main()
         width: 1400,
         height: 500,
         name: "basic_text_white_background".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
 
@@ -520,6 +620,7 @@ fn main()
         width: 500,
         height: 500,
         name: "text_trimming".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
 
@@ -585,6 +686,7 @@ fn main()
         width: 500,
         height: 500,
         name: "basic_text_subpixel".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
 
@@ -614,6 +716,7 @@ fn main()
         width: 1400,
         height: 500,
         name: "basic_text_black_background".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
 
@@ -643,6 +746,7 @@ fn main()
         width: 800,
         height: 800,
         name: "cropped_text".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
 
@@ -685,6 +789,7 @@ fn main()
         width: 640,
         height: 640,
         name: "wrapped_text_1".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
 
@@ -761,6 +866,7 @@ fn main()
         width: 640,
         height: 640,
         name: "text_tracking".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
 
@@ -784,6 +890,7 @@ fn main()
         width: 640,
         height: 640,
         name: "text_alignment".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
 
@@ -820,10 +927,251 @@ fn main()
         })
     });
 
+    tests.push(GLTest {
+        width: 640,
+        height: 200,
+        name: "text_shadow".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
+
+            let text = typeface.layout_text(
+                "The quick brown föx jumped",
+                40.0,
+                TextOptions::new()
+            );
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.draw_text_with_shadow(
+                    (10.0, 10.0),
+                    Color::BLACK,
+                    &TextShadow::new((3.0, 3.0), 0.0, Color::from_rgb(1.0, 0.6, 0.6)),
+                    &text
+                );
+
+                graphics.draw_text_with_shadow(
+                    (10.0, 100.0),
+                    Color::BLUE,
+                    &TextShadow::new(
+                        (4.0, 5.0),
+                        6.0,
+                        Color::from_rgba(0.0, 0.0, 0.0, 0.6)
+                    ),
+                    &text
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 640,
+        height: 200,
+        name: "text_outlined".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
+
+            let text = typeface.layout_text(
+                "The quick brown föx jumped",
+                40.0,
+                TextOptions::new()
+            );
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::from_rgb(0.5, 0.6, 0.9));
+
+                graphics.draw_text_outlined(
+                    (10.0, 10.0),
+                    Color::WHITE,
+                    Color::BLACK,
+                    2.0,
+                    &text
+                );
+
+                graphics.draw_text_outlined(
+                    (10.0, 100.0),
+                    Color::YELLOW,
+                    Color::from_rgba(0.0, 0.0, 0.0, 0.5),
+                    4.5,
+                    &text
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 640,
+        height: 320,
+        name: "text_along_path".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
+
+            let center = Vec2::new(160.0, 170.0);
+            let radius = 120.0;
+
+            let arc: Vec<Vec2> = (0..=64)
+                .map(|i| {
+                    let angle = std::f32::consts::PI * (1.0 + i as f32 / 64.0);
+                    center + Vec2::new(angle.cos(), angle.sin()) * radius
+                })
+                .collect();
+
+            let arc_text = typeface.layout_text(
+                "Speedy2D badge",
+                32.0,
+                TextOptions::new().with_wrap_to_width(
+                    std::f32::consts::PI * radius,
+                    TextAlignment::Center
+                )
+            );
+
+            let wave = Path::new().move_to((340.0, 200.0)).cubic_to(
+                (420.0, 60.0),
+                (520.0, 320.0),
+                (620.0, 160.0)
+            );
+
+            let wave_text =
+                typeface.layout_text("Text following a curve", 24.0, TextOptions::new());
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.draw_polyline(&arc, 1.0, Color::LIGHT_GRAY);
+                graphics.draw_text_along_path(&arc, Color::BLACK, &arc_text);
+
+                let wave_points = &wave.to_polylines()[0];
+
+                graphics.draw_polyline(wave_points, 1.0, Color::LIGHT_GRAY);
+                graphics.draw_text_along_path(wave_points, Color::BLUE, &wave_text);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 320,
+        height: 100,
+        name: "text_precached".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
+
+            renderer.draw_frame(|graphics| {
+                graphics.precache_glyphs(&typeface, 48.0, "0123456789:");
+            });
+
+            let text = typeface.layout_text("12:34:56", 48.0, TextOptions::new());
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+                graphics.precache_glyphs(&typeface, 24.0, "0123456789:");
+                graphics.draw_text((10.0, 10.0), Color::BLACK, &text);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 640,
+        height: 140,
+        name: "text_rasterized".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
+
+            let text = typeface.layout_text(
+                "The quick brown föx jumped",
+                40.0,
+                TextOptions::new()
+            );
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::from_rgb(0.9, 0.95, 1.0));
+
+                let image = graphics
+                    .rasterize_text_to_image(&text, Color::BLUE, 4)
+                    .unwrap();
+
+                graphics.draw_text((10.0, 10.0), Color::BLUE, &text);
+                graphics.draw_image((6.0, 76.0), &image);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 320,
+        height: 60,
+        name: "text_pixel_snapping".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
+
+            let unsnapped = typeface.layout_text(
+                "Small interface text, 11.3px",
+                11.3,
+                TextOptions::new()
+            );
+
+            let snapped = typeface.layout_text(
+                "Small interface text, 11.3px",
+                11.3,
+                TextOptions::new().with_pixel_snapping(true)
+            );
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+                graphics.draw_text((10.0, 10.0), Color::BLACK, &unsnapped);
+                graphics.draw_text((10.0, 30.0), Color::BLACK, &snapped);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 400,
+        height: 240,
+        name: "text_background".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
+
+            let yellow = Color::from_rgb(1.0, 0.95, 0.4);
+
+            let text = RichText::new()
+                .with_span(TextSpan::new("Some ", &typeface, 28.0))
+                .with_span(
+                    TextSpan::new("highlighted text which wraps", &typeface, 28.0)
+                        .with_background_color(yellow)
+                )
+                .with_span(TextSpan::new(" and ", &typeface, 28.0))
+                .with_span(
+                    TextSpan::new("more", &typeface, 28.0)
+                        .with_color(Color::WHITE)
+                        .with_background_color(Color::BLUE)
+                )
+                .layout(
+                    TextOptions::new().with_wrap_to_width(240.0, TextAlignment::Left)
+                );
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+                graphics.draw_text((10.0, 10.0), Color::BLACK, &text);
+
+                let image = graphics
+                    .rasterize_text_to_image(&text, Color::BLACK, 0)
+                    .unwrap();
+
+                graphics.draw_image((10.0, 120.0), &image);
+            });
+        })
+    });
+
     tests.push(GLTest {
         width: 640,
         height: 640,
         name: "text_line_spacing".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
 
@@ -866,6 +1214,7 @@ fn main()
         width: 500,
         height: 500,
         name: "text_line_break_1".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
 
@@ -886,6 +1235,7 @@ fn main()
         width: 500,
         height: 500,
         name: "text_line_break_2".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
 
@@ -906,6 +1256,7 @@ fn main()
         width: 640,
         height: 640,
         name: "wrapped_text_line_break".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
 
@@ -964,6 +1315,7 @@ fn main()
         width: 3000,
         height: 2000,
         name: "huge_text".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
 
@@ -980,6 +1332,7 @@ fn main()
         width: 640,
         height: 640,
         name: "image_load_from_raw_pixels".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let image =
                 image::open("test/assets/expected_images/test_half_circle.png").unwrap();
@@ -1006,6 +1359,7 @@ fn main()
         width: 640,
         height: 640,
         name: "image_load_from_raw_pixels_multiple_times".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let image =
                 image::open("test/assets/expected_images/test_half_circle.png").unwrap();
@@ -1034,6 +1388,7 @@ fn main()
         width: 640,
         height: 640,
         name: "image_load_from_raw_pixels_no_alpha".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let image =
                 image::open("test/assets/expected_images/test_half_circle.png").unwrap();
@@ -1061,6 +1416,7 @@ fn main()
         width: 640,
         height: 640,
         name: "image_load_from_file_path".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let image = renderer
                 .create_image_from_file_path(
@@ -1082,6 +1438,7 @@ fn main()
         width: 640,
         height: 640,
         name: "image_load_from_file_bytes".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let image = renderer
                 .create_image_from_file_bytes(
@@ -1104,6 +1461,7 @@ fn main()
         width: 640,
         height: 640,
         name: "image_load_from_raw_pixels_smiley".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let image =
                 image::open("test/assets/test_images/smiley_colormap.png").unwrap();
@@ -1130,6 +1488,7 @@ fn main()
         width: 100,
         height: 100,
         name: "clip_area".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             renderer.draw_frame(|graphics| {
                 graphics.clear_screen(Color::LIGHT_GRAY);
@@ -1151,6 +1510,7 @@ fn main()
         width: 400,
         height: 150,
         name: "clip_area_2".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
 
@@ -1169,6 +1529,7 @@ fn main()
         width: 500,
         height: 500,
         name: "polygon_test_1".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             renderer.draw_frame(|graphics| {
                 graphics.clear_screen(Color::WHITE);
@@ -1188,6 +1549,7 @@ fn main()
         width: 500,
         height: 500,
         name: "polygon_test_anticlockwise".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             renderer.draw_frame(|graphics| {
                 graphics.clear_screen(Color::WHITE);
@@ -1207,6 +1569,7 @@ fn main()
         width: 500,
         height: 500,
         name: "polygon_test_offset".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             renderer.draw_frame(|graphics| {
                 graphics.clear_screen(Color::WHITE);
@@ -1227,6 +1590,7 @@ fn main()
         width: 500,
         height: 500,
         name: "polygon_test_concave_anticlockwise".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             renderer.draw_frame(|graphics| {
                 graphics.clear_screen(Color::WHITE);
@@ -1242,10 +1606,60 @@ fn main()
         })
     });
 
+    tests.push(GLTest {
+        width: 400,
+        height: 400,
+        name: "polygon_test_fill_rules".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                let star = [
+                    (100.0, 20.0),
+                    (147.0, 165.0),
+                    (24.0, 75.0),
+                    (176.0, 75.0),
+                    (53.0, 165.0)
+                ];
+
+                let even_odd = Polygon::new_with_fill_rule(&star, FillRule::EvenOdd);
+                graphics.draw_polygon(&even_odd, (0.0, 0.0), Color::RED);
+
+                let non_zero = Polygon::new_with_fill_rule(&star, FillRule::NonZero);
+                graphics.draw_polygon(&non_zero, (200.0, 0.0), Color::BLUE);
+
+                let figure_eight =
+                    [(20.0, 220.0), (180.0, 220.0), (20.0, 380.0), (180.0, 380.0)];
+                let figure_eight =
+                    Polygon::new_with_fill_rule(&figure_eight, FillRule::NonZero);
+                graphics.draw_polygon(&figure_eight, (0.0, 0.0), Color::GREEN);
+
+                let frame = Polygon::new_with_holes(
+                    &[
+                        (220.0, 220.0),
+                        (380.0, 220.0),
+                        (380.0, 380.0),
+                        (220.0, 380.0)
+                    ],
+                    &[[
+                        (260.0, 260.0),
+                        (340.0, 260.0),
+                        (340.0, 340.0),
+                        (260.0, 340.0)
+                    ]],
+                    FillRule::EvenOdd
+                );
+                graphics.draw_polygon(&frame, (0.0, 0.0), Color::BLACK);
+            });
+        })
+    });
+
     tests.push(GLTest {
         width: 640,
         height: 479,
         name: "capture_1".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap(); // TODO dedupe
 
@@ -1283,6 +1697,7 @@ fn main()
         width: 500,
         height: 500,
         name: "round_rect_1".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             renderer.draw_frame(|graphics| {
                 graphics.clear_screen(Color::WHITE);
@@ -1299,6 +1714,7 @@ fn main()
         width: 500,
         height: 500,
         name: "round_rect_2".to_string(),
+        options: GLRendererOptions::default(),
         action: Box::new(|renderer| {
             renderer.draw_frame(|graphics| {
                 graphics.clear_screen(Color::WHITE);
@@ -1314,14 +1730,1488 @@ fn main()
         })
     });
 
-    for test in tests {
+    tests.push(GLTest {
+        width: 500,
+        height: 500,
+        name: "round_rect_corner_radii".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.draw_rounded_rectangle(
+                    Rectangle::from_tuples((50.0, 50.0), (250.0, 150.0))
+                        .rounded_with_radii(CornerRadii::new(30.0, 30.0, 0.0, 0.0)),
+                    Color::BLUE
+                );
+
+                graphics.draw_rounded_rectangle(
+                    Rectangle::from_tuples((50.0, 200.0), (450.0, 400.0))
+                        .rounded_with_radii(CornerRadii::new(10.0, 50.0, 80.0, 0.0)),
+                    Color::RED
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 300,
+        height: 300,
+        name: "rotated_rect".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                let rect = RotatedRectangle::new((150.0, 150.0), (160.0, 60.0), 0.5);
+
+                graphics.draw_rectangle(rect.bounding_rect(), Color::LIGHT_GRAY);
+                graphics.draw_rotated_rectangle(&rect, Color::BLUE);
+
+                for vertex in rect.vertices().iter() {
+                    graphics.draw_circle((*vertex, 4.0), Color::RED);
+                }
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 200,
+        height: 200,
+        name: "render_target_1".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let target = renderer
+                .create_render_target((100, 100), ImageSmoothingMode::NearestNeighbor)
+                .unwrap();
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+                graphics.set_clip(Some(Rectangle::from_tuples((0, 0), (150, 150))));
+
+                graphics.draw_to_target(&target, |graphics| {
+                    graphics.clear_screen(Color::BLUE);
+                    graphics.draw_rectangle(
+                        Rectangle::from_tuples((0.0, 0.0), (50.0, 20.0)),
+                        Color::RED
+                    );
+                    graphics.set_clip(Some(Rectangle::from_tuples((0, 80), (100, 100))));
+                    graphics.clear_screen(Color::GREEN);
+                });
+
+                graphics.draw_image((20.0, 20.0), target.image());
+                graphics.draw_image((120.0, 120.0), target.image());
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 100,
+        height: 100,
+        name: "antialiasing_msaa4".to_string(),
+        options: GLRendererOptions::new().with_antialiasing(Antialiasing::Msaa4),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                let polygon = Polygon::new(&[(10.0, 10.0), (90.0, 30.0), (40.0, 90.0)]);
+
+                graphics.draw_polygon(&polygon, (0.0, 0.0), Color::BLACK);
+                graphics.draw_circle(((70.0, 70.0), 20.0), Color::RED);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 300,
+        height: 300,
+        name: "image_rotated_scaled".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let size = Vector2::new(3, 3);
+
+            #[rustfmt::skip]
+            let pixels: Vec<u8> = vec![
+                255, 0, 0,   255, 255, 255,   0, 0, 255,
+                255, 255, 255,   0, 0, 0,   255, 255, 255,
+                0, 255, 0,   255, 255, 255,   255, 0, 255
+            ];
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::LIGHT_GRAY);
+
+                let nearest = graphics
+                    .create_image_from_raw_pixels(
+                        ImageDataType::RGB,
+                        ImageSmoothingMode::NearestNeighbor,
+                        size,
+                        &pixels
+                    )
+                    .unwrap();
+
+                let linear = graphics
+                    .create_image_from_raw_pixels(
+                        ImageDataType::RGB,
+                        ImageSmoothingMode::Linear,
+                        size,
+                        &pixels
+                    )
+                    .unwrap();
+
+                graphics.draw_rectangle_image(
+                    Rectangle::from_tuples((10.0, 10.0), (140.0, 100.0)),
+                    &nearest
+                );
+
+                graphics.draw_rectangle_image(
+                    Rectangle::from_tuples((160.0, 10.0), (290.0, 100.0)),
+                    &linear
+                );
+
+                graphics.draw_image_rotated((75.0, 200.0), &nearest, 0.5, 30.0);
+                graphics.draw_image_rotated(
+                    (225.0, 200.0),
+                    &linear,
+                    -std::f32::consts::FRAC_PI_4,
+                    30.0
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 400,
+        height: 300,
+        name: "transform_stack".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let typeface = Font::new(NOTO_SANS_REGULAR_BYTES).unwrap();
+            let text = typeface.layout_text("Hello", 32.0, TextOptions::new());
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.push_transform(Matrix2D::translate((100.0, 150.0)));
+
+                graphics.push_transform(Matrix2D::rotate(std::f32::consts::FRAC_PI_4));
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((-50.0, -50.0), (50.0, 50.0)),
+                    Color::RED
+                );
+                graphics.pop_transform();
+
+                graphics.push_transform(Matrix2D::scale((2.0, 1.0)));
+                graphics.draw_circle(((0.0, 0.0), 20.0), Color::BLUE);
+                graphics.pop_transform();
+
+                graphics.pop_transform();
+
+                graphics.set_transform(
+                    Matrix2D::rotate(-0.3).then(&Matrix2D::translate((220.0, 200.0)))
+                );
+                graphics.draw_text((0.0, 0.0), Color::BLACK, &text);
+                graphics.draw_line((0.0, 0.0), (150.0, 0.0), 2.0, Color::GREEN);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 400,
+        height: 200,
+        name: "circle_section_and_arc".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                use std::f32::consts::{FRAC_PI_2, PI};
+
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.draw_circle_section(
+                    (100.0, 100.0),
+                    80.0,
+                    0.0,
+                    FRAC_PI_2,
+                    Color::RED
+                );
+                graphics.draw_circle_section(
+                    (100.0, 100.0),
+                    80.0,
+                    PI,
+                    PI * 1.8,
+                    Color::BLUE
+                );
+                graphics.draw_circle_section(
+                    (100.0, 100.0),
+                    20.0,
+                    -1.0,
+                    10.0,
+                    Color::BLACK
+                );
+
+                graphics.draw_arc(
+                    (300.0, 100.0),
+                    70.0,
+                    -FRAC_PI_2,
+                    PI,
+                    15.0,
+                    Color::GREEN
+                );
+                graphics.draw_arc(
+                    (300.0, 100.0),
+                    30.0,
+                    0.0,
+                    PI * 2.0,
+                    4.0,
+                    Color::MAGENTA
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 300,
+        height: 200,
+        name: "ellipse_1".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+                graphics.draw_ellipse((80.0, 100.0), (60.0, 30.0), Color::RED);
+                graphics.draw_ellipse((80.0, 100.0), (10.0, 80.0), Color::BLUE);
+                graphics.draw_ellipse_shape(
+                    Ellipse::from_bounding_rect(&Rect::from_tuples(
+                        (180.0, 20.0),
+                        (280.0, 180.0)
+                    )),
+                    Color::GREEN
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 300,
+        height: 200,
+        name: "path_fill_and_stroke".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                let heart = Path::new()
+                    .move_to((100.0, 180.0))
+                    .cubic_to((0.0, 100.0), (40.0, 0.0), (100.0, 60.0))
+                    .cubic_to((160.0, 0.0), (200.0, 100.0), (100.0, 180.0))
+                    .close();
+
+                graphics.draw_path_filled(&heart, Color::RED);
+                graphics.draw_path_stroked(
+                    &heart,
+                    6.0,
+                    Color::from_rgba(0.0, 0.0, 0.0, 0.5)
+                );
+
+                let zigzag = Path::new()
+                    .move_to((200.0, 30.0))
+                    .line_to((280.0, 60.0))
+                    .line_to((200.0, 90.0))
+                    .quad_to((320.0, 130.0), (220.0, 170.0));
+
+                graphics.draw_path_stroked(
+                    &zigzag,
+                    12.0,
+                    Color::from_rgba(0.0, 0.0, 1.0, 0.5)
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 300,
+        height: 300,
+        name: "polyline_joins_and_caps".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                let color = Color::from_rgba(0.0, 0.0, 0.0, 0.5);
+
+                let styles = [
+                    (LineJoin::Miter, LineCap::Butt),
+                    (LineJoin::Round, LineCap::Round),
+                    (LineJoin::Bevel, LineCap::Square)
+                ];
+
+                for (i, (join, cap)) in styles.iter().enumerate() {
+                    let y = 20.0 + i as f32 * 90.0;
+
+                    graphics.draw_polyline_with_options(
+                        &[
+                            (30.0, y + 60.0),
+                            (80.0, y),
+                            (130.0, y + 60.0),
+                            (180.0, y + 30.0),
+                            (270.0, y + 40.0)
+                        ],
+                        &StrokeOptions::new(16.0)
+                            .with_line_join(*join)
+                            .with_line_cap(*cap),
+                        color
+                    );
+                }
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 300,
+        height: 200,
+        name: "linear_gradient".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                let rainbow = Gradient::linear((20.0, 0.0), (280.0, 0.0))
+                    .with_stop(0.0, Color::RED)
+                    .with_stop(0.25, Color::YELLOW)
+                    .with_stop(0.5, Color::GREEN)
+                    .with_stop(0.75, Color::CYAN)
+                    .with_stop(1.0, Color::BLUE);
+
+                graphics.draw_rectangle_gradient(
+                    Rectangle::from_tuples((0.0, 0.0), (300.0, 80.0)),
+                    &rainbow
+                );
+
+                let diagonal = Gradient::linear((20.0, 100.0), (120.0, 180.0))
+                    .with_stop(0.0, Color::BLACK)
+                    .with_stop(0.5, Color::MAGENTA)
+                    .with_stop(0.5, Color::from_rgba(0.0, 0.0, 1.0, 0.0))
+                    .with_stop(1.0, Color::BLUE);
+
+                let triangle =
+                    Polygon::new(&[(20.0, 100.0), (280.0, 110.0), (100.0, 190.0)]);
+
+                graphics.draw_polygon_gradient(&triangle, (0.0, 0.0), &diagonal);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 300,
+        height: 200,
+        name: "radial_gradient".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                let vignette = Gradient::radial((75.0, 100.0), 90.0)
+                    .with_stop(0.4, Color::from_rgba(0.0, 0.0, 0.0, 0.0))
+                    .with_stop(1.0, Color::BLACK);
+
+                graphics.draw_rectangle_gradient(
+                    Rectangle::from_tuples((0.0, 0.0), (150.0, 200.0)),
+                    &vignette
+                );
+
+                let glow = Gradient::radial((225.0, 60.0), 50.0)
+                    .with_stop(0.0, Color::YELLOW)
+                    .with_stop(0.3, Color::RED)
+                    .with_stop(1.0, Color::from_rgba(1.0, 0.0, 0.0, 0.0));
+
+                graphics.draw_circle_gradient((225.0, 60.0), 50.0, &glow);
+
+                let diamond = Path::new()
+                    .move_to((225.0, 120.0))
+                    .line_to((285.0, 160.0))
+                    .line_to((225.0, 200.0))
+                    .line_to((165.0, 160.0))
+                    .close();
+
+                let rings = Gradient::radial((225.0, 160.0), 40.0)
+                    .with_stop(0.0, Color::BLUE)
+                    .with_stop(0.5, Color::CYAN)
+                    .with_stop(0.5, Color::BLUE)
+                    .with_stop(1.0, Color::CYAN);
+
+                graphics.draw_path_filled_gradient(&diamond, &rings);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 300,
+        height: 150,
+        name: "rectangle_four_color_and_circle_two_color".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.draw_rectangle_four_color(
+                    Rectangle::from_tuples((10.0, 10.0), (140.0, 140.0)),
+                    [Color::RED, Color::GREEN, Color::BLUE, Color::YELLOW]
+                );
+
+                graphics.draw_circle_two_color(
+                    (225.0, 75.0),
+                    65.0,
+                    Color::WHITE,
+                    Color::MAGENTA
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 500,
+        height: 500,
+        name: "polygon_outline".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+                let poly = Polygon::new(&[
+                    (100.0, 400.0),
+                    (300.0, 400.0),
+                    (400.0, 100.0),
+                    (250.0, 50.0),
+                    (250.0, 350.0)
+                ]);
+                graphics.draw_polygon(&poly, (0.0, 0.0), Color::RED);
+                graphics.draw_polygon_outline(
+                    &poly,
+                    (0.0, 0.0),
+                    10.0,
+                    Color::from_rgba(0.0, 0.0, 0.0, 0.5)
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 300,
+        height: 300,
+        name: "geometry_handle".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let star = Polygon::new_with_fill_rule(
+                &[
+                    (50.0, 0.0),
+                    (79.4, 90.5),
+                    (2.4, 34.5),
+                    (97.6, 34.5),
+                    (20.6, 90.5)
+                ],
+                FillRule::NonZero
+            );
+
+            let geometry = renderer.create_geometry(&star).unwrap();
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((0.0, 0.0), (150.0, 150.0)),
+                    Color::YELLOW
+                );
+
+                graphics.draw_geometry(&geometry, (10.0, 10.0), Color::RED);
+                graphics.draw_geometry(
+                    &geometry,
+                    (90.0, 40.0),
+                    Color::from_rgba(0.0, 0.0, 1.0, 0.5)
+                );
+
+                graphics.push_transform(
+                    Matrix2D::rotate(std::f32::consts::FRAC_PI_4)
+                        .then(&Matrix2D::translate((200.0, 150.0)))
+                );
+                graphics.draw_geometry(&geometry, (-50.0, -50.0), Color::GREEN);
+                graphics.pop_transform();
+
+                graphics.draw_circle(((250.0, 250.0), 30.0), Color::MAGENTA);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 300,
+        height: 150,
+        name: "triangle_mesh".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let image = renderer
+                .create_image_from_raw_pixels(
+                    ImageDataType::RGB,
+                    ImageSmoothingMode::NearestNeighbor,
+                    Vector2::new(2, 2),
+                    &[255, 0, 0, 0, 255, 0, 0, 0, 255, 255, 255, 0]
+                )
+                .unwrap();
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                // A 3x3 grid of vertices, with the center vertex raised
+                let mut positions = Vec::new();
+                let mut colors = Vec::new();
+
+                for y in 0..3 {
+                    for x in 0..3 {
+                        let raised = if x == 1 && y == 1 { -30.0 } else { 0.0 };
+                        positions.push(Vec2::new(
+                            10.0 + x as f32 * 60.0,
+                            10.0 + y as f32 * 60.0 + raised
+                        ));
+                        colors.push(Color::from_rgb(x as f32 / 2.0, y as f32 / 2.0, 0.5));
+                    }
+                }
+
+                let mut indices = Vec::new();
+
+                for y in 0..2 {
+                    for x in 0..2 {
+                        let top_left = y * 3 + x;
+                        indices.extend_from_slice(&[
+                            top_left,
+                            top_left + 1,
+                            top_left + 4,
+                            top_left,
+                            top_left + 4,
+                            top_left + 3
+                        ]);
+                    }
+                }
+
+                graphics.draw_triangles(&positions, &colors, &indices);
+
+                graphics.draw_triangles_image_tinted(
+                    &[
+                        Vec2::new(160.0, 10.0),
+                        Vec2::new(290.0, 10.0),
+                        Vec2::new(290.0, 140.0),
+                        Vec2::new(160.0, 140.0)
+                    ],
+                    &[Color::WHITE, Color::WHITE, Color::GRAY, Color::WHITE],
+                    &[
+                        Vec2::new(0.0, 0.0),
+                        Vec2::new(1.0, 0.0),
+                        Vec2::new(1.0, 1.0),
+                        Vec2::new(0.0, 1.0)
+                    ],
+                    &[0, 1, 2, 0, 2, 3],
+                    &image
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 300,
+        height: 300,
+        name: "image_instances".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let image = renderer
+                .create_image_from_raw_pixels(
+                    ImageDataType::RGB,
+                    ImageSmoothingMode::NearestNeighbor,
+                    Vector2::new(2, 2),
+                    &[255, 255, 255, 0, 0, 0, 0, 0, 0, 255, 255, 255]
+                )
+                .unwrap();
+
+            let instances: Vec<ImageInstance> = (0..25)
+                .map(|i| {
+                    let x = (i % 5) as f32;
+                    let y = (i / 5) as f32;
+
+                    ImageInstance::new((30.0 + x * 60.0, 30.0 + y * 60.0))
+                        .with_scale(10.0 + x * 5.0)
+                        .with_rotation(y * 0.3)
+                        .with_tint(Color::from_rgba(1.0, x / 4.0, y / 4.0, 0.8))
+                })
+                .collect();
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+                graphics.draw_image_instances(&image, &instances);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 300,
+        height: 150,
+        name: "sprite_batch".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            // A 4x1 sprite sheet containing red, green, blue, and yellow sprites
+            let sheet = renderer
+                .create_image_from_raw_pixels(
+                    ImageDataType::RGB,
+                    ImageSmoothingMode::NearestNeighbor,
+                    Vector2::new(4, 1),
+                    &[255, 0, 0, 0, 255, 0, 0, 0, 255, 255, 255, 0]
+                )
+                .unwrap();
+
+            let checkerboard = renderer
+                .create_image_from_raw_pixels(
+                    ImageDataType::RGB,
+                    ImageSmoothingMode::NearestNeighbor,
+                    Vector2::new(2, 2),
+                    &[0, 0, 0, 255, 255, 255, 255, 255, 255, 0, 0, 0]
+                )
+                .unwrap();
+
+            let mut batch = SpriteBatch::new();
+
+            for i in 0..4 {
+                let frame = i as f32;
+
+                batch.push(
+                    &sheet,
+                    ImageInstance::new((40.0 + frame * 70.0, 40.0))
+                        .with_source_rect(Rectangle::from_tuples(
+                            (frame, 0.0),
+                            (frame + 1.0, 1.0)
+                        ))
+                        .with_scale(50.0)
+                        .with_rotation(frame * 0.2)
+                );
+
+                batch.push(
+                    &checkerboard,
+                    ImageInstance::new((40.0 + frame * 70.0, 110.0))
+                        .with_scale(20.0)
+                        .with_tint(Color::from_rgba(1.0, 1.0, 1.0, 0.25 + frame * 0.25))
+                );
+            }
+
+            assert_eq!(8, batch.len());
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+                graphics.draw_sprite_batch(&batch);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 300,
+        height: 200,
+        name: "image_nine_slice".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            // A 30x30 image with red corners, green edges, and a blue center,
+            // with borders ten pixels wide
+            let mut pixels = Vec::new();
+
+            for y in 0..30 {
+                for x in 0..30 {
+                    let edge_count =
+                        [x, y].iter().filter(|v| **v < 10 || **v >= 20).count();
+
+                    pixels.extend_from_slice(match edge_count {
+                        2 => &[255, 0, 0],
+                        1 => &[0, 255, 0],
+                        _ => &[0, 0, 255]
+                    });
+                }
+            }
+
+            let image = renderer
+                .create_image_from_raw_pixels(
+                    ImageDataType::RGB,
+                    ImageSmoothingMode::NearestNeighbor,
+                    Vector2::new(30, 30),
+                    &pixels
+                )
+                .unwrap();
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.draw_image_nine_slice(
+                    Rectangle::from_tuples((10.0, 10.0), (190.0, 90.0)),
+                    Insets::uniform(10.0),
+                    &image
+                );
+
+                graphics.draw_image_nine_slice_tinted(
+                    Rectangle::from_tuples((10.0, 110.0), (190.0, 190.0)),
+                    Insets::new(10.0, 5.0, 10.0, 15.0),
+                    Color::GRAY,
+                    &image
+                );
+
+                graphics.draw_image_nine_slice(
+                    Rectangle::from_tuples((210.0, 10.0), (290.0, 190.0)),
+                    Insets::uniform(10.0),
+                    &image
+                );
+
+                // Too small to fit the borders, so they are scaled down
+                graphics.draw_image_nine_slice(
+                    Rectangle::from_tuples((220.0, 20.0), (230.0, 30.0)),
+                    Insets::uniform(10.0),
+                    &image
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 250,
+        height: 100,
+        name: "image_tinted".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let mut pixels = Vec::new();
+
+            for y in 0..40 {
+                for x in 0..40 {
+                    let light = (x / 10 + y / 10) % 2 == 0;
+                    pixels.extend_from_slice(
+                        if light { &[255, 255, 255] } else { &[0, 0, 0] }
+                    );
+                }
+            }
+
+            let image = renderer
+                .create_image_from_raw_pixels(
+                    ImageDataType::RGB,
+                    ImageSmoothingMode::NearestNeighbor,
+                    Vector2::new(40, 40),
+                    &pixels
+                )
+                .unwrap();
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::from_rgb(0.8, 0.9, 1.0));
+
+                for i in 0..5 {
+                    graphics.draw_image_tinted(
+                        (10.0 + i as f32 * 48.0, 5.0),
+                        Color::from_rgba(1.0, 1.0, 1.0, 1.0 - i as f32 * 0.2),
+                        &image
+                    );
+                }
+
+                graphics.draw_image_tinted((10.0, 55.0), Color::RED, &image);
+                graphics.draw_image_tinted((58.0, 55.0), Color::GREEN, &image);
+                graphics.draw_image_tinted((106.0, 55.0), Color::BLUE, &image);
+                graphics.draw_image((154.0, 55.0), &image);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 300,
+        height: 100,
+        name: "image_atlas".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let mut builder = ImageAtlasBuilder::new((64, 64));
+
+            let checkerboard = builder
+                .add_image_from_raw_pixels(
+                    ImageDataType::RGB,
+                    (2, 2),
+                    &[255, 0, 0, 0, 0, 255, 0, 0, 255, 255, 0, 0]
+                )
+                .unwrap();
+
+            let stripes = builder
+                .add_image_from_raw_pixels(
+                    ImageDataType::RGBA,
+                    (3, 1),
+                    &[0, 128, 0, 255, 0, 0, 0, 0, 0, 128, 0, 255]
+                )
+                .unwrap();
+
+            let atlas = renderer
+                .create_image_atlas(&builder, ImageSmoothingMode::NearestNeighbor)
+                .unwrap();
+
+            let checkerboard = atlas.get(checkerboard).unwrap();
+            let stripes = atlas.get(stripes).unwrap();
+
+            assert_eq!(&Vector2::new(3, 1), stripes.size());
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.draw_rectangle_image(
+                    Rectangle::from_tuples((10.0, 10.0), (90.0, 90.0)),
+                    checkerboard
+                );
+
+                graphics.draw_image_rotated(
+                    (150.0, 50.0),
+                    stripes,
+                    std::f32::consts::FRAC_PI_4,
+                    20.0
+                );
+
+                graphics.draw_image_instances(
+                    checkerboard,
+                    &[
+                        ImageInstance::new((230.0, 30.0)).with_scale(20.0),
+                        ImageInstance::new((270.0, 70.0))
+                            .with_scale(20.0)
+                            .with_source_rect(Rectangle::from_tuples(
+                                (0.0, 0.0),
+                                (1.0, 2.0)
+                            ))
+                    ]
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 200,
+        height: 100,
+        name: "image_trilinear".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let mut pixels = Vec::new();
+
+            for y in 0..128 {
+                for x in 0..128 {
+                    let light = (x + y) % 2 == 0;
+                    pixels.extend_from_slice(
+                        if light { &[255, 255, 255] } else { &[0, 0, 0] }
+                    );
+                }
+            }
+
+            let create_image = |renderer: &mut GLRenderer, smoothing_mode| {
+                renderer
+                    .create_image_from_raw_pixels(
+                        ImageDataType::RGB,
+                        smoothing_mode,
+                        Vector2::new(128, 128),
+                        &pixels
+                    )
+                    .unwrap()
+            };
+
+            let linear = create_image(renderer, ImageSmoothingMode::Linear);
+            let trilinear = create_image(renderer, ImageSmoothingMode::Trilinear);
+
+            let target = renderer
+                .create_render_target((128, 128), ImageSmoothingMode::Trilinear)
+                .unwrap();
+
+            renderer.draw_frame(|graphics| {
+                graphics.draw_to_target(&target, |graphics| {
+                    graphics.draw_image((0.0, 0.0), &trilinear);
+                    graphics.draw_rectangle(
+                        Rectangle::from_tuples((0.0, 0.0), (64.0, 128.0)),
+                        Color::RED
+                    );
+                });
+
+                graphics.clear_screen(Color::WHITE);
+
+                for (i, image) in [&linear, &trilinear, target.image()].iter().enumerate()
+                {
+                    graphics.draw_rectangle_image(
+                        Rectangle::from_tuples(
+                            (10.0 + i as f32 * 60.0, 10.0),
+                            (47.0 + i as f32 * 60.0, 47.0)
+                        ),
+                        image
+                    );
+                }
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 300,
+        height: 100,
+        name: "image_wrap_and_tile".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            // A 20x20 image with a red top left quarter, and blue elsewhere
+            let mut pixels = Vec::new();
+
+            for y in 0..20 {
+                for x in 0..20 {
+                    pixels.extend_from_slice(
+                        if x < 10 && y < 10 {
+                            &[255, 0, 0]
+                        } else {
+                            &[0, 0, 255]
+                        }
+                    );
+                }
+            }
+
+            let create_image = |renderer: &mut GLRenderer| {
+                renderer
+                    .create_image_from_raw_pixels(
+                        ImageDataType::RGB,
+                        ImageSmoothingMode::NearestNeighbor,
+                        Vector2::new(20, 20),
+                        &pixels
+                    )
+                    .unwrap()
+            };
+
+            let repeat = create_image(renderer);
+            let mirrored = create_image(renderer);
+
+            renderer.set_image_wrap_mode(&repeat, ImageWrapMode::Repeat);
+            renderer.set_image_wrap_mode(&mirrored, ImageWrapMode::MirroredRepeat);
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                let coords = Rectangle::from_tuples((0.0, 0.0), (4.0, 4.0));
+
+                graphics.draw_rectangle_image_subset_tinted(
+                    Rectangle::from_tuples((10.0, 10.0), (90.0, 90.0)),
+                    Color::WHITE,
+                    &coords,
+                    &repeat
+                );
+
+                graphics.draw_rectangle_image_subset_tinted(
+                    Rectangle::from_tuples((110.0, 10.0), (190.0, 90.0)),
+                    Color::WHITE,
+                    &coords,
+                    &mirrored
+                );
+
+                graphics.draw_image_tiled(
+                    Rectangle::from_tuples((210.0, 10.0), (285.0, 85.0)),
+                    &mirrored
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 200,
+        height: 150,
+        name: "capture_region".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let target = renderer
+                .create_render_target((50, 50), ImageSmoothingMode::NearestNeighbor)
+                .unwrap();
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((0.0, 0.0), (50.0, 25.0)),
+                    Color::RED
+                );
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((0.0, 25.0), (50.0, 50.0)),
+                    Color::BLUE
+                );
+
+                let capture = graphics.capture_region(
+                    ImageDataType::RGBA,
+                    Rectangle::from_tuples((10, 15), (40, 35))
+                );
+
+                assert_eq!(Vector2::new(30, 20), capture.size());
+                assert_eq!(&[255, 0, 0, 255], &capture.data()[..4]);
+                assert_eq!(
+                    &[0, 0, 255, 255],
+                    &capture.data()[capture.data().len() - 4..]
+                );
+
+                // Regions extending outside the window are cropped
+                let cropped = graphics.capture_region(
+                    ImageDataType::RGB,
+                    Rectangle::from_tuples((150, 100), (400, 400))
+                );
+
+                assert_eq!(Vector2::new(50, 50), cropped.size());
+
+                let window_capture = graphics
+                    .create_image_from_raw_pixels(
+                        capture.format(),
+                        ImageSmoothingMode::NearestNeighbor,
+                        capture.size(),
+                        capture.data().as_slice()
+                    )
+                    .unwrap();
+
+                let target_capture = graphics.draw_to_target(&target, |graphics| {
+                    graphics.clear_screen(Color::GREEN);
+                    graphics.draw_rectangle(
+                        Rectangle::from_tuples((0.0, 0.0), (25.0, 50.0)),
+                        Color::BLACK
+                    );
+
+                    graphics.capture_region(
+                        ImageDataType::RGBA,
+                        Rectangle::from_tuples((20, 0), (30, 40))
+                    )
+                });
+
+                assert_eq!(Vector2::new(10, 40), target_capture.size());
+
+                let target_capture = graphics
+                    .create_image_from_raw_pixels(
+                        target_capture.format(),
+                        ImageSmoothingMode::NearestNeighbor,
+                        target_capture.size(),
+                        target_capture.data().as_slice()
+                    )
+                    .unwrap();
+
+                graphics.draw_image((80.0, 20.0), &window_capture);
+                graphics.draw_image((130.0, 20.0), &target_capture);
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 200,
+        height: 150,
+        name: "capture_async".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let target = renderer
+                .create_render_target((50, 50), ImageSmoothingMode::NearestNeighbor)
+                .unwrap();
+
+            let (pending, pending_target, expected) = renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((0.0, 0.0), (50.0, 25.0)),
+                    Color::RED
+                );
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((0.0, 25.0), (50.0, 50.0)),
+                    Color::BLUE
+                );
+
+                let region = Rectangle::from_tuples((10, 15), (60, 35));
+
+                let pending =
+                    graphics.capture_region_async(ImageDataType::RGB, region.clone());
+                let expected = graphics.capture_region(ImageDataType::RGB, region);
+
+                let pending_target = graphics.draw_to_target(&target, |graphics| {
+                    graphics.clear_screen(Color::GREEN);
+                    graphics.draw_rectangle(
+                        Rectangle::from_tuples((0.0, 0.0), (25.0, 50.0)),
+                        Color::BLACK
+                    );
+                    graphics.capture_async(ImageDataType::RGBA)
+                });
+
+                (pending, pending_target, expected)
+            });
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                let capture = graphics.finish_capture(pending);
+
+                assert_eq!(Vector2::new(50, 20), capture.size());
+                assert_eq!(expected.data(), capture.data());
+
+                let target_capture = graphics.finish_capture(pending_target);
+
+                assert_eq!(Vector2::new(50, 50), target_capture.size());
+                assert_eq!(&[0, 0, 0, 255], &target_capture.data()[..4]);
+
+                let window_capture = graphics
+                    .create_image_from_raw_pixels(
+                        capture.format(),
+                        ImageSmoothingMode::NearestNeighbor,
+                        capture.size(),
+                        capture.data().as_slice()
+                    )
+                    .unwrap();
+
+                let target_capture = graphics
+                    .create_image_from_raw_pixels(
+                        target_capture.format(),
+                        ImageSmoothingMode::NearestNeighbor,
+                        target_capture.size(),
+                        target_capture.data().as_slice()
+                    )
+                    .unwrap();
+
+                graphics.draw_image((20.0, 20.0), &window_capture);
+                graphics.draw_image((100.0, 20.0), &target_capture);
+            });
+        })
+    });
+
+    #[cfg(feature = "image-loading")]
+    tests.push(GLTest {
+        width: 100,
+        height: 50,
+        name: "animated_image".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let mut gif_bytes = Vec::new();
+
+            {
+                let frame = |color: [u8; 4]| {
+                    image::Frame::from_parts(
+                        image::RgbaImage::from_pixel(20, 20, image::Rgba(color)),
+                        0,
+                        0,
+                        image::Delay::from_numer_denom_ms(100, 1)
+                    )
+                };
+
+                let mut encoder = image::codecs::gif::GifEncoder::new(&mut gif_bytes);
+
+                encoder
+                    .encode_frames(vec![frame([255, 0, 0, 255]), frame([0, 0, 255, 255])])
+                    .unwrap();
+            }
+
+            let mut animation = renderer
+                .create_animated_image_from_file_bytes(
+                    None,
+                    ImageSmoothingMode::NearestNeighbor,
+                    std::io::Cursor::new(gif_bytes)
+                )
+                .unwrap();
+
+            assert_eq!(2, animation.frame_count());
+            assert_eq!(Vector2::new(20, 20), animation.size());
+            assert_eq!(Duration::from_millis(200), animation.duration());
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                let first = graphics
+                    .animated_image_frame(&mut animation, Duration::from_millis(50))
+                    .unwrap();
+                let second = graphics
+                    .animated_image_frame(&mut animation, Duration::from_millis(150))
+                    .unwrap();
+
+                // Frames are only uploaded once
+                assert_eq!(
+                    first,
+                    graphics
+                        .animated_image_frame(&mut animation, Duration::from_millis(250))
+                        .unwrap()
+                );
+
+                graphics.draw_image((10.0, 15.0), &first);
+                graphics.draw_image((60.0, 15.0), &second);
+            });
+        })
+    });
+
+    #[cfg(feature = "svg-loading")]
+    tests.push(GLTest {
+        width: 100,
+        height: 50,
+        name: "svg_image".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let svg =
+                br##"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+                <circle cx="5" cy="5" r="4" fill="#ff0000" fill-opacity="0.5"/>
+                <rect x="0" y="0" width="5" height="5" fill="#0000ff"/>
+            </svg>"##;
+
+            let small = renderer
+                .create_image_from_svg_bytes(svg, (20, 20), ImageSmoothingMode::Linear)
+                .unwrap();
+
+            let large = renderer
+                .create_image_from_svg_bytes(svg, (40, 40), ImageSmoothingMode::Linear)
+                .unwrap();
+
+            assert_eq!(Vector2::new(40, 40), *large.size());
+
+            assert!(renderer
+                .create_image_from_svg_bytes(
+                    b"not an svg",
+                    (10, 10),
+                    ImageSmoothingMode::Linear
+                )
+                .is_err());
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+                graphics.draw_image((10.0, 15.0), &small);
+                graphics.draw_image((50.0, 5.0), &large);
+            });
+        })
+    });
+
+    #[cfg(feature = "image-loading")]
+    tests.push(GLTest {
+        width: 250,
+        height: 250,
+        name: "image_async_load".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let pending = renderer.create_image_from_file_path_async(
+                None,
+                ImageSmoothingMode::NearestNeighbor,
+                "test/assets/test_images/smiley_colormap.png"
+            );
+
+            let missing = renderer.create_image_from_file_path_async(
+                None,
+                ImageSmoothingMode::NearestNeighbor,
+                "test/assets/test_images/does_not_exist.png"
+            );
+
+            // Nothing is uploaded until the next frame
+            assert!(pending.is_loading());
+
+            for _ in 0..500 {
+                if !pending.is_loading() && !missing.is_loading() {
+                    break;
+                }
+
+                std::thread::sleep(Duration::from_millis(10));
+
+                renderer.draw_frame(|graphics| {
+                    graphics.clear_screen(Color::WHITE);
+                });
+            }
+
+            assert!(missing.poll().unwrap().is_err());
+
+            let image = pending.poll().unwrap().unwrap();
+
+            assert_eq!(Vector2::new(225, 224), *image.size());
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+                graphics.draw_image((12.0, 13.0), &image);
+            });
+        })
+    });
+
+    #[cfg(feature = "image-loading")]
+    tests.push(GLTest {
+        width: 100,
+        height: 50,
+        name: "capture_to_file".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            let path = std::env::temp_dir().join("speedy2d_test_capture_to_file.png");
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((0.0, 0.0), (50.0, 25.0)),
+                    Color::RED
+                );
+                graphics.draw_circle(((75.0, 25.0), 20.0), Color::BLUE);
+
+                graphics
+                    .capture_to_file(&path, speedy2d::image::ImageFileFormat::PNG)
+                    .unwrap();
+            });
+
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::BLACK);
+
+                let image = graphics
+                    .create_image_from_file_path(
+                        None,
+                        ImageSmoothingMode::NearestNeighbor,
+                        &path
+                    )
+                    .unwrap();
+
+                graphics.draw_image((0.0, 0.0), &image);
+            });
+
+            std::fs::remove_file(&path).unwrap();
+        })
+    });
+
+    tests.push(GLTest {
+        width: 200,
+        height: 100,
+        name: "blend_modes".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                let modes = [
+                    BlendMode::Alpha,
+                    BlendMode::Additive,
+                    BlendMode::Multiply,
+                    BlendMode::Screen
+                ];
+
+                for (i, mode) in modes.iter().enumerate() {
+                    let x = i as f32 * 50.0;
+
+                    graphics.set_blend_mode(BlendMode::Alpha);
+
+                    graphics.draw_rectangle(
+                        Rectangle::from_tuples((x, 0.0), (x + 50.0, 50.0)),
+                        Color::from_rgb(0.5, 0.25, 0.0)
+                    );
+                    graphics.draw_rectangle(
+                        Rectangle::from_tuples((x, 50.0), (x + 50.0, 100.0)),
+                        Color::from_rgb(0.0, 0.25, 0.5)
+                    );
+
+                    graphics.set_blend_mode(*mode);
+                    assert_eq!(*mode, graphics.blend_mode());
+
+                    graphics.draw_circle(
+                        ((x + 25.0, 25.0), 20.0),
+                        Color::from_rgb(0.0, 0.5, 1.0)
+                    );
+                    graphics.draw_circle(
+                        ((x + 25.0, 75.0), 20.0),
+                        Color::from_rgba(1.0, 0.5, 0.0, 0.5)
+                    );
+                }
+            });
+
+            // The blend mode is reset at the start of each frame
+            renderer.draw_frame(|graphics| {
+                assert_eq!(BlendMode::Alpha, graphics.blend_mode());
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 200,
+        height: 100,
+        name: "clip_shapes".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                let shapes = [
+                    ClipShape::from(Rectangle::from_tuples((5.0, 5.0), (45.0, 45.0))),
+                    ClipShape::from(
+                        Rectangle::from_tuples((55.0, 5.0), (95.0, 45.0)).rounded(12.0)
+                    ),
+                    ClipShape::Circle {
+                        center: Vec2::new(125.0, 25.0),
+                        radius: 20.0
+                    },
+                    ClipShape::from(Polygon::new(&[
+                        (175.0, 5.0),
+                        (195.0, 45.0),
+                        (155.0, 45.0)
+                    ]))
+                ];
+
+                for shape in shapes {
+                    graphics.set_clip_shape(Some(shape));
+                    graphics.draw_rectangle(
+                        Rectangle::from_tuples((0.0, 0.0), (200.0, 100.0)),
+                        Color::BLUE
+                    );
+                }
+
+                // The clip shape is affected by the current transform
+                graphics.push_transform(
+                    Matrix2D::translate((-50.0, -75.0))
+                        .then(&Matrix2D::rotate(0.5))
+                        .then(&Matrix2D::translate((50.0, 75.0)))
+                );
+                graphics.set_clip_shape(Some(ClipShape::from(Rectangle::from_tuples(
+                    (30.0, 60.0),
+                    (70.0, 90.0)
+                ))));
+                graphics.pop_transform();
+
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((0.0, 50.0), (200.0, 100.0)),
+                    Color::RED
+                );
+
+                // Setting a rectangle clip replaces the clip shape
+                graphics.set_clip(Some(Rectangle::from_tuples((120, 60), (180, 90))));
+
+                graphics.draw_rectangle(
+                    Rectangle::from_tuples((0.0, 50.0), (200.0, 100.0)),
+                    Color::GREEN
+                );
+            });
+        })
+    });
+
+    tests.push(GLTest {
+        width: 200,
+        height: 100,
+        name: "transformed_clip".to_string(),
+        options: GLRendererOptions::default(),
+        action: Box::new(|renderer| {
+            renderer.draw_frame(|graphics| {
+                graphics.clear_screen(Color::WHITE);
+
+                // Scaled and translated, so the clip can use the scissor test
+                graphics.push_transform(
+                    Matrix2D::scale((2.0, 2.0)).then(&Matrix2D::translate((10.0, 10.0)))
+                );
+                graphics.set_transformed_clip(Some(Rectangle::from_tuples(
+                    (0.0, 0.0),
+                    (30.0, 20.0)
+                )));
+                graphics.draw_circle(((15.0, 20.0), 20.0), Color::BLUE);
+                graphics.pop_transform();
+
+                // Rotated, so the clip must use the stencil buffer
+                graphics.push_transform(
+                    Matrix2D::rotate(0.4).then(&Matrix2D::translate((140.0, 20.0)))
+                );
+                graphics.set_transformed_clip(Some(Rectangle::from_tuples(
+                    (0.0, 0.0),
+                    (40.0, 40.0)
+                )));
+                graphics.draw_circle(((20.0, 40.0), 30.0), Color::RED);
+                graphics.pop_transform();
+
+                graphics.set_transformed_clip(None);
+                graphics.draw_line((0.0, 95.0), (200.0, 95.0), 2.0, Color::BLACK);
+            });
+        })
+    });
+
+    for test in tests {
+        if event_loop.is_none() && test.options != GLRendererOptions::default() {
+            log::info!(
+                "Skipping test {}: options not supported by the software renderer",
+                test.name
+            );
+            continue;
+        }
+
         log::info!("Running test {}", test.name);
 
         run_test_with_new_context(
-            &event_loop,
+            event_loop.as_ref(),
             test.name,
             test.width,
             test.height,
+            test.options,
             test.action
         );
     }